@@ -0,0 +1,60 @@
+use crate::store::{StorePaths, load_trusted_devices, save_trusted_devices};
+use crate::types::{PairedDevice, PairedDeviceAction, TrustedDevice};
+
+/// Devices that paired with `guid`, most recently seen first; never-seen devices go last.
+pub fn list_paired_devices(
+    paths: &StorePaths,
+    guid: &str,
+) -> Result<Vec<PairedDevice>, &'static str> {
+    let file = load_trusted_devices(paths).map_err(|_| "Error")?;
+    Ok(paired_devices(&file.trusted_devices, guid))
+}
+
+/// Revokes, restores or forgets one of `guid`'s devices and returns its devices afterwards.
+/// Devices paired with another ccbox are reported as not found.
+pub fn update_paired_device(
+    paths: &StorePaths,
+    guid: &str,
+    device_id: &str,
+    action: PairedDeviceAction,
+) -> Result<Vec<PairedDevice>, &'static str> {
+    let mut file = load_trusted_devices(paths).map_err(|_| "Error")?;
+    let index = file
+        .trusted_devices
+        .iter()
+        .position(|device| device.device_id == device_id && is_paired_with(device, guid))
+        .ok_or("DeviceNotFound")?;
+    match action {
+        PairedDeviceAction::Revoke => file.trusted_devices[index].revoked = true,
+        PairedDeviceAction::Restore => file.trusted_devices[index].revoked = false,
+        PairedDeviceAction::Forget => {
+            file.trusted_devices.remove(index);
+        }
+    }
+    save_trusted_devices(paths, &file).map_err(|_| "Error")?;
+    Ok(paired_devices(&file.trusted_devices, guid))
+}
+
+fn is_paired_with(device: &TrustedDevice, guid: &str) -> bool {
+    device
+        .paired_guid
+        .as_deref()
+        .is_some_and(|paired| paired.eq_ignore_ascii_case(guid))
+}
+
+fn paired_devices(devices: &[TrustedDevice], guid: &str) -> Vec<PairedDevice> {
+    let mut paired = devices
+        .iter()
+        .filter(|device| is_paired_with(device, guid))
+        .map(|device| PairedDevice {
+            device_id: device.device_id.clone(),
+            label: device.label.clone(),
+            created_at: device.created_at.clone(),
+            last_seen_at: device.last_seen_at.clone(),
+            revoked: device.revoked,
+        })
+        .collect::<Vec<_>>();
+    // RFC 3339 timestamps from `now_iso` are all UTC, so they sort as strings.
+    paired.sort_by(|a, b| b.last_seen_at.cmp(&a.last_seen_at));
+    paired
+}
@@ -1,5 +1,8 @@
+pub mod devices;
+pub mod metadata;
 pub mod pairing;
 pub mod server;
 pub mod store;
 pub mod types;
 pub mod util;
+pub mod viewer;
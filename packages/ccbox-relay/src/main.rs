@@ -115,6 +115,7 @@ fn run_main() -> Result<(), String> {
                 last_seen_at: None,
                 revoked: false,
                 label,
+                paired_guid: None,
             });
             save_trusted_devices(&paths, &file).map_err(|e| e.to_string())?;
             println!("added trusted device {device_id}");
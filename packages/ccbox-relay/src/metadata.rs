@@ -0,0 +1,132 @@
+use crate::store::{StorePaths, load_metadata, save_metadata};
+use crate::types::MetadataEntry;
+use std::collections::HashMap;
+use std::io;
+
+/// Entries kept per sync group; larger uploads are rejected rather than truncated.
+pub const MAX_METADATA_ENTRIES: usize = 20_000;
+const MAX_METADATA_KEY_BYTES: usize = 1024;
+const MAX_METADATA_VALUE_BYTES: usize = 64 * 1024;
+
+/// Returns true if `candidate` should replace `current` under last-writer-wins.
+pub fn is_newer_entry(candidate: &MetadataEntry, current: &MetadataEntry) -> bool {
+    (candidate.updated_at_ms, candidate.machine.as_str())
+        > (current.updated_at_ms, current.machine.as_str())
+}
+
+/// Merges `incoming` into `stored`, keeping the newest entry per key. Output is sorted by key.
+pub fn merge_metadata_entries(
+    stored: Vec<MetadataEntry>,
+    incoming: Vec<MetadataEntry>,
+) -> Vec<MetadataEntry> {
+    let mut by_key: HashMap<String, MetadataEntry> = HashMap::new();
+    for entry in stored.into_iter().chain(incoming) {
+        match by_key.get(&entry.key) {
+            Some(current) if !is_newer_entry(&entry, current) => {}
+            _ => {
+                by_key.insert(entry.key.clone(), entry);
+            }
+        }
+    }
+    let mut merged = by_key.into_values().collect::<Vec<_>>();
+    merged.sort_by(|a, b| a.key.cmp(&b.key));
+    merged
+}
+
+fn is_valid_entry(entry: &MetadataEntry) -> bool {
+    !entry.key.is_empty()
+        && entry.key.len() <= MAX_METADATA_KEY_BYTES
+        && entry
+            .value
+            .as_ref()
+            .is_none_or(|value| value.len() <= MAX_METADATA_VALUE_BYTES)
+}
+
+/// Merges an upload into the group's stored document and returns the merged entries.
+pub fn sync_metadata_group(
+    paths: &StorePaths,
+    group: &str,
+    incoming: Vec<MetadataEntry>,
+) -> Result<Vec<MetadataEntry>, &'static str> {
+    if incoming.len() > MAX_METADATA_ENTRIES || !incoming.iter().all(is_valid_entry) {
+        return Err("InvalidParams");
+    }
+    let mut file = load_metadata(paths, group).map_err(io_code)?;
+    let merged = merge_metadata_entries(std::mem::take(&mut file.entries), incoming);
+    if merged.len() > MAX_METADATA_ENTRIES {
+        return Err("TooManyEntries");
+    }
+    file.entries = merged;
+    save_metadata(paths, group, &file).map_err(io_code)?;
+    Ok(file.entries)
+}
+
+fn io_code(error: io::Error) -> &'static str {
+    if error.kind() == io::ErrorKind::InvalidInput {
+        "InvalidParams"
+    } else {
+        "Error"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::make_store_paths;
+    use tempfile::tempdir;
+
+    fn entry(key: &str, value: Option<&str>, updated_at_ms: i64, machine: &str) -> MetadataEntry {
+        MetadataEntry {
+            key: key.to_string(),
+            value: value.map(str::to_string),
+            updated_at_ms,
+            machine: machine.to_string(),
+        }
+    }
+
+    #[test]
+    fn newest_entry_wins_and_group_is_persisted() {
+        let dir = tempdir().expect("tempdir");
+        let paths = make_store_paths(dir.path());
+        let group = "0d7c56a2-3b1b-4b7e-9a55-2f1c8c0f4a10";
+
+        let first = sync_metadata_group(
+            &paths,
+            group,
+            vec![
+                entry("alias:codex:a", Some("laptop name"), 200, "laptop"),
+                entry("alias:codex:b", Some("kept"), 100, "laptop"),
+            ],
+        )
+        .expect("first sync");
+        assert_eq!(first.len(), 2);
+
+        let merged = sync_metadata_group(
+            &paths,
+            group,
+            vec![
+                entry("alias:codex:a", Some("older desktop name"), 150, "desktop"),
+                entry("alias:codex:b", None, 300, "desktop"),
+            ],
+        )
+        .expect("second sync");
+        assert_eq!(
+            merged,
+            vec![
+                entry("alias:codex:a", Some("laptop name"), 200, "laptop"),
+                entry("alias:codex:b", None, 300, "desktop"),
+            ]
+        );
+
+        let tie = merge_metadata_entries(
+            vec![entry("k", Some("a"), 5, "aaa")],
+            vec![entry("k", Some("b"), 5, "bbb")],
+        );
+        assert_eq!(tie[0].value.as_deref(), Some("b"));
+
+        assert_eq!(
+            sync_metadata_group(&paths, "../escape", Vec::new()),
+            Err("InvalidParams")
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use crate::devices::{list_paired_devices, update_paired_device};
+use crate::metadata::sync_metadata_group;
 use crate::pairing::ensure_pairing_record;
 use crate::store::{
     StorePaths, delete_pairing, load_ccboxes, load_pairing, load_trusted_devices, save_ccboxes,
@@ -5,16 +7,19 @@ use crate::store::{
 };
 use crate::types::{
     AuthChallengePayload, AuthErrPayload, AuthHelloPayload, AuthOkPayload, AuthResponsePayload,
-    CcboxDevice, CcboxPairingCreatePayload, CcboxPairingErrPayload, CcboxPairingOkPayload,
-    CcboxRegisterPayload, EnvelopeIn, EnvelopeOut, MuxFramePayload, MuxFramePayloadOut,
-    PairingRecord, TrustedDevice,
+    CcboxDevice, CcboxDevicesErrPayload, CcboxDevicesOkPayload, CcboxDevicesUpdatePayload,
+    CcboxMetadataErrPayload, CcboxMetadataOkPayload, CcboxMetadataSyncPayload,
+    CcboxPairingCreatePayload, CcboxPairingErrPayload, CcboxPairingOkPayload, CcboxRegisterPayload,
+    EnvelopeIn, EnvelopeOut, MuxFramePayload, MuxFramePayloadOut, PairingRecord,
+    TranscriptPublishPayload, TrustedDevice,
 };
 use crate::util::{
-    CONTROL_V1_STREAM_ID, REMOTE_PROTOCOL_VERSION, build_auth_message, is_allowed_client_origin,
-    now_iso, random_nonce32, resolve_guid,
+    CONTROL_V1_STREAM_ID, REMOTE_PROTOCOL_VERSION, build_auth_message, build_view_message,
+    is_allowed_client_origin, now_iso, random_nonce32, resolve_guid,
 };
+use crate::viewer::render_transcript_html;
 use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
-use axum::extract::{ConnectInfo, Query, State};
+use axum::extract::{ConnectInfo, Path, Query, State};
 use axum::http::{HeaderMap, HeaderValue, StatusCode, header};
 use axum::response::{IntoResponse, Response};
 use axum::routing::{get, post};
@@ -44,6 +49,22 @@ pub struct AppState {
 pub struct RelayState {
     ccboxes_by_guid: RwLock<HashMap<String, CcboxConn>>,
     clients_by_session_id: RwLock<HashMap<String, ClientConn>>,
+    /// Transcripts published by each ccbox, oldest first; kept in memory only.
+    transcripts_by_guid: RwLock<HashMap<String, Vec<SharedTranscript>>>,
+    /// Serializes read-merge-write of metadata sync groups.
+    metadata_lock: Mutex<()>,
+}
+
+/// Transcripts kept per ccbox; publishing more drops the oldest.
+const MAX_SHARED_TRANSCRIPTS_PER_CCBOX: usize = 20;
+const MAX_TRANSCRIPT_ITEMS: usize = 2000;
+/// Longest lifetime a signed view link may ask for.
+const MAX_VIEW_LINK_TTL_SECONDS: u64 = 7 * 24 * 60 * 60;
+
+#[derive(Clone)]
+struct SharedTranscript {
+    transcript: TranscriptPublishPayload,
+    published_at: String,
 }
 
 pub struct RateLimiter {
@@ -101,6 +122,8 @@ impl RelayState {
         Self {
             ccboxes_by_guid: RwLock::new(HashMap::new()),
             clients_by_session_id: RwLock::new(HashMap::new()),
+            transcripts_by_guid: RwLock::new(HashMap::new()),
+            metadata_lock: Mutex::new(()),
         }
     }
 }
@@ -154,6 +177,14 @@ pub struct GuidQuery {
     pub guid: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct ViewQuery {
+    pub guid: Option<String>,
+    pub device_id: String,
+    pub expires: u64,
+    pub sig: String,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct PairApproveRequest {
     pub pairing_code: String,
@@ -177,6 +208,7 @@ pub fn build_router(state: AppState) -> Router {
         .route("/pair", post(pair_approve).options(pair_options))
         .route("/ccbox", get(ws_ccbox))
         .route("/client", get(ws_client))
+        .route("/view/{share_id}", get(view_transcript))
         .with_state(state)
 }
 
@@ -302,6 +334,136 @@ async fn pair_approve(
     res
 }
 
+async fn view_transcript(
+    ConnectInfo(peer_addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Path(share_id): Path<String>,
+    Query(query): Query<ViewQuery>,
+    State(state): State<AppState>,
+) -> Response {
+    let ip = resolve_request_ip(&headers, peer_addr);
+    if !state
+        .rate_limiter
+        .check(format!("view:{ip}"), 60, 60_000)
+        .await
+    {
+        log_event(
+            "view.rate_limited",
+            serde_json::json!({ "ip": ip.to_string() }),
+        );
+        return (StatusCode::TOO_MANY_REQUESTS, "rate limited").into_response();
+    }
+
+    let host_header = headers.get("host").and_then(|h| h.to_str().ok());
+    let Some(guid) = resolve_guid(host_header, query.guid.as_deref()) else {
+        return (StatusCode::BAD_REQUEST, "invalid guid").into_response();
+    };
+
+    let shared = match authorize_view(&state, &guid, &share_id, &query).await {
+        Ok(shared) => shared,
+        Err(code) => {
+            log_event(
+                "view.err",
+                serde_json::json!({
+                    "ip": ip.to_string(),
+                    "guid": guid,
+                    "share_id": share_id,
+                    "device_id": query.device_id,
+                    "code": code,
+                }),
+            );
+            let status = if code == "NotFound" {
+                StatusCode::NOT_FOUND
+            } else {
+                StatusCode::FORBIDDEN
+            };
+            return (status, code).into_response();
+        }
+    };
+
+    log_event(
+        "view.ok",
+        serde_json::json!({
+            "ip": ip.to_string(),
+            "guid": guid,
+            "share_id": share_id,
+            "device_id": query.device_id,
+        }),
+    );
+    let html = render_transcript_html(&shared.transcript, &shared.published_at);
+    let mut res = (StatusCode::OK, html).into_response();
+    let res_headers = res.headers_mut();
+    res_headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("text/html; charset=utf-8"),
+    );
+    res_headers.insert(
+        header::CONTENT_SECURITY_POLICY,
+        HeaderValue::from_static("default-src 'none'; style-src 'unsafe-inline'"),
+    );
+    res_headers.insert(header::CACHE_CONTROL, HeaderValue::from_static("no-store"));
+    res_headers.insert(
+        header::REFERRER_POLICY,
+        HeaderValue::from_static("no-referrer"),
+    );
+    res_headers.insert("x-robots-tag", HeaderValue::from_static("noindex"));
+    res
+}
+
+/// Checks a signed view link against the trusted (non-revoked) client devices and returns
+/// the transcript it points at.
+async fn authorize_view(
+    state: &AppState,
+    guid: &str,
+    share_id: &str,
+    query: &ViewQuery,
+) -> Result<SharedTranscript, &'static str> {
+    let now_s = (now_ms() / 1000) as u64;
+    if query.expires < now_s {
+        return Err("LinkExpired");
+    }
+    if query.expires > now_s.saturating_add(MAX_VIEW_LINK_TTL_SECONDS) {
+        return Err("InvalidParams");
+    }
+    if Uuid::parse_str(&query.device_id).is_err() {
+        return Err("InvalidDeviceId");
+    }
+
+    let signature = base64::engine::general_purpose::STANDARD
+        .decode(query.sig.trim())
+        .ok()
+        .and_then(|bytes| Signature::from_slice(&bytes).ok())
+        .ok_or("BadSignature")?;
+    let message = build_view_message(guid, share_id, &query.device_id, query.expires);
+    verify_device_signature(
+        &state.store_paths,
+        ConnectionKind::Client,
+        &query.device_id,
+        None,
+        &message,
+        &signature,
+    )
+    .await
+    .map_err(|error| match error {
+        AuthError::Code(code) => code,
+        AuthError::Io(_) => "Error",
+    })?;
+
+    state
+        .relay
+        .transcripts_by_guid
+        .read()
+        .await
+        .get(guid)
+        .and_then(|shared| {
+            shared
+                .iter()
+                .find(|shared| shared.transcript.share_id == share_id)
+        })
+        .cloned()
+        .ok_or("NotFound")
+}
+
 async fn pair_options(headers: HeaderMap) -> Response {
     let host_header = headers.get("host").and_then(|h| h.to_str().ok());
     let origin_header = headers.get("origin").and_then(|h| h.to_str().ok());
@@ -364,6 +526,7 @@ fn pair_approve_blocking(
         last_seen_at: None,
         revoked: false,
         label: body.label,
+        paired_guid: Some(guid.to_string()),
     });
     save_trusted_devices(paths, &trusted).map_err(|e| e.to_string())?;
     delete_pairing(paths, guid).map_err(|e| e.to_string())?;
@@ -519,6 +682,7 @@ async fn handle_socket(
     });
 
     let mut session_id: Option<String> = None;
+    let mut client_device_id: Option<String> = None;
     let mut registered_ccbox = false;
 
     while let Some(msg) = ws_receiver.next().await {
@@ -738,6 +902,7 @@ async fn handle_socket(
                         if kind == ConnectionKind::Client {
                             let sid = Uuid::new_v4().to_string();
                             session_id = Some(sid.clone());
+                            client_device_id = Some(device_id.clone());
                             state
                                 .relay
                                 .clients_by_session_id
@@ -852,6 +1017,137 @@ async fn handle_socket(
                         continue;
                     }
 
+                    if env.type_ == "ccbox/metadata/sync" {
+                        let req: CcboxMetadataSyncPayload =
+                            match serde_json::from_value(env.payload) {
+                                Ok(value) => value,
+                                Err(_) => {
+                                    send_envelope(
+                                        &tx,
+                                        "ccbox/metadata/err",
+                                        CcboxMetadataErrPayload {
+                                            code: "InvalidParams".to_string(),
+                                        },
+                                    );
+                                    continue;
+                                }
+                            };
+                        let group = req.group.trim().to_lowercase();
+                        let uploaded = req.entries.len();
+                        let store_paths = state.store_paths.clone();
+                        let group_for_blocking = group.clone();
+                        let _guard = state.relay.metadata_lock.lock().await;
+                        let result = tokio::task::spawn_blocking(move || {
+                            sync_metadata_group(&store_paths, &group_for_blocking, req.entries)
+                        })
+                        .await
+                        .unwrap_or(Err("Error"));
+
+                        match result {
+                            Ok(entries) => {
+                                log_event(
+                                    "metadata.sync.ok",
+                                    serde_json::json!({
+                                        "ip": ip.to_string(),
+                                        "guid": guid,
+                                        "conn_id": conn_id.to_string(),
+                                        "uploaded": uploaded,
+                                        "entries": entries.len(),
+                                    }),
+                                );
+                                send_envelope(
+                                    &tx,
+                                    "ccbox/metadata/ok",
+                                    CcboxMetadataOkPayload { entries },
+                                );
+                            }
+                            Err(code) => {
+                                log_event(
+                                    "metadata.sync.err",
+                                    serde_json::json!({
+                                        "ip": ip.to_string(),
+                                        "guid": guid,
+                                        "conn_id": conn_id.to_string(),
+                                        "code": code,
+                                    }),
+                                );
+                                send_envelope(
+                                    &tx,
+                                    "ccbox/metadata/err",
+                                    CcboxMetadataErrPayload {
+                                        code: code.to_string(),
+                                    },
+                                );
+                            }
+                        }
+                        continue;
+                    }
+
+                    if env.type_ == "ccbox/devices/list" || env.type_ == "ccbox/devices/update" {
+                        let update = if env.type_ == "ccbox/devices/update" {
+                            match serde_json::from_value::<CcboxDevicesUpdatePayload>(env.payload) {
+                                Ok(value) => Some(value),
+                                Err(_) => {
+                                    send_envelope(
+                                        &tx,
+                                        "ccbox/devices/err",
+                                        CcboxDevicesErrPayload {
+                                            code: "InvalidParams".to_string(),
+                                        },
+                                    );
+                                    continue;
+                                }
+                            }
+                        } else {
+                            None
+                        };
+                        let store_paths = state.store_paths.clone();
+                        let guid_for_blocking = guid.clone();
+                        let action = update.as_ref().map(|update| update.action);
+                        let result = tokio::task::spawn_blocking(move || match update {
+                            Some(update) => update_paired_device(
+                                &store_paths,
+                                &guid_for_blocking,
+                                &update.device_id,
+                                update.action,
+                            ),
+                            None => list_paired_devices(&store_paths, &guid_for_blocking),
+                        })
+                        .await
+                        .unwrap_or(Err("Error"));
+
+                        match result {
+                            Ok(devices) => {
+                                if let Some(action) = action {
+                                    log_event(
+                                        "devices.update.ok",
+                                        serde_json::json!({
+                                            "ip": ip.to_string(),
+                                            "guid": guid,
+                                            "conn_id": conn_id.to_string(),
+                                            "action": action,
+                                        }),
+                                    );
+                                }
+                                send_envelope(
+                                    &tx,
+                                    "ccbox/devices/ok",
+                                    CcboxDevicesOkPayload { devices },
+                                );
+                            }
+                            Err(code) => {
+                                send_envelope(
+                                    &tx,
+                                    "ccbox/devices/err",
+                                    CcboxDevicesErrPayload {
+                                        code: code.to_string(),
+                                    },
+                                );
+                            }
+                        }
+                        continue;
+                    }
+
                     if env.type_ == "ccbox/register" {
                         let reg: CcboxRegisterPayload = match serde_json::from_value(env.payload) {
                             Ok(value) => value,
@@ -880,6 +1176,41 @@ async fn handle_socket(
                         continue;
                     }
 
+                    if env.type_ == "ccbox/transcript/publish" {
+                        let mut transcript: TranscriptPublishPayload =
+                            match serde_json::from_value(env.payload) {
+                                Ok(value) => value,
+                                Err(_) => continue,
+                            };
+                        if Uuid::parse_str(&transcript.share_id).is_err() {
+                            continue;
+                        }
+                        transcript.items.truncate(MAX_TRANSCRIPT_ITEMS);
+                        log_event(
+                            "transcript.publish",
+                            serde_json::json!({
+                                "ip": ip.to_string(),
+                                "guid": guid,
+                                "conn_id": conn_id.to_string(),
+                                "share_id": transcript.share_id,
+                                "items": transcript.items.len(),
+                            }),
+                        );
+                        let mut map = state.relay.transcripts_by_guid.write().await;
+                        let shared = map.entry(guid.clone()).or_default();
+                        shared
+                            .retain(|existing| existing.transcript.share_id != transcript.share_id);
+                        shared.push(SharedTranscript {
+                            transcript,
+                            published_at: now_iso(),
+                        });
+                        if shared.len() > MAX_SHARED_TRANSCRIPTS_PER_CCBOX {
+                            let excess = shared.len() - MAX_SHARED_TRANSCRIPTS_PER_CCBOX;
+                            shared.drain(..excess);
+                        }
+                        continue;
+                    }
+
                     if env.type_ == "mux/frame" {
                         let mux: MuxFramePayload = match serde_json::from_value(env.payload) {
                             Ok(value) => value,
@@ -992,6 +1323,7 @@ async fn handle_socket(
                     ts: now_iso(),
                     payload: MuxFramePayloadOut {
                         session_id,
+                        device_id: client_device_id.clone(),
                         stream_id: CONTROL_V1_STREAM_ID,
                         payload_b64: base64::engine::general_purpose::STANDARD.encode(inner_bytes),
                     },
@@ -1280,7 +1612,7 @@ pub async fn run_http_server_on(
 mod tests {
     use super::*;
     use crate::store::{load_pairing, make_store_paths, save_trusted_devices};
-    use crate::types::{TrustedDevice, TrustedDevicesFile};
+    use crate::types::{PairedDevice, TrustedDevice, TrustedDevicesFile};
     use ed25519_dalek::{Signer as _, SigningKey};
     use rand_core::{OsRng, RngCore as _};
     use serde_json::Value;
@@ -1395,6 +1727,7 @@ mod tests {
                 last_seen_at: None,
                 revoked: false,
                 label: None,
+                paired_guid: None,
             }],
         };
         save_trusted_devices(&store_paths, &trusted).expect("trusted devices saved");
@@ -1445,6 +1778,7 @@ mod tests {
             .expect("ccbox/register");
 
         let (mut ccbox_write, mut ccbox_read) = ccbox_ws.split();
+        let expected_device_id = client_device_id.clone();
         let ccbox_task = tokio::spawn(async move {
             while let Some(msg) = ccbox_read.next().await {
                 let msg = msg.expect("ccbox ws message");
@@ -1465,6 +1799,7 @@ mod tests {
                         if mux.stream_id != CONTROL_V1_STREAM_ID {
                             continue;
                         }
+                        assert_eq!(mux.device_id.as_deref(), Some(expected_device_id.as_str()));
 
                         let inner_bytes = base64::engine::general_purpose::STANDARD
                             .decode(mux.payload_b64)
@@ -1500,6 +1835,7 @@ mod tests {
                             ts: now_iso(),
                             payload: MuxFramePayloadOut {
                                 session_id: mux.session_id,
+                                device_id: None,
                                 stream_id: CONTROL_V1_STREAM_ID,
                                 payload_b64: base64::engine::general_purpose::STANDARD
                                     .encode(response_inner_text.as_bytes()),
@@ -1511,6 +1847,34 @@ mod tests {
                             .send(ClientMessage::Text(out_text.into()))
                             .await
                             .expect("send mux response");
+
+                        // Subscriptions stream events to the same client session.
+                        let event_inner = serde_json::json!({
+                            "v": REMOTE_PROTOCOL_VERSION,
+                            "type": "event",
+                            "ts": now_iso(),
+                            "payload": {
+                                "topic": "processes.status",
+                                "data": { "process_id": "p1", "status": "exited", "exit_code": 0 }
+                            }
+                        });
+                        let event_env = EnvelopeOut {
+                            v: REMOTE_PROTOCOL_VERSION,
+                            type_: "mux/frame",
+                            ts: now_iso(),
+                            payload: MuxFramePayloadOut {
+                                session_id: response_env.payload.session_id.clone(),
+                                device_id: None,
+                                stream_id: CONTROL_V1_STREAM_ID,
+                                payload_b64: base64::engine::general_purpose::STANDARD
+                                    .encode(event_inner.to_string().as_bytes()),
+                            },
+                        };
+                        let out_text = serde_json::to_string(&event_env).expect("mux event json");
+                        ccbox_write
+                            .send(ClientMessage::Text(out_text.into()))
+                            .await
+                            .expect("send mux event");
                         return;
                     }
                     _ => {}
@@ -1566,7 +1930,25 @@ mod tests {
         }
         assert!(got_response, "expected a rpc/response message");
 
-        let _ = ccbox_task.await;
+        let mut got_event = false;
+        while let Some(msg) = client_ws.next().await {
+            let msg = msg.expect("client ws message");
+            if let ClientMessage::Text(text) = msg {
+                let env: EnvelopeIn = serde_json::from_str(&text).expect("client env");
+                if env.type_ != "event" {
+                    continue;
+                }
+                assert_eq!(
+                    env.payload.get("topic").and_then(Value::as_str),
+                    Some("processes.status")
+                );
+                got_event = true;
+                break;
+            }
+        }
+        assert!(got_event, "expected a streamed event");
+
+        ccbox_task.await.expect("ccbox task");
         server_task.abort();
     }
 
@@ -1791,4 +2173,222 @@ mod tests {
 
         server_task.abort();
     }
+
+    /// Sends a ccbox envelope and returns the `(type, payload)` of the first reply among `types`.
+    async fn ccbox_request(ws: &mut WsStream, request: Value, types: &[&str]) -> (String, Value) {
+        ws.send(ClientMessage::Text(request.to_string().into()))
+            .await
+            .expect("request send");
+        while let Some(msg) = ws.next().await {
+            if let ClientMessage::Text(text) = msg.expect("ws message") {
+                let env: Value = serde_json::from_str(&text).expect("json");
+                let type_ = env.get("type").and_then(Value::as_str).unwrap_or_default();
+                if types.contains(&type_) {
+                    return (type_.to_string(), env["payload"].clone());
+                }
+            }
+        }
+        panic!("socket closed before {types:?}");
+    }
+
+    #[tokio::test]
+    async fn paired_devices_are_listed_and_changed_only_by_their_ccbox() {
+        let dir = tempdir().expect("tempdir");
+        let store_paths = make_store_paths(dir.path());
+        let app = build_router(AppState {
+            store_paths: store_paths.clone(),
+            relay: Arc::new(RelayState::new()),
+            rate_limiter: Arc::new(RateLimiter::new()),
+        });
+        let listener = tokio::net::TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], 0)))
+            .await
+            .expect("bind");
+        let addr = listener.local_addr().expect("local addr");
+        let server_task = tokio::spawn(async move {
+            let _ = axum::serve(
+                listener,
+                app.into_make_service_with_connect_info::<SocketAddr>(),
+            )
+            .await;
+        });
+
+        let guid = Uuid::new_v4().to_string();
+        let ccbox_key = random_signing_key();
+        let ccbox_public_key_b64 =
+            base64::engine::general_purpose::STANDARD.encode(ccbox_key.verifying_key().to_bytes());
+        let (mut ccbox_ws, _) = connect_async(format!("ws://{addr}/ccbox?guid={guid}"))
+            .await
+            .expect("ccbox connect");
+        ws_authenticate(
+            &mut ccbox_ws,
+            "ccbox",
+            &guid,
+            &ccbox_key,
+            Some(&ccbox_public_key_b64),
+        )
+        .await;
+
+        let (_, pairing) = ccbox_request(
+            &mut ccbox_ws,
+            serde_json::json!({
+                "v": REMOTE_PROTOCOL_VERSION,
+                "type": "ccbox/pairing/create",
+                "payload": { "ttl_seconds": 120 },
+            }),
+            &["ccbox/pairing/ok"],
+        )
+        .await;
+        let device_id = Uuid::new_v4().to_string();
+        pair_approve_blocking(
+            &store_paths,
+            &guid,
+            PairApproveRequest {
+                pairing_code: pairing["pairing_code"].as_str().expect("code").to_string(),
+                device_id: device_id.clone(),
+                public_key_b64: "key".to_string(),
+                label: Some("phone".to_string()),
+            },
+        )
+        .expect("pair approve");
+        let mut trusted = load_trusted_devices(&store_paths).expect("trusted devices");
+        trusted.trusted_devices.push(TrustedDevice {
+            device_id: "other".to_string(),
+            public_key_b64: "key".to_string(),
+            created_at: now_iso(),
+            last_seen_at: None,
+            revoked: false,
+            label: None,
+            paired_guid: Some(Uuid::new_v4().to_string()),
+        });
+        save_trusted_devices(&store_paths, &trusted).expect("trusted devices saved");
+
+        let devices_reply = ["ccbox/devices/ok", "ccbox/devices/err"];
+        let update = |device_id: &str, action: &str| {
+            serde_json::json!({
+                "v": REMOTE_PROTOCOL_VERSION,
+                "type": "ccbox/devices/update",
+                "payload": { "device_id": device_id, "action": action },
+            })
+        };
+        let (type_, listed) = ccbox_request(
+            &mut ccbox_ws,
+            serde_json::json!({
+                "v": REMOTE_PROTOCOL_VERSION,
+                "type": "ccbox/devices/list",
+                "payload": {},
+            }),
+            &devices_reply,
+        )
+        .await;
+        assert_eq!(type_, "ccbox/devices/ok");
+        let devices: Vec<PairedDevice> =
+            serde_json::from_value(listed["devices"].clone()).expect("devices");
+        assert_eq!(devices.len(), 1);
+        assert_eq!(devices[0].device_id, device_id);
+        assert_eq!(devices[0].label.as_deref(), Some("phone"));
+        assert!(!devices[0].revoked);
+
+        let (_, revoked) =
+            ccbox_request(&mut ccbox_ws, update(&device_id, "revoke"), &devices_reply).await;
+        assert_eq!(revoked["devices"][0]["revoked"], Value::Bool(true));
+
+        let (type_, other) =
+            ccbox_request(&mut ccbox_ws, update("other", "forget"), &devices_reply).await;
+        assert_eq!(type_, "ccbox/devices/err");
+        assert_eq!(other["code"], "DeviceNotFound");
+
+        let (_, forgotten) =
+            ccbox_request(&mut ccbox_ws, update(&device_id, "forget"), &devices_reply).await;
+        assert_eq!(forgotten["devices"], serde_json::json!([]));
+        let remaining = load_trusted_devices(&store_paths).expect("trusted devices");
+        assert_eq!(remaining.trusted_devices.len(), 1);
+        assert_eq!(remaining.trusted_devices[0].device_id, "other");
+
+        server_task.abort();
+    }
+
+    #[tokio::test]
+    async fn view_links_require_a_trusted_device_signature() {
+        let dir = tempdir().expect("tempdir");
+        let store_paths = make_store_paths(dir.path());
+
+        let client_key = random_signing_key();
+        let device_id = Uuid::new_v4().to_string();
+        save_trusted_devices(
+            &store_paths,
+            &TrustedDevicesFile {
+                trusted_devices: vec![TrustedDevice {
+                    device_id: device_id.clone(),
+                    public_key_b64: base64::engine::general_purpose::STANDARD
+                        .encode(client_key.verifying_key().to_bytes()),
+                    created_at: now_iso(),
+                    last_seen_at: None,
+                    revoked: false,
+                    label: None,
+                    paired_guid: None,
+                }],
+            },
+        )
+        .expect("trusted devices saved");
+
+        let state = AppState {
+            store_paths,
+            relay: Arc::new(RelayState::new()),
+            rate_limiter: Arc::new(RateLimiter::new()),
+        };
+        let guid = Uuid::new_v4().to_string();
+        let share_id = Uuid::new_v4().to_string();
+        state.relay.transcripts_by_guid.write().await.insert(
+            guid.clone(),
+            vec![SharedTranscript {
+                transcript: serde_json::from_value(serde_json::json!({
+                    "share_id": share_id,
+                    "session_id": "codex:abc",
+                    "title": "Fix login",
+                    "items": [{ "kind": "user", "summary": "hi", "detail": "hi" }],
+                }))
+                .expect("transcript"),
+                published_at: now_iso(),
+            }],
+        );
+
+        let expires = (now_ms() / 1000) as u64 + 600;
+        let signed = |share_id: &str, expires: u64| {
+            let message = build_view_message(&guid, share_id, &device_id, expires);
+            ViewQuery {
+                guid: None,
+                device_id: device_id.clone(),
+                expires,
+                sig: base64::engine::general_purpose::STANDARD
+                    .encode(client_key.sign(&message).to_bytes()),
+            }
+        };
+
+        let shared = authorize_view(&state, &guid, &share_id, &signed(&share_id, expires))
+            .await
+            .expect("authorized");
+        assert_eq!(shared.transcript.title, "Fix login");
+
+        let other_share = Uuid::new_v4().to_string();
+        let mut tampered = signed(&share_id, expires);
+        tampered.expires += 1;
+        assert_eq!(
+            authorize_view(&state, &guid, &share_id, &tampered)
+                .await
+                .err(),
+            Some("BadSignature")
+        );
+        assert_eq!(
+            authorize_view(&state, &guid, &share_id, &signed(&share_id, 1))
+                .await
+                .err(),
+            Some("LinkExpired")
+        );
+        assert_eq!(
+            authorize_view(&state, &guid, &other_share, &signed(&other_share, expires))
+                .await
+                .err(),
+            Some("NotFound")
+        );
+    }
 }
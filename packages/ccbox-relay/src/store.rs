@@ -1,4 +1,4 @@
-use crate::types::{CcboxesFile, PairingRecord, TrustedDevicesFile};
+use crate::types::{CcboxesFile, MetadataFile, PairingRecord, TrustedDevicesFile};
 use serde::Serialize;
 use serde::de::DeserializeOwned;
 use std::fs;
@@ -10,6 +10,7 @@ pub struct StorePaths {
     pub trusted_devices_path: PathBuf,
     pub ccboxes_path: PathBuf,
     pub pairings_dir: PathBuf,
+    pub metadata_dir: PathBuf,
 }
 
 pub fn make_store_paths(data_dir: &Path) -> StorePaths {
@@ -17,6 +18,7 @@ pub fn make_store_paths(data_dir: &Path) -> StorePaths {
         trusted_devices_path: data_dir.join("trusted_devices.json"),
         ccboxes_path: data_dir.join("ccboxes.json"),
         pairings_dir: data_dir.join("pairings"),
+        metadata_dir: data_dir.join("metadata"),
     }
 }
 
@@ -89,3 +91,20 @@ pub fn delete_pairing(paths: &StorePaths, guid: &str) -> io::Result<()> {
         Err(error) => Err(error),
     }
 }
+
+fn metadata_path_for_group(paths: &StorePaths, group: &str) -> io::Result<PathBuf> {
+    if !crate::util::is_uuid(group) {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "invalid group"));
+    }
+    Ok(paths.metadata_dir.join(format!("{group}.json")))
+}
+
+pub fn load_metadata(paths: &StorePaths, group: &str) -> io::Result<MetadataFile> {
+    let path = metadata_path_for_group(paths, group)?;
+    Ok(read_json_file::<MetadataFile>(&path)?.unwrap_or_default())
+}
+
+pub fn save_metadata(paths: &StorePaths, group: &str, file: &MetadataFile) -> io::Result<()> {
+    let path = metadata_path_for_group(paths, group)?;
+    atomic_write_json(&path, file)
+}
@@ -11,6 +11,9 @@ pub struct TrustedDevice {
     pub last_seen_at: Option<IsoTimestamp>,
     pub revoked: bool,
     pub label: Option<String>,
+    /// The ccbox whose pairing code the device used; `ccbox/devices/*` only reach its own devices.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paired_guid: Option<String>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -112,6 +115,8 @@ pub struct CcboxPairingErrPayload {
 #[derive(Debug, Deserialize)]
 pub struct MuxFramePayload {
     pub session_id: String,
+    #[serde(default)]
+    pub device_id: Option<String>,
     pub stream_id: u64,
     pub payload_b64: String,
 }
@@ -119,6 +124,95 @@ pub struct MuxFramePayload {
 #[derive(Debug, Serialize)]
 pub struct MuxFramePayloadOut {
     pub session_id: String,
+    /// Authenticated client device that sent the frame; set on client-to-ccbox frames only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub device_id: Option<String>,
     pub stream_id: u64,
     pub payload_b64: String,
 }
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct TranscriptItem {
+    pub kind: String,
+    #[serde(default)]
+    pub timestamp: Option<String>,
+    pub summary: String,
+    #[serde(default)]
+    pub detail: String,
+}
+
+/// A session transcript the ccbox published for read-only viewing at `/view/{share_id}`.
+#[derive(Clone, Debug, Deserialize)]
+pub struct TranscriptPublishPayload {
+    pub share_id: String,
+    pub session_id: String,
+    pub title: String,
+    pub items: Vec<TranscriptItem>,
+}
+
+/// One synced metadata value; `value: None` records a deletion. The newest `updated_at_ms`
+/// wins, with `machine` breaking ties.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct MetadataEntry {
+    pub key: String,
+    #[serde(default)]
+    pub value: Option<String>,
+    pub updated_at_ms: i64,
+    pub machine: String,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct MetadataFile {
+    pub entries: Vec<MetadataEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CcboxMetadataSyncPayload {
+    pub group: String,
+    pub entries: Vec<MetadataEntry>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CcboxMetadataOkPayload {
+    pub entries: Vec<MetadataEntry>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CcboxMetadataErrPayload {
+    pub code: String,
+}
+
+/// A trusted device as the ccbox it paired with sees it (no key).
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct PairedDevice {
+    pub device_id: String,
+    pub label: Option<String>,
+    pub created_at: IsoTimestamp,
+    pub last_seen_at: Option<IsoTimestamp>,
+    pub revoked: bool,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PairedDeviceAction {
+    Revoke,
+    Restore,
+    /// Drops the device's key, so it has to pair again.
+    Forget,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CcboxDevicesUpdatePayload {
+    pub device_id: String,
+    pub action: PairedDeviceAction,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CcboxDevicesOkPayload {
+    pub devices: Vec<PairedDevice>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CcboxDevicesErrPayload {
+    pub code: String,
+}
@@ -7,6 +7,7 @@ use uuid::Uuid;
 pub const REMOTE_PROTOCOL_VERSION: u8 = 1;
 pub const CONTROL_V1_STREAM_ID: u64 = 10;
 pub const AUTH_DOMAIN_SEPARATOR: &str = "ccbox-remote-auth:v1";
+pub const VIEW_DOMAIN_SEPARATOR: &str = "ccbox-remote-view:v1";
 
 pub fn now_iso() -> String {
     OffsetDateTime::now_utc()
@@ -97,6 +98,21 @@ pub fn build_auth_message(device_kind: &str, device_id: &str, nonce: &[u8]) -> V
     out
 }
 
+/// Signing input for a transcript view link:
+/// `VIEW_DOMAIN_SEPARATOR || guid || share_id || device_id || expires (unix seconds, decimal)`.
+pub fn build_view_message(guid: &str, share_id: &str, device_id: &str, expires: u64) -> Vec<u8> {
+    let expires = expires.to_string();
+    let mut out = Vec::with_capacity(
+        VIEW_DOMAIN_SEPARATOR.len() + guid.len() + share_id.len() + device_id.len() + expires.len(),
+    );
+    out.extend_from_slice(VIEW_DOMAIN_SEPARATOR.as_bytes());
+    out.extend_from_slice(guid.as_bytes());
+    out.extend_from_slice(share_id.as_bytes());
+    out.extend_from_slice(device_id.as_bytes());
+    out.extend_from_slice(expires.as_bytes());
+    out
+}
+
 #[cfg(test)]
 mod tests {
     use base64::Engine as _;
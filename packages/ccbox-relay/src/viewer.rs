@@ -0,0 +1,127 @@
+use crate::types::TranscriptPublishPayload;
+
+/// Renders a published transcript as a standalone page: no scripts, inline styles only.
+pub fn render_transcript_html(transcript: &TranscriptPublishPayload, published_at: &str) -> String {
+    let mut out = String::new();
+    out.push_str("<!doctype html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    out.push_str("<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n");
+    out.push_str("<meta name=\"robots\" content=\"noindex\">\n");
+    out.push_str(&format!(
+        "<title>{} · ccbox</title>\n",
+        escape_html(&transcript.title)
+    ));
+    out.push_str(STYLE);
+    out.push_str("</head>\n<body>\n<header>\n");
+    out.push_str(&format!("<h1>{}</h1>\n", escape_html(&transcript.title)));
+    out.push_str(&format!(
+        "<p class=\"meta\">session {} · published {} · read-only</p>\n",
+        escape_html(&transcript.session_id),
+        escape_html(published_at)
+    ));
+    out.push_str("</header>\n<main>\n");
+
+    for item in &transcript.items {
+        out.push_str(&format!(
+            "<section class=\"item {}\">\n<div class=\"head\"><span class=\"kind\">{}</span>",
+            kind_class(&item.kind),
+            escape_html(&item.kind)
+        ));
+        if let Some(timestamp) = item.timestamp.as_deref() {
+            out.push_str(&format!(
+                "<span class=\"ts\">{}</span>",
+                escape_html(timestamp)
+            ));
+        }
+        out.push_str("</div>\n");
+        let text = if item.detail.trim().is_empty() {
+            &item.summary
+        } else {
+            &item.detail
+        };
+        out.push_str(&format!("<pre>{}</pre>\n</section>\n", escape_html(text)));
+    }
+    if transcript.items.is_empty() {
+        out.push_str("<p class=\"meta\">No transcript items.</p>\n");
+    }
+
+    out.push_str("</main>\n</body>\n</html>\n");
+    out
+}
+
+pub fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+/// Item kinds come from the ccbox; only known ones become CSS classes.
+fn kind_class(kind: &str) -> &'static str {
+    match kind {
+        "user" => "user",
+        "assistant" => "assistant",
+        "thinking" => "thinking",
+        "tool_call" | "tool_output" => "tool",
+        _ => "other",
+    }
+}
+
+const STYLE: &str = "<style>
+body { margin: 0; background: #0f1115; color: #e6e6e6; font: 14px/1.5 system-ui, sans-serif; }
+header, main { max-width: 960px; margin: 0 auto; padding: 12px 16px; }
+h1 { font-size: 18px; margin: 8px 0 4px; }
+.meta { color: #8b93a1; margin: 0; }
+.item { border-left: 3px solid #39404d; margin: 10px 0; padding: 4px 10px; }
+.item.user { border-color: #4c8bf5; }
+.item.assistant { border-color: #3fb950; }
+.item.thinking { border-color: #a371f7; }
+.item.tool { border-color: #d29922; }
+.head { display: flex; gap: 12px; color: #8b93a1; font-size: 12px; }
+.kind { text-transform: uppercase; letter-spacing: 0.04em; }
+pre { white-space: pre-wrap; word-break: break-word; margin: 4px 0; font: 13px/1.45 ui-monospace, monospace; }
+</style>
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::TranscriptItem;
+
+    #[test]
+    fn renders_escaped_items_without_scripts() {
+        let transcript = TranscriptPublishPayload {
+            share_id: "s1".to_string(),
+            session_id: "codex:abc".to_string(),
+            title: "Fix <login>".to_string(),
+            items: vec![
+                TranscriptItem {
+                    kind: "user".to_string(),
+                    timestamp: Some("2026-01-02T03:04:05Z".to_string()),
+                    summary: "summary".to_string(),
+                    detail: "<script>alert('x')</script>".to_string(),
+                },
+                TranscriptItem {
+                    kind: "weird\"kind".to_string(),
+                    timestamp: None,
+                    summary: "only summary".to_string(),
+                    detail: String::new(),
+                },
+            ],
+        };
+
+        let html = render_transcript_html(&transcript, "2026-01-02T03:05:00Z");
+        assert!(html.contains("<title>Fix &lt;login&gt; · ccbox</title>"));
+        assert!(html.contains("&lt;script&gt;alert(&#39;x&#39;)&lt;/script&gt;"));
+        assert!(!html.contains("<script"));
+        assert!(html.contains("<section class=\"item other\">"));
+        assert!(html.contains("<pre>only summary</pre>"));
+    }
+}
@@ -1,4 +1,5 @@
 export const AUTH_DOMAIN_SEPARATOR = "ccbox-remote-auth:v1" as const;
+export const VIEW_DOMAIN_SEPARATOR = "ccbox-remote-view:v1" as const;
 
 export type DeviceKind = "client" | "ccbox";
 
@@ -24,3 +25,27 @@ export function buildAuthMessageV1(
   return out;
 }
 
+/**
+ * Transcript view link signing input:
+ *   utf8(VIEW_DOMAIN_SEPARATOR) || utf8(guid) || utf8(share_id) || utf8(device_id) || utf8(expires)
+ * where `expires` is unix seconds in decimal.
+ */
+export function buildViewMessageV1(
+  guid: string,
+  shareId: string,
+  deviceId: string,
+  expires: number,
+): Uint8Array<ArrayBuffer> {
+  const enc = new TextEncoder();
+  const parts = [VIEW_DOMAIN_SEPARATOR, guid, shareId, deviceId, String(expires)].map((part) =>
+    enc.encode(part),
+  );
+  const out = new Uint8Array(parts.reduce((len, part) => len + part.length, 0));
+  let offset = 0;
+  for (const part of parts) {
+    out.set(part, offset);
+    offset += part.length;
+  }
+  return out;
+}
+
@@ -1,5 +1,6 @@
-import { base64ToBytes } from "./base64";
-import { loadOrCreateIdentity, resetIdentity } from "./identity";
+import { buildViewMessageV1 } from "./authMessage";
+import { base64ToBytes, bytesToBase64 } from "./base64";
+import { loadOrCreateIdentity, resetIdentity, sign } from "./identity";
 import { connectRemoteClient } from "./protocol";
 
 function getEl(id: string): HTMLElement {
@@ -74,6 +75,8 @@ const timelineCursorEl = getEl("timelineCursor");
 const timelineGetBtn = getButton("timelineGetBtn");
 const timelineSubBtn = getButton("timelineSubBtn");
 const timelineClearBtn = getButton("timelineClearBtn");
+const timelineShareBtn = getButton("timelineShareBtn");
+const shareStatusEl = getEl("shareStatus");
 const timelinePre = getPre("timelinePre");
 
 const tasksListBtn = getButton("tasksListBtn");
@@ -205,6 +208,19 @@ function toWsUrl(raw: string): string {
   return `${scheme}://${v}/client`;
 }
 
+/** Lifetime of a signed transcript view link (the relay accepts up to 7 days). */
+const VIEW_LINK_TTL_SECONDS = 24 * 60 * 60;
+
+function toViewUrl(raw: string, viewPath: string): string {
+  const u = new URL(toWsUrl(raw));
+  if (u.protocol === "ws:") u.protocol = "http:";
+  else if (u.protocol === "wss:") u.protocol = "https:";
+  else throw new Error("Unsupported URL scheme");
+  u.pathname = viewPath;
+  u.hash = "";
+  return u.toString();
+}
+
 function toPairUrl(raw: string): string {
   const wsUrl = toWsUrl(raw);
   const u = new URL(wsUrl);
@@ -521,6 +537,7 @@ function updateTimelineControls() {
   timelineGetBtn.disabled = !enabled;
   timelineSubBtn.disabled = !enabled;
   timelineClearBtn.disabled = !(enabled && (timelinePre.textContent ?? "").length > 0);
+  timelineShareBtn.disabled = !enabled;
 }
 
 function updateTaskControls() {
@@ -836,6 +853,45 @@ async function getTimeline() {
   }
 }
 
+async function shareTimeline() {
+  if (!client) return;
+  clearError();
+
+  const sessionId = sessionIdInput.value.trim();
+  if (!sessionId) {
+    setError("Missing session_id");
+    return;
+  }
+
+  try {
+    const result = await client.rpc("sessions.share", {
+      session_id: sessionId,
+      limit: parseTimelineLimit(),
+    });
+    if (!isObject(result)) throw new Error("Invalid sessions.share result");
+    const shareId = asString(result.share_id);
+    const guid = asString(result.guid);
+    const viewPath = asString(result.view_path);
+    if (!shareId || !guid || !viewPath) throw new Error("Invalid sessions.share result");
+
+    const identity = await loadOrCreateIdentity();
+    const expires = Math.floor(Date.now() / 1000) + VIEW_LINK_TTL_SECONDS;
+    const signature = await sign(
+      identity,
+      buildViewMessageV1(guid, shareId, identity.deviceId, expires),
+    );
+
+    const url = new URL(toViewUrl(connectInput.value, viewPath));
+    url.searchParams.set("guid", guid);
+    url.searchParams.set("device_id", identity.deviceId);
+    url.searchParams.set("expires", String(expires));
+    url.searchParams.set("sig", bytesToBase64(signature));
+    shareStatusEl.textContent = url.toString();
+  } catch (err) {
+    setError(String(err));
+  }
+}
+
 async function subscribeTimeline() {
   if (!client) return;
   clearError();
@@ -1135,6 +1191,10 @@ timelineSubBtn.addEventListener("click", () => {
   subscribeTimeline().catch((err) => setError(String(err)));
 });
 
+timelineShareBtn.addEventListener("click", () => {
+  shareTimeline().catch((err) => setError(String(err)));
+});
+
 timelineClearBtn.addEventListener("click", () => {
   clearTimeline();
   updateTimelineControls();
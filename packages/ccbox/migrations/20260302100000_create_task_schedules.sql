@@ -0,0 +1,16 @@
+-- Scheduled task spawns (v1)
+--
+-- Notes:
+-- - One pending schedule per task; rescheduling replaces the row.
+-- - `engine` is the agent engine id (`codex` | `claude`).
+-- - Rows are removed when the schedule fires or is cancelled.
+
+CREATE TABLE IF NOT EXISTS task_schedules (
+  task_id TEXT PRIMARY KEY NOT NULL,
+  engine TEXT NOT NULL,
+  due_at_unix_ms INTEGER NOT NULL,
+  created_at_unix_ms INTEGER NOT NULL,
+  FOREIGN KEY(task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_schedules_due_at ON task_schedules(due_at_unix_ms);
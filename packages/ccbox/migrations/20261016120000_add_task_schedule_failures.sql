@@ -0,0 +1,8 @@
+-- Failed scheduled spawns (v2)
+--
+-- Notes:
+-- - `failed_attempts` counts spawns of the current schedule that failed; each failure pushes
+--   `due_at_unix_ms` out with a growing backoff, and the row is dropped after the last attempt.
+-- - Rescheduling a task resets the count.
+
+ALTER TABLE task_schedules ADD COLUMN failed_attempts INTEGER NOT NULL DEFAULT 0;
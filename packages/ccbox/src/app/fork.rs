@@ -32,6 +32,8 @@ pub fn fork_context_from_timeline_item(
         TimelineItemKind::Thinking
         | TimelineItemKind::ToolCall
         | TimelineItemKind::TokenCount
+        | TimelineItemKind::Todo
+        | TimelineItemKind::Compaction
         | TimelineItemKind::Note => return Err(ForkSelectionError::UnsupportedKind),
     };
 
@@ -43,6 +45,8 @@ pub fn fork_context_from_timeline_item(
         TimelineItemKind::Thinking
         | TimelineItemKind::ToolCall
         | TimelineItemKind::TokenCount
+        | TimelineItemKind::Todo
+        | TimelineItemKind::Compaction
         | TimelineItemKind::Note => item.summary.clone(),
     };
 
@@ -73,11 +77,15 @@ mod tests {
                 id: "019c72c9-e13d-71b3-b853-5ff79aa22102".to_string(),
                 cwd: PathBuf::from("/tmp/project"),
                 started_at_rfc3339: "2026-02-18T21:45:57.803Z".to_string(),
+                git: None,
             },
             log_path: PathBuf::from("/tmp/session.jsonl"),
             title: "t".to_string(),
             file_size_bytes: 0,
             file_modified: Some(SystemTime::now()),
+            parent_id: None,
+            project_root: None,
+            remote: None,
         }
     }
 
@@ -1,23 +1,33 @@
 mod fork;
 mod line_editor;
 mod mouse;
+mod path_picker;
+mod session_history;
 mod text_editor;
 
 use crate::app::fork::{default_fork_prompt, fork_context_from_timeline_item};
 use crate::domain::{
-    AgentEngine, ForkContext, ProjectIndex, ProjectSummary, SessionEngine, SessionStats,
-    SessionSummary, SkillLoop, SkillSpan, SpawnIoMode, Task, TaskId, TaskImage, TimelineItem,
-    TimelineItemKind, TurnContextSummary, detect_skill_loops, detect_skill_spans, index_projects,
+    AgentEngine, AuditEntry, ContextUsage, EngineFilterPrefs, EngineModels, ForkContext, InputWait,
+    InputWaitReason, ListColumnsConfig, PairingOffer, PlanState, ProjectIndex, ProjectPrefs,
+    ProjectSummary, PromptAttachment, RelayDevice, RetryPolicy, ScheduleSpec, SessionDiff,
+    SessionEngine, SessionStats, SessionSummary, SkillLoop, SkillSpan, SkillUsageReport,
+    SpawnArgsConfig, SpawnIoMode, TIMELINE_PAGE_ITEMS, Task, TaskId, TaskImage, TaskSchedule,
+    TimelineItem, TimelineItemKind, TurnContextSummary, append_prompt_images,
+    compute_context_usage, detect_skill_loops, detect_skill_spans, format_transcript, fuzzy_score,
+    index_projects, inline_attachments, latest_plan_state, matches_search_terms,
+    parse_rfc3339_to_unix_ms, parse_schedule_spec, timeline_item_text, timeline_page,
 };
-use crate::infra::{ScanWarningCount, SessionIndex};
+use crate::infra::{ExternalAgentProcess, ProcessUsage, ScanWarningCount, SessionIndex};
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent};
 use std::collections::{BTreeMap, BTreeSet};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
 use thiserror::Error;
 
 pub use line_editor::LineEditor;
+pub use path_picker::{PathPicker, PathPickerAction};
+pub use session_history::{SessionHistory, SessionHistoryEntry};
 pub use text_editor::TextEditor;
 
 #[derive(Debug, Error)]
@@ -48,6 +58,28 @@ impl EngineFilter {
             Self::OpenCode => "OpenCode",
         }
     }
+
+    pub fn from_engine(engine: Option<SessionEngine>) -> Self {
+        match engine {
+            None => Self::All,
+            Some(SessionEngine::Codex) => Self::Codex,
+            Some(SessionEngine::Claude) => Self::Claude,
+            Some(SessionEngine::Gemini) => Self::Gemini,
+            Some(SessionEngine::OpenCode) => Self::OpenCode,
+            // Custom engines have no filter of their own; they show under "All".
+            Some(SessionEngine::Custom) => Self::All,
+        }
+    }
+
+    pub fn engine(self) -> Option<SessionEngine> {
+        match self {
+            Self::All => None,
+            Self::Codex => Some(SessionEngine::Codex),
+            Self::Claude => Some(SessionEngine::Claude),
+            Self::Gemini => Some(SessionEngine::Gemini),
+            Self::OpenCode => Some(SessionEngine::OpenCode),
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -56,6 +88,27 @@ pub struct AppData {
     pub projects: Vec<ProjectSummary>,
     pub warnings: ScanWarningCount,
     pub load_error: Option<String>,
+    /// Set while the startup scan still runs in the background; `projects` are partial until then.
+    pub scan_status: Option<ScanStatus>,
+}
+
+/// Progress of the background startup scan: inputs read so far and the engines whose sessions
+/// are already listed.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ScanStatus {
+    pub done: usize,
+    pub total: usize,
+    pub engines_done: Vec<&'static str>,
+}
+
+impl ScanStatus {
+    pub fn label(&self) -> String {
+        let mut label = format!("Scanning sessions… {}/{}", self.done, self.total);
+        if !self.engines_done.is_empty() {
+            label.push_str(&format!(" · {} done", self.engines_done.join(", ")));
+        }
+        label
+    }
 }
 
 impl AppData {
@@ -69,8 +122,27 @@ impl AppData {
             projects: sessions,
             warnings,
             load_error: None,
+            scan_status: None,
         }
     }
+
+    /// Whether `project_path` is a remote source's project; those only hold remote sessions.
+    pub fn is_remote_project(&self, project_path: &Path) -> bool {
+        self.projects.iter().any(|project| {
+            project.project_path == project_path
+                && project
+                    .sessions
+                    .iter()
+                    .any(|session| session.remote.is_some())
+        })
+    }
+
+    pub fn is_remote_log(&self, log_path: &Path) -> bool {
+        self.projects
+            .iter()
+            .flat_map(|project| project.sessions.iter())
+            .any(|session| session.log_path == log_path && session.remote.is_some())
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -81,7 +153,9 @@ pub struct AppModel {
     pub terminal_size: (u16, u16),
     pub notice: Option<String>,
     pub update_hint: Option<String>,
+    /// Filter applied to the current view; follows `engine_prefs` when switching views.
     pub engine_filter: EngineFilter,
+    pub engine_prefs: EngineFilterPrefs,
     pub help_open: bool,
     pub system_menu: Option<SystemMenuOverlay>,
     pub delete_confirm: Option<DeleteConfirmDialog>,
@@ -90,12 +164,35 @@ pub struct AppModel {
     pub delete_sessions_confirm: Option<DeleteSessionsConfirmDialog>,
     pub session_rename: Option<SessionRenameDialog>,
     pub session_move: Option<SessionMoveDialog>,
+    pub opencode_write_back: Option<OpenCodeWriteBackConfirm>,
     pub delete_task_confirm: Option<DeleteTaskConfirmDialog>,
     pub delete_tasks_confirm: Option<DeleteTasksConfirmDialog>,
     pub session_result_preview: Option<SessionResultPreviewOverlay>,
     pub session_stats_overlay: Option<SessionStatsOverlay>,
     pub project_stats_overlay: Option<ProjectStatsOverlay>,
+    pub audit_log_overlay: Option<AuditLogOverlay>,
+    pub devices_overlay: Option<DevicesOverlay>,
+    pub session_diff_overlay: Option<SessionDiffOverlay>,
+    pub recent_sessions_overlay: Option<RecentSessionsOverlay>,
+    pub quick_switcher: Option<QuickSwitcherOverlay>,
+    /// Opened sessions for Alt+Left/Alt+Right and the Recent overlay (F7).
+    pub session_history: SessionHistory,
+    /// Item count and duration columns in the Sessions list (F6).
+    pub show_session_columns: bool,
+    /// Sort of the Projects list (F4); kept while drilling into a project and back.
+    pub projects_order: ProjectsOrder,
+    /// Columns of the Projects, Sessions and Tasks lists, from the config.
+    pub list_columns: ListColumnsConfig,
+    /// Models F10 cycles through on spawn screens, from the config.
+    pub engine_models: EngineModels,
+    /// Extra engine arguments from the config, previewed on spawn screens.
+    pub spawn_args: SpawnArgsConfig,
+    /// Display names and hidden flags of projects; saved to the state dir when changed.
+    pub project_prefs: ProjectPrefs,
+    pub project_rename: Option<ProjectRenameDialog>,
     pub processes: Vec<ProcessInfo>,
+    /// Agents running outside ccbox; refreshed while Processes is open.
+    pub external_processes: Vec<ExternalProcess>,
 }
 
 impl AppModel {
@@ -113,6 +210,7 @@ impl AppModel {
             notice: None,
             update_hint: None,
             engine_filter: EngineFilter::All,
+            engine_prefs: EngineFilterPrefs::default(),
             help_open: false,
             system_menu: None,
             delete_confirm: None,
@@ -121,16 +219,32 @@ impl AppModel {
             delete_sessions_confirm: None,
             session_rename: None,
             session_move: None,
+            opencode_write_back: None,
             delete_task_confirm: None,
             delete_tasks_confirm: None,
             session_result_preview: None,
             session_stats_overlay: None,
             project_stats_overlay: None,
+            audit_log_overlay: None,
+            devices_overlay: None,
+            session_diff_overlay: None,
+            recent_sessions_overlay: None,
+            quick_switcher: None,
+            session_history: SessionHistory::default(),
+            show_session_columns: false,
+            projects_order: ProjectsOrder::RecentFirst,
+            list_columns: ListColumnsConfig::default(),
+            engine_models: EngineModels::default(),
+            spawn_args: SpawnArgsConfig::default(),
+            project_prefs: ProjectPrefs::default(),
+            project_rename: None,
             processes: Vec::new(),
+            external_processes: Vec::new(),
         }
     }
 
-    pub fn with_data(&self, data: AppData) -> Self {
+    pub fn with_data(&self, mut data: AppData) -> Self {
+        self.project_prefs.apply(&mut data.projects);
         if data.load_error.is_some() {
             return Self {
                 data,
@@ -140,6 +254,7 @@ impl AppModel {
                 notice: None,
                 update_hint: self.update_hint.clone(),
                 engine_filter: self.engine_filter,
+                engine_prefs: self.engine_prefs.clone(),
                 help_open: self.help_open,
                 system_menu: self.system_menu.clone(),
                 delete_confirm: self.delete_confirm.clone(),
@@ -148,12 +263,27 @@ impl AppModel {
                 delete_sessions_confirm: self.delete_sessions_confirm.clone(),
                 session_rename: self.session_rename.clone(),
                 session_move: self.session_move.clone(),
+                opencode_write_back: self.opencode_write_back.clone(),
                 delete_task_confirm: self.delete_task_confirm.clone(),
                 delete_tasks_confirm: self.delete_tasks_confirm.clone(),
                 session_result_preview: self.session_result_preview.clone(),
                 session_stats_overlay: self.session_stats_overlay.clone(),
                 project_stats_overlay: self.project_stats_overlay.clone(),
+                audit_log_overlay: self.audit_log_overlay.clone(),
+                devices_overlay: self.devices_overlay.clone(),
+                session_diff_overlay: self.session_diff_overlay.clone(),
+                recent_sessions_overlay: self.recent_sessions_overlay.clone(),
+                quick_switcher: self.quick_switcher.clone(),
+                session_history: self.session_history.clone(),
+                show_session_columns: self.show_session_columns,
+                projects_order: self.projects_order,
+                list_columns: self.list_columns.clone(),
+                engine_models: self.engine_models.clone(),
+                spawn_args: self.spawn_args.clone(),
+                project_prefs: self.project_prefs.clone(),
+                project_rename: self.project_rename.clone(),
                 processes: self.processes.clone(),
+                external_processes: self.external_processes.clone(),
             };
         }
 
@@ -172,8 +302,15 @@ impl AppModel {
                     selected: projects_view.selected,
                     selection_anchor: projects_view.selection_anchor.clone(),
                     selected_project_paths: projects_view.selected_project_paths.clone(),
+                    show_hidden: projects_view.show_hidden,
                 };
-                apply_project_filter(&data.projects, &mut next_view, self.engine_filter);
+                apply_project_filter(
+                    &data.projects,
+                    &mut next_view,
+                    self.engine_filter,
+                    &self.session_index,
+                    self.projects_order,
+                );
                 prune_project_selection(&data.projects, &mut next_view);
 
                 if let Some(path) = selected_project_path {
@@ -241,11 +378,33 @@ impl AppModel {
                             &data.projects,
                             &mut projects_view,
                             self.engine_filter,
+                            &self.session_index,
+                            self.projects_order,
                         );
                         View::Projects(projects_view)
                     }
                 }
             }
+            View::AllSessions(all_sessions_view) => {
+                let selected_log_path = all_sessions_view
+                    .selected_session(&self.data.projects)
+                    .map(|(_, session)| session.log_path.clone());
+                let mut next_view = all_sessions_view.clone();
+                apply_all_sessions_filter(&data.projects, &mut next_view, self.engine_filter);
+                if let Some(log_path) = selected_log_path
+                    && let Some(pos) = next_view.filtered_indices.iter().position(
+                        |(project_index, session_index)| {
+                            data.projects
+                                .get(*project_index)
+                                .and_then(|project| project.sessions.get(*session_index))
+                                .is_some_and(|session| session.log_path == log_path)
+                        },
+                    )
+                {
+                    next_view.selected = pos;
+                }
+                View::AllSessions(next_view)
+            }
             View::NewSession(new_session_view) => {
                 let selected_log_path = self
                     .data
@@ -300,6 +459,8 @@ impl AppModel {
                             &data.projects,
                             &mut projects_view,
                             self.engine_filter,
+                            &self.session_index,
+                            self.projects_order,
                         );
                         View::Projects(projects_view)
                     }
@@ -309,7 +470,7 @@ impl AppModel {
                 match data
                     .projects
                     .iter()
-                    .find(|project| project.project_path == detail_view.session.meta.cwd)
+                    .find(|project| project.project_path == detail_view.session.project_path())
                 {
                     Some(project) => {
                         let mut next_view = detail_view.clone();
@@ -344,6 +505,8 @@ impl AppModel {
                             &data.projects,
                             &mut projects_view,
                             self.engine_filter,
+                            &self.session_index,
+                            self.projects_order,
                         );
                         View::Projects(projects_view)
                     }
@@ -354,9 +517,25 @@ impl AppModel {
             View::TaskDetail(task_detail_view) => View::TaskDetail(task_detail_view.clone()),
             View::Processes(processes_view) => View::Processes(processes_view.clone()),
             View::ProcessOutput(output_view) => View::ProcessOutput(output_view.clone()),
+            View::Activity(activity_view) => {
+                let mut next_view = activity_view.clone();
+                next_view.refresh(
+                    &self.processes,
+                    &self.external_processes,
+                    &data.projects,
+                    activity_view.as_of,
+                );
+                View::Activity(next_view)
+            }
             View::Error => {
                 let mut projects_view = ProjectsView::new(&data.projects);
-                apply_project_filter(&data.projects, &mut projects_view, self.engine_filter);
+                apply_project_filter(
+                    &data.projects,
+                    &mut projects_view,
+                    self.engine_filter,
+                    &self.session_index,
+                    self.projects_order,
+                );
                 View::Projects(projects_view)
             }
         };
@@ -369,6 +548,7 @@ impl AppModel {
             notice: None,
             update_hint: self.update_hint.clone(),
             engine_filter: self.engine_filter,
+            engine_prefs: self.engine_prefs.clone(),
             help_open: self.help_open,
             system_menu: self.system_menu.clone(),
             delete_confirm: self.delete_confirm.clone(),
@@ -377,12 +557,27 @@ impl AppModel {
             delete_sessions_confirm: self.delete_sessions_confirm.clone(),
             session_rename: self.session_rename.clone(),
             session_move: self.session_move.clone(),
+            opencode_write_back: self.opencode_write_back.clone(),
             delete_task_confirm: self.delete_task_confirm.clone(),
             delete_tasks_confirm: self.delete_tasks_confirm.clone(),
             session_result_preview: self.session_result_preview.clone(),
             session_stats_overlay: self.session_stats_overlay.clone(),
             project_stats_overlay: self.project_stats_overlay.clone(),
+            audit_log_overlay: self.audit_log_overlay.clone(),
+            devices_overlay: self.devices_overlay.clone(),
+            session_diff_overlay: self.session_diff_overlay.clone(),
+            recent_sessions_overlay: self.recent_sessions_overlay.clone(),
+            quick_switcher: self.quick_switcher.clone(),
+            session_history: self.session_history.clone(),
+            show_session_columns: self.show_session_columns,
+            projects_order: self.projects_order,
+            list_columns: self.list_columns.clone(),
+            engine_models: self.engine_models.clone(),
+            spawn_args: self.spawn_args.clone(),
+            project_prefs: self.project_prefs.clone(),
+            project_rename: self.project_rename.clone(),
             processes: self.processes.clone(),
+            external_processes: self.external_processes.clone(),
         }
     }
 
@@ -395,6 +590,7 @@ impl AppModel {
             notice: self.notice.clone(),
             update_hint: self.update_hint.clone(),
             engine_filter: self.engine_filter,
+            engine_prefs: self.engine_prefs.clone(),
             help_open: self.help_open,
             system_menu: self.system_menu.clone(),
             delete_confirm: self.delete_confirm.clone(),
@@ -403,12 +599,27 @@ impl AppModel {
             delete_sessions_confirm: self.delete_sessions_confirm.clone(),
             session_rename: self.session_rename.clone(),
             session_move: self.session_move.clone(),
+            opencode_write_back: self.opencode_write_back.clone(),
             delete_task_confirm: self.delete_task_confirm.clone(),
             delete_tasks_confirm: self.delete_tasks_confirm.clone(),
             session_result_preview: self.session_result_preview.clone(),
             session_stats_overlay: self.session_stats_overlay.clone(),
             project_stats_overlay: self.project_stats_overlay.clone(),
+            audit_log_overlay: self.audit_log_overlay.clone(),
+            devices_overlay: self.devices_overlay.clone(),
+            session_diff_overlay: self.session_diff_overlay.clone(),
+            recent_sessions_overlay: self.recent_sessions_overlay.clone(),
+            quick_switcher: self.quick_switcher.clone(),
+            session_history: self.session_history.clone(),
+            show_session_columns: self.show_session_columns,
+            projects_order: self.projects_order,
+            list_columns: self.list_columns.clone(),
+            engine_models: self.engine_models.clone(),
+            spawn_args: self.spawn_args.clone(),
+            project_prefs: self.project_prefs.clone(),
+            project_rename: self.project_rename.clone(),
             processes: self.processes.clone(),
+            external_processes: self.external_processes.clone(),
         }
     }
 
@@ -421,6 +632,7 @@ impl AppModel {
             notice,
             update_hint: self.update_hint.clone(),
             engine_filter: self.engine_filter,
+            engine_prefs: self.engine_prefs.clone(),
             help_open: self.help_open,
             system_menu: self.system_menu.clone(),
             delete_confirm: self.delete_confirm.clone(),
@@ -429,12 +641,27 @@ impl AppModel {
             delete_sessions_confirm: self.delete_sessions_confirm.clone(),
             session_rename: self.session_rename.clone(),
             session_move: self.session_move.clone(),
+            opencode_write_back: self.opencode_write_back.clone(),
             delete_task_confirm: self.delete_task_confirm.clone(),
             delete_tasks_confirm: self.delete_tasks_confirm.clone(),
             session_result_preview: self.session_result_preview.clone(),
             session_stats_overlay: self.session_stats_overlay.clone(),
             project_stats_overlay: self.project_stats_overlay.clone(),
+            audit_log_overlay: self.audit_log_overlay.clone(),
+            devices_overlay: self.devices_overlay.clone(),
+            session_diff_overlay: self.session_diff_overlay.clone(),
+            recent_sessions_overlay: self.recent_sessions_overlay.clone(),
+            quick_switcher: self.quick_switcher.clone(),
+            session_history: self.session_history.clone(),
+            show_session_columns: self.show_session_columns,
+            projects_order: self.projects_order,
+            list_columns: self.list_columns.clone(),
+            engine_models: self.engine_models.clone(),
+            spawn_args: self.spawn_args.clone(),
+            project_prefs: self.project_prefs.clone(),
+            project_rename: self.project_rename.clone(),
             processes: self.processes.clone(),
+            external_processes: self.external_processes.clone(),
         }
     }
 
@@ -442,6 +669,60 @@ impl AppModel {
         apply_engine_filter(self.clone(), filter)
     }
 
+    /// Restores remembered filters and applies the one for the current view.
+    pub fn with_project_prefs(&self, prefs: ProjectPrefs) -> Self {
+        let mut model = self.clone();
+        model.project_prefs = prefs;
+        let data = model.data.clone();
+        model.with_data(data)
+    }
+
+    pub fn with_engine_prefs(&self, prefs: EngineFilterPrefs) -> Self {
+        let mut model = self.clone();
+        model.engine_prefs = prefs;
+        sync_engine_filter_with_view(model)
+    }
+
+    /// Opens the Sessions view of the nearest known project containing `dir`; `None` when `dir`
+    /// is not inside any project. Esc from there returns to Projects with that project selected.
+    pub fn with_project_for_dir(&self, dir: &Path) -> Option<Self> {
+        let project = dir.ancestors().find_map(|candidate| {
+            self.data
+                .projects
+                .iter()
+                .find(|project| project.project_path == candidate)
+        })?;
+        let mut view = SessionsView::new(project.project_path.clone(), project.sessions.len());
+        apply_session_filter(
+            &project.sessions,
+            self.session_index.as_ref(),
+            &mut view,
+            self.engine_filter,
+        );
+        let mut model = self.clone();
+        model.view = View::Sessions(view);
+        Some(sync_engine_filter_with_view(model))
+    }
+
+    /// The open path picker (move-session dialog, task project path or New Session working
+    /// directory), if any.
+    pub fn path_picker_mut(&mut self) -> Option<&mut PathPicker> {
+        if let Some(dialog) = self.session_move.as_mut() {
+            return Some(&mut dialog.picker);
+        }
+        match &mut self.view {
+            View::TaskCreate(TaskCreateView {
+                overlay: Some(TaskCreateOverlay::ProjectPath(picker)),
+                ..
+            }) => Some(picker),
+            View::NewSession(NewSessionView {
+                working_dir_picker: Some(picker),
+                ..
+            }) => Some(picker.as_mut()),
+            _ => None,
+        }
+    }
+
     pub fn with_session_index(&self, index: Arc<SessionIndex>) -> Self {
         apply_session_index_update(self.clone(), index)
     }
@@ -462,6 +743,18 @@ impl AppModel {
             .rev()
             .find(|item| item.kind == TimelineItemKind::Assistant)
             .map(|item| item.detail.clone());
+        let plan = latest_plan_state(&items);
+        let context_usage = compute_context_usage(&items, &turn_contexts);
+        let from_all_sessions = match &self.view {
+            View::AllSessions(view) => Some(view.clone()),
+            View::SessionDetail(view) => view.from_all_sessions.clone(),
+            _ => None,
+        };
+        let mut session_history = self.session_history.clone();
+        session_history.visit(SessionHistoryEntry {
+            from_sessions: from_sessions.clone(),
+            session: session.clone(),
+        });
 
         Self {
             data: self.data.clone(),
@@ -470,6 +763,7 @@ impl AppModel {
             notice: None,
             update_hint: self.update_hint.clone(),
             engine_filter: self.engine_filter,
+            engine_prefs: self.engine_prefs.clone(),
             help_open: self.help_open,
             system_menu: self.system_menu.clone(),
             delete_confirm: self.delete_confirm.clone(),
@@ -478,30 +772,51 @@ impl AppModel {
             delete_sessions_confirm: self.delete_sessions_confirm.clone(),
             session_rename: self.session_rename.clone(),
             session_move: self.session_move.clone(),
+            opencode_write_back: self.opencode_write_back.clone(),
             delete_task_confirm: self.delete_task_confirm.clone(),
             delete_tasks_confirm: self.delete_tasks_confirm.clone(),
             session_result_preview: self.session_result_preview.clone(),
             session_stats_overlay: self.session_stats_overlay.clone(),
             project_stats_overlay: self.project_stats_overlay.clone(),
+            audit_log_overlay: self.audit_log_overlay.clone(),
+            devices_overlay: self.devices_overlay.clone(),
+            session_diff_overlay: self.session_diff_overlay.clone(),
+            recent_sessions_overlay: self.recent_sessions_overlay.clone(),
+            quick_switcher: self.quick_switcher.clone(),
+            session_history,
+            show_session_columns: self.show_session_columns,
+            projects_order: self.projects_order,
+            list_columns: self.list_columns.clone(),
+            engine_models: self.engine_models.clone(),
+            spawn_args: self.spawn_args.clone(),
+            project_prefs: self.project_prefs.clone(),
+            project_rename: self.project_rename.clone(),
             processes: self.processes.clone(),
+            external_processes: self.external_processes.clone(),
             view: View::SessionDetail(SessionDetailView {
                 from_sessions,
+                from_all_sessions,
                 session,
                 items,
                 skill_spans,
                 skill_loops,
                 turn_contexts,
+                context_usage,
                 warnings,
                 truncated,
+                older: Arc::default(),
+                older_items: 0,
                 selected: 0,
                 focus: SessionDetailFocus::Timeline,
                 details_scroll: 0,
                 context_overlay_open: false,
                 last_output: last_output.clone(),
+                plan,
                 output_overlay_open: false,
                 output_overlay_scroll: 0,
                 skills_overlay_open: false,
                 skills_overlay_scroll: 0,
+                expanded_outputs: BTreeSet::new(),
             }),
         }
     }
@@ -512,6 +827,9 @@ pub struct SessionResultPreviewOverlay {
     pub session_title: String,
     pub output: String,
     pub scroll: u16,
+    /// A project's newest sessions that Space cycles through; empty for a single session.
+    pub cycle: Vec<SessionSummary>,
+    pub cycle_index: usize,
 }
 
 #[derive(Clone, Debug)]
@@ -521,6 +839,164 @@ pub struct SessionStatsOverlay {
     pub scroll: u16,
 }
 
+/// Recorded destructive and spawn actions, newest first.
+#[derive(Clone, Debug)]
+pub struct AuditLogOverlay {
+    pub entries: Vec<AuditEntry>,
+    pub scroll: u16,
+}
+
+/// Client devices paired through the relay in `relay.json` (Ctrl+B): revoke/restore or forget
+/// for re-pairing.
+#[derive(Clone, Debug)]
+pub struct DevicesOverlay {
+    pub devices: Vec<RelayDevice>,
+    pub selected: usize,
+    /// Waiting for the relay's device list.
+    pub loading: bool,
+    /// Pairing a new device through the relay (`n`); shown instead of the device list.
+    pub pairing: Option<PairingState>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PairingState {
+    Requesting,
+    Ready(PairingOffer),
+    Failed(String),
+}
+
+impl DevicesOverlay {
+    pub fn selected_device(&self) -> Option<&RelayDevice> {
+        self.devices.get(self.selected)
+    }
+}
+
+/// Two sessions aligned step by step; `a` is the one that started first.
+#[derive(Clone, Debug)]
+pub struct SessionDiffOverlay {
+    pub title_a: String,
+    pub title_b: String,
+    pub diff: SessionDiff,
+    pub scroll: u16,
+}
+
+/// Fuzzy jump list over projects, sessions, tasks and running processes (F9). Tasks are loaded
+/// from the store when it opens.
+#[derive(Clone, Debug)]
+pub struct QuickSwitcherOverlay {
+    pub query: String,
+    pub selected: usize,
+    pub tasks: Vec<TaskSummaryRow>,
+}
+
+impl QuickSwitcherOverlay {
+    pub fn new(tasks: Vec<TaskSummaryRow>) -> Self {
+        Self {
+            query: String::new(),
+            selected: 0,
+            tasks,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum QuickSwitchTarget {
+    Project(PathBuf),
+    Session {
+        project_path: PathBuf,
+        log_path: PathBuf,
+    },
+    Task(TaskId),
+    Process(String),
+}
+
+impl QuickSwitchTarget {
+    pub fn kind_label(&self) -> &'static str {
+        match self {
+            Self::Project(_) => "project",
+            Self::Session { .. } => "session",
+            Self::Task(_) => "task",
+            Self::Process(_) => "process",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct QuickSwitchItem {
+    pub target: QuickSwitchTarget,
+    pub label: String,
+    pub detail: String,
+}
+
+const QUICK_SWITCH_LIMIT: usize = 50;
+
+/// Best matches for the switcher's query, best first (at most 50). With an empty query:
+/// processes, projects, tasks, then sessions, in their usual order.
+pub fn quick_switch_items(
+    model: &AppModel,
+    overlay: &QuickSwitcherOverlay,
+) -> Vec<QuickSwitchItem> {
+    let mut candidates = Vec::new();
+    for process in &model.processes {
+        candidates.push(QuickSwitchItem {
+            target: QuickSwitchTarget::Process(process.id.clone()),
+            label: process.prompt_preview.clone(),
+            detail: format!(
+                "{} · {}",
+                process.engine.label(),
+                process.project_path.display()
+            ),
+        });
+    }
+    for project in &model.data.projects {
+        candidates.push(QuickSwitchItem {
+            target: QuickSwitchTarget::Project(project.project_path.clone()),
+            label: project.name.clone(),
+            detail: project.project_path.display().to_string(),
+        });
+    }
+    for task in &overlay.tasks {
+        candidates.push(QuickSwitchItem {
+            target: QuickSwitchTarget::Task(task.id.clone()),
+            label: task.title.clone(),
+            detail: task.project_path.display().to_string(),
+        });
+    }
+    for project in &model.data.projects {
+        for session in &project.sessions {
+            candidates.push(QuickSwitchItem {
+                target: QuickSwitchTarget::Session {
+                    project_path: project.project_path.clone(),
+                    log_path: session.log_path.clone(),
+                },
+                label: session.title.clone(),
+                detail: format!("{} · {}", project.name, session.meta.id),
+            });
+        }
+    }
+
+    let mut scored = candidates
+        .into_iter()
+        .filter_map(|item| {
+            let score = fuzzy_score(&overlay.query, &item.label)
+                .max(fuzzy_score(&overlay.query, &item.detail).map(|score| score - 10))?;
+            Some((score, item))
+        })
+        .collect::<Vec<_>>();
+    scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    scored
+        .into_iter()
+        .take(QUICK_SWITCH_LIMIT)
+        .map(|(_, item)| item)
+        .collect()
+}
+
+/// Picks from `AppModel::session_history.recent()`, newest first.
+#[derive(Clone, Debug)]
+pub struct RecentSessionsOverlay {
+    pub selected: usize,
+}
+
 #[derive(Clone, Debug)]
 pub struct ProjectStatsOverlay {
     pub project_name: String,
@@ -529,6 +1005,13 @@ pub struct ProjectStatsOverlay {
     pub indexed_sessions: usize,
     pub total_tokens_indexed: u64,
     pub missing_tokens_sessions: usize,
+    /// Sums over the sessions the index has them for.
+    pub tool_calls: u64,
+    pub assistant_turns: u64,
+    pub active_ms: u64,
+    pub last_activity_unix_ms: Option<i64>,
+    /// Skill usage across the project's sessions; loaded on demand (`s`).
+    pub skills: Option<SkillUsageReport>,
     pub scroll: u16,
 }
 
@@ -537,7 +1020,19 @@ impl ProjectStatsOverlay {
         let mut total_tokens_indexed = 0u64;
         let mut indexed_sessions = 0usize;
         let mut missing_tokens_sessions = 0usize;
+        let mut tool_calls = 0u64;
+        let mut assistant_turns = 0u64;
+        let mut active_ms = 0u64;
+        let mut last_activity_unix_ms = None;
         for session in &project.sessions {
+            tool_calls = tool_calls
+                .saturating_add(u64::from(index.tool_calls(&session.log_path).unwrap_or(0)));
+            assistant_turns = assistant_turns.saturating_add(u64::from(
+                index.assistant_turns(&session.log_path).unwrap_or(0),
+            ));
+            active_ms = active_ms.saturating_add(index.duration_ms(session).unwrap_or(0));
+            last_activity_unix_ms =
+                last_activity_unix_ms.max(index.last_activity_unix_ms(&session.log_path));
             match index.total_tokens(&session.log_path) {
                 Some(tokens) => {
                     total_tokens_indexed = total_tokens_indexed.saturating_add(tokens);
@@ -556,6 +1051,11 @@ impl ProjectStatsOverlay {
             indexed_sessions,
             total_tokens_indexed,
             missing_tokens_sessions,
+            tool_calls,
+            assistant_turns,
+            active_ms,
+            last_activity_unix_ms,
+            skills: None,
             scroll: 0,
         }
     }
@@ -577,6 +1077,7 @@ pub enum MainMenu {
     TaskDetail,
     Processes,
     ProcessOutput,
+    Activity,
     Error,
 }
 
@@ -595,6 +1096,7 @@ impl MainMenu {
             Self::TaskDetail => "Task",
             Self::Processes => "Processes",
             Self::ProcessOutput => "Output",
+            Self::Activity => "Activity",
             Self::Error => "Error",
         }
     }
@@ -613,7 +1115,7 @@ pub struct MainMenuEntry {
     pub key: MainMenuKey,
 }
 
-pub const MAIN_MENU_SYSTEM_ITEMS: [MainMenuEntry; 8] = [
+pub const MAIN_MENU_SYSTEM_ITEMS: [MainMenuEntry; 9] = [
     MainMenuEntry {
         label: "Help",
         hotkey: "F1 or ?",
@@ -662,6 +1164,14 @@ pub const MAIN_MENU_SYSTEM_ITEMS: [MainMenuEntry; 8] = [
             modifiers: KeyModifiers::CONTROL,
         },
     },
+    MainMenuEntry {
+        label: "Reload config",
+        hotkey: "Ctrl+G",
+        key: MainMenuKey {
+            code: KeyCode::Char('g'),
+            modifiers: KeyModifiers::CONTROL,
+        },
+    },
     MainMenuEntry {
         label: "Processes",
         hotkey: "P",
@@ -680,7 +1190,7 @@ pub const MAIN_MENU_SYSTEM_ITEMS: [MainMenuEntry; 8] = [
     },
 ];
 
-pub const MAIN_MENU_WINDOW_ITEMS: [MainMenuEntry; 13] = [
+pub const MAIN_MENU_WINDOW_ITEMS: [MainMenuEntry; 22] = [
     MainMenuEntry {
         label: "Projects",
         hotkey: "Ctrl+1 or Cmd+1",
@@ -697,6 +1207,14 @@ pub const MAIN_MENU_WINDOW_ITEMS: [MainMenuEntry; 13] = [
             modifiers: KeyModifiers::CONTROL,
         },
     },
+    MainMenuEntry {
+        label: "All Sessions",
+        hotkey: "Ctrl+5 or Cmd+5",
+        key: MainMenuKey {
+            code: KeyCode::Char('5'),
+            modifiers: KeyModifiers::CONTROL,
+        },
+    },
     MainMenuEntry {
         label: "Tasks",
         hotkey: "Ctrl+4 or Cmd+4",
@@ -713,6 +1231,38 @@ pub const MAIN_MENU_WINDOW_ITEMS: [MainMenuEntry; 13] = [
             modifiers: KeyModifiers::NONE,
         },
     },
+    MainMenuEntry {
+        label: "Recent Sessions",
+        hotkey: "F7",
+        key: MainMenuKey {
+            code: KeyCode::F(7),
+            modifiers: KeyModifiers::NONE,
+        },
+    },
+    MainMenuEntry {
+        label: "Quick Switcher",
+        hotkey: "F9",
+        key: MainMenuKey {
+            code: KeyCode::F(9),
+            modifiers: KeyModifiers::NONE,
+        },
+    },
+    MainMenuEntry {
+        label: "Back (session history)",
+        hotkey: "Alt+Left",
+        key: MainMenuKey {
+            code: KeyCode::Left,
+            modifiers: KeyModifiers::ALT,
+        },
+    },
+    MainMenuEntry {
+        label: "Forward (session history)",
+        hotkey: "Alt+Right",
+        key: MainMenuKey {
+            code: KeyCode::Right,
+            modifiers: KeyModifiers::ALT,
+        },
+    },
     MainMenuEntry {
         label: "Task Detail",
         hotkey: "Ctrl+D or Cmd+D",
@@ -745,6 +1295,22 @@ pub const MAIN_MENU_WINDOW_ITEMS: [MainMenuEntry; 13] = [
             modifiers: KeyModifiers::CONTROL,
         },
     },
+    MainMenuEntry {
+        label: "Activity",
+        hotkey: "Ctrl+6 or Cmd+6",
+        key: MainMenuKey {
+            code: KeyCode::Char('6'),
+            modifiers: KeyModifiers::CONTROL,
+        },
+    },
+    MainMenuEntry {
+        label: "Attach to pending approval",
+        hotkey: "F12",
+        key: MainMenuKey {
+            code: KeyCode::F(12),
+            modifiers: KeyModifiers::NONE,
+        },
+    },
     MainMenuEntry {
         label: "Output: stdout",
         hotkey: "s",
@@ -777,6 +1343,22 @@ pub const MAIN_MENU_WINDOW_ITEMS: [MainMenuEntry; 13] = [
             modifiers: KeyModifiers::NONE,
         },
     },
+    MainMenuEntry {
+        label: "Audit Log",
+        hotkey: "Ctrl+L or Cmd+L",
+        key: MainMenuKey {
+            code: KeyCode::Char('l'),
+            modifiers: KeyModifiers::CONTROL,
+        },
+    },
+    MainMenuEntry {
+        label: "Devices",
+        hotkey: "Ctrl+B or Cmd+B",
+        key: MainMenuKey {
+            code: KeyCode::Char('b'),
+            modifiers: KeyModifiers::CONTROL,
+        },
+    },
     MainMenuEntry {
         label: "Help",
         hotkey: "F1 or ?",
@@ -830,7 +1412,7 @@ pub const MAIN_MENU_ENGINE_ITEMS: [MainMenuEntry; 5] = [
     },
 ];
 
-pub const MAIN_MENU_PROJECTS_ITEMS: [MainMenuEntry; 4] = [
+pub const MAIN_MENU_PROJECTS_ITEMS: [MainMenuEntry; 10] = [
     MainMenuEntry {
         label: "Open",
         hotkey: "Enter",
@@ -847,6 +1429,54 @@ pub const MAIN_MENU_PROJECTS_ITEMS: [MainMenuEntry; 4] = [
             modifiers: KeyModifiers::NONE,
         },
     },
+    MainMenuEntry {
+        label: "Open in IDE",
+        hotkey: "Ctrl+W",
+        key: MainMenuKey {
+            code: KeyCode::Char('w'),
+            modifiers: KeyModifiers::CONTROL,
+        },
+    },
+    MainMenuEntry {
+        label: "New session (Tab-selected projects)",
+        hotkey: "Ctrl+N or Cmd+N",
+        key: MainMenuKey {
+            code: KeyCode::Char('n'),
+            modifiers: KeyModifiers::CONTROL,
+        },
+    },
+    MainMenuEntry {
+        label: "Cycle sort",
+        hotkey: "F4",
+        key: MainMenuKey {
+            code: KeyCode::F(4),
+            modifiers: KeyModifiers::NONE,
+        },
+    },
+    MainMenuEntry {
+        label: "Rename project",
+        hotkey: "Ctrl+E or Cmd+E",
+        key: MainMenuKey {
+            code: KeyCode::Char('e'),
+            modifiers: KeyModifiers::CONTROL,
+        },
+    },
+    MainMenuEntry {
+        label: "Hide / unhide project",
+        hotkey: "Ctrl+X or Cmd+X",
+        key: MainMenuKey {
+            code: KeyCode::Char('x'),
+            modifiers: KeyModifiers::CONTROL,
+        },
+    },
+    MainMenuEntry {
+        label: "Show hidden projects",
+        hotkey: "F6",
+        key: MainMenuKey {
+            code: KeyCode::F(6),
+            modifiers: KeyModifiers::NONE,
+        },
+    },
     MainMenuEntry {
         label: "Delete project logs",
         hotkey: "Del",
@@ -865,7 +1495,7 @@ pub const MAIN_MENU_PROJECTS_ITEMS: [MainMenuEntry; 4] = [
     },
 ];
 
-pub const MAIN_MENU_SESSIONS_ITEMS: [MainMenuEntry; 10] = [
+pub const MAIN_MENU_SESSIONS_ITEMS: [MainMenuEntry; 16] = [
     MainMenuEntry {
         label: "Open",
         hotkey: "Enter",
@@ -890,6 +1520,38 @@ pub const MAIN_MENU_SESSIONS_ITEMS: [MainMenuEntry; 10] = [
             modifiers: KeyModifiers::NONE,
         },
     },
+    MainMenuEntry {
+        label: "Columns: items/duration/tokens",
+        hotkey: "F6",
+        key: MainMenuKey {
+            code: KeyCode::F(6),
+            modifiers: KeyModifiers::NONE,
+        },
+    },
+    MainMenuEntry {
+        label: "Compare 2 selected",
+        hotkey: "Ctrl+K or Cmd+K",
+        key: MainMenuKey {
+            code: KeyCode::Char('k'),
+            modifiers: KeyModifiers::CONTROL,
+        },
+    },
+    MainMenuEntry {
+        label: "Jump to parent (subagent)",
+        hotkey: "Ctrl+U or Cmd+U",
+        key: MainMenuKey {
+            code: KeyCode::Char('u'),
+            modifiers: KeyModifiers::CONTROL,
+        },
+    },
+    MainMenuEntry {
+        label: "Search prompts/outputs",
+        hotkey: "Ctrl+F or Cmd+F",
+        key: MainMenuKey {
+            code: KeyCode::Char('f'),
+            modifiers: KeyModifiers::CONTROL,
+        },
+    },
     MainMenuEntry {
         label: "Tool errors only",
         hotkey: "Ctrl+X or Cmd+X",
@@ -906,6 +1568,14 @@ pub const MAIN_MENU_SESSIONS_ITEMS: [MainMenuEntry; 10] = [
             modifiers: KeyModifiers::CONTROL,
         },
     },
+    MainMenuEntry {
+        label: "Cycle sort",
+        hotkey: "F4",
+        key: MainMenuKey {
+            code: KeyCode::F(4),
+            modifiers: KeyModifiers::NONE,
+        },
+    },
     MainMenuEntry {
         label: "Rename",
         hotkey: "Ctrl+E or Cmd+E",
@@ -930,6 +1600,14 @@ pub const MAIN_MENU_SESSIONS_ITEMS: [MainMenuEntry; 10] = [
             modifiers: KeyModifiers::CONTROL,
         },
     },
+    MainMenuEntry {
+        label: "Open in IDE",
+        hotkey: "Ctrl+W",
+        key: MainMenuKey {
+            code: KeyCode::Char('w'),
+            modifiers: KeyModifiers::CONTROL,
+        },
+    },
     MainMenuEntry {
         label: "Delete session log",
         hotkey: "Del",
@@ -948,7 +1626,7 @@ pub const MAIN_MENU_SESSIONS_ITEMS: [MainMenuEntry; 10] = [
     },
 ];
 
-pub const MAIN_MENU_NEW_SESSION_ITEMS: [MainMenuEntry; 4] = [
+pub const MAIN_MENU_NEW_SESSION_ITEMS: [MainMenuEntry; 13] = [
     MainMenuEntry {
         label: "Send",
         hotkey: "Ctrl+Enter or Cmd+Enter",
@@ -965,6 +1643,14 @@ pub const MAIN_MENU_NEW_SESSION_ITEMS: [MainMenuEntry; 4] = [
             modifiers: KeyModifiers::NONE,
         },
     },
+    MainMenuEntry {
+        label: "Cycle model",
+        hotkey: "F10",
+        key: MainMenuKey {
+            code: KeyCode::F(10),
+            modifiers: KeyModifiers::NONE,
+        },
+    },
     MainMenuEntry {
         label: "Switch I/O mode",
         hotkey: "F4",
@@ -974,29 +1660,93 @@ pub const MAIN_MENU_NEW_SESSION_ITEMS: [MainMenuEntry; 4] = [
         },
     },
     MainMenuEntry {
-        label: "Cancel",
-        hotkey: "Esc",
+        label: "Toggle auto-retry",
+        hotkey: "F5",
         key: MainMenuKey {
-            code: KeyCode::Esc,
+            code: KeyCode::F(5),
             modifiers: KeyModifiers::NONE,
         },
     },
-];
-
-pub const MAIN_MENU_SESSION_ITEMS: [MainMenuEntry; 10] = [
     MainMenuEntry {
-        label: "Jump Tool -> ToolOut",
-        hotkey: "Enter",
+        label: "Toggle git worktree",
+        hotkey: "F8",
         key: MainMenuKey {
-            code: KeyCode::Enter,
+            code: KeyCode::F(8),
             modifiers: KeyModifiers::NONE,
         },
     },
     MainMenuEntry {
-        label: "Fork/Resume from here",
-        hotkey: "f",
+        label: "Toggle A/B on both engines",
+        hotkey: "F6",
         key: MainMenuKey {
-            code: KeyCode::Char('f'),
+            code: KeyCode::F(6),
+            modifiers: KeyModifiers::NONE,
+        },
+    },
+    MainMenuEntry {
+        label: "Insert file",
+        hotkey: "Ctrl+O or Cmd+O",
+        key: MainMenuKey {
+            code: KeyCode::Char('o'),
+            modifiers: KeyModifiers::CONTROL,
+        },
+    },
+    MainMenuEntry {
+        label: "Insert image",
+        hotkey: "Ctrl+I or Cmd+I",
+        key: MainMenuKey {
+            code: KeyCode::Char('i'),
+            modifiers: KeyModifiers::CONTROL,
+        },
+    },
+    MainMenuEntry {
+        label: "Paste image",
+        hotkey: "Ctrl+V",
+        key: MainMenuKey {
+            code: KeyCode::Char('v'),
+            modifiers: KeyModifiers::CONTROL,
+        },
+    },
+    MainMenuEntry {
+        label: "Attach project file",
+        hotkey: "@ at word start",
+        key: MainMenuKey {
+            code: KeyCode::Char('@'),
+            modifiers: KeyModifiers::NONE,
+        },
+    },
+    MainMenuEntry {
+        label: "Working directory",
+        hotkey: "Ctrl+P or Cmd+P",
+        key: MainMenuKey {
+            code: KeyCode::Char('p'),
+            modifiers: KeyModifiers::CONTROL,
+        },
+    },
+    MainMenuEntry {
+        label: "Cancel",
+        hotkey: "Esc",
+        key: MainMenuKey {
+            code: KeyCode::Esc,
+            modifiers: KeyModifiers::NONE,
+        },
+    },
+];
+
+pub const MAIN_MENU_SESSION_ITEMS: [MainMenuEntry; 17] = [
+    MainMenuEntry {
+        label: "Jump Tool -> ToolOut",
+        hotkey: "Enter",
+        key: MainMenuKey {
+            code: KeyCode::Enter,
+            modifiers: KeyModifiers::NONE,
+        },
+    },
+    MainMenuEntry {
+        label: "Fork/Resume from here",
+        hotkey: "f",
+        key: MainMenuKey {
+            code: KeyCode::Char('f'),
             modifiers: KeyModifiers::NONE,
         },
     },
@@ -1040,6 +1790,62 @@ pub const MAIN_MENU_SESSION_ITEMS: [MainMenuEntry; 10] = [
             modifiers: KeyModifiers::CONTROL,
         },
     },
+    MainMenuEntry {
+        label: "Copy item",
+        hotkey: "y",
+        key: MainMenuKey {
+            code: KeyCode::Char('y'),
+            modifiers: KeyModifiers::NONE,
+        },
+    },
+    MainMenuEntry {
+        label: "Copy last output",
+        hotkey: "Y",
+        key: MainMenuKey {
+            code: KeyCode::Char('Y'),
+            modifiers: KeyModifiers::NONE,
+        },
+    },
+    MainMenuEntry {
+        label: "Copy transcript",
+        hotkey: "Ctrl+Y or Cmd+Y",
+        key: MainMenuKey {
+            code: KeyCode::Char('y'),
+            modifiers: KeyModifiers::CONTROL,
+        },
+    },
+    MainMenuEntry {
+        label: "Open log in $EDITOR",
+        hotkey: "v",
+        key: MainMenuKey {
+            code: KeyCode::Char('v'),
+            modifiers: KeyModifiers::NONE,
+        },
+    },
+    MainMenuEntry {
+        label: "Next/previous user message",
+        hotkey: "] / [",
+        key: MainMenuKey {
+            code: KeyCode::Char(']'),
+            modifiers: KeyModifiers::NONE,
+        },
+    },
+    MainMenuEntry {
+        label: "Next/previous assistant message",
+        hotkey: "} / {",
+        key: MainMenuKey {
+            code: KeyCode::Char('}'),
+            modifiers: KeyModifiers::NONE,
+        },
+    },
+    MainMenuEntry {
+        label: "Expand/collapse tool output",
+        hotkey: "e",
+        key: MainMenuKey {
+            code: KeyCode::Char('e'),
+            modifiers: KeyModifiers::NONE,
+        },
+    },
     MainMenuEntry {
         label: "Visible Context",
         hotkey: "c",
@@ -1066,7 +1872,7 @@ pub const MAIN_MENU_SESSION_ITEMS: [MainMenuEntry; 10] = [
     },
 ];
 
-pub const MAIN_MENU_TASKS_ITEMS: [MainMenuEntry; 6] = [
+pub const MAIN_MENU_TASKS_ITEMS: [MainMenuEntry; 9] = [
     MainMenuEntry {
         label: "Open",
         hotkey: "Enter",
@@ -1091,6 +1897,14 @@ pub const MAIN_MENU_TASKS_ITEMS: [MainMenuEntry; 6] = [
             modifiers: KeyModifiers::NONE,
         },
     },
+    MainMenuEntry {
+        label: "Schedule",
+        hotkey: "Ctrl+S or Cmd+S",
+        key: MainMenuKey {
+            code: KeyCode::Char('s'),
+            modifiers: KeyModifiers::CONTROL,
+        },
+    },
     MainMenuEntry {
         label: "Switch engine",
         hotkey: "Shift+Tab",
@@ -1099,6 +1913,22 @@ pub const MAIN_MENU_TASKS_ITEMS: [MainMenuEntry; 6] = [
             modifiers: KeyModifiers::NONE,
         },
     },
+    MainMenuEntry {
+        label: "Cycle model",
+        hotkey: "F10",
+        key: MainMenuKey {
+            code: KeyCode::F(10),
+            modifiers: KeyModifiers::NONE,
+        },
+    },
+    MainMenuEntry {
+        label: "Toggle git worktree",
+        hotkey: "F8",
+        key: MainMenuKey {
+            code: KeyCode::F(8),
+            modifiers: KeyModifiers::NONE,
+        },
+    },
     MainMenuEntry {
         label: "Delete task",
         hotkey: "Del",
@@ -1160,7 +1990,7 @@ pub const MAIN_MENU_TASK_CREATE_ITEMS: [MainMenuEntry; 5] = [
     },
 ];
 
-pub const MAIN_MENU_TASK_DETAIL_ITEMS: [MainMenuEntry; 4] = [
+pub const MAIN_MENU_TASK_DETAIL_ITEMS: [MainMenuEntry; 7] = [
     MainMenuEntry {
         label: "Spawn",
         hotkey: "Ctrl+Enter or Cmd+Enter",
@@ -1177,6 +2007,30 @@ pub const MAIN_MENU_TASK_DETAIL_ITEMS: [MainMenuEntry; 4] = [
             modifiers: KeyModifiers::NONE,
         },
     },
+    MainMenuEntry {
+        label: "Cycle model",
+        hotkey: "F10",
+        key: MainMenuKey {
+            code: KeyCode::F(10),
+            modifiers: KeyModifiers::NONE,
+        },
+    },
+    MainMenuEntry {
+        label: "Toggle git worktree",
+        hotkey: "F8",
+        key: MainMenuKey {
+            code: KeyCode::F(8),
+            modifiers: KeyModifiers::NONE,
+        },
+    },
+    MainMenuEntry {
+        label: "Toggle A/B on both engines",
+        hotkey: "F6",
+        key: MainMenuKey {
+            code: KeyCode::F(6),
+            modifiers: KeyModifiers::NONE,
+        },
+    },
     MainMenuEntry {
         label: "Delete task",
         hotkey: "Del",
@@ -1195,7 +2049,7 @@ pub const MAIN_MENU_TASK_DETAIL_ITEMS: [MainMenuEntry; 4] = [
     },
 ];
 
-pub const MAIN_MENU_PROCESSES_ITEMS: [MainMenuEntry; 7] = [
+pub const MAIN_MENU_PROCESSES_ITEMS: [MainMenuEntry; 15] = [
     MainMenuEntry {
         label: "Attach (TTY)",
         hotkey: "a",
@@ -1204,6 +2058,14 @@ pub const MAIN_MENU_PROCESSES_ITEMS: [MainMenuEntry; 7] = [
             modifiers: KeyModifiers::NONE,
         },
     },
+    MainMenuEntry {
+        label: "Quick reply (TTY)",
+        hotkey: "r",
+        key: MainMenuKey {
+            code: KeyCode::Char('r'),
+            modifiers: KeyModifiers::NONE,
+        },
+    },
     MainMenuEntry {
         label: "Open stdout",
         hotkey: "s",
@@ -1229,13 +2091,61 @@ pub const MAIN_MENU_PROCESSES_ITEMS: [MainMenuEntry; 7] = [
         },
     },
     MainMenuEntry {
-        label: "Kill process",
+        label: "Save stdout / transcript",
+        hotkey: "w",
+        key: MainMenuKey {
+            code: KeyCode::Char('w'),
+            modifiers: KeyModifiers::NONE,
+        },
+    },
+    MainMenuEntry {
+        label: "Copy stdout / transcript",
+        hotkey: "y",
+        key: MainMenuKey {
+            code: KeyCode::Char('y'),
+            modifiers: KeyModifiers::NONE,
+        },
+    },
+    MainMenuEntry {
+        label: "Stop (SIGTERM)",
         hotkey: "k",
         key: MainMenuKey {
             code: KeyCode::Char('k'),
             modifiers: KeyModifiers::NONE,
         },
     },
+    MainMenuEntry {
+        label: "Force kill",
+        hotkey: "K",
+        key: MainMenuKey {
+            code: KeyCode::Char('K'),
+            modifiers: KeyModifiers::NONE,
+        },
+    },
+    MainMenuEntry {
+        label: "Kill all in project",
+        hotkey: "Ctrl+K or Cmd+K",
+        key: MainMenuKey {
+            code: KeyCode::Char('k'),
+            modifiers: KeyModifiers::CONTROL,
+        },
+    },
+    MainMenuEntry {
+        label: "Label / group",
+        hotkey: "g",
+        key: MainMenuKey {
+            code: KeyCode::Char('g'),
+            modifiers: KeyModifiers::NONE,
+        },
+    },
+    MainMenuEntry {
+        label: "Collapse group",
+        hotkey: "Space",
+        key: MainMenuKey {
+            code: KeyCode::Char(' '),
+            modifiers: KeyModifiers::NONE,
+        },
+    },
     MainMenuEntry {
         label: "Open session",
         hotkey: "Enter",
@@ -1244,6 +2154,14 @@ pub const MAIN_MENU_PROCESSES_ITEMS: [MainMenuEntry; 7] = [
             modifiers: KeyModifiers::NONE,
         },
     },
+    MainMenuEntry {
+        label: "Open task",
+        hotkey: "t",
+        key: MainMenuKey {
+            code: KeyCode::Char('t'),
+            modifiers: KeyModifiers::NONE,
+        },
+    },
     MainMenuEntry {
         label: "Back",
         hotkey: "Esc or Backspace",
@@ -1254,7 +2172,7 @@ pub const MAIN_MENU_PROCESSES_ITEMS: [MainMenuEntry; 7] = [
     },
 ];
 
-pub const MAIN_MENU_PROCESS_OUTPUT_ITEMS: [MainMenuEntry; 5] = [
+pub const MAIN_MENU_PROCESS_OUTPUT_ITEMS: [MainMenuEntry; 13] = [
     MainMenuEntry {
         label: "stdout",
         hotkey: "s",
@@ -1280,13 +2198,112 @@ pub const MAIN_MENU_PROCESS_OUTPUT_ITEMS: [MainMenuEntry; 5] = [
         },
     },
     MainMenuEntry {
-        label: "Kill process",
+        label: "Follow tail",
+        hotkey: "f",
+        key: MainMenuKey {
+            code: KeyCode::Char('f'),
+            modifiers: KeyModifiers::NONE,
+        },
+    },
+    MainMenuEntry {
+        label: "Search",
+        hotkey: "/",
+        key: MainMenuKey {
+            code: KeyCode::Char('/'),
+            modifiers: KeyModifiers::NONE,
+        },
+    },
+    MainMenuEntry {
+        label: "Next / previous match",
+        hotkey: "n / N",
+        key: MainMenuKey {
+            code: KeyCode::Char('n'),
+            modifiers: KeyModifiers::NONE,
+        },
+    },
+    MainMenuEntry {
+        label: "Raw / colors",
+        hotkey: "r",
+        key: MainMenuKey {
+            code: KeyCode::Char('r'),
+            modifiers: KeyModifiers::NONE,
+        },
+    },
+    MainMenuEntry {
+        label: "Save to file",
+        hotkey: "w",
+        key: MainMenuKey {
+            code: KeyCode::Char('w'),
+            modifiers: KeyModifiers::NONE,
+        },
+    },
+    MainMenuEntry {
+        label: "Copy output",
+        hotkey: "y",
+        key: MainMenuKey {
+            code: KeyCode::Char('y'),
+            modifiers: KeyModifiers::NONE,
+        },
+    },
+    MainMenuEntry {
+        label: "Stop (SIGTERM)",
         hotkey: "k",
         key: MainMenuKey {
             code: KeyCode::Char('k'),
             modifiers: KeyModifiers::NONE,
         },
     },
+    MainMenuEntry {
+        label: "Force kill",
+        hotkey: "K",
+        key: MainMenuKey {
+            code: KeyCode::Char('K'),
+            modifiers: KeyModifiers::NONE,
+        },
+    },
+    MainMenuEntry {
+        label: "Open session",
+        hotkey: "Enter",
+        key: MainMenuKey {
+            code: KeyCode::Enter,
+            modifiers: KeyModifiers::NONE,
+        },
+    },
+    MainMenuEntry {
+        label: "Back",
+        hotkey: "Esc or Backspace",
+        key: MainMenuKey {
+            code: KeyCode::Esc,
+            modifiers: KeyModifiers::NONE,
+        },
+    },
+];
+
+pub const MAIN_MENU_ACTIVITY_ITEMS: [MainMenuEntry; 4] = [
+    MainMenuEntry {
+        label: "Open",
+        hotkey: "Enter",
+        key: MainMenuKey {
+            code: KeyCode::Enter,
+            modifiers: KeyModifiers::NONE,
+        },
+    },
+    MainMenuEntry {
+        label: "Longer window",
+        hotkey: "+",
+        key: MainMenuKey {
+            code: KeyCode::Char('+'),
+            modifiers: KeyModifiers::NONE,
+        },
+    },
+    MainMenuEntry {
+        label: "Shorter window",
+        hotkey: "-",
+        key: MainMenuKey {
+            code: KeyCode::Char('-'),
+            modifiers: KeyModifiers::NONE,
+        },
+    },
     MainMenuEntry {
         label: "Back",
         hotkey: "Esc or Backspace",
@@ -1328,6 +2345,8 @@ pub const MAIN_MENUS_SESSIONS: [MainMenu; 4] = [
     MainMenu::Engine,
     MainMenu::Sessions,
 ];
+pub const MAIN_MENUS_ALL_SESSIONS: [MainMenu; 3] =
+    [MainMenu::System, MainMenu::Window, MainMenu::Engine];
 pub const MAIN_MENUS_NEW_SESSION: [MainMenu; 3] =
     [MainMenu::System, MainMenu::Window, MainMenu::NewSession];
 pub const MAIN_MENUS_SESSION_DETAIL: [MainMenu; 3] =
@@ -1341,12 +2360,15 @@ pub const MAIN_MENUS_PROCESSES: [MainMenu; 3] =
     [MainMenu::System, MainMenu::Window, MainMenu::Processes];
 pub const MAIN_MENUS_PROCESS_OUTPUT: [MainMenu; 3] =
     [MainMenu::System, MainMenu::Window, MainMenu::ProcessOutput];
+pub const MAIN_MENUS_ACTIVITY: [MainMenu; 3] =
+    [MainMenu::System, MainMenu::Window, MainMenu::Activity];
 pub const MAIN_MENUS_ERROR: [MainMenu; 3] = [MainMenu::System, MainMenu::Window, MainMenu::Error];
 
 pub fn main_menus_for_view(view: &View) -> &'static [MainMenu] {
     match view {
         View::Projects(_) => &MAIN_MENUS_PROJECTS,
         View::Sessions(_) => &MAIN_MENUS_SESSIONS,
+        View::AllSessions(_) => &MAIN_MENUS_ALL_SESSIONS,
         View::NewSession(_) => &MAIN_MENUS_NEW_SESSION,
         View::SessionDetail(_) => &MAIN_MENUS_SESSION_DETAIL,
         View::Tasks(_) => &MAIN_MENUS_TASKS,
@@ -1354,6 +2376,7 @@ pub fn main_menus_for_view(view: &View) -> &'static [MainMenu] {
         View::TaskDetail(_) => &MAIN_MENUS_TASK_DETAIL,
         View::Processes(_) => &MAIN_MENUS_PROCESSES,
         View::ProcessOutput(_) => &MAIN_MENUS_PROCESS_OUTPUT,
+        View::Activity(_) => &MAIN_MENUS_ACTIVITY,
         View::Error => &MAIN_MENUS_ERROR,
     }
 }
@@ -1372,6 +2395,7 @@ pub fn main_menu_items(menu: MainMenu) -> &'static [MainMenuEntry] {
         MainMenu::TaskDetail => &MAIN_MENU_TASK_DETAIL_ITEMS,
         MainMenu::Processes => &MAIN_MENU_PROCESSES_ITEMS,
         MainMenu::ProcessOutput => &MAIN_MENU_PROCESS_OUTPUT_ITEMS,
+        MainMenu::Activity => &MAIN_MENU_ACTIVITY_ITEMS,
         MainMenu::Error => &MAIN_MENU_ERROR_ITEMS,
     }
 }
@@ -1443,20 +2467,48 @@ pub struct SessionRenameDialog {
 }
 
 #[derive(Clone, Debug)]
-pub struct SessionMoveDialog {
-    pub session: SessionSummary,
+pub struct ProjectRenameDialog {
+    pub project_path: PathBuf,
     pub editor: LineEditor,
 }
 
 #[derive(Clone, Debug)]
-pub struct DeleteTaskConfirmDialog {
-    pub task_id: TaskId,
+pub struct SessionMoveDialog {
+    pub session: SessionSummary,
+    pub picker: PathPicker,
+}
+
+/// A rename or project move to copy into OpenCode's own DB.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum OpenCodeWriteBack {
+    Title(String),
+    Project(PathBuf),
+}
+
+/// Asks whether to write a rename/move of an OpenCode session back to the OpenCode DB.
+#[derive(Clone, Debug)]
+pub struct OpenCodeWriteBackConfirm {
+    pub session: SessionSummary,
+    pub change: OpenCodeWriteBack,
+}
+
+#[derive(Clone, Debug)]
+pub struct DeleteTaskConfirmDialog {
+    pub task_id: TaskId,
     pub task_title: String,
     pub project_path: PathBuf,
     pub selection: DeleteConfirmSelection,
     pub return_to_tasks: TasksView,
 }
 
+/// Confirms killing every running process spawned in a project (`Ctrl+K` in Processes).
+#[derive(Clone, Debug)]
+pub struct KillProjectConfirmDialog {
+    pub project_path: PathBuf,
+    pub process_ids: Vec<String>,
+    pub selection: DeleteConfirmSelection,
+}
+
 #[derive(Clone, Debug)]
 pub struct DeleteTasksConfirmDialog {
     pub task_ids: Vec<TaskId>,
@@ -1468,20 +2520,43 @@ pub struct DeleteTasksConfirmDialog {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum ProcessStatus {
     Running,
+    /// Exited with a failure; the retry policy will re-spawn it shortly.
+    RetryPending(Option<i32>),
     Exited(Option<i32>),
+    /// Sent SIGTERM (`k`) and still running; SIGKILL follows after the grace period.
+    Stopping,
     Killed,
 }
 
 impl ProcessStatus {
     pub fn is_running(&self) -> bool {
-        matches!(self, Self::Running)
+        matches!(self, Self::Running | Self::Stopping)
+    }
+
+    /// Running, or about to be re-spawned by the retry policy.
+    pub fn is_active(&self) -> bool {
+        matches!(self, Self::Running | Self::Stopping | Self::RetryPending(_))
+    }
+
+    /// The status once the process has exited: `Killed` when it was being stopped, otherwise
+    /// the exit code, or `RetryPending` when the retry policy re-spawns it.
+    pub fn on_exit(&self, exit_code: Option<i32>, will_retry: bool) -> Self {
+        match self {
+            Self::Running if will_retry => Self::RetryPending(exit_code),
+            Self::Running => Self::Exited(exit_code),
+            Self::Stopping => Self::Killed,
+            other => other.clone(),
+        }
     }
 
     pub fn label(&self) -> String {
         match self {
             Self::Running => "running".to_string(),
+            Self::RetryPending(Some(code)) => format!("exit {code}, retrying"),
+            Self::RetryPending(None) => "retrying".to_string(),
             Self::Exited(Some(code)) => format!("exit {code}"),
             Self::Exited(None) => "exited".to_string(),
+            Self::Stopping => "stopping".to_string(),
             Self::Killed => "killed".to_string(),
         }
     }
@@ -1548,12 +2623,158 @@ pub struct ProcessInfo {
     pub io_mode: ProcessIoMode,
     pub session_id: Option<String>,
     pub session_log_path: Option<PathBuf>,
+    pub retry_policy: RetryPolicy,
+    /// Earlier failed attempts, oldest first; the current attempt is `attempts.len() + 1`.
+    pub attempts: Vec<ProcessAttempt>,
+    /// Latest plan/todo snapshot from the process's session log.
+    pub plan: Option<PlanState>,
+    /// Set while the running agent is blocked on a question or plan approval.
+    pub input_wait: Option<InputWait>,
+    /// Latest CPU/RSS sample while running.
+    pub usage: Option<ProcessUsage>,
+    /// Set when the process was spawned from a task.
+    pub task_id: Option<TaskId>,
+    /// Title of that task at spawn time.
+    pub task_title: Option<String>,
+    /// Batch number shared by processes spawned together across several projects.
+    pub batch: Option<u32>,
+    /// A/B number shared by the two engines given the same prompt; their sessions are compared
+    /// once both finish.
+    pub compare: Option<u32>,
+    /// Group shown in Processes (`g`); task spawns start with the task title.
+    pub label: Option<String>,
+}
+
+impl ProcessInfo {
+    pub fn attempt(&self) -> u32 {
+        u32::try_from(self.attempts.len())
+            .unwrap_or(u32::MAX)
+            .saturating_add(1)
+    }
+}
+
+/// A/B runs whose two processes have both finished, with the processes in spawn order.
+pub fn finished_comparisons(processes: &[ProcessInfo]) -> Vec<(u32, [&ProcessInfo; 2])> {
+    let mut groups: BTreeMap<u32, Vec<&ProcessInfo>> = BTreeMap::new();
+    for process in processes {
+        if let Some(compare) = process.compare {
+            groups.entry(compare).or_default().push(process);
+        }
+    }
+    groups
+        .into_iter()
+        .filter_map(|(compare, group)| match group.as_slice() {
+            [a, b] if !a.status.is_active() && !b.status.is_active() => Some((compare, [*a, *b])),
+            _ => None,
+        })
+        .collect()
+}
+
+/// One row of the Processes list: a label header followed by its processes (unless collapsed),
+/// or an unlabelled process in spawn order.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ProcessRow {
+    Group {
+        label: String,
+        count: usize,
+        running: usize,
+        collapsed: bool,
+    },
+    /// Index into `AppModel::processes`; `grouped` rows render under their header.
+    Process { index: usize, grouped: bool },
+}
+
+/// An agent ccbox did not spawn, listed read-only after the spawned processes.
+#[derive(Clone, Debug)]
+pub struct ExternalProcess {
+    pub agent: ExternalAgentProcess,
+    /// The session it is most likely writing, once one shows up.
+    pub session: Option<SessionSummary>,
+}
+
+/// The newest session of the agent's engine with the agent's cwd, written since it started.
+pub fn external_agent_session(
+    data: &AppData,
+    agent: &ExternalAgentProcess,
+) -> Option<SessionSummary> {
+    data.projects
+        .iter()
+        .flat_map(|project| &project.sessions)
+        .filter(|session| session.engine == agent.engine && session.meta.cwd == agent.cwd)
+        .filter(|session| match (session.file_modified, agent.started_at) {
+            (Some(modified), Some(started_at)) => modified >= started_at,
+            _ => true,
+        })
+        .max_by_key(|session| session.file_modified)
+        .cloned()
+}
+
+/// Running processes blocked on a tool permission request, in spawn order.
+pub fn awaiting_approval(processes: &[ProcessInfo]) -> Vec<&ProcessInfo> {
+    processes
+        .iter()
+        .filter(|process| process.status.is_running())
+        .filter(|process| {
+            process
+                .input_wait
+                .as_ref()
+                .is_some_and(|wait| wait.reason == InputWaitReason::Approval)
+        })
+        .collect()
+}
+
+/// Groups sit where their first process was spawned and list every process with that label.
+pub fn process_rows(processes: &[ProcessInfo], collapsed: &BTreeSet<String>) -> Vec<ProcessRow> {
+    let mut rows = Vec::new();
+    let mut seen = BTreeSet::new();
+    for (index, process) in processes.iter().enumerate() {
+        let Some(label) = process.label.as_deref() else {
+            rows.push(ProcessRow::Process {
+                index,
+                grouped: false,
+            });
+            continue;
+        };
+        if !seen.insert(label) {
+            continue;
+        }
+        let members = processes
+            .iter()
+            .enumerate()
+            .filter(|(_, process)| process.label.as_deref() == Some(label))
+            .collect::<Vec<_>>();
+        let is_collapsed = collapsed.contains(label);
+        rows.push(ProcessRow::Group {
+            label: label.to_string(),
+            count: members.len(),
+            running: members
+                .iter()
+                .filter(|(_, process)| process.status.is_running())
+                .count(),
+            collapsed: is_collapsed,
+        });
+        if !is_collapsed {
+            rows.extend(members.into_iter().map(|(index, _)| ProcessRow::Process {
+                index,
+                grouped: true,
+            }));
+        }
+    }
+    rows
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProcessAttempt {
+    pub pid: u32,
+    pub started_at: SystemTime,
+    pub exit_code: Option<i32>,
 }
 
 #[derive(Clone, Debug)]
 pub enum View {
     Projects(ProjectsView),
     Sessions(SessionsView),
+    AllSessions(AllSessionsView),
     NewSession(NewSessionView),
     SessionDetail(SessionDetailView),
     Tasks(TasksView),
@@ -1561,6 +2782,7 @@ pub enum View {
     TaskDetail(TaskDetailView),
     Processes(ProcessesView),
     ProcessOutput(ProcessOutputView),
+    Activity(ActivityView),
     Error,
 }
 
@@ -1571,17 +2793,24 @@ pub struct ProjectsView {
     pub selected: usize,
     pub selection_anchor: Option<PathBuf>,
     pub selected_project_paths: BTreeSet<PathBuf>,
+    /// List hidden projects too (F6).
+    pub show_hidden: bool,
 }
 
 impl ProjectsView {
     pub fn new(projects: &[ProjectSummary]) -> Self {
-        let filtered_indices = (0..projects.len()).collect();
+        let filtered_indices = projects
+            .iter()
+            .enumerate()
+            .filter_map(|(index, project)| (!project.hidden).then_some(index))
+            .collect();
         Self {
             query: String::new(),
             filtered_indices,
             selected: 0,
             selection_anchor: None,
             selected_project_paths: BTreeSet::new(),
+            show_hidden: false,
         }
     }
 }
@@ -1591,6 +2820,8 @@ pub struct SessionsView {
     pub project_path: PathBuf,
     pub query: String,
     pub tool_errors_only: bool,
+    /// Also match the query against indexed prompt/output words (Ctrl+F).
+    pub search_content: bool,
     pub order: SessionsOrder,
     pub filtered_indices: Vec<usize>,
     pub session_selected: usize,
@@ -1604,6 +2835,7 @@ impl SessionsView {
             project_path,
             query: String::new(),
             tool_errors_only: false,
+            search_content: false,
             order: SessionsOrder::NewestFirst,
             filtered_indices: (0..session_count).collect(),
             session_selected: 0,
@@ -1622,23 +2854,104 @@ impl SessionsView {
     }
 }
 
+/// Sessions of every project in one list, most recently active first (Ctrl+5).
+#[derive(Clone, Debug, Default)]
+pub struct AllSessionsView {
+    pub query: String,
+    /// `(project, session)` indices into `AppData::projects`.
+    pub filtered_indices: Vec<(usize, usize)>,
+    pub selected: usize,
+}
+
+impl AllSessionsView {
+    pub fn selected_session<'a>(
+        &self,
+        projects: &'a [ProjectSummary],
+    ) -> Option<(&'a ProjectSummary, &'a SessionSummary)> {
+        let (project_index, session_index) = self.filtered_indices.get(self.selected).copied()?;
+        let project = projects.get(project_index)?;
+        Some((project, project.sessions.get(session_index)?))
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ProjectsOrder {
+    /// Most recently active first; the order projects are listed in.
+    RecentFirst,
+    MostSessions,
+    LargestFirst,
+    MostTokens,
+}
+
+impl ProjectsOrder {
+    /// The next sort mode for F4.
+    pub fn cycle(self) -> Self {
+        match self {
+            Self::RecentFirst => Self::MostSessions,
+            Self::MostSessions => Self::LargestFirst,
+            Self::LargestFirst => Self::MostTokens,
+            Self::MostTokens => Self::RecentFirst,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::RecentFirst => "recent first",
+            Self::MostSessions => "most sessions",
+            Self::LargestFirst => "largest first",
+            Self::MostTokens => "most tokens first",
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum SessionsOrder {
+    /// Last modified first; the order sessions are listed in.
     NewestFirst,
+    StartedFirst,
+    LargestFirst,
+    MostTokens,
+    LongestFirst,
+    Title,
     ToolErrorsFirst,
 }
 
 impl SessionsOrder {
     pub fn toggle_tool_errors_first(self) -> Self {
         match self {
-            Self::NewestFirst => Self::ToolErrorsFirst,
             Self::ToolErrorsFirst => Self::NewestFirst,
+            _ => Self::ToolErrorsFirst,
+        }
+    }
+
+    /// The next sort mode for F4; leaves tool-errors-first for newest first.
+    pub fn cycle(self) -> Self {
+        match self {
+            Self::NewestFirst => Self::StartedFirst,
+            Self::StartedFirst => Self::LargestFirst,
+            Self::LargestFirst => Self::MostTokens,
+            Self::MostTokens => Self::LongestFirst,
+            Self::LongestFirst => Self::Title,
+            Self::Title | Self::ToolErrorsFirst => Self::NewestFirst,
         }
     }
 
+    /// Orders that read the session index, so they change when it is refreshed.
+    pub fn is_index_backed(self) -> bool {
+        matches!(
+            self,
+            Self::MostTokens | Self::LongestFirst | Self::ToolErrorsFirst
+        )
+    }
+
     pub fn label(self) -> &'static str {
         match self {
             Self::NewestFirst => "newest first",
+            Self::StartedFirst => "started, newest first",
+            Self::LargestFirst => "largest first",
+            Self::MostTokens => "most tokens first",
+            Self::LongestFirst => "longest first",
+            Self::Title => "title A-Z",
             Self::ToolErrorsFirst => "tool errors first",
         }
     }
@@ -1650,7 +2963,38 @@ pub struct NewSessionView {
     pub editor: TextEditor,
     pub engine: AgentEngine,
     pub io_mode: SpawnIoMode,
+    pub retry: RetryPolicy,
+    /// Run the agent in a new git worktree instead of the project checkout.
+    pub worktree: bool,
     pub fork: Option<ForkContext>,
+    /// OpenCode session the prompt continues (`opencode run --session`).
+    pub resume: Option<SessionSummary>,
+    /// Path input of the "Insert file" dialog (Ctrl+O); the file's text goes in at the cursor.
+    pub insert_file: Option<LineEditor>,
+    /// Project-relative path input of the "Attach file" dialog, opened by typing `@` at a word start.
+    pub attach_file: Option<LineEditor>,
+    /// Files attached with `@path`; those still mentioned are fenced onto the prompt on send.
+    pub attachments: Vec<PromptAttachment>,
+    /// Path input of the "Insert image" dialog (Ctrl+I); Enter on an empty path pastes the clipboard.
+    pub image_path: Option<LineEditor>,
+    /// Images inserted as `[Image N]`, listed under the prompt and passed to engines that take
+    /// image inputs.
+    pub image_paths: Vec<PathBuf>,
+    /// Set when the prompt goes to several projects at once (Ctrl+N with projects Tab-selected).
+    pub batch: Option<BatchSpawn>,
+    /// Also spawn the prompt on [`AgentEngine::toggle`] of `engine` and compare the results (F6).
+    pub ab_compare: bool,
+    /// Model passed to `engine` (F10); `None` leaves the engine's default.
+    pub agent_model: Option<String>,
+    /// Directory the agent runs in instead of the project root (Ctrl+P); `None` is the root.
+    pub working_dir: Option<PathBuf>,
+    pub working_dir_picker: Option<Box<PathPicker>>,
+}
+
+#[derive(Clone, Debug)]
+pub struct BatchSpawn {
+    pub from_projects: ProjectsView,
+    pub project_paths: Vec<PathBuf>,
 }
 
 impl NewSessionView {
@@ -1660,9 +3004,27 @@ impl NewSessionView {
             editor: TextEditor::new(),
             engine: AgentEngine::Codex,
             io_mode: SpawnIoMode::Pipes,
+            retry: RetryPolicy::disabled(),
+            worktree: false,
             fork: None,
+            resume: None,
+            insert_file: None,
+            attach_file: None,
+            attachments: Vec::new(),
+            image_path: None,
+            image_paths: Vec::new(),
+            batch: None,
+            ab_compare: false,
+            agent_model: None,
+            working_dir: None,
+            working_dir_picker: None,
         }
     }
+
+    /// Forks and resumes lock the engine, I/O mode, retry and worktree settings.
+    pub fn is_resume(&self) -> bool {
+        self.fork.is_some() || self.resume.is_some()
+    }
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -1683,22 +3045,90 @@ impl SessionDetailFocus {
 #[derive(Clone, Debug)]
 pub struct SessionDetailView {
     pub from_sessions: SessionsView,
+    /// Set when opened from All Sessions; Esc goes back there instead of `from_sessions`.
+    pub from_all_sessions: Option<AllSessionsView>,
     pub session: SessionSummary,
     pub items: Vec<TimelineItem>,
     pub skill_spans: Vec<SkillSpan>,
     pub skill_loops: Vec<SkillLoop>,
     pub turn_contexts: BTreeMap<String, TurnContextSummary>,
+    /// Estimated context window use; `None` when the log has no token counts.
+    pub context_usage: Option<ContextUsage>,
     pub warnings: usize,
     pub truncated: bool,
+    /// Items of the log before `items[0]`, parsed when the log was read; shared so that cloning
+    /// the model doesn't copy them.
+    pub older: Arc<Vec<TimelineItem>>,
+    /// How many of `older` are not shown yet; a page at a time moves into `items` when scrolling
+    /// past the top.
+    pub older_items: usize,
     pub selected: usize,
     pub focus: SessionDetailFocus,
     pub details_scroll: u16,
     pub context_overlay_open: bool,
     pub last_output: Option<String>,
+    pub plan: Option<PlanState>,
     pub output_overlay_open: bool,
     pub output_overlay_scroll: u16,
     pub skills_overlay_open: bool,
     pub skills_overlay_scroll: u16,
+    /// ToolOut item indices shown in full; other long outputs render collapsed.
+    pub expanded_outputs: BTreeSet<usize>,
+}
+
+impl SessionDetailView {
+    /// Moves the page of `older` right above `items[0]` into `items` and selects the item above
+    /// the previous first one. Returns the notice to show.
+    fn load_older_page(&mut self) -> String {
+        let mut items = timeline_page(&self.older, self.older_items, TIMELINE_PAGE_ITEMS);
+        let count = items.len();
+        if count == 0 {
+            self.older_items = 0;
+            return "No older items.".to_string();
+        }
+
+        items.append(&mut self.items);
+        self.items = items;
+        self.older_items -= count;
+        self.selected = self.selected + count - 1;
+        self.details_scroll = 0;
+        self.expanded_outputs = self
+            .expanded_outputs
+            .iter()
+            .map(|index| index + count)
+            .collect();
+        self.skill_spans = detect_skill_spans(&self.items);
+        self.skill_loops = detect_skill_loops(&self.skill_spans);
+        self.context_usage = compute_context_usage(&self.items, &self.turn_contexts);
+        self.plan = latest_plan_state(&self.items);
+        if self.older_items > 0 {
+            format!(
+                "Loaded {count} older items ({} more above).",
+                self.older_items
+            )
+        } else {
+            format!("Loaded {count} older items.")
+        }
+    }
+}
+
+impl SessionDetailView {
+    /// The ToolOut behind the selection: the item itself, or the output of a selected Tool call.
+    pub fn selected_output_index(&self) -> Option<usize> {
+        let selected = self.selected.min(self.items.len().saturating_sub(1));
+        let item = self.items.get(selected)?;
+        match item.kind {
+            TimelineItemKind::ToolOutput => Some(selected),
+            TimelineItemKind::ToolCall => {
+                find_tool_output_index(&self.items, selected, item.call_id.as_deref()?)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn is_output_expanded(&self, index: usize) -> bool {
+        self.expanded_outputs.contains(&index)
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -1708,6 +3138,17 @@ pub struct TaskSummaryRow {
     pub project_path: PathBuf,
     pub updated_at: SystemTime,
     pub image_count: u32,
+    pub schedule: Option<TaskSchedule>,
+}
+
+#[derive(Clone, Debug)]
+pub struct TaskScheduleDialog {
+    pub task_id: TaskId,
+    pub task_title: String,
+    pub engine: AgentEngine,
+    pub is_scheduled: bool,
+    pub editor: LineEditor,
+    pub error: Option<String>,
 }
 
 #[derive(Clone, Debug)]
@@ -1720,6 +3161,11 @@ pub struct TasksView {
     pub selection_anchor: Option<TaskId>,
     pub selected_task_ids: BTreeSet<TaskId>,
     pub engine: AgentEngine,
+    /// Spawn tasks in a new git worktree named after the task.
+    pub worktree: bool,
+    /// Model passed to `engine` (F10); `None` leaves the engine's default.
+    pub agent_model: Option<String>,
+    pub schedule_dialog: Option<TaskScheduleDialog>,
 }
 
 impl TasksView {
@@ -1734,6 +3180,9 @@ impl TasksView {
             selection_anchor: None,
             selected_task_ids: BTreeSet::new(),
             engine: AgentEngine::Codex,
+            worktree: false,
+            agent_model: None,
+            schedule_dialog: None,
         }
     }
 
@@ -1744,23 +3193,28 @@ impl TasksView {
         prune_task_selection(&mut self);
 
         if let Some(task_id) = selected_id {
-            if let Some(pos) = self.filtered_indices.iter().position(|index| {
-                self.tasks
-                    .get(*index)
-                    .is_some_and(|task| task.id == task_id)
-            }) {
-                self.selected = pos;
-            }
+            self.select_task(&task_id);
         }
 
         self
     }
+
+    /// Moves the cursor to `task_id` if it passes the filter.
+    pub fn select_task(&mut self, task_id: &TaskId) {
+        if let Some(pos) = self.filtered_indices.iter().position(|index| {
+            self.tasks
+                .get(*index)
+                .is_some_and(|task| task.id == *task_id)
+        }) {
+            self.selected = pos;
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
 pub enum TaskCreateOverlay {
     ImagePath(LineEditor),
-    ProjectPath(LineEditor),
+    ProjectPath(PathPicker),
 }
 
 #[derive(Clone, Debug)]
@@ -1790,15 +3244,201 @@ pub struct TaskDetailView {
     pub task: Task,
     pub images: Vec<TaskImage>,
     pub engine: AgentEngine,
+    pub worktree: bool,
+    /// Spawn on both engines and compare the results (F6).
+    pub ab_compare: bool,
+    /// Model passed to `engine` (F10); `None` leaves the engine's default.
+    pub agent_model: Option<String>,
     pub scroll: u16,
 }
 
 #[derive(Clone, Debug)]
 pub struct ProcessesView {
     pub return_to: Box<View>,
+    /// Index into `process_rows`.
+    pub selected: usize,
+    /// Labels whose processes are hidden under their header.
+    pub collapsed: BTreeSet<String>,
+    /// Set while editing the label of the selected process or group (`g`).
+    pub label_input: Option<String>,
+    pub kill_project_confirm: Option<KillProjectConfirmDialog>,
+    /// Set while typing where to save the selected process's output (`w`).
+    pub export_input: Option<String>,
+    /// Set while typing a line to send to the selected TTY process (`r`).
+    pub reply_input: Option<String>,
+}
+
+impl ProcessesView {
+    pub fn new(return_to: Box<View>, selected: usize) -> Self {
+        Self {
+            return_to,
+            selected,
+            collapsed: BTreeSet::new(),
+            label_input: None,
+            kill_project_confirm: None,
+            export_input: None,
+            reply_input: None,
+        }
+    }
+
+    pub fn rows(&self, processes: &[ProcessInfo]) -> Vec<ProcessRow> {
+        process_rows(processes, &self.collapsed)
+    }
+
+    /// Spawned rows plus one row per external process after them.
+    pub fn row_count(&self, processes: &[ProcessInfo], external: &[ExternalProcess]) -> usize {
+        self.rows(processes).len() + external.len()
+    }
+
+    /// The external process on the selected row.
+    pub fn selected_external<'a>(
+        &self,
+        processes: &[ProcessInfo],
+        external: &'a [ExternalProcess],
+    ) -> Option<&'a ExternalProcess> {
+        external.get(self.selected.checked_sub(self.rows(processes).len())?)
+    }
+
+    /// The process on the selected row; `None` on a group header.
+    pub fn selected_process<'a>(&self, processes: &'a [ProcessInfo]) -> Option<&'a ProcessInfo> {
+        match self.rows(processes).get(self.selected)? {
+            ProcessRow::Process { index, .. } => processes.get(*index),
+            ProcessRow::Group { .. } => None,
+        }
+    }
+
+    /// The row showing `process_id`, or its group header when the group is collapsed.
+    pub fn row_of(&self, processes: &[ProcessInfo], process_id: &str) -> Option<usize> {
+        let process = processes.iter().find(|process| process.id == process_id)?;
+        self.rows(processes).iter().position(|row| match row {
+            ProcessRow::Process { index, .. } => processes[*index].id == process_id,
+            ProcessRow::Group {
+                label, collapsed, ..
+            } => *collapsed && process.label.as_ref() == Some(label),
+        })
+    }
+}
+
+/// Lookback choices for the Activity view's session list (`+`/`-`), in minutes.
+pub const ACTIVITY_WINDOWS_MINS: [u64; 5] = [5, 15, 30, 60, 240];
+/// A session written to within this long is shown as active rather than idle.
+const ACTIVITY_ACTIVE_SECS: u64 = 120;
+
+/// Running processes and recently written sessions of every project in one list (Ctrl+6).
+#[derive(Clone, Debug)]
+pub struct ActivityView {
+    pub return_to: Box<View>,
+    /// Index into `ACTIVITY_WINDOWS_MINS`.
+    pub window: usize,
+    /// When `rows` were last built; sessions are aged against it.
+    pub as_of: SystemTime,
+    pub rows: Vec<ActivityRow>,
     pub selected: usize,
 }
 
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ActivityRow {
+    /// A running process spawned by ccbox.
+    Process { process_id: String },
+    /// An agent started outside ccbox.
+    External { pid: u32 },
+    Session {
+        project_path: PathBuf,
+        log_path: PathBuf,
+    },
+}
+
+impl ActivityView {
+    pub fn new(return_to: Box<View>) -> Self {
+        Self {
+            return_to,
+            window: 1,
+            as_of: SystemTime::UNIX_EPOCH,
+            rows: Vec::new(),
+            selected: 0,
+        }
+    }
+
+    /// Whether a session modified at `modified` was written to in the last two minutes.
+    pub fn is_active_session(&self, modified: Option<SystemTime>) -> bool {
+        modified.is_some_and(|modified| {
+            self.as_of
+                .duration_since(modified)
+                .map_or(true, |age| age.as_secs() < ACTIVITY_ACTIVE_SECS)
+        })
+    }
+
+    pub fn window_mins(&self) -> u64 {
+        ACTIVITY_WINDOWS_MINS[self.window.min(ACTIVITY_WINDOWS_MINS.len() - 1)]
+    }
+
+    /// Rebuilds `rows` as of `now`, keeping the selected row when it is still listed. A session
+    /// that a listed process reported is shown on the process row only.
+    pub fn refresh(
+        &mut self,
+        processes: &[ProcessInfo],
+        external: &[ExternalProcess],
+        projects: &[ProjectSummary],
+        now: SystemTime,
+    ) {
+        let selected = self.rows.get(self.selected).cloned();
+        let running = processes
+            .iter()
+            .filter(|process| process.status.is_running())
+            .collect::<Vec<_>>();
+        let since = now
+            .checked_sub(Duration::from_secs(self.window_mins() * 60))
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+        let mut sessions = projects
+            .iter()
+            .flat_map(|project| {
+                project
+                    .sessions
+                    .iter()
+                    .map(move |session| (project, session))
+            })
+            .filter_map(|(project, session)| {
+                let modified = session
+                    .file_modified
+                    .filter(|modified| *modified >= since)?;
+                let is_listed = running.iter().any(|process| {
+                    process.session_log_path.as_deref() == Some(session.log_path.as_path())
+                }) || external.iter().any(|external| {
+                    external
+                        .session
+                        .as_ref()
+                        .is_some_and(|listed| listed.log_path == session.log_path)
+                });
+                (!is_listed).then_some((modified, project, session))
+            })
+            .collect::<Vec<_>>();
+        sessions.sort_by_key(|(modified, _, _)| std::cmp::Reverse(*modified));
+
+        self.rows = running
+            .iter()
+            .map(|process| ActivityRow::Process {
+                process_id: process.id.clone(),
+            })
+            .chain(external.iter().map(|external| ActivityRow::External {
+                pid: external.agent.pid,
+            }))
+            .chain(
+                sessions
+                    .into_iter()
+                    .map(|(_, project, session)| ActivityRow::Session {
+                        project_path: project.project_path.clone(),
+                        log_path: session.log_path.clone(),
+                    }),
+            )
+            .collect();
+        self.as_of = now;
+        self.selected = selected
+            .and_then(|selected| self.rows.iter().position(|row| *row == selected))
+            .unwrap_or(self.selected)
+            .min(self.rows.len().saturating_sub(1));
+    }
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum ProcessOutputKind {
     Stdout,
@@ -1824,7 +3464,75 @@ pub struct ProcessOutputView {
     pub file_path: PathBuf,
     pub buffer: Arc<String>,
     pub file_offset: u64,
+    /// First buffer line shown; ignored while `follow` keeps the tail in view.
     pub scroll: u16,
+    /// Show escape sequences as text instead of rendering their colors (`r`).
+    pub raw: bool,
+    /// Keep the last lines in view as output arrives (`f`).
+    pub follow: bool,
+    /// Applied search query (`/`); `n`/`N` jump between matching lines.
+    pub search: Option<String>,
+    /// Set while typing the search query.
+    pub search_input: Option<String>,
+    /// Set while typing where to save the whole output (`w`).
+    pub export_input: Option<String>,
+}
+
+impl ProcessOutputView {
+    /// Buffer lines containing the search query, ignoring case.
+    pub fn search_matches(&self) -> Vec<usize> {
+        let Some(query) = self.search.as_deref().map(str::to_lowercase) else {
+            return Vec::new();
+        };
+        self.buffer
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| line.to_lowercase().contains(&query))
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Scrolls up, leaving follow mode from the tail so the view does not jump.
+    pub fn scroll_up(&mut self, lines: usize, page: usize) {
+        if self.follow {
+            self.follow = false;
+            self.scroll = usize_to_u16(self.buffer.lines().count().saturating_sub(page));
+        }
+        self.scroll = self.scroll.saturating_sub(usize_to_u16(lines));
+    }
+
+    pub fn scroll_down(&mut self, lines: usize) {
+        if self.follow {
+            return;
+        }
+        let last = self.buffer.lines().count().saturating_sub(1);
+        self.scroll = usize_to_u16((self.scroll as usize + lines).min(last));
+    }
+
+    /// Scrolls to the next (or previous) match after the top line, wrapping around.
+    fn jump_to_match(&mut self, forward: bool) -> bool {
+        let matches = self.search_matches();
+        let top = self.scroll as usize;
+        let target = if forward {
+            matches.iter().find(|line| **line > top).or(matches.first())
+        } else {
+            matches
+                .iter()
+                .rev()
+                .find(|line| **line < top)
+                .or(matches.last())
+        };
+        let Some(line) = target.copied() else {
+            return false;
+        };
+        self.follow = false;
+        self.scroll = usize_to_u16(line);
+        true
+    }
+}
+
+fn usize_to_u16(value: usize) -> u16 {
+    u16::try_from(value).unwrap_or(u16::MAX)
 }
 
 #[derive(Clone, Debug)]
@@ -1839,6 +3547,8 @@ pub enum AppCommand {
     None,
     Quit,
     Rescan,
+    /// Recompute filesystem completions for the open path picker.
+    RefreshPathPicker,
     OpenTasks {
         return_to: Box<View>,
     },
@@ -1867,22 +3577,109 @@ pub enum AppCommand {
     SpawnTask {
         engine: AgentEngine,
         task_id: TaskId,
+        worktree: bool,
+        agent_model: Option<String>,
+    },
+    ScheduleTask {
+        from_tasks: TasksView,
+        task_id: TaskId,
+        engine: AgentEngine,
+        spec: ScheduleSpec,
+    },
+    CancelTaskSchedule {
+        from_tasks: TasksView,
+        task_id: TaskId,
+    },
+    /// Adds an image file to the New Task or New Session prompt as `[Image N]`.
+    InsertPromptImage {
+        path: PathBuf,
+    },
+    /// Spawns the same prompt on two engines; the sessions are compared once both finish.
+    SpawnAgentComparison {
+        engines: [AgentEngine; 2],
+        project_path: PathBuf,
+        prompt: String,
+        /// Passed to engines that take image inputs; the prompt already lists them.
+        image_paths: Vec<PathBuf>,
+        io_mode: SpawnIoMode,
+        retry: RetryPolicy,
+        worktree: bool,
+        /// Model of `engines[0]`; the other engine runs its default.
+        agent_model: Option<String>,
+    },
+    SpawnTaskComparison {
+        engines: [AgentEngine; 2],
+        task_id: TaskId,
+        worktree: bool,
+        /// Model of `engines[0]`; the other engine runs its default.
+        agent_model: Option<String>,
+    },
+    /// Spawns the same prompt in each project; the processes share a batch number.
+    SpawnAgentBatch {
+        engine: AgentEngine,
+        project_paths: Vec<PathBuf>,
+        prompt: String,
+        /// Passed to engines that take image inputs; the prompt already lists them.
+        image_paths: Vec<PathBuf>,
+        io_mode: SpawnIoMode,
+        retry: RetryPolicy,
+        worktree: bool,
+        agent_model: Option<String>,
+    },
+    /// Reads a text file into the New Session prompt at the cursor.
+    NewSessionInsertFile {
+        path: PathBuf,
     },
-    TaskCreateInsertImage {
+    /// Attaches a project file (relative to the New Session project) as `@path`.
+    NewSessionAttachFile {
         path: PathBuf,
     },
-    TaskCreatePasteImageFromClipboard,
+    /// Saves the clipboard image and adds it to the New Task or New Session prompt.
+    PastePromptImageFromClipboard,
+    /// `what` names the copied text in the confirmation notice.
+    CopyToClipboard {
+        text: String,
+        what: String,
+    },
+    /// Writes the whole output file of a process (not just the loaded tail) to `destination`,
+    /// or copies it to the clipboard when `None`.
+    ExportProcessOutput {
+        process_id: String,
+        kind: ProcessOutputKind,
+        destination: Option<PathBuf>,
+    },
+    /// Stores the labels just set on these processes in their records.
+    RecordProcessLabels {
+        process_ids: Vec<String>,
+    },
     OpenSessionDetail {
         from_sessions: SessionsView,
         session: SessionSummary,
     },
+    /// Launches the configured `ide_command` on the project directory, detached from the TUI.
+    OpenProjectInIde {
+        project_path: PathBuf,
+    },
+    /// Suspends the TUI and opens the session's raw log in `$VISUAL`/`$EDITOR`.
+    OpenInEditor {
+        session: SessionSummary,
+    },
     OpenSessionStats {
         session: SessionSummary,
     },
+    OpenSessionDiff {
+        a: SessionSummary,
+        b: SessionSummary,
+    },
     OpenSessionDetailByLogPath {
         project_path: PathBuf,
         log_path: PathBuf,
     },
+    /// Shows the first session from `index` on that has output; Space moves to the next one.
+    OpenProjectResultPreview {
+        sessions: Vec<SessionSummary>,
+        index: usize,
+    },
     OpenSessionResultPreview {
         session: SessionSummary,
     },
@@ -1894,6 +3691,10 @@ pub enum AppCommand {
         session: SessionSummary,
         project_path: Option<PathBuf>,
     },
+    WriteBackOpenCode {
+        session: SessionSummary,
+        change: OpenCodeWriteBack,
+    },
     DeleteProjectLogs {
         project_path: PathBuf,
     },
@@ -1910,15 +3711,55 @@ pub enum AppCommand {
         engine: AgentEngine,
         project_path: PathBuf,
         prompt: String,
+        /// Passed to engines that take image inputs; the prompt already lists them.
+        image_paths: Vec<PathBuf>,
         io_mode: SpawnIoMode,
+        retry: RetryPolicy,
+        worktree: bool,
+        agent_model: Option<String>,
     },
     ForkResumeCodexFromTimeline {
         fork: ForkContext,
         prompt: String,
     },
+    ResumeOpenCodeSession {
+        session: SessionSummary,
+        prompt: String,
+    },
+    /// `k` on a group header in Processes: every running process with `label`.
+    KillProcessGroup {
+        label: String,
+        process_ids: Vec<String>,
+        force: bool,
+    },
+    /// `k` stops the process (SIGTERM, then SIGKILL after the grace period); `K` (`force`)
+    /// kills it right away.
     KillProcess {
         process_id: String,
+        force: bool,
+    },
+    /// Types `text` and Enter into a TTY process without attaching (`r` in Processes).
+    WriteProcessTty {
+        process_id: String,
+        text: String,
+    },
+    OpenAuditLog,
+    OpenDevices,
+    SetDeviceRevoked {
+        device_id: String,
+        revoked: bool,
     },
+    /// Removes the device from the relay store so it has to pair again.
+    ForgetDevice {
+        device_id: String,
+    },
+    /// Registers this machine with the relay and requests a pairing code.
+    PairDevice,
+    OpenQuickSwitcher,
+    LoadProjectSkills {
+        project_path: PathBuf,
+    },
+    ReloadConfig,
     OpenProcessOutput {
         process_id: String,
         kind: ProcessOutputKind,
@@ -1929,11 +3770,47 @@ pub enum AppCommand {
 }
 
 pub fn update(model: AppModel, event: AppEvent) -> (AppModel, AppCommand) {
-    match event {
+    let (model, command) = match event {
         AppEvent::Key(key) => update_on_key(model, key),
         AppEvent::Paste(text) => update_on_paste(model, text),
         AppEvent::Mouse(mouse) => mouse::update_on_mouse(model, mouse),
+    };
+    let (model, command) = reject_remote_session_changes(model, command);
+    (sync_engine_filter_with_view(model), command)
+}
+
+/// Remote sessions are mirrored read-only: commands that would change, delete, resume or spawn
+/// in them are dropped with a notice.
+fn reject_remote_session_changes(model: AppModel, command: AppCommand) -> (AppModel, AppCommand) {
+    let data = &model.data;
+    let is_remote_change = match &command {
+        AppCommand::MoveSessionProject { session, .. }
+        | AppCommand::WriteBackOpenCode { session, .. }
+        | AppCommand::ResumeOpenCodeSession { session, .. } => session.remote.is_some(),
+        AppCommand::DeleteProjectLogs { project_path }
+        | AppCommand::OpenProjectInIde { project_path }
+        | AppCommand::SpawnAgentSession { project_path, .. }
+        | AppCommand::SpawnAgentComparison { project_path, .. }
+        | AppCommand::CreateTask { project_path, .. } => data.is_remote_project(project_path),
+        AppCommand::DeleteProjectLogsBatch { project_paths }
+        | AppCommand::SpawnAgentBatch { project_paths, .. } => project_paths
+            .iter()
+            .any(|project_path| data.is_remote_project(project_path)),
+        AppCommand::DeleteSessionLog { log_path } => data.is_remote_log(log_path),
+        AppCommand::DeleteSessionLogsBatch { log_paths } => log_paths
+            .iter()
+            .any(|log_path| data.is_remote_log(log_path)),
+        AppCommand::ForkResumeCodexFromTimeline { fork, .. } => {
+            data.is_remote_log(&fork.parent_log_path)
+        }
+        _ => false,
+    };
+    if !is_remote_change {
+        return (model, command);
     }
+    let mut model = model;
+    model.notice = Some("Remote sessions are read-only.".to_string());
+    (model, AppCommand::None)
 }
 
 fn update_on_key(model: AppModel, key: KeyEvent) -> (AppModel, AppCommand) {
@@ -1953,6 +3830,9 @@ fn update_on_key(model: AppModel, key: KeyEvent) -> (AppModel, AppCommand) {
     if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('r') {
         return (model, AppCommand::Rescan);
     }
+    if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('g') {
+        return (model, AppCommand::ReloadConfig);
+    }
 
     if command_modifier && matches!(key.code, KeyCode::Char('1')) {
         if model.help_open
@@ -1964,16 +3844,29 @@ fn update_on_key(model: AppModel, key: KeyEvent) -> (AppModel, AppCommand) {
             || model.delete_task_confirm.is_some()
             || model.delete_tasks_confirm.is_some()
             || model.session_rename.is_some()
+            || model.project_rename.is_some()
             || model.session_move.is_some()
+            || model.opencode_write_back.is_some()
             || model.session_result_preview.is_some()
             || model.session_stats_overlay.is_some()
             || model.project_stats_overlay.is_some()
+            || model.audit_log_overlay.is_some()
+            || model.devices_overlay.is_some()
+            || model.session_diff_overlay.is_some()
+            || model.recent_sessions_overlay.is_some()
+            || model.quick_switcher.is_some()
         {
             return (model, AppCommand::None);
         }
 
         let mut view = ProjectsView::new(&model.data.projects);
-        apply_project_filter(&model.data.projects, &mut view, model.engine_filter);
+        apply_project_filter(
+            &model.data.projects,
+            &mut view,
+            model.engine_filter,
+            &model.session_index,
+            model.projects_order,
+        );
         model.view = View::Projects(view);
         model.help_open = false;
         model.system_menu = None;
@@ -1992,6 +3885,11 @@ fn update_on_key(model: AppModel, key: KeyEvent) -> (AppModel, AppCommand) {
             || model.session_result_preview.is_some()
             || model.session_stats_overlay.is_some()
             || model.project_stats_overlay.is_some()
+            || model.audit_log_overlay.is_some()
+            || model.devices_overlay.is_some()
+            || model.session_diff_overlay.is_some()
+            || model.recent_sessions_overlay.is_some()
+            || model.quick_switcher.is_some()
         {
             return (model, AppCommand::None);
         }
@@ -2016,7 +3914,7 @@ fn update_on_key(model: AppModel, key: KeyEvent) -> (AppModel, AppCommand) {
         return (model, AppCommand::None);
     }
 
-    if command_modifier && matches!(key.code, KeyCode::Char('3')) {
+    if command_modifier && matches!(key.code, KeyCode::Char('5')) {
         if model.help_open
             || model.system_menu.is_some()
             || model.delete_confirm.is_some()
@@ -2028,19 +3926,85 @@ fn update_on_key(model: AppModel, key: KeyEvent) -> (AppModel, AppCommand) {
             || model.session_result_preview.is_some()
             || model.session_stats_overlay.is_some()
             || model.project_stats_overlay.is_some()
+            || model.audit_log_overlay.is_some()
+            || model.devices_overlay.is_some()
+            || model.session_diff_overlay.is_some()
+            || model.recent_sessions_overlay.is_some()
+            || model.quick_switcher.is_some()
         {
             return (model, AppCommand::None);
         }
 
-        if !matches!(&model.view, View::Processes(_)) {
-            open_processes_view(&mut model);
+        if !matches!(&model.view, View::AllSessions(_)) {
+            let mut view = AllSessionsView::default();
+            apply_all_sessions_filter(&model.data.projects, &mut view, model.engine_filter);
+            model.view = View::AllSessions(view);
         }
         model.help_open = false;
         model.system_menu = None;
         return (model, AppCommand::None);
     }
 
-    if command_modifier && matches!(key.code, KeyCode::Char('4')) {
+    if command_modifier && matches!(key.code, KeyCode::Char('3')) {
+        if model.help_open
+            || model.system_menu.is_some()
+            || model.delete_confirm.is_some()
+            || model.delete_projects_confirm.is_some()
+            || model.delete_session_confirm.is_some()
+            || model.delete_sessions_confirm.is_some()
+            || model.delete_task_confirm.is_some()
+            || model.delete_tasks_confirm.is_some()
+            || model.session_result_preview.is_some()
+            || model.session_stats_overlay.is_some()
+            || model.project_stats_overlay.is_some()
+            || model.audit_log_overlay.is_some()
+            || model.devices_overlay.is_some()
+            || model.session_diff_overlay.is_some()
+            || model.recent_sessions_overlay.is_some()
+            || model.quick_switcher.is_some()
+        {
+            return (model, AppCommand::None);
+        }
+
+        if !matches!(&model.view, View::Processes(_)) {
+            open_processes_view(&mut model);
+        }
+        model.help_open = false;
+        model.system_menu = None;
+        return (model, AppCommand::None);
+    }
+
+    if command_modifier && matches!(key.code, KeyCode::Char('6')) {
+        if model.help_open
+            || model.system_menu.is_some()
+            || model.delete_confirm.is_some()
+            || model.delete_projects_confirm.is_some()
+            || model.delete_session_confirm.is_some()
+            || model.delete_sessions_confirm.is_some()
+            || model.delete_task_confirm.is_some()
+            || model.delete_tasks_confirm.is_some()
+            || model.session_result_preview.is_some()
+            || model.session_stats_overlay.is_some()
+            || model.project_stats_overlay.is_some()
+            || model.audit_log_overlay.is_some()
+            || model.devices_overlay.is_some()
+            || model.session_diff_overlay.is_some()
+            || model.recent_sessions_overlay.is_some()
+            || model.quick_switcher.is_some()
+        {
+            return (model, AppCommand::None);
+        }
+
+        if !matches!(&model.view, View::Activity(_)) {
+            let return_to = Box::new(model.view.clone());
+            model.view = View::Activity(ActivityView::new(return_to));
+        }
+        model.help_open = false;
+        model.system_menu = None;
+        return (model, AppCommand::None);
+    }
+
+    if command_modifier && matches!(key.code, KeyCode::Char('4')) {
         if model.help_open
             || model.system_menu.is_some()
             || model.delete_confirm.is_some()
@@ -2052,6 +4016,11 @@ fn update_on_key(model: AppModel, key: KeyEvent) -> (AppModel, AppCommand) {
             || model.session_result_preview.is_some()
             || model.session_stats_overlay.is_some()
             || model.project_stats_overlay.is_some()
+            || model.audit_log_overlay.is_some()
+            || model.devices_overlay.is_some()
+            || model.session_diff_overlay.is_some()
+            || model.recent_sessions_overlay.is_some()
+            || model.quick_switcher.is_some()
         {
             return (model, AppCommand::None);
         }
@@ -2077,6 +4046,66 @@ fn update_on_key(model: AppModel, key: KeyEvent) -> (AppModel, AppCommand) {
         return (model, AppCommand::None);
     }
 
+    if command_modifier && matches!(key.code, KeyCode::Char('l') | KeyCode::Char('L')) {
+        if model.help_open
+            || model.system_menu.is_some()
+            || model.delete_confirm.is_some()
+            || model.delete_projects_confirm.is_some()
+            || model.delete_session_confirm.is_some()
+            || model.delete_sessions_confirm.is_some()
+            || model.delete_task_confirm.is_some()
+            || model.delete_tasks_confirm.is_some()
+            || model.session_rename.is_some()
+            || model.project_rename.is_some()
+            || model.session_move.is_some()
+            || model.opencode_write_back.is_some()
+            || model.session_result_preview.is_some()
+            || model.session_stats_overlay.is_some()
+            || model.project_stats_overlay.is_some()
+            || model.audit_log_overlay.is_some()
+            || model.devices_overlay.is_some()
+            || model.session_diff_overlay.is_some()
+            || model.recent_sessions_overlay.is_some()
+            || model.quick_switcher.is_some()
+        {
+            return (model, AppCommand::None);
+        }
+
+        model.help_open = false;
+        model.system_menu = None;
+        return (model, AppCommand::OpenAuditLog);
+    }
+
+    if command_modifier && matches!(key.code, KeyCode::Char('b') | KeyCode::Char('B')) {
+        if model.help_open
+            || model.system_menu.is_some()
+            || model.delete_confirm.is_some()
+            || model.delete_projects_confirm.is_some()
+            || model.delete_session_confirm.is_some()
+            || model.delete_sessions_confirm.is_some()
+            || model.delete_task_confirm.is_some()
+            || model.delete_tasks_confirm.is_some()
+            || model.session_rename.is_some()
+            || model.project_rename.is_some()
+            || model.session_move.is_some()
+            || model.opencode_write_back.is_some()
+            || model.session_result_preview.is_some()
+            || model.session_stats_overlay.is_some()
+            || model.project_stats_overlay.is_some()
+            || model.audit_log_overlay.is_some()
+            || model.devices_overlay.is_some()
+            || model.session_diff_overlay.is_some()
+            || model.recent_sessions_overlay.is_some()
+            || model.quick_switcher.is_some()
+        {
+            return (model, AppCommand::None);
+        }
+
+        model.help_open = false;
+        model.system_menu = None;
+        return (model, AppCommand::OpenDevices);
+    }
+
     if command_modifier && matches!(key.code, KeyCode::Char('d') | KeyCode::Char('D')) {
         if model.help_open
             || model.system_menu.is_some()
@@ -2089,6 +4118,11 @@ fn update_on_key(model: AppModel, key: KeyEvent) -> (AppModel, AppCommand) {
             || model.session_result_preview.is_some()
             || model.session_stats_overlay.is_some()
             || model.project_stats_overlay.is_some()
+            || model.audit_log_overlay.is_some()
+            || model.devices_overlay.is_some()
+            || model.session_diff_overlay.is_some()
+            || model.recent_sessions_overlay.is_some()
+            || model.quick_switcher.is_some()
         {
             return (model, AppCommand::None);
         }
@@ -2126,11 +4160,31 @@ fn update_on_key(model: AppModel, key: KeyEvent) -> (AppModel, AppCommand) {
             || model.session_result_preview.is_some()
             || model.session_stats_overlay.is_some()
             || model.project_stats_overlay.is_some()
+            || model.audit_log_overlay.is_some()
+            || model.devices_overlay.is_some()
+            || model.session_diff_overlay.is_some()
+            || model.recent_sessions_overlay.is_some()
+            || model.quick_switcher.is_some()
         {
             return (model, AppCommand::None);
         }
 
         match model.view.clone() {
+            View::Projects(projects_view) if projects_view.selected_project_paths.len() > 1 => {
+                let project_paths: Vec<PathBuf> = projects_view
+                    .selected_project_paths
+                    .iter()
+                    .cloned()
+                    .collect();
+                let sessions_view = SessionsView::new(project_paths[0].clone(), 0);
+                let mut new_session_view = NewSessionView::new(sessions_view);
+                new_session_view.engine = default_new_session_engine(model.engine_filter, None);
+                new_session_view.batch = Some(BatchSpawn {
+                    from_projects: projects_view,
+                    project_paths,
+                });
+                model.view = View::NewSession(new_session_view);
+            }
             View::Projects(projects_view) => {
                 let Some(project_index) = projects_view
                     .filtered_indices
@@ -2167,6 +4221,19 @@ fn update_on_key(model: AppModel, key: KeyEvent) -> (AppModel, AppCommand) {
                 new_session_view.engine = engine;
                 model.view = View::NewSession(new_session_view);
             }
+            View::AllSessions(all_sessions_view) => {
+                let Some((project, session)) =
+                    all_sessions_view.selected_session(&model.data.projects)
+                else {
+                    model.notice = Some("No session selected.".to_string());
+                    return (model, AppCommand::None);
+                };
+                let sessions_view = sessions_view_selecting(&model, project, &session.log_path);
+                let engine = default_new_session_engine(model.engine_filter, Some(project));
+                let mut new_session_view = NewSessionView::new(sessions_view);
+                new_session_view.engine = engine;
+                model.view = View::NewSession(new_session_view);
+            }
             View::SessionDetail(detail_view) => {
                 let sessions_view = detail_view.from_sessions;
                 let engine = default_new_session_engine(
@@ -2249,7 +4316,7 @@ fn update_on_key(model: AppModel, key: KeyEvent) -> (AppModel, AppCommand) {
                 model.view = View::NewSession(new_session_view);
             }
             View::NewSession(_) => {}
-            View::Processes(_) | View::ProcessOutput(_) | View::Error => {
+            View::Processes(_) | View::ProcessOutput(_) | View::Activity(_) | View::Error => {
                 model.notice = Some("Open a project to start a new session.".to_string());
                 return (model, AppCommand::None);
             }
@@ -2272,6 +4339,11 @@ fn update_on_key(model: AppModel, key: KeyEvent) -> (AppModel, AppCommand) {
             || model.session_result_preview.is_some()
             || model.session_stats_overlay.is_some()
             || model.project_stats_overlay.is_some()
+            || model.audit_log_overlay.is_some()
+            || model.devices_overlay.is_some()
+            || model.session_diff_overlay.is_some()
+            || model.recent_sessions_overlay.is_some()
+            || model.quick_switcher.is_some()
         {
             return (model, AppCommand::None);
         }
@@ -2315,10 +4387,17 @@ fn update_on_key(model: AppModel, key: KeyEvent) -> (AppModel, AppCommand) {
             || model.delete_task_confirm.is_some()
             || model.delete_tasks_confirm.is_some()
             || model.session_rename.is_some()
+            || model.project_rename.is_some()
             || model.session_move.is_some()
+            || model.opencode_write_back.is_some()
             || model.session_result_preview.is_some()
             || model.session_stats_overlay.is_some()
             || model.project_stats_overlay.is_some()
+            || model.audit_log_overlay.is_some()
+            || model.devices_overlay.is_some()
+            || model.session_diff_overlay.is_some()
+            || model.recent_sessions_overlay.is_some()
+            || model.quick_switcher.is_some()
         {
             return (model, AppCommand::None);
         }
@@ -2339,6 +4418,26 @@ fn update_on_key(model: AppModel, key: KeyEvent) -> (AppModel, AppCommand) {
         return update_system_menu_overlay(model, menu, key);
     }
 
+    if let Some(overlay) = model.audit_log_overlay.take() {
+        return update_audit_log_overlay(model, overlay, key);
+    }
+
+    if let Some(overlay) = model.devices_overlay.take() {
+        return update_devices_overlay(model, overlay, key);
+    }
+
+    if let Some(overlay) = model.session_diff_overlay.take() {
+        return update_session_diff_overlay(model, overlay, key);
+    }
+
+    if let Some(overlay) = model.recent_sessions_overlay.take() {
+        return update_recent_sessions_overlay(model, overlay, key);
+    }
+
+    if let Some(overlay) = model.quick_switcher.take() {
+        return update_quick_switcher(model, overlay, key);
+    }
+
     if let Some(overlay) = model.project_stats_overlay.take() {
         return update_project_stats_overlay(model, overlay, key);
     }
@@ -2359,6 +4458,10 @@ fn update_on_key(model: AppModel, key: KeyEvent) -> (AppModel, AppCommand) {
         return update_delete_session_confirm(model, confirm, key);
     }
 
+    if let Some(dialog) = model.project_rename.take() {
+        return update_project_rename_dialog(model, dialog, key);
+    }
+
     if let Some(dialog) = model.session_rename.take() {
         return update_session_rename_dialog(model, dialog, key);
     }
@@ -2367,6 +4470,10 @@ fn update_on_key(model: AppModel, key: KeyEvent) -> (AppModel, AppCommand) {
         return update_session_move_dialog(model, dialog, key);
     }
 
+    if let Some(confirm) = model.opencode_write_back.take() {
+        return update_opencode_write_back_confirm(model, confirm, key);
+    }
+
     if let Some(confirm) = model.delete_tasks_confirm.take() {
         return update_delete_tasks_confirm(model, confirm, key);
     }
@@ -2383,6 +4490,24 @@ fn update_on_key(model: AppModel, key: KeyEvent) -> (AppModel, AppCommand) {
         return update_delete_confirm(model, confirm, key);
     }
 
+    // Typing a process label or answering the kill dialog must not trigger `?`, `P` or the
+    // function keys.
+    if let View::Processes(processes_view) = &model.view
+        && (processes_view.label_input.is_some()
+            || processes_view.kill_project_confirm.is_some()
+            || processes_view.export_input.is_some()
+            || processes_view.reply_input.is_some())
+    {
+        let processes_view = processes_view.clone();
+        return update_processes(model, processes_view, key);
+    }
+    if let View::ProcessOutput(output_view) = &model.view
+        && (output_view.search_input.is_some() || output_view.export_input.is_some())
+    {
+        let output_view = output_view.clone();
+        return update_process_output(model, output_view, key);
+    }
+
     if matches!(key.code, KeyCode::F(1) | KeyCode::Char('?')) {
         model.help_open = !model.help_open;
         return (model, AppCommand::None);
@@ -2398,6 +4523,67 @@ fn update_on_key(model: AppModel, key: KeyEvent) -> (AppModel, AppCommand) {
         }
     }
 
+    if key.code == KeyCode::F(7) {
+        if model.session_history.recent().is_empty() {
+            model.notice = Some("No recently viewed sessions.".to_string());
+        } else {
+            model.recent_sessions_overlay = Some(RecentSessionsOverlay { selected: 0 });
+        }
+        return (model, AppCommand::None);
+    }
+
+    if key.code == KeyCode::F(9) {
+        return (model, AppCommand::OpenQuickSwitcher);
+    }
+
+    if key.code == KeyCode::F(12) {
+        let Some(process) = awaiting_approval(&model.processes).into_iter().next() else {
+            model.notice = Some("No process is waiting for approval.".to_string());
+            return (model, AppCommand::None);
+        };
+        if !process.io_mode.is_tty() {
+            let notice = format!(
+                "{} runs without a TTY; approve it where its agent runs.",
+                process.id
+            );
+            model.notice = Some(notice);
+            return (model, AppCommand::None);
+        }
+        let process_id = process.id.clone();
+        return (model, AppCommand::AttachProcessTty { process_id });
+    }
+
+    if key.modifiers.contains(KeyModifiers::ALT)
+        && matches!(key.code, KeyCode::Left | KeyCode::Right)
+    {
+        let showing = match &model.view {
+            View::SessionDetail(detail_view) => Some(detail_view.session.log_path.clone()),
+            _ => None,
+        };
+        let (entry, notice) = if key.code == KeyCode::Left {
+            (
+                model.session_history.back(showing.as_deref()),
+                "No earlier session in history.",
+            )
+        } else {
+            (
+                model.session_history.forward(),
+                "No later session in history.",
+            )
+        };
+        let Some(entry) = entry else {
+            model.notice = Some(notice.to_string());
+            return (model, AppCommand::None);
+        };
+        return (
+            model,
+            AppCommand::OpenSessionDetail {
+                from_sessions: entry.from_sessions,
+                session: entry.session,
+            },
+        );
+    }
+
     if key.code == KeyCode::Char('P')
         && !matches!(&model.view, View::Processes(_) | View::ProcessOutput(_))
     {
@@ -2409,12 +4595,14 @@ fn update_on_key(model: AppModel, key: KeyEvent) -> (AppModel, AppCommand) {
     match view {
         View::Projects(projects_view) => update_projects(model, projects_view, key),
         View::Sessions(sessions_view) => update_sessions(model, sessions_view, key),
+        View::AllSessions(all_sessions_view) => update_all_sessions(model, all_sessions_view, key),
         View::NewSession(new_session_view) => update_new_session(model, new_session_view, key),
         View::SessionDetail(detail_view) => update_session_detail(model, detail_view, key),
         View::Tasks(tasks_view) => update_tasks(model, tasks_view, key),
         View::TaskCreate(task_create_view) => update_task_create(model, task_create_view, key),
         View::TaskDetail(task_detail_view) => update_task_detail(model, task_detail_view, key),
         View::Processes(processes_view) => update_processes(model, processes_view, key),
+        View::Activity(activity_view) => update_activity(model, activity_view, key),
         View::ProcessOutput(output_view) => update_process_output(model, output_view, key),
         View::Error => update_error(model, key),
     }
@@ -2438,6 +4626,10 @@ fn infer_sessions_view_for_window_menu_view(view: &View, model: &AppModel) -> Op
             ))
         }
         View::Sessions(sessions_view) => Some(sessions_view.clone()),
+        View::AllSessions(all_sessions_view) => {
+            let (project, session) = all_sessions_view.selected_session(&model.data.projects)?;
+            Some(sessions_view_selecting(model, project, &session.log_path))
+        }
         View::NewSession(new_session_view) => Some(new_session_view.from_sessions.clone()),
         View::SessionDetail(detail_view) => Some(detail_view.from_sessions.clone()),
         View::Tasks(tasks_view) => {
@@ -2457,6 +4649,9 @@ fn infer_sessions_view_for_window_menu_view(view: &View, model: &AppModel) -> Op
         View::ProcessOutput(output_view) => {
             infer_sessions_view_for_window_menu_view(output_view.return_to.as_ref(), model)
         }
+        View::Activity(activity_view) => {
+            infer_sessions_view_for_window_menu_view(activity_view.return_to.as_ref(), model)
+        }
         View::Error => None,
     }
 }
@@ -2519,6 +4714,13 @@ fn infer_session_detail_target_view(
             let session = project.sessions.get(selected_index).cloned()?;
             Some((sessions_view.clone(), session))
         }
+        View::AllSessions(all_sessions_view) => {
+            let (project, session) = all_sessions_view.selected_session(&model.data.projects)?;
+            Some((
+                sessions_view_selecting(model, project, &session.log_path),
+                session.clone(),
+            ))
+        }
         View::NewSession(new_session_view) => {
             let from_sessions = new_session_view.from_sessions.clone();
             let project = from_sessions.current_project(&model.data.projects)?;
@@ -2548,6 +4750,9 @@ fn infer_session_detail_target_view(
         View::ProcessOutput(output_view) => {
             infer_session_detail_target_view(output_view.return_to.as_ref(), model)
         }
+        View::Activity(activity_view) => {
+            infer_session_detail_target_view(activity_view.return_to.as_ref(), model)
+        }
         View::Error => None,
     }
 }
@@ -2574,8 +4779,12 @@ fn infer_task_detail_target_view(view: &View) -> Option<(TasksView, TaskId)> {
         View::ProcessOutput(output_view) => {
             infer_task_detail_target_view(output_view.return_to.as_ref())
         }
+        View::Activity(activity_view) => {
+            infer_task_detail_target_view(activity_view.return_to.as_ref())
+        }
         View::Projects(_)
         | View::Sessions(_)
+        | View::AllSessions(_)
         | View::NewSession(_)
         | View::SessionDetail(_)
         | View::Error => None,
@@ -2593,7 +4802,33 @@ fn engine_filter_from_engine_menu_index(index: usize) -> Option<EngineFilter> {
     }
 }
 
+/// Picks `filter` for the current view and remembers it in `engine_prefs`.
 fn apply_engine_filter(mut model: AppModel, filter: EngineFilter) -> AppModel {
+    match &model.view {
+        View::Projects(_) => model.engine_prefs.projects = filter.engine(),
+        View::Sessions(view) => {
+            let project_path = view.project_path.clone();
+            model
+                .engine_prefs
+                .set_sessions(&project_path, filter.engine());
+        }
+        _ => {}
+    }
+    set_active_engine_filter(model, filter)
+}
+
+/// Switches to the remembered filter when the view changed between Projects and Sessions (or
+/// to another project's Sessions).
+fn sync_engine_filter_with_view(model: AppModel) -> AppModel {
+    let engine = match &model.view {
+        View::Projects(_) => model.engine_prefs.projects,
+        View::Sessions(view) => model.engine_prefs.sessions_for(&view.project_path),
+        _ => return model,
+    };
+    set_active_engine_filter(model, EngineFilter::from_engine(engine))
+}
+
+fn set_active_engine_filter(mut model: AppModel, filter: EngineFilter) -> AppModel {
     if model.engine_filter == filter {
         return model;
     }
@@ -2601,7 +4836,13 @@ fn apply_engine_filter(mut model: AppModel, filter: EngineFilter) -> AppModel {
 
     match model.view.clone() {
         View::Projects(mut view) => {
-            apply_project_filter(&model.data.projects, &mut view, model.engine_filter);
+            apply_project_filter(
+                &model.data.projects,
+                &mut view,
+                model.engine_filter,
+                &model.session_index,
+                model.projects_order,
+            );
             clear_project_selection(&mut view);
             model.view = View::Projects(view);
         }
@@ -2620,6 +4861,10 @@ fn apply_engine_filter(mut model: AppModel, filter: EngineFilter) -> AppModel {
             clear_sessions_selection(&mut view);
             model.view = View::Sessions(view);
         }
+        View::AllSessions(mut view) => {
+            apply_all_sessions_filter(&model.data.projects, &mut view, model.engine_filter);
+            model.view = View::AllSessions(view);
+        }
         _ => {}
     }
 
@@ -2629,8 +4874,17 @@ fn apply_engine_filter(mut model: AppModel, filter: EngineFilter) -> AppModel {
 fn apply_session_index_update(mut model: AppModel, index: Arc<SessionIndex>) -> AppModel {
     model.session_index = index;
 
+    if let View::Projects(mut view) = model.view.clone() {
+        if model.projects_order == ProjectsOrder::MostTokens {
+            resort_projects(&mut model, &mut view);
+            model.view = View::Projects(view);
+        }
+        return model;
+    }
+
     if let View::Sessions(mut view) = model.view.clone() {
-        if !view.tool_errors_only && view.order != SessionsOrder::ToolErrorsFirst {
+        let searches_content = view.search_content && !view.query.trim().is_empty();
+        if !view.tool_errors_only && !view.order.is_index_backed() && !searches_content {
             return model;
         }
 
@@ -2812,9 +5066,8 @@ fn apply_window_menu_open_output(
     let mut model = model;
 
     let process_id = match &model.view {
-        View::Processes(processes_view) => model
-            .processes
-            .get(processes_view.selected)
+        View::Processes(processes_view) => processes_view
+            .selected_process(&model.processes)
             .map(|process| process.id.clone()),
         View::ProcessOutput(output_view) => Some(output_view.process_id.clone()),
         _ => None,
@@ -2830,10 +5083,7 @@ fn apply_window_menu_open_output(
 
 fn open_processes_view(model: &mut AppModel) {
     let return_to = model.view.clone();
-    model.view = View::Processes(ProcessesView {
-        return_to: Box::new(return_to),
-        selected: 0,
-    });
+    model.view = View::Processes(ProcessesView::new(Box::new(return_to), 0));
 }
 
 fn update_on_paste(model: AppModel, text: String) -> (AppModel, AppCommand) {
@@ -2861,20 +5111,85 @@ fn update_on_paste(model: AppModel, text: String) -> (AppModel, AppCommand) {
     if model.project_stats_overlay.is_some() {
         return (model, AppCommand::None);
     }
+    if model.audit_log_overlay.is_some() {
+        return (model, AppCommand::None);
+    }
+    if model.devices_overlay.is_some() {
+        return (model, AppCommand::None);
+    }
+    if model.session_diff_overlay.is_some() {
+        return (model, AppCommand::None);
+    }
+    if model.recent_sessions_overlay.is_some() {
+        return (model, AppCommand::None);
+    }
+    if let Some(mut overlay) = model.quick_switcher.take() {
+        overlay.query.push_str(text.trim());
+        overlay.selected = 0;
+        model.quick_switcher = Some(overlay);
+        return (model, AppCommand::None);
+    }
+    if let Some(mut dialog) = model.project_rename.take() {
+        dialog.editor.insert_str(&text);
+        model.project_rename = Some(dialog);
+        return (model, AppCommand::None);
+    }
     if let Some(mut dialog) = model.session_rename.take() {
         dialog.editor.insert_str(&text);
         model.session_rename = Some(dialog);
         return (model, AppCommand::None);
     }
     if let Some(mut dialog) = model.session_move.take() {
-        dialog.editor.insert_str(&text);
+        dialog.picker.insert_str(&text);
         model.session_move = Some(dialog);
+        return (model, AppCommand::RefreshPathPicker);
+    }
+
+    if let View::Processes(processes_view) = &mut model.view
+        && let Some(input) = processes_view.label_input.as_mut()
+    {
+        input.push_str(text.trim());
+        return (model, AppCommand::None);
+    }
+    if let View::ProcessOutput(output_view) = &mut model.view
+        && let Some(input) = output_view
+            .search_input
+            .as_mut()
+            .or(output_view.export_input.as_mut())
+    {
+        input.push_str(text.trim());
+        return (model, AppCommand::None);
+    }
+    if let View::Processes(processes_view) = &mut model.view
+        && let Some(input) = processes_view.export_input.as_mut()
+    {
+        input.push_str(text.trim());
+        return (model, AppCommand::None);
+    }
+    // Enter sends the reply, so pasted line breaks become spaces.
+    if let View::Processes(processes_view) = &mut model.view
+        && let Some(input) = processes_view.reply_input.as_mut()
+    {
+        input.push_str(&text.trim_end().replace(['\r', '\n'], " "));
         return (model, AppCommand::None);
     }
 
     let view = model.view.clone();
     if let View::NewSession(mut new_session_view) = view {
-        new_session_view.editor.insert_str(&text);
+        if let Some(picker) = new_session_view.working_dir_picker.as_mut() {
+            picker.insert_str(text.trim());
+            model.view = View::NewSession(new_session_view);
+            return (model, AppCommand::RefreshPathPicker);
+        }
+        match new_session_view
+            .insert_file
+            .as_mut()
+            .or(new_session_view.attach_file.as_mut())
+            .or(new_session_view.image_path.as_mut())
+        {
+            Some(editor) => editor.insert_str(text.trim()),
+            None => new_session_view.editor.insert_str(&text),
+        }
         model.view = View::NewSession(new_session_view);
     } else if let View::TaskCreate(mut task_create_view) = view {
         match task_create_view.overlay.take() {
@@ -2882,15 +5197,24 @@ fn update_on_paste(model: AppModel, text: String) -> (AppModel, AppCommand) {
                 editor.insert_str(&text);
                 task_create_view.overlay = Some(TaskCreateOverlay::ImagePath(editor));
             }
-            Some(TaskCreateOverlay::ProjectPath(mut editor)) => {
-                editor.insert_str(&text);
-                task_create_view.overlay = Some(TaskCreateOverlay::ProjectPath(editor));
+            Some(TaskCreateOverlay::ProjectPath(mut picker)) => {
+                picker.insert_str(&text);
+                task_create_view.overlay = Some(TaskCreateOverlay::ProjectPath(picker));
+                model.view = View::TaskCreate(task_create_view);
+                return (model, AppCommand::RefreshPathPicker);
             }
             None => {
                 task_create_view.editor.insert_str(&text);
             }
         }
         model.view = View::TaskCreate(task_create_view);
+    } else if let View::Tasks(mut tasks_view) = view
+        && let Some(mut dialog) = tasks_view.schedule_dialog.take()
+    {
+        dialog.editor.insert_str(&text);
+        dialog.error = None;
+        tasks_view.schedule_dialog = Some(dialog);
+        model.view = View::Tasks(tasks_view);
     }
 
     (model, AppCommand::None)
@@ -2902,6 +5226,14 @@ fn update_session_result_preview_overlay(
     key: KeyEvent,
 ) -> (AppModel, AppCommand) {
     match key.code {
+        KeyCode::Char(' ') if preview.cycle.len() > 1 => {
+            let command = AppCommand::OpenProjectResultPreview {
+                sessions: preview.cycle.clone(),
+                index: (preview.cycle_index + 1) % preview.cycle.len(),
+            };
+            model.session_result_preview = Some(preview);
+            return (model, command);
+        }
         KeyCode::Esc | KeyCode::Backspace | KeyCode::Enter | KeyCode::Char(' ') => {
             model.session_result_preview = None;
             return (model, AppCommand::None);
@@ -2958,14 +5290,14 @@ fn update_session_stats_overlay(
     (model, AppCommand::None)
 }
 
-fn update_project_stats_overlay(
+fn update_audit_log_overlay(
     mut model: AppModel,
-    mut overlay: ProjectStatsOverlay,
+    mut overlay: AuditLogOverlay,
     key: KeyEvent,
 ) -> (AppModel, AppCommand) {
     match key.code {
         KeyCode::Esc | KeyCode::Backspace => {
-            model.project_stats_overlay = None;
+            model.audit_log_overlay = None;
             return (model, AppCommand::None);
         }
         KeyCode::Up => {
@@ -2985,72 +5317,322 @@ fn update_project_stats_overlay(
         _ => {}
     }
 
-    model.project_stats_overlay = Some(overlay);
+    model.audit_log_overlay = Some(overlay);
     (model, AppCommand::None)
 }
 
-fn update_session_rename_dialog(
+fn update_devices_overlay(
     mut model: AppModel,
-    mut dialog: SessionRenameDialog,
+    mut overlay: DevicesOverlay,
     key: KeyEvent,
 ) -> (AppModel, AppCommand) {
+    if overlay.pairing.is_some() {
+        if matches!(key.code, KeyCode::Esc | KeyCode::Backspace) {
+            overlay.pairing = None;
+            model.devices_overlay = Some(overlay);
+            // A device may have used the code meanwhile.
+            return (model, AppCommand::OpenDevices);
+        }
+        model.devices_overlay = Some(overlay);
+        return (model, AppCommand::None);
+    }
+
+    let last = overlay.devices.len().saturating_sub(1);
     match key.code {
-        KeyCode::Esc => {
-            model.session_rename = None;
+        KeyCode::Esc | KeyCode::Backspace => {
+            model.devices_overlay = None;
             return (model, AppCommand::None);
         }
-        KeyCode::Backspace => dialog.editor.backspace(),
-        KeyCode::Enter => {
-            let title = dialog.editor.text.trim().to_string();
-            let session = dialog.session.clone();
-            model.session_rename = None;
-            return (model, AppCommand::RenameSession { session, title });
+        KeyCode::Char('n') | KeyCode::Char('N') => {
+            overlay.pairing = Some(PairingState::Requesting);
+            model.devices_overlay = Some(overlay);
+            return (model, AppCommand::PairDevice);
         }
-        KeyCode::Left => dialog.editor.move_left(),
-        KeyCode::Right => dialog.editor.move_right(),
-        KeyCode::Home => dialog.editor.move_home(),
-        KeyCode::End => dialog.editor.move_end(),
-        KeyCode::Delete => dialog.editor.delete_forward(),
-        KeyCode::Char(character) => {
-            if is_text_input_char(character) {
-                dialog.editor.insert_char(character);
+        KeyCode::Up => {
+            overlay.selected = overlay.selected.saturating_sub(1);
+        }
+        KeyCode::Down => {
+            overlay.selected = (overlay.selected + 1).min(last);
+        }
+        KeyCode::Home => {
+            overlay.selected = 0;
+        }
+        KeyCode::End => {
+            overlay.selected = last;
+        }
+        KeyCode::Char('r') | KeyCode::Char('R') => {
+            if let Some(device) = overlay.selected_device() {
+                let command = AppCommand::SetDeviceRevoked {
+                    device_id: device.device_id.clone(),
+                    revoked: !device.revoked,
+                };
+                model.devices_overlay = Some(overlay);
+                return (model, command);
+            }
+        }
+        KeyCode::Char('p') | KeyCode::Char('P') => {
+            if let Some(device) = overlay.selected_device() {
+                let command = AppCommand::ForgetDevice {
+                    device_id: device.device_id.clone(),
+                };
+                model.devices_overlay = Some(overlay);
+                return (model, command);
             }
         }
         _ => {}
     }
 
-    model.session_rename = Some(dialog);
+    model.devices_overlay = Some(overlay);
     (model, AppCommand::None)
 }
 
-fn update_session_move_dialog(
+fn update_session_diff_overlay(
     mut model: AppModel,
-    mut dialog: SessionMoveDialog,
+    mut overlay: SessionDiffOverlay,
     key: KeyEvent,
 ) -> (AppModel, AppCommand) {
     match key.code {
-        KeyCode::Esc => {
-            model.session_move = None;
+        KeyCode::Esc | KeyCode::Backspace => {
+            model.session_diff_overlay = None;
             return (model, AppCommand::None);
         }
-        KeyCode::Backspace => dialog.editor.backspace(),
+        KeyCode::Up => {
+            overlay.scroll = overlay.scroll.saturating_sub(1);
+        }
+        KeyCode::Down => {
+            overlay.scroll = overlay.scroll.saturating_add(1);
+        }
+        KeyCode::PageUp => {
+            let step = page_step_standard_list(model.terminal_size) as u16;
+            overlay.scroll = overlay.scroll.saturating_sub(step);
+        }
+        KeyCode::PageDown => {
+            let step = page_step_standard_list(model.terminal_size) as u16;
+            overlay.scroll = overlay.scroll.saturating_add(step);
+        }
+        _ => {}
+    }
+
+    model.session_diff_overlay = Some(overlay);
+    (model, AppCommand::None)
+}
+
+fn update_quick_switcher(
+    mut model: AppModel,
+    mut overlay: QuickSwitcherOverlay,
+    key: KeyEvent,
+) -> (AppModel, AppCommand) {
+    match key.code {
+        KeyCode::Esc | KeyCode::F(9) => {
+            return (model, AppCommand::None);
+        }
+        KeyCode::Up => {
+            overlay.selected = overlay.selected.saturating_sub(1);
+        }
+        KeyCode::Down => {
+            overlay.selected = overlay.selected.saturating_add(1);
+        }
         KeyCode::Enter => {
-            let raw = dialog.editor.text.trim().to_string();
-            let session = dialog.session.clone();
-            let project_path = if raw.is_empty() {
-                None
-            } else {
-                Some(PathBuf::from(raw))
+            let Some(item) = quick_switch_items(&model, &overlay)
+                .into_iter()
+                .nth(overlay.selected)
+            else {
+                model.quick_switcher = Some(overlay);
+                return (model, AppCommand::None);
             };
-            model.session_move = None;
-            return (
+            return jump_to_quick_switch_target(model, overlay.tasks, item.target);
+        }
+        KeyCode::Backspace => {
+            overlay.query.pop();
+            overlay.selected = 0;
+        }
+        KeyCode::Char(character) if is_text_input_char(character) => {
+            overlay.query.push(character);
+            overlay.selected = 0;
+        }
+        _ => {}
+    }
+
+    let count = quick_switch_items(&model, &overlay).len();
+    overlay.selected = overlay.selected.min(count.saturating_sub(1));
+    model.quick_switcher = Some(overlay);
+    (model, AppCommand::None)
+}
+
+fn jump_to_quick_switch_target(
+    mut model: AppModel,
+    tasks: Vec<TaskSummaryRow>,
+    target: QuickSwitchTarget,
+) -> (AppModel, AppCommand) {
+    model.help_open = false;
+    model.system_menu = None;
+    match target {
+        QuickSwitchTarget::Project(project_path) => {
+            let Some(project) = model
+                .data
+                .projects
+                .iter()
+                .find(|project| project.project_path == project_path)
+            else {
+                return (model, AppCommand::None);
+            };
+            let mut sessions_view =
+                SessionsView::new(project.project_path.clone(), project.sessions.len());
+            apply_session_filter(
+                &project.sessions,
+                model.session_index.as_ref(),
+                &mut sessions_view,
+                model.engine_filter,
+            );
+            model.view = View::Sessions(sessions_view);
+            (model, AppCommand::None)
+        }
+        QuickSwitchTarget::Session {
+            project_path,
+            log_path,
+        } => {
+            let Some(project) = model
+                .data
+                .projects
+                .iter()
+                .find(|project| project.project_path == project_path)
+            else {
+                return (model, AppCommand::None);
+            };
+            let Some(session) = project
+                .sessions
+                .iter()
+                .find(|session| session.log_path == log_path)
+                .cloned()
+            else {
+                return (model, AppCommand::None);
+            };
+            let from_sessions = sessions_view_selecting(&model, project, &log_path);
+            (
                 model,
-                AppCommand::MoveSessionProject {
+                AppCommand::OpenSessionDetail {
+                    from_sessions,
                     session,
-                    project_path,
+                },
+            )
+        }
+        QuickSwitchTarget::Task(task_id) => {
+            let from_tasks = TasksView::new(Box::new(model.view.clone()), tasks);
+            (
+                model,
+                AppCommand::OpenTaskDetail {
+                    from_tasks,
+                    task_id,
+                },
+            )
+        }
+        QuickSwitchTarget::Process(process_id) => {
+            let return_to = match &model.view {
+                View::Processes(view) => view.return_to.clone(),
+                View::ProcessOutput(view) => view.return_to.clone(),
+                view => Box::new(view.clone()),
+            };
+            let mut view = ProcessesView::new(return_to, 0);
+            view.selected = view.row_of(&model.processes, &process_id).unwrap_or(0);
+            model.view = View::Processes(view);
+            (model, AppCommand::None)
+        }
+    }
+}
+
+fn update_recent_sessions_overlay(
+    mut model: AppModel,
+    mut overlay: RecentSessionsOverlay,
+    key: KeyEvent,
+) -> (AppModel, AppCommand) {
+    let count = model.session_history.recent().len();
+    match key.code {
+        KeyCode::Esc | KeyCode::Backspace | KeyCode::F(7) => {
+            return (model, AppCommand::None);
+        }
+        KeyCode::Up => {
+            overlay.selected = overlay.selected.saturating_sub(1);
+        }
+        KeyCode::Down => {
+            overlay.selected = (overlay.selected + 1).min(count.saturating_sub(1));
+        }
+        KeyCode::Enter => {
+            let Some(entry) = model
+                .session_history
+                .recent()
+                .get(overlay.selected)
+                .cloned()
+            else {
+                return (model, AppCommand::None);
+            };
+            return (
+                model,
+                AppCommand::OpenSessionDetail {
+                    from_sessions: entry.from_sessions,
+                    session: entry.session,
                 },
             );
         }
+        _ => {}
+    }
+
+    model.recent_sessions_overlay = Some(overlay);
+    (model, AppCommand::None)
+}
+
+fn update_project_stats_overlay(
+    mut model: AppModel,
+    mut overlay: ProjectStatsOverlay,
+    key: KeyEvent,
+) -> (AppModel, AppCommand) {
+    match key.code {
+        KeyCode::Esc | KeyCode::Backspace => {
+            model.project_stats_overlay = None;
+            return (model, AppCommand::None);
+        }
+        KeyCode::Up => {
+            overlay.scroll = overlay.scroll.saturating_sub(1);
+        }
+        KeyCode::Down => {
+            overlay.scroll = overlay.scroll.saturating_add(1);
+        }
+        KeyCode::PageUp => {
+            let step = page_step_standard_list(model.terminal_size) as u16;
+            overlay.scroll = overlay.scroll.saturating_sub(step);
+        }
+        KeyCode::PageDown => {
+            let step = page_step_standard_list(model.terminal_size) as u16;
+            overlay.scroll = overlay.scroll.saturating_add(step);
+        }
+        KeyCode::Char('s') if overlay.skills.is_none() => {
+            let project_path = overlay.project_path.clone();
+            model.project_stats_overlay = Some(overlay);
+            return (model, AppCommand::LoadProjectSkills { project_path });
+        }
+        _ => {}
+    }
+
+    model.project_stats_overlay = Some(overlay);
+    (model, AppCommand::None)
+}
+
+fn update_session_rename_dialog(
+    mut model: AppModel,
+    mut dialog: SessionRenameDialog,
+    key: KeyEvent,
+) -> (AppModel, AppCommand) {
+    match key.code {
+        KeyCode::Esc => {
+            model.session_rename = None;
+            return (model, AppCommand::None);
+        }
+        KeyCode::Backspace => dialog.editor.backspace(),
+        KeyCode::Enter => {
+            let title = dialog.editor.text.trim().to_string();
+            let session = dialog.session.clone();
+            model.session_rename = None;
+            return (model, AppCommand::RenameSession { session, title });
+        }
         KeyCode::Left => dialog.editor.move_left(),
         KeyCode::Right => dialog.editor.move_right(),
         KeyCode::Home => dialog.editor.move_home(),
@@ -3064,10 +5646,154 @@ fn update_session_move_dialog(
         _ => {}
     }
 
+    model.session_rename = Some(dialog);
+    (model, AppCommand::None)
+}
+
+fn update_project_rename_dialog(
+    mut model: AppModel,
+    mut dialog: ProjectRenameDialog,
+    key: KeyEvent,
+) -> (AppModel, AppCommand) {
+    match key.code {
+        KeyCode::Esc => return (model, AppCommand::None),
+        KeyCode::Backspace => dialog.editor.backspace(),
+        KeyCode::Enter => {
+            let name = dialog.editor.text.trim().to_string();
+            model.project_prefs.set_name(&dialog.project_path, &name);
+            refresh_project_prefs(&mut model);
+            model.notice = Some(if name.is_empty() {
+                "Project name reset to its folder name.".to_string()
+            } else {
+                format!("Project renamed to {name}.")
+            });
+            return (model, AppCommand::None);
+        }
+        KeyCode::Left => dialog.editor.move_left(),
+        KeyCode::Right => dialog.editor.move_right(),
+        KeyCode::Home => dialog.editor.move_home(),
+        KeyCode::End => dialog.editor.move_end(),
+        KeyCode::Delete => dialog.editor.delete_forward(),
+        KeyCode::Char(character) if is_text_input_char(character) => {
+            dialog.editor.insert_char(character);
+        }
+        _ => {}
+    }
+
+    model.project_rename = Some(dialog);
+    (model, AppCommand::None)
+}
+
+/// Re-applies `project_prefs` to the loaded projects and refreshes the Projects list.
+fn refresh_project_prefs(model: &mut AppModel) {
+    model.project_prefs.apply(&mut model.data.projects);
+    if let View::Projects(mut view) = model.view.clone() {
+        resort_projects(model, &mut view);
+        model.view = View::Projects(view);
+    }
+}
+
+/// Hides the selected projects (or the highlighted one), or shows them again when all of them
+/// are hidden already.
+fn toggle_hidden_projects(model: &mut AppModel, view: &mut ProjectsView) {
+    let project_paths = if view.selected_project_paths.is_empty() {
+        view.filtered_indices
+            .get(view.selected)
+            .and_then(|index| model.data.projects.get(*index))
+            .map(|project| vec![project.project_path.clone()])
+            .unwrap_or_default()
+    } else {
+        view.selected_project_paths.iter().cloned().collect()
+    };
+    let Some(first) = project_paths.first() else {
+        return;
+    };
+
+    let hidden = !project_paths
+        .iter()
+        .all(|path| model.project_prefs.is_hidden(path));
+    for path in &project_paths {
+        model.project_prefs.set_hidden(path, hidden);
+    }
+    let target = if project_paths.len() == 1 {
+        model
+            .data
+            .projects
+            .iter()
+            .find(|project| &project.project_path == first)
+            .map(|project| project.name.clone())
+            .unwrap_or_else(|| first.display().to_string())
+    } else {
+        format!("{} projects", project_paths.len())
+    };
+    model.project_prefs.apply(&mut model.data.projects);
+    clear_project_selection(view);
+    resort_projects(model, view);
+    model.notice = Some(if hidden {
+        format!("Hid {target} (F6 shows hidden projects).")
+    } else {
+        format!("Unhid {target}.")
+    });
+}
+
+fn update_session_move_dialog(
+    mut model: AppModel,
+    mut dialog: SessionMoveDialog,
+    key: KeyEvent,
+) -> (AppModel, AppCommand) {
+    match dialog.picker.handle_key(key) {
+        PathPickerAction::None => {}
+        PathPickerAction::Refresh => {
+            model.session_move = Some(dialog);
+            return (model, AppCommand::RefreshPathPicker);
+        }
+        PathPickerAction::Cancel => {
+            model.session_move = None;
+            return (model, AppCommand::None);
+        }
+        PathPickerAction::Submit(project_path) => {
+            model.session_move = None;
+            return (
+                model,
+                AppCommand::MoveSessionProject {
+                    session: dialog.session,
+                    project_path,
+                },
+            );
+        }
+    }
+
     model.session_move = Some(dialog);
     (model, AppCommand::None)
 }
 
+fn update_opencode_write_back_confirm(
+    mut model: AppModel,
+    confirm: OpenCodeWriteBackConfirm,
+    key: KeyEvent,
+) -> (AppModel, AppCommand) {
+    match key.code {
+        KeyCode::Char('y') | KeyCode::Char('Y') => {
+            model.opencode_write_back = None;
+            (
+                model,
+                AppCommand::WriteBackOpenCode {
+                    session: confirm.session,
+                    change: confirm.change,
+                },
+            )
+        }
+        KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc | KeyCode::Enter => {
+            model.opencode_write_back = None;
+            (model, AppCommand::None)
+        }
+        _ => {
+            model.opencode_write_back = Some(confirm);
+            (model, AppCommand::None)
+        }
+    }
+}
+
 fn update_delete_confirm(
     mut model: AppModel,
     mut confirm: DeleteConfirmDialog,
@@ -3340,15 +6066,22 @@ fn update_error(model: AppModel, key: KeyEvent) -> (AppModel, AppCommand) {
     match key.code {
         KeyCode::Esc | KeyCode::Backspace => {
             let mut view = ProjectsView::new(&model.data.projects);
-            apply_project_filter(&model.data.projects, &mut view, model.engine_filter);
-            (
-                AppModel {
-                    data: model.data.clone(),
-                    session_index: model.session_index.clone(),
-                    terminal_size: model.terminal_size,
+            apply_project_filter(
+                &model.data.projects,
+                &mut view,
+                model.engine_filter,
+                &model.session_index,
+                model.projects_order,
+            );
+            (
+                AppModel {
+                    data: model.data.clone(),
+                    session_index: model.session_index.clone(),
+                    terminal_size: model.terminal_size,
                     notice: None,
                     update_hint: model.update_hint.clone(),
                     engine_filter: model.engine_filter,
+                    engine_prefs: model.engine_prefs.clone(),
                     help_open: model.help_open,
                     system_menu: model.system_menu.clone(),
                     delete_confirm: model.delete_confirm.clone(),
@@ -3357,12 +6090,27 @@ fn update_error(model: AppModel, key: KeyEvent) -> (AppModel, AppCommand) {
                     delete_sessions_confirm: model.delete_sessions_confirm.clone(),
                     session_rename: model.session_rename.clone(),
                     session_move: model.session_move.clone(),
+                    opencode_write_back: model.opencode_write_back.clone(),
                     delete_task_confirm: model.delete_task_confirm.clone(),
                     delete_tasks_confirm: model.delete_tasks_confirm.clone(),
                     session_result_preview: model.session_result_preview.clone(),
                     session_stats_overlay: model.session_stats_overlay.clone(),
                     project_stats_overlay: model.project_stats_overlay.clone(),
+                    audit_log_overlay: model.audit_log_overlay.clone(),
+                    devices_overlay: model.devices_overlay.clone(),
+                    session_diff_overlay: model.session_diff_overlay.clone(),
+                    recent_sessions_overlay: model.recent_sessions_overlay.clone(),
+                    quick_switcher: model.quick_switcher.clone(),
+                    session_history: model.session_history.clone(),
+                    show_session_columns: model.show_session_columns,
+                    projects_order: model.projects_order,
+                    list_columns: model.list_columns.clone(),
+                    engine_models: model.engine_models.clone(),
+                    spawn_args: model.spawn_args.clone(),
+                    project_prefs: model.project_prefs.clone(),
+                    project_rename: model.project_rename.clone(),
                     processes: model.processes.clone(),
+                    external_processes: model.external_processes.clone(),
                     view: View::Projects(view),
                 },
                 AppCommand::None,
@@ -3372,14 +6120,90 @@ fn update_error(model: AppModel, key: KeyEvent) -> (AppModel, AppCommand) {
     }
 }
 
+/// How many of a project's newest sessions Space cycles through in the Result preview.
+const PROJECT_RESULT_PREVIEW_SESSIONS: usize = 5;
+
+/// Re-applies the Projects filter and sort, keeping the highlighted project highlighted.
+fn resort_projects(model: &mut AppModel, view: &mut ProjectsView) {
+    let selected_path = view
+        .filtered_indices
+        .get(view.selected)
+        .and_then(|index| model.data.projects.get(*index))
+        .map(|project| project.project_path.clone());
+    apply_project_filter(
+        &model.data.projects,
+        view,
+        model.engine_filter,
+        &model.session_index,
+        model.projects_order,
+    );
+    if let Some(path) = selected_path
+        && let Some(pos) = view.filtered_indices.iter().position(|index| {
+            model
+                .data
+                .projects
+                .get(*index)
+                .is_some_and(|project| project.project_path == path)
+        })
+    {
+        view.selected = pos;
+    }
+}
+
 fn update_projects(
     mut model: AppModel,
     mut view: ProjectsView,
     key: KeyEvent,
 ) -> (AppModel, AppCommand) {
+    let new_modifier = key.modifiers.contains(KeyModifiers::CONTROL)
+        || key.modifiers.contains(KeyModifiers::SUPER)
+        || key.modifiers.contains(KeyModifiers::META);
     let shift = key.modifiers.contains(KeyModifiers::SHIFT);
 
     match key.code {
+        KeyCode::Char('w') | KeyCode::Char('W') if new_modifier => {
+            let Some(project_index) = view.filtered_indices.get(view.selected).copied() else {
+                return (model, AppCommand::None);
+            };
+            let Some(project) = model.data.projects.get(project_index) else {
+                return (model, AppCommand::None);
+            };
+            let project_path = project.project_path.clone();
+            return (model, AppCommand::OpenProjectInIde { project_path });
+        }
+        KeyCode::Char('e') | KeyCode::Char('E') if new_modifier => {
+            let Some(project) = view
+                .filtered_indices
+                .get(view.selected)
+                .and_then(|index| model.data.projects.get(*index))
+            else {
+                return (model, AppCommand::None);
+            };
+            model.project_rename = Some(ProjectRenameDialog {
+                project_path: project.project_path.clone(),
+                editor: LineEditor::from_text(project.name.clone()),
+            });
+            model.help_open = false;
+            model.system_menu = None;
+            model.view = View::Projects(view);
+            return (model, AppCommand::None);
+        }
+        KeyCode::Char('x') | KeyCode::Char('X') if new_modifier => {
+            toggle_hidden_projects(&mut model, &mut view);
+            model.view = View::Projects(view);
+            return (model, AppCommand::None);
+        }
+        KeyCode::F(6) => {
+            view.show_hidden = !view.show_hidden;
+            resort_projects(&mut model, &mut view);
+            model.notice = Some(if view.show_hidden {
+                "Showing hidden projects.".to_string()
+            } else {
+                "Hidden projects left out.".to_string()
+            });
+            model.view = View::Projects(view);
+            return (model, AppCommand::None);
+        }
         KeyCode::F(3) => {
             let Some(project_index) = view.filtered_indices.get(view.selected).copied() else {
                 return (model, AppCommand::None);
@@ -3396,6 +6220,13 @@ fn update_projects(
             model.system_menu = None;
             return (model, AppCommand::None);
         }
+        KeyCode::F(4) => {
+            model.projects_order = model.projects_order.cycle();
+            resort_projects(&mut model, &mut view);
+            model.notice = Some(format!("Sort: {}", model.projects_order.label()));
+            model.view = View::Projects(view);
+            return (model, AppCommand::None);
+        }
         KeyCode::Enter => {
             let Some(project_index) = view.filtered_indices.get(view.selected).copied() else {
                 return (model, AppCommand::None);
@@ -3418,6 +6249,7 @@ fn update_projects(
                 notice: None,
                 update_hint: model.update_hint.clone(),
                 engine_filter: model.engine_filter,
+                engine_prefs: model.engine_prefs.clone(),
                 help_open: model.help_open,
                 system_menu: model.system_menu.clone(),
                 delete_confirm: model.delete_confirm.clone(),
@@ -3426,12 +6258,27 @@ fn update_projects(
                 delete_sessions_confirm: model.delete_sessions_confirm.clone(),
                 session_rename: model.session_rename.clone(),
                 session_move: model.session_move.clone(),
+                opencode_write_back: model.opencode_write_back.clone(),
                 delete_task_confirm: model.delete_task_confirm.clone(),
                 delete_tasks_confirm: model.delete_tasks_confirm.clone(),
                 session_result_preview: model.session_result_preview.clone(),
                 session_stats_overlay: model.session_stats_overlay.clone(),
                 project_stats_overlay: model.project_stats_overlay.clone(),
+                audit_log_overlay: model.audit_log_overlay.clone(),
+                devices_overlay: model.devices_overlay.clone(),
+                session_diff_overlay: model.session_diff_overlay.clone(),
+                recent_sessions_overlay: model.recent_sessions_overlay.clone(),
+                quick_switcher: model.quick_switcher.clone(),
+                session_history: model.session_history.clone(),
+                show_session_columns: model.show_session_columns,
+                projects_order: model.projects_order,
+                list_columns: model.list_columns.clone(),
+                engine_models: model.engine_models.clone(),
+                spawn_args: model.spawn_args.clone(),
+                project_prefs: model.project_prefs.clone(),
+                project_rename: model.project_rename.clone(),
                 processes: model.processes.clone(),
+                external_processes: model.external_processes.clone(),
                 view: View::Sessions(sessions_view),
             };
             return (next, AppCommand::None);
@@ -3439,7 +6286,13 @@ fn update_projects(
         KeyCode::Esc => {
             if !view.query.is_empty() {
                 view.query.clear();
-                apply_project_filter(&model.data.projects, &mut view, model.engine_filter);
+                apply_project_filter(
+                    &model.data.projects,
+                    &mut view,
+                    model.engine_filter,
+                    &model.session_index,
+                    model.projects_order,
+                );
                 clear_project_selection(&mut view);
             } else if !view.selected_project_paths.is_empty() {
                 clear_project_selection(&mut view);
@@ -3452,12 +6305,21 @@ fn update_projects(
             let Some(project) = model.data.projects.get(project_index) else {
                 return (model, AppCommand::None);
             };
-            let Some(session) = project.sessions.first().cloned() else {
+            if project.sessions.is_empty() {
                 model.notice = Some("No sessions in this project.".to_string());
                 return (model, AppCommand::None);
-            };
+            }
+            let sessions = project
+                .sessions
+                .iter()
+                .take(PROJECT_RESULT_PREVIEW_SESSIONS)
+                .cloned()
+                .collect();
 
-            return (model, AppCommand::OpenSessionResultPreview { session });
+            return (
+                model,
+                AppCommand::OpenProjectResultPreview { sessions, index: 0 },
+            );
         }
         KeyCode::Tab => {
             toggle_project_selection(&model.data.projects, &mut view);
@@ -3511,7 +6373,13 @@ fn update_projects(
         KeyCode::Backspace => {
             if !view.query.is_empty() {
                 view.query.pop();
-                apply_project_filter(&model.data.projects, &mut view, model.engine_filter);
+                apply_project_filter(
+                    &model.data.projects,
+                    &mut view,
+                    model.engine_filter,
+                    &model.session_index,
+                    model.projects_order,
+                );
                 clear_project_selection(&mut view);
             } else {
                 open_delete_confirm(&mut model, &view);
@@ -3523,7 +6391,13 @@ fn update_projects(
         KeyCode::Char(character) => {
             if is_text_input_char(character) {
                 view.query.push(character);
-                apply_project_filter(&model.data.projects, &mut view, model.engine_filter);
+                apply_project_filter(
+                    &model.data.projects,
+                    &mut view,
+                    model.engine_filter,
+                    &model.session_index,
+                    model.projects_order,
+                );
                 clear_project_selection(&mut view);
             }
         }
@@ -3538,6 +6412,7 @@ fn update_projects(
             notice: None,
             update_hint: model.update_hint.clone(),
             engine_filter: model.engine_filter,
+            engine_prefs: model.engine_prefs.clone(),
             help_open: model.help_open,
             system_menu: model.system_menu.clone(),
             delete_confirm: model.delete_confirm.clone(),
@@ -3546,18 +6421,131 @@ fn update_projects(
             delete_sessions_confirm: model.delete_sessions_confirm.clone(),
             session_rename: model.session_rename.clone(),
             session_move: model.session_move.clone(),
+            opencode_write_back: model.opencode_write_back.clone(),
             delete_task_confirm: model.delete_task_confirm.clone(),
             delete_tasks_confirm: model.delete_tasks_confirm.clone(),
             session_result_preview: model.session_result_preview.clone(),
             session_stats_overlay: model.session_stats_overlay.clone(),
             project_stats_overlay: model.project_stats_overlay.clone(),
+            audit_log_overlay: model.audit_log_overlay.clone(),
+            devices_overlay: model.devices_overlay.clone(),
+            session_diff_overlay: model.session_diff_overlay.clone(),
+            recent_sessions_overlay: model.recent_sessions_overlay.clone(),
+            quick_switcher: model.quick_switcher.clone(),
+            session_history: model.session_history.clone(),
+            show_session_columns: model.show_session_columns,
+            projects_order: model.projects_order,
+            list_columns: model.list_columns.clone(),
+            engine_models: model.engine_models.clone(),
+            spawn_args: model.spawn_args.clone(),
+            project_prefs: model.project_prefs.clone(),
+            project_rename: model.project_rename.clone(),
             processes: model.processes.clone(),
+            external_processes: model.external_processes.clone(),
             view: View::Projects(view),
         },
         AppCommand::None,
     )
 }
 
+fn update_all_sessions(
+    mut model: AppModel,
+    mut view: AllSessionsView,
+    key: KeyEvent,
+) -> (AppModel, AppCommand) {
+    let last = view.filtered_indices.len().saturating_sub(1);
+    match key.code {
+        KeyCode::Enter => {
+            let Some((project, session)) = view.selected_session(&model.data.projects) else {
+                return (model, AppCommand::None);
+            };
+            let from_sessions = sessions_view_selecting(&model, project, &session.log_path);
+            let session = session.clone();
+            model.view = View::AllSessions(view);
+            return (
+                model,
+                AppCommand::OpenSessionDetail {
+                    from_sessions,
+                    session,
+                },
+            );
+        }
+        KeyCode::Esc => {
+            if view.query.is_empty() {
+                let mut projects_view = ProjectsView::new(&model.data.projects);
+                apply_project_filter(
+                    &model.data.projects,
+                    &mut projects_view,
+                    model.engine_filter,
+                    &model.session_index,
+                    model.projects_order,
+                );
+                model.view = View::Projects(projects_view);
+                return (model, AppCommand::None);
+            }
+            view.query.clear();
+            apply_all_sessions_filter(&model.data.projects, &mut view, model.engine_filter);
+        }
+        KeyCode::F(6) => {
+            model.show_session_columns = !model.show_session_columns;
+        }
+        KeyCode::Up => {
+            view.selected = view.selected.saturating_sub(1);
+        }
+        KeyCode::Down => {
+            view.selected = (view.selected + 1).min(last);
+        }
+        KeyCode::PageUp => {
+            let step = page_step_standard_list(model.terminal_size);
+            view.selected = view.selected.saturating_sub(step);
+        }
+        KeyCode::PageDown => {
+            let step = page_step_standard_list(model.terminal_size);
+            view.selected = (view.selected + step).min(last);
+        }
+        KeyCode::Backspace => {
+            view.query.pop();
+            apply_all_sessions_filter(&model.data.projects, &mut view, model.engine_filter);
+        }
+        KeyCode::Char(character) if is_text_input_char(character) => {
+            view.query.push(character);
+            view.selected = 0;
+            apply_all_sessions_filter(&model.data.projects, &mut view, model.engine_filter);
+        }
+        _ => {}
+    }
+
+    model.view = View::AllSessions(view);
+    (model, AppCommand::None)
+}
+
+/// A Sessions view of `project` with the session at `log_path` selected.
+fn sessions_view_selecting(
+    model: &AppModel,
+    project: &ProjectSummary,
+    log_path: &Path,
+) -> SessionsView {
+    let mut sessions_view = SessionsView::new(project.project_path.clone(), project.sessions.len());
+    apply_session_filter(
+        &project.sessions,
+        model.session_index.as_ref(),
+        &mut sessions_view,
+        model.engine_filter,
+    );
+    if let Some(selected_index) = project
+        .sessions
+        .iter()
+        .position(|session| session.log_path == log_path)
+        && let Some(pos) = sessions_view
+            .filtered_indices
+            .iter()
+            .position(|index| *index == selected_index)
+    {
+        sessions_view.session_selected = pos;
+    }
+    sessions_view
+}
+
 fn open_delete_confirm(model: &mut AppModel, view: &ProjectsView) {
     if view.selected_project_paths.len() >= 2 {
         let project_paths = view
@@ -3657,7 +6645,7 @@ fn default_new_session_engine(
             .find_map(|session| match session.engine {
                 SessionEngine::Codex => Some(AgentEngine::Codex),
                 SessionEngine::Claude => Some(AgentEngine::Claude),
-                SessionEngine::Gemini | SessionEngine::OpenCode => None,
+                SessionEngine::Gemini | SessionEngine::OpenCode | SessionEngine::Custom => None,
             })
             .unwrap_or(AgentEngine::Codex),
     }
@@ -3667,6 +6655,8 @@ fn apply_project_filter(
     projects: &[ProjectSummary],
     view: &mut ProjectsView,
     engine: EngineFilter,
+    index: &SessionIndex,
+    order: ProjectsOrder,
 ) {
     let query = view.query.trim().to_lowercase();
     if query.is_empty() {
@@ -3674,29 +6664,49 @@ fn apply_project_filter(
             .iter()
             .enumerate()
             .filter_map(|(index, project)| {
-                project_matches_engine_filter(project, engine).then_some(index)
+                (project_matches_engine_filter(project, engine)
+                    && (view.show_hidden || !project.hidden))
+                    .then_some(index)
             })
             .collect();
     } else {
-        view.filtered_indices = projects
-            .iter()
-            .enumerate()
-            .filter_map(|(index, project)| {
-                if !project_matches_engine_filter(project, engine) {
+        view.filtered_indices =
+            rank_by_score(projects.iter().enumerate().filter_map(|(index, project)| {
+                if !project_matches_engine_filter(project, engine)
+                    || (project.hidden && !view.show_hidden)
+                {
                     return None;
                 }
-                let haystack = format!(
-                    "{}\n{}",
-                    project.name.to_lowercase(),
-                    project.project_path.display().to_string().to_lowercase()
-                );
-                if haystack.contains(&query) {
-                    Some(index)
-                } else {
-                    None
-                }
-            })
-            .collect();
+                let path = project.project_path.display().to_string();
+                let score = filter_score(&query, &[&project.name], &[&path])?;
+                Some((score, index))
+            }));
+    }
+
+    // Stable, so ties keep the recency (or match score) order.
+    match order {
+        ProjectsOrder::RecentFirst => {}
+        ProjectsOrder::MostSessions => view.filtered_indices.sort_by_key(|position| {
+            std::cmp::Reverse(
+                projects
+                    .get(*position)
+                    .map(|project| project.sessions.len()),
+            )
+        }),
+        ProjectsOrder::LargestFirst => view.filtered_indices.sort_by_key(|position| {
+            std::cmp::Reverse(
+                projects
+                    .get(*position)
+                    .map(ProjectSummary::total_size_bytes),
+            )
+        }),
+        ProjectsOrder::MostTokens => view.filtered_indices.sort_by_key(|position| {
+            std::cmp::Reverse(
+                projects
+                    .get(*position)
+                    .and_then(|project| index.sessions_tokens(&project.sessions)),
+            )
+        }),
     }
 
     if view.filtered_indices.is_empty() {
@@ -3708,6 +6718,45 @@ fn apply_project_filter(
     }
 }
 
+/// Fills `view.filtered_indices` with the sessions of every project that match the engine filter
+/// and the query (title, id, project, branch): best match first, then most recently modified.
+fn apply_all_sessions_filter(
+    projects: &[ProjectSummary],
+    view: &mut AllSessionsView,
+    engine: EngineFilter,
+) {
+    let (branch, query) = split_branch_filter(&view.query.trim().to_lowercase());
+    let mut matches = Vec::new();
+    for (project_index, project) in projects.iter().enumerate() {
+        for (session_index, session) in project.sessions.iter().enumerate() {
+            if !session_matches_engine_filter(session, engine)
+                || !session_matches_branch_filter(session, branch.as_deref())
+            {
+                continue;
+            }
+            let path = project.project_path.display().to_string();
+            let Some(score) = filter_score(
+                &query,
+                &[&session.title, &project.name],
+                &[&session.meta.id, &path],
+            ) else {
+                continue;
+            };
+            matches.push((score, session.file_modified, project_index, session_index));
+        }
+    }
+    matches.sort_by_key(|(score, modified, _, _)| {
+        (std::cmp::Reverse(*score), std::cmp::Reverse(*modified))
+    });
+    view.filtered_indices = matches
+        .into_iter()
+        .map(|(_, _, project_index, session_index)| (project_index, session_index))
+        .collect();
+    view.selected = view
+        .selected
+        .min(view.filtered_indices.len().saturating_sub(1));
+}
+
 fn prune_project_selection(projects: &[ProjectSummary], view: &mut ProjectsView) {
     let mut available = BTreeSet::new();
     for project in projects {
@@ -3729,7 +6778,7 @@ fn apply_session_filter(
     view: &mut SessionsView,
     engine: EngineFilter,
 ) {
-    let query = view.query.trim().to_lowercase();
+    let (branch, query) = split_branch_filter(&view.query.trim().to_lowercase());
     let errors_only = view.tool_errors_only;
     if query.is_empty() {
         view.filtered_indices = sessions
@@ -3742,36 +6791,39 @@ fn apply_session_filter(
                 if errors_only && !session_matches_tool_error_filter(session, index) {
                     return None;
                 }
+                if !session_matches_branch_filter(session, branch.as_deref()) {
+                    return None;
+                }
                 Some(session_index)
             })
             .collect();
     } else {
-        view.filtered_indices = sessions
-            .iter()
-            .enumerate()
-            .filter_map(|(session_index, session)| {
+        view.filtered_indices = rank_by_score(sessions.iter().enumerate().filter_map(
+            |(session_index, session)| {
                 if !session_matches_engine_filter(session, engine) {
                     return None;
                 }
                 if errors_only && !session_matches_tool_error_filter(session, index) {
                     return None;
                 }
-                let haystack = format!(
-                    "{}\n{}\n{}\n{}",
-                    session.title.to_lowercase(),
-                    session.meta.id.to_lowercase(),
-                    session.meta.started_at_rfc3339.to_lowercase(),
-                    session.log_path.display().to_string().to_lowercase()
-                );
-                if haystack.contains(&query) {
-                    Some(session_index)
-                } else {
-                    None
+                if !session_matches_branch_filter(session, branch.as_deref()) {
+                    return None;
                 }
-            })
-            .collect();
+                let score = session_filter_score(session, &query);
+                let content_match = view.search_content
+                    && index
+                        .search_terms(&session.log_path)
+                        .is_some_and(|terms| matches_search_terms(terms, &query));
+                // Prompt/output word matches rank below any match on the session itself.
+                let score = score.or(content_match.then_some(i64::MIN))?;
+                Some((score, session_index))
+            },
+        ));
     }
 
+    sort_sessions(sessions, index, view.order, &mut view.filtered_indices);
+    view.filtered_indices = nest_subagent_sessions(sessions, &view.filtered_indices);
+
     if view.order == SessionsOrder::ToolErrorsFirst && !view.filtered_indices.is_empty() {
         view.filtered_indices.sort_by(|a, b| {
             let a_session = sessions.get(*a);
@@ -3805,6 +6857,113 @@ fn apply_session_filter(
     }
 }
 
+/// Stable sort of `indices` by `order`; ties (and sessions the index has no value for, which go
+/// last) keep their filtered order. Runs before subagent nesting so children stay under their
+/// parent.
+fn sort_sessions(
+    sessions: &[SessionSummary],
+    index: &SessionIndex,
+    order: SessionsOrder,
+    indices: &mut [usize],
+) {
+    let session = |position: &usize| sessions.get(*position);
+    match order {
+        SessionsOrder::NewestFirst | SessionsOrder::ToolErrorsFirst => {}
+        SessionsOrder::StartedFirst => indices.sort_by_key(|position| {
+            std::cmp::Reverse(
+                session(position)
+                    .and_then(|session| parse_rfc3339_to_unix_ms(&session.meta.started_at_rfc3339)),
+            )
+        }),
+        SessionsOrder::LargestFirst => indices.sort_by_key(|position| {
+            std::cmp::Reverse(session(position).map(|session| session.file_size_bytes))
+        }),
+        SessionsOrder::MostTokens => indices.sort_by_key(|position| {
+            std::cmp::Reverse(
+                session(position).and_then(|session| index.total_tokens(&session.log_path)),
+            )
+        }),
+        SessionsOrder::LongestFirst => indices.sort_by_key(|position| {
+            std::cmp::Reverse(session(position).and_then(|session| index.duration_ms(session)))
+        }),
+        SessionsOrder::Title => indices.sort_by_cached_key(|position| {
+            session(position).map(|session| session.title.to_lowercase())
+        }),
+    }
+}
+
+/// Fuzzy score of a list filter `query` (lowercase): the best match over `fuzzy_fields` (titles,
+/// names), else a flat score when the query is a substring of one of `exact_fields` (ids, paths,
+/// dates), where a fuzzy match would hit almost any short query.
+fn filter_score(query: &str, fuzzy_fields: &[&str], exact_fields: &[&str]) -> Option<i64> {
+    fuzzy_fields
+        .iter()
+        .filter_map(|field| fuzzy_score(query, field))
+        .max()
+        .or_else(|| {
+            exact_fields
+                .iter()
+                .any(|field| field.to_lowercase().contains(query))
+                .then_some(0)
+        })
+}
+
+fn session_filter_score(session: &SessionSummary, query: &str) -> Option<i64> {
+    let log_path = session.log_path.display().to_string();
+    let git = session
+        .meta
+        .git
+        .as_ref()
+        .map(|git| git.label())
+        .unwrap_or_default();
+    filter_score(
+        query,
+        &[&session.title],
+        &[
+            &session.meta.id,
+            &session.meta.started_at_rfc3339,
+            &log_path,
+            &git,
+        ],
+    )
+}
+
+/// Indices ordered best score first; ties keep their original order.
+fn rank_by_score(scored: impl Iterator<Item = (i64, usize)>) -> Vec<usize> {
+    let mut scored = scored.collect::<Vec<_>>();
+    scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    scored.into_iter().map(|(_, index)| index).collect()
+}
+
+/// Pulls a `branch:NAME` term out of a (lowercase) Sessions filter query; the rest of the query
+/// is matched as text.
+fn split_branch_filter(query: &str) -> (Option<String>, String) {
+    if !query.contains("branch:") {
+        return (None, query.to_string());
+    }
+    let mut branch = None;
+    let mut rest = Vec::new();
+    for word in query.split_whitespace() {
+        match word.strip_prefix("branch:") {
+            Some(name) if !name.is_empty() => branch = Some(name.to_string()),
+            _ => rest.push(word),
+        }
+    }
+    (branch, rest.join(" "))
+}
+
+fn session_matches_branch_filter(session: &SessionSummary, branch: Option<&str>) -> bool {
+    let Some(branch) = branch else {
+        return true;
+    };
+    session
+        .meta
+        .git
+        .as_ref()
+        .and_then(|git| git.branch.as_deref())
+        .is_some_and(|name| name.to_lowercase().contains(branch))
+}
+
 fn session_matches_tool_error_filter(session: &SessionSummary, index: &SessionIndex) -> bool {
     index
         .tool_failures(&session.log_path)
@@ -3831,23 +6990,12 @@ fn apply_task_filter(view: &mut TasksView) {
     if query.is_empty() {
         view.filtered_indices = (0..view.tasks.len()).collect();
     } else {
-        view.filtered_indices = view
-            .tasks
-            .iter()
-            .enumerate()
-            .filter_map(|(index, task)| {
-                let haystack = format!(
-                    "{}\n{}",
-                    task.title.to_lowercase(),
-                    task.project_path.display().to_string().to_lowercase()
-                );
-                if haystack.contains(&query) {
-                    Some(index)
-                } else {
-                    None
-                }
-            })
-            .collect();
+        view.filtered_indices =
+            rank_by_score(view.tasks.iter().enumerate().filter_map(|(index, task)| {
+                let path = task.project_path.display().to_string();
+                let score = filter_score(&query, &[&task.title], &[&path])?;
+                Some((score, index))
+            }));
     }
 
     if view.filtered_indices.is_empty() {
@@ -4135,7 +7283,16 @@ fn selected_task_id(view: &TasksView) -> Option<TaskId> {
 
 fn open_delete_task_confirm_from_tasks(model: &mut AppModel, view: &TasksView) -> bool {
     if view.selected_task_ids.len() >= 2 {
-        let task_ids = view.selected_task_ids.iter().cloned().collect::<Vec<_>>();
+        let task_ids = view
+            .selected_task_ids
+            .iter()
+            .filter(|task_id| !task_id.is_team())
+            .cloned()
+            .collect::<Vec<_>>();
+        if task_ids.is_empty() {
+            model.notice = Some(TEAM_TASK_READ_ONLY_NOTICE.to_string());
+            return false;
+        }
         model.delete_tasks_confirm = Some(DeleteTasksConfirmDialog {
             task_count: task_ids.len(),
             task_ids,
@@ -4151,6 +7308,10 @@ fn open_delete_task_confirm_from_tasks(model: &mut AppModel, view: &TasksView) -
     let Some(task) = view.tasks.get(task_index) else {
         return false;
     };
+    if task.id.is_team() {
+        model.notice = Some(TEAM_TASK_READ_ONLY_NOTICE.to_string());
+        return false;
+    }
 
     model.delete_task_confirm = Some(DeleteTaskConfirmDialog {
         task_id: task.id.clone(),
@@ -4163,6 +7324,16 @@ fn open_delete_task_confirm_from_tasks(model: &mut AppModel, view: &TasksView) -
     true
 }
 
+/// Known project paths for the path picker, most recently active first.
+fn recent_project_paths(model: &AppModel) -> Vec<PathBuf> {
+    let mut projects = model.data.projects.iter().collect::<Vec<_>>();
+    projects.sort_by_key(|project| std::cmp::Reverse(project.last_modified));
+    projects
+        .into_iter()
+        .map(|project| project.project_path.clone())
+        .collect()
+}
+
 fn default_task_create_project_path(model: &AppModel, view: &TasksView) -> PathBuf {
     let selected_path = view
         .filtered_indices
@@ -4191,6 +7362,9 @@ fn infer_project_path_for_new_task_view(view: &View, model: &AppModel) -> Option
             Some(project.project_path.clone())
         }
         View::Sessions(sessions_view) => Some(sessions_view.project_path.clone()),
+        View::AllSessions(all_sessions_view) => all_sessions_view
+            .selected_session(&model.data.projects)
+            .map(|(project, _)| project.project_path.clone()),
         View::NewSession(new_session_view) => {
             Some(new_session_view.from_sessions.project_path.clone())
         }
@@ -4215,6 +7389,9 @@ fn infer_project_path_for_new_task_view(view: &View, model: &AppModel) -> Option
         View::ProcessOutput(output_view) => {
             infer_project_path_for_new_task_view(output_view.return_to.as_ref(), model)
         }
+        View::Activity(activity_view) => {
+            infer_project_path_for_new_task_view(activity_view.return_to.as_ref(), model)
+        }
         View::Error => None,
     }
 }
@@ -4264,6 +7441,76 @@ fn open_delete_session_confirm(model: &mut AppModel, view: &SessionsView) -> boo
     true
 }
 
+/// Moves each subagent session right below its parent, keeping the order of both otherwise.
+/// Subagents whose parent is not listed keep their place.
+fn nest_subagent_sessions(sessions: &[SessionSummary], indices: &[usize]) -> Vec<usize> {
+    let parents = indices
+        .iter()
+        .filter_map(|index| sessions.get(*index).map(|session| (*index, session)))
+        .filter(|(_, session)| session.parent_id.is_none())
+        .map(|(index, session)| (session.meta.id.as_str(), index))
+        .collect::<BTreeMap<_, _>>();
+
+    let mut roots = Vec::new();
+    let mut children: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for index in indices {
+        let parent = sessions
+            .get(*index)
+            .and_then(|session| session.parent_id.as_deref())
+            .and_then(|parent_id| parents.get(parent_id));
+        match parent {
+            Some(parent) => children.entry(*parent).or_default().push(*index),
+            None => roots.push(*index),
+        }
+    }
+
+    let mut nested = Vec::with_capacity(indices.len());
+    for index in roots {
+        nested.push(index);
+        nested.extend(children.remove(&index).unwrap_or_default());
+    }
+    nested
+}
+
+/// Re-sorts the Sessions list, keeping the highlighted session highlighted.
+fn set_sessions_order(model: &AppModel, view: &mut SessionsView, order: SessionsOrder) {
+    let selected_log_path = view
+        .current_project(&model.data.projects)
+        .and_then(|project| {
+            view.filtered_indices
+                .get(view.session_selected)
+                .copied()
+                .and_then(|index| project.sessions.get(index))
+        })
+        .map(|session| session.log_path.clone());
+
+    view.order = order;
+
+    if let Some(project) = view.current_project(&model.data.projects) {
+        apply_session_filter(
+            &project.sessions,
+            model.session_index.as_ref(),
+            view,
+            model.engine_filter,
+        );
+        if let Some(log_path) = selected_log_path
+            && let Some(pos) = view.filtered_indices.iter().position(|index| {
+                project
+                    .sessions
+                    .get(*index)
+                    .is_some_and(|session| session.log_path == log_path)
+            })
+        {
+            view.session_selected = pos;
+        }
+    } else {
+        view.filtered_indices.clear();
+        view.session_selected = 0;
+    }
+
+    clear_sessions_selection(view);
+}
+
 fn update_sessions(
     mut model: AppModel,
     mut view: SessionsView,
@@ -4290,13 +7537,67 @@ fn update_sessions(
             };
             return (model, AppCommand::OpenSessionStats { session });
         }
-        KeyCode::Char('x') | KeyCode::Char('X') if new_modifier => {
-            let selected_log_path = view
+        KeyCode::F(6) => {
+            model.show_session_columns = !model.show_session_columns;
+            model.view = View::Sessions(view);
+            return (model, AppCommand::None);
+        }
+        KeyCode::Char('k') | KeyCode::Char('K') if new_modifier => {
+            let mut selected = view
                 .current_project(&model.data.projects)
-                .and_then(|project| {
-                    view.filtered_indices
-                        .get(view.session_selected)
-                        .copied()
+                .map(|project| {
+                    project
+                        .sessions
+                        .iter()
+                        .filter(|session| view.selected_log_paths.contains(&session.log_path))
+                        .cloned()
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default();
+            selected.sort_by(|a, b| a.meta.started_at_rfc3339.cmp(&b.meta.started_at_rfc3339));
+            model.view = View::Sessions(view);
+            let Ok([a, b]) = <[SessionSummary; 2]>::try_from(selected) else {
+                model.notice = Some("Select two sessions (Tab) to compare.".to_string());
+                return (model, AppCommand::None);
+            };
+            return (model, AppCommand::OpenSessionDiff { a, b });
+        }
+        KeyCode::Char('u') | KeyCode::Char('U') if new_modifier => {
+            if let Some(project) = view.current_project(&model.data.projects) {
+                let parent_id = view
+                    .filtered_indices
+                    .get(view.session_selected)
+                    .and_then(|index| project.sessions.get(*index))
+                    .and_then(|session| session.parent_id.clone());
+                match parent_id {
+                    None => model.notice = Some("Not a subagent session.".to_string()),
+                    Some(parent_id) => {
+                        let position = view.filtered_indices.iter().position(|index| {
+                            project.sessions.get(*index).is_some_and(|session| {
+                                session.parent_id.is_none() && session.meta.id == parent_id
+                            })
+                        });
+                        match position {
+                            Some(position) => view.session_selected = position,
+                            None => {
+                                model.notice = Some(format!(
+                                    "Parent session {parent_id} is not in this list."
+                                ));
+                            }
+                        }
+                    }
+                }
+            }
+            model.view = View::Sessions(view);
+            return (model, AppCommand::None);
+        }
+        KeyCode::Char('x') | KeyCode::Char('X') if new_modifier => {
+            let selected_log_path = view
+                .current_project(&model.data.projects)
+                .and_then(|project| {
+                    view.filtered_indices
+                        .get(view.session_selected)
+                        .copied()
                         .and_then(|index| project.sessions.get(index))
                 })
                 .map(|session| session.log_path.clone());
@@ -4329,7 +7630,7 @@ fn update_sessions(
             model.view = View::Sessions(view);
             return (model, AppCommand::None);
         }
-        KeyCode::Char('o') | KeyCode::Char('O') if new_modifier => {
+        KeyCode::Char('f') | KeyCode::Char('F') if new_modifier => {
             let selected_log_path = view
                 .current_project(&model.data.projects)
                 .and_then(|project| {
@@ -4340,7 +7641,7 @@ fn update_sessions(
                 })
                 .map(|session| session.log_path.clone());
 
-            view.order = view.order.toggle_tool_errors_first();
+            view.search_content = !view.search_content;
 
             if let Some(project) = view.current_project(&model.data.projects) {
                 apply_session_filter(
@@ -4349,15 +7650,15 @@ fn update_sessions(
                     &mut view,
                     model.engine_filter,
                 );
-                if let Some(log_path) = selected_log_path {
-                    if let Some(pos) = view.filtered_indices.iter().position(|index| {
+                if let Some(log_path) = selected_log_path
+                    && let Some(pos) = view.filtered_indices.iter().position(|index| {
                         project
                             .sessions
                             .get(*index)
                             .is_some_and(|session| session.log_path == log_path)
-                    }) {
-                        view.session_selected = pos;
-                    }
+                    })
+                {
+                    view.session_selected = pos;
                 }
             } else {
                 view.filtered_indices.clear();
@@ -4368,6 +7669,24 @@ fn update_sessions(
             model.view = View::Sessions(view);
             return (model, AppCommand::None);
         }
+        KeyCode::Char('o') | KeyCode::Char('O') if new_modifier => {
+            let order = view.order.toggle_tool_errors_first();
+            set_sessions_order(&model, &mut view, order);
+            model.view = View::Sessions(view);
+            return (model, AppCommand::None);
+        }
+        KeyCode::F(4) => {
+            let order = view.order.cycle();
+            set_sessions_order(&model, &mut view, order);
+            model.notice = Some(format!("Sort: {}", order.label()));
+            model.view = View::Sessions(view);
+            return (model, AppCommand::None);
+        }
+        KeyCode::Char('w') | KeyCode::Char('W') if new_modifier => {
+            let project_path = view.project_path.clone();
+            model.view = View::Sessions(view);
+            return (model, AppCommand::OpenProjectInIde { project_path });
+        }
         KeyCode::Char('e') | KeyCode::Char('E') if new_modifier => {
             let Some(project) = view.current_project(&model.data.projects) else {
                 return (model, AppCommand::None);
@@ -4407,12 +7726,15 @@ fn update_sessions(
 
             model.session_move = Some(SessionMoveDialog {
                 session: session.clone(),
-                editor: LineEditor::from_text(session.meta.cwd.display().to_string()),
+                picker: PathPicker::new(
+                    session.meta.cwd.display().to_string(),
+                    recent_project_paths(&model),
+                ),
             });
             model.help_open = false;
             model.system_menu = None;
             model.view = View::Sessions(view);
-            return (model, AppCommand::None);
+            return (model, AppCommand::RefreshPathPicker);
         }
         KeyCode::Enter => {
             let Some(project) = view.current_project(&model.data.projects) else {
@@ -4482,7 +7804,18 @@ fn update_sessions(
                 &model.data.projects,
                 &mut projects_view,
                 model.engine_filter,
+                &model.session_index,
+                model.projects_order,
             );
+            if let Some(pos) = projects_view.filtered_indices.iter().position(|&index| {
+                model
+                    .data
+                    .projects
+                    .get(index)
+                    .is_some_and(|project| project.project_path == view.project_path)
+            }) {
+                projects_view.selected = pos;
+            }
             let next = AppModel {
                 data: model.data.clone(),
                 session_index: model.session_index.clone(),
@@ -4490,6 +7823,7 @@ fn update_sessions(
                 notice: None,
                 update_hint: model.update_hint.clone(),
                 engine_filter: model.engine_filter,
+                engine_prefs: model.engine_prefs.clone(),
                 help_open: model.help_open,
                 system_menu: model.system_menu.clone(),
                 delete_confirm: model.delete_confirm.clone(),
@@ -4498,12 +7832,27 @@ fn update_sessions(
                 delete_sessions_confirm: model.delete_sessions_confirm.clone(),
                 session_rename: model.session_rename.clone(),
                 session_move: model.session_move.clone(),
+                opencode_write_back: model.opencode_write_back.clone(),
                 delete_task_confirm: model.delete_task_confirm.clone(),
                 delete_tasks_confirm: model.delete_tasks_confirm.clone(),
                 session_result_preview: model.session_result_preview.clone(),
                 session_stats_overlay: model.session_stats_overlay.clone(),
                 project_stats_overlay: model.project_stats_overlay.clone(),
+                audit_log_overlay: model.audit_log_overlay.clone(),
+                devices_overlay: model.devices_overlay.clone(),
+                session_diff_overlay: model.session_diff_overlay.clone(),
+                recent_sessions_overlay: model.recent_sessions_overlay.clone(),
+                quick_switcher: model.quick_switcher.clone(),
+                session_history: model.session_history.clone(),
+                show_session_columns: model.show_session_columns,
+                projects_order: model.projects_order,
+                list_columns: model.list_columns.clone(),
+                engine_models: model.engine_models.clone(),
+                spawn_args: model.spawn_args.clone(),
+                project_prefs: model.project_prefs.clone(),
+                project_rename: model.project_rename.clone(),
                 processes: model.processes.clone(),
+                external_processes: model.external_processes.clone(),
                 view: View::Projects(projects_view),
             };
             return (next, AppCommand::None);
@@ -4596,6 +7945,8 @@ fn update_sessions(
                     &model.data.projects,
                     &mut projects_view,
                     model.engine_filter,
+                    &model.session_index,
+                    model.projects_order,
                 );
                 let next = AppModel {
                     data: model.data.clone(),
@@ -4604,6 +7955,7 @@ fn update_sessions(
                     notice: None,
                     update_hint: model.update_hint.clone(),
                     engine_filter: model.engine_filter,
+                    engine_prefs: model.engine_prefs.clone(),
                     help_open: model.help_open,
                     system_menu: model.system_menu.clone(),
                     delete_confirm: model.delete_confirm.clone(),
@@ -4612,12 +7964,27 @@ fn update_sessions(
                     delete_sessions_confirm: model.delete_sessions_confirm.clone(),
                     session_rename: model.session_rename.clone(),
                     session_move: model.session_move.clone(),
+                    opencode_write_back: model.opencode_write_back.clone(),
                     delete_task_confirm: model.delete_task_confirm.clone(),
                     delete_tasks_confirm: model.delete_tasks_confirm.clone(),
                     session_result_preview: model.session_result_preview.clone(),
                     session_stats_overlay: model.session_stats_overlay.clone(),
                     project_stats_overlay: model.project_stats_overlay.clone(),
+                    audit_log_overlay: model.audit_log_overlay.clone(),
+                    devices_overlay: model.devices_overlay.clone(),
+                    session_diff_overlay: model.session_diff_overlay.clone(),
+                    recent_sessions_overlay: model.recent_sessions_overlay.clone(),
+                    quick_switcher: model.quick_switcher.clone(),
+                    session_history: model.session_history.clone(),
+                    show_session_columns: model.show_session_columns,
+                    projects_order: model.projects_order,
+                    list_columns: model.list_columns.clone(),
+                    engine_models: model.engine_models.clone(),
+                    spawn_args: model.spawn_args.clone(),
+                    project_prefs: model.project_prefs.clone(),
+                    project_rename: model.project_rename.clone(),
                     processes: model.processes.clone(),
+                    external_processes: model.external_processes.clone(),
                     view: View::Projects(projects_view),
                 };
                 return (next, AppCommand::None);
@@ -4641,6 +8008,7 @@ fn update_sessions(
                 notice: None,
                 update_hint: model.update_hint.clone(),
                 engine_filter: model.engine_filter,
+                engine_prefs: model.engine_prefs.clone(),
                 help_open: model.help_open,
                 system_menu: model.system_menu.clone(),
                 delete_confirm: model.delete_confirm.clone(),
@@ -4649,12 +8017,27 @@ fn update_sessions(
                 delete_sessions_confirm: model.delete_sessions_confirm.clone(),
                 session_rename: model.session_rename.clone(),
                 session_move: model.session_move.clone(),
+                opencode_write_back: model.opencode_write_back.clone(),
                 delete_task_confirm: model.delete_task_confirm.clone(),
                 delete_tasks_confirm: model.delete_tasks_confirm.clone(),
                 session_result_preview: model.session_result_preview.clone(),
                 session_stats_overlay: model.session_stats_overlay.clone(),
                 project_stats_overlay: model.project_stats_overlay.clone(),
+                audit_log_overlay: model.audit_log_overlay.clone(),
+                devices_overlay: model.devices_overlay.clone(),
+                session_diff_overlay: model.session_diff_overlay.clone(),
+                recent_sessions_overlay: model.recent_sessions_overlay.clone(),
+                quick_switcher: model.quick_switcher.clone(),
+                session_history: model.session_history.clone(),
+                show_session_columns: model.show_session_columns,
+                projects_order: model.projects_order,
+                list_columns: model.list_columns.clone(),
+                engine_models: model.engine_models.clone(),
+                spawn_args: model.spawn_args.clone(),
+                project_prefs: model.project_prefs.clone(),
+                project_rename: model.project_rename.clone(),
                 processes: model.processes.clone(),
+                external_processes: model.external_processes.clone(),
                 view: View::NewSession(new_session_view),
             };
             return (next, AppCommand::None);
@@ -4707,6 +8090,7 @@ fn update_sessions(
             notice: None,
             update_hint: model.update_hint.clone(),
             engine_filter: model.engine_filter,
+            engine_prefs: model.engine_prefs.clone(),
             help_open: model.help_open,
             system_menu: model.system_menu.clone(),
             delete_confirm: model.delete_confirm.clone(),
@@ -4715,18 +8099,45 @@ fn update_sessions(
             delete_sessions_confirm: model.delete_sessions_confirm.clone(),
             session_rename: model.session_rename.clone(),
             session_move: model.session_move.clone(),
+            opencode_write_back: model.opencode_write_back.clone(),
             delete_task_confirm: model.delete_task_confirm.clone(),
             delete_tasks_confirm: model.delete_tasks_confirm.clone(),
             session_result_preview: model.session_result_preview.clone(),
             session_stats_overlay: model.session_stats_overlay.clone(),
             project_stats_overlay: model.project_stats_overlay.clone(),
+            audit_log_overlay: model.audit_log_overlay.clone(),
+            devices_overlay: model.devices_overlay.clone(),
+            session_diff_overlay: model.session_diff_overlay.clone(),
+            recent_sessions_overlay: model.recent_sessions_overlay.clone(),
+            quick_switcher: model.quick_switcher.clone(),
+            session_history: model.session_history.clone(),
+            show_session_columns: model.show_session_columns,
+            projects_order: model.projects_order,
+            list_columns: model.list_columns.clone(),
+            engine_models: model.engine_models.clone(),
+            spawn_args: model.spawn_args.clone(),
+            project_prefs: model.project_prefs.clone(),
+            project_rename: model.project_rename.clone(),
             processes: model.processes.clone(),
+            external_processes: model.external_processes.clone(),
             view: View::Sessions(view),
         },
         AppCommand::None,
     )
 }
 
+/// F10 on spawn screens: steps `current` through the configured models of `engine`.
+fn cycle_agent_model(model: &mut AppModel, engine: AgentEngine, current: &mut Option<String>) {
+    if model.engine_models.for_engine(engine).is_empty() {
+        model.notice = Some(format!(
+            "No {} models configured (\"models\" in config.json).",
+            engine.label()
+        ));
+        return;
+    }
+    *current = model.engine_models.next(engine, current.as_deref());
+}
+
 fn update_new_session(
     mut model: AppModel,
     mut view: NewSessionView,
@@ -4736,23 +8147,228 @@ fn update_new_session(
         || key.modifiers.contains(KeyModifiers::SUPER)
         || key.modifiers.contains(KeyModifiers::META);
 
+    if let Some(mut editor) = view.insert_file.take() {
+        match key.code {
+            KeyCode::Esc => {
+                model.view = View::NewSession(view);
+                return (model, AppCommand::None);
+            }
+            KeyCode::Enter => {
+                let path = editor.text.trim().to_string();
+                if path.is_empty() {
+                    model.view = View::NewSession(view);
+                    return (model, AppCommand::None);
+                }
+                if path == "-" {
+                    model.notice = Some("stdin is only available to `ccbox run`.".to_string());
+                    view.insert_file = Some(editor);
+                    model.view = View::NewSession(view);
+                    return (model, AppCommand::None);
+                }
+                view.insert_file = Some(editor);
+                model.view = View::NewSession(view);
+                return (
+                    model,
+                    AppCommand::NewSessionInsertFile {
+                        path: PathBuf::from(path),
+                    },
+                );
+            }
+            KeyCode::Backspace => editor.backspace(),
+            KeyCode::Left => editor.move_left(),
+            KeyCode::Right => editor.move_right(),
+            KeyCode::Home => editor.move_home(),
+            KeyCode::End => editor.move_end(),
+            KeyCode::Delete => editor.delete_forward(),
+            KeyCode::Char(character) if is_text_input_char(character) => {
+                editor.insert_char(character);
+            }
+            _ => {}
+        }
+        view.insert_file = Some(editor);
+        model.view = View::NewSession(view);
+        return (model, AppCommand::None);
+    }
+
+    if let Some(mut editor) = view.attach_file.take() {
+        match key.code {
+            KeyCode::Esc => {
+                view.editor.insert_char('@');
+                model.view = View::NewSession(view);
+                return (model, AppCommand::None);
+            }
+            KeyCode::Enter => {
+                let path = editor.text.trim().to_string();
+                if path.is_empty() {
+                    view.editor.insert_char('@');
+                    model.view = View::NewSession(view);
+                    return (model, AppCommand::None);
+                }
+                view.attach_file = Some(editor);
+                model.view = View::NewSession(view);
+                return (
+                    model,
+                    AppCommand::NewSessionAttachFile {
+                        path: PathBuf::from(path),
+                    },
+                );
+            }
+            KeyCode::Backspace => editor.backspace(),
+            KeyCode::Left => editor.move_left(),
+            KeyCode::Right => editor.move_right(),
+            KeyCode::Home => editor.move_home(),
+            KeyCode::End => editor.move_end(),
+            KeyCode::Delete => editor.delete_forward(),
+            KeyCode::Char(character) if is_text_input_char(character) => {
+                editor.insert_char(character);
+            }
+            _ => {}
+        }
+        view.attach_file = Some(editor);
+        model.view = View::NewSession(view);
+        return (model, AppCommand::None);
+    }
+
+    if let Some(mut editor) = view.image_path.take() {
+        match key.code {
+            KeyCode::Esc => {
+                model.view = View::NewSession(view);
+                return (model, AppCommand::None);
+            }
+            KeyCode::Char('v') | KeyCode::Char('V') if send_modifier => {
+                model.view = View::NewSession(view);
+                return (model, AppCommand::PastePromptImageFromClipboard);
+            }
+            KeyCode::Enter => {
+                let path = editor.text.trim().to_string();
+                model.view = View::NewSession(view);
+                if path.is_empty() {
+                    return (model, AppCommand::PastePromptImageFromClipboard);
+                }
+                if let View::NewSession(view) = &mut model.view {
+                    view.image_path = Some(editor);
+                }
+                return (
+                    model,
+                    AppCommand::InsertPromptImage {
+                        path: PathBuf::from(path),
+                    },
+                );
+            }
+            KeyCode::Backspace => editor.backspace(),
+            KeyCode::Left => editor.move_left(),
+            KeyCode::Right => editor.move_right(),
+            KeyCode::Home => editor.move_home(),
+            KeyCode::End => editor.move_end(),
+            KeyCode::Delete => editor.delete_forward(),
+            KeyCode::Char(character) if is_text_input_char(character) => {
+                editor.insert_char(character);
+            }
+            _ => {}
+        }
+        view.image_path = Some(editor);
+        model.view = View::NewSession(view);
+        return (model, AppCommand::None);
+    }
+
+    if let Some(mut picker) = view.working_dir_picker.take() {
+        match picker.handle_key(key) {
+            PathPickerAction::None => view.working_dir_picker = Some(picker),
+            PathPickerAction::Refresh => {
+                view.working_dir_picker = Some(picker);
+                model.view = View::NewSession(view);
+                return (model, AppCommand::RefreshPathPicker);
+            }
+            PathPickerAction::Cancel => {}
+            PathPickerAction::Submit(path) => {
+                view.working_dir = path.filter(|path| *path != view.from_sessions.project_path);
+            }
+        }
+        model.view = View::NewSession(view);
+        return (model, AppCommand::None);
+    }
+
     match key.code {
         KeyCode::Esc => {
-            model.view = View::Sessions(view.from_sessions.clone());
+            model.view = match view.batch {
+                Some(batch) => View::Projects(batch.from_projects),
+                None => View::Sessions(view.from_sessions.clone()),
+            };
             return (model, AppCommand::None);
         }
+        KeyCode::Char('o') | KeyCode::Char('O') if send_modifier => {
+            view.insert_file = Some(LineEditor::new());
+        }
+        KeyCode::Char('i') | KeyCode::Char('I') if send_modifier => {
+            view.image_path = Some(LineEditor::new());
+        }
+        KeyCode::Char('v') | KeyCode::Char('V') if send_modifier => {
+            model.view = View::NewSession(view);
+            return (model, AppCommand::PastePromptImageFromClipboard);
+        }
+        KeyCode::Char('p') | KeyCode::Char('P') if send_modifier => {
+            if view.is_resume() {
+                model.notice = Some("Working directory is locked for resume.".to_string());
+            } else if view.batch.is_some() {
+                model.notice = Some("Working directory is not available for batches.".to_string());
+            } else {
+                let project_path = view.from_sessions.project_path.clone();
+                let current = view.working_dir.as_ref().map_or_else(
+                    || format!("{}{}", project_path.display(), std::path::MAIN_SEPARATOR),
+                    |dir| dir.display().to_string(),
+                );
+                view.working_dir_picker =
+                    Some(Box::new(PathPicker::new(current, vec![project_path])));
+                model.view = View::NewSession(view);
+                return (model, AppCommand::RefreshPathPicker);
+            }
+        }
         KeyCode::F(4) => {
-            if view.fork.is_some() {
-                model.notice = Some("I/O mode is locked for fork resume.".to_string());
+            if view.is_resume() {
+                model.notice = Some("I/O mode is locked for resume.".to_string());
             } else {
                 view.io_mode = view.io_mode.toggle();
             }
         }
+        KeyCode::F(5) => {
+            if view.is_resume() {
+                model.notice = Some("Auto-retry is not available for resume.".to_string());
+            } else {
+                view.retry = view.retry.toggle();
+            }
+        }
+        KeyCode::F(8) => {
+            if view.is_resume() {
+                model.notice = Some("Worktrees are not available for resume.".to_string());
+            } else {
+                view.worktree = !view.worktree;
+            }
+        }
+        KeyCode::F(10) => {
+            if view.is_resume() {
+                model.notice = Some("Model is locked for resume.".to_string());
+            } else {
+                cycle_agent_model(&mut model, view.engine, &mut view.agent_model);
+            }
+        }
+        KeyCode::F(6) => {
+            if view.is_resume() {
+                model.notice = Some("A/B runs are not available for resume.".to_string());
+            } else if view.batch.is_some() {
+                model.notice = Some("A/B runs are not available for batches.".to_string());
+            } else {
+                view.ab_compare = !view.ab_compare;
+            }
+        }
         KeyCode::BackTab => {
-            if view.fork.is_some() {
-                model.notice = Some("Engine is locked to Codex for fork resume.".to_string());
+            if view.is_resume() {
+                model.notice = Some(format!(
+                    "Engine is locked to {} for resume.",
+                    view.engine.label()
+                ));
             } else {
                 view.engine = view.engine.toggle();
+                view.agent_model = None;
             }
         }
         KeyCode::Enter if send_modifier => {
@@ -4762,6 +8378,31 @@ fn update_new_session(
                 model.view = View::NewSession(view);
                 return (model, AppCommand::None);
             }
+            let prompt = append_prompt_images(
+                inline_attachments(&prompt, &view.attachments),
+                &view.image_paths,
+            );
+            let image_paths = view.image_paths.clone();
+
+            if let Some(batch) = view.batch {
+                model.view = View::Processes(ProcessesView::new(
+                    Box::new(View::Projects(batch.from_projects)),
+                    model.processes.len(),
+                ));
+                return (
+                    model,
+                    AppCommand::SpawnAgentBatch {
+                        engine: view.engine,
+                        project_paths: batch.project_paths,
+                        prompt,
+                        image_paths,
+                        io_mode: view.io_mode,
+                        retry: view.retry,
+                        worktree: view.worktree,
+                        agent_model: view.agent_model,
+                    },
+                );
+            }
 
             model.view = View::Sessions(view.from_sessions.clone());
             if let Some(fork) = view.fork.clone() {
@@ -4770,17 +8411,45 @@ fn update_new_session(
                     AppCommand::ForkResumeCodexFromTimeline { fork, prompt },
                 );
             }
+            if let Some(session) = view.resume.clone() {
+                return (model, AppCommand::ResumeOpenCodeSession { session, prompt });
+            }
 
-            let project_path = view.from_sessions.project_path.clone();
+            let project_path = view
+                .working_dir
+                .clone()
+                .unwrap_or_else(|| view.from_sessions.project_path.clone());
             let engine = view.engine;
             let io_mode = view.io_mode;
+            let retry = view.retry;
+            let worktree = view.worktree;
+            let agent_model = view.agent_model.clone();
+            if view.ab_compare {
+                return (
+                    model,
+                    AppCommand::SpawnAgentComparison {
+                        engines: [engine, engine.toggle()],
+                        project_path,
+                        prompt,
+                        image_paths,
+                        io_mode,
+                        retry,
+                        worktree,
+                        agent_model,
+                    },
+                );
+            }
             return (
                 model,
                 AppCommand::SpawnAgentSession {
                     engine,
                     project_path,
                     prompt,
+                    image_paths,
                     io_mode,
+                    retry,
+                    worktree,
+                    agent_model,
                 },
             );
         }
@@ -4814,6 +8483,15 @@ fn update_new_session(
         KeyCode::Tab => {
             view.editor.insert_str("    ");
         }
+        KeyCode::Char('@')
+            if view.batch.is_none()
+                && view
+                    .editor
+                    .char_before_cursor()
+                    .is_none_or(char::is_whitespace) =>
+        {
+            view.attach_file = Some(LineEditor::new());
+        }
         KeyCode::Char(character) => {
             if is_text_input_char(character) {
                 view.editor.insert_char(character);
@@ -4826,11 +8504,171 @@ fn update_new_session(
     (model, AppCommand::None)
 }
 
+fn update_activity(
+    mut model: AppModel,
+    mut view: ActivityView,
+    key: KeyEvent,
+) -> (AppModel, AppCommand) {
+    let last = view.rows.len().saturating_sub(1);
+    match key.code {
+        KeyCode::Esc | KeyCode::Backspace => {
+            model.view = *view.return_to;
+            return (model, AppCommand::None);
+        }
+        KeyCode::Up => {
+            view.selected = view.selected.saturating_sub(1);
+        }
+        KeyCode::Down => {
+            view.selected = (view.selected + 1).min(last);
+        }
+        KeyCode::PageUp => {
+            let step = page_step_standard_list(model.terminal_size);
+            view.selected = view.selected.saturating_sub(step);
+        }
+        KeyCode::PageDown => {
+            let step = page_step_standard_list(model.terminal_size);
+            view.selected = (view.selected + step).min(last);
+        }
+        KeyCode::Char('+') | KeyCode::Char('=') | KeyCode::Char('-') => {
+            view.window = if key.code == KeyCode::Char('-') {
+                view.window.saturating_sub(1)
+            } else {
+                (view.window + 1).min(ACTIVITY_WINDOWS_MINS.len() - 1)
+            };
+            let as_of = view.as_of;
+            view.refresh(
+                &model.processes,
+                &model.external_processes,
+                &model.data.projects,
+                as_of,
+            );
+        }
+        KeyCode::Enter => {
+            let command = match view.rows.get(view.selected) {
+                Some(ActivityRow::Process { process_id }) => {
+                    let Some(process) = model
+                        .processes
+                        .iter()
+                        .find(|process| process.id == *process_id)
+                    else {
+                        model.view = View::Activity(view);
+                        return (model, AppCommand::None);
+                    };
+                    // Without a reported session the output is the closest thing to watch.
+                    match &process.session_log_path {
+                        Some(log_path) => AppCommand::OpenSessionDetailByLogPath {
+                            project_path: process.project_path.clone(),
+                            log_path: log_path.clone(),
+                        },
+                        None => AppCommand::OpenProcessOutput {
+                            process_id: process.id.clone(),
+                            kind: ProcessOutputKind::Stdout,
+                        },
+                    }
+                }
+                Some(ActivityRow::External { pid }) => {
+                    let session = model
+                        .external_processes
+                        .iter()
+                        .find(|external| external.agent.pid == *pid)
+                        .and_then(|external| external.session.as_ref());
+                    match session {
+                        Some(session) => AppCommand::OpenSessionDetailByLogPath {
+                            project_path: session.project_path().to_path_buf(),
+                            log_path: session.log_path.clone(),
+                        },
+                        None => {
+                            model.notice = Some(format!("No session found yet for pid {pid}."));
+                            AppCommand::None
+                        }
+                    }
+                }
+                Some(ActivityRow::Session {
+                    project_path,
+                    log_path,
+                }) => AppCommand::OpenSessionDetailByLogPath {
+                    project_path: project_path.clone(),
+                    log_path: log_path.clone(),
+                },
+                None => AppCommand::None,
+            };
+            model.view = View::Activity(view);
+            return (model, command);
+        }
+        _ => {}
+    }
+
+    model.view = View::Activity(view);
+    (model, AppCommand::None)
+}
+
 fn update_processes(
     mut model: AppModel,
     mut view: ProcessesView,
     key: KeyEvent,
 ) -> (AppModel, AppCommand) {
+    if let Some(input) = view.label_input.take() {
+        let command = update_process_label_input(&mut model, &mut view, input, key);
+        model.view = View::Processes(view);
+        return (model, command);
+    }
+    if let Some(confirm) = view.kill_project_confirm.take() {
+        return update_kill_project_confirm(model, view, confirm, key);
+    }
+    if let Some(input) = view.export_input.take() {
+        let process_id = view
+            .selected_process(&model.processes)
+            .map(|process| process.id.clone())
+            .unwrap_or_default();
+        let (input, command) =
+            update_export_input(input, key, process_id, ProcessOutputKind::Stdout);
+        view.export_input = input;
+        model.view = View::Processes(view);
+        return (model, command);
+    }
+    if let Some(input) = view.reply_input.take() {
+        let process_id = view
+            .selected_process(&model.processes)
+            .map(|process| process.id.clone())
+            .unwrap_or_default();
+        let (input, command) = update_reply_input(input, key, process_id);
+        view.reply_input = input;
+        model.view = View::Processes(view);
+        return (model, command);
+    }
+    let new_modifier = key.modifiers.contains(KeyModifiers::CONTROL)
+        || key.modifiers.contains(KeyModifiers::SUPER)
+        || key.modifiers.contains(KeyModifiers::META);
+
+    let rows = view.rows(&model.processes);
+    let row_count = view.row_count(&model.processes, &model.external_processes);
+    let selected_group = match rows.get(view.selected) {
+        Some(ProcessRow::Group { label, .. }) => Some(label.clone()),
+        _ => None,
+    };
+    // External rows are read-only: Enter opens the detected session, process keys find nothing.
+    if let Some(external) = view.selected_external(&model.processes, &model.external_processes)
+        && key.code == KeyCode::Enter
+    {
+        match &external.session {
+            Some(session) => {
+                let command = AppCommand::OpenSessionDetailByLogPath {
+                    project_path: session.project_path().to_path_buf(),
+                    log_path: session.log_path.clone(),
+                };
+                model.view = View::Processes(view);
+                return (model, command);
+            }
+            None => {
+                model.notice = Some(format!(
+                    "No session found yet for pid {}.",
+                    external.agent.pid
+                ));
+                model.view = View::Processes(view);
+                return (model, AppCommand::None);
+            }
+        }
+    }
     match key.code {
         KeyCode::Esc | KeyCode::Backspace => {
             model.view = *view.return_to;
@@ -4840,8 +8678,8 @@ fn update_processes(
             view.selected = view.selected.saturating_sub(1);
         }
         KeyCode::Down => {
-            if !model.processes.is_empty() {
-                view.selected = (view.selected + 1).min(model.processes.len().saturating_sub(1));
+            if row_count > 0 {
+                view.selected = (view.selected + 1).min(row_count - 1);
             }
         }
         KeyCode::PageUp => {
@@ -4849,31 +8687,87 @@ fn update_processes(
             view.selected = view.selected.saturating_sub(step);
         }
         KeyCode::PageDown => {
-            if !model.processes.is_empty() {
+            if row_count > 0 {
                 let step = page_step_standard_list(model.terminal_size);
-                view.selected = (view.selected + step).min(model.processes.len().saturating_sub(1));
+                view.selected = (view.selected + step).min(row_count - 1);
             }
         }
-        KeyCode::Char('s') | KeyCode::Char('S') => {
-            if let Some(process_id) = model
+        KeyCode::Enter | KeyCode::Char(' ') if selected_group.is_some() => {
+            let label = selected_group.unwrap_or_default();
+            if !view.collapsed.remove(&label) {
+                view.collapsed.insert(label);
+            }
+        }
+        KeyCode::Char('g') | KeyCode::Char('G') => {
+            let current = match &selected_group {
+                Some(label) => Some(label.clone()),
+                None => view
+                    .selected_process(&model.processes)
+                    .map(|process| process.label.clone().unwrap_or_default()),
+            };
+            view.label_input = current;
+        }
+        KeyCode::Char('k') | KeyCode::Char('K') if new_modifier => {
+            let project_path = match &selected_group {
+                Some(label) => model
+                    .processes
+                    .iter()
+                    .find(|process| process.label.as_ref() == Some(label))
+                    .map(|process| process.project_path.clone()),
+                None => view
+                    .selected_process(&model.processes)
+                    .map(|process| process.project_path.clone()),
+            };
+            if let Some(project_path) = project_path {
+                let process_ids = running_process_ids_in_project(&model.processes, &project_path);
+                if process_ids.is_empty() {
+                    model.notice = Some(format!(
+                        "No running processes in {}.",
+                        project_path.display()
+                    ));
+                } else {
+                    view.kill_project_confirm = Some(KillProjectConfirmDialog {
+                        project_path,
+                        process_ids,
+                        selection: DeleteConfirmSelection::Cancel,
+                    });
+                }
+            }
+        }
+        KeyCode::Char('k') | KeyCode::Char('K') if selected_group.is_some() => {
+            let force = key.code == KeyCode::Char('K');
+            let label = selected_group.unwrap_or_default();
+            // Only a force kill reaches processes already stopping; `k` would restart their grace
+            // period.
+            let process_ids = model
                 .processes
-                .get(view.selected)
+                .iter()
+                .filter(|process| {
+                    process.label.as_ref() == Some(&label)
+                        && match process.status {
+                            ProcessStatus::Stopping => force,
+                            _ => process.status.is_active(),
+                        }
+                })
                 .map(|process| process.id.clone())
-            {
+                .collect::<Vec<_>>();
+            if process_ids.is_empty() {
+                model.notice = Some(format!("No running processes in {label}."));
+            } else {
                 model.view = View::Processes(view);
                 return (
                     model,
-                    AppCommand::OpenProcessOutput {
-                        process_id,
-                        kind: ProcessOutputKind::Stdout,
+                    AppCommand::KillProcessGroup {
+                        label,
+                        process_ids,
+                        force,
                     },
                 );
             }
         }
-        KeyCode::Char('e') | KeyCode::Char('E') => {
-            if let Some(process_id) = model
-                .processes
-                .get(view.selected)
+        KeyCode::Char('s') | KeyCode::Char('S') => {
+            if let Some(process_id) = view
+                .selected_process(&model.processes)
                 .map(|process| process.id.clone())
             {
                 model.view = View::Processes(view);
@@ -4881,20 +8775,77 @@ fn update_processes(
                     model,
                     AppCommand::OpenProcessOutput {
                         process_id,
-                        kind: ProcessOutputKind::Stderr,
+                        kind: ProcessOutputKind::Stdout,
                     },
                 );
             }
         }
-        KeyCode::Char('l') | KeyCode::Char('L') => {
-            if let Some(process_id) = model
-                .processes
-                .get(view.selected)
-                .map(|process| process.id.clone())
-            {
-                model.view = View::Processes(view);
-                return (
-                    model,
+        KeyCode::Char('w') | KeyCode::Char('W') => {
+            if let Some(process) = view.selected_process(&model.processes) {
+                view.export_input =
+                    Some(default_export_path(&process.id, ProcessOutputKind::Stdout));
+            }
+        }
+        KeyCode::Char('t') | KeyCode::Char('T') => {
+            if let Some(process) = view.selected_process(&model.processes) {
+                match process.task_id.clone() {
+                    Some(task_id) => {
+                        let from_tasks =
+                            TasksView::new(Box::new(View::Processes(view.clone())), Vec::new());
+                        model.view = View::Processes(view);
+                        return (
+                            model,
+                            AppCommand::OpenTaskDetail {
+                                from_tasks,
+                                task_id,
+                            },
+                        );
+                    }
+                    None => {
+                        model.notice = Some(format!("{} was not spawned from a task.", process.id));
+                    }
+                }
+            }
+        }
+        KeyCode::Char('y') | KeyCode::Char('Y') => {
+            if let Some(process_id) = view
+                .selected_process(&model.processes)
+                .map(|process| process.id.clone())
+            {
+                model.view = View::Processes(view);
+                return (
+                    model,
+                    AppCommand::ExportProcessOutput {
+                        process_id,
+                        kind: ProcessOutputKind::Stdout,
+                        destination: None,
+                    },
+                );
+            }
+        }
+        KeyCode::Char('e') | KeyCode::Char('E') => {
+            if let Some(process_id) = view
+                .selected_process(&model.processes)
+                .map(|process| process.id.clone())
+            {
+                model.view = View::Processes(view);
+                return (
+                    model,
+                    AppCommand::OpenProcessOutput {
+                        process_id,
+                        kind: ProcessOutputKind::Stderr,
+                    },
+                );
+            }
+        }
+        KeyCode::Char('l') | KeyCode::Char('L') => {
+            if let Some(process_id) = view
+                .selected_process(&model.processes)
+                .map(|process| process.id.clone())
+            {
+                model.view = View::Processes(view);
+                return (
+                    model,
                     AppCommand::OpenProcessOutput {
                         process_id,
                         kind: ProcessOutputKind::Log,
@@ -4903,17 +8854,33 @@ fn update_processes(
             }
         }
         KeyCode::Char('k') | KeyCode::Char('K') => {
-            if let Some(process_id) = model
-                .processes
-                .get(view.selected)
+            if let Some(process_id) = view
+                .selected_process(&model.processes)
                 .map(|process| process.id.clone())
             {
                 model.view = View::Processes(view);
-                return (model, AppCommand::KillProcess { process_id });
+                return (
+                    model,
+                    AppCommand::KillProcess {
+                        process_id,
+                        force: key.code == KeyCode::Char('K'),
+                    },
+                );
+            }
+        }
+        KeyCode::Char('r') | KeyCode::Char('R') => {
+            if let Some(process) = view.selected_process(&model.processes) {
+                if !process.io_mode.is_tty() {
+                    model.notice = Some("Process is not a TTY session.".to_string());
+                } else if !process.status.is_running() {
+                    model.notice = Some("Process is not running.".to_string());
+                } else {
+                    view.reply_input = Some(String::new());
+                }
             }
         }
         KeyCode::Char('a') | KeyCode::Char('A') => {
-            if let Some(process) = model.processes.get(view.selected) {
+            if let Some(process) = view.selected_process(&model.processes) {
                 if !process.io_mode.is_tty() {
                     model.notice = Some("Process is not a TTY session.".to_string());
                     model.view = View::Processes(view);
@@ -4931,7 +8898,7 @@ fn update_processes(
         }
         KeyCode::Enter => {
             if let Some((project_path, session_log_path)) =
-                model.processes.get(view.selected).map(|process| {
+                view.selected_process(&model.processes).map(|process| {
                     (
                         process.project_path.clone(),
                         process.session_log_path.clone(),
@@ -4955,34 +8922,305 @@ fn update_processes(
         _ => {}
     }
 
-    view.selected = view.selected.min(model.processes.len().saturating_sub(1));
+    view.selected = view.selected.min(
+        view.row_count(&model.processes, &model.external_processes)
+            .saturating_sub(1),
+    );
     model.view = View::Processes(view);
     (model, AppCommand::None)
 }
 
+/// Ids of the active processes spawned in `project_path` or below it (worktrees included).
+pub fn running_process_ids_in_project(
+    processes: &[ProcessInfo],
+    project_path: &Path,
+) -> Vec<String> {
+    processes
+        .iter()
+        .filter(|process| {
+            process.status.is_active() && process.project_path.starts_with(project_path)
+        })
+        .map(|process| process.id.clone())
+        .collect()
+}
+
+fn update_kill_project_confirm(
+    mut model: AppModel,
+    mut view: ProcessesView,
+    mut confirm: KillProjectConfirmDialog,
+    key: KeyEvent,
+) -> (AppModel, AppCommand) {
+    let kill = match key.code {
+        KeyCode::Esc | KeyCode::Backspace | KeyCode::Char('n') | KeyCode::Char('N') => false,
+        KeyCode::Enter => confirm.selection == DeleteConfirmSelection::Delete,
+        KeyCode::Char('y') | KeyCode::Char('Y') => true,
+        KeyCode::Left | KeyCode::Right => {
+            confirm.selection = confirm.selection.toggle();
+            view.kill_project_confirm = Some(confirm);
+            model.view = View::Processes(view);
+            return (model, AppCommand::None);
+        }
+        _ => {
+            view.kill_project_confirm = Some(confirm);
+            model.view = View::Processes(view);
+            return (model, AppCommand::None);
+        }
+    };
+    model.view = View::Processes(view);
+    if !kill {
+        return (model, AppCommand::None);
+    }
+    (
+        model,
+        AppCommand::KillProcessGroup {
+            label: confirm.project_path.display().to_string(),
+            process_ids: confirm.process_ids,
+            force: false,
+        },
+    )
+}
+
+/// Edits the `g` label: Enter applies it to the selected process, or renames the selected group;
+/// an empty label ungroups. The selection follows the relabelled process.
+fn update_process_label_input(
+    model: &mut AppModel,
+    view: &mut ProcessesView,
+    mut input: String,
+    key: KeyEvent,
+) -> AppCommand {
+    match key.code {
+        KeyCode::Esc => {}
+        KeyCode::Enter => {
+            let label = Some(input.trim().to_string()).filter(|label| !label.is_empty());
+            let rows = view.rows(&model.processes);
+            let relabel = |process: &ProcessInfo| match rows.get(view.selected) {
+                Some(ProcessRow::Group { label, .. }) => process.label.as_ref() == Some(label),
+                Some(ProcessRow::Process { index, .. }) => model.processes[*index].id == process.id,
+                None => false,
+            };
+            let ids = model
+                .processes
+                .iter()
+                .filter(|process| relabel(process))
+                .map(|process| process.id.clone())
+                .collect::<Vec<_>>();
+            if let Some(ProcessRow::Group { label: old, .. }) = rows.get(view.selected)
+                && view.collapsed.remove(old)
+                && let Some(label) = &label
+            {
+                view.collapsed.insert(label.clone());
+            }
+            for process in &mut model.processes {
+                if ids.contains(&process.id) {
+                    process.label = label.clone();
+                }
+            }
+            if let Some(first) = ids.first() {
+                let selected = match &label {
+                    Some(label) if view.collapsed.contains(label) => {
+                        view.row_of(&model.processes, first)
+                    }
+                    Some(label) => view.rows(&model.processes).iter().position(|row| {
+                        matches!(row, ProcessRow::Group { label: group, .. } if group == label)
+                    }),
+                    None => view.row_of(&model.processes, first),
+                };
+                view.selected = selected.unwrap_or(view.selected);
+            }
+            model.notice = Some(match label {
+                Some(label) => format!("Labelled {} process(es) {label}.", ids.len()),
+                None => format!("Ungrouped {} process(es).", ids.len()),
+            });
+            return AppCommand::RecordProcessLabels { process_ids: ids };
+        }
+        KeyCode::Backspace => {
+            input.pop();
+            view.label_input = Some(input);
+        }
+        KeyCode::Char(c)
+            if !key
+                .modifiers
+                .intersects(KeyModifiers::CONTROL | KeyModifiers::SUPER) =>
+        {
+            input.push(c);
+            view.label_input = Some(input);
+        }
+        _ => view.label_input = Some(input),
+    }
+    AppCommand::None
+}
+
+/// `p3-stdout.log`, relative to the directory ccbox was started in.
+fn default_export_path(process_id: &str, kind: ProcessOutputKind) -> String {
+    format!("{process_id}-{}.log", kind.label())
+}
+
+/// Edits the `w` save path; Enter returns the export command, Esc (or an empty path) cancels.
+/// The returned input is `None` once editing is over.
+fn update_export_input(
+    mut input: String,
+    key: KeyEvent,
+    process_id: String,
+    kind: ProcessOutputKind,
+) -> (Option<String>, AppCommand) {
+    match key.code {
+        KeyCode::Esc => (None, AppCommand::None),
+        KeyCode::Enter => {
+            let path = input.trim();
+            if path.is_empty() || process_id.is_empty() {
+                return (None, AppCommand::None);
+            }
+            (
+                None,
+                AppCommand::ExportProcessOutput {
+                    process_id,
+                    kind,
+                    destination: Some(PathBuf::from(path)),
+                },
+            )
+        }
+        KeyCode::Backspace => {
+            input.pop();
+            (Some(input), AppCommand::None)
+        }
+        KeyCode::Char(c)
+            if !key
+                .modifiers
+                .intersects(KeyModifiers::CONTROL | KeyModifiers::SUPER) =>
+        {
+            input.push(c);
+            (Some(input), AppCommand::None)
+        }
+        _ => (Some(input), AppCommand::None),
+    }
+}
+
+/// Edits the `r` reply; Enter sends it (an empty reply sends just Enter), Esc cancels. The
+/// returned input is `None` once editing is over.
+fn update_reply_input(
+    mut input: String,
+    key: KeyEvent,
+    process_id: String,
+) -> (Option<String>, AppCommand) {
+    match key.code {
+        KeyCode::Esc => (None, AppCommand::None),
+        KeyCode::Enter if process_id.is_empty() => (None, AppCommand::None),
+        KeyCode::Enter => (
+            None,
+            AppCommand::WriteProcessTty {
+                process_id,
+                text: input,
+            },
+        ),
+        KeyCode::Backspace => {
+            input.pop();
+            (Some(input), AppCommand::None)
+        }
+        KeyCode::Char(c)
+            if !key
+                .modifiers
+                .intersects(KeyModifiers::CONTROL | KeyModifiers::SUPER) =>
+        {
+            input.push(c);
+            (Some(input), AppCommand::None)
+        }
+        _ => (Some(input), AppCommand::None),
+    }
+}
+
+/// Edits the `/` query: Enter applies it and jumps to the next matching line, an empty query
+/// clears the search.
+fn update_process_output_search_input(
+    model: &mut AppModel,
+    view: &mut ProcessOutputView,
+    mut input: String,
+    key: KeyEvent,
+) {
+    match key.code {
+        KeyCode::Esc => {}
+        KeyCode::Enter => {
+            let query = input.trim().to_string();
+            view.search = Some(query.clone()).filter(|query| !query.is_empty());
+            if view.search.is_some() && !view.jump_to_match(true) {
+                model.notice = Some(format!("No matches for \"{query}\"."));
+            }
+        }
+        KeyCode::Backspace => {
+            input.pop();
+            view.search_input = Some(input);
+        }
+        KeyCode::Char(c)
+            if !key
+                .modifiers
+                .intersects(KeyModifiers::CONTROL | KeyModifiers::SUPER) =>
+        {
+            input.push(c);
+            view.search_input = Some(input);
+        }
+        _ => view.search_input = Some(input),
+    }
+}
+
 fn update_process_output(
     mut model: AppModel,
     mut view: ProcessOutputView,
     key: KeyEvent,
 ) -> (AppModel, AppCommand) {
+    if let Some(input) = view.search_input.take() {
+        update_process_output_search_input(&mut model, &mut view, input, key);
+        model.view = View::ProcessOutput(view);
+        return (model, AppCommand::None);
+    }
+    if let Some(input) = view.export_input.take() {
+        let (input, command) = update_export_input(input, key, view.process_id.clone(), view.kind);
+        view.export_input = input;
+        model.view = View::ProcessOutput(view);
+        return (model, command);
+    }
+
+    let page = page_step_standard_list(model.terminal_size);
     match key.code {
         KeyCode::Esc | KeyCode::Backspace => {
             model.view = *view.return_to;
             return (model, AppCommand::None);
         }
-        KeyCode::Up => {
-            view.scroll = view.scroll.saturating_sub(1);
+        KeyCode::Up => view.scroll_up(1, page),
+        KeyCode::Down => view.scroll_down(1),
+        KeyCode::PageUp => view.scroll_up(page, page),
+        KeyCode::PageDown => view.scroll_down(page),
+        KeyCode::Home => {
+            view.follow = false;
+            view.scroll = 0;
         }
-        KeyCode::Down => {
-            view.scroll = view.scroll.saturating_add(1);
+        KeyCode::End => view.follow = true,
+        KeyCode::Char('f') | KeyCode::Char('F') => {
+            if view.follow {
+                view.scroll_up(0, page);
+            } else {
+                view.follow = true;
+            }
         }
-        KeyCode::PageUp => {
-            let step = page_step_standard_list(model.terminal_size) as u16;
-            view.scroll = view.scroll.saturating_sub(step);
+        KeyCode::Char('/') => {
+            view.search_input = Some(view.search.clone().unwrap_or_default());
         }
-        KeyCode::PageDown => {
-            let step = page_step_standard_list(model.terminal_size) as u16;
-            view.scroll = view.scroll.saturating_add(step);
+        KeyCode::Char('w') | KeyCode::Char('W') => {
+            view.export_input = Some(default_export_path(&view.process_id, view.kind));
+        }
+        KeyCode::Char('y') | KeyCode::Char('Y') => {
+            let process_id = view.process_id.clone();
+            let kind = view.kind;
+            model.view = View::ProcessOutput(view);
+            return (
+                model,
+                AppCommand::ExportProcessOutput {
+                    process_id,
+                    kind,
+                    destination: None,
+                },
+            );
+        }
+        KeyCode::Char('n') | KeyCode::Char('N') if view.search.is_some() => {
+            view.jump_to_match(key.code == KeyCode::Char('n'));
         }
         KeyCode::Char('s') | KeyCode::Char('S') => {
             let process_id = view.process_id.clone();
@@ -5017,10 +9255,44 @@ fn update_process_output(
                 },
             );
         }
+        KeyCode::Char('r') | KeyCode::Char('R') => {
+            view.raw = !view.raw;
+        }
         KeyCode::Char('k') | KeyCode::Char('K') => {
             let process_id = view.process_id.clone();
             model.view = View::ProcessOutput(view);
-            return (model, AppCommand::KillProcess { process_id });
+            return (
+                model,
+                AppCommand::KillProcess {
+                    process_id,
+                    force: key.code == KeyCode::Char('K'),
+                },
+            );
+        }
+        KeyCode::Enter => {
+            let session = model
+                .processes
+                .iter()
+                .find(|process| process.id == view.process_id)
+                .and_then(|process| {
+                    process
+                        .session_log_path
+                        .clone()
+                        .map(|log_path| (process.project_path.clone(), log_path))
+                });
+            match session {
+                Some((project_path, log_path)) => {
+                    model.view = View::ProcessOutput(view);
+                    return (
+                        model,
+                        AppCommand::OpenSessionDetailByLogPath {
+                            project_path,
+                            log_path,
+                        },
+                    );
+                }
+                None => model.notice = Some("No session log path yet.".to_string()),
+            }
         }
         _ => {}
     }
@@ -5103,6 +9375,14 @@ fn update_session_detail(
         KeyCode::Esc | KeyCode::Backspace => {
             if view.context_overlay_open {
                 view.context_overlay_open = false;
+            } else if let Some(mut all_sessions) = view.from_all_sessions.take() {
+                apply_all_sessions_filter(
+                    &model.data.projects,
+                    &mut all_sessions,
+                    model.engine_filter,
+                );
+                model.view = View::AllSessions(all_sessions);
+                return (model, AppCommand::None);
             } else {
                 model.view = View::Sessions(view.from_sessions.clone());
                 return (model, AppCommand::None);
@@ -5124,13 +9404,75 @@ fn update_session_detail(
             model.help_open = false;
             model.system_menu = None;
         }
+        KeyCode::Char('y') | KeyCode::Char('Y') if new_modifier => {
+            let text = format_transcript(&view.items);
+            model.view = View::SessionDetail(view);
+            return (
+                model,
+                AppCommand::CopyToClipboard {
+                    text,
+                    what: "transcript".to_string(),
+                },
+            );
+        }
+        KeyCode::Char('y') => {
+            let selected = view.selected.min(view.items.len().saturating_sub(1));
+            let Some(item) = view.items.get(selected) else {
+                model.notice = Some("No timeline items.".to_string());
+                model.view = View::SessionDetail(view);
+                return (model, AppCommand::None);
+            };
+            let text = timeline_item_text(item);
+            model.view = View::SessionDetail(view);
+            return (
+                model,
+                AppCommand::CopyToClipboard {
+                    text,
+                    what: "selected item".to_string(),
+                },
+            );
+        }
+        KeyCode::Char('Y') => {
+            let Some(text) = view.last_output.clone() else {
+                model.notice = Some("No assistant output found.".to_string());
+                model.view = View::SessionDetail(view);
+                return (model, AppCommand::None);
+            };
+            model.view = View::SessionDetail(view);
+            return (
+                model,
+                AppCommand::CopyToClipboard {
+                    text,
+                    what: "last assistant output".to_string(),
+                },
+            );
+        }
+        KeyCode::Char('v') => {
+            let session = view.session.clone();
+            model.view = View::SessionDetail(view);
+            return (model, AppCommand::OpenInEditor { session });
+        }
         KeyCode::Char('p') | KeyCode::Char('P') if new_modifier => {
             model.session_move = Some(SessionMoveDialog {
                 session: view.session.clone(),
-                editor: LineEditor::from_text(view.session.meta.cwd.display().to_string()),
+                picker: PathPicker::new(
+                    view.session.meta.cwd.display().to_string(),
+                    recent_project_paths(&model),
+                ),
             });
             model.help_open = false;
             model.system_menu = None;
+            model.view = View::SessionDetail(view);
+            return (model, AppCommand::RefreshPathPicker);
+        }
+        KeyCode::Up | KeyCode::PageUp
+            if view.focus == SessionDetailFocus::Timeline
+                && view.selected == 0
+                && view.older_items > 0 =>
+        {
+            model.notice = Some(view.load_older_page());
+            model.view = View::SessionDetail(view);
+            return (model, AppCommand::None);
         }
         KeyCode::Up => match view.focus {
             SessionDetailFocus::Timeline => {
@@ -5198,9 +9540,19 @@ fn update_session_detail(
             }
         }
         KeyCode::Char('f') | KeyCode::Char('F') => {
+            if view.session.engine == SessionEngine::OpenCode {
+                // OpenCode resumes the whole session; it cannot branch from a timeline item.
+                let mut new_view = NewSessionView::new(view.from_sessions.clone());
+                new_view.engine = AgentEngine::OpenCode;
+                new_view.io_mode = SpawnIoMode::Pipes;
+                new_view.resume = Some(view.session.clone());
+                model.view = View::NewSession(new_view);
+                return (model, AppCommand::None);
+            }
             if view.session.engine != SessionEngine::Codex {
-                model.notice =
-                    Some("Fork/resume is only available for Codex sessions.".to_string());
+                model.notice = Some(
+                    "Fork/resume is only available for Codex and OpenCode sessions.".to_string(),
+                );
                 model.view = View::SessionDetail(view);
                 return (model, AppCommand::None);
             }
@@ -5241,6 +9593,40 @@ fn update_session_detail(
                 model.notice = Some("No assistant output found.".to_string());
             }
         }
+        KeyCode::Char(character @ ('[' | ']' | '{' | '}')) => {
+            let (kind, is_forward) = match character {
+                '[' => (TimelineItemKind::User, false),
+                ']' => (TimelineItemKind::User, true),
+                '{' => (TimelineItemKind::Assistant, false),
+                _ => (TimelineItemKind::Assistant, true),
+            };
+            match find_item_of_kind(&view.items, view.selected, kind, is_forward) {
+                Some(index) => {
+                    view.selected = index;
+                    view.details_scroll = 0;
+                }
+                None => {
+                    let label = if kind == TimelineItemKind::User {
+                        "user"
+                    } else {
+                        "assistant"
+                    };
+                    let direction = if is_forward { "next" } else { "previous" };
+                    model.notice = Some(format!("No {direction} {label} message."));
+                }
+            }
+        }
+        KeyCode::Char('e') => match view.selected_output_index() {
+            Some(index) => {
+                if !view.expanded_outputs.remove(&index) {
+                    view.expanded_outputs.insert(index);
+                }
+                view.details_scroll = 0;
+            }
+            None => {
+                model.notice = Some("No tool output to expand.".to_string());
+            }
+        },
         KeyCode::Char('c') => {
             view.context_overlay_open = !view.context_overlay_open;
         }
@@ -5257,12 +9643,19 @@ fn update_session_detail(
     (model, AppCommand::None)
 }
 
+const TEAM_TASK_READ_ONLY_NOTICE: &str =
+    "Team tasks are read-only (synced from the task registry).";
+
 fn update_tasks(mut model: AppModel, mut view: TasksView, key: KeyEvent) -> (AppModel, AppCommand) {
     let send_modifier = key.modifiers.contains(KeyModifiers::CONTROL)
         || key.modifiers.contains(KeyModifiers::SUPER)
         || key.modifiers.contains(KeyModifiers::META);
     let shift = key.modifiers.contains(KeyModifiers::SHIFT);
 
+    if let Some(dialog) = view.schedule_dialog.take() {
+        return update_task_schedule_dialog(model, view, dialog, key);
+    }
+
     match key.code {
         KeyCode::F(3) => {
             let Some(task_index) = view.filtered_indices.get(view.selected).copied() else {
@@ -5371,6 +9764,13 @@ fn update_tasks(mut model: AppModel, mut view: TasksView, key: KeyEvent) -> (App
         }
         KeyCode::BackTab => {
             view.engine = view.engine.toggle();
+            view.agent_model = None;
+        }
+        KeyCode::F(10) => {
+            cycle_agent_model(&mut model, view.engine, &mut view.agent_model);
+        }
+        KeyCode::F(8) => {
+            view.worktree = !view.worktree;
         }
         KeyCode::Delete => {
             open_delete_task_confirm_from_tasks(&mut model, &view);
@@ -5386,6 +9786,8 @@ fn update_tasks(mut model: AppModel, mut view: TasksView, key: KeyEvent) -> (App
                 AppCommand::SpawnTask {
                     engine: view.engine,
                     task_id,
+                    worktree: view.worktree,
+                    agent_model: view.agent_model.clone(),
                 },
             );
         }
@@ -5401,14 +9803,42 @@ fn update_tasks(mut model: AppModel, mut view: TasksView, key: KeyEvent) -> (App
                 },
             );
         }
-        KeyCode::Char('n') | KeyCode::Char('N') => {
-            let project_path = default_task_create_project_path(&model, &view);
-            model.view = View::TaskCreate(TaskCreateView::new(view, project_path));
-            return (model, AppCommand::None);
-        }
-        KeyCode::Char(character) => {
-            if is_text_input_char(character) {
-                view.query.push(character);
+        KeyCode::Char('s') | KeyCode::Char('S') if send_modifier => {
+            let Some(task) = view
+                .filtered_indices
+                .get(view.selected)
+                .and_then(|index| view.tasks.get(*index))
+            else {
+                model.notice = Some("No task selected.".to_string());
+                model.view = View::Tasks(view);
+                return (model, AppCommand::None);
+            };
+            if task.id.is_team() {
+                model.notice = Some(TEAM_TASK_READ_ONLY_NOTICE.to_string());
+                model.view = View::Tasks(view);
+                return (model, AppCommand::None);
+            }
+            view.schedule_dialog = Some(TaskScheduleDialog {
+                task_id: task.id.clone(),
+                task_title: task.title.clone(),
+                engine: task
+                    .schedule
+                    .as_ref()
+                    .map(|schedule| schedule.engine)
+                    .unwrap_or(view.engine),
+                is_scheduled: task.schedule.is_some(),
+                editor: LineEditor::new(),
+                error: None,
+            });
+        }
+        KeyCode::Char('n') | KeyCode::Char('N') => {
+            let project_path = default_task_create_project_path(&model, &view);
+            model.view = View::TaskCreate(TaskCreateView::new(view, project_path));
+            return (model, AppCommand::None);
+        }
+        KeyCode::Char(character) => {
+            if is_text_input_char(character) {
+                view.query.push(character);
                 apply_task_filter(&mut view);
                 clear_tasks_selection(&mut view);
             }
@@ -5420,6 +9850,65 @@ fn update_tasks(mut model: AppModel, mut view: TasksView, key: KeyEvent) -> (App
     (model, AppCommand::None)
 }
 
+fn update_task_schedule_dialog(
+    mut model: AppModel,
+    mut view: TasksView,
+    mut dialog: TaskScheduleDialog,
+    key: KeyEvent,
+) -> (AppModel, AppCommand) {
+    match key.code {
+        KeyCode::Esc => {
+            model.view = View::Tasks(view);
+            return (model, AppCommand::None);
+        }
+        KeyCode::Enter => {
+            let input = dialog.editor.text.trim().to_string();
+            if input.is_empty() && dialog.is_scheduled {
+                let task_id = dialog.task_id;
+                return (
+                    model,
+                    AppCommand::CancelTaskSchedule {
+                        from_tasks: view,
+                        task_id,
+                    },
+                );
+            }
+            match parse_schedule_spec(&input) {
+                Ok(spec) => {
+                    let task_id = dialog.task_id;
+                    let engine = dialog.engine;
+                    return (
+                        model,
+                        AppCommand::ScheduleTask {
+                            from_tasks: view,
+                            task_id,
+                            engine,
+                            spec,
+                        },
+                    );
+                }
+                Err(error) => dialog.error = Some(error.to_string()),
+            }
+        }
+        KeyCode::BackTab => dialog.engine = dialog.engine.toggle(),
+        KeyCode::Backspace => dialog.editor.backspace(),
+        KeyCode::Left => dialog.editor.move_left(),
+        KeyCode::Right => dialog.editor.move_right(),
+        KeyCode::Home => dialog.editor.move_home(),
+        KeyCode::End => dialog.editor.move_end(),
+        KeyCode::Delete => dialog.editor.delete_forward(),
+        KeyCode::Char(character) if is_text_input_char(character) => {
+            dialog.editor.insert_char(character);
+            dialog.error = None;
+        }
+        _ => {}
+    }
+
+    view.schedule_dialog = Some(dialog);
+    model.view = View::Tasks(view);
+    (model, AppCommand::None)
+}
+
 fn update_task_create(
     mut model: AppModel,
     mut view: TaskCreateView,
@@ -5441,7 +9930,7 @@ fn update_task_create(
                     KeyCode::Char('v') | KeyCode::Char('V') if command_modifier => {
                         view.overlay = None;
                         model.view = View::TaskCreate(view);
-                        return (model, AppCommand::TaskCreatePasteImageFromClipboard);
+                        return (model, AppCommand::PastePromptImageFromClipboard);
                     }
                     KeyCode::Backspace => editor.backspace(),
                     KeyCode::Enter => {
@@ -5449,13 +9938,13 @@ fn update_task_create(
                         if path.is_empty() {
                             view.overlay = None;
                             model.view = View::TaskCreate(view);
-                            return (model, AppCommand::TaskCreatePasteImageFromClipboard);
+                            return (model, AppCommand::PastePromptImageFromClipboard);
                         }
                         view.overlay = Some(TaskCreateOverlay::ImagePath(editor));
                         model.view = View::TaskCreate(view);
                         return (
                             model,
-                            AppCommand::TaskCreateInsertImage {
+                            AppCommand::InsertPromptImage {
                                 path: PathBuf::from(path),
                             },
                         );
@@ -5474,35 +9963,20 @@ fn update_task_create(
                 }
                 view.overlay = Some(TaskCreateOverlay::ImagePath(editor));
             }
-            TaskCreateOverlay::ProjectPath(mut editor) => {
-                match key.code {
-                    KeyCode::Esc => {
-                        view.overlay = None;
-                        model.view = View::TaskCreate(view);
-                        return (model, AppCommand::None);
-                    }
-                    KeyCode::Backspace => editor.backspace(),
-                    KeyCode::Enter => {
-                        let path = editor.text.trim().to_string();
-                        view.project_path = PathBuf::from(path);
-                        view.overlay = None;
-                        model.view = View::TaskCreate(view);
-                        return (model, AppCommand::None);
-                    }
-                    KeyCode::Left => editor.move_left(),
-                    KeyCode::Right => editor.move_right(),
-                    KeyCode::Home => editor.move_home(),
-                    KeyCode::End => editor.move_end(),
-                    KeyCode::Delete => editor.delete_forward(),
-                    KeyCode::Char(character) => {
-                        if is_text_input_char(character) {
-                            editor.insert_char(character);
-                        }
-                    }
-                    _ => {}
+            TaskCreateOverlay::ProjectPath(mut picker) => match picker.handle_key(key) {
+                PathPickerAction::None => {
+                    view.overlay = Some(TaskCreateOverlay::ProjectPath(picker));
                 }
-                view.overlay = Some(TaskCreateOverlay::ProjectPath(editor));
-            }
+                PathPickerAction::Refresh => {
+                    view.overlay = Some(TaskCreateOverlay::ProjectPath(picker));
+                    model.view = View::TaskCreate(view);
+                    return (model, AppCommand::RefreshPathPicker);
+                }
+                PathPickerAction::Cancel => {}
+                PathPickerAction::Submit(path) => {
+                    view.project_path = path.unwrap_or_default();
+                }
+            },
         }
 
         model.view = View::TaskCreate(view);
@@ -5516,7 +9990,7 @@ fn update_task_create(
         }
         KeyCode::Char('v') | KeyCode::Char('V') if command_modifier => {
             model.view = View::TaskCreate(view);
-            return (model, AppCommand::TaskCreatePasteImageFromClipboard);
+            return (model, AppCommand::PastePromptImageFromClipboard);
         }
         KeyCode::Char('s') | KeyCode::Char('S') if command_modifier => {
             let body = view.editor.text();
@@ -5547,9 +10021,12 @@ fn update_task_create(
         }
         KeyCode::Char('p') | KeyCode::Char('P') if command_modifier => {
             let current = view.project_path.display().to_string();
-            view.overlay = Some(TaskCreateOverlay::ProjectPath(LineEditor::from_text(
+            view.overlay = Some(TaskCreateOverlay::ProjectPath(PathPicker::new(
                 current,
+                recent_project_paths(&model),
             )));
+            model.view = View::TaskCreate(view);
+            return (model, AppCommand::RefreshPathPicker);
         }
         KeyCode::Enter => {
             view.editor.insert_newline();
@@ -5629,6 +10106,19 @@ fn update_task_detail(
         }
         KeyCode::BackTab => {
             view.engine = view.engine.toggle();
+            view.agent_model = None;
+        }
+        KeyCode::F(10) => {
+            cycle_agent_model(&mut model, view.engine, &mut view.agent_model);
+        }
+        KeyCode::F(8) => {
+            view.worktree = !view.worktree;
+        }
+        KeyCode::F(6) => {
+            view.ab_compare = !view.ab_compare;
+        }
+        KeyCode::Delete if view.task.id.is_team() => {
+            model.notice = Some(TEAM_TASK_READ_ONLY_NOTICE.to_string());
         }
         KeyCode::Delete => {
             model.delete_task_confirm = Some(DeleteTaskConfirmDialog {
@@ -5653,12 +10143,25 @@ fn update_task_detail(
             let step = page_step_standard_list(model.terminal_size) as u16;
             view.scroll = view.scroll.saturating_add(step);
         }
+        KeyCode::Enter if send_modifier && view.ab_compare => {
+            return (
+                model,
+                AppCommand::SpawnTaskComparison {
+                    engines: [view.engine, view.engine.toggle()],
+                    task_id: view.task.id.clone(),
+                    worktree: view.worktree,
+                    agent_model: view.agent_model.clone(),
+                },
+            );
+        }
         KeyCode::Enter if send_modifier => {
             return (
                 model,
                 AppCommand::SpawnTask {
                     engine: view.engine,
                     task_id: view.task.id.clone(),
+                    worktree: view.worktree,
+                    agent_model: view.agent_model.clone(),
                 },
             );
         }
@@ -5679,6 +10182,7 @@ mod task_create_clipboard_tests {
             project_path: PathBuf::from("/tmp/proj"),
             sessions: Vec::new(),
             last_modified: None,
+            hidden: false,
         }
     }
 
@@ -5700,7 +10204,7 @@ mod task_create_clipboard_tests {
         let model = task_create_model();
         let key = KeyEvent::new(KeyCode::Char('v'), KeyModifiers::CONTROL);
         let (_next, cmd) = update(model, AppEvent::Key(key));
-        assert!(matches!(cmd, AppCommand::TaskCreatePasteImageFromClipboard));
+        assert!(matches!(cmd, AppCommand::PastePromptImageFromClipboard));
     }
 
     #[test]
@@ -5714,12 +10218,124 @@ mod task_create_clipboard_tests {
 
         let key = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
         let (next, cmd) = update(model, AppEvent::Key(key));
-        assert!(matches!(cmd, AppCommand::TaskCreatePasteImageFromClipboard));
+        assert!(matches!(cmd, AppCommand::PastePromptImageFromClipboard));
         let View::TaskCreate(next_view) = next.view else {
             panic!("expected TaskCreate view");
         };
         assert!(next_view.overlay.is_none());
     }
+
+    #[test]
+    fn project_path_picker_offers_known_projects_and_sets_resolved_path() {
+        let model = task_create_model();
+        let key = KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL);
+        let (mut model, cmd) = update(model, AppEvent::Key(key));
+        assert!(matches!(cmd, AppCommand::RefreshPathPicker));
+
+        let picker = model.path_picker_mut().expect("picker open");
+        assert_eq!(picker.editor.text, "/tmp/proj");
+        assert_eq!(picker.recent, vec![PathBuf::from("/tmp/proj")]);
+        picker.completion.resolved_dir = Some(PathBuf::from("/tmp/proj"));
+
+        let key = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+        let (mut next, cmd) = update(model, AppEvent::Key(key));
+        assert!(matches!(cmd, AppCommand::None));
+        assert!(next.path_picker_mut().is_none());
+        let View::TaskCreate(view) = next.view else {
+            panic!("expected TaskCreate view");
+        };
+        assert_eq!(view.project_path, PathBuf::from("/tmp/proj"));
+    }
+}
+
+#[cfg(test)]
+mod task_schedule_tests {
+    use super::*;
+
+    fn tasks_model(schedule: Option<TaskSchedule>) -> AppModel {
+        let data = AppData::from_scan(
+            PathBuf::from("/tmp/sessions"),
+            Vec::new(),
+            ScanWarningCount::from(0usize),
+        );
+        let mut model = AppModel::new(data);
+        let return_to = Box::new(model.view.clone());
+        let tasks = vec![TaskSummaryRow {
+            id: TaskId::new("t1".to_string()),
+            title: "nightly".to_string(),
+            project_path: PathBuf::from("/tmp/p1"),
+            updated_at: SystemTime::UNIX_EPOCH,
+            image_count: 0,
+            schedule,
+        }];
+        model.view = View::Tasks(TasksView::new(return_to, tasks));
+        model
+    }
+
+    fn type_text(mut model: AppModel, text: &str) -> AppModel {
+        for ch in text.chars() {
+            let key = KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE);
+            model = update(model, AppEvent::Key(key)).0;
+        }
+        model
+    }
+
+    #[test]
+    fn ctrl_s_then_enter_emits_schedule_command() {
+        let model = tasks_model(None);
+        let key = KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL);
+        let (model, cmd) = update(model, AppEvent::Key(key));
+        assert!(matches!(cmd, AppCommand::None));
+
+        let model = type_text(model, "in 2h");
+        let key = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+        let (_next, cmd) = update(model, AppEvent::Key(key));
+        let AppCommand::ScheduleTask {
+            task_id,
+            engine,
+            spec,
+            ..
+        } = cmd
+        else {
+            panic!("expected ScheduleTask command");
+        };
+        assert_eq!(task_id, TaskId::new("t1".to_string()));
+        assert_eq!(engine, AgentEngine::Codex);
+        assert_eq!(
+            spec,
+            ScheduleSpec::After(std::time::Duration::from_secs(7_200))
+        );
+    }
+
+    #[test]
+    fn invalid_schedule_keeps_dialog_open_with_error() {
+        let model = tasks_model(None);
+        let key = KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL);
+        let (model, _cmd) = update(model, AppEvent::Key(key));
+        let model = type_text(model, "soon");
+        let key = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+        let (next, cmd) = update(model, AppEvent::Key(key));
+        assert!(matches!(cmd, AppCommand::None));
+        let View::Tasks(view) = next.view else {
+            panic!("expected Tasks view");
+        };
+        let dialog = view.schedule_dialog.expect("dialog stays open");
+        assert!(dialog.error.is_some());
+    }
+
+    #[test]
+    fn empty_input_cancels_existing_schedule() {
+        let model = tasks_model(Some(TaskSchedule {
+            task_id: TaskId::new("t1".to_string()),
+            engine: AgentEngine::Claude,
+            due_at: SystemTime::UNIX_EPOCH,
+        }));
+        let key = KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL);
+        let (model, _cmd) = update(model, AppEvent::Key(key));
+        let key = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+        let (_next, cmd) = update(model, AppEvent::Key(key));
+        assert!(matches!(cmd, AppCommand::CancelTaskSchedule { .. }));
+    }
 }
 
 #[cfg(test)]
@@ -5733,11 +10349,15 @@ mod multi_select_tests {
                 id: id.to_string(),
                 cwd: PathBuf::from(project_path),
                 started_at_rfc3339: "2026-02-01T00:00:00Z".to_string(),
+                git: None,
             },
             log_path: PathBuf::from(log_file),
             title: format!("session {id}"),
             file_size_bytes: 123,
             file_modified: None,
+            parent_id: None,
+            project_root: None,
+            remote: None,
         }
     }
 
@@ -5747,18 +10367,21 @@ mod multi_select_tests {
             project_path: PathBuf::from("/tmp/p1"),
             sessions: vec![make_session("/tmp/p1", "s1", "/tmp/sessions/p1-s1.jsonl")],
             last_modified: None,
+            hidden: false,
         };
         let p2 = ProjectSummary {
             name: "p2".to_string(),
             project_path: PathBuf::from("/tmp/p2"),
             sessions: vec![make_session("/tmp/p2", "s2", "/tmp/sessions/p2-s2.jsonl")],
             last_modified: None,
+            hidden: false,
         };
         let p3 = ProjectSummary {
             name: "p3".to_string(),
             project_path: PathBuf::from("/tmp/p3"),
             sessions: vec![make_session("/tmp/p3", "s3", "/tmp/sessions/p3-s3.jsonl")],
             last_modified: None,
+            hidden: false,
         };
 
         let data = AppData::from_scan(
@@ -5770,105 +10393,2152 @@ mod multi_select_tests {
     }
 
     #[test]
-    fn shift_down_selects_range_in_projects() {
-        let model = projects_model();
+    fn projects_and_sessions_keep_their_own_engine_filter() {
+        let model = projects_model().with_engine_filter(EngineFilter::Codex);
+        assert_eq!(model.engine_prefs.projects, Some(SessionEngine::Codex));
+
+        let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+        let (model, _cmd) = update(model, AppEvent::Key(enter));
+        let View::Sessions(view) = &model.view else {
+            panic!("expected sessions view");
+        };
+        let project_path = view.project_path.clone();
+        assert_eq!(model.engine_filter, EngineFilter::All);
 
-        let key = KeyEvent::new(KeyCode::Down, KeyModifiers::SHIFT);
-        let (next, cmd) = update(model, AppEvent::Key(key));
-        assert!(matches!(cmd, AppCommand::None));
+        let model = model.with_engine_filter(EngineFilter::Claude);
+        assert_eq!(
+            model.engine_prefs.sessions_for(&project_path),
+            Some(SessionEngine::Claude)
+        );
 
-        let View::Projects(view) = next.view else {
-            panic!("expected Projects view");
+        let esc = KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE);
+        let (model, _cmd) = update(model, AppEvent::Key(esc));
+        assert!(matches!(model.view, View::Projects(_)));
+        assert_eq!(model.engine_filter, EngineFilter::Codex);
+
+        let (model, _cmd) = update(model, AppEvent::Key(enter));
+        assert!(matches!(model.view, View::Sessions(_)));
+        assert_eq!(model.engine_filter, EngineFilter::Claude);
+    }
+
+    #[test]
+    fn ctrl_f_toggles_full_text_filter_and_keeps_metadata_matches() {
+        let model = projects_model()
+            .with_project_for_dir(Path::new("/tmp/p1"))
+            .expect("project");
+        let full_text = KeyEvent::new(KeyCode::Char('f'), KeyModifiers::CONTROL);
+        let (model, _cmd) = update(model, AppEvent::Key(full_text));
+        let (model, _cmd) = update(model, AppEvent::Paste("s1".to_string()));
+        let View::Sessions(view) = &model.view else {
+            panic!("expected sessions view");
         };
-        assert_eq!(view.selected, 1);
-        assert_eq!(view.selected_project_paths.len(), 2);
-        assert!(
-            view.selected_project_paths
-                .contains(&PathBuf::from("/tmp/p1"))
-        );
-        assert!(
-            view.selected_project_paths
-                .contains(&PathBuf::from("/tmp/p2"))
-        );
+        assert!(view.search_content);
+        assert_eq!(view.filtered_indices.len(), 1);
+
+        let (model, _cmd) = update(model, AppEvent::Key(full_text));
+        let View::Sessions(view) = &model.view else {
+            panic!("expected sessions view");
+        };
+        assert!(!view.search_content);
+        assert_eq!(view.filtered_indices.len(), 1);
     }
 
     #[test]
-    fn delete_opens_batch_confirm_for_selected_projects() {
-        let model = projects_model();
-        let key = KeyEvent::new(KeyCode::Down, KeyModifiers::SHIFT);
-        let (model, _cmd) = update(model, AppEvent::Key(key));
+    fn f8_spawns_new_session_in_a_worktree() {
+        let model = projects_model()
+            .with_project_for_dir(Path::new("/tmp/p1"))
+            .expect("project");
+        let new_session = KeyEvent::new(KeyCode::Char('n'), KeyModifiers::CONTROL);
+        let (model, _cmd) = update(model, AppEvent::Key(new_session));
+        let worktree = KeyEvent::new(KeyCode::F(8), KeyModifiers::NONE);
+        let (model, _cmd) = update(model, AppEvent::Key(worktree));
+        let (model, _cmd) = update(model, AppEvent::Paste("Fix CI".to_string()));
+        let View::NewSession(view) = &model.view else {
+            panic!("expected new session view");
+        };
+        assert!(view.worktree);
+
+        let send = KeyEvent::new(KeyCode::Enter, KeyModifiers::CONTROL);
+        match update(model, AppEvent::Key(send)).1 {
+            AppCommand::SpawnAgentSession {
+                prompt, worktree, ..
+            } => {
+                assert_eq!(prompt, "Fix CI");
+                assert!(worktree);
+            }
+            other => panic!("expected SpawnAgentSession, got {other:?}"),
+        }
+    }
 
-        let key = KeyEvent::new(KeyCode::Delete, KeyModifiers::NONE);
-        let (next, cmd) = update(model, AppEvent::Key(key));
-        assert!(matches!(cmd, AppCommand::None));
-        assert!(next.delete_projects_confirm.is_some());
+    #[test]
+    fn ctrl_n_with_selected_projects_spawns_a_batch() {
+        let model = projects_model();
+        let tab = KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE);
+        let down = KeyEvent::new(KeyCode::Down, KeyModifiers::NONE);
+        let (model, _cmd) = update(model, AppEvent::Key(tab));
+        let (model, _cmd) = update(model, AppEvent::Key(down));
+        let (model, _cmd) = update(model, AppEvent::Key(tab));
+        let new_session = KeyEvent::new(KeyCode::Char('n'), KeyModifiers::CONTROL);
+        let (model, _cmd) = update(model, AppEvent::Key(new_session));
+        let (model, _cmd) = update(model, AppEvent::Paste("Bump deps".to_string()));
+        let View::NewSession(view) = &model.view else {
+            panic!("expected new session view");
+        };
+        assert_eq!(
+            view.batch.as_ref().map(|batch| batch.project_paths.len()),
+            Some(2)
+        );
 
-        let key = KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE);
-        let (_next, cmd) = update(next, AppEvent::Key(key));
+        let send = KeyEvent::new(KeyCode::Enter, KeyModifiers::CONTROL);
+        let (model, cmd) = update(model, AppEvent::Key(send));
         match cmd {
-            AppCommand::DeleteProjectLogsBatch { project_paths } => {
+            AppCommand::SpawnAgentBatch {
+                project_paths,
+                prompt,
+                ..
+            } => {
+                assert_eq!(prompt, "Bump deps");
                 assert_eq!(project_paths.len(), 2);
             }
-            _ => panic!("expected DeleteProjectLogsBatch"),
+            other => panic!("expected SpawnAgentBatch, got {other:?}"),
         }
+        let View::Processes(view) = &model.view else {
+            panic!("expected processes view");
+        };
+        assert!(matches!(view.return_to.as_ref(), View::Projects(_)));
     }
 
     #[test]
-    fn delete_opens_batch_confirm_for_selected_sessions() {
-        let mut model = projects_model();
-        let project = model.data.projects.first().expect("project");
-        model.view = View::Sessions(SessionsView::new(
-            project.project_path.clone(),
-            project.sessions.len(),
-        ));
-
-        // Seed a sessions view with at least two sessions.
-        if let View::Sessions(view) = &mut model.view {
-            view.filtered_indices = vec![0, 0];
+    fn f6_in_new_session_spawns_on_both_engines() {
+        let model = projects_model()
+            .with_project_for_dir(Path::new("/tmp/p1"))
+            .expect("project");
+        let new_session = KeyEvent::new(KeyCode::Char('n'), KeyModifiers::CONTROL);
+        let (model, _cmd) = update(model, AppEvent::Key(new_session));
+        let ab = KeyEvent::new(KeyCode::F(6), KeyModifiers::NONE);
+        let (model, _cmd) = update(model, AppEvent::Key(ab));
+        let (model, _cmd) = update(model, AppEvent::Paste("Fix CI".to_string()));
+
+        let send = KeyEvent::new(KeyCode::Enter, KeyModifiers::CONTROL);
+        match update(model, AppEvent::Key(send)).1 {
+            AppCommand::SpawnAgentComparison {
+                engines, prompt, ..
+            } => {
+                assert_eq!(engines, [AgentEngine::Codex, AgentEngine::Claude]);
+                assert_eq!(prompt, "Fix CI");
+            }
+            other => panic!("expected SpawnAgentComparison, got {other:?}"),
         }
+    }
 
-        // Better: build a project with 2 sessions.
-        let p = ProjectSummary {
-            name: "p".to_string(),
-            project_path: PathBuf::from("/tmp/p"),
-            sessions: vec![
-                make_session("/tmp/p", "a", "/tmp/sessions/p-a.jsonl"),
-                make_session("/tmp/p", "b", "/tmp/sessions/p-b.jsonl"),
-            ],
-            last_modified: None,
+    #[test]
+    fn finished_comparisons_wait_for_both_processes() {
+        let process = |id: &str, engine: AgentEngine, status: ProcessStatus| ProcessInfo {
+            id: id.to_string(),
+            pid: 1,
+            engine,
+            project_path: PathBuf::from("/tmp/p1"),
+            prompt_preview: "Fix CI".to_string(),
+            started_at: SystemTime::UNIX_EPOCH,
+            status,
+            io_mode: ProcessIoMode::Tty {
+                transcript_path: PathBuf::from("/tmp/t"),
+                log_path: PathBuf::from("/tmp/l"),
+            },
+            session_id: None,
+            session_log_path: None,
+            retry_policy: RetryPolicy::disabled(),
+            attempts: Vec::new(),
+            plan: None,
+            input_wait: None,
+            usage: None,
+            task_id: None,
+            task_title: None,
+            batch: None,
+            compare: Some(1),
+            label: None,
         };
-        model.data.projects = vec![p.clone()];
-        model.view = View::Sessions(SessionsView::new(p.project_path.clone(), p.sessions.len()));
+        let mut processes = vec![
+            process("p1", AgentEngine::Codex, ProcessStatus::Exited(Some(0))),
+            process("p2", AgentEngine::Claude, ProcessStatus::Running),
+        ];
+        assert!(finished_comparisons(&processes).is_empty());
 
-        let key = KeyEvent::new(KeyCode::Down, KeyModifiers::SHIFT);
-        let (model, _cmd) = update(model, AppEvent::Key(key));
+        processes[1].status = ProcessStatus::Exited(Some(1));
+        let ready = finished_comparisons(&processes);
+        assert_eq!(ready.len(), 1);
+        assert_eq!(ready[0].0, 1);
+        assert_eq!(ready[0].1[1].engine, AgentEngine::Claude);
+    }
 
-        let key = KeyEvent::new(KeyCode::Delete, KeyModifiers::NONE);
-        let (next, cmd) = update(model, AppEvent::Key(key));
-        assert!(matches!(cmd, AppCommand::None));
-        assert!(next.delete_sessions_confirm.is_some());
+    #[test]
+    fn processes_group_by_label_and_a_header_collapses_or_kills_the_group() {
+        let process = |id: &str, label: Option<&str>, status: ProcessStatus| ProcessInfo {
+            id: id.to_string(),
+            pid: 1,
+            engine: AgentEngine::Codex,
+            project_path: PathBuf::from("/tmp/p1"),
+            prompt_preview: "Fix CI".to_string(),
+            started_at: SystemTime::UNIX_EPOCH,
+            status,
+            io_mode: ProcessIoMode::Tty {
+                transcript_path: PathBuf::from("/tmp/t"),
+                log_path: PathBuf::from("/tmp/l"),
+            },
+            session_id: None,
+            session_log_path: None,
+            retry_policy: RetryPolicy::disabled(),
+            attempts: Vec::new(),
+            plan: None,
+            input_wait: None,
+            usage: None,
+            task_id: None,
+            task_title: None,
+            batch: None,
+            compare: None,
+            label: label.map(str::to_string),
+        };
+        let mut model = projects_model();
+        model.processes = vec![
+            process("p1", Some("Fix CI"), ProcessStatus::Running),
+            process("p2", None, ProcessStatus::Running),
+            process("p3", Some("Fix CI"), ProcessStatus::Exited(Some(0))),
+        ];
+        let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+        let (model, _cmd) = update(model, key(KeyCode::Char('P')));
+        let View::Processes(view) = &model.view else {
+            panic!("expected processes view");
+        };
+        assert_eq!(
+            view.rows(&model.processes),
+            [
+                ProcessRow::Group {
+                    label: "Fix CI".to_string(),
+                    count: 2,
+                    running: 1,
+                    collapsed: false,
+                },
+                ProcessRow::Process {
+                    index: 0,
+                    grouped: true
+                },
+                ProcessRow::Process {
+                    index: 2,
+                    grouped: true
+                },
+                ProcessRow::Process {
+                    index: 1,
+                    grouped: false
+                },
+            ]
+        );
 
-        let key = KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE);
-        let (_next, cmd) = update(next, AppEvent::Key(key));
-        match cmd {
-            AppCommand::DeleteSessionLogsBatch { log_paths } => {
-                assert_eq!(log_paths.len(), 2);
+        // Label p2 into the group: g, type (paste), Enter.
+        let mut model = model;
+        if let View::Processes(view) = &mut model.view {
+            view.selected = 3;
+        }
+        let (model, _cmd) = update(model, key(KeyCode::Char('g')));
+        let (model, _cmd) = update(model, AppEvent::Paste("Fix CI".to_string()));
+        let (model, cmd) = update(model, key(KeyCode::Enter));
+        assert_eq!(model.processes[1].label.as_deref(), Some("Fix CI"));
+        assert!(matches!(
+            cmd,
+            AppCommand::RecordProcessLabels { process_ids } if process_ids == ["p2"]
+        ));
+
+        let (mut model, _cmd) = update(model, key(KeyCode::Char(' ')));
+        if let View::Processes(view) = &mut model.view {
+            assert_eq!(view.rows(&model.processes).len(), 1);
+            view.selected = 0;
+        }
+        match update(model.clone(), key(KeyCode::Char('k'))).1 {
+            AppCommand::KillProcessGroup {
+                label,
+                process_ids,
+                force,
+            } => {
+                assert_eq!(label, "Fix CI");
+                assert_eq!(process_ids, ["p1", "p2"]);
+                assert!(!force);
             }
-            _ => panic!("expected DeleteSessionLogsBatch"),
+            other => panic!("expected KillProcessGroup, got {other:?}"),
+        }
+
+        // `k` leaves a member that is already stopping alone; Shift+K force-kills it too.
+        model.processes[0].status = ProcessStatus::Stopping;
+        match update(model.clone(), key(KeyCode::Char('k'))).1 {
+            AppCommand::KillProcessGroup { process_ids, .. } => assert_eq!(process_ids, ["p2"]),
+            other => panic!("expected KillProcessGroup, got {other:?}"),
+        }
+        match update(model, key(KeyCode::Char('K'))).1 {
+            AppCommand::KillProcessGroup {
+                process_ids, force, ..
+            } => {
+                assert_eq!(process_ids, ["p1", "p2"]);
+                assert!(force);
+            }
+            other => panic!("expected KillProcessGroup, got {other:?}"),
         }
     }
 
     #[test]
-    fn delete_opens_batch_confirm_for_selected_tasks() {
-        let mut model = projects_model();
-        let return_to = Box::new(model.view.clone());
-        let tasks = vec![
-            TaskSummaryRow {
+    fn a_stopping_process_counts_as_running_until_it_exits() {
+        let stopping = ProcessStatus::Stopping;
+        assert!(stopping.is_running());
+        assert_eq!(stopping.label(), "stopping");
+        assert_eq!(stopping.on_exit(Some(143), false), ProcessStatus::Killed);
+        assert_eq!(stopping.on_exit(None, true), ProcessStatus::Killed);
+        assert_eq!(
+            ProcessStatus::Running.on_exit(Some(1), true),
+            ProcessStatus::RetryPending(Some(1))
+        );
+        assert_eq!(
+            ProcessStatus::Running.on_exit(Some(0), false),
+            ProcessStatus::Exited(Some(0))
+        );
+        assert_eq!(
+            ProcessStatus::Killed.on_exit(None, false),
+            ProcessStatus::Killed
+        );
+    }
+    #[test]
+    fn process_output_searches_follows_and_exports() {
+        let mut model = projects_model().with_terminal_size(100, 30);
+        model.view = View::ProcessOutput(ProcessOutputView {
+            return_to: Box::new(model.view.clone()),
+            process_id: "p1".to_string(),
+            kind: ProcessOutputKind::Stdout,
+            file_path: PathBuf::from("/tmp/stdout.log"),
+            buffer: Arc::new(
+                (0..100)
+                    .map(|n| {
+                        if n % 40 == 5 {
+                            format!("ERROR {n}")
+                        } else {
+                            format!("ok {n}")
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            ),
+            file_offset: 0,
+            scroll: 0,
+            raw: false,
+            follow: false,
+            search: None,
+            search_input: None,
+            export_input: None,
+        });
+        let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+        let scroll = |model: &AppModel| match &model.view {
+            View::ProcessOutput(view) => (view.scroll, view.follow),
+            _ => panic!("expected process output view"),
+        };
+
+        // `/error` Enter jumps to the first match.
+        let (model, _cmd) = update(model, key(KeyCode::Char('/')));
+        let (model, _cmd) = update(model, AppEvent::Paste("error".to_string()));
+        let (model, _cmd) = update(model, key(KeyCode::Enter));
+        assert_eq!(scroll(&model), (5, false));
+        let (model, _cmd) = update(model, key(KeyCode::Char('n')));
+        assert_eq!(scroll(&model), (45, false));
+        let (model, _cmd) = update(model, key(KeyCode::Char('N')));
+        assert_eq!(scroll(&model), (5, false));
+
+        let (model, _cmd) = update(model, key(KeyCode::Char('f')));
+        assert_eq!(scroll(&model), (5, true));
+        let (model, _cmd) = update(model, key(KeyCode::Up));
+        let (top, follow) = scroll(&model);
+        assert!(!follow);
+        assert!(top > 50, "scroll up continues from the tail, got {top}");
+
+        // `w` offers a default file name; the edited path comes back in the export command.
+        let (model, _cmd) = update(model, key(KeyCode::Char('w')));
+        let View::ProcessOutput(view) = &model.view else {
+            panic!("expected process output view");
+        };
+        assert_eq!(view.export_input.as_deref(), Some("p1-stdout.log"));
+        let (model, _cmd) = update(model, key(KeyCode::Backspace));
+        let (model, _cmd) = update(model, key(KeyCode::Backspace));
+        let (model, _cmd) = update(model, key(KeyCode::Backspace));
+        let (model, _cmd) = update(model, AppEvent::Paste("txt".to_string()));
+        match update(model, key(KeyCode::Enter)).1 {
+            AppCommand::ExportProcessOutput {
+                process_id,
+                kind,
+                destination,
+            } => {
+                assert_eq!(process_id, "p1");
+                assert_eq!(kind, ProcessOutputKind::Stdout);
+                assert_eq!(destination, Some(PathBuf::from("p1-stdout.txt")));
+            }
+            other => panic!("expected ExportProcessOutput, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn enter_in_process_output_opens_the_reported_session() {
+        let mut model = projects_model();
+        model.processes = vec![ProcessInfo {
+            id: "p1".to_string(),
+            pid: 1,
+            engine: AgentEngine::Codex,
+            project_path: PathBuf::from("/tmp/p1"),
+            prompt_preview: "Fix CI".to_string(),
+            started_at: SystemTime::UNIX_EPOCH,
+            status: ProcessStatus::Running,
+            io_mode: ProcessIoMode::Pipes {
+                stdout_path: PathBuf::from("/tmp/o"),
+                stderr_path: PathBuf::from("/tmp/e"),
+                log_path: PathBuf::from("/tmp/l"),
+            },
+            session_id: None,
+            session_log_path: None,
+            retry_policy: RetryPolicy::disabled(),
+            attempts: Vec::new(),
+            plan: None,
+            input_wait: None,
+            usage: None,
+            task_id: None,
+            task_title: None,
+            batch: None,
+            compare: None,
+            label: None,
+        }];
+        model.view = View::ProcessOutput(ProcessOutputView {
+            return_to: Box::new(model.view.clone()),
+            process_id: "p1".to_string(),
+            kind: ProcessOutputKind::Stdout,
+            file_path: PathBuf::from("/tmp/o"),
+            buffer: Arc::new(String::new()),
+            file_offset: 0,
+            scroll: 0,
+            raw: false,
+            follow: true,
+            search: None,
+            search_input: None,
+            export_input: None,
+        });
+        let enter = AppEvent::Key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+        let (mut model, cmd) = update(model, enter.clone());
+        assert!(matches!(cmd, AppCommand::None));
+        assert_eq!(model.notice.as_deref(), Some("No session log path yet."));
+
+        model.processes[0].session_log_path = Some(PathBuf::from("/tmp/s.jsonl"));
+        match update(model, enter).1 {
+            AppCommand::OpenSessionDetailByLogPath {
+                project_path,
+                log_path,
+            } => {
+                assert_eq!(project_path, PathBuf::from("/tmp/p1"));
+                assert_eq!(log_path, PathBuf::from("/tmp/s.jsonl"));
+            }
+            other => panic!("expected OpenSessionDetailByLogPath, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn ctrl_k_in_processes_confirms_then_kills_the_project() {
+        let process = |id: &str, project: &str, status: ProcessStatus| ProcessInfo {
+            id: id.to_string(),
+            pid: 1,
+            engine: AgentEngine::Codex,
+            project_path: PathBuf::from(project),
+            prompt_preview: "Fix CI".to_string(),
+            started_at: SystemTime::UNIX_EPOCH,
+            status,
+            io_mode: ProcessIoMode::Pipes {
+                stdout_path: PathBuf::from("/tmp/o"),
+                stderr_path: PathBuf::from("/tmp/e"),
+                log_path: PathBuf::from("/tmp/l"),
+            },
+            session_id: None,
+            session_log_path: None,
+            retry_policy: RetryPolicy::disabled(),
+            attempts: Vec::new(),
+            plan: None,
+            input_wait: None,
+            usage: None,
+            task_id: None,
+            task_title: None,
+            batch: None,
+            compare: None,
+            label: None,
+        };
+        let mut model = projects_model();
+        model.processes = vec![
+            process("p1", "/tmp/p1", ProcessStatus::Running),
+            process("p2", "/tmp/p2", ProcessStatus::Running),
+            process("p3", "/tmp/p1/.worktrees/x", ProcessStatus::Running),
+            process("p4", "/tmp/p1", ProcessStatus::Exited(Some(0))),
+        ];
+        let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+        let ctrl_k = AppEvent::Key(KeyEvent::new(KeyCode::Char('k'), KeyModifiers::CONTROL));
+        let (model, _cmd) = update(model, key(KeyCode::Char('P')));
+        let (model, cmd) = update(model, ctrl_k.clone());
+        assert!(matches!(cmd, AppCommand::None));
+        let View::Processes(view) = &model.view else {
+            panic!("expected processes view");
+        };
+        let confirm = view.kill_project_confirm.as_ref().expect("confirm dialog");
+        assert_eq!(confirm.process_ids, ["p1", "p3"]);
+
+        // Enter on the default Cancel button closes the dialog without killing.
+        let (model, cmd) = update(model, key(KeyCode::Enter));
+        assert!(matches!(cmd, AppCommand::None));
+        let (model, _cmd) = update(model, ctrl_k);
+        match update(model, key(KeyCode::Char('y'))).1 {
+            AppCommand::KillProcessGroup {
+                label,
+                process_ids,
+                force,
+            } => {
+                assert_eq!(label, "/tmp/p1");
+                assert_eq!(process_ids, ["p1", "p3"]);
+                assert!(!force);
+            }
+            other => panic!("expected KillProcessGroup, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn t_in_processes_opens_the_originating_task() {
+        let process = |id: &str, task_id: Option<&str>| ProcessInfo {
+            id: id.to_string(),
+            pid: 1,
+            engine: AgentEngine::Codex,
+            project_path: PathBuf::from("/tmp/p1"),
+            prompt_preview: "Fix CI".to_string(),
+            started_at: SystemTime::UNIX_EPOCH,
+            status: ProcessStatus::Running,
+            io_mode: ProcessIoMode::Pipes {
+                stdout_path: PathBuf::from("/tmp/o"),
+                stderr_path: PathBuf::from("/tmp/e"),
+                log_path: PathBuf::from("/tmp/l"),
+            },
+            session_id: None,
+            session_log_path: None,
+            retry_policy: RetryPolicy::disabled(),
+            attempts: Vec::new(),
+            plan: None,
+            input_wait: None,
+            usage: None,
+            task_id: task_id.map(|id| TaskId::new(id.to_string())),
+            task_title: task_id.map(|_| "Fix CI".to_string()),
+            batch: None,
+            compare: None,
+            label: None,
+        };
+        let mut model = projects_model();
+        model.processes = vec![process("p1", None), process("p2", Some("t-1"))];
+        let select = |mut model: AppModel, id: &str| {
+            let mut view = ProcessesView::new(Box::new(model.view.clone()), 0);
+            view.selected = view.row_of(&model.processes, id).expect("row");
+            model.view = View::Processes(view);
+            model
+        };
+        let t = AppEvent::Key(KeyEvent::new(KeyCode::Char('t'), KeyModifiers::NONE));
+
+        let (model, cmd) = update(select(model, "p1"), t.clone());
+        assert!(matches!(cmd, AppCommand::None));
+        assert_eq!(
+            model.notice.as_deref(),
+            Some("p1 was not spawned from a task.")
+        );
+
+        match update(select(model, "p2"), t).1 {
+            AppCommand::OpenTaskDetail {
+                from_tasks,
+                task_id,
+            } => {
+                assert_eq!(task_id, TaskId::new("t-1".to_string()));
+                assert!(matches!(*from_tasks.return_to, View::Processes(_)));
+            }
+            other => panic!("expected OpenTaskDetail, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn enter_on_an_external_agent_opens_its_session() {
+        let mut model = projects_model();
+        let agent = |pid: u32, cwd: &str, engine: SessionEngine| ExternalAgentProcess {
+            pid,
+            engine,
+            cwd: PathBuf::from(cwd),
+            command: "codex".to_string(),
+            started_at: None,
+        };
+        model.external_processes = [
+            agent(7, "/tmp/p2", SessionEngine::Codex),
+            agent(8, "/tmp/p2", SessionEngine::Claude),
+        ]
+        .into_iter()
+        .map(|agent| ExternalProcess {
+            session: external_agent_session(&model.data, &agent),
+            agent,
+        })
+        .collect();
+        assert!(model.external_processes[1].session.is_none());
+        let enter = AppEvent::Key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        let select = |mut model: AppModel, selected: usize| {
+            let mut view = ProcessesView::new(Box::new(model.view.clone()), 0);
+            view.selected = selected;
+            model.view = View::Processes(view);
+            model
+        };
+
+        match update(select(model.clone(), 0), enter.clone()).1 {
+            AppCommand::OpenSessionDetailByLogPath {
+                project_path,
+                log_path,
+            } => {
+                assert_eq!(project_path, PathBuf::from("/tmp/p2"));
+                assert_eq!(log_path, PathBuf::from("/tmp/sessions/p2-s2.jsonl"));
+            }
+            other => panic!("expected OpenSessionDetailByLogPath, got {other:?}"),
+        }
+
+        let (model, cmd) = update(select(model, 1), enter);
+        assert!(matches!(cmd, AppCommand::None));
+        assert_eq!(
+            model.notice.as_deref(),
+            Some("No session found yet for pid 8.")
+        );
+    }
+
+    #[test]
+    fn activity_lists_running_processes_then_recent_sessions() {
+        let at = |secs: u64| SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(secs);
+        let mut model = projects_model();
+        for (secs, project) in [100, 500, 1_000]
+            .into_iter()
+            .zip(model.data.projects.iter_mut())
+        {
+            project.sessions[0].file_modified = Some(at(secs));
+        }
+        model.processes = vec![ProcessInfo {
+            id: "p1".to_string(),
+            pid: 1,
+            engine: AgentEngine::Codex,
+            project_path: PathBuf::from("/tmp/p2"),
+            prompt_preview: "Fix CI".to_string(),
+            started_at: at(400),
+            status: ProcessStatus::Running,
+            io_mode: ProcessIoMode::Pipes {
+                stdout_path: PathBuf::from("/tmp/o"),
+                stderr_path: PathBuf::from("/tmp/e"),
+                log_path: PathBuf::from("/tmp/l"),
+            },
+            session_id: None,
+            session_log_path: Some(PathBuf::from("/tmp/sessions/p2-s2.jsonl")),
+            retry_policy: RetryPolicy::disabled(),
+            attempts: Vec::new(),
+            plan: None,
+            input_wait: None,
+            usage: None,
+            task_id: None,
+            task_title: None,
+            batch: None,
+            compare: None,
+            label: None,
+        }];
+        let key =
+            |code: KeyCode, modifiers: KeyModifiers| AppEvent::Key(KeyEvent::new(code, modifiers));
+
+        let (mut model, _cmd) = update(model, key(KeyCode::Char('6'), KeyModifiers::CONTROL));
+        let View::Activity(view) = &mut model.view else {
+            panic!("expected activity view");
+        };
+        view.refresh(
+            &model.processes,
+            &model.external_processes,
+            &model.data.projects,
+            at(1_060),
+        );
+        // p2's session is shown on its process row; p1's is older than 15 minutes.
+        assert_eq!(
+            view.rows,
+            [
+                ActivityRow::Process {
+                    process_id: "p1".to_string()
+                },
+                ActivityRow::Session {
+                    project_path: PathBuf::from("/tmp/p3"),
+                    log_path: PathBuf::from("/tmp/sessions/p3-s3.jsonl"),
+                },
+            ]
+        );
+        assert!(view.is_active_session(Some(at(1_000))));
+        assert!(!view.is_active_session(Some(at(500))));
+
+        let (model, _cmd) = update(model, key(KeyCode::Char('+'), KeyModifiers::NONE));
+        let View::Activity(view) = &model.view else {
+            panic!("expected activity view");
+        };
+        assert_eq!(view.window_mins(), 30);
+        assert_eq!(view.rows.len(), 3);
+
+        match update(model.clone(), key(KeyCode::Enter, KeyModifiers::NONE)).1 {
+            AppCommand::OpenSessionDetailByLogPath { log_path, .. } => {
+                assert_eq!(log_path, PathBuf::from("/tmp/sessions/p2-s2.jsonl"));
+            }
+            other => panic!("expected OpenSessionDetailByLogPath, got {other:?}"),
+        }
+        let (model, _cmd) = update(model, key(KeyCode::Esc, KeyModifiers::NONE));
+        assert!(matches!(model.view, View::Projects(_)));
+    }
+
+    #[test]
+    fn f12_attaches_to_the_first_process_awaiting_approval() {
+        let process = |id: &str, reason: Option<InputWaitReason>| ProcessInfo {
+            id: id.to_string(),
+            pid: 1,
+            engine: AgentEngine::Claude,
+            project_path: PathBuf::from("/tmp/p1"),
+            prompt_preview: "Fix CI".to_string(),
+            started_at: SystemTime::UNIX_EPOCH,
+            status: ProcessStatus::Running,
+            io_mode: ProcessIoMode::Tty {
+                transcript_path: PathBuf::from("/tmp/t"),
+                log_path: PathBuf::from("/tmp/l"),
+            },
+            session_id: None,
+            session_log_path: None,
+            retry_policy: RetryPolicy::disabled(),
+            attempts: Vec::new(),
+            plan: None,
+            input_wait: reason.map(|reason| InputWait {
+                reason,
+                call_id: None,
+                detail: "Do you want to make this edit to main.rs?".to_string(),
+                is_from_tty: true,
+            }),
+            usage: None,
+            task_id: None,
+            task_title: None,
+            batch: None,
+            compare: None,
+            label: None,
+        };
+        let f12 = AppEvent::Key(KeyEvent::new(KeyCode::F(12), KeyModifiers::NONE));
+        let mut model = projects_model();
+        model.processes = vec![process("p1", Some(InputWaitReason::Prompt))];
+
+        let (mut model, cmd) = update(model, f12.clone());
+        assert!(matches!(cmd, AppCommand::None));
+        assert_eq!(
+            model.notice.as_deref(),
+            Some("No process is waiting for approval.")
+        );
+
+        model
+            .processes
+            .push(process("p2", Some(InputWaitReason::Approval)));
+        model
+            .processes
+            .push(process("p3", Some(InputWaitReason::Approval)));
+        let awaiting = awaiting_approval(&model.processes)
+            .iter()
+            .map(|process| process.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(awaiting, ["p2", "p3"]);
+        match update(model, f12).1 {
+            AppCommand::AttachProcessTty { process_id } => assert_eq!(process_id, "p2"),
+            other => panic!("expected AttachProcessTty, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn r_in_processes_types_a_reply_into_the_selected_tty_process() {
+        let process = |id: &str, status: ProcessStatus| ProcessInfo {
+            id: id.to_string(),
+            pid: 1,
+            engine: AgentEngine::Claude,
+            project_path: PathBuf::from("/tmp/p1"),
+            prompt_preview: "Fix CI".to_string(),
+            started_at: SystemTime::UNIX_EPOCH,
+            status,
+            io_mode: ProcessIoMode::Tty {
+                transcript_path: PathBuf::from("/tmp/t"),
+                log_path: PathBuf::from("/tmp/l"),
+            },
+            session_id: None,
+            session_log_path: None,
+            retry_policy: RetryPolicy::disabled(),
+            attempts: Vec::new(),
+            plan: None,
+            input_wait: None,
+            usage: None,
+            task_id: None,
+            task_title: None,
+            batch: None,
+            compare: None,
+            label: None,
+        };
+        let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+        let mut model = projects_model();
+        model.processes = vec![process("p1", ProcessStatus::Exited(Some(0)))];
+        model.view = View::Processes(ProcessesView::new(Box::new(model.view.clone()), 0));
+
+        let (mut model, _) = update(model, key(KeyCode::Char('r')));
+        assert_eq!(model.notice.as_deref(), Some("Process is not running."));
+
+        model.processes = vec![process("p1", ProcessStatus::Running)];
+        let (model, _) = update(model, key(KeyCode::Char('r')));
+        let (model, _) = update(model, key(KeyCode::Char('y')));
+        let (model, _) = update(model, AppEvent::Paste("es\n".to_string()));
+        let View::Processes(view) = &model.view else {
+            panic!("expected Processes view");
+        };
+        assert_eq!(view.reply_input.as_deref(), Some("yes"));
+
+        let (model, cmd) = update(model, key(KeyCode::Enter));
+        match cmd {
+            AppCommand::WriteProcessTty { process_id, text } => {
+                assert_eq!(process_id, "p1");
+                assert_eq!(text, "yes");
+            }
+            other => panic!("expected WriteProcessTty, got {other:?}"),
+        }
+        let View::Processes(view) = &model.view else {
+            panic!("expected Processes view");
+        };
+        assert!(view.reply_input.is_none());
+    }
+    #[test]
+    fn ctrl_o_in_new_session_asks_for_a_file_to_insert() {
+        let model = projects_model()
+            .with_project_for_dir(Path::new("/tmp/p1"))
+            .expect("project");
+        let new_session = KeyEvent::new(KeyCode::Char('n'), KeyModifiers::CONTROL);
+        let (model, _cmd) = update(model, AppEvent::Key(new_session));
+        let insert_file = KeyEvent::new(KeyCode::Char('o'), KeyModifiers::CONTROL);
+        let (model, _cmd) = update(model, AppEvent::Key(insert_file));
+        let (model, _cmd) = update(model, AppEvent::Paste("-\n".to_string()));
+
+        let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+        let (model, cmd) = update(model, AppEvent::Key(enter));
+        assert!(matches!(cmd, AppCommand::None));
+        assert!(model.notice.is_some());
+
+        let backspace = KeyEvent::new(KeyCode::Backspace, KeyModifiers::NONE);
+        let (model, _cmd) = update(model, AppEvent::Key(backspace));
+        let (model, _cmd) = update(model, AppEvent::Paste("task.md".to_string()));
+        let (model, cmd) = update(model, AppEvent::Key(enter));
+        match cmd {
+            AppCommand::NewSessionInsertFile { path } => {
+                assert_eq!(path, PathBuf::from("task.md"));
+            }
+            other => panic!("expected NewSessionInsertFile, got {other:?}"),
+        }
+        let View::NewSession(view) = &model.view else {
+            panic!("expected new session view");
+        };
+        assert!(view.insert_file.is_some());
+        assert!(view.editor.text().is_empty());
+    }
+
+    #[test]
+    fn f10_cycles_configured_models_and_the_spawn_carries_the_choice() {
+        let mut model = projects_model()
+            .with_project_for_dir(Path::new("/tmp/p1"))
+            .expect("project");
+        let new_session = KeyEvent::new(KeyCode::Char('n'), KeyModifiers::CONTROL);
+        let f10 = KeyEvent::new(KeyCode::F(10), KeyModifiers::NONE);
+        let (next, _cmd) = update(model, AppEvent::Key(new_session));
+        let (next, _cmd) = update(next, AppEvent::Key(f10));
+        assert!(next.notice.is_some());
+
+        model = next;
+        model.notice = None;
+        model.engine_models.codex = vec!["gpt-5".to_string()];
+        let (model, _cmd) = update(model, AppEvent::Key(f10));
+        let View::NewSession(view) = &model.view else {
+            panic!("expected new session view");
+        };
+        assert_eq!(view.agent_model.as_deref(), Some("gpt-5"));
+
+        let (model, _cmd) = update(model, AppEvent::Paste("Fix it".to_string()));
+        let send = KeyEvent::new(KeyCode::Enter, KeyModifiers::CONTROL);
+        let (_model, cmd) = update(model.clone(), AppEvent::Key(send));
+        match cmd {
+            AppCommand::SpawnAgentSession { agent_model, .. } => {
+                assert_eq!(agent_model.as_deref(), Some("gpt-5"));
+            }
+            other => panic!("expected SpawnAgentSession, got {other:?}"),
+        }
+
+        let switch_engine = KeyEvent::new(KeyCode::BackTab, KeyModifiers::NONE);
+        let (model, _cmd) = update(model, AppEvent::Key(switch_engine));
+        let View::NewSession(view) = &model.view else {
+            panic!("expected new session view");
+        };
+        assert_eq!(view.agent_model, None);
+    }
+
+    #[test]
+    fn ctrl_p_in_new_session_picks_the_directory_the_agent_runs_in() {
+        let model = projects_model()
+            .with_project_for_dir(Path::new("/tmp/p1"))
+            .expect("project");
+        let new_session = KeyEvent::new(KeyCode::Char('n'), KeyModifiers::CONTROL);
+        let (model, _cmd) = update(model, AppEvent::Key(new_session));
+        let pick_dir = KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL);
+        let (mut model, cmd) = update(model, AppEvent::Key(pick_dir));
+        assert!(matches!(cmd, AppCommand::RefreshPathPicker));
+        let picker = model.path_picker_mut().expect("picker open");
+        assert_eq!(picker.recent, vec![PathBuf::from("/tmp/p1")]);
+        picker.completion.resolved_dir = Some(PathBuf::from("/tmp/p1/web"));
+
+        let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+        let (model, _cmd) = update(model, AppEvent::Key(enter));
+        let View::NewSession(view) = &model.view else {
+            panic!("expected new session view");
+        };
+        assert!(view.working_dir_picker.is_none());
+        assert_eq!(view.working_dir, Some(PathBuf::from("/tmp/p1/web")));
+
+        let (model, _cmd) = update(model, AppEvent::Paste("Fix it".to_string()));
+        let send = KeyEvent::new(KeyCode::Enter, KeyModifiers::CONTROL);
+        let (_model, cmd) = update(model, AppEvent::Key(send));
+        match cmd {
+            AppCommand::SpawnAgentSession { project_path, .. } => {
+                assert_eq!(project_path, PathBuf::from("/tmp/p1/web"));
+            }
+            other => panic!("expected SpawnAgentSession, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn ctrl_i_in_new_session_inserts_images_that_are_sent_with_the_prompt() {
+        let model = projects_model()
+            .with_project_for_dir(Path::new("/tmp/p1"))
+            .expect("project");
+        let new_session = KeyEvent::new(KeyCode::Char('n'), KeyModifiers::CONTROL);
+        let (model, _cmd) = update(model, AppEvent::Key(new_session));
+        let insert_image = KeyEvent::new(KeyCode::Char('i'), KeyModifiers::CONTROL);
+        let (model, _cmd) = update(model, AppEvent::Key(insert_image));
+
+        let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+        let (model, cmd) = update(model, AppEvent::Key(enter));
+        assert!(matches!(cmd, AppCommand::PastePromptImageFromClipboard));
+
+        let (model, _cmd) = update(model, AppEvent::Key(insert_image));
+        let (model, _cmd) = update(model, AppEvent::Paste("/tmp/shot.png".to_string()));
+        let (mut model, cmd) = update(model, AppEvent::Key(enter));
+        match cmd {
+            AppCommand::InsertPromptImage { path } => {
+                assert_eq!(path, PathBuf::from("/tmp/shot.png"));
+            }
+            other => panic!("expected InsertPromptImage, got {other:?}"),
+        }
+        let View::NewSession(view) = &mut model.view else {
+            panic!("expected new session view");
+        };
+        view.image_path = None;
+        view.image_paths.push(PathBuf::from("/tmp/shot.png"));
+        view.editor.insert_str("Fix [Image 1]");
+
+        let send = KeyEvent::new(KeyCode::Enter, KeyModifiers::CONTROL);
+        let (_model, cmd) = update(model, AppEvent::Key(send));
+        match cmd {
+            AppCommand::SpawnAgentSession {
+                prompt,
+                image_paths,
+                ..
+            } => {
+                assert_eq!(
+                    prompt,
+                    "Fix [Image 1]\n\nAttached images:\n[Image 1] /tmp/shot.png\n"
+                );
+                assert_eq!(image_paths, vec![PathBuf::from("/tmp/shot.png")]);
+            }
+            other => panic!("expected SpawnAgentSession, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn at_sign_in_new_session_attaches_files_that_are_fenced_on_send() {
+        let model = projects_model()
+            .with_project_for_dir(Path::new("/tmp/p1"))
+            .expect("project");
+        let new_session = KeyEvent::new(KeyCode::Char('n'), KeyModifiers::CONTROL);
+        let (model, _cmd) = update(model, AppEvent::Key(new_session));
+        let (model, _cmd) = update(model, AppEvent::Paste("mail a".to_string()));
+        let at = KeyEvent::new(KeyCode::Char('@'), KeyModifiers::NONE);
+        let (model, _cmd) = update(model, AppEvent::Key(at));
+        let (model, _cmd) = update(model, AppEvent::Paste("b, see ".to_string()));
+        let (model, _cmd) = update(model, AppEvent::Key(at));
+        let (model, _cmd) = update(model, AppEvent::Paste("src/lib.rs".to_string()));
+
+        let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+        let (mut model, cmd) = update(model, AppEvent::Key(enter));
+        match cmd {
+            AppCommand::NewSessionAttachFile { path } => {
+                assert_eq!(path, PathBuf::from("src/lib.rs"));
+            }
+            other => panic!("expected NewSessionAttachFile, got {other:?}"),
+        }
+        let View::NewSession(view) = &mut model.view else {
+            panic!("expected new session view");
+        };
+        assert_eq!(view.editor.text(), "mail a@b, see ");
+        view.attach_file = None;
+        view.editor.insert_str("@src/lib.rs");
+        view.attachments.push(PromptAttachment {
+            path: "src/lib.rs".to_string(),
+            text: "pub fn a() {}".to_string(),
+        });
+
+        let send = KeyEvent::new(KeyCode::Enter, KeyModifiers::CONTROL);
+        let (_model, cmd) = update(model, AppEvent::Key(send));
+        match cmd {
+            AppCommand::SpawnAgentSession { prompt, .. } => assert_eq!(
+                prompt,
+                "mail a@b, see @src/lib.rs\n\n```src/lib.rs\npub fn a() {}\n```"
+            ),
+            other => panic!("expected SpawnAgentSession, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn all_sessions_lists_every_project_newest_first_and_returns_from_detail() {
+        let mut model = projects_model();
+        for (offset, project) in [5u64, 9, 1].into_iter().zip(model.data.projects.iter_mut()) {
+            project.sessions[0].file_modified =
+                Some(SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(offset));
+        }
+        let all_sessions = KeyEvent::new(KeyCode::Char('5'), KeyModifiers::CONTROL);
+        let (model, _cmd) = update(model, AppEvent::Key(all_sessions));
+        let View::AllSessions(view) = &model.view else {
+            panic!("expected all sessions view");
+        };
+        assert_eq!(view.filtered_indices, vec![(1, 0), (0, 0), (2, 0)]);
+
+        let mut model = model;
+        for character in ['p', '3'] {
+            let key = KeyEvent::new(KeyCode::Char(character), KeyModifiers::NONE);
+            model = update(model, AppEvent::Key(key)).0;
+        }
+        let View::AllSessions(view) = &model.view else {
+            panic!("expected all sessions view");
+        };
+        assert_eq!(view.filtered_indices, vec![(2, 0)]);
+
+        let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+        let (model, cmd) = update(model, AppEvent::Key(enter));
+        let AppCommand::OpenSessionDetail {
+            from_sessions,
+            session,
+        } = cmd
+        else {
+            panic!("expected OpenSessionDetail, got {cmd:?}");
+        };
+        assert_eq!(from_sessions.project_path, PathBuf::from("/tmp/p3"));
+        let model = model.open_session_detail(
+            from_sessions,
+            session,
+            Vec::new(),
+            BTreeMap::new(),
+            0,
+            false,
+        );
+
+        let esc = KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE);
+        let (model, _cmd) = update(model, AppEvent::Key(esc));
+        let View::AllSessions(view) = &model.view else {
+            panic!("expected all sessions view after Esc");
+        };
+        assert_eq!(view.query, "p3");
+    }
+
+    #[test]
+    fn opencode_write_back_runs_only_on_y() {
+        let mut model = projects_model();
+        let confirm = OpenCodeWriteBackConfirm {
+            session: make_session("/tmp/p1", "s1", "/tmp/sessions/p1-s1.jsonl"),
+            change: OpenCodeWriteBack::Title("Fix login".to_string()),
+        };
+        model.opencode_write_back = Some(confirm.clone());
+        let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+        let (mut model, cmd) = update(model, AppEvent::Key(enter));
+        assert!(matches!(cmd, AppCommand::None));
+        assert!(model.opencode_write_back.is_none());
+
+        model.opencode_write_back = Some(confirm);
+        let y = KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE);
+        let (model, cmd) = update(model, AppEvent::Key(y));
+        assert!(matches!(
+            cmd,
+            AppCommand::WriteBackOpenCode {
+                change: OpenCodeWriteBack::Title(_),
+                ..
+            }
+        ));
+        assert!(model.opencode_write_back.is_none());
+    }
+
+    #[test]
+    fn f_on_an_opencode_session_resumes_it_with_the_prompt() {
+        let mut session = make_session("/tmp/p1", "ses_1", "/tmp/opencode/ses_1.jsonl");
+        session.engine = SessionEngine::OpenCode;
+        let model = projects_model().open_session_detail(
+            SessionsView::new(PathBuf::from("/tmp/p1"), 1),
+            session,
+            Vec::new(),
+            BTreeMap::new(),
+            0,
+            false,
+        );
+
+        let f = KeyEvent::new(KeyCode::Char('f'), KeyModifiers::NONE);
+        let (model, cmd) = update(model, AppEvent::Key(f));
+        assert!(matches!(cmd, AppCommand::None));
+        let View::NewSession(view) = &model.view else {
+            panic!("expected new session view");
+        };
+        assert_eq!(view.engine, AgentEngine::OpenCode);
+        assert!(view.is_resume());
+
+        let back_tab = KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT);
+        let (model, _cmd) = update(model, AppEvent::Key(back_tab));
+        let (model, _cmd) = update(model, AppEvent::Paste("Now add tests".to_string()));
+        let View::NewSession(view) = &model.view else {
+            panic!("expected new session view");
+        };
+        assert_eq!(view.engine, AgentEngine::OpenCode);
+        let send = KeyEvent::new(KeyCode::Enter, KeyModifiers::CONTROL);
+        let (_model, cmd) = update(model, AppEvent::Key(send));
+        match cmd {
+            AppCommand::ResumeOpenCodeSession { session, prompt } => {
+                assert_eq!(session.meta.id, "ses_1");
+                assert_eq!(prompt, "Now add tests");
+            }
+            other => panic!("expected ResumeOpenCodeSession, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn subagent_sessions_nest_under_their_parent_and_ctrl_u_jumps_back() {
+        let mut model = projects_model();
+        let mut parent = make_session("/tmp/p1", "s1", "/tmp/sessions/p1-s1.jsonl");
+        parent.engine = SessionEngine::Claude;
+        let mut subagent = make_session("/tmp/p1", "agent-a1", "/tmp/sessions/agent-a1.jsonl");
+        subagent.engine = SessionEngine::Claude;
+        subagent.parent_id = Some("s1".to_string());
+        let other = make_session("/tmp/p1", "s2", "/tmp/sessions/p1-s2.jsonl");
+        model.data.projects[0].sessions = vec![parent, other, subagent];
+
+        let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+        let (model, _cmd) = update(model, AppEvent::Key(enter));
+        let View::Sessions(view) = &model.view else {
+            panic!("expected sessions view");
+        };
+        assert_eq!(view.filtered_indices, vec![0, 2, 1]);
+
+        let ctrl_u = KeyEvent::new(KeyCode::Char('u'), KeyModifiers::CONTROL);
+        let (model, _cmd) = update(model, AppEvent::Key(ctrl_u));
+        assert_eq!(model.notice.as_deref(), Some("Not a subagent session."));
+
+        let down = KeyEvent::new(KeyCode::Down, KeyModifiers::NONE);
+        let (model, _cmd) = update(model, AppEvent::Key(down));
+        let (model, _cmd) = update(model, AppEvent::Key(ctrl_u));
+        let View::Sessions(view) = &model.view else {
+            panic!("expected sessions view");
+        };
+        assert_eq!(view.session_selected, 0);
+    }
+
+    #[test]
+    fn project_stats_s_requests_skill_usage_once() {
+        let model = projects_model();
+        let f3 = KeyEvent::new(KeyCode::F(3), KeyModifiers::NONE);
+        let (model, _cmd) = update(model, AppEvent::Key(f3));
+        let project_path = model
+            .project_stats_overlay
+            .as_ref()
+            .expect("stats overlay")
+            .project_path
+            .clone();
+
+        let s = KeyEvent::new(KeyCode::Char('s'), KeyModifiers::NONE);
+        let (mut model, cmd) = update(model, AppEvent::Key(s));
+        match cmd {
+            AppCommand::LoadProjectSkills { project_path: path } => {
+                assert_eq!(path, project_path);
+            }
+            other => panic!("expected LoadProjectSkills, got {other:?}"),
+        }
+
+        model
+            .project_stats_overlay
+            .as_mut()
+            .expect("overlay")
+            .skills = Some(SkillUsageReport::default());
+        let (model, cmd) = update(model, AppEvent::Key(s));
+        assert!(matches!(cmd, AppCommand::None));
+        assert!(model.project_stats_overlay.is_some());
+    }
+
+    #[test]
+    fn quick_switcher_fuzzy_matches_and_jumps() {
+        let model = projects_model();
+        let open = KeyEvent::new(KeyCode::F(9), KeyModifiers::NONE);
+        let (mut model, cmd) = update(model, AppEvent::Key(open));
+        assert!(matches!(cmd, AppCommand::OpenQuickSwitcher));
+        model.quick_switcher = Some(QuickSwitcherOverlay::new(Vec::new()));
+
+        let (model, _cmd) = update(model, AppEvent::Paste("p2".to_string()));
+        let overlay = model.quick_switcher.as_ref().expect("switcher");
+        let items = quick_switch_items(&model, overlay);
+        assert_eq!(
+            items[0].target,
+            QuickSwitchTarget::Project(PathBuf::from("/tmp/p2"))
+        );
+        let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+        let (model, _cmd) = update(model, AppEvent::Key(enter));
+        assert!(model.quick_switcher.is_none());
+        let View::Sessions(view) = &model.view else {
+            panic!("expected sessions view");
+        };
+        assert_eq!(view.project_path, PathBuf::from("/tmp/p2"));
+
+        let mut model = model;
+        model.quick_switcher = Some(QuickSwitcherOverlay::new(Vec::new()));
+        let (model, _cmd) = update(model, AppEvent::Paste("session s3".to_string()));
+        match update(model, AppEvent::Key(enter)).1 {
+            AppCommand::OpenSessionDetail {
+                from_sessions,
+                session,
+            } => {
+                assert_eq!(from_sessions.project_path, PathBuf::from("/tmp/p3"));
+                assert_eq!(session.meta.id, "s3");
+            }
+            other => panic!("expected OpenSessionDetail, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn project_filter_ranks_fuzzy_matches() {
+        let project = |name: &str, path: &str| ProjectSummary {
+            name: name.to_string(),
+            project_path: PathBuf::from(path),
+            sessions: Vec::new(),
+            last_modified: None,
+            hidden: false,
+        };
+        let projects = vec![
+            project("rapid", "/work/rapid"),
+            project("notes", "/work/notes"),
+            project("api-gateway", "/srv/api-gateway"),
+            project("web", "/work/clients/acme"),
+        ];
+        let mut view = ProjectsView::new(&projects);
+        view.query = "api".to_string();
+        apply_project_filter(
+            &projects,
+            &mut view,
+            EngineFilter::All,
+            &SessionIndex::default(),
+            ProjectsOrder::RecentFirst,
+        );
+        assert_eq!(view.filtered_indices, vec![2, 0]);
+
+        view.query = "agw".to_string();
+        apply_project_filter(
+            &projects,
+            &mut view,
+            EngineFilter::All,
+            &SessionIndex::default(),
+            ProjectsOrder::RecentFirst,
+        );
+        assert_eq!(view.filtered_indices, vec![2]);
+
+        // Paths still match as substrings only.
+        view.query = "acme".to_string();
+        apply_project_filter(
+            &projects,
+            &mut view,
+            EngineFilter::All,
+            &SessionIndex::default(),
+            ProjectsOrder::RecentFirst,
+        );
+        assert_eq!(view.filtered_indices, vec![3]);
+    }
+
+    #[test]
+    fn branch_term_filters_sessions_by_git_branch() {
+        let mut on_branch = make_session("/tmp/p1", "s1", "/tmp/sessions/p1-s1.jsonl");
+        on_branch.meta.git = Some(crate::domain::SessionGit {
+            branch: Some("feature/Login".to_string()),
+            commit: Some("4f5934caa96e82bc".to_string()),
+        });
+        let sessions = vec![
+            on_branch,
+            make_session("/tmp/p1", "s2", "/tmp/sessions/p1-s2.jsonl"),
+        ];
+        let mut view = SessionsView::new(PathBuf::from("/tmp/p1"), sessions.len());
+        let mut filter = |query: &str| {
+            view.query = query.to_string();
+            apply_session_filter(
+                &sessions,
+                &SessionIndex::default(),
+                &mut view,
+                EngineFilter::All,
+            );
+            view.filtered_indices.clone()
+        };
+
+        assert_eq!(filter("branch:login"), vec![0]);
+        assert_eq!(filter("branch:login s2"), Vec::<usize>::new());
+        assert_eq!(filter("4f5934c"), vec![0]);
+        assert_eq!(filter("branch:"), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn projects_sort_by_session_count_and_size() {
+        let project = |name: &str, sizes: &[u64]| ProjectSummary {
+            name: name.to_string(),
+            project_path: PathBuf::from(format!("/tmp/{name}")),
+            sessions: sizes
+                .iter()
+                .enumerate()
+                .map(|(position, size)| {
+                    let mut session = make_session(
+                        &format!("/tmp/{name}"),
+                        &format!("{name}-{position}"),
+                        &format!("/tmp/sessions/{name}-{position}.jsonl"),
+                    );
+                    session.file_size_bytes = *size;
+                    session
+                })
+                .collect(),
+            last_modified: None,
+            hidden: false,
+        };
+        let projects = vec![
+            project("recent", &[10]),
+            project("busy", &[10, 20, 30]),
+            project("heavy", &[5_000]),
+        ];
+        let mut view = ProjectsView::new(&projects);
+        let mut sort = |order: ProjectsOrder| {
+            apply_project_filter(
+                &projects,
+                &mut view,
+                EngineFilter::All,
+                &SessionIndex::default(),
+                order,
+            );
+            view.filtered_indices.clone()
+        };
+
+        assert_eq!(sort(ProjectsOrder::RecentFirst), vec![0, 1, 2]);
+        assert_eq!(sort(ProjectsOrder::MostSessions), vec![1, 0, 2]);
+        assert_eq!(sort(ProjectsOrder::LargestFirst), vec![2, 1, 0]);
+        assert_eq!(sort(ProjectsOrder::MostTokens), vec![0, 1, 2]);
+        assert_eq!(
+            ProjectsOrder::MostTokens.cycle(),
+            ProjectsOrder::RecentFirst
+        );
+    }
+
+    #[test]
+    fn keeps_remote_sessions_read_only() {
+        let mut session = make_session("/work/app", "remote", "/mirror/box/codex/a.jsonl");
+        session.remote = Some("box".to_string());
+        let project = ProjectSummary {
+            name: "box:app".to_string(),
+            project_path: PathBuf::from("box:/work/app"),
+            sessions: vec![session.clone()],
+            last_modified: None,
+            hidden: false,
+        };
+        let data = AppData::from_scan(
+            PathBuf::from("/tmp/sessions"),
+            vec![project],
+            ScanWarningCount::from(0usize),
+        );
+        let mut model = AppModel::new(data);
+        model.delete_confirm = Some(DeleteConfirmDialog {
+            project_name: "box:app".to_string(),
+            project_path: PathBuf::from("box:/work/app"),
+            session_count: 1,
+            total_size_bytes: 0,
+            selection: DeleteConfirmSelection::Cancel,
+        });
+
+        let (model, command) = update(model, AppEvent::Key(KeyEvent::from(KeyCode::Char('y'))));
+        assert!(matches!(command, AppCommand::None));
+        assert_eq!(
+            model.notice.as_deref(),
+            Some("Remote sessions are read-only.")
+        );
+
+        let (_, command) = reject_remote_session_changes(
+            model,
+            AppCommand::DeleteSessionLog {
+                log_path: session.log_path.clone(),
+            },
+        );
+        assert!(matches!(command, AppCommand::None));
+    }
+
+    #[test]
+    fn renames_and_hides_projects_from_the_projects_view() {
+        let project = |name: &str| ProjectSummary {
+            name: name.to_string(),
+            project_path: PathBuf::from(format!("/tmp/{name}")),
+            sessions: vec![make_session(
+                &format!("/tmp/{name}"),
+                name,
+                &format!("/tmp/sessions/{name}.jsonl"),
+            )],
+            last_modified: None,
+            hidden: false,
+        };
+        let data = AppData::from_scan(
+            PathBuf::from("/tmp/sessions"),
+            vec![project("app"), project("scratch")],
+            ScanWarningCount::from(0usize),
+        );
+        let mut prefs = ProjectPrefs::default();
+        prefs.set_hidden(Path::new("/tmp/scratch"), true);
+        let model = AppModel::new(data).with_project_prefs(prefs);
+        let visible = |model: &AppModel| match &model.view {
+            View::Projects(view) => view
+                .filtered_indices
+                .iter()
+                .map(|index| model.data.projects[*index].name.clone())
+                .collect::<Vec<_>>(),
+            _ => Vec::new(),
+        };
+        assert_eq!(visible(&model), vec!["app"]);
+
+        let ctrl = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::CONTROL));
+        let (mut model, _) = update(model, ctrl(KeyCode::Char('e')));
+        let dialog = model.project_rename.as_mut().expect("rename dialog");
+        dialog.editor = LineEditor::from_text("Main app".to_string());
+        let (model, _) = update(model, AppEvent::Key(KeyEvent::from(KeyCode::Enter)));
+        assert_eq!(
+            model.project_prefs.name_for(Path::new("/tmp/app")),
+            Some("Main app")
+        );
+        assert_eq!(visible(&model), vec!["Main app"]);
+
+        let (model, _) = update(model, AppEvent::Key(KeyEvent::from(KeyCode::F(6))));
+        assert_eq!(visible(&model), vec!["Main app", "scratch"]);
+        let (model, _) = update(model, ctrl(KeyCode::Char('x')));
+        assert!(model.project_prefs.is_hidden(Path::new("/tmp/app")));
+        let (model, _) = update(model, AppEvent::Key(KeyEvent::from(KeyCode::F(6))));
+        assert!(visible(&model).is_empty());
+    }
+
+    #[test]
+    fn sort_modes_cycle_and_reorder_sessions() {
+        let mut small = make_session("/tmp/p1", "s1", "/tmp/sessions/p1-s1.jsonl");
+        small.title = "beta".to_string();
+        small.file_size_bytes = 10;
+        small.meta.started_at_rfc3339 = "2026-02-03T00:00:00Z".to_string();
+        let mut large = make_session("/tmp/p1", "s2", "/tmp/sessions/p1-s2.jsonl");
+        large.title = "Alpha".to_string();
+        large.file_size_bytes = 900;
+        large.meta.started_at_rfc3339 = "2026-02-01T00:00:00Z".to_string();
+        let mut middle = make_session("/tmp/p1", "s3", "/tmp/sessions/p1-s3.jsonl");
+        middle.title = "gamma".to_string();
+        middle.file_size_bytes = 50;
+        middle.meta.started_at_rfc3339 = "2026-02-02T00:00:00Z".to_string();
+        let sessions = vec![small, large, middle];
+
+        let mut view = SessionsView::new(PathBuf::from("/tmp/p1"), sessions.len());
+        let mut sort = |order: SessionsOrder| {
+            view.order = order;
+            apply_session_filter(
+                &sessions,
+                &SessionIndex::default(),
+                &mut view,
+                EngineFilter::All,
+            );
+            view.filtered_indices.clone()
+        };
+
+        assert_eq!(sort(SessionsOrder::NewestFirst), vec![0, 1, 2]);
+        assert_eq!(sort(SessionsOrder::StartedFirst), vec![0, 2, 1]);
+        assert_eq!(sort(SessionsOrder::LargestFirst), vec![1, 2, 0]);
+        assert_eq!(sort(SessionsOrder::Title), vec![1, 0, 2]);
+        // Nothing indexed yet: the listed order stays.
+        assert_eq!(sort(SessionsOrder::MostTokens), vec![0, 1, 2]);
+
+        let mut order = SessionsOrder::NewestFirst;
+        let mut seen = Vec::new();
+        loop {
+            order = order.cycle();
+            seen.push(order);
+            if order == SessionsOrder::NewestFirst {
+                break;
+            }
+        }
+        assert_eq!(seen.len(), 6);
+        assert_eq!(
+            SessionsOrder::ToolErrorsFirst.cycle(),
+            SessionsOrder::NewestFirst
+        );
+        assert_eq!(
+            SessionsOrder::Title.toggle_tool_errors_first(),
+            SessionsOrder::ToolErrorsFirst
+        );
+    }
+
+    #[test]
+    fn up_at_the_top_loads_the_previous_page_of_items() {
+        let item = |summary: &str| TimelineItem {
+            kind: TimelineItemKind::Note,
+            turn_id: None,
+            call_id: None,
+            source_line_no: None,
+            timestamp: None,
+            timestamp_ms: None,
+            summary: summary.to_string(),
+            detail: String::new(),
+        };
+        let mut model = projects_model().open_session_detail(
+            SessionsView::new(PathBuf::from("/tmp/p1"), 1),
+            make_session("/tmp/p1", "s1", "/tmp/sessions/p1-s1.jsonl"),
+            vec![item("c"), item("d")],
+            BTreeMap::new(),
+            0,
+            false,
+        );
+        if let View::SessionDetail(view) = &mut model.view {
+            view.older = Arc::new(vec![item("a"), item("b")]);
+            view.older_items = 2;
+        }
+
+        let up = KeyEvent::new(KeyCode::Up, KeyModifiers::NONE);
+        let (model, cmd) = update(model, AppEvent::Key(up));
+        assert!(matches!(cmd, AppCommand::None));
+        let View::SessionDetail(view) = &model.view else {
+            panic!("expected session detail view");
+        };
+        let summaries = view
+            .items
+            .iter()
+            .map(|item| item.summary.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(summaries, ["a", "b", "c", "d"]);
+        assert_eq!((view.older_items, view.selected), (0, 1));
+    }
+
+    #[test]
+    fn page_up_at_the_top_loads_the_previous_page_from_the_parsed_items() {
+        let item = |summary: &str| TimelineItem {
+            kind: TimelineItemKind::Note,
+            turn_id: None,
+            call_id: None,
+            source_line_no: None,
+            timestamp: None,
+            timestamp_ms: None,
+            summary: summary.to_string(),
+            detail: String::new(),
+        };
+        let mut model = projects_model().open_session_detail(
+            SessionsView::new(PathBuf::from("/tmp/p1"), 1),
+            make_session("/tmp/p1", "s1", "/tmp/sessions/p1-s1.jsonl"),
+            vec![item("b")],
+            BTreeMap::new(),
+            0,
+            false,
+        );
+        if let View::SessionDetail(view) = &mut model.view {
+            // "a" was already loaded earlier; only "z" is left above it.
+            view.older = Arc::new(vec![item("z"), item("a")]);
+            view.older_items = 1;
+            view.items.insert(0, item("a"));
+        }
+
+        let page_up = KeyEvent::new(KeyCode::PageUp, KeyModifiers::NONE);
+        let (model, cmd) = update(model, AppEvent::Key(page_up));
+        assert!(matches!(cmd, AppCommand::None));
+        let View::SessionDetail(view) = &model.view else {
+            panic!("expected session detail view");
+        };
+        let summaries = view
+            .items
+            .iter()
+            .map(|item| item.summary.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(summaries, ["z", "a", "b"]);
+        assert_eq!((view.older_items, view.selected), (0, 0));
+        assert_eq!(model.notice.as_deref(), Some("Loaded 1 older items."));
+
+        let (model, cmd) = update(model, AppEvent::Key(page_up));
+        assert!(matches!(cmd, AppCommand::None));
+        let View::SessionDetail(view) = &model.view else {
+            panic!("expected session detail view");
+        };
+        assert_eq!(view.items.len(), 3);
+    }
+
+    #[test]
+    fn e_toggles_the_tool_output_of_the_selected_call() {
+        let session = make_session("/tmp/p1", "s1", "/tmp/sessions/p1-s1.jsonl");
+        let item = |kind, call_id: Option<&str>| TimelineItem {
+            kind,
+            turn_id: None,
+            call_id: call_id.map(str::to_string),
+            source_line_no: None,
+            timestamp: None,
+            timestamp_ms: None,
+            summary: String::new(),
+            detail: String::new(),
+        };
+        let items = vec![
+            item(TimelineItemKind::User, None),
+            item(TimelineItemKind::ToolCall, Some("c1")),
+            item(TimelineItemKind::ToolOutput, Some("c1")),
+        ];
+        let model = projects_model().open_session_detail(
+            SessionsView::new(PathBuf::from("/tmp/p1"), 1),
+            session,
+            items,
+            BTreeMap::new(),
+            0,
+            false,
+        );
+
+        let expand = KeyEvent::new(KeyCode::Char('e'), KeyModifiers::NONE);
+        let (model, _cmd) = update(model, AppEvent::Key(expand));
+        assert_eq!(model.notice.as_deref(), Some("No tool output to expand."));
+
+        let down = KeyEvent::new(KeyCode::Down, KeyModifiers::NONE);
+        let (model, _cmd) = update(model, AppEvent::Key(down));
+        let (model, _cmd) = update(model, AppEvent::Key(expand));
+        let View::SessionDetail(view) = &model.view else {
+            panic!("expected session detail view");
+        };
+        assert!(view.is_output_expanded(2));
+
+        let (model, _cmd) = update(model, AppEvent::Key(down));
+        let (model, _cmd) = update(model, AppEvent::Key(expand));
+        let View::SessionDetail(view) = &model.view else {
+            panic!("expected session detail view");
+        };
+        assert!(!view.is_output_expanded(2));
+    }
+
+    #[test]
+    fn y_copies_the_selected_item_last_output_or_transcript() {
+        let item = |kind, detail: &str| TimelineItem {
+            kind,
+            turn_id: None,
+            call_id: None,
+            source_line_no: None,
+            timestamp: None,
+            timestamp_ms: None,
+            summary: detail.to_string(),
+            detail: detail.to_string(),
+        };
+        let model = projects_model().open_session_detail(
+            SessionsView::new(PathBuf::from("/tmp/p1"), 1),
+            make_session("/tmp/p1", "s1", "/tmp/sessions/p1-s1.jsonl"),
+            vec![
+                item(TimelineItemKind::User, "fix it"),
+                item(TimelineItemKind::Assistant, "done"),
+            ],
+            BTreeMap::new(),
+            0,
+            false,
+        );
+        let copied = |model: &AppModel, code, modifiers| match update(
+            model.clone(),
+            AppEvent::Key(KeyEvent::new(code, modifiers)),
+        )
+        .1
+        {
+            AppCommand::CopyToClipboard { text, what } => (text, what),
+            other => panic!("expected CopyToClipboard, got {other:?}"),
+        };
+
+        assert_eq!(
+            copied(&model, KeyCode::Char('y'), KeyModifiers::NONE),
+            ("fix it".to_string(), "selected item".to_string())
+        );
+        assert_eq!(
+            copied(&model, KeyCode::Char('Y'), KeyModifiers::SHIFT).0,
+            "done"
+        );
+        assert_eq!(
+            copied(&model, KeyCode::Char('y'), KeyModifiers::CONTROL),
+            (
+                "USER\nfix it\n\nASSISTANT\ndone\n".to_string(),
+                "transcript".to_string()
+            )
+        );
+
+        match update(
+            model,
+            AppEvent::Key(KeyEvent::new(KeyCode::Char('v'), KeyModifiers::NONE)),
+        )
+        .1
+        {
+            AppCommand::OpenInEditor { session } => assert_eq!(session.meta.id, "s1"),
+            other => panic!("expected OpenInEditor, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn brackets_jump_between_user_and_assistant_messages() {
+        let item = |kind| TimelineItem {
+            kind,
+            turn_id: None,
+            call_id: None,
+            source_line_no: None,
+            timestamp: None,
+            timestamp_ms: None,
+            summary: String::new(),
+            detail: String::new(),
+        };
+        let items = vec![
+            item(TimelineItemKind::User),
+            item(TimelineItemKind::ToolCall),
+            item(TimelineItemKind::Assistant),
+            item(TimelineItemKind::User),
+            item(TimelineItemKind::Assistant),
+        ];
+        let model = projects_model().open_session_detail(
+            SessionsView::new(PathBuf::from("/tmp/p1"), 1),
+            make_session("/tmp/p1", "s1", "/tmp/sessions/p1-s1.jsonl"),
+            items,
+            BTreeMap::new(),
+            0,
+            false,
+        );
+        let press = |model: AppModel, character: char| {
+            let key = KeyEvent::new(KeyCode::Char(character), KeyModifiers::NONE);
+            let (model, _cmd) = update(model, AppEvent::Key(key));
+            let View::SessionDetail(view) = &model.view else {
+                panic!("expected session detail view");
+            };
+            let selected = view.selected;
+            (model, selected)
+        };
+
+        let (model, selected) = press(model, ']');
+        assert_eq!(selected, 3);
+        let (model, selected) = press(model, '{');
+        assert_eq!(selected, 2);
+        let (model, selected) = press(model, '}');
+        assert_eq!(selected, 4);
+        let (model, selected) = press(model, ']');
+        assert_eq!(selected, 4);
+        assert_eq!(model.notice.as_deref(), Some("No next user message."));
+        let (_model, selected) = press(model, '[');
+        assert_eq!(selected, 3);
+    }
+
+    #[test]
+    fn alt_arrows_and_f7_reopen_sessions_from_history() {
+        let open = |model: &AppModel, id: &str| {
+            let session = make_session("/tmp/p1", id, &format!("/tmp/sessions/p1-{id}.jsonl"));
+            model.open_session_detail(
+                SessionsView::new(PathBuf::from("/tmp/p1"), 1),
+                session,
+                Vec::new(),
+                BTreeMap::new(),
+                0,
+                false,
+            )
+        };
+        let opened_id = |cmd: AppCommand| match cmd {
+            AppCommand::OpenSessionDetail { session, .. } => session.meta.id,
+            other => panic!("expected OpenSessionDetail, got {other:?}"),
+        };
+        let back = KeyEvent::new(KeyCode::Left, KeyModifiers::ALT);
+        let forward = KeyEvent::new(KeyCode::Right, KeyModifiers::ALT);
+
+        let model = open(&open(&projects_model(), "s1"), "s2");
+        let (model, cmd) = update(model.clone(), AppEvent::Key(back));
+        assert_eq!(opened_id(cmd), "s1");
+        let model = open(&model, "s1");
+        let (model, cmd) = update(model, AppEvent::Key(forward));
+        assert_eq!(opened_id(cmd), "s2");
+        let model = open(&model, "s2");
+        let (model, cmd) = update(model, AppEvent::Key(forward));
+        assert!(matches!(cmd, AppCommand::None));
+        assert_eq!(
+            model.notice.as_deref(),
+            Some("No later session in history.")
+        );
+
+        let recent = KeyEvent::new(KeyCode::F(7), KeyModifiers::NONE);
+        let down = KeyEvent::new(KeyCode::Down, KeyModifiers::NONE);
+        let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+        let (model, _cmd) = update(model, AppEvent::Key(recent));
+        assert!(model.recent_sessions_overlay.is_some());
+        let (model, _cmd) = update(model, AppEvent::Key(down));
+        let (model, cmd) = update(model, AppEvent::Key(enter));
+        assert_eq!(opened_id(cmd), "s1");
+        assert!(model.recent_sessions_overlay.is_none());
+    }
+
+    #[test]
+    fn ctrl_k_compares_two_selected_sessions_oldest_first() {
+        let mut newer = make_session("/tmp/p1", "s2", "/tmp/sessions/p1-s2.jsonl");
+        newer.meta.started_at_rfc3339 = "2026-02-02T00:00:00Z".to_string();
+        let older = make_session("/tmp/p1", "s1", "/tmp/sessions/p1-s1.jsonl");
+        let project = ProjectSummary {
+            name: "p1".to_string(),
+            project_path: PathBuf::from("/tmp/p1"),
+            sessions: vec![newer, older],
+            last_modified: None,
+            hidden: false,
+        };
+        let data = AppData::from_scan(
+            PathBuf::from("/tmp/sessions"),
+            vec![project],
+            ScanWarningCount::from(0usize),
+        );
+        let model = AppModel::new(data)
+            .with_project_for_dir(Path::new("/tmp/p1"))
+            .expect("project");
+
+        let compare = KeyEvent::new(KeyCode::Char('k'), KeyModifiers::CONTROL);
+        let tab = KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE);
+        let down = KeyEvent::new(KeyCode::Down, KeyModifiers::NONE);
+        let (model, _cmd) = update(model, AppEvent::Key(tab));
+        let (model, cmd) = update(model, AppEvent::Key(compare));
+        assert!(matches!(cmd, AppCommand::None));
+        assert_eq!(
+            model.notice.as_deref(),
+            Some("Select two sessions (Tab) to compare.")
+        );
+
+        let (model, _cmd) = update(model, AppEvent::Key(down));
+        let (model, _cmd) = update(model, AppEvent::Key(tab));
+        let (_model, cmd) = update(model, AppEvent::Key(compare));
+        let AppCommand::OpenSessionDiff { a, b } = cmd else {
+            panic!("expected OpenSessionDiff, got {cmd:?}");
+        };
+        assert_eq!((a.meta.id.as_str(), b.meta.id.as_str()), ("s1", "s2"));
+    }
+
+    #[test]
+    fn start_dir_opens_containing_project_and_esc_selects_it() {
+        let model = projects_model();
+        assert!(
+            model
+                .with_project_for_dir(Path::new("/tmp/other"))
+                .is_none()
+        );
+
+        let model = model
+            .with_project_for_dir(Path::new("/tmp/p2/src/app"))
+            .expect("inside p2");
+        let View::Sessions(view) = &model.view else {
+            panic!("expected sessions view");
+        };
+        assert_eq!(view.project_path, PathBuf::from("/tmp/p2"));
+
+        let esc = KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE);
+        let (model, _cmd) = update(model, AppEvent::Key(esc));
+        let View::Projects(view) = &model.view else {
+            panic!("expected projects view");
+        };
+        let selected = view.filtered_indices[view.selected];
+        assert_eq!(
+            model.data.projects[selected].project_path,
+            PathBuf::from("/tmp/p2")
+        );
+    }
+
+    #[test]
+    fn shift_down_selects_range_in_projects() {
+        let model = projects_model();
+
+        let key = KeyEvent::new(KeyCode::Down, KeyModifiers::SHIFT);
+        let (next, cmd) = update(model, AppEvent::Key(key));
+        assert!(matches!(cmd, AppCommand::None));
+
+        let View::Projects(view) = next.view else {
+            panic!("expected Projects view");
+        };
+        assert_eq!(view.selected, 1);
+        assert_eq!(view.selected_project_paths.len(), 2);
+        assert!(
+            view.selected_project_paths
+                .contains(&PathBuf::from("/tmp/p1"))
+        );
+        assert!(
+            view.selected_project_paths
+                .contains(&PathBuf::from("/tmp/p2"))
+        );
+    }
+
+    #[test]
+    fn ctrl_w_opens_the_project_in_the_ide_from_projects_and_sessions() {
+        let key = KeyEvent::new(KeyCode::Char('w'), KeyModifiers::CONTROL);
+        let (model, cmd) = update(projects_model(), AppEvent::Key(key));
+        assert!(matches!(
+            cmd,
+            AppCommand::OpenProjectInIde { ref project_path } if project_path == Path::new("/tmp/p1")
+        ));
+
+        let (model, _cmd) = update(
+            model,
+            AppEvent::Key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)),
+        );
+        let (model, cmd) = update(model, AppEvent::Key(key));
+        assert!(matches!(
+            cmd,
+            AppCommand::OpenProjectInIde { ref project_path } if project_path == Path::new("/tmp/p1")
+        ));
+        assert!(matches!(model.view, View::Sessions(_)));
+    }
+
+    #[test]
+    fn space_cycles_project_result_preview_through_recent_sessions() {
+        let key = KeyEvent::new(KeyCode::Char(' '), KeyModifiers::NONE);
+        let (mut model, cmd) = update(projects_model(), AppEvent::Key(key));
+        let AppCommand::OpenProjectResultPreview { sessions, index } = cmd else {
+            panic!("expected project result preview");
+        };
+        assert_eq!(index, 0);
+        assert_eq!(sessions.len(), 1);
+
+        let session = |id: &str| make_session("/tmp/p1", id, &format!("/tmp/{id}.jsonl"));
+        model.session_result_preview = Some(SessionResultPreviewOverlay {
+            session_title: "s1".to_string(),
+            output: "done".to_string(),
+            scroll: 0,
+            cycle: vec![session("s1"), session("s2")],
+            cycle_index: 1,
+        });
+        let (mut model, cmd) = update(model, AppEvent::Key(key));
+        assert!(matches!(
+            cmd,
+            AppCommand::OpenProjectResultPreview { index: 0, .. }
+        ));
+        assert!(model.session_result_preview.is_some());
+
+        if let Some(preview) = model.session_result_preview.as_mut() {
+            preview.cycle.clear();
+        }
+        let (model, cmd) = update(model, AppEvent::Key(key));
+        assert!(matches!(cmd, AppCommand::None));
+        assert!(model.session_result_preview.is_none());
+    }
+
+    #[test]
+    fn ctrl_l_opens_audit_log_and_esc_closes_it() {
+        let model = projects_model();
+        let key = KeyEvent::new(KeyCode::Char('l'), KeyModifiers::CONTROL);
+        let (mut model, cmd) = update(model, AppEvent::Key(key));
+        assert!(matches!(cmd, AppCommand::OpenAuditLog));
+
+        model.audit_log_overlay = Some(AuditLogOverlay {
+            entries: Vec::new(),
+            scroll: 0,
+        });
+        let (model, cmd) = update(model, AppEvent::Key(key));
+        assert!(matches!(cmd, AppCommand::None));
+        assert!(model.audit_log_overlay.is_some());
+
+        let key = KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE);
+        let (model, _cmd) = update(model, AppEvent::Key(key));
+        assert!(model.audit_log_overlay.is_none());
+    }
+
+    #[test]
+    fn devices_overlay_revokes_and_forgets_the_selected_device() {
+        let key = KeyEvent::new(KeyCode::Char('b'), KeyModifiers::CONTROL);
+        let (mut model, cmd) = update(projects_model(), AppEvent::Key(key));
+        assert!(matches!(cmd, AppCommand::OpenDevices));
+
+        let device = |device_id: &str, revoked: bool| RelayDevice {
+            device_id: device_id.to_string(),
+            label: None,
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+            last_seen_at: None,
+            revoked,
+        };
+        model.devices_overlay = Some(DevicesOverlay {
+            devices: vec![device("phone", false), device("tablet", true)],
+            selected: 0,
+            loading: false,
+            pairing: None,
+        });
+        let (model, _cmd) = update(
+            model,
+            AppEvent::Key(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE)),
+        );
+        let (model, cmd) = update(
+            model,
+            AppEvent::Key(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::NONE)),
+        );
+        assert!(matches!(
+            cmd,
+            AppCommand::SetDeviceRevoked { ref device_id, revoked: false } if device_id == "tablet"
+        ));
+        let (model, cmd) = update(
+            model,
+            AppEvent::Key(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::NONE)),
+        );
+        assert!(matches!(
+            cmd,
+            AppCommand::ForgetDevice { ref device_id } if device_id == "tablet"
+        ));
+
+        let (model, cmd) = update(
+            model,
+            AppEvent::Key(KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE)),
+        );
+        assert!(matches!(cmd, AppCommand::PairDevice));
+        assert_eq!(
+            model
+                .devices_overlay
+                .as_ref()
+                .and_then(|o| o.pairing.clone()),
+            Some(PairingState::Requesting)
+        );
+        let (model, cmd) = update(
+            model,
+            AppEvent::Key(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::NONE)),
+        );
+        assert!(matches!(cmd, AppCommand::None));
+        let (model, cmd) = update(
+            model,
+            AppEvent::Key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE)),
+        );
+        assert!(matches!(cmd, AppCommand::OpenDevices));
+        assert!(
+            model
+                .devices_overlay
+                .as_ref()
+                .is_some_and(|o| o.pairing.is_none())
+        );
+
+        let (model, _cmd) = update(
+            model,
+            AppEvent::Key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE)),
+        );
+        assert!(model.devices_overlay.is_none());
+    }
+
+    #[test]
+    fn ctrl_g_and_system_menu_entry_reload_config() {
+        let key = KeyEvent::new(KeyCode::Char('g'), KeyModifiers::CONTROL);
+        let (_model, cmd) = update(projects_model(), AppEvent::Key(key));
+        assert!(matches!(cmd, AppCommand::ReloadConfig));
+
+        let entry = MAIN_MENU_SYSTEM_ITEMS
+            .iter()
+            .find(|entry| entry.label == "Reload config")
+            .expect("menu entry");
+        let key = KeyEvent::new(entry.key.code, entry.key.modifiers);
+        let (_model, cmd) = update(projects_model(), AppEvent::Key(key));
+        assert!(matches!(cmd, AppCommand::ReloadConfig));
+    }
+
+    #[test]
+    fn window_menu_opens_audit_log_and_devices() {
+        let open = |label: &str| {
+            let entry = MAIN_MENU_WINDOW_ITEMS
+                .iter()
+                .find(|entry| entry.label == label)
+                .expect("menu entry");
+            let key = KeyEvent::new(entry.key.code, entry.key.modifiers);
+            update(projects_model(), AppEvent::Key(key)).1
+        };
+        assert!(matches!(open("Audit Log"), AppCommand::OpenAuditLog));
+        assert!(matches!(open("Devices"), AppCommand::OpenDevices));
+        assert!(
+            !MAIN_MENU_SYSTEM_ITEMS
+                .iter()
+                .any(|entry| matches!(entry.label, "Audit Log" | "Devices"))
+        );
+    }
+
+    #[test]
+    fn delete_opens_batch_confirm_for_selected_projects() {
+        let model = projects_model();
+        let key = KeyEvent::new(KeyCode::Down, KeyModifiers::SHIFT);
+        let (model, _cmd) = update(model, AppEvent::Key(key));
+
+        let key = KeyEvent::new(KeyCode::Delete, KeyModifiers::NONE);
+        let (next, cmd) = update(model, AppEvent::Key(key));
+        assert!(matches!(cmd, AppCommand::None));
+        assert!(next.delete_projects_confirm.is_some());
+
+        let key = KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE);
+        let (_next, cmd) = update(next, AppEvent::Key(key));
+        match cmd {
+            AppCommand::DeleteProjectLogsBatch { project_paths } => {
+                assert_eq!(project_paths.len(), 2);
+            }
+            _ => panic!("expected DeleteProjectLogsBatch"),
+        }
+    }
+
+    #[test]
+    fn delete_opens_batch_confirm_for_selected_sessions() {
+        let mut model = projects_model();
+        let project = model.data.projects.first().expect("project");
+        model.view = View::Sessions(SessionsView::new(
+            project.project_path.clone(),
+            project.sessions.len(),
+        ));
+
+        // Seed a sessions view with at least two sessions.
+        if let View::Sessions(view) = &mut model.view {
+            view.filtered_indices = vec![0, 0];
+        }
+
+        // Better: build a project with 2 sessions.
+        let p = ProjectSummary {
+            name: "p".to_string(),
+            project_path: PathBuf::from("/tmp/p"),
+            sessions: vec![
+                make_session("/tmp/p", "a", "/tmp/sessions/p-a.jsonl"),
+                make_session("/tmp/p", "b", "/tmp/sessions/p-b.jsonl"),
+            ],
+            last_modified: None,
+            hidden: false,
+        };
+        model.data.projects = vec![p.clone()];
+        model.view = View::Sessions(SessionsView::new(p.project_path.clone(), p.sessions.len()));
+
+        let key = KeyEvent::new(KeyCode::Down, KeyModifiers::SHIFT);
+        let (model, _cmd) = update(model, AppEvent::Key(key));
+
+        let key = KeyEvent::new(KeyCode::Delete, KeyModifiers::NONE);
+        let (next, cmd) = update(model, AppEvent::Key(key));
+        assert!(matches!(cmd, AppCommand::None));
+        assert!(next.delete_sessions_confirm.is_some());
+
+        let key = KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE);
+        let (_next, cmd) = update(next, AppEvent::Key(key));
+        match cmd {
+            AppCommand::DeleteSessionLogsBatch { log_paths } => {
+                assert_eq!(log_paths.len(), 2);
+            }
+            _ => panic!("expected DeleteSessionLogsBatch"),
+        }
+    }
+
+    #[test]
+    fn delete_opens_batch_confirm_for_selected_tasks() {
+        let mut model = projects_model();
+        let return_to = Box::new(model.view.clone());
+        let tasks = vec![
+            TaskSummaryRow {
                 id: TaskId::new("t1".to_string()),
                 title: "one".to_string(),
                 project_path: PathBuf::from("/tmp/p1"),
                 updated_at: SystemTime::UNIX_EPOCH,
                 image_count: 0,
+                schedule: None,
             },
             TaskSummaryRow {
                 id: TaskId::new("t2".to_string()),
@@ -5876,6 +12546,7 @@ mod multi_select_tests {
                 project_path: PathBuf::from("/tmp/p1"),
                 updated_at: SystemTime::UNIX_EPOCH,
                 image_count: 0,
+                schedule: None,
             },
         ];
         model.view = View::Tasks(TasksView::new(return_to, tasks));
@@ -5897,12 +12568,68 @@ mod multi_select_tests {
             _ => panic!("expected DeleteTasksBatch"),
         }
     }
+
+    #[test]
+    fn team_tasks_cannot_be_deleted_or_scheduled() {
+        let mut model = projects_model();
+        let return_to = Box::new(model.view.clone());
+        let tasks = vec![TaskSummaryRow {
+            id: TaskId::team("shared-1"),
+            title: "shared".to_string(),
+            project_path: PathBuf::from("/tmp/p1"),
+            updated_at: SystemTime::UNIX_EPOCH,
+            image_count: 0,
+            schedule: None,
+        }];
+        model.view = View::Tasks(TasksView::new(return_to, tasks));
+
+        let key = KeyEvent::new(KeyCode::Delete, KeyModifiers::NONE);
+        let (next, _cmd) = update(model, AppEvent::Key(key));
+        assert!(next.delete_task_confirm.is_none());
+        assert!(
+            next.notice
+                .as_deref()
+                .is_some_and(|n| n.contains("read-only"))
+        );
+
+        let key = KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL);
+        let (next, _cmd) = update(next, AppEvent::Key(key));
+        let View::Tasks(view) = next.view else {
+            panic!("expected Tasks view");
+        };
+        assert!(view.schedule_dialog.is_none());
+    }
 }
 
 fn is_text_input_char(character: char) -> bool {
     !character.is_control()
 }
 
+/// Nearest item of `kind` after (or before) `selected`.
+fn find_item_of_kind(
+    items: &[TimelineItem],
+    selected: usize,
+    kind: TimelineItemKind,
+    is_forward: bool,
+) -> Option<usize> {
+    if is_forward {
+        items
+            .iter()
+            .enumerate()
+            .skip(selected + 1)
+            .find(|(_, item)| item.kind == kind)
+            .map(|(index, _)| index)
+    } else {
+        items
+            .iter()
+            .enumerate()
+            .take(selected.min(items.len()))
+            .rev()
+            .find(|(_, item)| item.kind == kind)
+            .map(|(index, _)| index)
+    }
+}
+
 fn find_tool_output_index(
     items: &[TimelineItem],
     selected_index: usize,
@@ -103,6 +103,31 @@ fn apply_scroll(mut model: AppModel, col: u16, row: u16, direction: ScrollDirect
         return model;
     }
 
+    if let Some(mut overlay) = model.audit_log_overlay.take() {
+        let step = usize_to_u16(SCROLL_STEP);
+        match direction {
+            ScrollDirection::Up => {
+                overlay.scroll = overlay.scroll.saturating_sub(step);
+            }
+            ScrollDirection::Down => {
+                overlay.scroll = overlay.scroll.saturating_add(step);
+            }
+        }
+        model.audit_log_overlay = Some(overlay);
+        return model;
+    }
+
+    if let Some(mut overlay) = model.devices_overlay.take() {
+        overlay.selected = match direction {
+            ScrollDirection::Up => overlay.selected.saturating_sub(1),
+            ScrollDirection::Down => {
+                (overlay.selected + 1).min(overlay.devices.len().saturating_sub(1))
+            }
+        };
+        model.devices_overlay = Some(overlay);
+        return model;
+    }
+
     if let Some(mut overlay) = model.project_stats_overlay.take() {
         let step = usize_to_u16(SCROLL_STEP);
         match direction {
@@ -128,6 +153,11 @@ fn apply_scroll(mut model: AppModel, col: u16, row: u16, direction: ScrollDirect
             view.session_selected = scroll_index(view.session_selected, total, direction);
             model.view = View::Sessions(view);
         }
+        View::AllSessions(mut view) => {
+            let total = view.filtered_indices.len();
+            view.selected = scroll_index(view.selected, total, direction);
+            model.view = View::AllSessions(view);
+        }
         View::SessionDetail(mut view) => {
             let panels = session_detail_panels(model.terminal_size);
             let focus_from_mouse = panels.and_then(|panels| {
@@ -181,19 +211,24 @@ fn apply_scroll(mut model: AppModel, col: u16, row: u16, direction: ScrollDirect
             model.view = View::TaskDetail(view);
         }
         View::Processes(mut view) => {
-            let total = model.processes.len();
+            let total = view.row_count(&model.processes, &model.external_processes);
             view.selected = scroll_index(view.selected, total, direction);
             model.view = View::Processes(view);
         }
+        View::Activity(mut view) => {
+            let total = view.rows.len();
+            view.selected = scroll_index(view.selected, total, direction);
+            model.view = View::Activity(view);
+        }
         View::ProcessOutput(mut view) => {
-            let step = usize_to_u16(SCROLL_STEP);
             match direction {
                 ScrollDirection::Up => {
-                    view.scroll = view.scroll.saturating_sub(step);
-                }
-                ScrollDirection::Down => {
-                    view.scroll = view.scroll.saturating_add(step);
+                    view.scroll_up(
+                        SCROLL_STEP,
+                        super::page_step_standard_list(model.terminal_size),
+                    );
                 }
+                ScrollDirection::Down => view.scroll_down(SCROLL_STEP),
             }
             model.view = View::ProcessOutput(view);
         }
@@ -222,6 +257,8 @@ fn apply_left_click(mut model: AppModel, col: u16, row: u16) -> AppModel {
         || model.session_result_preview.is_some()
         || model.session_stats_overlay.is_some()
         || model.project_stats_overlay.is_some()
+        || model.audit_log_overlay.is_some()
+        || model.devices_overlay.is_some()
     {
         return model;
     }
@@ -253,6 +290,19 @@ fn apply_left_click(mut model: AppModel, col: u16, row: u16) -> AppModel {
                 model.view = View::Sessions(view);
             }
         }
+        View::AllSessions(mut view) => {
+            let list_area = standard_list_area(model.terminal_size);
+            if let Some(selected) = hit_test_list_click(
+                list_area,
+                view.selected,
+                view.filtered_indices.len(),
+                col,
+                row,
+            ) {
+                view.selected = selected;
+                model.view = View::AllSessions(view);
+            }
+        }
         View::Tasks(mut view) => {
             let list_area = standard_list_area(model.terminal_size);
             if let Some(selected) = hit_test_list_click(
@@ -268,13 +318,20 @@ fn apply_left_click(mut model: AppModel, col: u16, row: u16) -> AppModel {
         }
         View::Processes(mut view) => {
             let list_area = standard_list_area(model.terminal_size);
-            if let Some(selected) =
-                hit_test_list_click(list_area, view.selected, model.processes.len(), col, row)
-            {
+            let total = view.row_count(&model.processes, &model.external_processes);
+            if let Some(selected) = hit_test_list_click(list_area, view.selected, total, col, row) {
                 view.selected = selected;
                 model.view = View::Processes(view);
             }
         }
+        View::Activity(mut view) => {
+            let list_area = standard_list_area(model.terminal_size);
+            let total = view.rows.len();
+            if let Some(selected) = hit_test_list_click(list_area, view.selected, total, col, row) {
+                view.selected = selected;
+                model.view = View::Activity(view);
+            }
+        }
         View::SessionDetail(mut view) => {
             if let Some(panels) = session_detail_panels(model.terminal_size) {
                 if rect_contains(panels.timeline, col, row) {
@@ -628,6 +685,7 @@ mod tests {
                 project_path: PathBuf::from(format!("/tmp/project-{idx}")),
                 sessions: Vec::new(),
                 last_modified: None,
+                hidden: false,
             })
             .collect()
     }
@@ -695,6 +753,8 @@ mod tests {
             session_title: "session".to_string(),
             output: "hello\nworld".to_string(),
             scroll: 0,
+            cycle: Vec::new(),
+            cycle_index: 0,
         });
 
         let mouse = MouseEvent {
@@ -0,0 +1,227 @@
+use crate::app::LineEditor;
+use crate::infra::PathCompletion;
+use crossterm::event::{KeyCode, KeyEvent};
+use std::path::{Path, PathBuf};
+
+const MAX_SUGGESTIONS: usize = 8;
+
+/// Path entry with recent-project suggestions and filesystem completion. The shell refreshes
+/// `completion` after every edit (see `PathPickerAction::Refresh`), so the picker itself stays pure.
+#[derive(Clone, Debug)]
+pub struct PathPicker {
+    pub editor: LineEditor,
+    /// Known project paths, most recent first.
+    pub recent: Vec<PathBuf>,
+    pub completion: PathCompletion,
+    /// Highlighted suggestion; `None` while typing.
+    pub selected: Option<usize>,
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PathPickerAction {
+    None,
+    /// The text changed; the shell should recompute `completion`.
+    Refresh,
+    Cancel,
+    /// An existing directory, or `None` when the input is empty.
+    Submit(Option<PathBuf>),
+}
+
+impl PathPicker {
+    pub fn new(text: String, recent: Vec<PathBuf>) -> Self {
+        Self {
+            editor: LineEditor::from_text(text),
+            recent,
+            completion: PathCompletion::default(),
+            selected: None,
+            error: None,
+        }
+    }
+
+    /// Recent paths containing the input (case-insensitive), then filesystem completions.
+    pub fn suggestions(&self) -> Vec<String> {
+        let query = self.editor.text.trim().to_lowercase();
+        let mut out = Vec::new();
+        for path in &self.recent {
+            let text = path.display().to_string();
+            if text != self.editor.text.trim() && text.to_lowercase().contains(&query) {
+                out.push(text);
+            }
+        }
+        for candidate in &self.completion.candidates {
+            if !out.contains(candidate) {
+                out.push(candidate.clone());
+            }
+        }
+        out.truncate(MAX_SUGGESTIONS);
+        out
+    }
+
+    pub fn insert_str(&mut self, text: &str) -> PathPickerAction {
+        self.editor.insert_str(text);
+        self.edited()
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> PathPickerAction {
+        match key.code {
+            KeyCode::Esc => return PathPickerAction::Cancel,
+            KeyCode::Up => {
+                let count = self.suggestions().len();
+                if count > 0 {
+                    self.selected = match self.selected {
+                        None | Some(0) => Some(count - 1),
+                        Some(index) => Some(index - 1),
+                    };
+                }
+            }
+            KeyCode::Down => {
+                let count = self.suggestions().len();
+                if count > 0 {
+                    self.selected = match self.selected {
+                        Some(index) if index + 1 < count => Some(index + 1),
+                        _ => Some(0),
+                    };
+                }
+            }
+            KeyCode::Tab => return self.complete(),
+            KeyCode::Enter => {
+                if self.selected.is_some() {
+                    return self.complete();
+                }
+                return self.submit();
+            }
+            KeyCode::Backspace => {
+                self.editor.backspace();
+                return self.edited();
+            }
+            KeyCode::Delete => {
+                self.editor.delete_forward();
+                return self.edited();
+            }
+            KeyCode::Left => self.editor.move_left(),
+            KeyCode::Right => self.editor.move_right(),
+            KeyCode::Home => self.editor.move_home(),
+            KeyCode::End => self.editor.move_end(),
+            KeyCode::Char(character) if !character.is_control() => {
+                self.editor.insert_char(character);
+                return self.edited();
+            }
+            _ => {}
+        }
+        PathPickerAction::None
+    }
+
+    /// Takes the highlighted suggestion, or extends the input to the longest common prefix of
+    /// the filesystem completions.
+    fn complete(&mut self) -> PathPickerAction {
+        let suggestions = self.suggestions();
+        let next = match self.selected.and_then(|index| suggestions.get(index)) {
+            Some(choice) => Some(choice.clone()),
+            None => common_prefix(&self.completion.candidates)
+                .filter(|prefix| prefix.len() > self.editor.text.len()),
+        };
+        let Some(next) = next else {
+            return PathPickerAction::None;
+        };
+        self.editor = LineEditor::from_text(next);
+        self.edited()
+    }
+
+    fn submit(&mut self) -> PathPickerAction {
+        if self.editor.text.trim().is_empty() {
+            return PathPickerAction::Submit(None);
+        }
+        match &self.completion.resolved_dir {
+            Some(path) => PathPickerAction::Submit(Some(path.clone())),
+            None => {
+                self.error = Some(format!(
+                    "Not a directory: {}",
+                    Path::new(self.editor.text.trim()).display()
+                ));
+                PathPickerAction::None
+            }
+        }
+    }
+
+    fn edited(&mut self) -> PathPickerAction {
+        self.selected = None;
+        self.error = None;
+        PathPickerAction::Refresh
+    }
+}
+
+fn common_prefix(values: &[String]) -> Option<String> {
+    let (first, rest) = values.split_first()?;
+    let mut prefix = first.as_str();
+    for value in rest {
+        while !value.starts_with(prefix) {
+            let cut = prefix.char_indices().last().map_or(0, |(index, _)| index);
+            prefix = &prefix[..cut];
+        }
+    }
+    Some(prefix.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn tab_completes_common_prefix_and_enter_requires_a_directory() {
+        let mut picker = PathPicker::new("/work/a".to_string(), Vec::new());
+        picker.completion = PathCompletion {
+            candidates: vec!["/work/app-api/".to_string(), "/work/app-web/".to_string()],
+            resolved_dir: None,
+        };
+        assert_eq!(
+            picker.handle_key(key(KeyCode::Tab)),
+            PathPickerAction::Refresh
+        );
+        assert_eq!(picker.editor.text, "/work/app-");
+
+        assert_eq!(
+            picker.handle_key(key(KeyCode::Enter)),
+            PathPickerAction::None
+        );
+        assert_eq!(picker.error.as_deref(), Some("Not a directory: /work/app-"));
+
+        picker.completion.resolved_dir = Some(PathBuf::from("/work/app-"));
+        assert_eq!(
+            picker.handle_key(key(KeyCode::Enter)),
+            PathPickerAction::Submit(Some(PathBuf::from("/work/app-")))
+        );
+    }
+
+    #[test]
+    fn arrows_pick_recent_paths_matching_the_input() {
+        let mut picker = PathPicker::new(
+            String::new(),
+            vec![PathBuf::from("/work/api"), PathBuf::from("/work/web")],
+        );
+        assert_eq!(picker.insert_str("WE"), PathPickerAction::Refresh);
+        assert_eq!(picker.suggestions(), vec!["/work/web".to_string()]);
+
+        assert_eq!(
+            picker.handle_key(key(KeyCode::Down)),
+            PathPickerAction::None
+        );
+        assert_eq!(
+            picker.handle_key(key(KeyCode::Enter)),
+            PathPickerAction::Refresh
+        );
+        assert_eq!(picker.editor.text, "/work/web");
+        assert_eq!(picker.selected, None);
+
+        let mut empty = PathPicker::new(String::new(), Vec::new());
+        assert_eq!(
+            empty.handle_key(key(KeyCode::Enter)),
+            PathPickerAction::Submit(None)
+        );
+    }
+}
@@ -0,0 +1,133 @@
+use crate::app::SessionsView;
+use crate::domain::SessionSummary;
+use std::path::Path;
+
+const MAX_HISTORY: usize = 50;
+const MAX_RECENT: usize = 20;
+
+/// An opened session plus the Sessions view it was opened from, so going back restores both.
+#[derive(Clone, Debug)]
+pub struct SessionHistoryEntry {
+    pub from_sessions: SessionsView,
+    pub session: SessionSummary,
+}
+
+/// Browser-style back/forward over opened sessions, plus a most-recent-first list of distinct
+/// sessions for the Recent overlay.
+#[derive(Clone, Debug, Default)]
+pub struct SessionHistory {
+    entries: Vec<SessionHistoryEntry>,
+    position: usize,
+    recent: Vec<SessionHistoryEntry>,
+}
+
+impl SessionHistory {
+    /// Records an opened session. Reopening the current entry (as back/forward do) only
+    /// refreshes it; anything else drops the forward entries first.
+    pub fn visit(&mut self, entry: SessionHistoryEntry) {
+        self.recent
+            .retain(|recent| recent.session.log_path != entry.session.log_path);
+        self.recent.insert(0, entry.clone());
+        self.recent.truncate(MAX_RECENT);
+
+        if let Some(current) = self.entries.get_mut(self.position)
+            && current.session.log_path == entry.session.log_path
+        {
+            *current = entry;
+            return;
+        }
+        self.entries.truncate(self.position.saturating_add(1));
+        self.entries.push(entry);
+        if self.entries.len() > MAX_HISTORY {
+            self.entries.remove(0);
+        }
+        self.position = self.entries.len() - 1;
+    }
+
+    /// `showing` is the log of the open session, if any. Away from the current entry (say, back
+    /// in the Sessions list) this returns to it; otherwise it steps to the previous one.
+    pub fn back(&mut self, showing: Option<&Path>) -> Option<SessionHistoryEntry> {
+        let current = self.entries.get(self.position)?;
+        if showing != Some(current.session.log_path.as_path()) {
+            return Some(current.clone());
+        }
+        if self.position == 0 {
+            return None;
+        }
+        self.position -= 1;
+        self.entries.get(self.position).cloned()
+    }
+
+    pub fn forward(&mut self) -> Option<SessionHistoryEntry> {
+        if self.position + 1 >= self.entries.len() {
+            return None;
+        }
+        self.position += 1;
+        self.entries.get(self.position).cloned()
+    }
+
+    pub fn recent(&self) -> &[SessionHistoryEntry] {
+        &self.recent
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{SessionEngine, SessionMeta};
+    use std::path::PathBuf;
+
+    fn entry(id: &str) -> SessionHistoryEntry {
+        SessionHistoryEntry {
+            from_sessions: SessionsView::new(PathBuf::from("/tmp/p1"), 3),
+            session: SessionSummary {
+                engine: SessionEngine::Codex,
+                meta: SessionMeta {
+                    id: id.to_string(),
+                    cwd: PathBuf::from("/tmp/p1"),
+                    started_at_rfc3339: "2026-01-01T00:00:00Z".to_string(),
+                    git: None,
+                },
+                log_path: PathBuf::from(format!("/tmp/sessions/{id}.jsonl")),
+                title: id.to_string(),
+                file_size_bytes: 0,
+                file_modified: None,
+                parent_id: None,
+                project_root: None,
+                remote: None,
+            },
+        }
+    }
+
+    fn id(entry: Option<SessionHistoryEntry>) -> Option<String> {
+        entry.map(|entry| entry.session.meta.id)
+    }
+
+    #[test]
+    fn back_and_forward_walk_visits_and_a_new_visit_drops_forward_entries() {
+        let mut history = SessionHistory::default();
+        let showing = |id: &str| PathBuf::from(format!("/tmp/sessions/{id}.jsonl"));
+        assert_eq!(id(history.back(None)), None);
+
+        history.visit(entry("a"));
+        history.visit(entry("b"));
+        history.visit(entry("c"));
+        assert_eq!(id(history.back(Some(&showing("c")))), Some("b".to_string()));
+        history.visit(entry("b"));
+        assert_eq!(id(history.back(Some(&showing("b")))), Some("a".to_string()));
+        assert_eq!(id(history.back(Some(&showing("a")))), None);
+        assert_eq!(id(history.forward()), Some("b".to_string()));
+
+        history.visit(entry("d"));
+        assert_eq!(id(history.forward()), None);
+        assert_eq!(id(history.back(None)), Some("d".to_string()));
+        assert_eq!(id(history.back(Some(&showing("d")))), Some("b".to_string()));
+
+        let recent = history
+            .recent()
+            .iter()
+            .map(|entry| entry.session.meta.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(recent, vec!["d", "b", "c", "a"]);
+    }
+}
@@ -20,6 +20,11 @@ impl TextEditor {
         self.lines.join("\n")
     }
 
+    pub fn char_before_cursor(&self) -> Option<char> {
+        let line = self.lines.get(self.cursor_row)?;
+        line.chars().nth(self.cursor_col.checked_sub(1)?)
+    }
+
     pub fn insert_char(&mut self, ch: char) {
         let mut buffer = [0u8; 4];
         self.insert_str(ch.encode_utf8(&mut buffer));
@@ -1,8 +1,14 @@
 use crate::domain::{
-    ProjectSummary, SessionEngine, TimelineItem, TimelineItemKind, compute_skill_metrics,
-    detect_skill_loops, detect_skill_spans, index_projects,
+    AgentEngine, ProjectSummary, SessionColumn, SessionDiff, SessionDiffStatus, SessionEngine,
+    SessionKey, SpawnIoMode, TimelineItem, TimelineItemKind, compute_skill_metrics,
+    detect_skill_loops, detect_skill_spans, diff_session_timelines, index_projects,
+    parse_session_columns, system_time_to_rfc3339, token_usage_by_model,
+};
+use crate::infra::{
+    LoadLastAssistantOutputError, LoadSessionTimelineError, ProcessManager, ProcessSignal,
+    SpawnedAgentIo, SpawnedAgentProcess, TaskImportConflict, UpdateChannel, WatchSignal,
+    load_last_assistant_output, load_session_timeline, scan_all_sessions, watch_session_file,
 };
-use crate::infra::{LoadSessionTimelineError, load_session_timeline, scan_all_sessions};
 use std::fs;
 use std::io::{self, BufRead, IsTerminal, Write};
 use std::path::{Path, PathBuf};
@@ -11,27 +17,56 @@ use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use thiserror::Error;
 
 const DEFAULT_LIMIT: usize = 10;
+const FOLLOW_POLL_INTERVAL: Duration = Duration::from_secs(1);
+const DIFF_LINE_WIDTH: usize = 120;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum CliInvocation {
     PrintHelp,
     PrintVersion,
-    Tui { engine: Option<SessionEngine> },
+    Tui {
+        engine: Option<SessionEngine>,
+        /// `--project PATH`; without it the TUI opens the project containing the current folder.
+        project: Option<PathBuf>,
+        /// `--profile-startup`: write startup timings to `startup_profile.json` in the state dir.
+        profile_startup: bool,
+        /// `tui --session ID`: open this session (`engine:id` pins the engine) once scanned.
+        session: Option<String>,
+        /// `tui --item N`: select the 1-based timeline item N of `session`, as numbered by
+        /// `history`.
+        item: Option<usize>,
+    },
     Serve(crate::remote::ServeOptions),
     Command(CliCommand),
 }
 
+/// Output of the `projects` and `sessions` listings.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ListFormat {
+    /// Tab-separated rows without a header (the default).
+    #[default]
+    Tsv,
+    /// RFC 4180 rows with a header line.
+    Csv,
+    Json,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum CliCommand {
     Projects {
         engine: Option<SessionEngine>,
+        format: ListFormat,
     },
     Sessions {
         project_path: Option<PathBuf>,
+        /// `--all`: every project's sessions, newest first, with a project_path column.
+        all: bool,
         engine: Option<SessionEngine>,
         offset: usize,
         limit: usize,
-        size: bool,
+        /// Extra columns (`--columns`, `--size`), inserted before `log_path`.
+        columns: Vec<SessionColumn>,
+        format: ListFormat,
     },
     History {
         log_path: Option<PathBuf>,
@@ -41,6 +76,9 @@ pub enum CliCommand {
         limit: usize,
         full: bool,
         size: bool,
+        follow: bool,
+        /// `--item N`: print only the 1-based timeline item N, in full.
+        item: Option<usize>,
     },
     Skills {
         log_path: Option<PathBuf>,
@@ -48,8 +86,84 @@ pub enum CliCommand {
         engine: Option<SessionEngine>,
         json: bool,
         full: bool,
+        /// Aggregate over every session of the project instead of one session.
+        project: bool,
+    },
+    /// Open the raw session log in `$VISUAL`/`$EDITOR`.
+    Open {
+        log_path: Option<PathBuf>,
+        session_id: Option<String>,
+        engine: Option<SessionEngine>,
+    },
+    /// Print the last assistant output of a session, for shell pipelines.
+    Result {
+        /// A session log or project directory (positional or `--project`).
+        log_path: Option<PathBuf>,
+        session_id: Option<String>,
+        engine: Option<SessionEngine>,
+    },
+    Diff {
+        /// Each side is a log path, a project directory (its latest session) or a session id.
+        session_a: String,
+        session_b: String,
+        engine: Option<SessionEngine>,
+        full: bool,
+    },
+    TasksExport {
+        out: Option<PathBuf>,
+    },
+    TasksImport {
+        pack_path: PathBuf,
+        on_conflict: TaskImportConflict,
+        project_path: Option<PathBuf>,
+    },
+    AuditLog {
+        offset: usize,
+        limit: usize,
+        json: bool,
+    },
+    /// Agent worktrees of the repository at `project_path` (default: current directory).
+    WorktreesList {
+        project_path: Option<PathBuf>,
+    },
+    WorktreesMerge {
+        project_path: Option<PathBuf>,
+        name: String,
+    },
+    WorktreesRemove {
+        project_path: Option<PathBuf>,
+        name: String,
+        force: bool,
+    },
+    Update {
+        channel: UpdateChannel,
+        rollback: bool,
+    },
+    /// Blocks until the ccbox-spawned process `process_id` (`pN`) exits and exits with its code.
+    Await {
+        process_id: String,
+        timeout: Option<Duration>,
     },
-    Update,
+    /// Sends SIGTERM to every running ccbox-spawned process in the project (or below it, e.g.
+    /// worktrees), after a confirmation unless `yes`.
+    Kill {
+        project_path: Option<PathBuf>,
+        yes: bool,
+    },
+    /// Spawns an agent without the TUI, streams its output and waits for it to exit.
+    Run {
+        engine: AgentEngine,
+        /// Empty for the current folder; several projects each get their own agent.
+        project_paths: Vec<PathBuf>,
+        prompt: RunPrompt,
+    },
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RunPrompt {
+    Text(String),
+    /// Read the prompt from this file, or from stdin when it is `-`.
+    File(PathBuf),
 }
 
 #[derive(Debug, Error)]
@@ -68,6 +182,67 @@ pub enum CliParseError {
 
     #[error("unexpected argument: {0}")]
     UnexpectedArgument(String),
+
+    #[error("missing argument: {0}")]
+    MissingArgument(String),
+}
+
+/// `ccbox tui [--engine E] [--project PATH] [--session ID [--item N]]`; the global flags may
+/// also come before `tui`.
+fn parse_tui_args<'a>(
+    args: impl Iterator<Item = &'a String>,
+    mut engine: Option<SessionEngine>,
+    mut project: Option<PathBuf>,
+    mut profile_startup: bool,
+) -> Result<CliInvocation, CliParseError> {
+    let mut session: Option<String> = None;
+    let mut item: Option<usize> = None;
+    let mut args = args.peekable();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--engine" | "-e" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| CliParseError::MissingFlagValue("--engine".to_string()))?;
+                engine = parse_engine_flag("--engine", value)?;
+            }
+            "--project" | "-p" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| CliParseError::MissingFlagValue("--project".to_string()))?;
+                project = Some(PathBuf::from(value));
+            }
+            "--profile-startup" => {
+                profile_startup = true;
+            }
+            "--session" | "--id" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| CliParseError::MissingFlagValue("--session".to_string()))?;
+                session = Some(value.to_string());
+            }
+            "--item" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| CliParseError::MissingFlagValue("--item".to_string()))?;
+                item = Some(parse_item_flag(value)?);
+            }
+            _ if arg.starts_with('-') => {
+                return Err(CliParseError::UnknownFlag(arg.to_string()));
+            }
+            _ => return Err(CliParseError::UnexpectedArgument(arg.to_string())),
+        }
+    }
+    if item.is_some() && session.is_none() {
+        return Err(CliParseError::MissingFlagValue("--session".to_string()));
+    }
+    Ok(CliInvocation::Tui {
+        engine,
+        project,
+        profile_startup,
+        session,
+        item,
+    })
 }
 
 pub fn parse_invocation(args: &[String]) -> Result<CliInvocation, CliParseError> {
@@ -80,6 +255,8 @@ pub fn parse_invocation(args: &[String]) -> Result<CliInvocation, CliParseError>
 
     let mut iter = args.iter().skip(1).peekable();
     let mut global_engine: Option<SessionEngine> = None;
+    let mut global_project: Option<PathBuf> = None;
+    let mut profile_startup = false;
     while let Some(arg) = iter.peek() {
         match arg.as_str() {
             "--engine" | "-e" => {
@@ -89,6 +266,17 @@ pub fn parse_invocation(args: &[String]) -> Result<CliInvocation, CliParseError>
                     .ok_or_else(|| CliParseError::MissingFlagValue("--engine".to_string()))?;
                 global_engine = parse_engine_flag("--engine", value)?;
             }
+            "--project" | "-p" => {
+                let _ = iter.next();
+                let value = iter
+                    .next()
+                    .ok_or_else(|| CliParseError::MissingFlagValue("--project".to_string()))?;
+                global_project = Some(PathBuf::from(value));
+            }
+            "--profile-startup" => {
+                let _ = iter.next();
+                profile_startup = true;
+            }
             "--" => {
                 let _ = iter.next();
                 break;
@@ -100,8 +288,23 @@ pub fn parse_invocation(args: &[String]) -> Result<CliInvocation, CliParseError>
     let Some(subcommand) = iter.next() else {
         return Ok(CliInvocation::Tui {
             engine: global_engine,
+            project: global_project,
+            profile_startup,
+            session: None,
+            item: None,
         });
     };
+    if subcommand == "tui" {
+        return parse_tui_args(iter, global_engine, global_project, profile_startup);
+    }
+    if global_project.is_some() {
+        return Err(CliParseError::UnexpectedArgument("--project".to_string()));
+    }
+    if profile_startup {
+        return Err(CliParseError::UnexpectedArgument(
+            "--profile-startup".to_string(),
+        ));
+    }
 
     match subcommand.as_str() {
         "serve" => {
@@ -114,6 +317,7 @@ pub fn parse_invocation(args: &[String]) -> Result<CliInvocation, CliParseError>
             let mut print_identity = false;
             let mut no_relay = false;
             let mut listen_addr: Option<String> = None;
+            let mut api_port: Option<u16> = None;
 
             let mut args = iter.peekable();
             while let Some(arg) = args.next() {
@@ -157,6 +361,19 @@ pub fn parse_invocation(args: &[String]) -> Result<CliInvocation, CliParseError>
                         })?;
                         listen_addr = Some((*value).to_string());
                     }
+                    "--port" => {
+                        let value = args
+                            .next()
+                            .ok_or_else(|| CliParseError::MissingFlagValue("--port".to_string()))?;
+                        let port =
+                            value
+                                .parse::<u16>()
+                                .map_err(|_| CliParseError::InvalidFlagValue {
+                                    flag: "--port".to_string(),
+                                    value: value.to_string(),
+                                })?;
+                        api_port = Some(port);
+                    }
                     "--enable-shell" => {
                         enable_shell = true;
                     }
@@ -182,14 +399,25 @@ pub fn parse_invocation(args: &[String]) -> Result<CliInvocation, CliParseError>
                 print_identity,
                 no_relay,
                 listen_addr,
+                api_port,
             }))
         }
         "projects" => {
             let mut engine: Option<SessionEngine> = global_engine;
+            let mut format = ListFormat::default();
 
             let mut args = iter.peekable();
             while let Some(arg) = args.next() {
                 match arg.as_str() {
+                    "--json" => {
+                        format = ListFormat::Json;
+                    }
+                    "--format" => {
+                        let value = args.next().ok_or_else(|| {
+                            CliParseError::MissingFlagValue("--format".to_string())
+                        })?;
+                        format = parse_list_format_flag("--format", value)?;
+                    }
                     "--engine" | "-e" => {
                         let value = args.next().ok_or_else(|| {
                             CliParseError::MissingFlagValue("--engine".to_string())
@@ -205,18 +433,35 @@ pub fn parse_invocation(args: &[String]) -> Result<CliInvocation, CliParseError>
                 }
             }
 
-            Ok(CliInvocation::Command(CliCommand::Projects { engine }))
+            Ok(CliInvocation::Command(CliCommand::Projects {
+                engine,
+                format,
+            }))
         }
         "sessions" => {
             let mut project_path: Option<PathBuf> = None;
+            let mut all = false;
             let mut engine: Option<SessionEngine> = global_engine;
             let mut offset = 0usize;
             let mut limit = DEFAULT_LIMIT;
-            let mut size = false;
+            let mut columns: Vec<SessionColumn> = Vec::new();
+            let mut format = ListFormat::default();
 
             let mut args = iter.peekable();
             while let Some(arg) = args.next() {
                 match arg.as_str() {
+                    "--all" | "-a" => {
+                        all = true;
+                    }
+                    "--json" => {
+                        format = ListFormat::Json;
+                    }
+                    "--format" => {
+                        let value = args.next().ok_or_else(|| {
+                            CliParseError::MissingFlagValue("--format".to_string())
+                        })?;
+                        format = parse_list_format_flag("--format", value)?;
+                    }
                     "--engine" | "-e" => {
                         let value = args.next().ok_or_else(|| {
                             CliParseError::MissingFlagValue("--engine".to_string())
@@ -236,7 +481,20 @@ pub fn parse_invocation(args: &[String]) -> Result<CliInvocation, CliParseError>
                         offset = parse_usize_flag("--offset", value)?;
                     }
                     "--size" => {
-                        size = true;
+                        if !columns.contains(&SessionColumn::Size) {
+                            columns.push(SessionColumn::Size);
+                        }
+                    }
+                    "--columns" => {
+                        let value = args.next().ok_or_else(|| {
+                            CliParseError::MissingFlagValue("--columns".to_string())
+                        })?;
+                        columns = parse_session_columns(value).ok_or_else(|| {
+                            CliParseError::InvalidFlagValue {
+                                flag: "--columns".to_string(),
+                                value: (*value).to_string(),
+                            }
+                        })?;
                     }
                     _ if arg.starts_with('-') => {
                         return Err(CliParseError::UnknownFlag(arg.to_string()));
@@ -249,18 +507,27 @@ pub fn parse_invocation(args: &[String]) -> Result<CliInvocation, CliParseError>
                     }
                 }
             }
+            if all && let Some(project_path) = project_path {
+                return Err(CliParseError::UnexpectedArgument(
+                    project_path.display().to_string(),
+                ));
+            }
 
             Ok(CliInvocation::Command(CliCommand::Sessions {
                 project_path,
+                all,
                 engine,
                 offset,
                 limit,
-                size,
+                columns,
+                format,
             }))
         }
         "history" => {
             let mut full = false;
             let mut size = false;
+            let mut follow = false;
+            let mut item: Option<usize> = None;
             let mut offset = 0usize;
             let mut limit = DEFAULT_LIMIT;
             let mut log_path: Option<PathBuf> = None;
@@ -300,6 +567,15 @@ pub fn parse_invocation(args: &[String]) -> Result<CliInvocation, CliParseError>
                     "--size" => {
                         size = true;
                     }
+                    "--follow" | "-f" => {
+                        follow = true;
+                    }
+                    "--item" => {
+                        let value = args
+                            .next()
+                            .ok_or_else(|| CliParseError::MissingFlagValue("--item".to_string()))?;
+                        item = Some(parse_item_flag(value)?);
+                    }
                     _ if arg.starts_with('-') => {
                         return Err(CliParseError::UnknownFlag(arg.to_string()));
                     }
@@ -319,6 +595,9 @@ pub fn parse_invocation(args: &[String]) -> Result<CliInvocation, CliParseError>
                     }
                 }
             }
+            if item.is_some() && follow {
+                return Err(CliParseError::UnexpectedArgument("--follow".to_string()));
+            }
 
             Ok(CliInvocation::Command(CliCommand::History {
                 log_path,
@@ -328,11 +607,14 @@ pub fn parse_invocation(args: &[String]) -> Result<CliInvocation, CliParseError>
                 limit,
                 full,
                 size,
+                follow,
+                item,
             }))
         }
         "skills" => {
             let mut json = false;
             let mut full = false;
+            let mut project = false;
             let mut log_path: Option<PathBuf> = None;
             let mut session_id: Option<String> = None;
             let mut engine: Option<SessionEngine> = global_engine;
@@ -346,6 +628,9 @@ pub fn parse_invocation(args: &[String]) -> Result<CliInvocation, CliParseError>
                     "--full" => {
                         full = true;
                     }
+                    "--project" => {
+                        project = true;
+                    }
                     "--engine" | "-e" => {
                         let value = args.next().ok_or_else(|| {
                             CliParseError::MissingFlagValue("--engine".to_string())
@@ -378,55 +663,550 @@ pub fn parse_invocation(args: &[String]) -> Result<CliInvocation, CliParseError>
                 }
             }
 
+            if project && let Some(session_id) = session_id {
+                return Err(CliParseError::UnexpectedArgument(session_id));
+            }
+
             Ok(CliInvocation::Command(CliCommand::Skills {
                 log_path,
                 session_id,
                 engine,
                 json,
                 full,
+                project,
             }))
         }
-        "update" => {
-            let rest = iter.collect::<Vec<_>>();
-            if let Some(arg) = rest.first() {
-                if arg.starts_with('-') {
-                    return Err(CliParseError::UnknownFlag((*arg).to_string()));
+        "open" => {
+            let mut log_path: Option<PathBuf> = None;
+            let mut session_id: Option<String> = None;
+            let mut engine: Option<SessionEngine> = global_engine;
+
+            let mut args = iter.peekable();
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--engine" | "-e" => {
+                        let value = args.next().ok_or_else(|| {
+                            CliParseError::MissingFlagValue("--engine".to_string())
+                        })?;
+                        engine = parse_engine_flag("--engine", value)?;
+                    }
+                    "--id" | "--session-id" => {
+                        let value = args
+                            .next()
+                            .ok_or_else(|| CliParseError::MissingFlagValue("--id".to_string()))?;
+                        session_id = Some((*value).to_string());
+                    }
+                    _ if arg.starts_with('-') => {
+                        return Err(CliParseError::UnknownFlag(arg.to_string()));
+                    }
+                    _ => {
+                        if looks_like_path(arg) {
+                            if log_path.is_some() {
+                                return Err(CliParseError::UnexpectedArgument(arg.to_string()));
+                            }
+                            log_path = Some(PathBuf::from(arg));
+                            continue;
+                        }
+
+                        if session_id.is_some() {
+                            return Err(CliParseError::UnexpectedArgument(arg.to_string()));
+                        }
+                        session_id = Some((*arg).to_string());
+                    }
                 }
-                return Err(CliParseError::UnexpectedArgument((*arg).to_string()));
             }
-            Ok(CliInvocation::Command(CliCommand::Update))
-        }
-        other => Err(CliParseError::UnknownSubcommand(other.to_string())),
-    }
-}
 
-#[derive(Debug, Error)]
-pub enum CliRunError {
-    #[error(transparent)]
-    Scan(#[from] crate::infra::ScanError),
+            Ok(CliInvocation::Command(CliCommand::Open {
+                log_path,
+                session_id,
+                engine,
+            }))
+        }
+        "result" => {
+            let mut log_path: Option<PathBuf> = None;
+            let mut session_id: Option<String> = None;
+            let mut engine: Option<SessionEngine> = global_engine;
 
-    #[error(transparent)]
-    LoadTimeline(#[from] LoadSessionTimelineError),
+            let mut args = iter.peekable();
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--engine" | "-e" => {
+                        let value = args.next().ok_or_else(|| {
+                            CliParseError::MissingFlagValue("--engine".to_string())
+                        })?;
+                        engine = parse_engine_flag("--engine", value)?;
+                    }
+                    "--id" | "--session-id" => {
+                        let value = args
+                            .next()
+                            .ok_or_else(|| CliParseError::MissingFlagValue("--id".to_string()))?;
+                        session_id = Some((*value).to_string());
+                    }
+                    "--project" | "-p" => {
+                        let value = args.next().ok_or_else(|| {
+                            CliParseError::MissingFlagValue("--project".to_string())
+                        })?;
+                        if log_path.is_some() {
+                            return Err(CliParseError::UnexpectedArgument(value.to_string()));
+                        }
+                        log_path = Some(PathBuf::from(value));
+                    }
+                    _ if arg.starts_with('-') => {
+                        return Err(CliParseError::UnknownFlag(arg.to_string()));
+                    }
+                    _ => {
+                        if looks_like_path(arg) {
+                            if log_path.is_some() {
+                                return Err(CliParseError::UnexpectedArgument(arg.to_string()));
+                            }
+                            log_path = Some(PathBuf::from(arg));
+                            continue;
+                        }
 
-    #[error(transparent)]
-    PrepareSessionLog(#[from] crate::infra::PrepareSessionLogError),
+                        if session_id.is_some() {
+                            return Err(CliParseError::UnexpectedArgument(arg.to_string()));
+                        }
+                        session_id = Some((*arg).to_string());
+                    }
+                }
+            }
 
-    #[error("project not found: {0}\nHint: run `ccbox projects` and copy the full project path.")]
-    ProjectNotFound(String),
+            Ok(CliInvocation::Command(CliCommand::Result {
+                log_path,
+                session_id,
+                engine,
+            }))
+        }
+        "diff" => {
+            let mut full = false;
+            let mut engine: Option<SessionEngine> = global_engine;
+            let mut sessions: Vec<String> = Vec::new();
 
-    #[error("project has no sessions: {0}")]
-    ProjectHasNoSessions(String),
+            let mut args = iter.peekable();
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--full" => {
+                        full = true;
+                    }
+                    "--engine" | "-e" => {
+                        let value = args.next().ok_or_else(|| {
+                            CliParseError::MissingFlagValue("--engine".to_string())
+                        })?;
+                        engine = parse_engine_flag("--engine", value)?;
+                    }
+                    _ if arg.starts_with('-') => {
+                        return Err(CliParseError::UnknownFlag(arg.to_string()));
+                    }
+                    _ => {
+                        if sessions.len() == 2 {
+                            return Err(CliParseError::UnexpectedArgument(arg.to_string()));
+                        }
+                        sessions.push((*arg).to_string());
+                    }
+                }
+            }
 
-    #[error("project has no sessions for engine {engine}: {project}")]
-    ProjectHasNoSessionsForEngine { project: String, engine: String },
+            let mut sessions = sessions.into_iter();
+            let (Some(session_a), Some(session_b)) = (sessions.next(), sessions.next()) else {
+                return Err(CliParseError::MissingArgument(
+                    "two sessions to compare (log path, project directory or session id)"
+                        .to_string(),
+                ));
+            };
+            Ok(CliInvocation::Command(CliCommand::Diff {
+                session_a,
+                session_b,
+                engine,
+                full,
+            }))
+        }
+        "tasks" => {
+            let Some(action) = iter.next() else {
+                return Err(CliParseError::MissingArgument(
+                    "tasks subcommand (export|import)".to_string(),
+                ));
+            };
+            match action.as_str() {
+                "export" => {
+                    let mut out: Option<PathBuf> = None;
+
+                    let mut args = iter.peekable();
+                    while let Some(arg) = args.next() {
+                        match arg.as_str() {
+                            "--out" | "-o" => {
+                                let value = args.next().ok_or_else(|| {
+                                    CliParseError::MissingFlagValue("--out".to_string())
+                                })?;
+                                out = Some(PathBuf::from(value));
+                            }
+                            _ if arg.starts_with('-') => {
+                                return Err(CliParseError::UnknownFlag(arg.to_string()));
+                            }
+                            _ => {
+                                return Err(CliParseError::UnexpectedArgument(arg.to_string()));
+                            }
+                        }
+                    }
 
-    #[error(
-        "session not found: {0}\nHint: run `ccbox sessions <project-path>` and copy the session id column."
-    )]
-    SessionNotFound(String),
+                    Ok(CliInvocation::Command(CliCommand::TasksExport { out }))
+                }
+                "import" => {
+                    let mut pack_path: Option<PathBuf> = None;
+                    let mut on_conflict = TaskImportConflict::default();
+                    let mut project_path: Option<PathBuf> = None;
+
+                    let mut args = iter.peekable();
+                    while let Some(arg) = args.next() {
+                        match arg.as_str() {
+                            "--on-conflict" => {
+                                let value = args.next().ok_or_else(|| {
+                                    CliParseError::MissingFlagValue("--on-conflict".to_string())
+                                })?;
+                                on_conflict =
+                                    TaskImportConflict::parse(value).ok_or_else(|| {
+                                        CliParseError::InvalidFlagValue {
+                                            flag: "--on-conflict".to_string(),
+                                            value: value.to_string(),
+                                        }
+                                    })?;
+                            }
+                            "--project" => {
+                                let value = args.next().ok_or_else(|| {
+                                    CliParseError::MissingFlagValue("--project".to_string())
+                                })?;
+                                project_path = Some(PathBuf::from(value));
+                            }
+                            _ if arg.starts_with('-') => {
+                                return Err(CliParseError::UnknownFlag(arg.to_string()));
+                            }
+                            _ => {
+                                if pack_path.is_some() {
+                                    return Err(CliParseError::UnexpectedArgument(arg.to_string()));
+                                }
+                                pack_path = Some(PathBuf::from(arg));
+                            }
+                        }
+                    }
 
-    #[error(
-        "session id matches multiple sessions: {0}\nHint: pass a project directory before the session id, e.g. `ccbox history /path/to/project {0}`."
+                    let pack_path = pack_path
+                        .ok_or_else(|| CliParseError::MissingArgument("pack.json".to_string()))?;
+                    Ok(CliInvocation::Command(CliCommand::TasksImport {
+                        pack_path,
+                        on_conflict,
+                        project_path,
+                    }))
+                }
+                other => Err(CliParseError::UnknownSubcommand(format!("tasks {other}"))),
+            }
+        }
+        "run" => {
+            let mut engine = AgentEngine::Codex;
+            let mut project_paths: Vec<PathBuf> = Vec::new();
+            let mut prompt: Option<RunPrompt> = None;
+
+            let mut args = iter.peekable();
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--engine" => {
+                        let value = args.next().ok_or_else(|| {
+                            CliParseError::MissingFlagValue("--engine".to_string())
+                        })?;
+                        engine = match value.trim() {
+                            "codex" => AgentEngine::Codex,
+                            "claude" => AgentEngine::Claude,
+                            _ => {
+                                return Err(CliParseError::InvalidFlagValue {
+                                    flag: "--engine".to_string(),
+                                    value: value.to_string(),
+                                });
+                            }
+                        };
+                    }
+                    "--project" => {
+                        let value = args.next().ok_or_else(|| {
+                            CliParseError::MissingFlagValue("--project".to_string())
+                        })?;
+                        project_paths.push(PathBuf::from(value));
+                    }
+                    "--projects" => {
+                        let value = args.next().ok_or_else(|| {
+                            CliParseError::MissingFlagValue("--projects".to_string())
+                        })?;
+                        for path in value.split(',').map(str::trim) {
+                            if path.is_empty() {
+                                return Err(CliParseError::InvalidFlagValue {
+                                    flag: "--projects".to_string(),
+                                    value: value.to_string(),
+                                });
+                            }
+                            if !project_paths.iter().any(|known| known == Path::new(path)) {
+                                project_paths.push(PathBuf::from(path));
+                            }
+                        }
+                    }
+                    _ if prompt.is_some() => {
+                        return Err(CliParseError::UnexpectedArgument(arg.to_string()));
+                    }
+                    "--prompt-file" => {
+                        let value = args.next().ok_or_else(|| {
+                            CliParseError::MissingFlagValue("--prompt-file".to_string())
+                        })?;
+                        prompt = Some(RunPrompt::File(PathBuf::from(value)));
+                    }
+                    "-" => {
+                        prompt = Some(RunPrompt::File(PathBuf::from("-")));
+                    }
+                    _ if arg.starts_with('-') => {
+                        return Err(CliParseError::UnknownFlag(arg.to_string()));
+                    }
+                    _ => {
+                        prompt = Some(RunPrompt::Text(arg.to_string()));
+                    }
+                }
+            }
+
+            let prompt = prompt
+                .filter(|prompt| !matches!(prompt, RunPrompt::Text(text) if text.trim().is_empty()))
+                .ok_or_else(|| CliParseError::MissingArgument("prompt".to_string()))?;
+            Ok(CliInvocation::Command(CliCommand::Run {
+                engine,
+                project_paths,
+                prompt,
+            }))
+        }
+        "worktrees" => {
+            let mut action: Option<String> = None;
+            let mut name: Option<String> = None;
+            let mut project_path: Option<PathBuf> = None;
+            let mut force = false;
+
+            let mut args = iter.peekable();
+            if let Some(first) = args.peek()
+                && matches!(first.as_str(), "list" | "merge" | "remove")
+            {
+                action = args.next().map(|value| value.to_string());
+            }
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--project" => {
+                        let value = args.next().ok_or_else(|| {
+                            CliParseError::MissingFlagValue("--project".to_string())
+                        })?;
+                        project_path = Some(PathBuf::from(value));
+                    }
+                    "--force" if action.as_deref() == Some("remove") => {
+                        force = true;
+                    }
+                    _ if arg.starts_with('-') => {
+                        return Err(CliParseError::UnknownFlag(arg.to_string()));
+                    }
+                    _ => match action.as_deref() {
+                        Some("merge" | "remove") if name.is_none() => {
+                            name = Some(arg.to_string());
+                        }
+                        None | Some("list") if project_path.is_none() => {
+                            project_path = Some(PathBuf::from(arg));
+                        }
+                        _ => {
+                            return Err(CliParseError::UnexpectedArgument(arg.to_string()));
+                        }
+                    },
+                }
+            }
+
+            let missing_name = || CliParseError::MissingArgument("worktree name".to_string());
+            match action.as_deref() {
+                Some("merge") => Ok(CliInvocation::Command(CliCommand::WorktreesMerge {
+                    project_path,
+                    name: name.ok_or_else(missing_name)?,
+                })),
+                Some("remove") => Ok(CliInvocation::Command(CliCommand::WorktreesRemove {
+                    project_path,
+                    name: name.ok_or_else(missing_name)?,
+                    force,
+                })),
+                _ => Ok(CliInvocation::Command(CliCommand::WorktreesList {
+                    project_path,
+                })),
+            }
+        }
+        "audit-log" => {
+            let mut offset = 0usize;
+            let mut limit = DEFAULT_LIMIT;
+            let mut json = false;
+
+            let mut args = iter.peekable();
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--limit" | "-l" => {
+                        let value = args.next().ok_or_else(|| {
+                            CliParseError::MissingFlagValue("--limit".to_string())
+                        })?;
+                        limit = parse_usize_flag("--limit", value)?;
+                    }
+                    "--offset" | "-o" => {
+                        let value = args.next().ok_or_else(|| {
+                            CliParseError::MissingFlagValue("--offset".to_string())
+                        })?;
+                        offset = parse_usize_flag("--offset", value)?;
+                    }
+                    "--json" => {
+                        json = true;
+                    }
+                    _ if arg.starts_with('-') => {
+                        return Err(CliParseError::UnknownFlag(arg.to_string()));
+                    }
+                    _ => {
+                        return Err(CliParseError::UnexpectedArgument(arg.to_string()));
+                    }
+                }
+            }
+
+            Ok(CliInvocation::Command(CliCommand::AuditLog {
+                offset,
+                limit,
+                json,
+            }))
+        }
+        "await" => {
+            let mut process_id: Option<String> = None;
+            let mut timeout: Option<Duration> = None;
+
+            let mut args = iter.peekable();
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--timeout" | "-t" => {
+                        let value = args.next().ok_or_else(|| {
+                            CliParseError::MissingFlagValue("--timeout".to_string())
+                        })?;
+                        let seconds = parse_usize_flag("--timeout", value)?;
+                        timeout = Some(Duration::from_secs(seconds as u64));
+                    }
+                    _ if arg.starts_with('-') => {
+                        return Err(CliParseError::UnknownFlag(arg.to_string()));
+                    }
+                    _ => {
+                        if process_id.is_some() {
+                            return Err(CliParseError::UnexpectedArgument(arg.to_string()));
+                        }
+                        process_id = Some((*arg).to_string());
+                    }
+                }
+            }
+
+            let process_id = process_id
+                .ok_or_else(|| CliParseError::MissingArgument("process-id".to_string()))?;
+            Ok(CliInvocation::Command(CliCommand::Await {
+                process_id,
+                timeout,
+            }))
+        }
+        "kill" => {
+            let mut project_path: Option<PathBuf> = None;
+            let mut yes = false;
+
+            let mut args = iter.peekable();
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--project" | "-p" => {
+                        let value = args.next().ok_or_else(|| {
+                            CliParseError::MissingFlagValue("--project".to_string())
+                        })?;
+                        project_path = Some(PathBuf::from(value));
+                    }
+                    "--yes" | "-y" => {
+                        yes = true;
+                    }
+                    _ if arg.starts_with('-') => {
+                        return Err(CliParseError::UnknownFlag(arg.to_string()));
+                    }
+                    _ => return Err(CliParseError::UnexpectedArgument(arg.to_string())),
+                }
+            }
+
+            Ok(CliInvocation::Command(CliCommand::Kill {
+                project_path,
+                yes,
+            }))
+        }
+        "update" => {
+            let mut channel = UpdateChannel::default();
+            let mut rollback = false;
+
+            let mut args = iter.peekable();
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--channel" => {
+                        let value = args.next().ok_or_else(|| {
+                            CliParseError::MissingFlagValue("--channel".to_string())
+                        })?;
+                        channel = UpdateChannel::parse(value).ok_or_else(|| {
+                            CliParseError::InvalidFlagValue {
+                                flag: "--channel".to_string(),
+                                value: value.to_string(),
+                            }
+                        })?;
+                    }
+                    "--rollback" => {
+                        rollback = true;
+                    }
+                    _ if arg.starts_with('-') => {
+                        return Err(CliParseError::UnknownFlag(arg.to_string()));
+                    }
+                    _ => {
+                        return Err(CliParseError::UnexpectedArgument(arg.to_string()));
+                    }
+                }
+            }
+
+            Ok(CliInvocation::Command(CliCommand::Update {
+                channel,
+                rollback,
+            }))
+        }
+        other => Err(CliParseError::UnknownSubcommand(other.to_string())),
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum CliRunError {
+    #[error(transparent)]
+    Scan(#[from] crate::infra::ScanError),
+
+    #[error(transparent)]
+    LoadTimeline(#[from] LoadSessionTimelineError),
+
+    #[error(transparent)]
+    LoadLastOutput(#[from] LoadLastAssistantOutputError),
+
+    #[error(transparent)]
+    WatchSessionFile(#[from] crate::infra::WatchSessionFileError),
+
+    #[error(transparent)]
+    PrepareSessionLog(#[from] crate::infra::PrepareSessionLogError),
+
+    #[error(transparent)]
+    Editor(#[from] crate::infra::OpenEditorError),
+
+    #[error(transparent)]
+    Worktree(#[from] crate::infra::WorktreeError),
+
+    #[error("project not found: {0}\nHint: run `ccbox projects` and copy the full project path.")]
+    ProjectNotFound(String),
+
+    #[error("project has no sessions: {0}")]
+    ProjectHasNoSessions(String),
+
+    #[error("project has no sessions for engine {engine}: {project}")]
+    ProjectHasNoSessionsForEngine { project: String, engine: String },
+
+    #[error(
+        "session not found: {0}\nHint: run `ccbox sessions <project-path>` and copy the session id column."
+    )]
+    SessionNotFound(String),
+
+    #[error(
+        "session id matches multiple sessions: {0}\nHint: pass a project directory before the session id, e.g. `ccbox history /path/to/project {0}`, or name the engine with `--engine` or `--id <engine>:{0}`."
     )]
     SessionIdAmbiguous(String),
 
@@ -435,9 +1215,68 @@ pub enum CliRunError {
     )]
     HistoryIdWithLogPath(String),
 
+    #[error("item {item} is out of range: the session has {total} timeline items")]
+    HistoryItemOutOfRange { item: usize, total: usize },
+
+    #[error("session has no assistant output yet: {0}")]
+    NoAssistantOutput(String),
+
     #[error(transparent)]
     Update(#[from] crate::infra::UpdateError),
 
+    #[error(transparent)]
+    ProcessManager(#[from] crate::infra::ProcessManagerError),
+
+    #[error(transparent)]
+    Spawn(#[from] crate::infra::SpawnAgentProcessError),
+
+    #[error("agent exited with {0}")]
+    AgentFailed(String),
+
+    #[error(
+        "process not found: {0}\nHint: pass a process id (pN) shown by `ccbox run` or the Processes screen."
+    )]
+    ProcessNotFound(String),
+
+    #[error("process {process_id} exited with {code}")]
+    ProcessExited { process_id: String, code: i32 },
+
+    #[error("process {0} was killed by a signal")]
+    ProcessKilled(String),
+
+    #[error("timed out after {seconds}s waiting for process {process_id}")]
+    AwaitTimedOut { process_id: String, seconds: u64 },
+
+    #[error("refusing to kill {0} process(es) without confirmation\nHint: pass --yes.")]
+    KillNotConfirmed(usize),
+
+    #[error(transparent)]
+    PromptFile(#[from] crate::infra::ReadPromptFileError),
+
+    #[error("prompt is empty")]
+    EmptyPrompt,
+
+    #[error(transparent)]
+    TaskStore(#[from] crate::infra::TaskStoreError),
+
+    #[error(transparent)]
+    TaskPack(#[from] crate::infra::TaskPackError),
+
+    #[error(transparent)]
+    ResolveStateDir(#[from] crate::infra::ResolveCcboxStateDirError),
+
+    #[error(transparent)]
+    Config(#[from] crate::infra::LoadCcboxConfigError),
+
+    #[error(transparent)]
+    Encryption(#[from] crate::infra::EncryptionError),
+
+    #[error(transparent)]
+    AuditLog(#[from] crate::infra::AuditLogError),
+
+    #[error("failed to encode audit entry: {0}")]
+    EncodeAuditEntry(#[from] serde_json::Error),
+
     #[error(transparent)]
     WriteOutput(#[from] io::Error),
 
@@ -445,6 +1284,18 @@ pub enum CliRunError {
     CurrentDir(String),
 }
 
+impl CliRunError {
+    /// Exit status for ccbox itself: `await` passes the awaited process's code through and
+    /// times out with 124, like `timeout(1)`; every other failure is 1.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::ProcessExited { code, .. } => *code,
+            Self::AwaitTimedOut { .. } => 124,
+            _ => 1,
+        }
+    }
+}
+
 struct CliUpdateNotice {
     cached_hint: Option<String>,
     rx: Option<Receiver<Option<String>>>,
@@ -585,7 +1436,7 @@ pub fn run(command: CliCommand, sessions_dir: &Path) -> Result<(), CliRunError>
     let mut err = io::BufWriter::new(stderr.lock());
 
     let mut update_notice = match command {
-        CliCommand::Update => CliUpdateNotice {
+        CliCommand::Update { .. } => CliUpdateNotice {
             cached_hint: None,
             rx: None,
             use_color: should_color_stderr(),
@@ -594,26 +1445,45 @@ pub fn run(command: CliCommand, sessions_dir: &Path) -> Result<(), CliRunError>
     };
 
     match command {
-        CliCommand::Projects { engine } => {
+        CliCommand::Projects { engine, format } => {
             let (projects, warnings, notice) = load_projects(sessions_dir)?;
-            for project in projects {
-                let session_count = project
-                    .sessions
-                    .iter()
-                    .filter(|session| engine.is_none_or(|engine| session.engine == engine))
-                    .count();
-                if session_count == 0 {
-                    continue;
+            if format == ListFormat::Json {
+                let payload = build_projects_json_payload(&projects, engine);
+                let rendered =
+                    serde_json::to_string_pretty(&payload).unwrap_or_else(|_| payload.to_string());
+                if !write_line(&mut out, &rendered)? {
+                    return Ok(());
                 }
-                let line = format!(
-                    "{}\t{}\t{}",
-                    project.name,
-                    project.project_path.display(),
-                    session_count
-                );
-                if !write_line(&mut out, &line)? {
+            } else {
+                if format == ListFormat::Csv
+                    && !write_line(
+                        &mut out,
+                        &format_list_row(format, &["name", "path", "session_count"]),
+                    )?
+                {
                     return Ok(());
                 }
+                for project in &projects {
+                    let session_count = project
+                        .sessions
+                        .iter()
+                        .filter(|session| engine.is_none_or(|engine| session.engine == engine))
+                        .count();
+                    if session_count == 0 {
+                        continue;
+                    }
+                    let line = format_list_row(
+                        format,
+                        &[
+                            project.name.clone(),
+                            project.project_path.display().to_string(),
+                            session_count.to_string(),
+                        ],
+                    );
+                    if !write_line(&mut out, &line)? {
+                        return Ok(());
+                    }
+                }
             }
             if let Some(notice) = notice {
                 if !write_line(&mut err, &notice)? {
@@ -628,42 +1498,93 @@ pub fn run(command: CliCommand, sessions_dir: &Path) -> Result<(), CliRunError>
         }
         CliCommand::Sessions {
             project_path,
+            all,
             engine,
             offset,
             limit,
-            size,
+            columns,
+            format,
         } => {
             let (projects, warnings, notice) = load_projects(sessions_dir)?;
-            let project = select_project(projects, project_path)?;
+            let projects = if all {
+                projects
+            } else {
+                vec![select_project(projects, project_path)?]
+            };
 
-            let sessions = project
-                .sessions
+            let mut rows = projects
                 .iter()
-                .filter(|session| engine.is_none_or(|engine| session.engine == engine))
+                .flat_map(|project| {
+                    project
+                        .sessions
+                        .iter()
+                        .map(move |session| (project, session))
+                })
+                .filter(|(_, session)| engine.is_none_or(|engine| session.engine == engine))
+                .collect::<Vec<_>>();
+            if all {
+                rows.sort_by_key(|(_, session)| std::cmp::Reverse(session.file_modified));
+            }
+            let rows = rows
+                .into_iter()
                 .skip(offset)
-                .take(limit);
-            for session in sessions {
-                let line = if size {
-                    format!(
-                        "{}\t{}\t{}\t{}\t{}",
-                        session.meta.started_at_rfc3339,
-                        session.meta.id,
-                        session.title,
-                        session.file_size_bytes,
-                        session.log_path.display(),
-                    )
-                } else {
-                    format!(
-                        "{}\t{}\t{}\t{}",
-                        session.meta.started_at_rfc3339,
-                        session.meta.id,
-                        session.title,
-                        session.log_path.display(),
-                    )
-                };
-                if !write_line(&mut out, &line)? {
+                .take(limit)
+                .collect::<Vec<_>>();
+            let sessions = rows.iter().map(|(_, session)| *session).collect::<Vec<_>>();
+            // Token counts, item counts and durations come from the index the TUI maintains;
+            // listed sessions it hasn't seen yet are indexed here (without saving).
+            let needs_index = format == ListFormat::Json
+                || columns.iter().any(|column| *column != SessionColumn::Size);
+            let index = if needs_index {
+                let prior = crate::infra::resolve_ccbox_state_dir()
+                    .ok()
+                    .and_then(|dir| crate::infra::load_session_index(&dir).ok())
+                    .unwrap_or_default();
+                let listed = sessions
+                    .iter()
+                    .map(|session| (*session).clone())
+                    .collect::<Vec<_>>();
+                crate::infra::refresh_session_index(&listed, &prior)
+            } else {
+                crate::infra::SessionIndex::default()
+            };
+            if format == ListFormat::Json {
+                let project = (!all).then(|| &projects[0]);
+                let payload = build_sessions_json_payload(project, &rows, &index);
+                let rendered =
+                    serde_json::to_string_pretty(&payload).unwrap_or_else(|_| payload.to_string());
+                if !write_line(&mut out, &rendered)? {
                     return Ok(());
                 }
+            } else {
+                if format == ListFormat::Csv {
+                    let mut header = vec!["started_at"];
+                    if all {
+                        header.push("project_path");
+                    }
+                    header.extend(["session_id", "title"]);
+                    header.extend(columns.iter().map(|column| session_column_header(*column)));
+                    header.push("log_path");
+                    if !write_line(&mut out, &format_list_row(format, &header))? {
+                        return Ok(());
+                    }
+                }
+                for (project, session) in &rows {
+                    let mut fields = vec![session.meta.started_at_rfc3339.clone()];
+                    if all {
+                        fields.push(project.project_path.display().to_string());
+                    }
+                    fields.extend([session.meta.id.clone(), session.title.clone()]);
+                    fields.extend(
+                        columns
+                            .iter()
+                            .map(|column| session_column_value(*column, session, &index)),
+                    );
+                    fields.push(session.log_path.display().to_string());
+                    if !write_line(&mut out, &format_list_row(format, &fields))? {
+                        return Ok(());
+                    }
+                }
             }
             if let Some(notice) = notice {
                 if !write_line(&mut err, &notice)? {
@@ -684,6 +1605,8 @@ pub fn run(command: CliCommand, sessions_dir: &Path) -> Result<(), CliRunError>
             limit,
             full,
             size,
+            follow,
+            item,
         } => {
             let log_path =
                 resolve_history_log_path(sessions_dir, &mut err, log_path, session_id, engine)?;
@@ -691,10 +1614,29 @@ pub fn run(command: CliCommand, sessions_dir: &Path) -> Result<(), CliRunError>
             let file_size_bytes = fs::metadata(&log_path).ok().map(|meta| meta.len());
             let timeline = load_session_timeline(&log_path)?;
             let total_items = timeline.items.len();
+            if let Some(item) = item {
+                let timeline_item =
+                    timeline
+                        .items
+                        .get(item - 1)
+                        .ok_or(CliRunError::HistoryItemOutOfRange {
+                            item,
+                            total: total_items,
+                        })?;
+                print_timeline_item(&mut out, item, timeline_item, true)?;
+                update_notice.write_hint(&mut err)?;
+                return Ok(());
+            }
+            // Following starts from the tail, like `tail -f`.
+            let offset = if follow {
+                offset.max(total_items.saturating_sub(limit))
+            } else {
+                offset
+            };
             let mut printed = 0usize;
-            for item in timeline.items.iter().skip(offset).take(limit) {
+            for (index, item) in timeline.items.iter().enumerate().skip(offset).take(limit) {
                 printed = printed.saturating_add(1);
-                if !print_timeline_item(&mut out, item, full)? {
+                if !print_timeline_item(&mut out, index + 1, item, full)? {
                     return Ok(());
                 }
             }
@@ -717,6 +1659,74 @@ pub fn run(command: CliCommand, sessions_dir: &Path) -> Result<(), CliRunError>
             if timeline.truncated && !write_line(&mut err, "truncated: true")? {
                 return Ok(());
             }
+            if follow {
+                out.flush()?;
+                err.flush()?;
+                return follow_history(&mut out, &log_path, total_items, full);
+            }
+            update_notice.write_hint(&mut err)?;
+            Ok(())
+        }
+        CliCommand::Open {
+            log_path,
+            session_id,
+            engine,
+        } => {
+            let log_path =
+                resolve_history_log_path(sessions_dir, &mut err, log_path, session_id, engine)?;
+            crate::infra::open_in_editor(&log_path)?;
+            Ok(())
+        }
+        CliCommand::Result {
+            log_path,
+            session_id,
+            engine,
+        } => {
+            let log_path =
+                resolve_history_log_path(sessions_dir, &mut err, log_path, session_id, engine)?;
+            let output = load_last_assistant_output(&log_path)?
+                .output
+                .ok_or_else(|| CliRunError::NoAssistantOutput(log_path.display().to_string()))?;
+            if !write_line(&mut out, output.trim_end_matches('\n'))? {
+                return Ok(());
+            }
+            out.flush()?;
+            Ok(())
+        }
+        CliCommand::Skills {
+            log_path,
+            engine,
+            json,
+            project: true,
+            ..
+        } => {
+            let (projects, warnings, notice) = load_projects(sessions_dir)?;
+            let project = select_project(projects, log_path)?;
+            let sessions = project
+                .sessions
+                .iter()
+                .filter(|session| engine.is_none_or(|engine| session.engine == engine));
+            let report = crate::infra::project_skill_usage(sessions);
+
+            if json {
+                let payload = build_project_skills_json_payload(&project, &report);
+                let rendered =
+                    serde_json::to_string_pretty(&payload).unwrap_or_else(|_| payload.to_string());
+                if !write_line(&mut out, &rendered)? {
+                    return Ok(());
+                }
+            } else {
+                print_project_skills_human(&mut out, &project, &report)?;
+            }
+
+            if warnings > 0 && !write_line(&mut err, &format!("warnings: {warnings}"))? {
+                return Ok(());
+            }
+            if let Some(notice) = notice
+                && !write_line(&mut err, &notice)?
+            {
+                return Ok(());
+            }
             update_notice.write_hint(&mut err)?;
             Ok(())
         }
@@ -726,6 +1736,7 @@ pub fn run(command: CliCommand, sessions_dir: &Path) -> Result<(), CliRunError>
             engine,
             json,
             full,
+            project: false,
         } => {
             let log_path =
                 resolve_history_log_path(sessions_dir, &mut err, log_path, session_id, engine)?;
@@ -739,8 +1750,16 @@ pub fn run(command: CliCommand, sessions_dir: &Path) -> Result<(), CliRunError>
                 .collect::<Vec<_>>();
 
             if json {
-                let payload =
-                    build_skills_json_payload(&log_path, &timeline.items, &spans, &loops, &metrics);
+                let tokens_by_model =
+                    token_usage_by_model(&timeline.items, &timeline.turn_contexts);
+                let payload = build_skills_json_payload(
+                    &log_path,
+                    &timeline.items,
+                    &spans,
+                    &loops,
+                    &metrics,
+                    &tokens_by_model,
+                );
                 let rendered =
                     serde_json::to_string_pretty(&payload).unwrap_or_else(|_| payload.to_string());
                 if !write_line(&mut out, &rendered)? {
@@ -761,10 +1780,212 @@ pub fn run(command: CliCommand, sessions_dir: &Path) -> Result<(), CliRunError>
             update_notice.write_hint(&mut err)?;
             Ok(())
         }
-        CliCommand::Update => match crate::infra::self_update()? {
-            Some(update) => {
+        CliCommand::Diff {
+            session_a,
+            session_b,
+            engine,
+            full,
+        } => {
+            let mut sides = Vec::with_capacity(2);
+            for session in [session_a, session_b] {
+                let (log_path, session_id) = if looks_like_path(&session) {
+                    (Some(PathBuf::from(session)), None)
+                } else {
+                    (None, Some(session))
+                };
+                let log_path =
+                    resolve_history_log_path(sessions_dir, &mut err, log_path, session_id, engine)?;
+                let timeline = load_session_timeline(&log_path)?;
+                sides.push((log_path, timeline));
+            }
+            let (path_a, timeline_a) = &sides[0];
+            let (path_b, timeline_b) = &sides[1];
+
+            let diff = diff_session_timelines(&timeline_a.items, &timeline_b.items);
+            let header = [
+                format!("--- a: {}", path_a.display()),
+                format!("+++ b: {}", path_b.display()),
+            ];
+            for line in header {
+                if !write_line(&mut out, &line)? {
+                    return Ok(());
+                }
+            }
+            if !print_session_diff(&mut out, &diff, full)? {
+                return Ok(());
+            }
+
+            let warnings = timeline_a.warnings + timeline_b.warnings;
+            if warnings > 0 && !write_line(&mut err, &format!("warnings: {warnings}"))? {
+                return Ok(());
+            }
+            if (timeline_a.truncated || timeline_b.truncated)
+                && !write_line(&mut err, "truncated: true")?
+            {
+                return Ok(());
+            }
+            update_notice.write_hint(&mut err)?;
+            Ok(())
+        }
+        CliCommand::TasksExport { out: out_path } => {
+            let store = crate::infra::TaskStore::open_default()?;
+            let cipher = load_artifact_cipher()?;
+            let (pack, summary) = crate::infra::export_task_pack(&store, cipher.as_ref())?;
+            let line = format!(
+                "exported:\t{} tasks\t{} images",
+                summary.tasks, summary.images
+            );
+            match out_path {
+                Some(path) => {
+                    crate::infra::write_task_pack(&path, &pack, cipher.as_ref())?;
+                    write_line(&mut out, &line)?;
+                }
+                None => {
+                    let bytes = crate::infra::encode_task_pack(&pack, cipher.as_ref())?;
+                    match out.write_all(&bytes).and_then(|()| out.flush()) {
+                        Ok(()) => {}
+                        Err(error) if error.kind() == io::ErrorKind::BrokenPipe => return Ok(()),
+                        Err(error) => return Err(error.into()),
+                    }
+                    write_line(&mut err, &line)?;
+                }
+            }
+            if summary.missing_images > 0 {
+                let warning = format!(
+                    "warning: {} image file(s) no longer exist and were skipped",
+                    summary.missing_images
+                );
+                write_line(&mut err, &warning)?;
+            }
+            Ok(())
+        }
+        CliCommand::TasksImport {
+            pack_path,
+            on_conflict,
+            project_path,
+        } => {
+            let cipher = load_artifact_cipher()?;
+            let pack = crate::infra::read_task_pack(&pack_path, cipher.as_ref())?;
+            let store = crate::infra::TaskStore::open_default()?;
+            let images_dir = crate::infra::resolve_task_images_dir()?;
+            let summary = crate::infra::import_task_pack(
+                &store,
+                &pack,
+                &images_dir,
+                on_conflict,
+                project_path.as_deref(),
+                cipher.as_ref(),
+            )?;
+            let line = format!(
+                "imported:\t{}\toverwritten:\t{}\tduplicated:\t{}\tskipped:\t{}",
+                summary.imported, summary.overwritten, summary.duplicated, summary.skipped
+            );
+            write_line(&mut out, &line)?;
+            Ok(())
+        }
+        CliCommand::AuditLog {
+            offset,
+            limit,
+            json,
+        } => {
+            let state_dir = crate::infra::resolve_ccbox_state_dir()?;
+            let entries = crate::infra::load_audit_entries(&state_dir)?;
+            for entry in entries.iter().rev().skip(offset).take(limit) {
+                let line = if json {
+                    serde_json::to_string(entry)?
+                } else {
+                    entry.to_tsv()
+                };
+                if !write_line(&mut out, &line)? {
+                    return Ok(());
+                }
+            }
+            update_notice.write_hint(&mut err)?;
+            Ok(())
+        }
+        CliCommand::WorktreesList { project_path } => {
+            let project_path = project_path_or_current_dir(project_path)?;
+            for worktree in crate::infra::list_agent_worktrees(&project_path)? {
+                let line = format!(
+                    "{}\t{}\t{}",
+                    worktree.name,
+                    worktree.branch,
+                    worktree.path.display()
+                );
+                if !write_line(&mut out, &line)? {
+                    return Ok(());
+                }
+            }
+            Ok(())
+        }
+        CliCommand::WorktreesMerge { project_path, name } => {
+            let project_path = project_path_or_current_dir(project_path)?;
+            let summary = crate::infra::merge_agent_worktree(&project_path, &name)?;
+            out.write_all(summary.as_bytes())?;
+            Ok(())
+        }
+        CliCommand::WorktreesRemove {
+            project_path,
+            name,
+            force,
+        } => {
+            let project_path = project_path_or_current_dir(project_path)?;
+            let branch = crate::infra::remove_agent_worktree(&project_path, &name, force)?;
+            write_line(&mut out, &format!("removed:\t{branch}"))?;
+            Ok(())
+        }
+        CliCommand::Run {
+            engine,
+            mut project_paths,
+            prompt,
+        } => {
+            if project_paths.is_empty() {
+                project_paths.push(project_path_or_current_dir(None)?);
+            }
+            let prompt = match prompt {
+                RunPrompt::Text(text) => text,
+                RunPrompt::File(path) => crate::infra::read_prompt_file(&path)?,
+            };
+            if prompt.trim().is_empty() {
+                return Err(CliRunError::EmptyPrompt);
+            }
+            run_agent_headless(
+                engine,
+                &project_paths,
+                &prompt,
+                sessions_dir,
+                &mut out,
+                &mut err,
+            )
+        }
+        CliCommand::Await {
+            process_id,
+            timeout,
+        } => await_process(sessions_dir, process_id, timeout),
+        CliCommand::Kill { project_path, yes } => {
+            let project_path = project_path_or_current_dir(project_path)?;
+            kill_project_processes(sessions_dir, &project_path, yes, &mut out, &mut err)
+        }
+        CliCommand::Update { rollback: true, .. } => {
+            let version = crate::infra::rollback_update()?;
+            let line = format!(
+                "rolled-back:\tv{}\t->\tv{version}",
+                env!("CARGO_PKG_VERSION")
+            );
+            write_line(&mut out, &line)?;
+            Ok(())
+        }
+        CliCommand::Update { channel, .. } => match crate::infra::self_update(channel)? {
+            Some(installed) => {
+                let update = &installed.update;
                 let line = format!("updated:\tv{}\t->\t{}", update.current, update.latest_tag);
                 write_line(&mut out, &line)?;
+                if installed.signature == crate::infra::ReleaseSignature::Unchecked {
+                    write_line(
+                        &mut err,
+                        "note: this build has no release key; only the sha256 was checked",
+                    )?;
+                }
                 Ok(())
             }
             None => {
@@ -878,12 +2099,109 @@ fn print_skills_human(
     Ok(())
 }
 
+fn build_projects_json_payload(
+    projects: &[ProjectSummary],
+    engine: Option<SessionEngine>,
+) -> serde_json::Value {
+    let rows = projects
+        .iter()
+        .filter_map(|project| {
+            let sessions = project
+                .sessions
+                .iter()
+                .filter(|session| engine.is_none_or(|engine| session.engine == engine))
+                .collect::<Vec<_>>();
+            if sessions.is_empty() {
+                return None;
+            }
+            let mut engines = sessions
+                .iter()
+                .map(|session| session.engine)
+                .collect::<Vec<_>>();
+            engines.sort();
+            engines.dedup();
+            Some(serde_json::json!({
+                "name": project.name.clone(),
+                "path": project.project_path.display().to_string(),
+                "session_count": sessions.len(),
+                "engines": engines.into_iter().map(engine_flag_value).collect::<Vec<_>>(),
+                "last_modified": project.last_modified.and_then(system_time_to_rfc3339),
+            }))
+        })
+        .collect::<Vec<_>>();
+    serde_json::json!({ "projects": rows })
+}
+
+/// `project` is `None` for `sessions --all`; each row names its project either way.
+fn build_sessions_json_payload(
+    project: Option<&ProjectSummary>,
+    sessions: &[(&ProjectSummary, &crate::domain::SessionSummary)],
+    index: &crate::infra::SessionIndex,
+) -> serde_json::Value {
+    let rows = sessions
+        .iter()
+        .map(|(session_project, session)| {
+            serde_json::json!({
+                "key": SessionKey::new(session.engine, session.meta.id.clone()).to_string(),
+                "project_path": session_project.project_path.display().to_string(),
+                "session_id": session.meta.id.clone(),
+                "engine": engine_flag_value(session.engine),
+                "title": session.title.clone(),
+                "started_at": session.meta.started_at_rfc3339.clone(),
+                "modified_at": session.file_modified.and_then(system_time_to_rfc3339),
+                "log_path": session.log_path.display().to_string(),
+                "file_size_bytes": session.file_size_bytes,
+                "total_tokens": index.total_tokens(&session.log_path),
+                "item_count": index.item_count(&session.log_path),
+                "duration_ms": index.duration_ms(session),
+                "tool_calls": index.tool_calls(&session.log_path),
+                "assistant_turns": index.assistant_turns(&session.log_path),
+                "last_activity_unix_ms": index.last_activity_unix_ms(&session.log_path),
+                "git_branch": session.meta.git.as_ref().and_then(|git| git.branch.clone()),
+                "git_commit": session.meta.git.as_ref().and_then(|git| git.commit.clone()),
+            })
+        })
+        .collect::<Vec<_>>();
+    serde_json::json!({
+        "project": project.map(|project| serde_json::json!({
+            "name": project.name.clone(),
+            "path": project.project_path.display().to_string(),
+        })),
+        "sessions": rows,
+    })
+}
+
+fn session_column_header(column: SessionColumn) -> &'static str {
+    match column {
+        SessionColumn::Size => "file_size_bytes",
+        SessionColumn::Items => "item_count",
+        SessionColumn::Duration => "duration_ms",
+        SessionColumn::Tokens => "total_tokens",
+    }
+}
+
+/// Empty when the index has no value for the session.
+fn session_column_value(
+    column: SessionColumn,
+    session: &crate::domain::SessionSummary,
+    index: &crate::infra::SessionIndex,
+) -> String {
+    let value = match column {
+        SessionColumn::Size => Some(session.file_size_bytes),
+        SessionColumn::Items => index.item_count(&session.log_path),
+        SessionColumn::Duration => index.duration_ms(session),
+        SessionColumn::Tokens => index.total_tokens(&session.log_path),
+    };
+    value.map(|value| value.to_string()).unwrap_or_default()
+}
+
 fn build_skills_json_payload(
     log_path: &Path,
     items: &[TimelineItem],
     spans: &[crate::domain::SkillSpan],
     loops: &[crate::domain::SkillLoop],
     metrics: &[crate::domain::SkillMetrics],
+    tokens_by_model: &[crate::domain::ModelTokenUsage],
 ) -> serde_json::Value {
     let session_id = infer_session_id(log_path);
     let session_duration_ms = session_duration_ms(items);
@@ -924,10 +2242,25 @@ fn build_skills_json_payload(
         })
         .collect::<Vec<_>>();
 
+    let tokens_json = tokens_by_model
+        .iter()
+        .map(|usage| {
+            serde_json::json!({
+                "model": usage.model.clone(),
+                "input_tokens": usage.input_tokens,
+                "cached_input_tokens": usage.cached_input_tokens,
+                "output_tokens": usage.output_tokens,
+                "reasoning_tokens": usage.reasoning_tokens,
+                "total_tokens": usage.total_tokens,
+            })
+        })
+        .collect::<Vec<_>>();
+
     serde_json::json!({
         "session_id": session_id,
         "spans": spans_json,
         "loops": loops_json,
+        "tokens_by_model": tokens_json,
         "summary": {
             "total_spans": spans.len(),
             "total_skill_duration_ms": total_skill_duration_ms,
@@ -942,15 +2275,11 @@ fn infer_session_id(log_path: &Path) -> String {
         return id;
     }
 
-    log_path
-        .file_stem()
-        .map(|s| s.to_string_lossy().to_string())
-        .unwrap_or_else(|| log_path.display().to_string())
+    crate::infra::log_file_stem(log_path).unwrap_or_else(|| log_path.display().to_string())
 }
 
 fn extract_session_id_from_session_meta(path: &Path) -> Option<String> {
-    let file = fs::File::open(path).ok()?;
-    let reader = io::BufReader::new(file);
+    let reader = crate::infra::open_log_reader(path).ok()?;
     for line_result in reader.lines().take(200) {
         let line = line_result.ok()?;
         let trimmed = line.trim();
@@ -992,6 +2321,81 @@ fn total_top_level_skill_duration_ms(
     total
 }
 
+fn print_project_skills_human(
+    out: &mut impl Write,
+    project: &ProjectSummary,
+    report: &crate::domain::SkillUsageReport,
+) -> Result<(), CliRunError> {
+    write_line(
+        out,
+        &format!(
+            "Skills in {} ({}): {} sessions scanned, {} with skills",
+            project.name,
+            project.project_path.display(),
+            report.sessions_scanned,
+            report.sessions_with_skills
+        ),
+    )?;
+    if report.skills.is_empty() {
+        write_line(out, "No skill spans detected.")?;
+        return Ok(());
+    }
+    write_line(out, "")?;
+
+    write_line(
+        out,
+        "  Skill                     Calls  Sessions  Loops  Tools  Duration      Output",
+    )?;
+    for usage in &report.skills {
+        write_line(
+            out,
+            &format!(
+                "  {:<24} {:>6}  {:>8}  {:>5}  {:>5}  {:>8}  {:>10}",
+                truncate_end(&usage.name, 24),
+                usage.invocations,
+                usage.sessions,
+                usage.loops,
+                usage.tool_calls,
+                format_duration_ms(Some(usage.duration_ms)),
+                format!("{} chars", format_commas_usize(usage.output_chars)),
+            ),
+        )?;
+    }
+    Ok(())
+}
+
+fn build_project_skills_json_payload(
+    project: &ProjectSummary,
+    report: &crate::domain::SkillUsageReport,
+) -> serde_json::Value {
+    let skills_json = report
+        .skills
+        .iter()
+        .map(|usage| {
+            serde_json::json!({
+                "name": usage.name.clone(),
+                "invocations": usage.invocations,
+                "sessions": usage.sessions,
+                "loops": usage.loops,
+                "tool_calls": usage.tool_calls,
+                "duration_ms": usage.duration_ms,
+                "output_chars": usage.output_chars,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    serde_json::json!({
+        "project": {
+            "name": project.name.clone(),
+            "path": project.project_path.display().to_string(),
+        },
+        "sessions_scanned": report.sessions_scanned,
+        "sessions_with_skills": report.sessions_with_skills,
+        "total_invocations": report.total_invocations(),
+        "skills": skills_json,
+    })
+}
+
 fn format_duration_ms(ms: Option<i64>) -> String {
     let Some(ms) = ms else {
         return "-".to_string();
@@ -1096,135 +2500,724 @@ fn format_commas_usize(value: usize) -> String {
         if count > 0 && count % 3 == 0 {
             out.push(',');
         }
-        out.push(ch);
+        out.push(ch);
+    }
+    out.chars().rev().collect()
+}
+
+fn load_projects(
+    sessions_dir: &Path,
+) -> Result<(Vec<ProjectSummary>, usize, Option<String>), CliRunError> {
+    let output = scan_all_sessions(sessions_dir);
+    Ok((
+        index_projects(&output.sessions),
+        output.warnings.get(),
+        output.notice,
+    ))
+}
+
+fn project_path_or_current_dir(project_path: Option<PathBuf>) -> Result<PathBuf, CliRunError> {
+    match project_path {
+        Some(path) => Ok(path),
+        None => std::env::current_dir().map_err(|error| CliRunError::CurrentDir(error.to_string())),
+    }
+}
+
+fn select_project(
+    projects: Vec<ProjectSummary>,
+    requested: Option<PathBuf>,
+) -> Result<ProjectSummary, CliRunError> {
+    let base_dir =
+        std::env::current_dir().map_err(|error| CliRunError::CurrentDir(error.to_string()))?;
+
+    let requested = requested.map(|path| {
+        if path.is_absolute() {
+            path
+        } else {
+            base_dir.join(path)
+        }
+    });
+
+    let candidates: Vec<PathBuf> = match requested.as_ref() {
+        Some(path) => path.ancestors().map(|path| path.to_path_buf()).collect(),
+        None => base_dir
+            .ancestors()
+            .map(|path| path.to_path_buf())
+            .collect(),
+    };
+
+    let canonical_projects = projects
+        .iter()
+        .map(|project| project.project_path.canonicalize().ok())
+        .collect::<Vec<_>>();
+
+    for candidate in candidates {
+        let canonical_candidate = candidate.canonicalize().ok();
+        for (idx, project) in projects.iter().enumerate() {
+            if project.project_path == candidate {
+                return Ok(project.clone());
+            }
+            if let (Some(project_canon), Some(candidate_canon)) = (
+                canonical_projects.get(idx).and_then(|value| value.as_ref()),
+                canonical_candidate.as_ref(),
+            ) {
+                if project_canon == candidate_canon {
+                    return Ok(project.clone());
+                }
+            }
+        }
+    }
+
+    let attempted = requested.unwrap_or(base_dir);
+    Err(CliRunError::ProjectNotFound(
+        attempted.display().to_string(),
+    ))
+}
+
+/// Prints timeline items appended to `log_path` after the first `printed` ones until the output
+/// closes or the process is interrupted. The log is re-parsed on each change so turn grouping
+/// matches a fresh `history` run; a shrinking log (rewritten or rotated) restarts the count.
+fn follow_history(
+    out: &mut impl Write,
+    log_path: &Path,
+    mut printed: usize,
+    full: bool,
+) -> Result<(), CliRunError> {
+    let watcher = watch_session_file(log_path)?;
+    let mut last_size = fs::metadata(log_path).ok().map(|meta| meta.len());
+    loop {
+        // Some filesystems (network mounts, some containers) drop notify events; the size check
+        // on timeout covers them.
+        let signaled = matches!(
+            watcher.recv_timeout(FOLLOW_POLL_INTERVAL),
+            Some(WatchSignal::Changed)
+        );
+        let size = fs::metadata(log_path).ok().map(|meta| meta.len());
+        if !signaled && size == last_size {
+            continue;
+        }
+        last_size = size;
+
+        let timeline = match load_session_timeline(log_path) {
+            Ok(timeline) => timeline,
+            Err(LoadSessionTimelineError::OpenFile(error))
+                if error.kind() == io::ErrorKind::NotFound =>
+            {
+                continue;
+            }
+            Err(error) => return Err(error.into()),
+        };
+        if timeline.items.len() < printed {
+            printed = 0;
+        }
+        for (index, item) in timeline.items.iter().enumerate().skip(printed) {
+            if !print_timeline_item(out, index + 1, item, full)? {
+                return Ok(());
+            }
+        }
+        printed = timeline.items.len();
+        match out.flush() {
+            Ok(()) => {}
+            Err(error) if error.kind() == io::ErrorKind::BrokenPipe => return Ok(()),
+            Err(error) => return Err(error.into()),
+        }
+    }
+}
+
+/// `number` is the 1-based position of `item` in the whole timeline, printed as `#N` so it can be
+/// passed back to `history --item` or `tui --item`.
+fn print_timeline_item(
+    out: &mut impl Write,
+    number: usize,
+    item: &TimelineItem,
+    full: bool,
+) -> io::Result<bool> {
+    if item.kind == TimelineItemKind::Turn {
+        if !write_line(out, "")? {
+            return Ok(false);
+        }
+        if !write_line(out, &format!("#{number} == {} ==", item.summary))? {
+            return Ok(false);
+        }
+        return Ok(true);
+    }
+
+    let kind = kind_label(item.kind);
+    let timestamp = item.timestamp.as_deref().unwrap_or("");
+    let turn_id = item.turn_id.as_deref().unwrap_or("");
+
+    let line = match (timestamp.is_empty(), turn_id.is_empty()) {
+        (true, true) => format!("#{number} {kind}: {}", item.summary),
+        (false, true) => format!("#{number} [{timestamp}] {kind}: {}", item.summary),
+        (true, false) => format!("#{number} [{}] {kind}: {}", short_id(turn_id), item.summary),
+        (false, false) => format!(
+            "#{number} [{timestamp}] [{}] {kind}: {}",
+            short_id(turn_id),
+            item.summary
+        ),
+    };
+    if !write_line(out, &line)? {
+        return Ok(false);
+    }
+
+    if full {
+        let detail = item.detail.trim_end();
+        if !detail.is_empty() {
+            for line in detail.lines() {
+                if !write_line(out, &format!("  {line}"))? {
+                    return Ok(false);
+                }
+            }
+        }
+        if !write_line(out, "")? {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+/// Unified-diff style: `  ` same, `-` only in (or as in) a, `+` only in (or as in) b. Without
+/// `full` each step is cut to its first line.
+fn print_session_diff(out: &mut impl Write, diff: &SessionDiff, full: bool) -> io::Result<bool> {
+    for step in &diff.steps {
+        let kind = kind_label(step.kind);
+        let sides: &[(char, Option<&String>)] = match step.status {
+            SessionDiffStatus::Same => &[(' ', step.a.as_ref())],
+            _ => &[('-', step.a.as_ref()), ('+', step.b.as_ref())],
+        };
+        for (marker, text) in sides {
+            let Some(text) = text else {
+                continue;
+            };
+            if !write_diff_text(out, *marker, &format!("{kind}: "), text, full)? {
+                return Ok(false);
+            }
+        }
+    }
+
+    if !write_line(out, "")? || !write_line(out, "== final output ==")? {
+        return Ok(false);
+    }
+    let finals: &[(char, &Option<String>)] = if diff.is_same_final_output() {
+        &[(' ', &diff.final_a)]
+    } else {
+        &[('-', &diff.final_a), ('+', &diff.final_b)]
+    };
+    for (marker, text) in finals {
+        let text = text.as_deref().unwrap_or("(no assistant output)");
+        if !write_diff_text(out, *marker, "", text, full)? {
+            return Ok(false);
+        }
+    }
+
+    let summary = format!(
+        "\nsteps: {} same, {} changed, {} only in a, {} only in b",
+        diff.count(SessionDiffStatus::Same),
+        diff.count(SessionDiffStatus::Changed),
+        diff.count(SessionDiffStatus::OnlyA),
+        diff.count(SessionDiffStatus::OnlyB)
+    );
+    write_line(out, &summary)
+}
+
+fn write_diff_text(
+    out: &mut impl Write,
+    marker: char,
+    label: &str,
+    text: &str,
+    full: bool,
+) -> io::Result<bool> {
+    let mut lines = text.lines();
+    let first = lines.next().unwrap_or("");
+    if !full {
+        let more = lines.next().is_some();
+        let mut line = format!("{marker} {label}{}", truncate_end(first, DIFF_LINE_WIDTH));
+        if more || unicode_width::UnicodeWidthStr::width(first) > DIFF_LINE_WIDTH {
+            line.push_str(" …");
+        }
+        return write_line(out, &line);
+    }
+    if !write_line(out, &format!("{marker} {label}{first}"))? {
+        return Ok(false);
+    }
+    for line in lines {
+        if !write_line(out, &format!("{marker}   {line}"))? {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+fn kind_label(kind: TimelineItemKind) -> &'static str {
+    match kind {
+        TimelineItemKind::Turn => "TURN",
+        TimelineItemKind::User => "USER",
+        TimelineItemKind::Assistant => "ASSISTANT",
+        TimelineItemKind::Thinking => "THINKING",
+        TimelineItemKind::ToolCall => "TOOL",
+        TimelineItemKind::ToolOutput => "TOOL_OUT",
+        TimelineItemKind::TokenCount => "TOKENS",
+        TimelineItemKind::Todo => "TODOS",
+        TimelineItemKind::Compaction => "COMPACTED",
+        TimelineItemKind::Note => "NOTE",
+    }
+}
+
+fn short_id(value: &str) -> String {
+    let max = 8usize;
+    value.chars().take(max).collect()
+}
+
+const RUN_POLL_INTERVAL: Duration = Duration::from_millis(100);
+const RUN_SESSION_LOG_WAIT: Duration = Duration::from_secs(5);
+const AWAIT_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// One agent of `ccbox run`, with the state needed to tail its output.
+struct HeadlessRun {
+    project_path: PathBuf,
+    process: SpawnedAgentProcess,
+    stdout: TailedOutput,
+    stderr: TailedOutput,
+    process_log: PathBuf,
+    session_id: Option<String>,
+    session_log: Option<PathBuf>,
+    exit_code: Option<Option<i32>>,
+}
+
+/// A process output file copied to ours as it grows. With a prefix, only whole lines are
+/// copied, each starting with the prefix, so the output of several agents stays readable.
+struct TailedOutput {
+    path: PathBuf,
+    offset: u64,
+    prefix: Option<String>,
+    pending: Vec<u8>,
+}
+
+impl TailedOutput {
+    fn new(path: PathBuf, prefix: Option<String>) -> Self {
+        Self {
+            path,
+            offset: 0,
+            prefix,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Copies bytes appended since the last call. Returns whether anything was read; a file
+    /// that doesn't exist yet counts as empty.
+    fn copy_appended(&mut self, out: &mut impl Write) -> Result<bool, CliRunError> {
+        use std::io::{Read as _, Seek as _, SeekFrom};
+
+        let mut file = match fs::File::open(&self.path) {
+            Ok(file) => file,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(false),
+            Err(error) => return Err(error.into()),
+        };
+        file.seek(SeekFrom::Start(self.offset))?;
+        let mut bytes = Vec::new();
+        file.read_to_end(&mut bytes)?;
+        if bytes.is_empty() {
+            return Ok(false);
+        }
+        self.offset += bytes.len() as u64;
+        let Some(prefix) = &self.prefix else {
+            write_bytes(out, &bytes)?;
+            return Ok(true);
+        };
+        self.pending.extend_from_slice(&bytes);
+        let Some(last_newline) = self.pending.iter().rposition(|byte| *byte == b'\n') else {
+            return Ok(true);
+        };
+        let rest = self.pending.split_off(last_newline + 1);
+        let lines = std::mem::replace(&mut self.pending, rest);
+        write_bytes(out, &prefix_lines(prefix, &lines))?;
+        Ok(true)
+    }
+
+    /// Writes a trailing partial line, if any.
+    fn finish(&mut self, out: &mut impl Write) -> Result<(), CliRunError> {
+        if let Some(prefix) = &self.prefix
+            && !self.pending.is_empty()
+        {
+            let mut lines = std::mem::take(&mut self.pending);
+            lines.push(b'\n');
+            write_bytes(out, &prefix_lines(prefix, &lines))?;
+        }
+        Ok(())
+    }
+}
+
+fn prefix_lines(prefix: &str, lines: &[u8]) -> Vec<u8> {
+    let mut prefixed = Vec::with_capacity(lines.len());
+    for line in lines.split_inclusive(|byte| *byte == b'\n') {
+        prefixed.extend_from_slice(prefix.as_bytes());
+        prefixed.extend_from_slice(line);
+    }
+    prefixed
+}
+
+fn write_bytes(out: &mut impl Write, bytes: &[u8]) -> Result<(), CliRunError> {
+    match out.write_all(bytes).and_then(|()| out.flush()) {
+        Ok(()) => Ok(()),
+        Err(error) if error.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+        Err(error) => Err(error.into()),
+    }
+}
+
+/// `[name] ` prefix for a project's output when several agents run at once.
+/// Polls the exit code the owning ccbox (TUI, `run` or `serve`) records in the process's log dir.
+/// A process whose owner quit before it ended is never recorded, so only `timeout` ends the wait.
+fn await_process(
+    sessions_dir: &Path,
+    process_id: String,
+    timeout: Option<Duration>,
+) -> Result<(), CliRunError> {
+    let process_dir = crate::infra::process_logs_dir(sessions_dir).join(&process_id);
+    let is_plain_id = Path::new(&process_id).file_name() == Some(std::ffi::OsStr::new(&process_id));
+    if !is_plain_id || !process_dir.is_dir() {
+        return Err(CliRunError::ProcessNotFound(process_id));
+    }
+
+    let started = std::time::Instant::now();
+    loop {
+        match crate::infra::read_recorded_exit(&process_dir)? {
+            Some(crate::infra::RecordedExit::Code(0)) => return Ok(()),
+            Some(crate::infra::RecordedExit::Code(code)) => {
+                return Err(CliRunError::ProcessExited { process_id, code });
+            }
+            Some(crate::infra::RecordedExit::Signal) => {
+                return Err(CliRunError::ProcessKilled(process_id));
+            }
+            None => {}
+        }
+        if let Some(timeout) = timeout
+            && started.elapsed() >= timeout
+        {
+            return Err(CliRunError::AwaitTimedOut {
+                process_id,
+                seconds: timeout.as_secs(),
+            });
+        }
+        std::thread::sleep(AWAIT_POLL_INTERVAL);
     }
-    out.chars().rev().collect()
 }
 
-fn load_projects(
+/// Kills the running processes recorded for `project_path` and prints `killed:` (or `gone:`,
+/// when the pid had already exited) per process. Asks on the terminal unless `yes`.
+fn kill_project_processes(
     sessions_dir: &Path,
-) -> Result<(Vec<ProjectSummary>, usize, Option<String>), CliRunError> {
-    let output = scan_all_sessions(sessions_dir);
-    Ok((
-        index_projects(&output.sessions),
-        output.warnings.get(),
-        output.notice,
-    ))
-}
+    project_path: &Path,
+    yes: bool,
+    out: &mut impl Write,
+    err: &mut impl Write,
+) -> Result<(), CliRunError> {
+    let project_path = if project_path.is_absolute() {
+        project_path.to_path_buf()
+    } else {
+        std::env::current_dir()
+            .map_err(|error| CliRunError::CurrentDir(error.to_string()))?
+            .join(project_path)
+    };
+    let processes = processes_in_project(
+        crate::infra::running_recorded_processes(&crate::infra::process_logs_dir(sessions_dir))?,
+        &project_path,
+    );
+    if processes.is_empty() {
+        writeln!(err, "no running processes in {}", project_path.display())?;
+        return Ok(());
+    }
 
-fn select_project(
-    projects: Vec<ProjectSummary>,
-    requested: Option<PathBuf>,
-) -> Result<ProjectSummary, CliRunError> {
-    let base_dir =
-        std::env::current_dir().map_err(|error| CliRunError::CurrentDir(error.to_string()))?;
+    if !yes {
+        if !io::stdin().is_terminal() {
+            return Err(CliRunError::KillNotConfirmed(processes.len()));
+        }
+        for process in &processes {
+            writeln!(
+                err,
+                "{}\tpid {}\t{}",
+                process.id,
+                process.pid,
+                process.project_path.display()
+            )?;
+        }
+        write!(
+            err,
+            "Kill {} process(es) in {}? [y/N] ",
+            processes.len(),
+            project_path.display()
+        )?;
+        err.flush()?;
+        let mut answer = String::new();
+        io::stdin().lock().read_line(&mut answer)?;
+        if !matches!(answer.trim(), "y" | "Y" | "yes") {
+            writeln!(err, "aborted")?;
+            return Ok(());
+        }
+    }
 
-    let requested = requested.map(|path| {
-        if path.is_absolute() {
-            path
-        } else {
-            base_dir.join(path)
+    for process in processes {
+        if !crate::infra::terminate_pid(process.pid)? {
+            write_line(out, &format!("gone:\t{}", process.id))?;
+            continue;
         }
-    });
+        crate::infra::record_audit_entry(
+            &crate::infra::audit_entry_now(crate::domain::AuditAction::Kill, process.id.clone())
+                .with_detail(format!("ccbox kill --project {}", project_path.display())),
+        )?;
+        write_line(
+            out,
+            &format!("killed:\t{}\tpid {}", process.id, process.pid),
+        )?;
+    }
+    Ok(())
+}
 
-    let candidates: Vec<PathBuf> = match requested.as_ref() {
-        Some(path) => path.ancestors().map(|path| path.to_path_buf()).collect(),
-        None => base_dir
-            .ancestors()
-            .map(|path| path.to_path_buf())
-            .collect(),
-    };
+/// Recorded processes spawned in `project_path` or below it, matching symlinked paths too.
+fn processes_in_project(
+    processes: Vec<crate::infra::RecordedProcess>,
+    project_path: &Path,
+) -> Vec<crate::infra::RecordedProcess> {
+    let canonical = project_path.canonicalize().ok();
+    processes
+        .into_iter()
+        .filter(|process| {
+            process.project_path.starts_with(project_path)
+                || canonical.as_ref().is_some_and(|canonical| {
+                    process
+                        .project_path
+                        .canonicalize()
+                        .is_ok_and(|path| path.starts_with(canonical))
+                })
+        })
+        .collect()
+}
 
-    let canonical_projects = projects
-        .iter()
-        .map(|project| project.project_path.canonicalize().ok())
-        .collect::<Vec<_>>();
+fn run_output_prefix(project_path: &Path) -> String {
+    let name = project_path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| project_path.display().to_string());
+    format!("[{name}] ")
+}
 
-    for candidate in candidates {
-        let canonical_candidate = candidate.canonicalize().ok();
-        for (idx, project) in projects.iter().enumerate() {
-            if project.project_path == candidate {
-                return Ok(project.clone());
-            }
-            if let (Some(project_canon), Some(candidate_canon)) = (
-                canonical_projects.get(idx).and_then(|value| value.as_ref()),
-                canonical_candidate.as_ref(),
-            ) {
-                if project_canon == candidate_canon {
-                    return Ok(project.clone());
-                }
+/// Spawns `engine` with piped IO in every project, copies their stdout/stderr to ours as they
+/// grow, and once all of them exit prints the sessions they wrote. With several projects each
+/// output line is prefixed with the project name. Process logs are sealed only after the
+/// output is copied, since sealing replaces the files being tailed.
+fn run_agent_headless(
+    engine: AgentEngine,
+    project_paths: &[PathBuf],
+    prompt: &str,
+    sessions_dir: &Path,
+    out: &mut impl Write,
+    err: &mut impl Write,
+) -> Result<(), CliRunError> {
+    let config = crate::infra::load_ccbox_config(&crate::infra::resolve_ccbox_config_path()?)?;
+    let cipher = crate::infra::ArtifactCipher::from_config(&config.encryption)?;
+    let (tx, rx) = channel::<ProcessSignal>();
+    let mut manager = ProcessManager::new(sessions_dir.to_path_buf(), tx)?;
+    manager.set_process_limits(config.process_limits.clone());
+    manager.set_spawn_args(config.spawn_args.clone());
+    manager.set_project_env(config.project_env.clone());
+
+    let is_batch = project_paths.len() > 1;
+    let mut runs = Vec::new();
+    for project_path in project_paths {
+        let process =
+            manager.spawn_agent_process(engine, project_path, prompt, SpawnIoMode::Pipes)?;
+        let SpawnedAgentIo::Pipes {
+            stdout_path,
+            stderr_path,
+            log_path: process_log,
+        } = process.io.clone()
+        else {
+            return Err(CliRunError::AgentFailed("an unexpected TTY".to_string()));
+        };
+        let prefix = is_batch.then(|| run_output_prefix(project_path));
+        // Printed up front so a backgrounded run can be waited on with `ccbox await`.
+        let line = format!(
+            "{}process_id:\t{}",
+            prefix.as_deref().unwrap_or(""),
+            process.id
+        );
+        write_line(err, &line)?;
+        runs.push(HeadlessRun {
+            project_path: project_path.clone(),
+            process,
+            stdout: TailedOutput::new(stdout_path, prefix.clone()),
+            stderr: TailedOutput::new(stderr_path, prefix),
+            process_log,
+            session_id: None,
+            session_log: None,
+            exit_code: None,
+        });
+    }
+
+    while runs.iter().any(|run| run.exit_code.is_none()) {
+        for run in &mut runs {
+            run.stdout.copy_appended(out)?;
+            run.stderr.copy_appended(err)?;
+        }
+        while let Ok(signal) = rx.try_recv() {
+            record_run_signal(&mut runs, signal);
+        }
+        for exit in manager.poll_exits() {
+            if let Some(run) = runs
+                .iter_mut()
+                .find(|run| run.process.id == exit.process_id)
+            {
+                run.exit_code = Some(exit.exit_code);
             }
         }
+        std::thread::sleep(RUN_POLL_INTERVAL);
     }
-
-    let attempted = requested.unwrap_or(base_dir);
-    Err(CliRunError::ProjectNotFound(
-        attempted.display().to_string(),
-    ))
-}
-
-fn print_timeline_item(out: &mut impl Write, item: &TimelineItem, full: bool) -> io::Result<bool> {
-    if item.kind == TimelineItemKind::Turn {
-        if !write_line(out, "")? {
-            return Ok(false);
+    // The pipe readers may still be writing the last lines after the exit.
+    loop {
+        std::thread::sleep(RUN_POLL_INTERVAL);
+        let mut copied = false;
+        for run in &mut runs {
+            copied |= run.stdout.copy_appended(out)?;
+            copied |= run.stderr.copy_appended(err)?;
         }
-        if !write_line(out, &format!("== {} ==", item.summary))? {
-            return Ok(false);
+        if !copied {
+            break;
         }
-        return Ok(true);
+    }
+    for run in &mut runs {
+        run.stdout.finish(out)?;
+        run.stderr.finish(err)?;
     }
 
-    let kind = kind_label(item.kind);
-    let timestamp = item.timestamp.as_deref().unwrap_or("");
-    let turn_id = item.turn_id.as_deref().unwrap_or("");
-
-    let line = match (timestamp.is_empty(), turn_id.is_empty()) {
-        (true, true) => format!("{kind}: {}", item.summary),
-        (false, true) => format!("[{timestamp}] {kind}: {}", item.summary),
-        (true, false) => format!("[{}] {kind}: {}", short_id(turn_id), item.summary),
-        (false, false) => format!(
-            "[{timestamp}] [{}] {kind}: {}",
-            short_id(turn_id),
-            item.summary
-        ),
-    };
-    if !write_line(out, &line)? {
-        return Ok(false);
+    let wait_until = std::time::Instant::now() + RUN_SESSION_LOG_WAIT;
+    while runs
+        .iter()
+        .any(|run| run.session_id.is_some() && run.session_log.is_none())
+    {
+        let remaining = wait_until.saturating_duration_since(std::time::Instant::now());
+        match rx.recv_timeout(remaining) {
+            Ok(signal) => record_run_signal(&mut runs, signal),
+            Err(_) => break,
+        }
     }
 
-    if full {
-        let detail = item.detail.trim_end();
-        if !detail.is_empty() {
-            for line in detail.lines() {
-                if !write_line(out, &format!("  {line}"))? {
-                    return Ok(false);
+    let mut failures = Vec::new();
+    for run in &mut runs {
+        if run.session_log.is_none()
+            && let Some(session) = find_run_session(
+                sessions_dir,
+                engine,
+                &run.project_path,
+                &run.process,
+                run.session_id.as_deref(),
+            )
+        {
+            run.session_id = Some(session.meta.id);
+            run.session_log = Some(session.log_path);
+        }
+
+        let mut process_log = run.process_log.clone();
+        if let Some(cipher) = &cipher {
+            cipher.seal_file(&run.stdout.path)?;
+            cipher.seal_file(&run.stderr.path)?;
+            process_log = cipher.seal_file(&run.process_log)?;
+            let process_dir = run.process_log.parent().unwrap_or(Path::new(""));
+            for name in ["prompt.txt", "last_message.txt"] {
+                let path = process_dir.join(name);
+                if path.exists() {
+                    cipher.seal_file(&path)?;
                 }
             }
         }
-        if !write_line(out, "")? {
-            return Ok(false);
+
+        let exit_code = run.exit_code.flatten();
+        let exit_label = exit_code.map_or_else(|| "signal".to_string(), |code| code.to_string());
+        let log_path = run
+            .session_log
+            .as_ref()
+            .map(|path| path.display().to_string());
+        let mut lines = Vec::new();
+        if is_batch {
+            lines.push(format!("project:\t{}", run.project_path.display()));
+        }
+        lines.extend([
+            format!("session_id:\t{}", run.session_id.as_deref().unwrap_or("-")),
+            format!("log_path:\t{}", log_path.as_deref().unwrap_or("-")),
+            format!("process_log:\t{}", process_log.display()),
+            format!("exit_code:\t{exit_label}"),
+        ]);
+        for line in lines {
+            write_line(out, &line)?;
         }
-    }
 
-    Ok(true)
+        match exit_code {
+            Some(0) => {}
+            Some(code) => failures.push(format!("exit code {code}")),
+            None => failures.push("a signal".to_string()),
+        }
+    }
+    out.flush()?;
+
+    match failures.as_slice() {
+        [] => Ok(()),
+        [failure] if !is_batch => Err(CliRunError::AgentFailed(failure.clone())),
+        _ => Err(CliRunError::AgentFailed(format!(
+            "a failure in {} of {} projects",
+            failures.len(),
+            runs.len()
+        ))),
+    }
 }
 
-fn kind_label(kind: TimelineItemKind) -> &'static str {
-    match kind {
-        TimelineItemKind::Turn => "TURN",
-        TimelineItemKind::User => "USER",
-        TimelineItemKind::Assistant => "ASSISTANT",
-        TimelineItemKind::Thinking => "THINKING",
-        TimelineItemKind::ToolCall => "TOOL",
-        TimelineItemKind::ToolOutput => "TOOL_OUT",
-        TimelineItemKind::TokenCount => "TOKENS",
-        TimelineItemKind::Note => "NOTE",
+fn record_run_signal(runs: &mut [HeadlessRun], signal: ProcessSignal) {
+    let process_id = match &signal {
+        ProcessSignal::SessionMeta { process_id, .. }
+        | ProcessSignal::SessionLogPath { process_id, .. } => process_id.clone(),
+    };
+    let Some(run) = runs.iter_mut().find(|run| run.process.id == process_id) else {
+        return;
+    };
+    match signal {
+        ProcessSignal::SessionMeta { session_id, .. } => run.session_id = Some(session_id),
+        ProcessSignal::SessionLogPath { log_path, .. } => run.session_log = Some(log_path),
     }
 }
 
-fn short_id(value: &str) -> String {
-    let max = 8usize;
-    value.chars().take(max).collect()
+/// The session a headless run wrote: the one with the reported id, or else the newest session of
+/// the engine in `project_path` written since the process started.
+fn find_run_session(
+    sessions_dir: &Path,
+    engine: AgentEngine,
+    project_path: &Path,
+    process: &SpawnedAgentProcess,
+    session_id: Option<&str>,
+) -> Option<crate::domain::SessionSummary> {
+    let engine = match engine {
+        AgentEngine::Codex => SessionEngine::Codex,
+        AgentEngine::Claude => SessionEngine::Claude,
+        AgentEngine::OpenCode => SessionEngine::OpenCode,
+    };
+    scan_all_sessions(sessions_dir)
+        .sessions
+        .into_iter()
+        .filter(|session| session.engine == engine)
+        .filter(|session| match session_id {
+            Some(id) => session.meta.id == id,
+            None => {
+                session.meta.cwd == project_path
+                    && session
+                        .file_modified
+                        .is_some_and(|modified| modified >= process.started_at)
+            }
+        })
+        .max_by_key(|session| session.file_modified)
+}
+
+fn load_artifact_cipher() -> Result<Option<crate::infra::ArtifactCipher>, CliRunError> {
+    let config = crate::infra::load_ccbox_config(&crate::infra::resolve_ccbox_config_path()?)?;
+    Ok(crate::infra::ArtifactCipher::from_config(
+        &config.encryption,
+    )?)
 }
 
 fn write_line(out: &mut impl Write, line: &str) -> io::Result<bool> {
@@ -1244,6 +3237,17 @@ fn parse_usize_flag(flag: &str, value: &str) -> Result<usize, CliParseError> {
         })
 }
 
+/// Timeline item numbers are 1-based, as printed by `history`.
+fn parse_item_flag(value: &str) -> Result<usize, CliParseError> {
+    match value.parse::<usize>() {
+        Ok(item) if item > 0 => Ok(item),
+        _ => Err(CliParseError::InvalidFlagValue {
+            flag: "--item".to_string(),
+            value: value.to_string(),
+        }),
+    }
+}
+
 fn parse_engine_flag(flag: &str, value: &str) -> Result<Option<SessionEngine>, CliParseError> {
     let normalized = value.trim().to_ascii_lowercase();
     match normalized.as_str() {
@@ -1259,12 +3263,43 @@ fn parse_engine_flag(flag: &str, value: &str) -> Result<Option<SessionEngine>, C
     }
 }
 
+fn parse_list_format_flag(flag: &str, value: &str) -> Result<ListFormat, CliParseError> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "tsv" => Ok(ListFormat::Tsv),
+        "csv" => Ok(ListFormat::Csv),
+        "json" => Ok(ListFormat::Json),
+        other => Err(CliParseError::InvalidFlagValue {
+            flag: flag.to_string(),
+            value: other.to_string(),
+        }),
+    }
+}
+
+/// One TSV or CSV line. TSV fields are written as-is; CSV fields are quoted when they contain a
+/// comma, quote or line break, with embedded quotes doubled.
+fn format_list_row(format: ListFormat, fields: &[impl AsRef<str>]) -> String {
+    let fields = fields.iter().map(AsRef::as_ref);
+    match format {
+        ListFormat::Csv => fields.map(csv_field).collect::<Vec<_>>().join(","),
+        ListFormat::Tsv | ListFormat::Json => fields.collect::<Vec<_>>().join("\t"),
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
 fn engine_flag_value(engine: SessionEngine) -> &'static str {
     match engine {
         SessionEngine::Codex => "codex",
         SessionEngine::Claude => "claude",
         SessionEngine::Gemini => "gemini",
         SessionEngine::OpenCode => "opencode",
+        SessionEngine::Custom => "custom",
     }
 }
 
@@ -1276,7 +3311,7 @@ fn looks_like_path(value: &str) -> bool {
         || value.starts_with('~')
         || value.contains('/')
         || value.contains('\\')
-        || value.ends_with(".jsonl")
+        || crate::infra::is_jsonl_log_path(Path::new(value))
         || value.ends_with(".json")
         || Path::new(value).exists()
 }
@@ -1288,6 +3323,12 @@ fn resolve_history_log_path(
     session_id: Option<String>,
     engine: Option<SessionEngine>,
 ) -> Result<PathBuf, CliRunError> {
+    // `--id claude:<uuid>` pins the engine; Claude and Codex ids can collide.
+    let (session_id, engine) = match session_id.as_deref().and_then(SessionKey::parse) {
+        Some(key) => (Some(key.id), Some(key.engine)),
+        None => (session_id, engine),
+    };
+
     match (log_path, session_id) {
         (Some(path), None) => {
             if !fs::metadata(&path).is_ok_and(|meta| meta.is_dir()) {
@@ -1323,22 +3364,28 @@ fn resolve_history_log_path(
             let (projects, warnings, notice) = load_projects(sessions_dir)?;
             write_scan_notice(err, notice, warnings)?;
             let project = select_project(projects, Some(path))?;
-            let session = project
+            let matches = project
                 .sessions
                 .iter()
-                .find(|session| {
+                .filter(|session| {
                     session.meta.id == session_id
                         && engine.is_none_or(|engine| session.engine == engine)
                 })
-                .cloned()
-                .ok_or_else(|| {
-                    CliRunError::SessionNotFound(format!(
-                        "{} (project {})",
-                        session_id,
-                        project.project_path.display()
-                    ))
-                })?;
-            Ok(crate::infra::prepare_session_log_path(&session)?)
+                .collect::<Vec<_>>();
+            if matches
+                .iter()
+                .any(|session| session.engine != matches[0].engine)
+            {
+                return Err(CliRunError::SessionIdAmbiguous(session_id));
+            }
+            let session = matches.first().ok_or_else(|| {
+                CliRunError::SessionNotFound(format!(
+                    "{} (project {})",
+                    session_id,
+                    project.project_path.display()
+                ))
+            })?;
+            Ok(crate::infra::prepare_session_log_path(session)?)
         }
         (None, None) => {
             let (projects, warnings, notice) = load_projects(sessions_dir)?;
@@ -1414,7 +3461,55 @@ mod tests {
     #[test]
     fn parse_defaults_to_tui_when_no_args() {
         let parsed = parse_invocation(&args(&["ccbox"])).expect("parse");
-        assert_eq!(parsed, CliInvocation::Tui { engine: None });
+        assert_eq!(
+            parsed,
+            CliInvocation::Tui {
+                engine: None,
+                project: None,
+                profile_startup: false,
+                session: None,
+                item: None
+            }
+        );
+    }
+
+    #[test]
+    fn parse_project_flag_only_applies_to_tui() {
+        let parsed = parse_invocation(&args(&["ccbox", "--project", "/work/app"])).expect("parse");
+        assert_eq!(
+            parsed,
+            CliInvocation::Tui {
+                engine: None,
+                project: Some(PathBuf::from("/work/app")),
+                profile_startup: false,
+                session: None,
+                item: None
+            }
+        );
+        assert!(matches!(
+            parse_invocation(&args(&["ccbox", "--project", "/work/app", "sessions"])),
+            Err(CliParseError::UnexpectedArgument(_))
+        ));
+    }
+
+    #[test]
+    fn parse_profile_startup_flag_only_applies_to_tui() {
+        let parsed = parse_invocation(&args(&["ccbox", "--profile-startup", "-p", "/work/app"]))
+            .expect("parse");
+        assert_eq!(
+            parsed,
+            CliInvocation::Tui {
+                engine: None,
+                project: Some(PathBuf::from("/work/app")),
+                profile_startup: true,
+                session: None,
+                item: None
+            }
+        );
+        assert!(matches!(
+            parse_invocation(&args(&["ccbox", "--profile-startup", "projects"])),
+            Err(CliParseError::UnexpectedArgument(flag)) if flag == "--profile-startup"
+        ));
     }
 
     #[test]
@@ -1423,13 +3518,30 @@ mod tests {
         assert_eq!(parsed, CliInvocation::PrintHelp);
     }
 
+    #[test]
+    fn parse_serve_port_flag() {
+        let parsed = parse_invocation(&args(&["ccbox", "serve", "--port", "8765"])).expect("parse");
+        let CliInvocation::Serve(opts) = parsed else {
+            panic!("expected serve, got {parsed:?}");
+        };
+        assert_eq!(opts.api_port, Some(8765));
+        assert!(matches!(
+            parse_invocation(&args(&["ccbox", "serve", "--port", "http"])),
+            Err(CliParseError::InvalidFlagValue { .. })
+        ));
+    }
+
     #[test]
     fn parse_engine_flag_before_subcommand_applies_to_tui() {
         let parsed = parse_invocation(&args(&["ccbox", "--engine", "claude"])).expect("parse");
         assert_eq!(
             parsed,
             CliInvocation::Tui {
-                engine: Some(SessionEngine::Claude)
+                engine: Some(SessionEngine::Claude),
+                project: None,
+                profile_startup: false,
+                session: None,
+                item: None
             }
         );
     }
@@ -1441,51 +3553,186 @@ mod tests {
         assert_eq!(
             parsed,
             CliInvocation::Command(CliCommand::Projects {
-                engine: Some(SessionEngine::Claude)
+                engine: Some(SessionEngine::Claude),
+                format: ListFormat::Tsv
+            })
+        );
+    }
+
+    #[test]
+    fn parse_projects_command() {
+        let parsed = parse_invocation(&args(&["ccbox", "projects"])).expect("parse");
+        assert_eq!(
+            parsed,
+            CliInvocation::Command(CliCommand::Projects {
+                engine: None,
+                format: ListFormat::Tsv
+            })
+        );
+    }
+
+    #[test]
+    fn parse_sessions_command_defaults_to_current_dir_project() {
+        let parsed = parse_invocation(&args(&["ccbox", "sessions"])).expect("parse");
+        assert_eq!(
+            parsed,
+            CliInvocation::Command(CliCommand::Sessions {
+                project_path: None,
+                all: false,
+                engine: None,
+                offset: 0,
+                limit: DEFAULT_LIMIT,
+                columns: Vec::new(),
+                format: ListFormat::Tsv
+            })
+        );
+    }
+
+    #[test]
+    fn parse_history_command_supports_full_flag() {
+        let parsed = parse_invocation(&args(&["ccbox", "history", "--full", "/tmp/session.jsonl"]))
+            .expect("parse");
+        assert_eq!(
+            parsed,
+            CliInvocation::Command(CliCommand::History {
+                log_path: Some(PathBuf::from("/tmp/session.jsonl")),
+                session_id: None,
+                engine: None,
+                offset: 0,
+                limit: DEFAULT_LIMIT,
+                full: true,
+                size: false,
+                follow: false,
+                item: None,
             })
         );
     }
 
     #[test]
-    fn parse_projects_command() {
-        let parsed = parse_invocation(&args(&["ccbox", "projects"])).expect("parse");
+    fn parse_history_follow_flag() {
+        let parsed =
+            parse_invocation(&args(&["ccbox", "history", "-f", "--limit", "5"])).expect("parse");
         assert_eq!(
             parsed,
-            CliInvocation::Command(CliCommand::Projects { engine: None })
+            CliInvocation::Command(CliCommand::History {
+                log_path: None,
+                session_id: None,
+                engine: None,
+                offset: 0,
+                limit: 5,
+                full: false,
+                size: false,
+                follow: true,
+                item: None,
+            })
         );
     }
 
     #[test]
-    fn parse_sessions_command_defaults_to_current_dir_project() {
-        let parsed = parse_invocation(&args(&["ccbox", "sessions"])).expect("parse");
+    fn history_numbers_items_and_tui_links_to_one() {
+        let parsed = parse_invocation(&args(&[
+            "ccbox",
+            "-p",
+            "/work/app",
+            "tui",
+            "--session",
+            "claude:abc",
+            "--item",
+            "42",
+        ]))
+        .expect("parse");
         assert_eq!(
             parsed,
-            CliInvocation::Command(CliCommand::Sessions {
-                project_path: None,
+            CliInvocation::Tui {
                 engine: None,
-                offset: 0,
-                limit: DEFAULT_LIMIT,
-                size: false
-            })
+                project: Some(PathBuf::from("/work/app")),
+                profile_startup: false,
+                session: Some("claude:abc".to_string()),
+                item: Some(42)
+            }
+        );
+        assert!(matches!(
+            parse_invocation(&args(&["ccbox", "tui", "--item", "3"])),
+            Err(CliParseError::MissingFlagValue(flag)) if flag == "--session"
+        ));
+        assert!(matches!(
+            parse_invocation(&args(&["ccbox", "history", "--item", "0"])),
+            Err(CliParseError::InvalidFlagValue { .. })
+        ));
+        assert!(matches!(
+            parse_invocation(&args(&["ccbox", "history", "--item", "2", "-f"])),
+            Err(CliParseError::UnexpectedArgument(flag)) if flag == "--follow"
+        ));
+
+        let item = TimelineItem {
+            kind: TimelineItemKind::ToolCall,
+            turn_id: None,
+            call_id: None,
+            source_line_no: None,
+            timestamp: Some("2026-01-02T03:04:05Z".to_string()),
+            timestamp_ms: None,
+            summary: "exec_command()".to_string(),
+            detail: "ls".to_string(),
+        };
+        let mut out = Vec::new();
+        assert!(print_timeline_item(&mut out, 42, &item, true).expect("print"));
+        assert_eq!(
+            String::from_utf8(out).expect("utf8"),
+            "#42 [2026-01-02T03:04:05Z] TOOL: exec_command()\n  ls\n\n"
         );
     }
 
     #[test]
-    fn parse_history_command_supports_full_flag() {
-        let parsed = parse_invocation(&args(&["ccbox", "history", "--full", "/tmp/session.jsonl"]))
-            .expect("parse");
+    fn parse_diff_takes_two_sessions_and_prints_unified_steps() {
+        let parsed = parse_invocation(&args(&[
+            "ccbox",
+            "diff",
+            "/tmp/a.jsonl",
+            "claude:abc",
+            "--full",
+        ]))
+        .expect("parse");
         assert_eq!(
             parsed,
-            CliInvocation::Command(CliCommand::History {
-                log_path: Some(PathBuf::from("/tmp/session.jsonl")),
-                session_id: None,
+            CliInvocation::Command(CliCommand::Diff {
+                session_a: "/tmp/a.jsonl".to_string(),
+                session_b: "claude:abc".to_string(),
                 engine: None,
-                offset: 0,
-                limit: DEFAULT_LIMIT,
-                full: true,
-                size: false
+                full: true
             })
         );
+        assert!(matches!(
+            parse_invocation(&args(&["ccbox", "diff", "abc"])),
+            Err(CliParseError::MissingArgument(_))
+        ));
+
+        let item = |kind, text: &str| TimelineItem {
+            kind,
+            turn_id: None,
+            call_id: None,
+            source_line_no: None,
+            timestamp: None,
+            timestamp_ms: None,
+            summary: text.to_string(),
+            detail: String::new(),
+        };
+        let a = [
+            item(TimelineItemKind::User, "fix it"),
+            item(TimelineItemKind::Assistant, "done"),
+        ];
+        let b = [
+            item(TimelineItemKind::User, "fix it"),
+            item(TimelineItemKind::ToolCall, "exec_command()"),
+            item(TimelineItemKind::Assistant, "done"),
+        ];
+        let mut out = Vec::new();
+        assert!(
+            print_session_diff(&mut out, &diff_session_timelines(&a, &b), false).expect("print")
+        );
+        assert_eq!(
+            String::from_utf8(out).expect("utf8"),
+            "  USER: fix it\n+ TOOL: exec_command()\n\n== final output ==\n  done\n\nsteps: 1 same, 0 changed, 0 only in a, 1 only in b\n"
+        );
     }
 
     #[test]
@@ -1500,7 +3747,9 @@ mod tests {
                 offset: 0,
                 limit: DEFAULT_LIMIT,
                 full: false,
-                size: false
+                size: false,
+                follow: false,
+                item: None,
             })
         );
     }
@@ -1515,14 +3764,43 @@ mod tests {
             parsed,
             CliInvocation::Command(CliCommand::Sessions {
                 project_path: None,
+                all: false,
                 engine: None,
                 offset: 5,
                 limit: 25,
-                size: true
+                columns: vec![SessionColumn::Size],
+                format: ListFormat::Tsv
             })
         );
     }
 
+    #[test]
+    fn parse_sessions_columns_flag_keeps_order_and_merges_size() {
+        let parsed = parse_invocation(&args(&[
+            "ccbox",
+            "sessions",
+            "--columns",
+            "duration,items",
+            "--size",
+        ]))
+        .expect("parse");
+        let CliInvocation::Command(CliCommand::Sessions { columns, .. }) = parsed else {
+            panic!("expected sessions, got {parsed:?}");
+        };
+        assert_eq!(
+            columns,
+            vec![
+                SessionColumn::Duration,
+                SessionColumn::Items,
+                SessionColumn::Size
+            ]
+        );
+        assert!(matches!(
+            parse_invocation(&args(&["ccbox", "sessions", "--columns", "cost"])),
+            Err(CliParseError::InvalidFlagValue { .. })
+        ));
+    }
+
     #[test]
     fn parse_history_supports_limit_offset_and_size_flags() {
         let parsed = parse_invocation(&args(&[
@@ -1538,7 +3816,9 @@ mod tests {
                 offset: 5,
                 limit: 25,
                 full: false,
-                size: true
+                size: true,
+                follow: false,
+                item: None,
             })
         );
     }
@@ -1555,7 +3835,9 @@ mod tests {
                 offset: 0,
                 limit: DEFAULT_LIMIT,
                 full: false,
-                size: false
+                size: false,
+                follow: false,
+                item: None,
             })
         );
     }
@@ -1573,7 +3855,9 @@ mod tests {
                 offset: 0,
                 limit: DEFAULT_LIMIT,
                 full: false,
-                size: false
+                size: false,
+                follow: false,
+                item: None,
             })
         );
     }
@@ -1585,7 +3869,8 @@ mod tests {
         assert_eq!(
             parsed,
             CliInvocation::Command(CliCommand::Projects {
-                engine: Some(SessionEngine::Claude)
+                engine: Some(SessionEngine::Claude),
+                format: ListFormat::Tsv
             })
         );
     }
@@ -1598,10 +3883,12 @@ mod tests {
             parsed,
             CliInvocation::Command(CliCommand::Sessions {
                 project_path: None,
+                all: false,
                 engine: Some(SessionEngine::Gemini),
                 offset: 0,
                 limit: DEFAULT_LIMIT,
-                size: false
+                columns: Vec::new(),
+                format: ListFormat::Tsv
             })
         );
     }
@@ -1619,7 +3906,9 @@ mod tests {
                 offset: 0,
                 limit: DEFAULT_LIMIT,
                 full: false,
-                size: false
+                size: false,
+                follow: false,
+                item: None,
             })
         );
     }
@@ -1634,9 +3923,166 @@ mod tests {
                 session_id: None,
                 engine: None,
                 json: false,
-                full: false
+                full: false,
+                project: false
+            })
+        );
+    }
+
+    #[test]
+    fn parse_sessions_all_rejects_a_project_path() {
+        assert_eq!(
+            parse_invocation(&args(&["ccbox", "sessions", "--all", "--json"])).expect("parse"),
+            CliInvocation::Command(CliCommand::Sessions {
+                project_path: None,
+                all: true,
+                engine: None,
+                offset: 0,
+                limit: DEFAULT_LIMIT,
+                columns: Vec::new(),
+                format: ListFormat::Json
+            })
+        );
+        assert!(matches!(
+            parse_invocation(&args(&["ccbox", "sessions", "--all", "/tmp/p"])),
+            Err(CliParseError::UnexpectedArgument(arg)) if arg == "/tmp/p"
+        ));
+    }
+
+    #[test]
+    fn parse_projects_and_sessions_json_flags() {
+        assert_eq!(
+            parse_invocation(&args(&["ccbox", "projects", "--json"])).expect("parse"),
+            CliInvocation::Command(CliCommand::Projects {
+                engine: None,
+                format: ListFormat::Json
+            })
+        );
+        assert_eq!(
+            parse_invocation(&args(&["ccbox", "sessions", "/tmp/p", "--json", "--size"]))
+                .expect("parse"),
+            CliInvocation::Command(CliCommand::Sessions {
+                project_path: Some(PathBuf::from("/tmp/p")),
+                all: false,
+                engine: None,
+                offset: 0,
+                limit: DEFAULT_LIMIT,
+                columns: vec![SessionColumn::Size],
+                format: ListFormat::Json
+            })
+        );
+    }
+
+    #[test]
+    fn parse_format_flag_and_quote_csv_fields() {
+        assert_eq!(
+            parse_invocation(&args(&["ccbox", "projects", "--format", "CSV"])).expect("parse"),
+            CliInvocation::Command(CliCommand::Projects {
+                engine: None,
+                format: ListFormat::Csv
+            })
+        );
+        assert!(parse_invocation(&args(&["ccbox", "sessions", "--format", "xlsx"])).is_err());
+
+        assert_eq!(
+            format_list_row(
+                ListFormat::Csv,
+                &[
+                    "2026-02-20",
+                    "s1",
+                    "Fix \"login\", then deploy",
+                    "/work/app"
+                ]
+            ),
+            r#"2026-02-20,s1,"Fix ""login"", then deploy",/work/app"#
+        );
+        assert_eq!(
+            format_list_row(ListFormat::Csv, &["line\nbreak"]),
+            "\"line\nbreak\""
+        );
+        assert_eq!(format_list_row(ListFormat::Tsv, &["a,b", "c"]), "a,b\tc");
+    }
+
+    #[test]
+    fn projects_and_sessions_json_payloads_carry_engine_keys() {
+        let session = |engine, id: &str| {
+            crate::domain::make_session_summary(
+                crate::domain::SessionMeta {
+                    id: id.to_string(),
+                    cwd: PathBuf::from("/work/app"),
+                    started_at_rfc3339: "2026-02-20T00:00:00Z".to_string(),
+                    git: None,
+                },
+                PathBuf::from(format!("/logs/{id}.jsonl")),
+                format!("title {id}"),
+                42,
+                Some(UNIX_EPOCH),
+                engine,
+            )
+        };
+        let project = ProjectSummary {
+            name: "app".to_string(),
+            project_path: PathBuf::from("/work/app"),
+            sessions: vec![
+                session(SessionEngine::Claude, "s1"),
+                session(SessionEngine::Codex, "s2"),
+            ],
+            last_modified: None,
+            hidden: false,
+        };
+
+        let projects =
+            build_projects_json_payload(std::slice::from_ref(&project), Some(SessionEngine::Codex));
+        assert_eq!(
+            projects,
+            serde_json::json!({ "projects": [{
+                "name": "app",
+                "path": "/work/app",
+                "session_count": 1,
+                "engines": ["codex"],
+                "last_modified": null,
+            }]})
+        );
+
+        let sessions = project
+            .sessions
+            .iter()
+            .take(1)
+            .map(|session| (&project, session))
+            .collect::<Vec<_>>();
+        let payload = build_sessions_json_payload(
+            Some(&project),
+            &sessions,
+            &crate::infra::SessionIndex::default(),
+        );
+        assert_eq!(
+            payload["sessions"][0],
+            serde_json::json!({
+                "key": "claude:s1",
+                "project_path": "/work/app",
+                "session_id": "s1",
+                "engine": "claude",
+                "title": "title s1",
+                "started_at": "2026-02-20T00:00:00Z",
+                "modified_at": "1970-01-01T00:00:00Z",
+                "log_path": "/logs/s1.jsonl",
+                "file_size_bytes": 42,
+                "total_tokens": null,
+                "item_count": null,
+                "duration_ms": null,
+                "tool_calls": null,
+                "assistant_turns": null,
+                "last_activity_unix_ms": null,
+                "git_branch": null,
+                "git_commit": null,
             })
         );
+        assert_eq!(payload["project"]["path"], "/work/app");
+
+        let all =
+            build_sessions_json_payload(None, &sessions, &crate::infra::SessionIndex::default());
+        assert_eq!(all["project"], serde_json::Value::Null);
+        assert_eq!(all["sessions"][0]["project_path"], "/work/app");
     }
 
     #[test]
@@ -1658,7 +4104,8 @@ mod tests {
                 session_id: None,
                 engine: Some(SessionEngine::Claude),
                 json: true,
-                full: true
+                full: true,
+                project: false
             })
         );
     }
@@ -1673,7 +4120,8 @@ mod tests {
                 session_id: Some("019c754c".to_string()),
                 engine: None,
                 json: false,
-                full: false
+                full: false,
+                project: false
             })
         );
     }
@@ -1689,9 +4137,31 @@ mod tests {
                 session_id: Some("019c754c".to_string()),
                 engine: None,
                 json: false,
-                full: false
+                full: false,
+                project: false
+            })
+        );
+    }
+
+    #[test]
+    fn parse_skills_project_takes_a_project_path_but_no_session_id() {
+        let parsed = parse_invocation(&args(&["ccbox", "skills", "--project", "/tmp/project"]))
+            .expect("parse");
+        assert_eq!(
+            parsed,
+            CliInvocation::Command(CliCommand::Skills {
+                log_path: Some(PathBuf::from("/tmp/project")),
+                session_id: None,
+                engine: None,
+                json: false,
+                full: false,
+                project: true
             })
         );
+        assert!(matches!(
+            parse_invocation(&args(&["ccbox", "skills", "--project", "019c754c"])),
+            Err(CliParseError::UnexpectedArgument(_))
+        ));
     }
 
     #[test]
@@ -1705,8 +4175,314 @@ mod tests {
                 session_id: Some("019c754c".to_string()),
                 engine: None,
                 json: false,
-                full: false
+                full: false,
+                project: false
+            })
+        );
+    }
+
+    #[test]
+    fn parse_open_accepts_path_id_and_engine() {
+        let parsed = parse_invocation(&args(&[
+            "ccbox",
+            "open",
+            "/tmp/project",
+            "019c754c",
+            "--engine",
+            "claude",
+        ]))
+        .expect("parse");
+        assert_eq!(
+            parsed,
+            CliInvocation::Command(CliCommand::Open {
+                log_path: Some(PathBuf::from("/tmp/project")),
+                session_id: Some("019c754c".to_string()),
+                engine: Some(SessionEngine::Claude),
+            })
+        );
+        assert!(parse_invocation(&args(&["ccbox", "open", "--json"])).is_err());
+    }
+
+    #[test]
+    fn parse_result_takes_a_session_id_and_project_flag() {
+        let parsed = parse_invocation(&args(&[
+            "ccbox",
+            "result",
+            "codex:019c754c",
+            "--project",
+            "/tmp/project",
+        ]))
+        .expect("parse");
+        assert_eq!(
+            parsed,
+            CliInvocation::Command(CliCommand::Result {
+                log_path: Some(PathBuf::from("/tmp/project")),
+                session_id: Some("codex:019c754c".to_string()),
+                engine: None,
+            })
+        );
+        assert!(matches!(
+            parse_invocation(&args(&[
+                "ccbox",
+                "result",
+                "./a.jsonl",
+                "-p",
+                "/tmp/project"
+            ])),
+            Err(CliParseError::UnexpectedArgument(_))
+        ));
+    }
+
+    #[test]
+    fn parse_worktrees_actions() {
+        let parse = |argv: &[&str]| parse_invocation(&args(argv));
+        assert_eq!(
+            parse(&["ccbox", "worktrees", "/work/app"]).expect("parse"),
+            CliInvocation::Command(CliCommand::WorktreesList {
+                project_path: Some(PathBuf::from("/work/app")),
+            })
+        );
+        assert_eq!(
+            parse(&["ccbox", "worktrees", "merge", "fix-ci"]).expect("parse"),
+            CliInvocation::Command(CliCommand::WorktreesMerge {
+                project_path: None,
+                name: "fix-ci".to_string(),
+            })
+        );
+        assert_eq!(
+            parse(&[
+                "ccbox",
+                "worktrees",
+                "remove",
+                "fix-ci",
+                "--force",
+                "--project",
+                "/work/app"
+            ])
+            .expect("parse"),
+            CliInvocation::Command(CliCommand::WorktreesRemove {
+                project_path: Some(PathBuf::from("/work/app")),
+                name: "fix-ci".to_string(),
+                force: true,
+            })
+        );
+        assert!(parse(&["ccbox", "worktrees", "merge"]).is_err());
+        assert!(parse(&["ccbox", "worktrees", "--force"]).is_err());
+    }
+
+    #[test]
+    fn parse_tasks_export_and_import() {
+        let parsed = parse_invocation(&args(&["ccbox", "tasks", "export", "--out", "pack.json"]))
+            .expect("parse");
+        assert_eq!(
+            parsed,
+            CliInvocation::Command(CliCommand::TasksExport {
+                out: Some(PathBuf::from("pack.json"))
+            })
+        );
+
+        let parsed = parse_invocation(&args(&[
+            "ccbox",
+            "tasks",
+            "import",
+            "pack.json",
+            "--on-conflict",
+            "overwrite",
+            "--project",
+            "/work/app",
+        ]))
+        .expect("parse");
+        assert_eq!(
+            parsed,
+            CliInvocation::Command(CliCommand::TasksImport {
+                pack_path: PathBuf::from("pack.json"),
+                on_conflict: TaskImportConflict::Overwrite,
+                project_path: Some(PathBuf::from("/work/app")),
+            })
+        );
+
+        assert!(parse_invocation(&args(&["ccbox", "tasks", "import"])).is_err());
+        assert!(
+            parse_invocation(&args(&[
+                "ccbox",
+                "tasks",
+                "import",
+                "pack.json",
+                "--on-conflict",
+                "merge"
+            ]))
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn parse_audit_log() {
+        assert_eq!(
+            parse_invocation(&args(&["ccbox", "audit-log", "--limit", "50", "--json"]))
+                .expect("parse"),
+            CliInvocation::Command(CliCommand::AuditLog {
+                offset: 0,
+                limit: 50,
+                json: true,
+            })
+        );
+        assert!(parse_invocation(&args(&["ccbox", "audit-log", "extra"])).is_err());
+    }
+
+    #[test]
+    fn parse_update_channel_and_rollback() {
+        assert_eq!(
+            parse_invocation(&args(&["ccbox", "update", "--channel", "beta"])).expect("parse"),
+            CliInvocation::Command(CliCommand::Update {
+                channel: UpdateChannel::Beta,
+                rollback: false,
+            })
+        );
+        assert_eq!(
+            parse_invocation(&args(&["ccbox", "update", "--rollback"])).expect("parse"),
+            CliInvocation::Command(CliCommand::Update {
+                channel: UpdateChannel::Stable,
+                rollback: true,
+            })
+        );
+        assert!(parse_invocation(&args(&["ccbox", "update", "--channel", "nightly"])).is_err());
+    }
+
+    #[test]
+    fn parse_run_command() {
+        assert_eq!(
+            parse_invocation(&args(&[
+                "ccbox",
+                "run",
+                "--engine",
+                "claude",
+                "--project",
+                "/work/app",
+                "fix the build",
+            ]))
+            .expect("parse"),
+            CliInvocation::Command(CliCommand::Run {
+                engine: AgentEngine::Claude,
+                project_paths: vec![PathBuf::from("/work/app")],
+                prompt: RunPrompt::Text("fix the build".to_string()),
+            })
+        );
+        assert_eq!(
+            parse_invocation(&args(&["ccbox", "run", "--prompt-file", "task.md"])).expect("parse"),
+            CliInvocation::Command(CliCommand::Run {
+                engine: AgentEngine::Codex,
+                project_paths: Vec::new(),
+                prompt: RunPrompt::File(PathBuf::from("task.md")),
+            })
+        );
+        assert_eq!(
+            parse_invocation(&args(&["ccbox", "run", "-"])).expect("parse"),
+            CliInvocation::Command(CliCommand::Run {
+                engine: AgentEngine::Codex,
+                project_paths: Vec::new(),
+                prompt: RunPrompt::File(PathBuf::from("-")),
+            })
+        );
+        assert!(
+            parse_invocation(&args(&["ccbox", "run", "--prompt-file", "a.md", "text"])).is_err()
+        );
+    }
+
+    #[test]
+    fn parse_await_takes_a_process_id_and_timeout() {
+        assert_eq!(
+            parse_invocation(&args(&["ccbox", "await", "p7", "--timeout", "30"])).expect("parse"),
+            CliInvocation::Command(CliCommand::Await {
+                process_id: "p7".to_string(),
+                timeout: Some(Duration::from_secs(30)),
+            })
+        );
+        assert!(matches!(
+            parse_invocation(&args(&["ccbox", "await"])),
+            Err(CliParseError::MissingArgument(_))
+        ));
+
+        let temp = tempfile::tempdir().expect("tempdir");
+        let process_dir = crate::infra::process_logs_dir(temp.path()).join("p7");
+        fs::create_dir_all(&process_dir).expect("mkdir");
+        fs::write(process_dir.join("exit_code"), "3\n").expect("write");
+        let error = await_process(temp.path(), "p7".to_string(), None).expect_err("exit 3");
+        assert_eq!(error.exit_code(), 3);
+        assert!(matches!(
+            await_process(temp.path(), "../p7".to_string(), None),
+            Err(CliRunError::ProcessNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn kill_takes_a_project_and_matches_processes_below_it() {
+        assert_eq!(
+            parse_invocation(&args(&["ccbox", "kill", "--project", "/tmp/p1", "--yes"]))
+                .expect("parse"),
+            CliInvocation::Command(CliCommand::Kill {
+                project_path: Some(PathBuf::from("/tmp/p1")),
+                yes: true,
+            })
+        );
+        assert!(parse_invocation(&args(&["ccbox", "kill", "p1"])).is_err());
+
+        let process = |id: &str, project: &str| crate::infra::RecordedProcess {
+            id: id.to_string(),
+            pid: 1,
+            project_path: PathBuf::from(project),
+        };
+        let matched = processes_in_project(
+            vec![
+                process("p1", "/tmp/ccbox-kill/app"),
+                process("p2", "/tmp/ccbox-kill/app-2"),
+                process("p3", "/tmp/ccbox-kill/app/.worktrees/fix"),
+            ],
+            Path::new("/tmp/ccbox-kill/app"),
+        );
+        assert_eq!(
+            matched
+                .iter()
+                .map(|process| process.id.as_str())
+                .collect::<Vec<_>>(),
+            ["p1", "p3"]
+        );
+    }
+
+    #[test]
+    fn parse_run_across_several_projects() {
+        assert_eq!(
+            parse_invocation(&args(&[
+                "ccbox",
+                "run",
+                "--projects",
+                "/a, /b,/a",
+                "--project",
+                "/c",
+                "go"
+            ]))
+            .expect("parse"),
+            CliInvocation::Command(CliCommand::Run {
+                engine: AgentEngine::Codex,
+                project_paths: vec![
+                    PathBuf::from("/a"),
+                    PathBuf::from("/b"),
+                    PathBuf::from("/c"),
+                ],
+                prompt: RunPrompt::Text("go".to_string()),
             })
         );
+        assert!(parse_invocation(&args(&["ccbox", "run", "--projects", "/a,,/b", "go"])).is_err());
+    }
+
+    #[test]
+    fn tailed_output_prefixes_whole_lines() {
+        assert_eq!(
+            prefix_lines("[app] ", b"one\ntwo\n"),
+            b"[app] one\n[app] two\n".to_vec()
+        );
+        assert_eq!(run_output_prefix(Path::new("/work/app")), "[app] ");
+        assert!(parse_invocation(&args(&["ccbox", "run"])).is_err());
+        assert!(parse_invocation(&args(&["ccbox", "run", "  "])).is_err());
+        assert!(parse_invocation(&args(&["ccbox", "run", "a", "b"])).is_err());
     }
 }
@@ -0,0 +1,330 @@
+use crate::domain::{TimelineItem, TimelineItemKind, parse_plan_tool_call};
+use serde_json::Value;
+
+/// Tools that block the agent until a human answers.
+const QUESTION_TOOLS: &[&str] = &["AskUserQuestion", "request_user_input"];
+
+/// Tool that presents a plan and waits for the human to approve it.
+const PLAN_APPROVAL_TOOL: &str = "ExitPlanMode";
+
+/// Text that interactive agents leave on screen while a dialog waits for a keypress, matched
+/// case-insensitively. Tool permission requests (Claude's "Do you want to make this edit",
+/// Codex's "Allow command?") come first so they are told apart from other prompts.
+const TTY_PROMPT_PATTERNS: &[(&str, InputWaitReason)] = &[
+    ("do you want to make this edit", InputWaitReason::Approval),
+    ("do you want to create", InputWaitReason::Approval),
+    ("do you want to proceed", InputWaitReason::Approval),
+    (
+        "would you like to run the following command",
+        InputWaitReason::Approval,
+    ),
+    (
+        "would you like to make the following edits",
+        InputWaitReason::Approval,
+    ),
+    ("allow command", InputWaitReason::Approval),
+    ("allow this", InputWaitReason::Approval),
+    ("waiting for your approval", InputWaitReason::Approval),
+    ("do you want to", InputWaitReason::Prompt),
+    ("would you like to", InputWaitReason::Prompt),
+    ("(y/n)", InputWaitReason::Prompt),
+    ("[y/n]", InputWaitReason::Prompt),
+    ("yes, and don't ask again", InputWaitReason::Approval),
+    ("press enter to", InputWaitReason::Prompt),
+];
+
+/// How many of the last non-empty transcript lines can hold the prompt.
+const TTY_PROMPT_LINES: usize = 8;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum InputWaitReason {
+    Question,
+    PlanApproval,
+    /// A tool permission request: a Codex command asking to run outside the sandbox, or a
+    /// permission dialog on the TTY.
+    Approval,
+    /// Any other prompt on the TTY.
+    Prompt,
+}
+
+impl InputWaitReason {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Question => "question",
+            Self::PlanApproval => "plan approval",
+            Self::Approval => "approval",
+            Self::Prompt => "prompt",
+        }
+    }
+}
+
+/// A live session is blocked on a human: the agent asked a question, presented a plan or
+/// requested a permission and no answer has been logged yet, or its TTY shows a prompt.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InputWait {
+    pub reason: InputWaitReason,
+    pub call_id: Option<String>,
+    pub detail: String,
+    /// Read from the TTY transcript rather than the session log.
+    pub is_from_tty: bool,
+}
+
+impl InputWait {
+    pub fn label(&self) -> String {
+        if self.detail.is_empty() {
+            return format!("needs input: {}", self.reason.label());
+        }
+        format!("needs input: {} · {}", self.reason.label(), self.detail)
+    }
+}
+
+/// Folds timeline items appended to a session log into the current wait state.
+///
+/// A question or plan-approval tool call starts a wait. The wait ends when that call gets its
+/// output, the user sends a message, or the agent moves on (speaks or calls another tool).
+pub fn next_input_wait(current: Option<InputWait>, items: &[TimelineItem]) -> Option<InputWait> {
+    items.iter().fold(current, |wait, item| match item.kind {
+        TimelineItemKind::ToolCall => input_wait_from_tool_call(item),
+        TimelineItemKind::ToolOutput => wait.filter(|wait| {
+            wait.call_id.is_none() || item.call_id.is_none() || wait.call_id != item.call_id
+        }),
+        TimelineItemKind::User | TimelineItemKind::Assistant => None,
+        _ => wait,
+    })
+}
+
+/// The prompt an idle interactive agent is showing at the end of its TTY transcript. Escape
+/// sequences are skipped and a carriage return starts the line over, as on screen.
+pub fn tty_input_wait(transcript_tail: &str) -> Option<InputWait> {
+    let text = strip_escapes(transcript_tail);
+    let lines = text
+        .lines()
+        .map(|line| line.rsplit('\r').next().unwrap_or(line))
+        .map(|line| line.trim_matches(|c: char| c.is_whitespace() || "│┃|╭╮╰╯─".contains(c)))
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>();
+    // The question sits above its options, so the earliest match in the window is the prompt.
+    let (prompt, reason) = lines[lines.len().saturating_sub(TTY_PROMPT_LINES)..]
+        .iter()
+        .find_map(|line| {
+            let lower = line.to_lowercase();
+            TTY_PROMPT_PATTERNS
+                .iter()
+                .find(|(pattern, _)| lower.contains(pattern))
+                .map(|(_, reason)| (line, *reason))
+        })?;
+    Some(InputWait {
+        reason,
+        call_id: None,
+        detail: prompt.chars().take(80).collect(),
+        is_from_tty: true,
+    })
+}
+
+/// Drops CSI (`ESC [ … final`), OSC (`ESC ] … BEL/ST`) and two-byte escapes.
+fn strip_escapes(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut chars = input.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\u{1b}' {
+            out.push(ch);
+            continue;
+        }
+        match chars.next() {
+            Some('[') => {
+                for next in chars.by_ref() {
+                    if ('\u{40}'..='\u{7e}').contains(&next) {
+                        break;
+                    }
+                }
+            }
+            Some(']') => {
+                while let Some(next) = chars.next() {
+                    if next == '\u{7}' {
+                        break;
+                    }
+                    if next == '\u{1b}' {
+                        chars.next();
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    out
+}
+
+fn input_wait_from_tool_call(item: &TimelineItem) -> Option<InputWait> {
+    let (tool_name, _) = item.summary.split_once("()")?;
+    let (reason, detail) = if QUESTION_TOOLS.contains(&tool_name) {
+        (InputWaitReason::Question, first_question(&item.detail))
+    } else if tool_name == PLAN_APPROVAL_TOOL {
+        let detail = parse_plan_tool_call(tool_name, &item.detail)
+            .map(|plan| format!("{} steps", plan.steps.len()))
+            .unwrap_or_default();
+        (InputWaitReason::PlanApproval, detail)
+    } else if let Some(detail) = escalation_request(&item.detail) {
+        (InputWaitReason::Approval, detail)
+    } else {
+        return None;
+    };
+    Some(InputWait {
+        reason,
+        call_id: item.call_id.clone(),
+        detail,
+        is_from_tty: false,
+    })
+}
+
+/// A Codex command that asks to run outside the sandbox waits for the user's approval. Returns
+/// its justification, or the command when none is given.
+fn escalation_request(arguments: &str) -> Option<String> {
+    let value = serde_json::from_str::<Value>(arguments).ok()?;
+    let is_escalated = value.get("with_escalated_permissions") == Some(&Value::Bool(true))
+        || value.get("sandbox_permissions").and_then(Value::as_str) == Some("require_escalated");
+    if !is_escalated {
+        return None;
+    }
+    let command = match value.get("command") {
+        Some(Value::Array(parts)) => parts
+            .iter()
+            .filter_map(Value::as_str)
+            .collect::<Vec<_>>()
+            .join(" "),
+        Some(Value::String(command)) => command.clone(),
+        _ => String::new(),
+    };
+    Some(
+        value
+            .get("justification")
+            .and_then(Value::as_str)
+            .map(|text| text.trim().to_string())
+            .filter(|text| !text.is_empty())
+            .unwrap_or(command),
+    )
+}
+
+fn first_question(arguments: &str) -> String {
+    let Ok(value) = serde_json::from_str::<Value>(arguments) else {
+        return String::new();
+    };
+    value
+        .get("questions")
+        .and_then(|questions| questions.get(0))
+        .and_then(|question| question.get("question"))
+        .or_else(|| value.get("question"))
+        .and_then(|v| v.as_str())
+        .map(|text| text.trim().to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(
+        kind: TimelineItemKind,
+        call_id: Option<&str>,
+        summary: &str,
+        detail: Value,
+    ) -> TimelineItem {
+        TimelineItem {
+            kind,
+            turn_id: None,
+            call_id: call_id.map(str::to_string),
+            source_line_no: None,
+            timestamp: None,
+            timestamp_ms: None,
+            summary: summary.to_string(),
+            detail: detail.to_string(),
+        }
+    }
+
+    #[test]
+    fn question_waits_until_answered() {
+        let ask = item(
+            TimelineItemKind::ToolCall,
+            Some("c1"),
+            "AskUserQuestion()",
+            serde_json::json!({ "questions": [{ "question": "Which DB?", "options": [] }] }),
+        );
+        let other_output = item(TimelineItemKind::ToolOutput, Some("c0"), "", Value::Null);
+        let wait = next_input_wait(None, &[ask, other_output]).expect("wait");
+        assert_eq!(wait.reason, InputWaitReason::Question);
+        assert_eq!(wait.label(), "needs input: question · Which DB?");
+
+        let answer = item(TimelineItemKind::ToolOutput, Some("c1"), "", Value::Null);
+        assert_eq!(next_input_wait(Some(wait), &[answer]), None);
+    }
+
+    #[test]
+    fn plan_approval_clears_when_agent_moves_on() {
+        let exit_plan = item(
+            TimelineItemKind::ToolCall,
+            Some("p1"),
+            "ExitPlanMode()",
+            serde_json::json!({ "plan": "1. Parse\n2. Render" }),
+        );
+        let wait = next_input_wait(None, &[exit_plan]).expect("wait");
+        assert_eq!(wait.label(), "needs input: plan approval · 2 steps");
+
+        let thinking = item(TimelineItemKind::Thinking, None, "", Value::Null);
+        let wait = next_input_wait(Some(wait), &[thinking]).expect("still waiting");
+
+        let user = item(TimelineItemKind::User, None, "yes", Value::Null);
+        assert_eq!(next_input_wait(Some(wait), &[user]), None);
+
+        let bash = item(
+            TimelineItemKind::ToolCall,
+            Some("b1"),
+            "Bash()",
+            serde_json::json!({ "command": "ls" }),
+        );
+        assert_eq!(next_input_wait(None, &[bash]), None);
+
+        let escalated = item(
+            TimelineItemKind::ToolCall,
+            Some("s1"),
+            "shell()",
+            serde_json::json!({
+                "command": ["cargo", "publish"],
+                "with_escalated_permissions": true,
+                "justification": "Publish needs network access",
+            }),
+        );
+        let wait = next_input_wait(None, &[escalated]).expect("wait");
+        assert_eq!(
+            wait.label(),
+            "needs input: approval · Publish needs network access"
+        );
+        let output = item(TimelineItemKind::ToolOutput, Some("s1"), "", Value::Null);
+        assert_eq!(next_input_wait(Some(wait), &[output]), None);
+    }
+
+    #[test]
+    fn tty_prompt_is_found_only_near_the_end() {
+        let dialog = "\u{1b}[2K\u{1b}[1m│ Do you want to make this edit to main.rs?\u{1b}[0m │\r\n\
+                      │ ❯ 1. Yes                                 │\r\n\
+                      │   2. Yes, and don't ask again            │\r\n\
+                      │   3. No, and tell Claude what to do       │\r\n\
+                      ╰──────────────────────────────────────────╯\r\n";
+        let wait = tty_input_wait(dialog).expect("wait");
+        assert_eq!(wait.reason, InputWaitReason::Approval);
+        assert!(wait.is_from_tty);
+        assert_eq!(wait.detail, "Do you want to make this edit to main.rs?");
+        assert_eq!(
+            wait.label(),
+            "needs input: approval · Do you want to make this edit to main.rs?"
+        );
+        let wait = tty_input_wait("Overwrite config? (y/N) ").expect("wait");
+        assert_eq!(wait.reason, InputWaitReason::Prompt);
+
+        let moved_on = format!(
+            "{dialog}{}",
+            "Editing main.rs…\r\n".repeat(TTY_PROMPT_LINES)
+        );
+        assert_eq!(tty_input_wait(&moved_on), None);
+        assert_eq!(tty_input_wait("Continue (y/n)? \r50%\r"), None);
+    }
+}
@@ -0,0 +1,114 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    Delete,
+    Rename,
+    Fork,
+    Kill,
+    Spawn,
+}
+
+impl AuditAction {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Delete => "delete",
+            Self::Rename => "rename",
+            Self::Fork => "fork",
+            Self::Kill => "kill",
+            Self::Spawn => "spawn",
+        }
+    }
+}
+
+/// Where an audited action was triggered from.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditSource {
+    Tui,
+    Relay,
+}
+
+impl AuditSource {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Tui => "tui",
+            Self::Relay => "relay",
+        }
+    }
+}
+
+/// One line of the audit log: who did what to which target, and when.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub at_rfc3339: String,
+    pub actor: String,
+    pub source: AuditSource,
+    /// Trusted device id of the relay client, when the action came through the relay.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device_id: Option<String>,
+    pub action: AuditAction,
+    pub target: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub detail: String,
+}
+
+impl AuditEntry {
+    pub fn with_detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = detail.into();
+        self
+    }
+
+    pub fn with_device(mut self, device_id: Option<&str>) -> Self {
+        self.source = AuditSource::Relay;
+        self.device_id = device_id.map(str::to_string);
+        self
+    }
+
+    /// Tab-separated form used by `ccbox audit-log`.
+    pub fn to_tsv(&self) -> String {
+        [
+            self.at_rfc3339.as_str(),
+            self.source.label(),
+            self.actor.as_str(),
+            self.device_id.as_deref().unwrap_or("-"),
+            self.action.label(),
+            self.target.as_str(),
+            self.detail.as_str(),
+        ]
+        .iter()
+        .map(|field| field.replace(['\t', '\n'], " "))
+        .collect::<Vec<_>>()
+        .join("\t")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serializes_relay_entries_and_flattens_tsv_fields() {
+        let entry = AuditEntry {
+            at_rfc3339: "2026-03-01T10:00:00Z".to_string(),
+            actor: "dev".to_string(),
+            source: AuditSource::Tui,
+            device_id: None,
+            action: AuditAction::Spawn,
+            target: "/work/app".to_string(),
+            detail: String::new(),
+        }
+        .with_device(Some("d1"))
+        .with_detail("Codex: fix\tthe build");
+
+        let json = serde_json::to_value(&entry).expect("json");
+        assert_eq!(json["source"], "relay");
+        assert_eq!(json["action"], "spawn");
+        assert_eq!(json["device_id"], "d1");
+        assert_eq!(
+            entry.to_tsv(),
+            "2026-03-01T10:00:00Z\trelay\tdev\td1\tspawn\t/work/app\tCodex: fix the build"
+        );
+    }
+}
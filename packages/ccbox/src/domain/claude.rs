@@ -40,13 +40,15 @@ pub fn parse_claude_sessions_index(text: &str) -> Result<ClaudeSessionsIndex, se
     serde_json::from_str(text)
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct ClaudeSessionMetaHint {
     pub cwd: Option<PathBuf>,
     pub session_id: Option<String>,
     pub timestamp: Option<String>,
     pub summary: Option<String>,
     pub first_prompt: Option<String>,
+    /// Subagent (sidechain) records carry the parent session's id in `sessionId`.
+    pub is_sidechain: bool,
 }
 
 pub fn extract_claude_session_meta_hint(value: &Value) -> ClaudeSessionMetaHint {
@@ -79,12 +81,18 @@ pub fn extract_claude_session_meta_hint(value: &Value) -> ClaudeSessionMetaHint
         .and_then(|v| v.as_str())
         .map(|s| s.to_string());
 
+    let is_sidechain = value
+        .get("isSidechain")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+
     ClaudeSessionMetaHint {
         cwd,
         session_id,
         timestamp,
         summary,
         first_prompt,
+        is_sidechain,
     }
 }
 
@@ -440,6 +448,24 @@ mod tests {
         assert_eq!(out_items[0].detail, "ok");
     }
 
+    #[test]
+    fn sidechain_records_point_at_the_parent_session() {
+        let json = serde_json::json!({
+            "type": "user",
+            "isSidechain": true,
+            "sessionId": "parent-1",
+            "agentId": "a1b2",
+            "cwd": "/tmp/project",
+            "message": { "content": "Find the failing test" }
+        });
+        let hint = extract_claude_session_meta_hint(&json);
+        assert!(hint.is_sidechain);
+        assert_eq!(hint.session_id.as_deref(), Some("parent-1"));
+
+        let main = serde_json::json!({ "type": "user", "isSidechain": false, "sessionId": "s1" });
+        assert!(!extract_claude_session_meta_hint(&main).is_sidechain);
+    }
+
     #[test]
     fn extracts_user_text_from_string_content() {
         let json = serde_json::json!({
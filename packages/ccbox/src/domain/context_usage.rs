@@ -0,0 +1,162 @@
+use crate::domain::{TimelineItem, TurnContextSummary, token_events};
+use std::collections::BTreeMap;
+
+/// A call whose context is at least this share of the window counts as near the limit.
+const NEAR_LIMIT_RATIO: f64 = 0.8;
+/// A drop below this share of the previous call's context, after being past
+/// `COMPACTION_FROM_RATIO` of the window, is read as a compaction.
+const COMPACTION_DROP_RATIO: f64 = 0.5;
+const COMPACTION_FROM_RATIO: f64 = 0.6;
+
+/// How full the model's context window got over a session, estimated from the token counts
+/// logged after each model call.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContextUsage {
+    /// Context size (prompt, cached prompt and output) of the latest call.
+    pub last_tokens: u64,
+    pub peak_tokens: u64,
+    /// From the log when it says; otherwise a known limit for the model, if any.
+    pub context_window: Option<u64>,
+    pub model: Option<String>,
+    /// Sharp drops in context size after it was close to the window.
+    pub likely_compactions: usize,
+}
+
+impl ContextUsage {
+    pub fn last_ratio(&self) -> Option<f64> {
+        self.ratio(self.last_tokens)
+    }
+
+    pub fn peak_ratio(&self) -> Option<f64> {
+        self.ratio(self.peak_tokens)
+    }
+
+    pub fn is_near_limit(&self) -> bool {
+        self.peak_ratio()
+            .is_some_and(|ratio| ratio >= NEAR_LIMIT_RATIO)
+    }
+
+    fn ratio(&self, tokens: u64) -> Option<f64> {
+        let window = self.context_window.filter(|window| *window > 0)?;
+        Some(tokens as f64 / window as f64)
+    }
+}
+
+/// Context use per call, or `None` when the timeline has no token counts (Claude logs).
+pub fn compute_context_usage(
+    items: &[TimelineItem],
+    turn_contexts: &BTreeMap<String, TurnContextSummary>,
+) -> Option<ContextUsage> {
+    let events = token_events(items, turn_contexts);
+    let last = events.last()?;
+    let model = Some(last.usage.model.clone()).filter(|model| model != "unknown");
+    let context_window = events
+        .iter()
+        .rev()
+        .find_map(|event| event.context_window)
+        .or_else(|| model.as_deref().and_then(known_context_window));
+
+    let mut usage = ContextUsage {
+        last_tokens: 0,
+        peak_tokens: 0,
+        context_window,
+        model,
+        likely_compactions: 0,
+    };
+    let mut previous = 0u64;
+    for event in &events {
+        let tokens = if event.usage.has_categories() {
+            event
+                .usage
+                .input_tokens
+                .saturating_add(event.usage.cached_input_tokens)
+                .saturating_add(event.usage.output_tokens)
+        } else {
+            event.usage.total_tokens
+        };
+        if tokens == 0 {
+            continue;
+        }
+        let was_near_limit = usage
+            .ratio(previous)
+            .is_some_and(|ratio| ratio >= COMPACTION_FROM_RATIO);
+        if was_near_limit && (tokens as f64) < previous as f64 * COMPACTION_DROP_RATIO {
+            usage.likely_compactions += 1;
+        }
+        usage.peak_tokens = usage.peak_tokens.max(tokens);
+        usage.last_tokens = tokens;
+        previous = tokens;
+    }
+    Some(usage)
+}
+
+/// Context windows of common models, for logs that don't record one.
+pub fn known_context_window(model: &str) -> Option<u64> {
+    let model = model.to_ascii_lowercase();
+    let window = if model.contains("gemini") {
+        1_048_576
+    } else if model.contains("gpt-4.1") {
+        1_047_576
+    } else if model.contains("gpt-5") || model.contains("codex") {
+        272_000
+    } else if model.contains("claude") || model.starts_with("o3") || model.starts_with("o4") {
+        200_000
+    } else if model.contains("gpt-4o") {
+        128_000
+    } else {
+        return None;
+    };
+    Some(window)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::TimelineItemKind;
+
+    fn token_count(input: u64, cached: u64, output: u64, window: Option<u64>) -> TimelineItem {
+        let mut info = serde_json::json!({
+            "total_token_usage": { "total_tokens": input + output },
+            "last_token_usage": {
+                "input_tokens": input,
+                "cached_input_tokens": cached,
+                "output_tokens": output,
+                "total_tokens": input + output,
+            },
+        });
+        if let Some(window) = window {
+            info["model_context_window"] = serde_json::json!(window);
+        }
+        TimelineItem {
+            kind: TimelineItemKind::TokenCount,
+            turn_id: None,
+            call_id: None,
+            source_line_no: None,
+            timestamp: None,
+            timestamp_ms: None,
+            summary: "tokens".to_string(),
+            detail: info.to_string(),
+        }
+    }
+
+    #[test]
+    fn tracks_peak_use_and_flags_drops_after_a_full_window() {
+        let items = vec![
+            token_count(40_000, 30_000, 2_000, None),
+            token_count(180_000, 170_000, 4_000, Some(200_000)),
+            token_count(30_000, 0, 1_000, Some(200_000)),
+        ];
+        let usage = compute_context_usage(&items, &BTreeMap::new()).expect("usage");
+
+        assert_eq!(usage.context_window, Some(200_000));
+        assert_eq!(usage.peak_tokens, 184_000);
+        assert_eq!(usage.last_tokens, 31_000);
+        assert_eq!(usage.likely_compactions, 1);
+        assert!(usage.is_near_limit());
+        assert_eq!(usage.last_ratio(), Some(0.155));
+
+        assert_eq!(compute_context_usage(&[], &BTreeMap::new()), None);
+        assert_eq!(known_context_window("gpt-5-codex"), Some(272_000));
+        assert_eq!(known_context_window("mystery-model"), None);
+    }
+}
@@ -0,0 +1,399 @@
+use crate::domain::{
+    TimelineItem, TimelineItemKind, derive_title_from_user_text, parse_rfc3339_to_unix_ms,
+};
+use serde::Deserialize;
+use serde_json::Value;
+use std::path::PathBuf;
+
+/// How a custom engine's session files are laid out.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+pub enum CustomLogFlavor {
+    /// One OpenAI chat message per line: `role`, `content`, `tool_calls`, `tool_call_id`.
+    #[serde(rename = "jsonl-openai")]
+    JsonlOpenAi,
+    /// One Anthropic message per line (`role` + content blocks), optionally under `message`.
+    #[serde(rename = "jsonl-anthropic")]
+    JsonlAnthropic,
+    /// A single JSON document with a `messages` array (or a bare array of messages).
+    #[serde(rename = "single-json")]
+    SingleJson,
+}
+
+/// A user-declared agent whose sessions ccbox scans next to the built-in engines
+/// (`custom_engines` in the config).
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct CustomEngineConfig {
+    pub name: String,
+    /// Session files, e.g. `~/.aider/sessions/**/*.jsonl`; `*`, `?` and `**` are supported.
+    pub sessions_glob: String,
+    pub flavor: CustomLogFlavor,
+}
+
+impl CustomEngineConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("custom_engines: name must not be empty".to_string());
+        }
+        if self.sessions_glob.trim().is_empty() {
+            return Err(format!(
+                "custom_engines: `{}` needs a sessions_glob",
+                self.name
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// What a custom session file says about itself; missing fields fall back to the file.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CustomSessionMeta {
+    pub id: Option<String>,
+    pub cwd: Option<PathBuf>,
+    pub started_at_rfc3339: Option<String>,
+    pub title: Option<String>,
+}
+
+/// The directory to walk for `pattern`: its components up to the first wildcard.
+pub fn glob_base_dir(pattern: &str) -> PathBuf {
+    let mut base = PathBuf::new();
+    for component in pattern.split('/') {
+        if component.contains(['*', '?']) {
+            break;
+        }
+        if component.is_empty() && base.as_os_str().is_empty() {
+            base.push("/");
+            continue;
+        }
+        base.push(component);
+    }
+    base
+}
+
+/// Matches a `/`-separated path against a glob: `*` and `?` stay within one component, `**`
+/// spans any number of components.
+pub fn glob_matches(pattern: &str, path: &str) -> bool {
+    let pattern = pattern.split('/').collect::<Vec<_>>();
+    let path = path.split('/').collect::<Vec<_>>();
+    match_components(&pattern, &path)
+}
+
+fn match_components(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => (0..=path.len()).any(|skip| match_components(rest, &path[skip..])),
+        Some((first, rest)) => match path.split_first() {
+            Some((component, path_rest)) => {
+                match_component(first.as_bytes(), component.as_bytes())
+                    && match_components(rest, path_rest)
+            }
+            None => false,
+        },
+    }
+}
+
+fn match_component(pattern: &[u8], text: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => text.is_empty(),
+        Some((b'*', rest)) => (0..=text.len()).any(|skip| match_component(rest, &text[skip..])),
+        Some((b'?', rest)) => !text.is_empty() && match_component(rest, &text[1..]),
+        Some((byte, rest)) => text.first() == Some(byte) && match_component(rest, &text[1..]),
+    }
+}
+
+/// The messages of a session: the lines of a JSONL flavor, or the `messages` of a single-json
+/// document.
+pub fn custom_log_messages(flavor: CustomLogFlavor, text: &str) -> Vec<Value> {
+    match flavor {
+        CustomLogFlavor::JsonlOpenAi | CustomLogFlavor::JsonlAnthropic => text
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+            .collect(),
+        CustomLogFlavor::SingleJson => {
+            let Ok(document) = serde_json::from_str::<Value>(text) else {
+                return Vec::new();
+            };
+            match document {
+                Value::Array(messages) => messages,
+                document => document
+                    .get("messages")
+                    .and_then(|messages| messages.as_array())
+                    .cloned()
+                    .unwrap_or_default(),
+            }
+        }
+    }
+}
+
+/// Session id, cwd, start time and title from the first fields that carry them: `session_id` /
+/// `sessionId`, `cwd`, `timestamp` / `created_at`, and the first user message. A single-json
+/// document's own top-level fields come first.
+pub fn extract_custom_session_meta(flavor: CustomLogFlavor, text: &str) -> CustomSessionMeta {
+    let mut sources = Vec::new();
+    if flavor == CustomLogFlavor::SingleJson
+        && let Ok(document) = serde_json::from_str::<Value>(text)
+        && document.is_object()
+    {
+        sources.push(document);
+    }
+    let messages = custom_log_messages(flavor, text);
+    sources.extend(messages.iter().cloned());
+
+    let mut meta = CustomSessionMeta::default();
+    for value in &sources {
+        let string = |keys: &[&str]| {
+            keys.iter()
+                .find_map(|key| value.get(*key).and_then(|v| v.as_str()))
+                .map(str::to_string)
+        };
+        if meta.id.is_none() {
+            meta.id = string(&["session_id", "sessionId"]);
+        }
+        if meta.cwd.is_none() {
+            meta.cwd = string(&["cwd"]).map(PathBuf::from);
+        }
+        if meta.started_at_rfc3339.is_none() {
+            meta.started_at_rfc3339 = string(&["timestamp", "created_at", "createdAt"]);
+        }
+    }
+    meta.title = messages.iter().find_map(|value| {
+        let message = chat_message(value);
+        if message.get("role").and_then(|v| v.as_str()) != Some("user") {
+            return None;
+        }
+        let text = content_text(message.get("content").unwrap_or(&Value::Null));
+        derive_title_from_user_text(&text)
+    });
+    meta
+}
+
+/// Timeline items of one chat message in either OpenAI or Anthropic shape: text by role,
+/// thinking blocks, `tool_use` blocks / `tool_calls`, and `tool_result` blocks / `tool` messages.
+pub fn parse_chat_message_items(value: &Value, source_line_no: Option<u64>) -> Vec<TimelineItem> {
+    let message = chat_message(value);
+    let role = message.get("role").and_then(|v| v.as_str()).unwrap_or("");
+    let timestamp = value
+        .get("timestamp")
+        .or_else(|| message.get("timestamp"))
+        .and_then(|v| v.as_str())
+        .map(str::to_string);
+    let timestamp_ms = timestamp.as_deref().and_then(parse_rfc3339_to_unix_ms);
+    let item =
+        |kind: TimelineItemKind, call_id: Option<String>, summary: String, detail| TimelineItem {
+            kind,
+            turn_id: None,
+            call_id,
+            source_line_no,
+            timestamp: timestamp.clone(),
+            timestamp_ms,
+            summary,
+            detail,
+        };
+    let text_kind = match role {
+        "user" => TimelineItemKind::User,
+        "assistant" | "model" => TimelineItemKind::Assistant,
+        _ => TimelineItemKind::Note,
+    };
+
+    let mut items = Vec::new();
+    let content = message.get("content").unwrap_or(&Value::Null);
+    if role == "tool" {
+        let text = content_text(content);
+        let call_id = message
+            .get("tool_call_id")
+            .and_then(|v| v.as_str())
+            .map(str::to_string);
+        items.push(item(
+            TimelineItemKind::ToolOutput,
+            call_id,
+            first_line(&text).unwrap_or_else(|| "tool output".to_string()),
+            text,
+        ));
+    } else if let Some(blocks) = content.as_array() {
+        for block in blocks {
+            let block_type = block.get("type").and_then(|v| v.as_str()).unwrap_or("");
+            match block_type {
+                "text" | "input_text" | "output_text" => {
+                    let text = block.get("text").and_then(|v| v.as_str()).unwrap_or("");
+                    if let Some(summary) = first_line(text) {
+                        items.push(item(text_kind, None, summary, text.trim_end().to_string()));
+                    }
+                }
+                "thinking" | "reasoning" => {
+                    let text = block
+                        .get("thinking")
+                        .or_else(|| block.get("text"))
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("");
+                    if let Some(summary) = first_line(text) {
+                        items.push(item(
+                            TimelineItemKind::Thinking,
+                            None,
+                            summary,
+                            text.trim_end().to_string(),
+                        ));
+                    }
+                }
+                "tool_use" => {
+                    let name = block.get("name").and_then(|v| v.as_str()).unwrap_or("tool");
+                    let input = block.get("input").unwrap_or(&Value::Null);
+                    let call_id = block.get("id").and_then(|v| v.as_str()).map(str::to_string);
+                    items.push(item(
+                        TimelineItemKind::ToolCall,
+                        call_id,
+                        format!("{name}()"),
+                        serde_json::to_string_pretty(input).unwrap_or_else(|_| input.to_string()),
+                    ));
+                }
+                "tool_result" => {
+                    let text = content_text(block.get("content").unwrap_or(&Value::Null));
+                    let call_id = block
+                        .get("tool_use_id")
+                        .and_then(|v| v.as_str())
+                        .map(str::to_string);
+                    items.push(item(
+                        TimelineItemKind::ToolOutput,
+                        call_id,
+                        first_line(&text).unwrap_or_else(|| "tool output".to_string()),
+                        text,
+                    ));
+                }
+                _ => {}
+            }
+        }
+    } else if let Some(text) = content.as_str()
+        && let Some(summary) = first_line(text)
+    {
+        items.push(item(text_kind, None, summary, text.trim_end().to_string()));
+    }
+
+    for call in message
+        .get("tool_calls")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+    {
+        let function = call.get("function").unwrap_or(&Value::Null);
+        let name = function
+            .get("name")
+            .and_then(|v| v.as_str())
+            .unwrap_or("tool");
+        let arguments = function
+            .get("arguments")
+            .and_then(|v| v.as_str())
+            .unwrap_or("");
+        let detail = serde_json::from_str::<Value>(arguments)
+            .ok()
+            .and_then(|value| serde_json::to_string_pretty(&value).ok())
+            .unwrap_or_else(|| arguments.to_string());
+        let call_id = call.get("id").and_then(|v| v.as_str()).map(str::to_string);
+        items.push(item(
+            TimelineItemKind::ToolCall,
+            call_id,
+            format!("{name}()"),
+            detail,
+        ));
+    }
+    items
+}
+
+/// Whether a JSON line looks like a bare chat message rather than a built-in engine's record.
+pub fn is_chat_message_line(value: &Value) -> bool {
+    value.get("type").is_none() && chat_message(value).get("role").is_some()
+}
+
+fn chat_message(value: &Value) -> &Value {
+    value
+        .get("message")
+        .filter(|message| message.is_object())
+        .unwrap_or(value)
+}
+
+fn content_text(content: &Value) -> String {
+    match content {
+        Value::String(text) => text.trim_end().to_string(),
+        Value::Array(blocks) => blocks
+            .iter()
+            .filter_map(|block| {
+                block
+                    .as_str()
+                    .or_else(|| block.get("text").and_then(|v| v.as_str()))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim_end()
+            .to_string(),
+        _ => String::new(),
+    }
+}
+
+fn first_line(text: &str) -> Option<String> {
+    text.lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn globs_match_within_and_across_components() {
+        assert!(glob_matches(
+            "/home/u/.agent/**/*.jsonl",
+            "/home/u/.agent/2026/01/s1.jsonl"
+        ));
+        assert!(glob_matches(
+            "/home/u/.agent/**/*.jsonl",
+            "/home/u/.agent/s1.jsonl"
+        ));
+        assert!(!glob_matches(
+            "/home/u/.agent/*.jsonl",
+            "/home/u/.agent/a/s1.jsonl"
+        ));
+        assert!(glob_matches("/tmp/s?.json", "/tmp/s1.json"));
+        assert_eq!(
+            glob_base_dir("/home/u/.agent/**/*.jsonl"),
+            PathBuf::from("/home/u/.agent")
+        );
+    }
+
+    #[test]
+    fn parses_openai_and_anthropic_messages() {
+        let text = [
+            r#"{"session_id":"s1","cwd":"/work/app","timestamp":"2026-03-01T10:00:00Z","role":"user","content":"Fix the build\nplease"}"#,
+            r#"{"role":"assistant","content":null,"tool_calls":[{"id":"c1","type":"function","function":{"name":"shell","arguments":"{\"cmd\":\"make\"}"}}]}"#,
+            r#"{"role":"tool","tool_call_id":"c1","content":"ok"}"#,
+            r#"{"message":{"role":"assistant","content":[{"type":"thinking","thinking":"hmm"},{"type":"text","text":"Done."}]}}"#,
+        ]
+        .join("\n");
+
+        let meta = extract_custom_session_meta(CustomLogFlavor::JsonlOpenAi, &text);
+        assert_eq!(meta.id.as_deref(), Some("s1"));
+        assert_eq!(meta.cwd, Some(PathBuf::from("/work/app")));
+        assert_eq!(meta.title.as_deref(), Some("Fix the build"));
+
+        let kinds = custom_log_messages(CustomLogFlavor::JsonlOpenAi, &text)
+            .iter()
+            .flat_map(|value| parse_chat_message_items(value, None))
+            .map(|item| (item.kind, item.call_id))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            vec![
+                (TimelineItemKind::User, None),
+                (TimelineItemKind::ToolCall, Some("c1".to_string())),
+                (TimelineItemKind::ToolOutput, Some("c1".to_string())),
+                (TimelineItemKind::Thinking, None),
+                (TimelineItemKind::Assistant, None),
+            ]
+        );
+
+        let document = r#"{"id":"x","cwd":"/work/app","messages":[{"role":"user","content":[{"type":"text","text":"hi"}]}]}"#;
+        let meta = extract_custom_session_meta(CustomLogFlavor::SingleJson, document);
+        assert_eq!(meta.title.as_deref(), Some("hi"));
+        assert_eq!(meta.cwd, Some(PathBuf::from("/work/app")));
+    }
+}
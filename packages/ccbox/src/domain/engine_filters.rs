@@ -0,0 +1,52 @@
+use crate::domain::SessionEngine;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Remembered engine filters (`None` = all engines). Projects and Sessions keep their own filter;
+/// a filter picked in a project's Sessions view is kept for that project and becomes the default
+/// for projects that have none.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct EngineFilterPrefs {
+    pub projects: Option<SessionEngine>,
+    pub sessions: Option<SessionEngine>,
+    pub project_overrides: BTreeMap<PathBuf, Option<SessionEngine>>,
+}
+
+impl EngineFilterPrefs {
+    pub fn sessions_for(&self, project_path: &Path) -> Option<SessionEngine> {
+        self.project_overrides
+            .get(project_path)
+            .copied()
+            .unwrap_or(self.sessions)
+    }
+
+    pub fn set_sessions(&mut self, project_path: &Path, engine: Option<SessionEngine>) {
+        self.sessions = engine;
+        self.project_overrides
+            .insert(project_path.to_path_buf(), engine);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn project_overrides_win_over_the_sessions_default() {
+        let mut prefs = EngineFilterPrefs {
+            projects: Some(SessionEngine::Codex),
+            ..EngineFilterPrefs::default()
+        };
+        assert_eq!(prefs.sessions_for(Path::new("/work/app")), None);
+
+        prefs.set_sessions(Path::new("/work/app"), Some(SessionEngine::Claude));
+        prefs.set_sessions(Path::new("/work/api"), None);
+        assert_eq!(
+            prefs.sessions_for(Path::new("/work/app")),
+            Some(SessionEngine::Claude)
+        );
+        assert_eq!(prefs.sessions_for(Path::new("/work/api")), None);
+        assert_eq!(prefs.sessions_for(Path::new("/work/new")), None);
+        assert_eq!(prefs.projects, Some(SessionEngine::Codex));
+    }
+}
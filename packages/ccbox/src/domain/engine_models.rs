@@ -0,0 +1,81 @@
+use crate::domain::AgentEngine;
+use serde::Deserialize;
+
+/// Models offered per engine when spawning (`models` in the config). Empty lists mean the
+/// engine's own default is the only choice.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq)]
+pub struct EngineModels {
+    #[serde(default)]
+    pub codex: Vec<String>,
+    #[serde(default)]
+    pub claude: Vec<String>,
+}
+
+impl EngineModels {
+    pub fn for_engine(&self, engine: AgentEngine) -> &[String] {
+        match engine {
+            AgentEngine::Codex => &self.codex,
+            AgentEngine::Claude => &self.claude,
+            // OpenCode is only spawned to resume a session, which keeps its model.
+            AgentEngine::OpenCode => &[],
+        }
+    }
+
+    /// The model after `current` for `engine`: the engine default (`None`), then each configured
+    /// model in order, then back to the default. An unknown `current` restarts at the default.
+    pub fn next(&self, engine: AgentEngine, current: Option<&str>) -> Option<String> {
+        let models = self.for_engine(engine);
+        let next_index = match current {
+            None => 0,
+            Some(current) => match models.iter().position(|model| model == current) {
+                Some(index) => index + 1,
+                None => return None,
+            },
+        };
+        models.get(next_index).cloned()
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        for (engine, models) in [("codex", &self.codex), ("claude", &self.claude)] {
+            if let Some(model) = models
+                .iter()
+                .find(|model| model.trim().is_empty() || model.contains(char::is_whitespace))
+            {
+                return Err(format!(
+                    "models.{engine} entry {model:?} must be a non-empty name without spaces"
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_cycles_default_then_configured_models_per_engine() {
+        let models = EngineModels {
+            claude: vec!["opus".to_string(), "sonnet".to_string()],
+            ..EngineModels::default()
+        };
+        assert_eq!(
+            models.next(AgentEngine::Claude, None).as_deref(),
+            Some("opus")
+        );
+        assert_eq!(
+            models.next(AgentEngine::Claude, Some("opus")).as_deref(),
+            Some("sonnet")
+        );
+        assert_eq!(models.next(AgentEngine::Claude, Some("sonnet")), None);
+        assert_eq!(models.next(AgentEngine::Codex, None), None);
+        assert!(models.validate().is_ok());
+
+        let bad = EngineModels {
+            codex: vec!["gpt 5".to_string()],
+            ..EngineModels::default()
+        };
+        assert!(bad.validate().is_err());
+    }
+}
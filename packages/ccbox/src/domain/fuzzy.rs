@@ -0,0 +1,111 @@
+/// A fuzzy match: its score (higher is better) and the matched character positions of the
+/// candidate, for highlighting.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FuzzyMatch {
+    pub score: i64,
+    pub positions: Vec<usize>,
+}
+
+/// Matches `query` against `candidate` as a case-insensitive subsequence; `None` when some query
+/// character is missing. Consecutive characters and matches at word starts count extra, skipped
+/// characters cost a little, and a contiguous occurrence is preferred over a scattered one when
+/// it scores higher. Spaces in the query are ignored.
+pub fn fuzzy_match(query: &str, candidate: &str) -> Option<FuzzyMatch> {
+    let query = query
+        .chars()
+        .filter(|ch| !ch.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect::<Vec<_>>();
+    if query.is_empty() {
+        return Some(FuzzyMatch {
+            score: 0,
+            positions: Vec::new(),
+        });
+    }
+    let chars = candidate.chars().collect::<Vec<_>>();
+    let lower = chars
+        .iter()
+        .map(|ch| ch.to_lowercase().next().unwrap_or(*ch))
+        .collect::<Vec<_>>();
+
+    let mut positions = Vec::with_capacity(query.len());
+    for (index, ch) in lower.iter().enumerate() {
+        if query.get(positions.len()) == Some(ch) {
+            positions.push(index);
+        }
+    }
+    if positions.len() < query.len() {
+        return None;
+    }
+    let mut best = FuzzyMatch {
+        score: score_positions(&chars, &positions),
+        positions,
+    };
+
+    for start in 0..lower.len().saturating_sub(query.len() - 1) {
+        if lower[start..start + query.len()] == query[..] {
+            let positions = (start..start + query.len()).collect::<Vec<_>>();
+            let score = score_positions(&chars, &positions);
+            if score > best.score {
+                best = FuzzyMatch { score, positions };
+            }
+        }
+    }
+    Some(best)
+}
+
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<i64> {
+    fuzzy_match(query, candidate).map(|found| found.score)
+}
+
+fn score_positions(chars: &[char], positions: &[usize]) -> i64 {
+    let mut score = 0i64;
+    let mut streak = 0i64;
+    let mut previous: Option<usize> = None;
+    for &position in positions {
+        let gap = previous.map_or(position, |previous| position - previous - 1);
+        streak = if gap == 0 && previous.is_some() {
+            streak + 1
+        } else {
+            1
+        };
+        let is_word_start = position == 0 || !chars[position - 1].is_alphanumeric();
+        score += 1 + streak * 2 + if is_word_start { 6 } else { 0 };
+        score -= i64::try_from(gap).unwrap_or(i64::MAX).min(20);
+        previous = Some(position);
+    }
+    score
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_subsequences_and_prefers_tight_word_start_matches() {
+        assert_eq!(fuzzy_score("", "anything"), Some(0));
+        assert_eq!(fuzzy_score("xyz", "ccbox"), None);
+        assert!(fuzzy_score("ccb", "CCBox").is_some());
+
+        let prefix = fuzzy_score("api", "api-server").expect("prefix");
+        let scattered = fuzzy_score("api", "a plain icon").expect("scattered");
+        let inner = fuzzy_score("api", "rapid").expect("inner");
+        assert!(prefix > inner);
+        assert!(prefix > scattered);
+        assert!(fuzzy_score("fix ci", "Fix the flaky CI").is_some());
+    }
+
+    #[test]
+    fn prefers_a_contiguous_occurrence_for_highlighting() {
+        assert_eq!(
+            fuzzy_match("log", "a lot of logs")
+                .expect("match")
+                .positions,
+            vec![9, 10, 11]
+        );
+        assert_eq!(
+            fuzzy_match("fc", "Fix CI").expect("match").positions,
+            vec![0, 4]
+        );
+    }
+}
@@ -223,7 +223,15 @@ fn parse_gemini_message(
 
     if let Some(tokens) = value.get("tokens") {
         if !tokens.is_null() {
-            let (summary, detail) = format_tokens_item(tokens);
+            // The model is kept with the counts so stats can break tokens down per model.
+            let mut tokens = tokens.clone();
+            if let (Some(counts), Some(model)) = (
+                tokens.as_object_mut(),
+                value.get("model").and_then(|v| v.as_str()),
+            ) {
+                counts.insert("model".to_string(), Value::String(model.to_string()));
+            }
+            let (summary, detail) = format_tokens_item(&tokens);
             items.push(TimelineItem {
                 kind: TimelineItemKind::TokenCount,
                 turn_id: None,
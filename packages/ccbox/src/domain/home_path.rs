@@ -0,0 +1,32 @@
+use std::path::{Path, PathBuf};
+
+/// `~` and `~/…` resolved against `home`. Anything else, including `~user/…` and every path
+/// while `home` is unknown, is returned unchanged.
+pub fn expand_home(path: &Path, home: Option<&Path>) -> PathBuf {
+    match (path.strip_prefix("~"), home) {
+        (Ok(rest), Some(home)) if rest.as_os_str().is_empty() => home.to_path_buf(),
+        (Ok(rest), Some(home)) => home.join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expands_only_a_leading_tilde_component() {
+        let home = Some(Path::new("/home/me"));
+        let expand = |path: &str| expand_home(Path::new(path), home);
+        assert_eq!(expand("~"), PathBuf::from("/home/me"));
+        assert_eq!(expand("~/"), PathBuf::from("/home/me"));
+        assert_eq!(expand("~/work/app"), PathBuf::from("/home/me/work/app"));
+        assert_eq!(expand("~other/app"), PathBuf::from("~other/app"));
+        assert_eq!(expand("/srv/~/app"), PathBuf::from("/srv/~/app"));
+        assert_eq!(expand("app/~"), PathBuf::from("app/~"));
+        assert_eq!(
+            expand_home(Path::new("~/app"), None),
+            PathBuf::from("~/app")
+        );
+    }
+}
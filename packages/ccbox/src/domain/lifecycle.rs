@@ -0,0 +1,159 @@
+use serde_json::{Map, Value};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LifecycleEventKind {
+    ProcessStarted,
+    ProcessExited,
+    ProcessFailed,
+    SessionDetected,
+    SessionDeleted,
+    /// A process spawned from a task exited with code 0.
+    TaskCompleted,
+    /// A running process started waiting on a human (question, plan approval or TTY prompt).
+    ProcessNeedsAttention,
+}
+
+impl LifecycleEventKind {
+    pub const ALL: [Self; 7] = [
+        Self::ProcessStarted,
+        Self::ProcessExited,
+        Self::ProcessFailed,
+        Self::SessionDetected,
+        Self::SessionDeleted,
+        Self::TaskCompleted,
+        Self::ProcessNeedsAttention,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::ProcessStarted => "process.started",
+            Self::ProcessExited => "process.exited",
+            Self::ProcessFailed => "process.failed",
+            Self::SessionDetected => "session.detected",
+            Self::SessionDeleted => "session.deleted",
+            Self::TaskCompleted => "task.completed",
+            Self::ProcessNeedsAttention => "process.needs_attention",
+        }
+    }
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.as_str() == raw)
+    }
+}
+
+/// Something that happened to a process or session, with flat string fields for templates.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LifecycleEvent {
+    pub kind: LifecycleEventKind,
+    pub fields: Vec<(&'static str, String)>,
+}
+
+impl LifecycleEvent {
+    pub fn new(kind: LifecycleEventKind) -> Self {
+        Self {
+            kind,
+            fields: Vec::new(),
+        }
+    }
+
+    pub fn with(mut self, name: &'static str, value: impl Into<String>) -> Self {
+        self.fields.push((name, value.into()));
+        self
+    }
+
+    /// Default payload: `{"event": "...", <fields>}`.
+    pub fn to_json(&self) -> Value {
+        let mut object = Map::new();
+        object.insert("event".to_string(), Value::from(self.kind.as_str()));
+        for (name, value) in &self.fields {
+            object.insert((*name).to_string(), Value::from(value.as_str()));
+        }
+        Value::Object(object)
+    }
+
+    /// Replaces `{{event}}` and `{{<field>}}` placeholders. Values are JSON-string escaped (without
+    /// quotes) so they can sit inside a JSON template; unknown placeholders become empty.
+    pub fn render(&self, template: &str) -> String {
+        self.render_with(template, escape_json_string)
+    }
+
+    /// Like `render`, but each value becomes one single-quoted POSIX shell word, so a template
+    /// such as `notify-send {{title}}` can't be broken by quotes or `;` in the value.
+    pub fn render_shell(&self, template: &str) -> String {
+        self.render_with(template, quote_shell_word)
+    }
+
+    fn render_with(&self, template: &str, escape: fn(&str) -> String) -> String {
+        let mut out = String::with_capacity(template.len());
+        let mut rest = template;
+        while let Some(start) = rest.find("{{") {
+            out.push_str(&rest[..start]);
+            let after = &rest[start + 2..];
+            let Some(end) = after.find("}}") else {
+                out.push_str(&rest[start..]);
+                return out;
+            };
+            let name = after[..end].trim();
+            let value = if name == "event" {
+                Some(self.kind.as_str())
+            } else {
+                self.fields
+                    .iter()
+                    .find(|(field, _)| *field == name)
+                    .map(|(_, value)| value.as_str())
+            };
+            out.push_str(&escape(value.unwrap_or_default()));
+            rest = &after[end + 2..];
+        }
+        out.push_str(rest);
+        out
+    }
+}
+
+fn escape_json_string(value: &str) -> String {
+    let quoted = Value::from(value).to_string();
+    quoted[1..quoted.len() - 1].to_string()
+}
+
+fn quote_shell_word(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_templates_with_escaped_values() {
+        let event = LifecycleEvent::new(LifecycleEventKind::ProcessFailed)
+            .with("engine", "Codex")
+            .with("prompt", "Fix \"login\"\nthen test");
+        assert_eq!(
+            event.render(r#"{"text":"{{event}} {{ engine }}: {{prompt}}{{missing}}"}"#),
+            r#"{"text":"process.failed Codex: Fix \"login\"\nthen test"}"#
+        );
+        assert_eq!(event.render("open {{ never closed"), "open {{ never closed");
+        assert_eq!(
+            LifecycleEvent::new(LifecycleEventKind::SessionDeleted)
+                .with("title", "it's done; rm -rf /")
+                .render_shell("echo {{event}} {{title}}"),
+            r"echo 'session.deleted' 'it'\''s done; rm -rf /'"
+        );
+        assert_eq!(
+            event.to_json(),
+            serde_json::json!({
+                "event": "process.failed",
+                "engine": "Codex",
+                "prompt": "Fix \"login\"\nthen test"
+            })
+        );
+    }
+
+    #[test]
+    fn parses_event_names() {
+        for kind in LifecycleEventKind::ALL {
+            assert_eq!(LifecycleEventKind::parse(kind.as_str()), Some(kind));
+        }
+        assert_eq!(LifecycleEventKind::parse("process.killed"), None);
+    }
+}
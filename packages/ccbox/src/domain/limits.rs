@@ -0,0 +1,210 @@
+use crate::domain::AgentEngine;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// OS limits applied to a spawned agent process. Unset fields are left as inherited.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ResourceLimits {
+    /// Niceness (0..=19); only lowering priority is allowed without privileges.
+    #[serde(default)]
+    pub nice: Option<i32>,
+
+    /// CPU time limit (`RLIMIT_CPU`); the process gets SIGXCPU once exceeded.
+    #[serde(default)]
+    pub cpu_seconds: Option<u64>,
+
+    /// Address-space limit (`RLIMIT_AS`) in MiB.
+    #[serde(default)]
+    pub memory_mb: Option<u64>,
+
+    /// Open file descriptor limit (`RLIMIT_NOFILE`).
+    #[serde(default)]
+    pub open_files: Option<u64>,
+}
+
+impl ResourceLimits {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Field-wise override: values set in `other` win.
+    pub fn overridden_by(self, other: Self) -> Self {
+        Self {
+            nice: other.nice.or(self.nice),
+            cpu_seconds: other.cpu_seconds.or(self.cpu_seconds),
+            memory_mb: other.memory_mb.or(self.memory_mb),
+            open_files: other.open_files.or(self.open_files),
+        }
+    }
+
+    pub fn memory_bytes(&self) -> Option<u64> {
+        self.memory_mb.map(|mb| mb.saturating_mul(1024 * 1024))
+    }
+
+    pub fn label(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(nice) = self.nice {
+            parts.push(format!("nice {nice}"));
+        }
+        if let Some(cpu_seconds) = self.cpu_seconds {
+            parts.push(format!("cpu {cpu_seconds}s"));
+        }
+        if let Some(memory_mb) = self.memory_mb {
+            parts.push(format!("mem {memory_mb}MB"));
+        }
+        if let Some(open_files) = self.open_files {
+            parts.push(format!("files {open_files}"));
+        }
+        if parts.is_empty() {
+            return "none".to_string();
+        }
+        parts.join(" · ")
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(nice) = self.nice
+            && !(0..=19).contains(&nice)
+        {
+            return Err(format!("nice must be within 0..=19 (got {nice})"));
+        }
+        for (name, value) in [
+            ("cpu_seconds", self.cpu_seconds),
+            ("memory_mb", self.memory_mb),
+            ("open_files", self.open_files),
+        ] {
+            if value == Some(0) {
+                return Err(format!("{name} must be greater than 0"));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Limits for spawned processes: `default`, then per engine (`codex`, `claude`), then per project.
+/// A project entry applies to its directory and everything below it; the deepest match wins.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ProcessLimitsConfig {
+    #[serde(default)]
+    pub default: ResourceLimits,
+
+    #[serde(default)]
+    pub engines: BTreeMap<String, ResourceLimits>,
+
+    #[serde(default)]
+    pub projects: BTreeMap<PathBuf, ResourceLimits>,
+}
+
+impl ProcessLimitsConfig {
+    pub fn resolve(&self, engine: AgentEngine, project_path: &Path) -> ResourceLimits {
+        let engine_key = engine.label().to_ascii_lowercase();
+        let mut limits = self.default;
+        if let Some(engine_limits) = self.engines.get(&engine_key) {
+            limits = limits.overridden_by(*engine_limits);
+        }
+        let project_limits = self
+            .projects
+            .iter()
+            .filter(|(path, _)| project_path.starts_with(path))
+            .max_by_key(|(path, _)| path.components().count());
+        if let Some((_, project_limits)) = project_limits {
+            limits = limits.overridden_by(*project_limits);
+        }
+        limits
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        self.default
+            .validate()
+            .map_err(|error| format!("process_limits.default: {error}"))?;
+        for (engine, limits) in &self.engines {
+            if !matches!(engine.as_str(), "codex" | "claude" | "opencode") {
+                return Err(format!(
+                    "process_limits.engines: unknown engine `{engine}` (expected codex, claude or opencode)"
+                ));
+            }
+            limits
+                .validate()
+                .map_err(|error| format!("process_limits.engines.{engine}: {error}"))?;
+        }
+        for (path, limits) in &self.projects {
+            limits
+                .validate()
+                .map_err(|error| format!("process_limits.projects[{}]: {error}", path.display()))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_default_then_engine_then_deepest_project() {
+        let config: ProcessLimitsConfig = serde_json::from_value(serde_json::json!({
+            "default": { "nice": 5, "memory_mb": 4096 },
+            "engines": { "claude": { "memory_mb": 8192, "open_files": 1024 } },
+            "projects": {
+                "/work": { "cpu_seconds": 600 },
+                "/work/big": { "cpu_seconds": 7200 }
+            }
+        }))
+        .expect("config");
+
+        let codex = config.resolve(AgentEngine::Codex, Path::new("/tmp/x"));
+        assert_eq!(
+            codex,
+            ResourceLimits {
+                nice: Some(5),
+                memory_mb: Some(4096),
+                ..ResourceLimits::default()
+            }
+        );
+
+        let claude = config.resolve(AgentEngine::Claude, Path::new("/work/big/app"));
+        assert_eq!(
+            claude,
+            ResourceLimits {
+                nice: Some(5),
+                cpu_seconds: Some(7200),
+                memory_mb: Some(8192),
+                open_files: Some(1024),
+            }
+        );
+        assert_eq!(
+            config
+                .resolve(AgentEngine::Codex, Path::new("/workshop"))
+                .cpu_seconds,
+            None
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_limits() {
+        let mut config = ProcessLimitsConfig::default();
+        assert_eq!(config.validate(), Ok(()));
+
+        config.default.nice = Some(-5);
+        assert!(config.validate().is_err());
+
+        config.default.nice = None;
+        config
+            .engines
+            .insert("gemini".to_string(), ResourceLimits::default());
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn label_lists_set_limits() {
+        assert_eq!(ResourceLimits::default().label(), "none");
+        let limits = ResourceLimits {
+            nice: Some(10),
+            memory_mb: Some(2048),
+            ..ResourceLimits::default()
+        };
+        assert_eq!(limits.label(), "nice 10 · mem 2048MB");
+    }
+}
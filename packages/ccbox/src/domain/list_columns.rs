@@ -0,0 +1,257 @@
+use serde::Deserialize;
+
+/// A column of the Projects, Sessions or Tasks list (`list_columns` in the config).
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ListColumn {
+    /// The CX/CL/GM/OC badge before the name.
+    Engine,
+    Sessions,
+    Branch,
+    Items,
+    Duration,
+    Tokens,
+    /// Sparkline of the last token-count events.
+    Activity,
+    Size,
+    Schedule,
+    Images,
+    Modified,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ListKind {
+    Projects,
+    Sessions,
+    Tasks,
+}
+
+impl ListColumn {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Engine => "engine",
+            Self::Sessions => "sessions",
+            Self::Branch => "branch",
+            Self::Items => "items",
+            Self::Duration => "duration",
+            Self::Tokens => "tokens",
+            Self::Activity => "activity",
+            Self::Size => "size",
+            Self::Schedule => "schedule",
+            Self::Images => "images",
+            Self::Modified => "modified",
+        }
+    }
+
+    /// Narrow terminals drop the lowest priority columns first.
+    fn priority(self) -> u8 {
+        match self {
+            Self::Modified | Self::Engine => 9,
+            Self::Sessions | Self::Schedule => 8,
+            Self::Size => 7,
+            Self::Tokens => 6,
+            Self::Duration => 5,
+            Self::Images => 4,
+            Self::Items => 3,
+            Self::Branch => 2,
+            Self::Activity => 1,
+        }
+    }
+}
+
+impl ListKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Projects => "projects",
+            Self::Sessions => "sessions",
+            Self::Tasks => "tasks",
+        }
+    }
+
+    pub fn supported(self) -> &'static [ListColumn] {
+        use ListColumn::*;
+        match self {
+            Self::Projects => &[Engine, Sessions, Size, Tokens, Modified],
+            Self::Sessions => &[
+                Engine, Branch, Items, Duration, Tokens, Activity, Size, Modified,
+            ],
+            Self::Tasks => &[Schedule, Images, Modified],
+        }
+    }
+
+    pub fn default_columns(self) -> &'static [ListColumn] {
+        use ListColumn::*;
+        match self {
+            Self::Projects => &[Engine, Sessions, Size, Tokens, Modified],
+            Self::Sessions => &[Engine, Branch, Size, Modified],
+            Self::Tasks => &[Schedule, Images, Modified],
+        }
+    }
+}
+
+/// The columns chosen per list; a list left out keeps its default columns.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ListColumnsConfig {
+    #[serde(default)]
+    pub projects: Option<Vec<ListColumn>>,
+
+    #[serde(default)]
+    pub sessions: Option<Vec<ListColumn>>,
+
+    #[serde(default)]
+    pub tasks: Option<Vec<ListColumn>>,
+}
+
+impl ListColumnsConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        for kind in [ListKind::Projects, ListKind::Sessions, ListKind::Tasks] {
+            let Some(columns) = self.configured(kind) else {
+                continue;
+            };
+            if let Some(column) = columns
+                .iter()
+                .find(|column| !kind.supported().contains(column))
+            {
+                return Err(format!(
+                    "list_columns: `{}` is not a {} column",
+                    column.name(),
+                    kind.name()
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Configured columns of `kind` in order without duplicates, else its defaults.
+    pub fn columns(&self, kind: ListKind) -> Vec<ListColumn> {
+        let configured = self.configured(kind).unwrap_or(kind.default_columns());
+        let mut columns = Vec::new();
+        for column in configured {
+            if !columns.contains(column) {
+                columns.push(*column);
+            }
+        }
+        columns
+    }
+
+    /// Sessions columns; `show_metrics` (F6) adds item count, duration, tokens and activity
+    /// before size and age when they are not configured already.
+    pub fn session_columns(&self, show_metrics: bool) -> Vec<ListColumn> {
+        let mut columns = self.columns(ListKind::Sessions);
+        if show_metrics {
+            let at = columns
+                .iter()
+                .position(|column| matches!(column, ListColumn::Size | ListColumn::Modified))
+                .unwrap_or(columns.len());
+            let metrics = [
+                ListColumn::Items,
+                ListColumn::Duration,
+                ListColumn::Tokens,
+                ListColumn::Activity,
+            ]
+            .into_iter()
+            .filter(|column| !columns.contains(column))
+            .collect::<Vec<_>>();
+            columns.splice(at..at, metrics);
+        }
+        columns
+    }
+
+    fn configured(&self, kind: ListKind) -> Option<&[ListColumn]> {
+        match kind {
+            ListKind::Projects => self.projects.as_deref(),
+            ListKind::Sessions => self.sessions.as_deref(),
+            ListKind::Tasks => self.tasks.as_deref(),
+        }
+    }
+}
+
+/// Which of `columns` (rendered `widths` wide, `separator` apart) fit in `available` cells,
+/// dropping the lowest priority ones first.
+pub fn fit_list_columns(
+    columns: &[ListColumn],
+    widths: &[usize],
+    available: usize,
+    separator: usize,
+) -> Vec<bool> {
+    let mut keep = vec![true; columns.len()];
+    let used = |keep: &[bool]| {
+        let kept = keep.iter().filter(|is_kept| **is_kept).count();
+        let cells = widths
+            .iter()
+            .zip(keep)
+            .filter(|(_, is_kept)| **is_kept)
+            .map(|(width, _)| *width)
+            .sum::<usize>();
+        cells + separator * kept.saturating_sub(1)
+    };
+
+    let mut by_priority = (0..columns.len()).collect::<Vec<_>>();
+    by_priority.sort_by_key(|index| columns[*index].priority());
+    for index in by_priority {
+        if used(&keep) <= available {
+            break;
+        }
+        keep[index] = false;
+    }
+    keep
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn configured_columns_fall_back_to_defaults_and_shrink_to_fit() {
+        let config: ListColumnsConfig = serde_json::from_value(serde_json::json!({
+            "sessions": ["branch", "tokens", "modified", "tokens"]
+        }))
+        .expect("config");
+        assert_eq!(config.validate(), Ok(()));
+        assert_eq!(
+            config.columns(ListKind::Sessions),
+            vec![ListColumn::Branch, ListColumn::Tokens, ListColumn::Modified]
+        );
+        assert_eq!(
+            config.session_columns(true),
+            vec![
+                ListColumn::Branch,
+                ListColumn::Tokens,
+                ListColumn::Items,
+                ListColumn::Duration,
+                ListColumn::Activity,
+                ListColumn::Modified,
+            ]
+        );
+        assert_eq!(
+            config.columns(ListKind::Tasks),
+            ListKind::Tasks.default_columns()
+        );
+
+        let bad: ListColumnsConfig =
+            serde_json::from_value(serde_json::json!({ "tasks": ["tokens"] })).expect("config");
+        assert!(bad.validate().is_err());
+        assert!(
+            serde_json::from_value::<ListColumnsConfig>(serde_json::json!({
+                "projects": ["tags"]
+            }))
+            .is_err()
+        );
+
+        let columns = [ListColumn::Branch, ListColumn::Size, ListColumn::Modified];
+        let widths = [20, 8, 7];
+        assert_eq!(
+            fit_list_columns(&columns, &widths, 100, 5),
+            vec![true, true, true]
+        );
+        assert_eq!(
+            fit_list_columns(&columns, &widths, 20, 5),
+            vec![false, true, true]
+        );
+        assert_eq!(
+            fit_list_columns(&columns, &widths, 5, 5),
+            vec![false, false, false]
+        );
+    }
+}
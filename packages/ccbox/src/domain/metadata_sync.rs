@@ -0,0 +1,180 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Key prefixes ccbox syncs; entries under other prefixes are carried along untouched.
+pub const METADATA_KEY_PREFIXES: [&str; 5] = [
+    "alias:",
+    "session_project:",
+    "project_name:",
+    "project_hidden:",
+    "task:",
+];
+
+/// One synced metadata value; `value: None` records a deletion.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct MetadataEntry {
+    pub key: String,
+    #[serde(default)]
+    pub value: Option<String>,
+    pub updated_at_ms: i64,
+    pub machine: String,
+}
+
+impl MetadataEntry {
+    /// Last-writer-wins: the later timestamp wins, and the machine id breaks ties so every
+    /// machine picks the same winner.
+    pub fn is_newer_than(&self, other: &MetadataEntry) -> bool {
+        (self.updated_at_ms, self.machine.as_str()) > (other.updated_at_ms, other.machine.as_str())
+    }
+}
+
+pub fn is_synced_metadata_key(key: &str) -> bool {
+    METADATA_KEY_PREFIXES
+        .iter()
+        .any(|prefix| key.starts_with(prefix))
+}
+
+/// Stamps what changed locally since the last sync: new or edited values and removed keys
+/// (as deletions) get `now_ms` and this machine; everything else keeps its synced entry.
+pub fn stamp_local_metadata(
+    synced: &BTreeMap<String, MetadataEntry>,
+    current: &BTreeMap<String, String>,
+    machine: &str,
+    now_ms: i64,
+) -> BTreeMap<String, MetadataEntry> {
+    let stamp = |key: &str, value: Option<&String>| MetadataEntry {
+        key: key.to_string(),
+        value: value.cloned(),
+        updated_at_ms: now_ms,
+        machine: machine.to_string(),
+    };
+
+    let mut out = synced.clone();
+    for (key, value) in current {
+        let is_unchanged = synced
+            .get(key)
+            .is_some_and(|entry| entry.value.as_ref() == Some(value));
+        if !is_unchanged {
+            out.insert(key.clone(), stamp(key, Some(value)));
+        }
+    }
+    for (key, entry) in synced {
+        if entry.value.is_some() && is_synced_metadata_key(key) && !current.contains_key(key) {
+            out.insert(key.clone(), stamp(key, None));
+        }
+    }
+    out
+}
+
+/// Merges `incoming` into `local`, keeping the newer entry per key.
+pub fn merge_metadata(
+    local: &mut BTreeMap<String, MetadataEntry>,
+    incoming: impl IntoIterator<Item = MetadataEntry>,
+) {
+    for entry in incoming {
+        match local.get(&entry.key) {
+            Some(current) if !entry.is_newer_than(current) => {}
+            _ => {
+                local.insert(entry.key.clone(), entry);
+            }
+        }
+    }
+}
+
+/// Merged entries whose value differs from the local stores and must be written back.
+pub fn metadata_to_apply<'a>(
+    merged: &'a BTreeMap<String, MetadataEntry>,
+    current: &BTreeMap<String, String>,
+) -> Vec<&'a MetadataEntry> {
+    merged
+        .values()
+        .filter(|entry| is_synced_metadata_key(&entry.key))
+        .filter(|entry| entry.value.as_ref() != current.get(&entry.key))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(key: &str, value: Option<&str>, updated_at_ms: i64, machine: &str) -> MetadataEntry {
+        MetadataEntry {
+            key: key.to_string(),
+            value: value.map(str::to_string),
+            updated_at_ms,
+            machine: machine.to_string(),
+        }
+    }
+
+    fn by_key(entries: Vec<MetadataEntry>) -> BTreeMap<String, MetadataEntry> {
+        entries
+            .into_iter()
+            .map(|entry| (entry.key.clone(), entry))
+            .collect()
+    }
+
+    #[test]
+    fn stamps_only_local_edits_and_removals() {
+        let synced = by_key(vec![
+            entry("alias:codex:a", Some("same"), 10, "desktop"),
+            entry("alias:codex:b", Some("old"), 10, "desktop"),
+            entry("alias:codex:c", Some("gone"), 10, "desktop"),
+            entry("future:x", Some("kept"), 10, "desktop"),
+        ]);
+        let current = BTreeMap::from([
+            ("alias:codex:a".to_string(), "same".to_string()),
+            ("alias:codex:b".to_string(), "new".to_string()),
+            ("alias:codex:d".to_string(), "added".to_string()),
+        ]);
+
+        let stamped = stamp_local_metadata(&synced, &current, "laptop", 50);
+
+        assert_eq!(stamped["alias:codex:a"], synced["alias:codex:a"]);
+        assert_eq!(
+            stamped["alias:codex:b"],
+            entry("alias:codex:b", Some("new"), 50, "laptop")
+        );
+        assert_eq!(
+            stamped["alias:codex:c"],
+            entry("alias:codex:c", None, 50, "laptop")
+        );
+        assert_eq!(
+            stamped["alias:codex:d"],
+            entry("alias:codex:d", Some("added"), 50, "laptop")
+        );
+        assert_eq!(stamped["future:x"], synced["future:x"]);
+    }
+
+    #[test]
+    fn merge_keeps_the_newest_entry_and_reports_what_to_apply() {
+        let mut local = by_key(vec![
+            entry("alias:codex:a", Some("laptop"), 20, "laptop"),
+            entry("project_name:/p", Some("Mine"), 5, "laptop"),
+        ]);
+        merge_metadata(
+            &mut local,
+            vec![
+                entry("alias:codex:a", Some("desktop"), 15, "desktop"),
+                entry("project_name:/p", None, 30, "desktop"),
+                entry("task:t1", Some("{}"), 5, "desktop"),
+            ],
+        );
+        assert_eq!(local["alias:codex:a"].value.as_deref(), Some("laptop"));
+        assert_eq!(local["project_name:/p"].value, None);
+
+        let current = BTreeMap::from([
+            ("alias:codex:a".to_string(), "laptop".to_string()),
+            ("project_name:/p".to_string(), "Mine".to_string()),
+        ]);
+        let keys = metadata_to_apply(&local, &current)
+            .into_iter()
+            .map(|entry| entry.key.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(keys, vec!["project_name:/p", "task:t1"]);
+
+        let tie_a = entry("k", Some("a"), 7, "aaa");
+        let tie_b = entry("k", Some("b"), 7, "bbb");
+        assert!(tie_b.is_newer_than(&tie_a));
+        assert!(!tie_a.is_newer_than(&tie_b));
+    }
+}
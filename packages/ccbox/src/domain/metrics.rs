@@ -0,0 +1,76 @@
+use std::fmt::Write as _;
+
+/// Point-in-time values exposed on the metrics endpoint.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct MetricsSnapshot {
+    pub sessions_scanned: u64,
+    pub parse_failures: u64,
+    pub watcher_events: u64,
+    pub running_processes: u64,
+    pub tokens_indexed: u64,
+}
+
+impl MetricsSnapshot {
+    /// Prometheus text exposition format (version 0.0.4).
+    pub fn render_prometheus(&self) -> String {
+        let metrics = [
+            (
+                "ccbox_sessions_scanned_total",
+                "counter",
+                "Session logs read by scans and rescans.",
+                self.sessions_scanned,
+            ),
+            (
+                "ccbox_parse_failures_total",
+                "counter",
+                "Session logs that failed to parse during scans.",
+                self.parse_failures,
+            ),
+            (
+                "ccbox_watcher_events_total",
+                "counter",
+                "File watcher change events.",
+                self.watcher_events,
+            ),
+            (
+                "ccbox_running_processes",
+                "gauge",
+                "Agent processes currently running.",
+                self.running_processes,
+            ),
+            (
+                "ccbox_tokens_indexed",
+                "gauge",
+                "Total tokens across sessions in the session index.",
+                self.tokens_indexed,
+            ),
+        ];
+        let mut out = String::new();
+        for (name, kind, help, value) in metrics {
+            let _ = writeln!(out, "# HELP {name} {help}");
+            let _ = writeln!(out, "# TYPE {name} {kind}");
+            let _ = writeln!(out, "{name} {value}");
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_prometheus_text_format() {
+        let snapshot = MetricsSnapshot {
+            sessions_scanned: 12,
+            running_processes: 2,
+            ..MetricsSnapshot::default()
+        };
+        let text = snapshot.render_prometheus();
+        assert!(text.contains(
+            "# TYPE ccbox_sessions_scanned_total counter\nccbox_sessions_scanned_total 12\n"
+        ));
+        assert!(text.contains("# TYPE ccbox_running_processes gauge\nccbox_running_processes 2\n"));
+        assert_eq!(text.lines().count(), 15);
+    }
+}
@@ -1,19 +1,77 @@
+mod attention;
+mod audit;
 mod claude;
+mod context_usage;
+mod custom_engine;
+mod engine_filters;
+mod engine_models;
+mod fuzzy;
 mod gemini;
+mod home_path;
+mod lifecycle;
+mod limits;
+mod list_columns;
+mod metadata_sync;
+mod metrics;
 mod parse;
+mod plan;
+mod project_env;
+mod project_prefs;
+mod prompt_attachments;
 mod remote;
+mod remote_policy;
+mod remote_source;
+mod retry;
+mod schedule;
+mod session_columns;
+mod session_diff;
+mod session_dirs;
+mod session_key;
+mod session_search;
 mod skill_span;
+mod spawn_args;
 mod stats;
 mod tasks;
 mod timeline;
+mod transcript;
 mod types;
+mod worktree;
 
+pub use attention::*;
+pub use audit::*;
 pub use claude::*;
+pub use context_usage::*;
+pub use custom_engine::*;
+pub use engine_filters::*;
+pub use engine_models::*;
+pub use fuzzy::*;
 pub use gemini::*;
+pub use home_path::*;
+pub use lifecycle::*;
+pub use limits::*;
+pub use list_columns::*;
+pub use metadata_sync::*;
+pub use metrics::*;
 pub use parse::*;
+pub use plan::*;
+pub use project_env::*;
+pub use project_prefs::*;
+pub use prompt_attachments::*;
 pub use remote::*;
+pub use remote_policy::*;
+pub use remote_source::*;
+pub use retry::*;
+pub use schedule::*;
+pub use session_columns::*;
+pub use session_diff::*;
+pub use session_dirs::*;
+pub use session_key::*;
+pub use session_search::*;
 pub use skill_span::*;
+pub use spawn_args::*;
 pub use stats::*;
 pub use tasks::*;
 pub use timeline::*;
+pub use transcript::*;
 pub use types::*;
+pub use worktree::*;
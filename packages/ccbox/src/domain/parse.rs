@@ -1,7 +1,7 @@
-use crate::domain::{ProjectSummary, SessionEngine, SessionMeta, SessionSummary};
+use crate::domain::{ProjectSummary, SessionEngine, SessionGit, SessionMeta, SessionSummary};
 use serde::Deserialize;
 use std::collections::BTreeMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 use thiserror::Error;
 
@@ -26,6 +26,14 @@ struct SessionMetaPayload {
     id: String,
     timestamp: String,
     cwd: String,
+    #[serde(default)]
+    git: Option<SessionMetaGit>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SessionMetaGit {
+    commit_hash: Option<String>,
+    branch: Option<String>,
 }
 
 pub fn parse_session_meta_line(line: &str) -> Result<SessionMeta, ParseError> {
@@ -38,6 +46,13 @@ pub fn parse_session_meta_line(line: &str) -> Result<SessionMeta, ParseError> {
         id: parsed.payload.id,
         cwd: PathBuf::from(parsed.payload.cwd),
         started_at_rfc3339: parsed.payload.timestamp,
+        git: parsed.payload.git.and_then(|git| {
+            let git = SessionGit {
+                branch: git.branch.filter(|branch| !branch.is_empty()),
+                commit: git.commit_hash.filter(|commit| !commit.is_empty()),
+            };
+            (git.branch.is_some() || git.commit.is_some()).then_some(git)
+        }),
     })
 }
 
@@ -111,7 +126,7 @@ pub fn index_projects(sessions: &[SessionSummary]) -> Vec<ProjectSummary> {
     let mut grouped: BTreeMap<PathBuf, Vec<SessionSummary>> = BTreeMap::new();
     for session in sessions {
         grouped
-            .entry(session.meta.cwd.clone())
+            .entry(session.project_path().to_path_buf())
             .or_default()
             .push(session.clone());
     }
@@ -126,28 +141,12 @@ pub fn index_projects(sessions: &[SessionSummary]) -> Vec<ProjectSummary> {
                 .first()
                 .and_then(|session| session.file_modified);
 
-            let is_gemini_project = project_sessions
-                .iter()
-                .all(|session| session.engine == SessionEngine::Gemini);
-            let name = if is_gemini_project {
-                let hash = project_path
-                    .file_name()
-                    .map(|name| name.to_string_lossy().to_string())
-                    .unwrap_or_else(|| project_path.display().to_string());
-                let prefix: String = hash.chars().take(8).collect();
-                format!("gemini:{prefix}")
-            } else {
-                project_path
-                    .file_name()
-                    .map(|name| name.to_string_lossy().to_string())
-                    .unwrap_or_else(|| project_path.display().to_string())
-            };
-
             ProjectSummary {
-                name,
+                name: default_project_name(&project_path, &project_sessions),
                 project_path,
                 sessions: project_sessions,
                 last_modified,
+                hidden: false,
             }
         })
         .collect();
@@ -157,6 +156,35 @@ pub fn index_projects(sessions: &[SessionSummary]) -> Vec<ProjectSummary> {
     projects
 }
 
+/// The folder name of the project, `gemini:<hash prefix>` for Gemini's hashed project dirs, or
+/// `<remote>:<folder>` for a remote source's project.
+pub fn default_project_name(project_path: &Path, sessions: &[SessionSummary]) -> String {
+    let folder = project_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| project_path.display().to_string());
+    let is_gemini_project = !sessions.is_empty()
+        && sessions
+            .iter()
+            .all(|session| session.engine == SessionEngine::Gemini);
+    let remote = sessions
+        .first()
+        .and_then(|session| session.remote.as_deref());
+    if let Some(remote) = remote
+        && sessions
+            .iter()
+            .all(|session| session.remote.as_deref() == Some(remote))
+    {
+        return format!("{remote}:{folder}");
+    }
+    if is_gemini_project {
+        let prefix: String = folder.chars().take(8).collect();
+        format!("gemini:{prefix}")
+    } else {
+        folder
+    }
+}
+
 pub fn make_session_summary(
     meta: SessionMeta,
     log_path: PathBuf,
@@ -172,6 +200,9 @@ pub fn make_session_summary(
         title,
         file_size_bytes,
         file_modified,
+        parent_id: None,
+        project_root: None,
+        remote: None,
     }
 }
 
@@ -185,6 +216,15 @@ mod tests {
         let meta = parse_session_meta_line(line).expect("meta");
         assert_eq!(meta.id, "abc");
         assert_eq!(meta.cwd.to_string_lossy(), "/tmp/project");
+        assert_eq!(meta.git, None);
+
+        let line = r#"{"type":"session_meta","payload":{"id":"abc","timestamp":"2026-02-18T21:39:39.022Z","cwd":"/tmp/project","git":{"commit_hash":"4f5934caa96e82bc32692c615e74feef28178191","branch":"main","repository_url":"git@github.com:o/r.git"}}}"#;
+        let git = parse_session_meta_line(line)
+            .expect("meta")
+            .git
+            .expect("git");
+        assert_eq!(git.branch.as_deref(), Some("main"));
+        assert_eq!(git.label(), "main@4f5934c");
     }
 
     #[test]
@@ -212,4 +252,53 @@ mod tests {
         ));
         assert!(!is_metadata_prompt("do the thing"));
     }
+
+    #[test]
+    fn groups_sessions_under_their_repo_root() {
+        let session = |id: &str, cwd: &str, root: Option<&str>| {
+            let mut session = make_session_summary(
+                SessionMeta {
+                    id: id.to_string(),
+                    cwd: PathBuf::from(cwd),
+                    started_at_rfc3339: "2026-02-20T00:00:00Z".to_string(),
+                    git: None,
+                },
+                PathBuf::from(format!("/logs/{id}.jsonl")),
+                id.to_string(),
+                0,
+                None,
+                SessionEngine::Codex,
+            );
+            session.project_root = root.map(PathBuf::from);
+            session
+        };
+        let mut remote = session("e", "/work/app", Some("box:/work/app"));
+        remote.remote = Some("box".to_string());
+        let sessions = vec![
+            session("a", "/work/app", None),
+            session("b", "/work/app/packages/web", Some("/work/app")),
+            session("c", "/work/app-feature", Some("/work/app")),
+            session("d", "/work/other", None),
+            remote,
+        ];
+
+        let projects = index_projects(&sessions);
+
+        assert_eq!(projects.len(), 3);
+        assert!(projects.iter().any(|project| project.name == "box:app"
+            && project.project_path == Path::new("box:/work/app")));
+        let app = projects
+            .iter()
+            .find(|project| project.name == "app")
+            .expect("app");
+        assert_eq!(app.project_path, PathBuf::from("/work/app"));
+        let labels = app
+            .sessions
+            .iter()
+            .map(SessionSummary::grouped_cwd_label)
+            .collect::<Vec<_>>();
+        assert!(labels.contains(&None));
+        assert!(labels.contains(&Some("packages/web".to_string())));
+        assert!(labels.contains(&Some("/work/app-feature".to_string())));
+    }
 }
@@ -0,0 +1,391 @@
+use crate::domain::{TimelineItem, TimelineItemKind};
+use serde_json::Value;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PlanStepStatus {
+    Pending,
+    InProgress,
+    Completed,
+}
+
+impl PlanStepStatus {
+    fn parse(value: &str) -> Self {
+        match value {
+            "completed" | "done" => Self::Completed,
+            "in_progress" | "in-progress" | "active" => Self::InProgress,
+            _ => Self::Pending,
+        }
+    }
+
+    pub fn is_completed(self) -> bool {
+        matches!(self, Self::Completed)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlanStep {
+    pub text: String,
+    pub status: PlanStepStatus,
+}
+
+/// Latest agent plan/todo list as written by a plan tool call.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlanState {
+    pub steps: Vec<PlanStep>,
+    pub explanation: Option<String>,
+}
+
+impl PlanState {
+    pub fn completed_count(&self) -> usize {
+        self.steps
+            .iter()
+            .filter(|step| step.status.is_completed())
+            .count()
+    }
+
+    pub fn progress_label(&self) -> String {
+        format!("{}/{} steps", self.completed_count(), self.steps.len())
+    }
+}
+
+/// Parses the arguments of a plan tool call. Claude's `TodoWrite` and Codex's `update_plan`
+/// replace the whole list on every call, so each call is a full snapshot; `ExitPlanMode`
+/// carries a markdown plan whose list items become steps. Returns `None` for other tools.
+pub fn parse_plan_tool_call(tool_name: &str, arguments: &str) -> Option<PlanState> {
+    let value = serde_json::from_str::<Value>(arguments).ok()?;
+    match tool_name {
+        "TodoWrite" => parse_todo_list(value.get("todos")?, "content"),
+        "update_plan" => {
+            let mut plan = parse_todo_list(value.get("plan")?, "step")?;
+            plan.explanation = value
+                .get("explanation")
+                .and_then(|v| v.as_str())
+                .map(str::trim)
+                .filter(|text| !text.is_empty())
+                .map(str::to_string);
+            Some(plan)
+        }
+        "ExitPlanMode" => parse_markdown_plan(value.get("plan")?.as_str()?),
+        _ => None,
+    }
+}
+
+/// Plan snapshot carried by a timeline tool call, if it is a plan tool.
+pub fn parse_plan_timeline_item(item: &TimelineItem) -> Option<PlanState> {
+    if item.kind != TimelineItemKind::ToolCall {
+        return None;
+    }
+    let (tool_name, _) = item.summary.split_once("()")?;
+    parse_plan_tool_call(tool_name, &item.detail)
+}
+
+fn parse_markdown_plan(markdown: &str) -> Option<PlanState> {
+    let steps = markdown
+        .lines()
+        .filter_map(|line| {
+            let item = strip_list_marker(line.trim_start())?;
+            let (status, text) = if let Some(rest) = item.strip_prefix("[ ]") {
+                (PlanStepStatus::Pending, rest)
+            } else if let Some(rest) = item
+                .strip_prefix("[x]")
+                .or_else(|| item.strip_prefix("[X]"))
+            {
+                (PlanStepStatus::Completed, rest)
+            } else {
+                (PlanStepStatus::Pending, item)
+            };
+            let text = text.trim();
+            (!text.is_empty()).then(|| PlanStep {
+                text: text.to_string(),
+                status,
+            })
+        })
+        .collect::<Vec<_>>();
+    (!steps.is_empty()).then_some(PlanState {
+        steps,
+        explanation: None,
+    })
+}
+
+fn strip_list_marker(line: &str) -> Option<&str> {
+    if let Some(rest) = line.strip_prefix("- ").or_else(|| line.strip_prefix("* ")) {
+        return Some(rest);
+    }
+    let digits = line.chars().take_while(|ch| ch.is_ascii_digit()).count();
+    if digits == 0 {
+        return None;
+    }
+    line[digits..].strip_prefix(". ")
+}
+
+fn parse_todo_list(todos: &Value, text_key: &str) -> Option<PlanState> {
+    let steps = todos
+        .as_array()?
+        .iter()
+        .filter_map(|todo| {
+            let text = todo.get(text_key).and_then(|v| v.as_str())?.trim();
+            if text.is_empty() {
+                return None;
+            }
+            let status = todo
+                .get("status")
+                .and_then(|v| v.as_str())
+                .map(PlanStepStatus::parse)
+                .unwrap_or(PlanStepStatus::Pending);
+            Some(PlanStep {
+                text: text.to_string(),
+                status,
+            })
+        })
+        .collect();
+    Some(PlanState {
+        steps,
+        explanation: None,
+    })
+}
+
+/// Most recent plan snapshot in `items`, including an emptied list.
+pub fn latest_plan_snapshot(items: &[TimelineItem]) -> Option<PlanState> {
+    items.iter().rev().find_map(parse_plan_timeline_item)
+}
+
+/// Finds the most recent plan in a timeline. An emptied todo list clears the plan.
+pub fn latest_plan_state(items: &[TimelineItem]) -> Option<PlanState> {
+    latest_plan_snapshot(items).filter(|plan| !plan.steps.is_empty())
+}
+
+/// Adds a `Todo` item after every Claude `TodoWrite` call and `TodoRead` result, so the list
+/// can be followed as it evolves through the session.
+pub fn with_todo_items(items: Vec<TimelineItem>) -> Vec<TimelineItem> {
+    let mut todo_read_calls = Vec::new();
+    let mut out = Vec::with_capacity(items.len());
+    for item in items {
+        let todos = match item.kind {
+            TimelineItemKind::ToolCall if item.summary == "TodoRead()" => {
+                todo_read_calls.extend(item.call_id.clone());
+                None
+            }
+            TimelineItemKind::ToolCall if item.summary == "TodoWrite()" => {
+                parse_plan_tool_call("TodoWrite", &item.detail)
+            }
+            TimelineItemKind::ToolOutput
+                if item
+                    .call_id
+                    .as_ref()
+                    .is_some_and(|call_id| todo_read_calls.contains(call_id)) =>
+            {
+                parse_todo_read_output(&item.detail)
+            }
+            _ => None,
+        };
+        let todo_item = todos.map(|todos| TimelineItem {
+            kind: TimelineItemKind::Todo,
+            turn_id: item.turn_id.clone(),
+            call_id: item.call_id.clone(),
+            source_line_no: item.source_line_no,
+            timestamp: item.timestamp.clone(),
+            timestamp_ms: item.timestamp_ms,
+            summary: format!("Todos · {}", todos.progress_label()),
+            detail: format_todo_checklist(&todos),
+        });
+        out.push(item);
+        out.extend(todo_item);
+    }
+    out
+}
+
+/// `TodoRead` answers with a sentence followed by the list as a JSON array.
+fn parse_todo_read_output(output: &str) -> Option<PlanState> {
+    let start = output.find('[')?;
+    let end = output.rfind(']')?;
+    let todos = serde_json::from_str::<Value>(output.get(start..=end)?).ok()?;
+    parse_todo_list(&todos, "content")
+}
+
+/// One `[x]` (done), `[~]` (in progress) or `[ ]` (pending) line per step.
+pub fn format_todo_checklist(todos: &PlanState) -> String {
+    todos
+        .steps
+        .iter()
+        .map(|step| {
+            let marker = match step.status {
+                PlanStepStatus::Completed => "[x]",
+                PlanStepStatus::InProgress => "[~]",
+                PlanStepStatus::Pending => "[ ]",
+            };
+            format!("{marker} {}", step.text)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Reads back a checklist written by `format_todo_checklist`.
+pub fn parse_todo_checklist(text: &str) -> PlanState {
+    let steps = text
+        .lines()
+        .filter_map(|line| {
+            let (marker, rest) = line.split_at_checked(3)?;
+            let status = match marker {
+                "[x]" => PlanStepStatus::Completed,
+                "[~]" => PlanStepStatus::InProgress,
+                "[ ]" => PlanStepStatus::Pending,
+                _ => return None,
+            };
+            Some(PlanStep {
+                text: rest.trim().to_string(),
+                status,
+            })
+        })
+        .collect();
+    PlanState {
+        steps,
+        explanation: None,
+    }
+}
+
+/// The todo list as it stood at the end of the timeline (possibly emptied).
+pub fn final_todo_state(items: &[TimelineItem]) -> Option<PlanState> {
+    items
+        .iter()
+        .rev()
+        .find(|item| item.kind == TimelineItemKind::Todo)
+        .map(|item| parse_todo_checklist(&item.detail))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tool_call(name: &str, detail: Value) -> TimelineItem {
+        TimelineItem {
+            kind: TimelineItemKind::ToolCall,
+            turn_id: None,
+            call_id: None,
+            source_line_no: None,
+            timestamp: None,
+            timestamp_ms: None,
+            summary: format!("{name}()"),
+            detail: detail.to_string(),
+        }
+    }
+
+    #[test]
+    fn parses_claude_todo_write_statuses() {
+        let args = serde_json::json!({
+            "todos": [
+                { "content": "Read code", "status": "completed", "activeForm": "Reading code" },
+                { "content": "Write fix", "status": "in_progress", "activeForm": "Writing fix" },
+                { "content": "Run tests", "status": "pending", "activeForm": "Running tests" }
+            ]
+        });
+        let plan = parse_plan_tool_call("TodoWrite", &args.to_string()).expect("plan");
+        let statuses = plan
+            .steps
+            .iter()
+            .map(|step| step.status)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            statuses,
+            vec![
+                PlanStepStatus::Completed,
+                PlanStepStatus::InProgress,
+                PlanStepStatus::Pending
+            ]
+        );
+        assert_eq!(plan.steps[1].text, "Write fix");
+        assert_eq!(plan.progress_label(), "1/3 steps");
+        assert_eq!(parse_plan_tool_call("Bash", &args.to_string()), None);
+    }
+
+    #[test]
+    fn parses_codex_update_plan_with_explanation() {
+        let args = serde_json::json!({
+            "explanation": "Starting with the parser",
+            "plan": [
+                { "step": "Parse events", "status": "completed" },
+                { "step": "Render widget", "status": "in_progress" }
+            ]
+        });
+        let plan = parse_plan_tool_call("update_plan", &args.to_string()).expect("plan");
+        assert_eq!(plan.progress_label(), "1/2 steps");
+        assert_eq!(plan.steps[1].status, PlanStepStatus::InProgress);
+        assert_eq!(
+            plan.explanation.as_deref(),
+            Some("Starting with the parser")
+        );
+
+        let mut item = tool_call("update_plan", args);
+        item.summary = "update_plan() · 1/2 steps".to_string();
+        assert_eq!(parse_plan_timeline_item(&item), Some(plan));
+    }
+
+    #[test]
+    fn parses_markdown_plan_from_exit_plan_mode() {
+        let args = serde_json::json!({
+            "plan": "## Plan\n\n1. Add parser\n2. Wire UI\n- [x] Spike\nNotes here"
+        });
+        let plan = parse_plan_tool_call("ExitPlanMode", &args.to_string()).expect("plan");
+        let texts = plan
+            .steps
+            .iter()
+            .map(|step| step.text.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(texts, vec!["Add parser", "Wire UI", "Spike"]);
+        assert_eq!(plan.completed_count(), 1);
+    }
+
+    #[test]
+    fn todo_writes_and_reads_become_checklist_items() {
+        let mut write = tool_call(
+            "TodoWrite",
+            serde_json::json!({ "todos": [
+                { "content": "Read code", "status": "completed" },
+                { "content": "Write fix", "status": "in_progress" }
+            ] }),
+        );
+        write.call_id = Some("toolu_1".to_string());
+        let mut read = tool_call("TodoRead", serde_json::json!({}));
+        read.call_id = Some("toolu_2".to_string());
+        let read_output = TimelineItem {
+            kind: TimelineItemKind::ToolOutput,
+            call_id: Some("toolu_2".to_string()),
+            detail: r#"Here is the current list: [{"content":"Write fix","status":"completed","id":"2"}]"#
+                .to_string(),
+            ..read.clone()
+        };
+
+        let items = with_todo_items(vec![write, read, read_output]);
+        let todos = items
+            .iter()
+            .filter(|item| item.kind == TimelineItemKind::Todo)
+            .collect::<Vec<_>>();
+        assert_eq!(todos.len(), 2);
+        assert_eq!(todos[0].summary, "Todos · 1/2 steps");
+        assert_eq!(todos[0].detail, "[x] Read code\n[~] Write fix");
+        assert_eq!(items[1].kind, TimelineItemKind::Todo);
+
+        let last = final_todo_state(&items).expect("todos");
+        assert_eq!(last.steps.len(), 1);
+        assert_eq!(last.steps[0].text, "Write fix");
+        assert!(last.steps[0].status.is_completed());
+    }
+
+    #[test]
+    fn latest_plan_wins_and_empty_list_clears() {
+        let first = tool_call(
+            "TodoWrite",
+            serde_json::json!({ "todos": [{ "content": "a", "status": "pending" }] }),
+        );
+        let second = tool_call(
+            "TodoWrite",
+            serde_json::json!({ "todos": [{ "content": "a", "status": "completed" }] }),
+        );
+        let bash = tool_call("Bash", serde_json::json!({ "command": "ls" }));
+        let items = vec![first, second.clone(), bash.clone()];
+        let plan = latest_plan_state(&items).expect("plan");
+        assert_eq!(plan.completed_count(), 1);
+
+        let cleared = tool_call("TodoWrite", serde_json::json!({ "todos": [] }));
+        assert_eq!(latest_plan_state(&[second, bash, cleared]), None);
+    }
+}
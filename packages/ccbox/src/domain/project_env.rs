@@ -0,0 +1,103 @@
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Environment variables set for agents spawned in a project (`project_env` in the config).
+/// An entry applies to its directory and everything below it; deeper entries override shallower
+/// ones key by key.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(transparent)]
+pub struct ProjectEnvConfig {
+    pub projects: BTreeMap<PathBuf, BTreeMap<String, String>>,
+}
+
+impl ProjectEnvConfig {
+    /// Variables for `project_path`, with `${NAME}` in values replaced by `lookup(NAME)` (empty
+    /// when unset), so `"PATH": "/opt/tools/bin:${PATH}"` extends the inherited value.
+    pub fn resolve(
+        &self,
+        project_path: &Path,
+        lookup: impl Fn(&str) -> Option<String>,
+    ) -> BTreeMap<String, String> {
+        let mut matches = self
+            .projects
+            .iter()
+            .filter(|(path, _)| project_path.starts_with(path))
+            .collect::<Vec<_>>();
+        matches.sort_by_key(|(path, _)| path.components().count());
+
+        let mut env = BTreeMap::new();
+        for (_, vars) in matches {
+            for (name, value) in vars {
+                env.insert(name.clone(), expand_env_refs(value, &lookup));
+            }
+        }
+        env
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        for (path, vars) in &self.projects {
+            if let Some(name) = vars.keys().find(|name| {
+                name.is_empty() || name.contains(['=', '\0']) || name.contains(char::is_whitespace)
+            }) {
+                return Err(format!(
+                    "project_env[{}]: invalid variable name {name:?}",
+                    path.display()
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Replaces `${NAME}` with `lookup(NAME)`; an unterminated `${` is kept as written.
+fn expand_env_refs(value: &str, lookup: impl Fn(&str) -> Option<String>) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find('}') else {
+            out.push_str(&rest[start..]);
+            return out;
+        };
+        out.push_str(&lookup(&after[..end]).unwrap_or_default());
+        rest = &after[end + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_merges_nested_projects_and_expands_references() {
+        let config: ProjectEnvConfig = serde_json::from_str(
+            r#"{
+                "/work": { "API_URL": "https://staging", "PATH": "/work/bin:${PATH}" },
+                "/work/app": { "API_URL": "https://prod", "TOKEN": "${MISSING}x" },
+                "/other": { "SKIPPED": "1" }
+            }"#,
+        )
+        .expect("parse");
+        let lookup = |name: &str| (name == "PATH").then(|| "/usr/bin".to_string());
+
+        let env = config.resolve(Path::new("/work/app/web"), lookup);
+        assert_eq!(
+            env.into_iter().collect::<Vec<_>>(),
+            [
+                ("API_URL".to_string(), "https://prod".to_string()),
+                ("PATH".to_string(), "/work/bin:/usr/bin".to_string()),
+                ("TOKEN".to_string(), "x".to_string()),
+            ]
+        );
+        assert!(config.resolve(Path::new("/home"), lookup).is_empty());
+        assert!(config.validate().is_ok());
+
+        let bad: ProjectEnvConfig =
+            serde_json::from_str(r#"{ "/work": { "A=B": "1" } }"#).expect("parse");
+        assert!(bad.validate().is_err());
+    }
+}
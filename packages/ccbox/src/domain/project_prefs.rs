@@ -0,0 +1,97 @@
+use crate::domain::{ProjectSummary, default_project_name};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Per-project display names and hidden flags, keyed by project path.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ProjectPrefs {
+    pub projects: BTreeMap<PathBuf, ProjectPref>,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ProjectPref {
+    /// Shown instead of the folder name.
+    pub name: Option<String>,
+    /// Left out of the Projects list unless hidden projects are shown.
+    pub hidden: bool,
+}
+
+impl ProjectPrefs {
+    pub fn name_for(&self, project_path: &Path) -> Option<&str> {
+        self.projects.get(project_path)?.name.as_deref()
+    }
+
+    pub fn is_hidden(&self, project_path: &Path) -> bool {
+        self.projects
+            .get(project_path)
+            .is_some_and(|pref| pref.hidden)
+    }
+
+    /// Sets the display name; an empty `name` goes back to the folder name.
+    pub fn set_name(&mut self, project_path: &Path, name: &str) {
+        let name = name.trim();
+        self.update(project_path, |pref| {
+            pref.name = (!name.is_empty()).then(|| name.to_string());
+        });
+    }
+
+    pub fn set_hidden(&mut self, project_path: &Path, hidden: bool) {
+        self.update(project_path, |pref| pref.hidden = hidden);
+    }
+
+    /// Sets each project's name and hidden flag from these prefs.
+    pub fn apply(&self, projects: &mut [ProjectSummary]) {
+        for project in projects {
+            project.name = match self.name_for(&project.project_path) {
+                Some(name) => name.to_string(),
+                None => default_project_name(&project.project_path, &project.sessions),
+            };
+            project.hidden = self.is_hidden(&project.project_path);
+        }
+    }
+
+    /// Entries left without a name or hidden flag are dropped.
+    fn update(&mut self, project_path: &Path, change: impl FnOnce(&mut ProjectPref)) {
+        let mut pref = self.projects.remove(project_path).unwrap_or_default();
+        change(&mut pref);
+        if pref != ProjectPref::default() {
+            self.projects.insert(project_path.to_path_buf(), pref);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(path: &str) -> ProjectSummary {
+        ProjectSummary {
+            name: "raw".to_string(),
+            project_path: PathBuf::from(path),
+            sessions: Vec::new(),
+            last_modified: None,
+            hidden: false,
+        }
+    }
+
+    #[test]
+    fn renames_and_hides_projects_and_drops_cleared_entries() {
+        let mut prefs = ProjectPrefs::default();
+        prefs.set_name(Path::new("/work/app"), "  Main app ");
+        prefs.set_hidden(Path::new("/tmp/scratch"), true);
+
+        let mut projects = vec![project("/work/app"), project("/tmp/scratch")];
+        prefs.apply(&mut projects);
+        assert_eq!(projects[0].name, "Main app");
+        assert!(!projects[0].hidden);
+        assert_eq!(projects[1].name, "scratch");
+        assert!(projects[1].hidden);
+
+        prefs.set_name(Path::new("/work/app"), "");
+        prefs.set_hidden(Path::new("/tmp/scratch"), false);
+        assert!(prefs.projects.is_empty());
+        prefs.apply(&mut projects);
+        assert_eq!(projects[0].name, "app");
+        assert!(!projects[1].hidden);
+    }
+}
@@ -0,0 +1,91 @@
+/// Largest single file that may be attached to a prompt.
+pub const MAX_ATTACHMENT_BYTES: u64 = 64 * 1024;
+/// Combined size of all attachments on one prompt; prompts travel as a single argument.
+pub const MAX_ATTACHMENTS_TOTAL_BYTES: u64 = 256 * 1024;
+
+/// A project file attached to a prompt with `@path`; `text` is read when the file is attached.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PromptAttachment {
+    /// Project-relative path, spelled the way it appears after `@` in the prompt.
+    pub path: String,
+    pub text: String,
+}
+
+pub fn attachments_total_bytes(attachments: &[PromptAttachment]) -> u64 {
+    attachments
+        .iter()
+        .map(|attachment| attachment.text.len() as u64)
+        .sum()
+}
+
+/// True when `prompt` still contains `@path` as a whole token.
+pub fn is_attachment_mentioned(prompt: &str, path: &str) -> bool {
+    let mention = format!("@{path}");
+    prompt.match_indices(&mention).any(|(start, _)| {
+        let before = prompt[..start].chars().next_back();
+        let after = prompt[start + mention.len()..]
+            .trim_start_matches([',', '.', ';', ':', ')', '!', '?'])
+            .chars()
+            .next();
+        before.is_none_or(char::is_whitespace) && after.is_none_or(char::is_whitespace)
+    })
+}
+
+/// Appends every attachment still mentioned in `prompt` as a fenced block labelled with its path.
+/// Attachments whose `@path` was deleted from the prompt are dropped.
+pub fn inline_attachments(prompt: &str, attachments: &[PromptAttachment]) -> String {
+    let mut out = prompt.to_string();
+    for attachment in attachments
+        .iter()
+        .filter(|attachment| is_attachment_mentioned(prompt, &attachment.path))
+    {
+        out.push_str("\n\n");
+        out.push_str(&fence_attachment(attachment));
+    }
+    out
+}
+
+/// Fences `attachment` with a run of backticks longer than any inside the file.
+fn fence_attachment(attachment: &PromptAttachment) -> String {
+    let longest_run = attachment
+        .text
+        .split(|c| c != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or(0);
+    let fence = "`".repeat(longest_run.max(2) + 1);
+    let text = attachment.text.trim_end_matches(['\r', '\n']);
+    format!("{fence}{}\n{text}\n{fence}", attachment.path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attachment(path: &str, text: &str) -> PromptAttachment {
+        PromptAttachment {
+            path: path.to_string(),
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn inline_attachments_fences_mentioned_files_and_drops_the_rest() {
+        let attachments = [
+            attachment("src/lib.rs", "pub fn a() {}\n"),
+            attachment("README.md", "Use ```sh``` blocks."),
+            attachment("docs/old.md", "gone"),
+        ];
+        let prompt = "Review @src/lib.rs, then update @README.md. Not user@docs/old.md";
+
+        assert_eq!(
+            inline_attachments(prompt, &attachments),
+            format!(
+                "{prompt}\n\n```src/lib.rs\npub fn a() {{}}\n```\n\n\
+                 ````README.md\nUse ```sh``` blocks.\n````"
+            )
+        );
+        assert!(!is_attachment_mentioned("@src/lib.rs.bak", "src/lib.rs"));
+        assert_eq!(attachments_total_bytes(&attachments), 38);
+    }
+}
@@ -20,6 +20,36 @@ pub fn build_auth_message(device_kind: &str, device_id: &str, nonce_bytes: &[u8]
     out
 }
 
+/// A client device paired with this machine through the relay in `relay.json`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RelayDevice {
+    pub device_id: String,
+    pub label: Option<String>,
+    pub created_at: String,
+    pub last_seen_at: Option<String>,
+    pub revoked: bool,
+}
+
+impl RelayDevice {
+    /// The label, or the device id when the device was paired without one.
+    pub fn display_name(&self) -> &str {
+        self.label
+            .as_deref()
+            .filter(|label| !label.trim().is_empty())
+            .unwrap_or(&self.device_id)
+    }
+}
+
+/// A pairing code issued by the relay for this machine, with the link a client opens to
+/// enter it and that link as terminal QR rows.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PairingOffer {
+    pub pairing_code: String,
+    pub expires_at: String,
+    pub web_url: String,
+    pub qr_rows: Vec<String>,
+}
+
 #[cfg(test)]
 mod tests {
     use base64::Engine as _;
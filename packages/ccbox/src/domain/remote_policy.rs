@@ -0,0 +1,268 @@
+use crate::domain::AgentEngine;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use thiserror::Error;
+
+/// What a relay client may spawn. Unset fields don't restrict.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RemoteSpawnRule {
+    /// Projects (and their subdirectories) the device may spawn in.
+    #[serde(default)]
+    pub allowed_projects: Option<Vec<PathBuf>>,
+
+    /// Engines the device may spawn (`codex`, `claude`).
+    #[serde(default)]
+    pub allowed_engines: Option<Vec<String>>,
+
+    /// Spawned processes are killed once they have run this long.
+    #[serde(default)]
+    pub max_runtime_minutes: Option<u64>,
+
+    /// Each spawn waits for a `y` at the terminal running `ccbox serve`.
+    #[serde(default)]
+    pub require_confirmation: bool,
+
+    /// Commands the device may send (see `REMOTE_COMMANDS`); read-only methods are always allowed.
+    #[serde(default)]
+    pub allowed_commands: Option<Vec<String>>,
+}
+
+/// Control methods that change the workstation and can be restricted per device.
+pub const REMOTE_COMMANDS: [&str; 6] = [
+    "tasks.create",
+    "tasks.delete",
+    "tasks.spawn",
+    "agents.spawn",
+    "processes.kill",
+    "sessions.share",
+];
+
+#[derive(Clone, Debug, Eq, PartialEq, Error)]
+pub enum RemoteSpawnDenied {
+    #[error("engine {0} is not allowed for this device")]
+    Engine(&'static str),
+
+    #[error("project {0} is not allowed for this device")]
+    Project(String),
+
+    #[error("command {0} is not allowed for this device")]
+    Command(String),
+}
+
+impl RemoteSpawnRule {
+    /// `canonicalize` resolves `..` and symlinks (`fs::canonicalize` outside tests); see
+    /// `check_project`.
+    pub fn check(
+        &self,
+        engine: AgentEngine,
+        project_path: &Path,
+        canonicalize: impl Fn(&Path) -> Option<PathBuf>,
+    ) -> Result<(), RemoteSpawnDenied> {
+        if let Some(engines) = &self.allowed_engines
+            && !engines
+                .iter()
+                .any(|allowed| allowed.eq_ignore_ascii_case(engine.label()))
+        {
+            return Err(RemoteSpawnDenied::Engine(engine.label()));
+        }
+        self.check_project(project_path, canonicalize)
+    }
+
+    /// Compares canonical paths, so `..` or a symlink can't lead out of an allowed project. A
+    /// project that doesn't canonicalize is denied when `allowed_projects` is set; an allowed
+    /// project that doesn't is skipped.
+    pub fn check_project(
+        &self,
+        project_path: &Path,
+        canonicalize: impl Fn(&Path) -> Option<PathBuf>,
+    ) -> Result<(), RemoteSpawnDenied> {
+        let Some(projects) = &self.allowed_projects else {
+            return Ok(());
+        };
+        let is_allowed = canonicalize(project_path).is_some_and(|project_path| {
+            projects
+                .iter()
+                .filter_map(|allowed| canonicalize(allowed))
+                .any(|allowed| project_path.starts_with(allowed))
+        });
+        if !is_allowed {
+            return Err(RemoteSpawnDenied::Project(
+                project_path.display().to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Read-only methods pass; `REMOTE_COMMANDS` must be listed in `allowed_commands` when set.
+    pub fn check_command(&self, method: &str) -> Result<(), RemoteSpawnDenied> {
+        if !REMOTE_COMMANDS.contains(&method) {
+            return Ok(());
+        }
+        match &self.allowed_commands {
+            Some(commands) if !commands.iter().any(|allowed| allowed == method) => {
+                Err(RemoteSpawnDenied::Command(method.to_string()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn max_runtime(&self) -> Option<Duration> {
+        self.max_runtime_minutes
+            .map(|minutes| Duration::from_secs(minutes.saturating_mul(60)))
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        for engine in self.allowed_engines.iter().flatten() {
+            if !matches!(engine.as_str(), "codex" | "claude") {
+                return Err(format!(
+                    "unknown engine `{engine}` in allowed_engines (expected codex or claude)"
+                ));
+            }
+        }
+        for command in self.allowed_commands.iter().flatten() {
+            if !REMOTE_COMMANDS.contains(&command.as_str()) {
+                return Err(format!(
+                    "unknown command `{command}` in allowed_commands (expected one of {})",
+                    REMOTE_COMMANDS.join(", ")
+                ));
+            }
+        }
+        if self.max_runtime_minutes == Some(0) {
+            return Err("max_runtime_minutes must be greater than 0".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Spawn permissions for relay clients: `default`, replaced wholesale by a `devices` entry
+/// keyed by the trusted device id.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RemoteSpawnPolicy {
+    #[serde(default)]
+    pub default: RemoteSpawnRule,
+
+    #[serde(default)]
+    pub devices: BTreeMap<String, RemoteSpawnRule>,
+}
+
+impl RemoteSpawnPolicy {
+    pub fn rule_for(&self, device_id: Option<&str>) -> &RemoteSpawnRule {
+        device_id
+            .and_then(|device_id| {
+                self.devices
+                    .iter()
+                    .find(|(id, _)| id.eq_ignore_ascii_case(device_id))
+            })
+            .map_or(&self.default, |(_, rule)| rule)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        self.default
+            .validate()
+            .map_err(|error| format!("remote_spawn.default: {error}"))?;
+        for (device_id, rule) in &self.devices {
+            rule.validate()
+                .map_err(|error| format!("remote_spawn.devices.{device_id}: {error}"))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn as_is(path: &Path) -> Option<PathBuf> {
+        Some(path.to_path_buf())
+    }
+
+    #[test]
+    fn device_rule_replaces_default_and_restricts_spawns() {
+        let policy: RemoteSpawnPolicy = serde_json::from_value(serde_json::json!({
+            "default": { "allowed_engines": ["codex"] },
+            "devices": {
+                "0B5F6A1E-0000-4000-8000-000000000001": {
+                    "allowed_projects": ["/work/app"],
+                    "max_runtime_minutes": 30,
+                    "require_confirmation": true
+                }
+            }
+        }))
+        .expect("policy");
+        assert_eq!(policy.validate(), Ok(()));
+
+        let default = policy.rule_for(None);
+        assert_eq!(
+            default.check(AgentEngine::Claude, Path::new("/tmp"), as_is),
+            Err(RemoteSpawnDenied::Engine("Claude"))
+        );
+        assert_eq!(
+            default.check(AgentEngine::Codex, Path::new("/tmp"), as_is),
+            Ok(())
+        );
+
+        let device = policy.rule_for(Some("0b5f6a1e-0000-4000-8000-000000000001"));
+        assert!(device.require_confirmation);
+        assert_eq!(device.max_runtime(), Some(Duration::from_secs(1800)));
+        assert_eq!(
+            device.check(AgentEngine::Claude, Path::new("/work/app/api"), as_is),
+            Ok(())
+        );
+        assert!(matches!(
+            device.check(AgentEngine::Codex, Path::new("/work/other"), as_is),
+            Err(RemoteSpawnDenied::Project(_))
+        ));
+        // A project that doesn't resolve is denied.
+        assert!(matches!(
+            device.check(AgentEngine::Codex, Path::new("/work/app/gone"), |_| None),
+            Err(RemoteSpawnDenied::Project(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_unknown_engines_and_zero_runtime() {
+        let policy: RemoteSpawnPolicy = serde_json::from_value(serde_json::json!({
+            "devices": { "d1": { "allowed_engines": ["gemini"] } }
+        }))
+        .expect("policy");
+        assert!(policy.validate().is_err());
+
+        let policy: RemoteSpawnPolicy = serde_json::from_value(serde_json::json!({
+            "default": { "max_runtime_minutes": 0 }
+        }))
+        .expect("policy");
+        assert!(policy.validate().is_err());
+
+        let policy: RemoteSpawnPolicy = serde_json::from_value(serde_json::json!({
+            "default": { "allowed_commands": ["shell.exec"] }
+        }))
+        .expect("policy");
+        assert!(policy.validate().is_err());
+    }
+
+    #[test]
+    fn allowed_commands_gate_mutating_methods_only() {
+        let policy: RemoteSpawnPolicy = serde_json::from_value(serde_json::json!({
+            "devices": { "phone": { "allowed_commands": ["tasks.create"] } }
+        }))
+        .expect("policy");
+        assert_eq!(policy.validate(), Ok(()));
+
+        let phone = policy.rule_for(Some("phone"));
+        assert_eq!(phone.check_command("tasks.create"), Ok(()));
+        assert_eq!(phone.check_command("tasks.list"), Ok(()));
+        assert_eq!(
+            phone.check_command("tasks.spawn"),
+            Err(RemoteSpawnDenied::Command("tasks.spawn".to_string()))
+        );
+        assert_eq!(
+            policy.rule_for(None).check_command("processes.kill"),
+            Ok(())
+        );
+        assert!(phone.check_command("sessions.share").is_err());
+    }
+}
@@ -0,0 +1,223 @@
+use serde::Deserialize;
+use std::collections::BTreeSet;
+use std::path::{Component, Path};
+
+pub const DEFAULT_REMOTE_CODEX_DIR: &str = "~/.codex/sessions";
+pub const DEFAULT_REMOTE_CLAUDE_DIR: &str = "~/.claude/projects";
+
+/// Another machine whose session logs are mirrored over SSH and listed read-only
+/// (`remote_sources` in the config).
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RemoteSourceConfig {
+    /// Prefix of the remote's project paths and name of its mirror directory.
+    pub name: String,
+    /// `ssh://[user@]host[:port]`; anything else `ssh` accepts as a host alias works too.
+    pub url: String,
+    #[serde(default)]
+    pub codex_dir: Option<String>,
+    #[serde(default)]
+    pub claude_dir: Option<String>,
+}
+
+impl RemoteSourceConfig {
+    pub fn codex_dir(&self) -> &str {
+        self.codex_dir
+            .as_deref()
+            .unwrap_or(DEFAULT_REMOTE_CODEX_DIR)
+    }
+
+    pub fn claude_dir(&self) -> &str {
+        self.claude_dir
+            .as_deref()
+            .unwrap_or(DEFAULT_REMOTE_CLAUDE_DIR)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        let is_valid_name = !self.name.is_empty()
+            && self
+                .name
+                .chars()
+                .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_');
+        if !is_valid_name {
+            return Err(format!(
+                "remote_sources: name `{}` may only use letters, digits, `-` and `_`",
+                self.name
+            ));
+        }
+        SshTarget::parse(&self.url)
+            .map(|_| ())
+            .ok_or_else(|| format!("remote_sources: `{}` has an invalid url", self.name))
+    }
+}
+
+/// Validates each source and that names are unique.
+pub fn validate_remote_sources(sources: &[RemoteSourceConfig]) -> Result<(), String> {
+    let mut names = BTreeSet::new();
+    for source in sources {
+        source.validate()?;
+        if !names.insert(source.name.as_str()) {
+            return Err(format!("remote_sources: `{}` is listed twice", source.name));
+        }
+    }
+    Ok(())
+}
+
+/// The source whose `<name>:<cwd>` project `project_path` is, if any.
+pub fn remote_source_of_project<'a>(
+    sources: &'a [RemoteSourceConfig],
+    project_path: &Path,
+) -> Option<&'a RemoteSourceConfig> {
+    let path = project_path.to_string_lossy();
+    sources.iter().find(|source| {
+        path.strip_prefix(source.name.as_str())
+            .is_some_and(|rest| rest.starts_with(':'))
+    })
+}
+
+/// Where `ssh` connects: the `[user@]host` destination and an optional port.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SshTarget {
+    pub destination: String,
+    pub port: Option<u16>,
+}
+
+impl SshTarget {
+    /// Parses `ssh://[user@]host[:port]` or a bare `[user@]host`.
+    pub fn parse(url: &str) -> Option<Self> {
+        let rest = url.trim();
+        let rest = rest.strip_prefix("ssh://").unwrap_or(rest);
+        let rest = rest.strip_suffix('/').unwrap_or(rest);
+        let (destination, port) = match rest.rsplit_once(':') {
+            Some((destination, port)) => (destination, Some(port.parse::<u16>().ok()?)),
+            None => (rest, None),
+        };
+        let host = destination.rsplit('@').next().unwrap_or_default();
+        let is_valid =
+            !host.is_empty() && !destination.starts_with('-') && !destination.contains(['/', ' ']);
+        is_valid.then(|| Self {
+            destination: destination.to_string(),
+            port,
+        })
+    }
+}
+
+/// A session log on the remote: its path relative to the sessions dir and its size.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RemoteFile {
+    pub relative_path: String,
+    pub size: u64,
+}
+
+/// Files listed by the remote listing script, per sessions dir.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RemoteListing {
+    pub codex: Vec<RemoteFile>,
+    pub claude: Vec<RemoteFile>,
+}
+
+pub const REMOTE_CODEX_MARKER: &str = "@@ccbox codex";
+pub const REMOTE_CLAUDE_MARKER: &str = "@@ccbox claude";
+
+/// Parses `wc -c` lines (`<size> ./<path>`) under the section markers. `total` lines and paths
+/// that would leave the sessions dir are skipped.
+pub fn parse_remote_listing(stdout: &str) -> RemoteListing {
+    let mut listing = RemoteListing::default();
+    let mut section: Option<&mut Vec<RemoteFile>> = None;
+    for line in stdout.lines() {
+        match line.trim() {
+            REMOTE_CODEX_MARKER => section = Some(&mut listing.codex),
+            REMOTE_CLAUDE_MARKER => section = Some(&mut listing.claude),
+            line => {
+                if let Some(files) = section.as_deref_mut()
+                    && let Some(file) = parse_listing_line(line)
+                {
+                    files.push(file);
+                }
+            }
+        }
+    }
+    listing
+}
+
+fn parse_listing_line(line: &str) -> Option<RemoteFile> {
+    let (size, path) = line.split_once(char::is_whitespace)?;
+    let size = size.parse().ok()?;
+    let relative_path = path.trim_start().strip_prefix("./")?;
+    let is_inside = Path::new(relative_path)
+        .components()
+        .all(|component| matches!(component, Component::Normal(_)));
+    (is_inside && !relative_path.is_empty()).then(|| RemoteFile {
+        relative_path: relative_path.to_string(),
+        size,
+    })
+}
+
+/// Quotes `path` for a POSIX shell, keeping a leading `~/` expandable.
+pub fn shell_quote_remote_path(path: &str) -> String {
+    match path.strip_prefix("~/") {
+        Some(rest) => format!("\"$HOME\"/{}", shell_quote(rest)),
+        None if path == "~" => "\"$HOME\"".to_string(),
+        None => shell_quote(path),
+    }
+}
+
+fn shell_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "'\\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_ssh_urls_and_remote_listings() {
+        assert_eq!(
+            SshTarget::parse("ssh://dev@build-box:2222"),
+            Some(SshTarget {
+                destination: "dev@build-box".to_string(),
+                port: Some(2222),
+            })
+        );
+        assert_eq!(
+            SshTarget::parse("build-box").map(|target| target.port),
+            Some(None)
+        );
+        assert_eq!(SshTarget::parse("ssh://-oProxyCommand=x"), None);
+        assert_eq!(SshTarget::parse("ssh://host:ssh"), None);
+
+        let listing = parse_remote_listing(
+            "@@ccbox codex\n  120 ./2025/01/02/rollout-a.jsonl\n 88 ./../escape.jsonl\n  208 total\n\
+             @@ccbox claude\n42 ./-work-app/b.jsonl\n",
+        );
+        assert_eq!(
+            listing.codex,
+            vec![RemoteFile {
+                relative_path: "2025/01/02/rollout-a.jsonl".to_string(),
+                size: 120,
+            }]
+        );
+        assert_eq!(listing.claude.len(), 1);
+        assert_eq!(listing.claude[0].relative_path, "-work-app/b.jsonl");
+
+        assert_eq!(
+            shell_quote_remote_path("~/it's/logs"),
+            "\"$HOME\"/'it'\\''s/logs'"
+        );
+
+        let source = |name: &str| RemoteSourceConfig {
+            name: name.to_string(),
+            url: "ssh://box".to_string(),
+            codex_dir: None,
+            claude_dir: None,
+        };
+        assert!(validate_remote_sources(&[source("box"), source("lab")]).is_ok());
+        assert!(validate_remote_sources(&[source("box"), source("box")]).is_err());
+        assert!(validate_remote_sources(&[source("a/b")]).is_err());
+
+        let sources = [source("box")];
+        assert!(remote_source_of_project(&sources, Path::new("box:/work/app")).is_some());
+        assert!(remote_source_of_project(&sources, Path::new("boxer:/work/app")).is_none());
+        assert!(remote_source_of_project(&sources, Path::new("/work/box:app")).is_none());
+    }
+}
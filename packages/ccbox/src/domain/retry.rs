@@ -0,0 +1,93 @@
+use std::time::Duration;
+
+const MAX_RETRY_BACKOFF: Duration = Duration::from_secs(300);
+
+/// Re-spawn policy for agent processes that fail shortly after starting.
+/// Attempts are 1-based; `max_attempts` counts the initial spawn.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub backoff: Duration,
+    pub quick_exit_window: Duration,
+}
+
+impl RetryPolicy {
+    pub const fn disabled() -> Self {
+        Self {
+            max_attempts: 1,
+            backoff: Duration::ZERO,
+            quick_exit_window: Duration::ZERO,
+        }
+    }
+
+    pub const fn standard() -> Self {
+        Self {
+            max_attempts: 3,
+            backoff: Duration::from_secs(5),
+            quick_exit_window: Duration::from_secs(60),
+        }
+    }
+
+    pub fn is_enabled(self) -> bool {
+        self.max_attempts > 1
+    }
+
+    pub fn toggle(self) -> Self {
+        if self.is_enabled() {
+            Self::disabled()
+        } else {
+            Self::standard()
+        }
+    }
+
+    pub fn label(self) -> String {
+        if self.is_enabled() {
+            format!("{}x", self.max_attempts)
+        } else {
+            "off".to_string()
+        }
+    }
+
+    /// A failed attempt is retried when it exited non-zero (or by signal) within the
+    /// quick-exit window and attempts remain. Long runs that fail are left alone.
+    pub fn should_retry(self, attempt: u32, exit_code: Option<i32>, ran_for: Duration) -> bool {
+        exit_code != Some(0) && ran_for <= self.quick_exit_window && attempt < self.max_attempts
+    }
+
+    /// Delay before spawning the attempt after `attempt`: doubles each time, capped at 5 minutes.
+    pub fn backoff_for(self, attempt: u32) -> Duration {
+        let factor = 1u32
+            .checked_shl(attempt.saturating_sub(1))
+            .unwrap_or(u32::MAX);
+        self.backoff
+            .checked_mul(factor)
+            .unwrap_or(MAX_RETRY_BACKOFF)
+            .min(MAX_RETRY_BACKOFF)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retries_quick_failures_until_attempts_run_out() {
+        let policy = RetryPolicy::standard();
+        let quick = Duration::from_secs(3);
+        assert!(policy.should_retry(1, Some(1), quick));
+        assert!(policy.should_retry(2, None, quick));
+        assert!(!policy.should_retry(3, Some(1), quick));
+        assert!(!policy.should_retry(1, Some(0), quick));
+        assert!(!policy.should_retry(1, Some(1), Duration::from_secs(61)));
+        assert!(!RetryPolicy::disabled().should_retry(1, Some(1), Duration::ZERO));
+    }
+
+    #[test]
+    fn backoff_doubles_and_is_capped() {
+        let policy = RetryPolicy::standard();
+        assert_eq!(policy.backoff_for(1), Duration::from_secs(5));
+        assert_eq!(policy.backoff_for(2), Duration::from_secs(10));
+        assert_eq!(policy.backoff_for(3), Duration::from_secs(20));
+        assert_eq!(policy.backoff_for(40), MAX_RETRY_BACKOFF);
+    }
+}
@@ -0,0 +1,233 @@
+use std::time::Duration;
+use thiserror::Error;
+
+const MS_PER_DAY: i64 = 86_400_000;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ScheduleSpec {
+    At { hour: u8, minute: u8 },
+    After(Duration),
+}
+
+impl ScheduleSpec {
+    pub fn label(self) -> String {
+        match self {
+            Self::At { hour, minute } => format!("at {hour:02}:{minute:02}"),
+            Self::After(delay) => {
+                let total_secs = delay.as_secs();
+                let (days, hours) = (total_secs / 86_400, (total_secs % 86_400) / 3_600);
+                let (minutes, seconds) = ((total_secs % 3_600) / 60, total_secs % 60);
+                let mut parts = Vec::new();
+                for (value, unit) in [(days, 'd'), (hours, 'h'), (minutes, 'm'), (seconds, 's')] {
+                    if value > 0 {
+                        parts.push(format!("{value}{unit}"));
+                    }
+                }
+                format!("in {}", parts.join(" "))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum ScheduleParseError {
+    #[error("schedule is empty (try `at 02:00` or `in 2h`)")]
+    Empty,
+
+    #[error("invalid time of day: {0} (expected HH:MM)")]
+    InvalidTime(String),
+
+    #[error("invalid delay: {0} (expected e.g. 90m, 2h, 1h30m)")]
+    InvalidDelay(String),
+}
+
+/// Parses `at 02:00`, `02:00`, `in 2h`, `in 1h 30m` or a bare delay like `45m`.
+/// A leading `run` is ignored so the phrasing from the task list (`run at 02:00`) works as-is.
+pub fn parse_schedule_spec(input: &str) -> Result<ScheduleSpec, ScheduleParseError> {
+    let normalized = input.trim().to_ascii_lowercase();
+    let mut rest = normalized.as_str();
+    if let Some(stripped) = rest.strip_prefix("run ") {
+        rest = stripped.trim_start();
+    }
+    if rest.is_empty() {
+        return Err(ScheduleParseError::Empty);
+    }
+
+    if let Some(time) = rest.strip_prefix("at ") {
+        return parse_time_of_day(time.trim());
+    }
+    if let Some(delay) = rest.strip_prefix("in ") {
+        return parse_delay(delay.trim()).map(ScheduleSpec::After);
+    }
+    if rest.contains(':') {
+        return parse_time_of_day(rest);
+    }
+    parse_delay(rest).map(ScheduleSpec::After)
+}
+
+fn parse_time_of_day(value: &str) -> Result<ScheduleSpec, ScheduleParseError> {
+    let invalid = || ScheduleParseError::InvalidTime(value.to_string());
+    let (hour, minute) = value.split_once(':').ok_or_else(invalid)?;
+    if hour.is_empty() || hour.len() > 2 || minute.len() != 2 {
+        return Err(invalid());
+    }
+    let hour = hour.parse::<u8>().map_err(|_| invalid())?;
+    let minute = minute.parse::<u8>().map_err(|_| invalid())?;
+    if hour > 23 || minute > 59 {
+        return Err(invalid());
+    }
+    Ok(ScheduleSpec::At { hour, minute })
+}
+
+fn parse_delay(value: &str) -> Result<Duration, ScheduleParseError> {
+    let invalid = || ScheduleParseError::InvalidDelay(value.to_string());
+    let compact = value
+        .chars()
+        .filter(|ch| !ch.is_whitespace())
+        .collect::<String>();
+    if compact.is_empty() {
+        return Err(invalid());
+    }
+
+    let mut total_secs = 0u64;
+    let mut digits = String::new();
+    for ch in compact.chars() {
+        if ch.is_ascii_digit() {
+            digits.push(ch);
+            continue;
+        }
+        let unit_secs = match ch {
+            's' => 1,
+            'm' => 60,
+            'h' => 3_600,
+            'd' => 86_400,
+            _ => return Err(invalid()),
+        };
+        let amount = digits.parse::<u64>().map_err(|_| invalid())?;
+        digits.clear();
+        total_secs = amount
+            .checked_mul(unit_secs)
+            .and_then(|secs| total_secs.checked_add(secs))
+            .ok_or_else(invalid)?;
+    }
+    if !digits.is_empty() || total_secs == 0 {
+        return Err(invalid());
+    }
+    Ok(Duration::from_secs(total_secs))
+}
+
+/// Resolves a schedule to an absolute due time (unix ms).
+/// `At` picks the next occurrence of the wall-clock time strictly after `now_unix_ms`,
+/// given the local UTC offset in seconds.
+pub fn resolve_schedule_due_unix_ms(
+    spec: ScheduleSpec,
+    now_unix_ms: i64,
+    utc_offset_seconds: i64,
+) -> i64 {
+    match spec {
+        ScheduleSpec::After(delay) => {
+            let delay_ms = i64::try_from(delay.as_millis()).unwrap_or(i64::MAX);
+            now_unix_ms.saturating_add(delay_ms)
+        }
+        ScheduleSpec::At { hour, minute } => {
+            let offset_ms = utc_offset_seconds.saturating_mul(1000);
+            let local_now = now_unix_ms.saturating_add(offset_ms);
+            let day_start = local_now.div_euclid(MS_PER_DAY) * MS_PER_DAY;
+            let time_of_day_ms = (i64::from(hour) * 3_600 + i64::from(minute) * 60) * 1000;
+            let mut due_local = day_start + time_of_day_ms;
+            if due_local <= local_now {
+                due_local += MS_PER_DAY;
+            }
+            due_local.saturating_sub(offset_ms)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_accepts_time_of_day_forms() {
+        let expected = ScheduleSpec::At { hour: 2, minute: 0 };
+        assert_eq!(parse_schedule_spec("at 02:00"), Ok(expected));
+        assert_eq!(parse_schedule_spec("run at 2:00"), Ok(expected));
+        assert_eq!(parse_schedule_spec(" 02:00 "), Ok(expected));
+    }
+
+    #[test]
+    fn parse_accepts_delay_forms() {
+        assert_eq!(
+            parse_schedule_spec("in 2h"),
+            Ok(ScheduleSpec::After(Duration::from_secs(7_200)))
+        );
+        assert_eq!(
+            parse_schedule_spec("run in 1h 30m"),
+            Ok(ScheduleSpec::After(Duration::from_secs(5_400)))
+        );
+        assert_eq!(
+            parse_schedule_spec("45m"),
+            Ok(ScheduleSpec::After(Duration::from_secs(2_700)))
+        );
+    }
+
+    #[test]
+    fn parse_rejects_invalid_input() {
+        assert_eq!(parse_schedule_spec("  "), Err(ScheduleParseError::Empty));
+        assert!(matches!(
+            parse_schedule_spec("at 24:00"),
+            Err(ScheduleParseError::InvalidTime(_))
+        ));
+        assert!(matches!(
+            parse_schedule_spec("in 2"),
+            Err(ScheduleParseError::InvalidDelay(_))
+        ));
+        assert!(matches!(
+            parse_schedule_spec("in 0m"),
+            Err(ScheduleParseError::InvalidDelay(_))
+        ));
+        assert!(matches!(
+            parse_schedule_spec("tomorrow"),
+            Err(ScheduleParseError::InvalidDelay(_))
+        ));
+    }
+
+    #[test]
+    fn label_round_trips_through_parser() {
+        for input in ["at 02:05", "in 1d 2h", "in 1h 30m", "in 45s"] {
+            let spec = parse_schedule_spec(input).expect("parse");
+            assert_eq!(spec.label(), input);
+        }
+    }
+
+    #[test]
+    fn resolve_delay_adds_to_now() {
+        let spec = ScheduleSpec::After(Duration::from_secs(60));
+        assert_eq!(resolve_schedule_due_unix_ms(spec, 1_000, 3_600), 61_000);
+    }
+
+    #[test]
+    fn resolve_time_of_day_picks_next_occurrence_in_local_time() {
+        // 2026-01-01T01:00:00Z is 03:00 at UTC+2.
+        let now = 1_767_229_200_000i64;
+        let offset = 2 * 3_600;
+
+        let later_today = ScheduleSpec::At { hour: 4, minute: 0 };
+        assert_eq!(
+            resolve_schedule_due_unix_ms(later_today, now, offset),
+            now + 3_600_000
+        );
+
+        let earlier_today = ScheduleSpec::At { hour: 2, minute: 0 };
+        assert_eq!(
+            resolve_schedule_due_unix_ms(earlier_today, now, offset),
+            now + 23 * 3_600_000
+        );
+
+        let exactly_now = ScheduleSpec::At { hour: 3, minute: 0 };
+        assert_eq!(
+            resolve_schedule_due_unix_ms(exactly_now, now, offset),
+            now + MS_PER_DAY
+        );
+    }
+}
@@ -0,0 +1,98 @@
+const MB: u64 = 1_000_000;
+const MINUTE_MS: u64 = 60_000;
+
+/// Optional per-session metric columns, shared by the Sessions view and `sessions --columns`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SessionColumn {
+    /// Log file size in bytes.
+    Size,
+    /// Log records (JSONL lines; messages for Gemini).
+    Items,
+    /// Milliseconds from session start to the last logged event.
+    Duration,
+    /// Running token total from the log's last token-count event.
+    Tokens,
+}
+
+/// How unusual a metric value is; the Sessions view colors `High` and `Excessive` cells.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum MetricLevel {
+    Normal,
+    High,
+    Excessive,
+}
+
+impl SessionColumn {
+    pub const ALL: [SessionColumn; 4] = [Self::Size, Self::Items, Self::Duration, Self::Tokens];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Size => "size",
+            Self::Items => "items",
+            Self::Duration => "duration",
+            Self::Tokens => "tokens",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|column| column.name() == name.trim().to_ascii_lowercase())
+    }
+
+    /// Thresholds: size 10/50 MB, items 2k/10k, duration 30 min/2 h, tokens 5M/20M.
+    pub fn level(self, value: u64) -> MetricLevel {
+        let (high, excessive) = match self {
+            Self::Size => (10 * MB, 50 * MB),
+            Self::Items => (2_000, 10_000),
+            Self::Duration => (30 * MINUTE_MS, 120 * MINUTE_MS),
+            Self::Tokens => (5_000_000, 20_000_000),
+        };
+        if value > excessive {
+            MetricLevel::Excessive
+        } else if value > high {
+            MetricLevel::High
+        } else {
+            MetricLevel::Normal
+        }
+    }
+}
+
+/// Parses a comma-separated list like `size,items`; duplicates are dropped, order is kept.
+pub fn parse_session_columns(value: &str) -> Option<Vec<SessionColumn>> {
+    let mut columns = Vec::new();
+    for name in value.split(',').filter(|name| !name.trim().is_empty()) {
+        let column = SessionColumn::from_name(name)?;
+        if !columns.contains(&column) {
+            columns.push(column);
+        }
+    }
+    (!columns.is_empty()).then_some(columns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_column_lists_and_grades_values() {
+        assert_eq!(
+            parse_session_columns("duration, SIZE,duration"),
+            Some(vec![SessionColumn::Duration, SessionColumn::Size])
+        );
+        assert_eq!(
+            parse_session_columns("tokens,size"),
+            Some(vec![SessionColumn::Tokens, SessionColumn::Size])
+        );
+        assert_eq!(parse_session_columns("size,cost"), None);
+        assert_eq!(parse_session_columns(""), None);
+
+        assert_eq!(SessionColumn::Size.level(5 * MB), MetricLevel::Normal);
+        assert_eq!(SessionColumn::Size.level(20 * MB), MetricLevel::High);
+        assert_eq!(SessionColumn::Size.level(51 * MB), MetricLevel::Excessive);
+        assert_eq!(
+            SessionColumn::Duration.level(3 * 60 * MINUTE_MS),
+            MetricLevel::Excessive
+        );
+    }
+}
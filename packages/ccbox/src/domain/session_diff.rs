@@ -0,0 +1,287 @@
+use crate::domain::{TimelineItem, TimelineItemKind};
+
+/// Above this many table cells the middle of the timelines is paired positionally instead of
+/// aligned (about 16 MB of `u32`s).
+const MAX_ALIGN_CELLS: usize = 4_000_000;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SessionDiffStatus {
+    Same,
+    Changed,
+    OnlyA,
+    OnlyB,
+}
+
+/// One aligned prompt or tool call. `a`/`b` hold the step text from each session.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SessionDiffStep {
+    pub kind: TimelineItemKind,
+    pub status: SessionDiffStatus,
+    pub a: Option<String>,
+    pub b: Option<String>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SessionDiff {
+    pub steps: Vec<SessionDiffStep>,
+    /// Last assistant message of each session.
+    pub final_a: Option<String>,
+    pub final_b: Option<String>,
+}
+
+impl SessionDiff {
+    pub fn count(&self, status: SessionDiffStatus) -> usize {
+        self.steps
+            .iter()
+            .filter(|step| step.status == status)
+            .count()
+    }
+
+    pub fn is_same_final_output(&self) -> bool {
+        self.final_a == self.final_b
+    }
+}
+
+/// Aligns the user prompts and tool calls of two sessions (longest common subsequence on the
+/// step text) and pairs up what differs in between. Steps of the same kind that sit in the
+/// same gap become `Changed`; the rest are only in one session.
+pub fn diff_session_timelines(a: &[TimelineItem], b: &[TimelineItem]) -> SessionDiff {
+    let steps_a = diff_steps(a);
+    let steps_b = diff_steps(b);
+
+    let prefix = steps_a
+        .iter()
+        .zip(&steps_b)
+        .take_while(|(left, right)| left == right)
+        .count();
+    let suffix = steps_a[prefix..]
+        .iter()
+        .rev()
+        .zip(steps_b[prefix..].iter().rev())
+        .take_while(|(left, right)| left == right)
+        .count();
+    let middle_a = &steps_a[prefix..steps_a.len() - suffix];
+    let middle_b = &steps_b[prefix..steps_b.len() - suffix];
+
+    let mut steps = Vec::new();
+    for step in &steps_a[..prefix] {
+        steps.push(same_step(step));
+    }
+    let mut gap_a = Vec::new();
+    let mut gap_b = Vec::new();
+    for pair in align(middle_a, middle_b) {
+        match pair {
+            (Some(left), Some(_)) => {
+                push_gap(&mut steps, &mut gap_a, &mut gap_b);
+                steps.push(same_step(&middle_a[left]));
+            }
+            (Some(left), None) => gap_a.push(&middle_a[left]),
+            (None, Some(right)) => gap_b.push(&middle_b[right]),
+            (None, None) => {}
+        }
+    }
+    push_gap(&mut steps, &mut gap_a, &mut gap_b);
+    for step in &steps_a[steps_a.len() - suffix..] {
+        steps.push(same_step(step));
+    }
+
+    SessionDiff {
+        steps,
+        final_a: final_output(a),
+        final_b: final_output(b),
+    }
+}
+
+fn diff_steps(items: &[TimelineItem]) -> Vec<(TimelineItemKind, String)> {
+    items
+        .iter()
+        .filter_map(|item| match item.kind {
+            TimelineItemKind::User => Some((item.kind, message_text(item))),
+            TimelineItemKind::ToolCall => {
+                let detail = item.detail.trim();
+                let text = if detail.is_empty() {
+                    item.summary.clone()
+                } else {
+                    format!("{} {detail}", item.summary)
+                };
+                Some((item.kind, text))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn message_text(item: &TimelineItem) -> String {
+    let detail = item.detail.trim();
+    if detail.is_empty() {
+        item.summary.trim().to_string()
+    } else {
+        detail.to_string()
+    }
+}
+
+fn final_output(items: &[TimelineItem]) -> Option<String> {
+    items
+        .iter()
+        .rev()
+        .find(|item| item.kind == TimelineItemKind::Assistant)
+        .map(message_text)
+}
+
+fn same_step((kind, text): &(TimelineItemKind, String)) -> SessionDiffStep {
+    SessionDiffStep {
+        kind: *kind,
+        status: SessionDiffStatus::Same,
+        a: Some(text.clone()),
+        b: Some(text.clone()),
+    }
+}
+
+/// Index pairs in order; `(Some, Some)` marks equal steps.
+fn align<T: PartialEq>(a: &[T], b: &[T]) -> Vec<(Option<usize>, Option<usize>)> {
+    let cells = (a.len() + 1).saturating_mul(b.len() + 1);
+    if cells > MAX_ALIGN_CELLS {
+        return (0..a.len())
+            .map(|index| (Some(index), None))
+            .chain((0..b.len()).map(|index| (None, Some(index))))
+            .collect();
+    }
+
+    // lengths[i][j] = LCS length of a[i..] and b[j..].
+    let width = b.len() + 1;
+    let mut lengths = vec![0u32; cells];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lengths[i * width + j] = if a[i] == b[j] {
+                lengths[(i + 1) * width + j + 1] + 1
+            } else {
+                lengths[(i + 1) * width + j].max(lengths[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut out = Vec::with_capacity(a.len() + b.len());
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            out.push((Some(i), Some(j)));
+            i += 1;
+            j += 1;
+        } else if lengths[(i + 1) * width + j] >= lengths[i * width + j + 1] {
+            out.push((Some(i), None));
+            i += 1;
+        } else {
+            out.push((None, Some(j)));
+            j += 1;
+        }
+    }
+    out.extend((i..a.len()).map(|index| (Some(index), None)));
+    out.extend((j..b.len()).map(|index| (None, Some(index))));
+    out
+}
+
+fn push_gap(
+    steps: &mut Vec<SessionDiffStep>,
+    gap_a: &mut Vec<&(TimelineItemKind, String)>,
+    gap_b: &mut Vec<&(TimelineItemKind, String)>,
+) {
+    let (mut left, mut right) = (gap_a.drain(..).peekable(), gap_b.drain(..).peekable());
+    loop {
+        let step = match (left.peek(), right.peek()) {
+            (Some((kind_a, text_a)), Some((kind_b, text_b))) if kind_a == kind_b => {
+                let step = SessionDiffStep {
+                    kind: *kind_a,
+                    status: SessionDiffStatus::Changed,
+                    a: Some(text_a.clone()),
+                    b: Some(text_b.clone()),
+                };
+                left.next();
+                right.next();
+                step
+            }
+            (Some((kind, text)), _) => {
+                let step = SessionDiffStep {
+                    kind: *kind,
+                    status: SessionDiffStatus::OnlyA,
+                    a: Some(text.clone()),
+                    b: None,
+                };
+                left.next();
+                step
+            }
+            (None, Some((kind, text))) => {
+                let step = SessionDiffStep {
+                    kind: *kind,
+                    status: SessionDiffStatus::OnlyB,
+                    a: None,
+                    b: Some(text.clone()),
+                };
+                right.next();
+                step
+            }
+            (None, None) => break,
+        };
+        steps.push(step);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(kind: TimelineItemKind, summary: &str, detail: &str) -> TimelineItem {
+        TimelineItem {
+            kind,
+            turn_id: None,
+            call_id: None,
+            source_line_no: None,
+            timestamp: None,
+            timestamp_ms: None,
+            summary: summary.to_string(),
+            detail: detail.to_string(),
+        }
+    }
+
+    #[test]
+    fn aligns_prompts_and_tool_calls_and_compares_final_output() {
+        use TimelineItemKind::{Assistant, ToolCall, ToolOutput, User};
+        let a = vec![
+            item(User, "fix the test", "fix the test"),
+            item(ToolCall, "exec_command()", r#"{"cmd":"cargo test"}"#),
+            item(ToolOutput, "ok", "ok"),
+            item(ToolCall, "read_file()", r#"{"path":"a.rs"}"#),
+            item(Assistant, "done", "done"),
+        ];
+        let b = vec![
+            item(User, "fix the test", "fix the test"),
+            item(ToolCall, "exec_command()", r#"{"cmd":"cargo test -q"}"#),
+            item(ToolCall, "read_file()", r#"{"path":"a.rs"}"#),
+            item(User, "also lint", "also lint"),
+            item(Assistant, "fixed", "fixed and linted"),
+        ];
+
+        let diff = diff_session_timelines(&a, &b);
+        let statuses = diff
+            .steps
+            .iter()
+            .map(|step| (step.kind, step.status))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            statuses,
+            vec![
+                (User, SessionDiffStatus::Same),
+                (ToolCall, SessionDiffStatus::Changed),
+                (ToolCall, SessionDiffStatus::Same),
+                (User, SessionDiffStatus::OnlyB),
+            ]
+        );
+        assert_eq!(
+            diff.steps[1].b.as_deref(),
+            Some(r#"exec_command() {"cmd":"cargo test -q"}"#)
+        );
+        assert_eq!(diff.count(SessionDiffStatus::Same), 2);
+        assert_eq!(diff.final_b.as_deref(), Some("fixed and linted"));
+        assert!(!diff.is_same_final_output());
+        assert!(diff_session_timelines(&a, &a).is_same_final_output());
+    }
+}
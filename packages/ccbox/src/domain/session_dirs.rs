@@ -0,0 +1,93 @@
+use crate::domain::expand_home;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// Session roots scanned and watched in addition to each engine's default (`extra_session_dirs`
+/// in the config), e.g. a second Codex home or a team mirror. Entries use the layout of the
+/// default they extend: a Codex `sessions` folder, a Claude `projects` folder, a Gemini root
+/// (the folder holding `tmp`). A leading `~/` is the home directory.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SessionDirsConfig {
+    #[serde(default)]
+    pub codex: Vec<PathBuf>,
+    #[serde(default)]
+    pub claude: Vec<PathBuf>,
+    #[serde(default)]
+    pub gemini: Vec<PathBuf>,
+}
+
+impl SessionDirsConfig {
+    /// The configured dirs with `~/` expanded against `home` and duplicates dropped.
+    pub fn resolve(&self, home: Option<&Path>) -> Self {
+        let resolve_all = |dirs: &[PathBuf]| {
+            let mut resolved = Vec::<PathBuf>::new();
+            for dir in dirs {
+                let dir = expand_home(dir, home);
+                if !resolved.contains(&dir) {
+                    resolved.push(dir);
+                }
+            }
+            resolved
+        };
+        Self {
+            codex: resolve_all(&self.codex),
+            claude: resolve_all(&self.claude),
+            gemini: resolve_all(&self.gemini),
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        for (engine, dirs) in [
+            ("codex", &self.codex),
+            ("claude", &self.claude),
+            ("gemini", &self.gemini),
+        ] {
+            if let Some(dir) = dirs
+                .iter()
+                .find(|dir| !dir.is_absolute() && !dir.starts_with("~"))
+            {
+                return Err(format!(
+                    "extra_session_dirs.{engine}: {} must be an absolute path or start with ~/",
+                    dir.display()
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_expands_home_and_drops_duplicates() {
+        let config = SessionDirsConfig {
+            codex: vec![
+                PathBuf::from("~/.codex-work/sessions"),
+                PathBuf::from("/home/me/.codex-work/sessions"),
+                PathBuf::from("/mnt/team/codex"),
+            ],
+            claude: Vec::new(),
+            gemini: vec![PathBuf::from("~")],
+        };
+
+        let resolved = config.resolve(Some(Path::new("/home/me")));
+        assert_eq!(
+            resolved.codex,
+            [
+                PathBuf::from("/home/me/.codex-work/sessions"),
+                PathBuf::from("/mnt/team/codex")
+            ]
+        );
+        assert_eq!(resolved.gemini, [PathBuf::from("/home/me")]);
+        assert!(config.validate().is_ok());
+
+        let relative = SessionDirsConfig {
+            claude: vec![PathBuf::from("mirror/projects")],
+            ..SessionDirsConfig::default()
+        };
+        assert!(relative.validate().is_err());
+    }
+}
@@ -0,0 +1,163 @@
+use crate::domain::{SessionEngine, SessionSummary};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Identifies a session across engines. Session ids are only unique within one engine; Claude and
+/// Codex both use UUIDs, so a bare id can name two different sessions.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct SessionKey {
+    pub engine: SessionEngine,
+    pub id: String,
+}
+
+impl SessionKey {
+    pub fn new(engine: SessionEngine, id: impl Into<String>) -> Self {
+        Self {
+            engine,
+            id: id.into(),
+        }
+    }
+
+    /// Parses the `engine:id` form; `None` when the prefix is not a known engine.
+    pub fn parse(value: &str) -> Option<Self> {
+        let (prefix, id) = value.split_once(':')?;
+        let engine = session_engine_from_slug(prefix)?;
+        (!id.is_empty()).then(|| Self::new(engine, id))
+    }
+
+    pub fn matches(&self, session: &SessionSummary) -> bool {
+        session.engine == self.engine && session.meta.id == self.id
+    }
+}
+
+impl fmt::Display for SessionKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", session_engine_slug(self.engine), self.id)
+    }
+}
+
+pub fn session_engine_slug(engine: SessionEngine) -> &'static str {
+    match engine {
+        SessionEngine::Codex => "codex",
+        SessionEngine::Claude => "claude",
+        SessionEngine::Gemini => "gemini",
+        SessionEngine::OpenCode => "opencode",
+        SessionEngine::Custom => "custom",
+    }
+}
+
+pub fn session_engine_from_slug(slug: &str) -> Option<SessionEngine> {
+    match slug {
+        "codex" => Some(SessionEngine::Codex),
+        "claude" => Some(SessionEngine::Claude),
+        "gemini" => Some(SessionEngine::Gemini),
+        "opencode" => Some(SessionEngine::OpenCode),
+        "custom" => Some(SessionEngine::Custom),
+        _ => None,
+    }
+}
+
+/// Moves entries stored under a bare session id onto their `(engine, id)` key when exactly one
+/// engine has a session with that id. Ambiguous and unmatched ids stay in `legacy`; an existing
+/// keyed entry wins over a legacy one. Returns whether anything moved.
+pub fn migrate_legacy_session_keys<V>(
+    legacy: &mut BTreeMap<String, V>,
+    keyed: &mut BTreeMap<SessionKey, V>,
+    sessions: &[SessionSummary],
+) -> bool {
+    let mut resolved = Vec::new();
+    for id in legacy.keys() {
+        let mut engines = sessions
+            .iter()
+            .filter(|session| &session.meta.id == id)
+            .map(|session| session.engine)
+            .collect::<Vec<_>>();
+        engines.sort();
+        engines.dedup();
+        if let [engine] = engines.as_slice() {
+            resolved.push(SessionKey::new(*engine, id.clone()));
+        }
+    }
+
+    let changed = !resolved.is_empty();
+    for key in resolved {
+        if let Some(value) = legacy.remove(&key.id) {
+            keyed.entry(key).or_insert(value);
+        }
+    }
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{SessionMeta, make_session_summary};
+    use std::path::PathBuf;
+
+    fn session(engine: SessionEngine, id: &str) -> SessionSummary {
+        make_session_summary(
+            SessionMeta {
+                id: id.to_string(),
+                cwd: PathBuf::from("/work/app"),
+                started_at_rfc3339: "2026-02-20T00:00:00Z".to_string(),
+                git: None,
+            },
+            PathBuf::from(format!("/logs/{id}.jsonl")),
+            "t".to_string(),
+            0,
+            None,
+            engine,
+        )
+    }
+
+    #[test]
+    fn parses_and_formats_engine_prefixed_keys() {
+        let key = SessionKey::parse("claude:0b6c-11").expect("key");
+        assert_eq!(key, SessionKey::new(SessionEngine::Claude, "0b6c-11"));
+        assert_eq!(key.to_string(), "claude:0b6c-11");
+        assert!(key.matches(&session(SessionEngine::Claude, "0b6c-11")));
+        assert!(!key.matches(&session(SessionEngine::Codex, "0b6c-11")));
+        assert_eq!(SessionKey::parse("0b6c-11"), None);
+        assert_eq!(SessionKey::parse("cursor:0b6c-11"), None);
+        assert_eq!(SessionKey::parse("codex:"), None);
+    }
+
+    #[test]
+    fn migrates_only_unambiguous_legacy_ids() {
+        let sessions = vec![
+            session(SessionEngine::Codex, "shared"),
+            session(SessionEngine::Claude, "shared"),
+            session(SessionEngine::Gemini, "solo"),
+            session(SessionEngine::Codex, "kept"),
+        ];
+        let mut legacy = BTreeMap::from([
+            ("shared".to_string(), "a"),
+            ("solo".to_string(), "b"),
+            ("kept".to_string(), "old"),
+            ("gone".to_string(), "c"),
+        ]);
+        let mut keyed = BTreeMap::from([(SessionKey::new(SessionEngine::Codex, "kept"), "new")]);
+
+        assert!(migrate_legacy_session_keys(
+            &mut legacy,
+            &mut keyed,
+            &sessions
+        ));
+        assert_eq!(
+            keyed,
+            BTreeMap::from([
+                (SessionKey::new(SessionEngine::Codex, "kept"), "new"),
+                (SessionKey::new(SessionEngine::Gemini, "solo"), "b"),
+            ])
+        );
+        assert_eq!(
+            legacy,
+            BTreeMap::from([("gone".to_string(), "c"), ("shared".to_string(), "a")])
+        );
+        assert!(!migrate_legacy_session_keys(
+            &mut legacy,
+            &mut keyed,
+            &sessions
+        ));
+    }
+}
@@ -0,0 +1,53 @@
+use std::collections::BTreeSet;
+
+/// Caps keep the index file small and per-keystroke filtering fast.
+const MAX_SEARCH_TERMS: usize = 4_000;
+const MAX_TERM_CHARS: usize = 40;
+
+/// Distinct lowercase words of the given texts (prompts and outputs), space-separated. Words
+/// shorter than two characters or longer than 40 (hashes, base64) are skipped.
+pub fn build_search_terms<'a>(texts: impl IntoIterator<Item = &'a str>) -> String {
+    let mut terms = BTreeSet::new();
+    for text in texts {
+        for word in text.split(|ch: char| !(ch.is_alphanumeric() || ch == '_')) {
+            let chars = word.chars().count();
+            if !(2..=MAX_TERM_CHARS).contains(&chars) {
+                continue;
+            }
+            terms.insert(word.to_lowercase());
+            if terms.len() >= MAX_SEARCH_TERMS {
+                return terms.into_iter().collect::<Vec<_>>().join(" ");
+            }
+        }
+    }
+    terms.into_iter().collect::<Vec<_>>().join(" ")
+}
+
+/// True when every word of `query` is part of some indexed term (so `test` finds `testing`).
+/// Expects a lowercase query.
+pub fn matches_search_terms(terms: &str, query: &str) -> bool {
+    let mut words = query
+        .split(|ch: char| !(ch.is_alphanumeric() || ch == '_'))
+        .filter(|word| !word.is_empty())
+        .peekable();
+    words.peek().is_some() && words.all(|word| terms.contains(word))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn indexes_distinct_words_and_matches_every_query_word() {
+        let terms = build_search_terms([
+            "Fix the flaky `cargo test` in CI",
+            "Done: tests pass. Hash 0123456789abcdef0123456789abcdef0123456789abcdef",
+        ]);
+        assert_eq!(terms, "cargo ci done fix flaky hash in pass test tests the");
+
+        assert!(matches_search_terms(&terms, "flaky test"));
+        assert!(matches_search_terms(&terms, "cargo-tes"));
+        assert!(!matches_search_terms(&terms, "flaky clippy"));
+        assert!(!matches_search_terms(&terms, " - "));
+    }
+}
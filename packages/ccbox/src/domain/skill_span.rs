@@ -39,6 +39,94 @@ pub struct SkillLoop {
     pub span_indices: Vec<usize>,
 }
 
+/// Totals of one skill across the sessions of a project.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SkillUsage {
+    pub name: String,
+    /// Spans of this skill, nested invocations included.
+    pub invocations: u32,
+    /// Sessions that invoked it at least once.
+    pub sessions: u32,
+    /// Runs of consecutive top-level invocations (see `detect_skill_loops`).
+    pub loops: u32,
+    pub tool_calls: u32,
+    /// Sum of span durations that had timestamps.
+    pub duration_ms: i64,
+    pub output_chars: usize,
+}
+
+/// Skill usage aggregated over many sessions; sessions are added one at a time so their
+/// timelines don't have to stay in memory.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SkillUsageReport {
+    pub sessions_scanned: usize,
+    pub sessions_with_skills: usize,
+    /// Most invoked first, then by name.
+    pub skills: Vec<SkillUsage>,
+}
+
+impl SkillUsageReport {
+    pub fn add_session(
+        &mut self,
+        spans: &[SkillSpan],
+        loops: &[SkillLoop],
+        metrics: &[SkillMetrics],
+    ) {
+        self.sessions_scanned = self.sessions_scanned.saturating_add(1);
+        if spans.is_empty() {
+            return;
+        }
+        self.sessions_with_skills = self.sessions_with_skills.saturating_add(1);
+
+        let mut seen: Vec<&str> = Vec::new();
+        for (idx, span) in spans.iter().enumerate() {
+            let usage = self.usage_mut(&span.name);
+            usage.invocations = usage.invocations.saturating_add(1);
+            if let Some(m) = metrics.get(idx) {
+                usage.tool_calls = usage.tool_calls.saturating_add(m.tool_calls);
+                usage.duration_ms = usage
+                    .duration_ms
+                    .saturating_add(m.duration_ms.unwrap_or(0).max(0));
+                usage.output_chars = usage.output_chars.saturating_add(m.output_chars);
+            }
+            if !seen.contains(&span.name.as_str()) {
+                seen.push(&span.name);
+                usage.sessions = usage.sessions.saturating_add(1);
+            }
+        }
+        for entry in loops {
+            let usage = self.usage_mut(&entry.name);
+            usage.loops = usage.loops.saturating_add(1);
+        }
+
+        self.skills.sort_by(|a, b| {
+            b.invocations
+                .cmp(&a.invocations)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+    }
+
+    pub fn total_invocations(&self) -> u32 {
+        self.skills
+            .iter()
+            .fold(0u32, |total, usage| total.saturating_add(usage.invocations))
+    }
+
+    fn usage_mut(&mut self, name: &str) -> &mut SkillUsage {
+        let pos = match self.skills.iter().position(|usage| usage.name == name) {
+            Some(pos) => pos,
+            None => {
+                self.skills.push(SkillUsage {
+                    name: name.to_string(),
+                    ..SkillUsage::default()
+                });
+                self.skills.len() - 1
+            }
+        };
+        &mut self.skills[pos]
+    }
+}
+
 pub fn extract_skill_name(detail_json: &str) -> Option<String> {
     let value: serde_json::Value = serde_json::from_str(detail_json).ok()?;
     value
@@ -405,4 +493,58 @@ mod tests {
         assert_eq!(spans.len(), 1);
         assert_eq!(spans[0].end_idx, Some(2));
     }
+
+    #[test]
+    fn usage_report_aggregates_sessions_most_invoked_first() {
+        let span = |name: &str| SkillSpan {
+            name: name.to_string(),
+            start_idx: 0,
+            end_idx: None,
+            call_id: String::new(),
+            depth: 0,
+            parent_span_idx: None,
+        };
+        let metrics = |tool_calls: u32, duration_ms: Option<i64>| SkillMetrics {
+            tool_calls,
+            tool_outputs: tool_calls,
+            duration_ms,
+            output_chars: 10,
+        };
+
+        let mut report = SkillUsageReport::default();
+        report.add_session(
+            &[span("review"), span("commit"), span("commit")],
+            &[SkillLoop {
+                name: "commit".to_string(),
+                span_indices: vec![1, 2],
+            }],
+            &[
+                metrics(1, Some(500)),
+                metrics(2, Some(1_000)),
+                metrics(3, None),
+            ],
+        );
+        report.add_session(&[], &[], &[]);
+        report.add_session(&[span("review")], &[], &[metrics(4, Some(250))]);
+
+        assert_eq!(report.sessions_scanned, 3);
+        assert_eq!(report.sessions_with_skills, 2);
+        assert_eq!(report.total_invocations(), 4);
+        let names: Vec<&str> = report.skills.iter().map(|u| u.name.as_str()).collect();
+        assert_eq!(names, vec!["commit", "review"]);
+        assert_eq!(
+            report.skills[0],
+            SkillUsage {
+                name: "commit".to_string(),
+                invocations: 2,
+                sessions: 1,
+                loops: 1,
+                tool_calls: 5,
+                duration_ms: 1_000,
+                output_chars: 20,
+            }
+        );
+        assert_eq!(report.skills[1].sessions, 2);
+        assert_eq!(report.skills[1].duration_ms, 750);
+    }
 }
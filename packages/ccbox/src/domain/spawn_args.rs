@@ -0,0 +1,117 @@
+use crate::domain::AgentEngine;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+const ENGINE_KEYS: [&str; 3] = ["codex", "claude", "opencode"];
+
+/// Extra CLI arguments per engine (`spawn_args` in the config), e.g. `--sandbox workspace-write`
+/// for Codex. A project entry replaces the global list of the engines it names and applies to its
+/// directory and everything below it; the deepest match wins.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SpawnArgsConfig {
+    #[serde(default)]
+    pub engines: BTreeMap<String, Vec<String>>,
+
+    #[serde(default)]
+    pub projects: BTreeMap<PathBuf, BTreeMap<String, Vec<String>>>,
+}
+
+impl SpawnArgsConfig {
+    pub fn resolve(&self, engine: AgentEngine, project_path: &Path) -> &[String] {
+        let engine_key = engine.label().to_ascii_lowercase();
+        self.projects
+            .iter()
+            .filter(|(path, _)| project_path.starts_with(path))
+            .filter_map(|(path, engines)| Some((path, engines.get(&engine_key)?)))
+            .max_by_key(|(path, _)| path.components().count())
+            .map(|(_, args)| args)
+            .or_else(|| self.engines.get(&engine_key))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        validate_engine_args("spawn_args.engines", &self.engines)?;
+        for (path, engines) in &self.projects {
+            validate_engine_args(&format!("spawn_args.projects[{}]", path.display()), engines)?;
+        }
+        Ok(())
+    }
+}
+
+/// Space-joined `args` for previews and process log headers; `none` when empty.
+pub fn spawn_args_label(args: &[String]) -> String {
+    if args.is_empty() {
+        return "none".to_string();
+    }
+    args.join(" ")
+}
+
+fn validate_engine_args(
+    section: &str,
+    engines: &BTreeMap<String, Vec<String>>,
+) -> Result<(), String> {
+    for (engine, args) in engines {
+        if !ENGINE_KEYS.contains(&engine.as_str()) {
+            return Err(format!(
+                "{section}: unknown engine `{engine}` (expected codex, claude or opencode)"
+            ));
+        }
+        if args.iter().any(|arg| arg.trim().is_empty()) {
+            return Err(format!("{section}.{engine}: arguments must not be empty"));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn resolve_prefers_the_deepest_project_that_names_the_engine() {
+        let config = SpawnArgsConfig {
+            engines: BTreeMap::from([
+                ("codex".to_string(), args(&["--sandbox", "workspace-write"])),
+                ("claude".to_string(), args(&["--permission-mode", "plan"])),
+            ]),
+            projects: BTreeMap::from([
+                (
+                    PathBuf::from("/work"),
+                    BTreeMap::from([("codex".to_string(), args(&["--sandbox", "read-only"]))]),
+                ),
+                (
+                    PathBuf::from("/work/app"),
+                    BTreeMap::from([("claude".to_string(), Vec::new())]),
+                ),
+            ]),
+        };
+
+        let app = Path::new("/work/app/web");
+        assert_eq!(
+            config.resolve(AgentEngine::Codex, app),
+            args(&["--sandbox", "read-only"])
+        );
+        assert!(config.resolve(AgentEngine::Claude, app).is_empty());
+        assert_eq!(
+            spawn_args_label(config.resolve(AgentEngine::Claude, Path::new("/home"))),
+            "--permission-mode plan"
+        );
+        assert_eq!(
+            spawn_args_label(config.resolve(AgentEngine::OpenCode, app)),
+            "none"
+        );
+        assert!(config.validate().is_ok());
+
+        let bad = SpawnArgsConfig {
+            engines: BTreeMap::from([("gemini".to_string(), args(&["--yolo"]))]),
+            ..SpawnArgsConfig::default()
+        };
+        assert!(bad.validate().is_err());
+    }
+}
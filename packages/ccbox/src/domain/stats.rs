@@ -1,4 +1,7 @@
-use crate::domain::{SessionMeta, TimelineItem, TimelineItemKind};
+use crate::domain::{
+    PlanState, SessionMeta, TimelineItem, TimelineItemKind, TurnContextSummary, final_todo_state,
+    tool_call_durations,
+};
 use serde_json::Value;
 use std::collections::{BTreeMap, BTreeSet};
 use time::OffsetDateTime;
@@ -10,12 +13,51 @@ pub struct ToolUsage {
     pub calls: usize,
 }
 
+/// Slow tool calls listed in session stats.
+pub const SLOWEST_TOOL_CALLS: usize = 5;
+
+/// One tool call and the time until its output arrived.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SlowToolCall {
+    pub name: String,
+    pub duration_ms: u64,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct FileChange {
     pub path: String,
     pub operations: usize,
 }
 
+/// Tokens attributed to one model. Input excludes cache reads; output includes reasoning.
+/// Categories stay zero when the log only records totals.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ModelTokenUsage {
+    /// `unknown` when the log doesn't name the model.
+    pub model: String,
+    pub input_tokens: u64,
+    pub cached_input_tokens: u64,
+    pub output_tokens: u64,
+    pub reasoning_tokens: u64,
+    pub total_tokens: u64,
+}
+
+impl ModelTokenUsage {
+    pub fn has_categories(&self) -> bool {
+        self.input_tokens > 0 || self.cached_input_tokens > 0 || self.output_tokens > 0
+    }
+
+    fn add(&mut self, other: &ModelTokenUsage) {
+        self.input_tokens = self.input_tokens.saturating_add(other.input_tokens);
+        self.cached_input_tokens = self
+            .cached_input_tokens
+            .saturating_add(other.cached_input_tokens);
+        self.output_tokens = self.output_tokens.saturating_add(other.output_tokens);
+        self.reasoning_tokens = self.reasoning_tokens.saturating_add(other.reasoning_tokens);
+        self.total_tokens = self.total_tokens.saturating_add(other.total_tokens);
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct SessionStats {
     pub start_ms: Option<i64>,
@@ -24,6 +66,10 @@ pub struct SessionStats {
 
     pub total_tokens: Option<u64>,
     pub last_tokens: Option<u64>,
+    /// Largest total first; empty when the log has no per-event token counts.
+    pub tokens_by_model: Vec<ModelTokenUsage>,
+    /// Running token total at the end of each turn (see [`cumulative_tokens_by_turn`]).
+    pub cumulative_tokens: Vec<u64>,
 
     pub tool_calls_total: usize,
     pub tool_calls_success: usize,
@@ -31,12 +77,20 @@ pub struct SessionStats {
     pub tool_calls_error: usize,
     pub tool_calls_unknown: usize,
     pub tools_used: Vec<ToolUsage>,
+    /// Up to [`SLOWEST_TOOL_CALLS`] calls with the longest call-to-output time, slowest first.
+    pub slowest_tools: Vec<SlowToolCall>,
 
     pub apply_patch_calls: usize,
     pub apply_patch_operations: usize,
     pub files_changed: Vec<FileChange>,
     pub lines_added: usize,
     pub lines_removed: usize,
+
+    /// Claude's todo list at the end of the session; `None` when it never used one.
+    pub final_todos: Option<PlanState>,
+
+    /// Times Codex compacted the context, dropping earlier turns for a summary.
+    pub compactions: usize,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -47,7 +101,11 @@ pub enum ToolOutputOutcome {
     Unknown,
 }
 
-pub fn compute_session_stats(meta: &SessionMeta, items: &[TimelineItem]) -> SessionStats {
+pub fn compute_session_stats(
+    meta: &SessionMeta,
+    items: &[TimelineItem],
+    turn_contexts: &BTreeMap<String, TurnContextSummary>,
+) -> SessionStats {
     let mut start_ms = items.iter().filter_map(|item| item.timestamp_ms).min();
     let end_ms = items.iter().filter_map(|item| item.timestamp_ms).max();
     if start_ms.is_none() {
@@ -60,6 +118,8 @@ pub fn compute_session_stats(meta: &SessionMeta, items: &[TimelineItem]) -> Sess
     };
 
     let (total_tokens, last_tokens) = compute_token_usage(items);
+    let tokens_by_model = token_usage_by_model(items, turn_contexts);
+    let cumulative_tokens = cumulative_tokens_by_turn(items);
 
     let mut tool_calls_total = 0usize;
     let mut tool_calls_success = 0usize;
@@ -67,6 +127,8 @@ pub fn compute_session_stats(meta: &SessionMeta, items: &[TimelineItem]) -> Sess
     let mut tool_calls_error = 0usize;
     let mut tool_calls_unknown = 0usize;
     let mut tools_used_counts: BTreeMap<String, usize> = BTreeMap::new();
+    let durations = tool_call_durations(items);
+    let mut slowest_tools = Vec::new();
 
     let mut apply_patch_calls = 0usize;
     let mut apply_patch_operations = 0usize;
@@ -83,6 +145,16 @@ pub fn compute_session_stats(meta: &SessionMeta, items: &[TimelineItem]) -> Sess
 
         let tool_name = tool_name_from_summary(&item.summary);
         *tools_used_counts.entry(tool_name.clone()).or_insert(0) += 1;
+        if let Some(&duration_ms) = item
+            .call_id
+            .as_deref()
+            .and_then(|call_id| durations.get(call_id))
+        {
+            slowest_tools.push(SlowToolCall {
+                name: tool_name.clone(),
+                duration_ms,
+            });
+        }
 
         if is_apply_patch_tool(&tool_name) {
             apply_patch_calls += 1;
@@ -111,6 +183,8 @@ pub fn compute_session_stats(meta: &SessionMeta, items: &[TimelineItem]) -> Sess
     }
 
     let tools_used = sort_tool_usage(tools_used_counts);
+    slowest_tools.sort_by_key(|call| std::cmp::Reverse(call.duration_ms));
+    slowest_tools.truncate(SLOWEST_TOOL_CALLS);
     let files_changed = sort_file_changes(files_changed_ops);
 
     SessionStats {
@@ -119,17 +193,25 @@ pub fn compute_session_stats(meta: &SessionMeta, items: &[TimelineItem]) -> Sess
         duration_ms,
         total_tokens,
         last_tokens,
+        tokens_by_model,
+        cumulative_tokens,
         tool_calls_total,
         tool_calls_success,
         tool_calls_invalid,
         tool_calls_error,
         tool_calls_unknown,
         tools_used,
+        slowest_tools,
         apply_patch_calls,
         apply_patch_operations,
         files_changed,
         lines_added,
         lines_removed,
+        final_todos: final_todo_state(items),
+        compactions: items
+            .iter()
+            .filter(|item| item.kind == TimelineItemKind::Compaction)
+            .count(),
     }
 }
 
@@ -161,6 +243,247 @@ fn compute_token_usage(items: &[TimelineItem]) -> (Option<u64>, Option<u64>) {
     (best_total, best_last)
 }
 
+/// Sums each token-count event by model and category. Codex events name no model, so theirs
+/// comes from the turn context; OpenCode and Gemini record it with the counts.
+pub fn token_usage_by_model(
+    items: &[TimelineItem],
+    turn_contexts: &BTreeMap<String, TurnContextSummary>,
+) -> Vec<ModelTokenUsage> {
+    let mut by_model: BTreeMap<String, ModelTokenUsage> = BTreeMap::new();
+    for TokenEvent { usage, .. } in token_events(items, turn_contexts) {
+        by_model
+            .entry(usage.model.clone())
+            .or_insert_with(|| ModelTokenUsage {
+                model: usage.model.clone(),
+                ..ModelTokenUsage::default()
+            })
+            .add(&usage);
+    }
+
+    let mut usages = by_model.into_values().collect::<Vec<_>>();
+    usages.sort_by_key(|usage| std::cmp::Reverse(usage.total_tokens));
+    usages
+}
+
+/// Usage of one model call, as recorded by a token-count timeline item.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenEvent {
+    pub usage: ModelTokenUsage,
+    /// The model's context window, when the log states it (Codex `model_context_window`).
+    pub context_window: Option<u64>,
+}
+
+/// Token-count events in log order (see `token_usage_by_model` for where models come from).
+pub fn token_events(
+    items: &[TimelineItem],
+    turn_contexts: &BTreeMap<String, TurnContextSummary>,
+) -> Vec<TokenEvent> {
+    let mut events = Vec::new();
+    let mut previous_total = 0u64;
+    for item in items {
+        if item.kind != TimelineItemKind::TokenCount {
+            continue;
+        }
+        let turn_model = item
+            .turn_id
+            .as_deref()
+            .and_then(|turn_id| turn_contexts.get(turn_id))
+            .and_then(|context| context.model.as_deref());
+        if let Some(event) = parse_token_event(&item.detail, turn_model, &mut previous_total) {
+            events.push(event);
+        }
+    }
+    events
+}
+
+/// One event's usage. Codex-shaped details (also used for OpenCode) carry `last_token_usage`;
+/// Gemini details are the message's `tokens` object, or a bare number.
+fn parse_token_event(
+    detail: &str,
+    turn_model: Option<&str>,
+    previous_total: &mut u64,
+) -> Option<TokenEvent> {
+    let parsed: Value = serde_json::from_str(detail).ok()?;
+    let context_window = parsed.get("model_context_window").and_then(|v| v.as_u64());
+    parse_token_event_usage(&parsed, turn_model, previous_total).map(|usage| TokenEvent {
+        usage,
+        context_window,
+    })
+}
+
+fn parse_token_event_usage(
+    parsed: &Value,
+    turn_model: Option<&str>,
+    previous_total: &mut u64,
+) -> Option<ModelTokenUsage> {
+    let count = |value: Option<&Value>, key: &str| {
+        value
+            .and_then(|value| value.get(key))
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0)
+    };
+
+    if let Some(number) = parsed.as_u64() {
+        return Some(ModelTokenUsage {
+            model: turn_model.unwrap_or("unknown").to_string(),
+            total_tokens: number,
+            ..ModelTokenUsage::default()
+        });
+    }
+
+    if let Some(running_total) = parsed
+        .get("total_token_usage")
+        .and_then(|v| v.get("total_tokens"))
+        .and_then(|v| v.as_u64())
+    {
+        let last = parsed.get("last_token_usage");
+        let total = last
+            .and_then(|v| v.get("total_tokens"))
+            .and_then(|v| v.as_u64())
+            .unwrap_or_else(|| running_total.saturating_sub(*previous_total));
+        *previous_total = running_total;
+
+        if let Some(opencode) = parsed.get("opencode") {
+            let tokens = opencode.get("tokens");
+            let cache = tokens.and_then(|tokens| tokens.get("cache"));
+            let reasoning = count(tokens, "reasoning");
+            let model = opencode
+                .get("modelID")
+                .and_then(|v| v.as_str())
+                .or(turn_model)
+                .unwrap_or("unknown");
+            return Some(ModelTokenUsage {
+                model: model.to_string(),
+                input_tokens: count(tokens, "input").saturating_add(count(cache, "write")),
+                cached_input_tokens: count(cache, "read"),
+                output_tokens: count(tokens, "output").saturating_add(reasoning),
+                reasoning_tokens: reasoning,
+                total_tokens: total,
+            });
+        }
+
+        let cached = count(last, "cached_input_tokens");
+        return Some(ModelTokenUsage {
+            model: turn_model.unwrap_or("unknown").to_string(),
+            input_tokens: count(last, "input_tokens").saturating_sub(cached),
+            cached_input_tokens: cached,
+            output_tokens: count(last, "output_tokens"),
+            reasoning_tokens: count(last, "reasoning_output_tokens"),
+            total_tokens: total,
+        });
+    }
+
+    let counts = Some(parsed);
+    let cached = count(counts, "cached");
+    let thoughts = count(counts, "thoughts");
+    let input = count(counts, "input");
+    let output = count(counts, "output");
+    let total = parsed
+        .get("total")
+        .and_then(|v| v.as_u64())
+        .unwrap_or_else(|| input.saturating_add(output).saturating_add(thoughts));
+    if total == 0 {
+        return None;
+    }
+    let model = parsed
+        .get("model")
+        .and_then(|v| v.as_str())
+        .or(turn_model)
+        .unwrap_or("unknown");
+    Some(ModelTokenUsage {
+        model: model.to_string(),
+        input_tokens: input.saturating_sub(cached),
+        cached_input_tokens: cached,
+        output_tokens: output.saturating_add(thoughts),
+        reasoning_tokens: thoughts,
+        total_tokens: total,
+    })
+}
+
+/// Largest running token total in the log (the last one, unless the log was rewritten).
+pub fn session_total_tokens(items: &[TimelineItem]) -> Option<u64> {
+    compute_token_usage(items).0
+}
+
+/// Tokens used per token-count event, oldest first, keeping the most recent `limit`. Each value
+/// is the event's `last_token_usage`, or the growth of the running total when that is missing.
+pub fn recent_token_activity(items: &[TimelineItem], limit: usize) -> Vec<u64> {
+    let mut activity = Vec::new();
+    let mut previous_total = 0u64;
+    for item in items {
+        if item.kind != TimelineItemKind::TokenCount {
+            continue;
+        }
+        let Some((total, last)) = parse_token_count_detail(item.detail.as_str()) else {
+            continue;
+        };
+        activity.push(last.unwrap_or_else(|| total.saturating_sub(previous_total)));
+        previous_total = total;
+    }
+    let skip = activity.len().saturating_sub(limit);
+    activity.split_off(skip)
+}
+
+/// Running token total at the end of each turn, oldest first; a turn starts at each user message,
+/// and tokens counted before the first one form a turn of their own. Empty when the log has no
+/// token counts.
+pub fn cumulative_tokens_by_turn(items: &[TimelineItem]) -> Vec<u64> {
+    let mut turns = Vec::new();
+    let mut running = 0u64;
+    let mut has_tokens = false;
+    for item in items {
+        match item.kind {
+            TimelineItemKind::User => turns.push(running),
+            TimelineItemKind::TokenCount => {
+                let Some((total, _)) = parse_token_count_detail(item.detail.as_str()) else {
+                    continue;
+                };
+                has_tokens = true;
+                running = running.max(total);
+                match turns.last_mut() {
+                    Some(turn) => *turn = running,
+                    None => turns.push(running),
+                }
+            }
+            _ => {}
+        }
+    }
+    if !has_tokens {
+        return Vec::new();
+    }
+    turns
+}
+
+/// The turn (1-based) that added the most tokens to `cumulative`, with the tokens it added.
+pub fn most_expensive_turn(cumulative: &[u64]) -> Option<(usize, u64)> {
+    let mut previous = 0u64;
+    let mut best: Option<(usize, u64)> = None;
+    for (index, total) in cumulative.iter().enumerate() {
+        let added = total.saturating_sub(previous);
+        previous = *total;
+        if added > 0 && best.is_none_or(|(_, most)| added > most) {
+            best = Some((index + 1, added));
+        }
+    }
+    best
+}
+
+/// One block character per value, scaled to the largest (`▁` for zero, `█` for the maximum).
+pub fn sparkline(values: &[u64]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let max = values.iter().copied().max().unwrap_or(0);
+    values
+        .iter()
+        .map(|value| {
+            if max == 0 {
+                return BARS[0];
+            }
+            let level = (u128::from(*value) * 7).div_ceil(u128::from(max));
+            BARS[usize::try_from(level).unwrap_or(7).min(7)]
+        })
+        .collect()
+}
+
 fn parse_token_count_detail(detail: &str) -> Option<(u64, Option<u64>)> {
     let parsed: Value = serde_json::from_str(detail).ok()?;
     let total = parsed
@@ -358,6 +681,54 @@ mod tests {
         assert_eq!(removed, 1);
     }
 
+    #[test]
+    fn lists_the_slowest_tool_calls_from_call_and_output_timestamps() {
+        let item = |kind, call_id: &str, summary: &str, timestamp_ms| TimelineItem {
+            kind,
+            turn_id: None,
+            call_id: Some(call_id.to_string()),
+            source_line_no: None,
+            timestamp: None,
+            timestamp_ms,
+            summary: summary.to_string(),
+            detail: String::new(),
+        };
+        let items = vec![
+            item(TimelineItemKind::ToolCall, "c1", "shell()", Some(1_000)),
+            item(
+                TimelineItemKind::ToolCall,
+                "c2",
+                "apply_patch()",
+                Some(1_500),
+            ),
+            item(TimelineItemKind::ToolOutput, "c2", "ok", Some(1_700)),
+            item(TimelineItemKind::ToolOutput, "c1", "ok", Some(9_000)),
+            item(TimelineItemKind::ToolCall, "c3", "read()", None),
+            item(TimelineItemKind::ToolOutput, "c3", "ok", Some(9_500)),
+        ];
+        let meta = SessionMeta {
+            id: "s".to_string(),
+            cwd: "/tmp".into(),
+            started_at_rfc3339: String::new(),
+            git: None,
+        };
+
+        let stats = compute_session_stats(&meta, &items, &BTreeMap::new());
+        assert_eq!(
+            stats.slowest_tools,
+            [
+                SlowToolCall {
+                    name: "shell".to_string(),
+                    duration_ms: 8_000,
+                },
+                SlowToolCall {
+                    name: "apply_patch".to_string(),
+                    duration_ms: 200,
+                },
+            ]
+        );
+    }
+
     #[test]
     fn classifies_exec_command_exit_codes() {
         assert_eq!(
@@ -370,6 +741,142 @@ mod tests {
         );
     }
 
+    #[test]
+    fn cumulative_tokens_grow_per_user_turn() {
+        let item = |kind, detail: &str| TimelineItem {
+            kind,
+            turn_id: None,
+            call_id: None,
+            source_line_no: None,
+            timestamp: None,
+            timestamp_ms: None,
+            summary: String::new(),
+            detail: detail.to_string(),
+        };
+        let tokens = |total: u64| {
+            item(
+                TimelineItemKind::TokenCount,
+                &format!(r#"{{"total_token_usage":{{"total_tokens":{total}}}}}"#),
+            )
+        };
+        let items = vec![
+            item(TimelineItemKind::User, "fix it"),
+            tokens(100),
+            tokens(250),
+            item(TimelineItemKind::User, "and the tests"),
+            tokens(2_250),
+            item(TimelineItemKind::User, "thanks"),
+        ];
+
+        let cumulative = cumulative_tokens_by_turn(&items);
+        assert_eq!(cumulative, vec![250, 2_250, 2_250]);
+        assert_eq!(most_expensive_turn(&cumulative), Some((2, 2_000)));
+        assert!(cumulative_tokens_by_turn(&items[..1]).is_empty());
+        assert_eq!(most_expensive_turn(&[]), None);
+    }
+
+    #[test]
+    fn token_activity_keeps_recent_usage_and_draws_a_sparkline() {
+        let token_count = |detail: &str| TimelineItem {
+            kind: TimelineItemKind::TokenCount,
+            turn_id: None,
+            call_id: None,
+            source_line_no: None,
+            timestamp: None,
+            timestamp_ms: None,
+            summary: "tokens".to_string(),
+            detail: detail.to_string(),
+        };
+        let items = vec![
+            token_count(r#"{"total_token_usage":{"total_tokens":100}}"#),
+            token_count(
+                r#"{"total_token_usage":{"total_tokens":400},"last_token_usage":{"total_tokens":300}}"#,
+            ),
+            token_count(r#"{"total_token_usage":{"total_tokens":400}}"#),
+            token_count(r#"{"total_token_usage":{"total_tokens":1000}}"#),
+        ];
+
+        assert_eq!(session_total_tokens(&items), Some(1000));
+        assert_eq!(recent_token_activity(&items, 8), vec![100, 300, 0, 600]);
+        assert_eq!(recent_token_activity(&items, 2), vec![0, 600]);
+        assert_eq!(sparkline(&[100, 300, 0, 600]), "▃▅▁█");
+        assert_eq!(sparkline(&[0, 0]), "▁▁");
+        assert_eq!(sparkline(&[]), "");
+    }
+
+    #[test]
+    fn breaks_tokens_down_by_model_and_category() {
+        let token_count = |turn_id: Option<&str>, detail: serde_json::Value| TimelineItem {
+            kind: TimelineItemKind::TokenCount,
+            turn_id: turn_id.map(str::to_string),
+            call_id: None,
+            source_line_no: None,
+            timestamp: None,
+            timestamp_ms: None,
+            summary: "tokens".to_string(),
+            detail: detail.to_string(),
+        };
+        let codex_usage = |input: u64, cached: u64, output: u64, total: u64| {
+            serde_json::json!({
+                "total_token_usage": { "total_tokens": total },
+                "last_token_usage": {
+                    "input_tokens": input,
+                    "cached_input_tokens": cached,
+                    "output_tokens": output,
+                    "reasoning_output_tokens": 5,
+                    "total_tokens": input + output,
+                },
+            })
+        };
+        let items = vec![
+            token_count(Some("t1"), codex_usage(100, 40, 20, 120)),
+            token_count(Some("t2"), codex_usage(200, 150, 30, 350)),
+            token_count(
+                None,
+                serde_json::json!({ "model": "gemini-2.5-pro", "input": 50, "cached": 10, "output": 5, "thoughts": 3, "total": 58 }),
+            ),
+        ];
+        let mut turn_contexts = BTreeMap::new();
+        for turn_id in ["t1", "t2"] {
+            turn_contexts.insert(
+                turn_id.to_string(),
+                TurnContextSummary {
+                    turn_id: turn_id.to_string(),
+                    cwd: None,
+                    model: Some("gpt-5-codex".to_string()),
+                    personality: None,
+                    approval_policy: None,
+                    sandbox_policy: None,
+                    user_instructions_len: None,
+                    developer_instructions_len: None,
+                },
+            );
+        }
+
+        let usages = token_usage_by_model(&items, &turn_contexts);
+        assert_eq!(
+            usages,
+            vec![
+                ModelTokenUsage {
+                    model: "gpt-5-codex".to_string(),
+                    input_tokens: 110,
+                    cached_input_tokens: 190,
+                    output_tokens: 50,
+                    reasoning_tokens: 10,
+                    total_tokens: 350,
+                },
+                ModelTokenUsage {
+                    model: "gemini-2.5-pro".to_string(),
+                    input_tokens: 40,
+                    cached_input_tokens: 10,
+                    output_tokens: 8,
+                    reasoning_tokens: 3,
+                    total_tokens: 58,
+                },
+            ]
+        );
+    }
+
     #[test]
     fn classifies_invalid_tool_use() {
         assert_eq!(
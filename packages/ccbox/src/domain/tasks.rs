@@ -1,7 +1,10 @@
+use crate::domain::AgentEngine;
 use std::fmt;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 
+const TEAM_TASK_ID_PREFIX: &str = "team:";
+
 #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct TaskId(String);
 
@@ -9,6 +12,16 @@ impl TaskId {
     pub fn new(value: String) -> Self {
         Self(value)
     }
+
+    /// Id of a read-only task pulled from the team registry. The prefix keeps registry ids
+    /// from colliding with local task ids.
+    pub fn team(registry_id: &str) -> Self {
+        Self(format!("{TEAM_TASK_ID_PREFIX}{registry_id}"))
+    }
+
+    pub fn is_team(&self) -> bool {
+        self.0.starts_with(TEAM_TASK_ID_PREFIX)
+    }
 }
 
 impl fmt::Display for TaskId {
@@ -34,6 +47,13 @@ pub struct TaskImage {
     pub added_at: SystemTime,
 }
 
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TaskSchedule {
+    pub task_id: TaskId,
+    pub engine: AgentEngine,
+    pub due_at: SystemTime,
+}
+
 pub fn derive_task_title(body: &str) -> String {
     const MAX_CHARS: usize = 120;
 
@@ -49,25 +69,39 @@ pub fn derive_task_title(body: &str) -> String {
 }
 
 pub fn format_task_spawn_prompt(task: &Task, images: &[TaskImage]) -> String {
-    let mut out = String::new();
-    out.push_str(&task.body);
+    let mut out = task.body.clone();
+    push_image_list(
+        &mut out,
+        images
+            .iter()
+            .map(|image| (image.ordinal, image.source_path.as_path())),
+    );
+    out
+}
 
-    if !images.is_empty() {
-        if !out.ends_with('\n') {
-            out.push('\n');
-        }
+/// Lists New Session images under the prompt the same way task images are, numbered from 1.
+pub fn append_prompt_images(prompt: String, image_paths: &[PathBuf]) -> String {
+    let mut out = prompt;
+    push_image_list(
+        &mut out,
+        (1u32..).zip(image_paths.iter().map(PathBuf::as_path)),
+    );
+    out
+}
+
+fn push_image_list<'a>(out: &mut String, images: impl Iterator<Item = (u32, &'a Path)>) {
+    let mut images = images.peekable();
+    if images.peek().is_none() {
+        return;
+    }
+    if !out.ends_with('\n') {
         out.push('\n');
-        out.push_str("Attached images:\n");
-        for image in images {
-            out.push_str(&format!(
-                "[Image {}] {}\n",
-                image.ordinal,
-                image.source_path.display()
-            ));
-        }
     }
-
-    out
+    out.push('\n');
+    out.push_str("Attached images:\n");
+    for (ordinal, path) in images {
+        out.push_str(&format!("[Image {ordinal}] {}\n", path.display()));
+    }
 }
 
 #[cfg(test)]
@@ -114,5 +148,10 @@ mod tests {
         assert!(prompt.contains("Attached images:\n"));
         assert!(prompt.contains("[Image 1] /tmp/a.png\n"));
         assert!(prompt.contains("[Image 2] /tmp/b.png\n"));
+        assert_eq!(
+            append_prompt_images("Fix it".to_string(), &[PathBuf::from("/tmp/a.png")]),
+            "Fix it\n\nAttached images:\n[Image 1] /tmp/a.png\n"
+        );
+        assert_eq!(append_prompt_images("Fix it".to_string(), &[]), "Fix it");
     }
 }
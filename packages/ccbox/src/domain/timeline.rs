@@ -1,3 +1,4 @@
+use crate::domain::parse_plan_tool_call;
 use serde_json::Value;
 use std::collections::BTreeMap;
 use time::OffsetDateTime;
@@ -12,6 +13,11 @@ pub enum TimelineItemKind {
     ToolCall,
     ToolOutput,
     TokenCount,
+    /// Claude's todo list as written by `TodoWrite` or returned by `TodoRead`; the detail is a
+    /// `[x]`/`[~]`/`[ ]` checklist.
+    Todo,
+    /// Codex compacted the context: earlier turns were replaced by a summary.
+    Compaction,
     Note,
 }
 
@@ -44,12 +50,36 @@ pub struct TurnContextSummary {
     pub developer_instructions_len: Option<usize>,
 }
 
+/// Timeline items Session Detail loads at once; older ones are fetched a page at a time as the
+/// user scrolls past the top.
+pub const TIMELINE_PAGE_ITEMS: usize = 5_000;
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct SessionTimeline {
     pub items: Vec<TimelineItem>,
     pub turn_contexts: BTreeMap<String, TurnContextSummary>,
     pub warnings: usize,
+    /// Reading stopped early on an unreadable line.
     pub truncated: bool,
+    /// Items before `items[0]`, split off by `into_newest_page` and shown a page at a time.
+    pub older: Vec<TimelineItem>,
+}
+
+impl SessionTimeline {
+    /// Keeps the newest `limit` items in `items` and moves the rest to the end of `older`.
+    pub fn into_newest_page(mut self, limit: usize) -> Self {
+        let keep_from = self.items.len().saturating_sub(limit);
+        let newest = self.items.split_off(keep_from);
+        self.older.append(&mut self.items);
+        self.items = newest;
+        self
+    }
+}
+
+/// The up to `limit` items right before index `end`.
+pub fn timeline_page(items: &[TimelineItem], end: usize, limit: usize) -> Vec<TimelineItem> {
+    let end = end.min(items.len());
+    items[end.saturating_sub(limit)..end].to_vec()
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -72,10 +102,42 @@ pub fn parse_log_value(value: &Value, current_turn_id: Option<&str>) -> ParsedLo
         "response_item" => {
             parse_response_item_value(value, current_turn_id, timestamp, timestamp_ms)
         }
+        "compacted" => parse_compacted_value(value, current_turn_id, timestamp, timestamp_ms),
         _ => ParsedLogLine::Ignore,
     }
 }
 
+/// Codex writes a `compacted` line each time it summarizes the history to free up context
+/// (the `context_compacted` event that accompanies it is not counted again).
+fn parse_compacted_value(
+    value: &Value,
+    current_turn_id: Option<&str>,
+    timestamp: Option<String>,
+    timestamp_ms: Option<i64>,
+) -> ParsedLogLine {
+    let message = value
+        .get("payload")
+        .and_then(|payload| payload.get("message"))
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .unwrap_or("");
+    let detail = if message.is_empty() {
+        "Earlier context was replaced; no summary was recorded.".to_string()
+    } else {
+        message.to_string()
+    };
+    ParsedLogLine::Item(TimelineItem {
+        kind: TimelineItemKind::Compaction,
+        turn_id: current_turn_id.map(str::to_string),
+        call_id: None,
+        source_line_no: None,
+        timestamp,
+        timestamp_ms,
+        summary: "Context compacted".to_string(),
+        detail,
+    })
+}
+
 fn parse_turn_context_value(value: &Value) -> ParsedLogLine {
     let payload = value.get("payload").unwrap_or(&Value::Null);
     let Some(turn_id) = payload.get("turn_id").and_then(|v| v.as_str()) else {
@@ -347,6 +409,12 @@ fn parse_function_call(
         }
     }
 
+    if name == "update_plan"
+        && let Some(plan) = parse_plan_tool_call(name, &detail)
+    {
+        summary = format!("update_plan() · {}", plan.progress_label());
+    }
+
     ParsedLogLine::Item(TimelineItem {
         kind: TimelineItemKind::ToolCall,
         turn_id: current_turn_id.map(str::to_string),
@@ -466,6 +534,38 @@ fn parse_custom_tool_call_output(
     })
 }
 
+/// Milliseconds from each tool call to its first output, keyed by call id. Calls or outputs
+/// without a timestamp, and outputs stamped before their call, are left out.
+pub fn tool_call_durations(items: &[TimelineItem]) -> BTreeMap<String, u64> {
+    let mut output_ms = BTreeMap::new();
+    for item in items {
+        if item.kind != TimelineItemKind::ToolOutput {
+            continue;
+        }
+        if let (Some(call_id), Some(ts)) = (item.call_id.as_deref(), item.timestamp_ms) {
+            output_ms.entry(call_id).or_insert(ts);
+        }
+    }
+
+    let mut durations = BTreeMap::new();
+    for item in items {
+        if item.kind != TimelineItemKind::ToolCall {
+            continue;
+        }
+        let (Some(call_id), Some(call_ts)) = (item.call_id.as_deref(), item.timestamp_ms) else {
+            continue;
+        };
+        if let Some(ms) = output_ms
+            .get(call_id)
+            .and_then(|out_ts| out_ts.checked_sub(call_ts))
+            .and_then(|ms| u64::try_from(ms).ok())
+        {
+            durations.entry(call_id.to_string()).or_insert(ms);
+        }
+    }
+    durations
+}
+
 fn first_non_empty_line(text: &str) -> Option<String> {
     text.lines()
         .map(|line| line.trim())
@@ -479,10 +579,48 @@ pub(crate) fn parse_rfc3339_to_unix_ms(value: &str) -> Option<i64> {
     i64::try_from(ms).ok()
 }
 
+pub(crate) fn system_time_to_rfc3339(value: std::time::SystemTime) -> Option<String> {
+    OffsetDateTime::from(value).format(&Rfc3339).ok()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn pages_load_the_newest_items_first() {
+        let item = |summary: &str| TimelineItem {
+            kind: TimelineItemKind::Note,
+            turn_id: None,
+            call_id: None,
+            source_line_no: None,
+            timestamp: None,
+            timestamp_ms: None,
+            summary: summary.to_string(),
+            detail: String::new(),
+        };
+        let summaries = |items: &[TimelineItem]| {
+            items
+                .iter()
+                .map(|item| item.summary.clone())
+                .collect::<Vec<_>>()
+        };
+        let all = ["a", "b", "c", "d", "e"].map(item).to_vec();
+        let timeline = SessionTimeline {
+            items: all.clone(),
+            turn_contexts: BTreeMap::new(),
+            warnings: 0,
+            truncated: false,
+            older: Vec::new(),
+        }
+        .into_newest_page(2);
+
+        assert_eq!(summaries(&timeline.items), ["d", "e"]);
+        assert_eq!(summaries(&timeline.older), ["a", "b", "c"]);
+        assert_eq!(summaries(&timeline_page(&all, 3, 2)), ["b", "c"]);
+        assert_eq!(summaries(&timeline_page(&all, 1, 2)), ["a"]);
+    }
+
     #[test]
     fn parses_turn_context_summary() {
         let json = serde_json::json!({
@@ -524,6 +662,30 @@ mod tests {
         assert_eq!(parsed, ParsedLogLine::Ignore);
     }
 
+    #[test]
+    fn parses_compacted_lines_as_compaction_items() {
+        let json = serde_json::json!({
+            "timestamp": "2026-02-18T22:10:00.000Z",
+            "type": "compacted",
+            "payload": { "message": "Summary: fixed the parser, tests pending." }
+        });
+        match parse_log_value(&json, Some("t3")) {
+            ParsedLogLine::Item(item) => {
+                assert_eq!(item.kind, TimelineItemKind::Compaction);
+                assert_eq!(item.turn_id.as_deref(), Some("t3"));
+                assert_eq!(item.summary, "Context compacted");
+                assert_eq!(item.detail, "Summary: fixed the parser, tests pending.");
+            }
+            other => panic!("unexpected parse result: {other:?}"),
+        }
+
+        let event = serde_json::json!({
+            "type": "event_msg",
+            "payload": { "type": "context_compacted" }
+        });
+        assert_eq!(parse_log_value(&event, None), ParsedLogLine::Ignore);
+    }
+
     #[test]
     fn parses_user_message_response_item() {
         let json = serde_json::json!({
@@ -548,6 +710,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn summarizes_update_plan_call_with_progress() {
+        let arguments = serde_json::json!({
+            "plan": [
+                { "step": "a", "status": "completed" },
+                { "step": "b", "status": "pending" }
+            ]
+        });
+        let json = serde_json::json!({
+            "timestamp": "2026-02-18T21:45:57.766Z",
+            "type": "response_item",
+            "payload": {
+                "type": "function_call",
+                "name": "update_plan",
+                "call_id": "call_1",
+                "arguments": arguments.to_string()
+            }
+        });
+        match parse_log_value(&json, Some("t1")) {
+            ParsedLogLine::Item(item) => {
+                assert_eq!(item.kind, TimelineItemKind::ToolCall);
+                assert_eq!(item.summary, "update_plan() · 1/2 steps");
+            }
+            other => panic!("unexpected parse result: {other:?}"),
+        }
+    }
+
     #[test]
     fn parses_codex_skill_injection_as_synthetic_skill_call() {
         let json = serde_json::json!({
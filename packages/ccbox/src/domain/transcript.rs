@@ -0,0 +1,96 @@
+use crate::domain::{TimelineItem, TimelineItemKind};
+
+/// The copyable text of one item: its full detail, or the summary when there is none.
+pub fn timeline_item_text(item: &TimelineItem) -> String {
+    let detail = item.detail.trim_end();
+    if detail.trim().is_empty() {
+        item.summary.clone()
+    } else {
+        detail.to_string()
+    }
+}
+
+/// Plain-text transcript of a whole session: one `[timestamp] KIND` header per item followed by
+/// its text. Token counts are left out.
+pub fn format_transcript(items: &[TimelineItem]) -> String {
+    let mut out = String::new();
+    for item in items {
+        let label = match item.kind {
+            TimelineItemKind::Turn => {
+                out.push_str(&format!("== {} ==\n\n", item.summary));
+                continue;
+            }
+            TimelineItemKind::TokenCount => continue,
+            TimelineItemKind::User => "USER",
+            TimelineItemKind::Assistant => "ASSISTANT",
+            TimelineItemKind::Thinking => "THINKING",
+            TimelineItemKind::ToolCall => "TOOL",
+            TimelineItemKind::ToolOutput => "TOOL_OUT",
+            TimelineItemKind::Todo => "TODOS",
+            TimelineItemKind::Compaction => "COMPACTED",
+            TimelineItemKind::Note => "NOTE",
+        };
+        if let Some(timestamp) = item.timestamp.as_deref() {
+            out.push_str(&format!("[{timestamp}] "));
+        }
+        out.push_str(label);
+        if item.kind == TimelineItemKind::ToolCall {
+            out.push_str(&format!(": {}", item.summary));
+            if !item.detail.trim().is_empty() {
+                out.push_str(&format!("\n{}", item.detail.trim_end()));
+            }
+        } else {
+            out.push_str(&format!("\n{}", timeline_item_text(item)));
+        }
+        out.push_str("\n\n");
+    }
+    out.truncate(out.trim_end().len());
+    out.push('\n');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(
+        kind: TimelineItemKind,
+        timestamp: Option<&str>,
+        summary: &str,
+        detail: &str,
+    ) -> TimelineItem {
+        TimelineItem {
+            kind,
+            turn_id: None,
+            call_id: None,
+            source_line_no: None,
+            timestamp: timestamp.map(str::to_string),
+            timestamp_ms: None,
+            summary: summary.to_string(),
+            detail: detail.to_string(),
+        }
+    }
+
+    #[test]
+    fn formats_items_with_headers_and_skips_token_counts() {
+        let ts = Some("2026-01-01T00:00:00Z");
+        let items = vec![
+            item(TimelineItemKind::Turn, None, "Turn 1", ""),
+            item(TimelineItemKind::User, ts, "fix it", "fix it\nplease"),
+            item(
+                TimelineItemKind::ToolCall,
+                ts,
+                "exec_command()",
+                r#"{"cmd":"ls"}"#,
+            ),
+            item(TimelineItemKind::TokenCount, ts, "tokens", "1200"),
+            item(TimelineItemKind::Assistant, None, "done", ""),
+        ];
+
+        assert_eq!(
+            format_transcript(&items),
+            "== Turn 1 ==\n\n[2026-01-01T00:00:00Z] USER\nfix it\nplease\n\n[2026-01-01T00:00:00Z] TOOL: exec_command()\n{\"cmd\":\"ls\"}\n\nASSISTANT\ndone\n"
+        );
+        assert_eq!(timeline_item_text(&items[4]), "done");
+    }
+}
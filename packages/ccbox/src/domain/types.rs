@@ -1,17 +1,19 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum AgentEngine {
     Codex,
     Claude,
+    /// Only spawned to resume an existing OpenCode session.
+    OpenCode,
 }
 
 impl AgentEngine {
     pub fn toggle(self) -> Self {
         match self {
             Self::Codex => Self::Claude,
-            Self::Claude => Self::Codex,
+            Self::Claude | Self::OpenCode => Self::Codex,
         }
     }
 
@@ -19,16 +21,19 @@ impl AgentEngine {
         match self {
             Self::Codex => "Codex",
             Self::Claude => "Claude",
+            Self::OpenCode => "OpenCode",
         }
     }
 }
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum SessionEngine {
     Codex,
     Claude,
     Gemini,
     OpenCode,
+    /// Declared in the config's `custom_engines`.
+    Custom,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -58,6 +63,32 @@ pub struct SessionMeta {
     pub id: String,
     pub cwd: PathBuf,
     pub started_at_rfc3339: String,
+    /// Codex records this in `session_meta`; other engines get the cwd's HEAD at scan time.
+    pub git: Option<SessionGit>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SessionGit {
+    pub branch: Option<String>,
+    pub commit: Option<String>,
+}
+
+impl SessionGit {
+    pub fn short_commit(&self) -> Option<&str> {
+        self.commit
+            .as_deref()
+            .map(|commit| commit.get(..7).unwrap_or(commit))
+    }
+
+    /// `branch@abc1234`, or whichever half is known.
+    pub fn label(&self) -> String {
+        match (self.branch.as_deref(), self.short_commit()) {
+            (Some(branch), Some(commit)) => format!("{branch}@{commit}"),
+            (Some(branch), None) => branch.to_string(),
+            (None, Some(commit)) => commit.to_string(),
+            (None, None) => String::new(),
+        }
+    }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -68,6 +99,32 @@ pub struct SessionSummary {
     pub title: String,
     pub file_size_bytes: u64,
     pub file_modified: Option<SystemTime>,
+    /// Session that spawned this one; set for Claude subagent (sidechain) logs.
+    pub parent_id: Option<String>,
+    /// Path of the project this session is listed under when it isn't the cwd: the git
+    /// repository root (`group_projects_by_repo`), or `<remote>:<cwd>` for a remote session.
+    /// `meta.cwd` stays the raw cwd.
+    pub project_root: Option<PathBuf>,
+    /// Name of the remote source (`remote_sources`) this session is mirrored from; such sessions
+    /// are read-only.
+    pub remote: Option<String>,
+}
+
+impl SessionSummary {
+    /// The path of the project this session is listed under.
+    pub fn project_path(&self) -> &Path {
+        self.project_root.as_deref().unwrap_or(&self.meta.cwd)
+    }
+
+    /// The raw cwd of a session listed under another project path: relative to it when inside
+    /// it (a subdirectory), else the full path (a worktree elsewhere, or a remote cwd).
+    pub fn grouped_cwd_label(&self) -> Option<String> {
+        let root = self.project_root.as_deref()?;
+        Some(match self.meta.cwd.strip_prefix(root) {
+            Ok(relative) => relative.display().to_string(),
+            Err(_) => self.meta.cwd.display().to_string(),
+        })
+    }
 }
 
 pub type ProjectIndex = Vec<ProjectSummary>;
@@ -78,6 +135,18 @@ pub struct ProjectSummary {
     pub project_path: PathBuf,
     pub sessions: Vec<SessionSummary>,
     pub last_modified: Option<SystemTime>,
+    /// Hidden from the Projects list (see `ProjectPrefs`).
+    pub hidden: bool,
+}
+
+impl ProjectSummary {
+    /// Combined size of the project's session logs.
+    pub fn total_size_bytes(&self) -> u64 {
+        self.sessions
+            .iter()
+            .map(|session| session.file_size_bytes)
+            .sum()
+    }
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
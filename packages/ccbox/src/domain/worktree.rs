@@ -0,0 +1,50 @@
+/// Branches of agent worktrees live under this prefix (`ccbox/<name>`).
+pub const WORKTREE_BRANCH_PREFIX: &str = "ccbox/";
+
+const MAX_SLUG_CHARS: usize = 40;
+
+/// Worktree (and branch) name derived from a task title or prompt: lowercase ASCII words joined
+/// by `-`, at most 40 characters.
+pub fn worktree_slug(title: &str) -> String {
+    let mut slug = String::new();
+    for word in title
+        .split(|ch: char| !ch.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+    {
+        let separator = usize::from(!slug.is_empty());
+        if slug.len() + separator + word.len() > MAX_SLUG_CHARS {
+            if slug.is_empty() {
+                slug.push_str(&word[..MAX_SLUG_CHARS].to_ascii_lowercase());
+            }
+            break;
+        }
+        if separator == 1 {
+            slug.push('-');
+        }
+        slug.push_str(&word.to_ascii_lowercase());
+    }
+    if slug.is_empty() {
+        "session".to_string()
+    } else {
+        slug
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slugs_keep_ascii_words_within_the_limit() {
+        assert_eq!(
+            worktree_slug("Fix the flaky `cargo test` in CI!"),
+            "fix-the-flaky-cargo-test-in-ci"
+        );
+        assert_eq!(
+            worktree_slug("Refactor the session index loader and add incremental updates"),
+            "refactor-the-session-index-loader-and"
+        );
+        assert_eq!(worktree_slug("修正する"), "session");
+        assert_eq!(worktree_slug(&"a".repeat(50)).len(), MAX_SLUG_CHARS);
+    }
+}
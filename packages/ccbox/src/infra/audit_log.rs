@@ -0,0 +1,115 @@
+use crate::domain::{AuditAction, AuditEntry, AuditSource};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+
+#[derive(Debug, Error)]
+pub enum AuditLogError {
+    #[error(transparent)]
+    ResolveStateDir(#[from] super::ResolveCcboxStateDirError),
+
+    #[error("failed to encode audit entry: {0}")]
+    Encode(#[from] serde_json::Error),
+
+    #[error("failed to access audit log {path}: {source}")]
+    Io {
+        path: String,
+        #[source]
+        source: io::Error,
+    },
+}
+
+pub fn audit_log_path(state_dir: &Path) -> PathBuf {
+    state_dir.join("audit.jsonl")
+}
+
+/// A local (TUI) entry stamped with the current time and OS user.
+pub fn audit_entry_now(action: AuditAction, target: impl Into<String>) -> AuditEntry {
+    AuditEntry {
+        at_rfc3339: OffsetDateTime::now_utc()
+            .format(&Rfc3339)
+            .unwrap_or_default(),
+        actor: local_user_name(),
+        source: AuditSource::Tui,
+        device_id: None,
+        action,
+        target: target.into(),
+        detail: String::new(),
+    }
+}
+
+/// Appends one JSON line; the log is never rewritten.
+pub fn append_audit_entry(state_dir: &Path, entry: &AuditEntry) -> Result<(), AuditLogError> {
+    let path = audit_log_path(state_dir);
+    let io_error = |source: io::Error| AuditLogError::Io {
+        path: path.display().to_string(),
+        source,
+    };
+    let mut line = serde_json::to_string(entry)?;
+    line.push('\n');
+    fs::create_dir_all(state_dir).map_err(io_error)?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(io_error)?;
+    file.write_all(line.as_bytes()).map_err(io_error)
+}
+
+/// Records an entry in the default state dir.
+pub fn record_audit_entry(entry: &AuditEntry) -> Result<(), AuditLogError> {
+    append_audit_entry(&super::resolve_ccbox_state_dir()?, entry)
+}
+
+/// Reads every entry, oldest first. Lines that don't parse are skipped.
+pub fn load_audit_entries(state_dir: &Path) -> Result<Vec<AuditEntry>, AuditLogError> {
+    let path = audit_log_path(state_dir);
+    let raw = match fs::read_to_string(&path) {
+        Ok(raw) => raw,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(source) => {
+            return Err(AuditLogError::Io {
+                path: path.display().to_string(),
+                source,
+            });
+        }
+    };
+    Ok(raw
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+fn local_user_name() -> String {
+    ["USER", "USERNAME", "LOGNAME"]
+        .iter()
+        .find_map(|name| std::env::var(name).ok().filter(|value| !value.is_empty()))
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn appends_entries_and_skips_corrupt_lines() -> Result<(), Box<dyn std::error::Error>> {
+        let dir = tempdir()?;
+        assert!(load_audit_entries(dir.path())?.is_empty());
+
+        let delete = audit_entry_now(AuditAction::Delete, "/logs/a.jsonl");
+        append_audit_entry(dir.path(), &delete)?;
+        fs::OpenOptions::new()
+            .append(true)
+            .open(audit_log_path(dir.path()))?
+            .write_all(b"{not json\n")?;
+        let kill = audit_entry_now(AuditAction::Kill, "p1").with_device(Some("d1"));
+        append_audit_entry(dir.path(), &kill)?;
+
+        assert_eq!(load_audit_entries(dir.path())?, vec![delete, kill]);
+        Ok(())
+    }
+}
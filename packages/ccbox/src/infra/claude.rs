@@ -2,15 +2,15 @@ use crate::domain::{
     ClaudeSessionsIndexEntry, SessionEngine, SessionMeta, SessionSummary, SessionTimeline,
     TimelineItemKind, derive_title_from_user_text, extract_claude_session_meta_hint,
     is_metadata_prompt, make_session_summary, parse_claude_sessions_index,
-    parse_claude_timeline_items, parse_claude_user_message_text,
+    parse_claude_timeline_items, parse_claude_user_message_text, system_time_to_rfc3339,
+    with_todo_items,
 };
-use crate::infra::{LastAssistantOutput, ScanWarningCount};
+use crate::infra::{LastAssistantOutput, ScanProgress, ScanWarningCount, scan_in_parallel};
 use dirs::home_dir;
 use std::collections::BTreeMap;
-use std::fs::{self, File};
-use std::io::{self, BufRead, BufReader};
+use std::fs;
+use std::io::{self, BufRead};
 use std::path::{Path, PathBuf};
-use std::time::SystemTime;
 use time::OffsetDateTime;
 use time::format_description::well_known::Rfc3339;
 
@@ -21,12 +21,10 @@ pub struct ClaudeScanOutput {
     pub notice: Option<String>,
 }
 
-const MAX_TIMELINE_ITEMS: usize = 10_000;
 const CLAUDE_PROJECT_CONTEXT_PREFIX: &str = "## Project Context";
 
 pub fn load_claude_last_assistant_output(path: &Path) -> io::Result<LastAssistantOutput> {
-    let file = File::open(path)?;
-    let reader = BufReader::new(file);
+    let reader = super::open_log_reader(path)?;
 
     let mut warnings = 0usize;
     let mut last_output: Option<String> = None;
@@ -122,8 +120,7 @@ fn extract_first_non_empty_line_after_heading(text: &str, heading: &str) -> Opti
 }
 
 pub fn load_claude_session_timeline(path: &Path) -> io::Result<SessionTimeline> {
-    let file = File::open(path)?;
-    let reader = BufReader::new(file);
+    let reader = super::open_log_reader(path)?;
 
     let mut warnings = 0usize;
     let mut truncated = false;
@@ -149,25 +146,15 @@ pub fn load_claude_session_timeline(path: &Path) -> io::Result<SessionTimeline>
             }
         };
 
-        let next_items = parse_claude_timeline_items(&value, line_no);
-        for item in next_items {
-            if items.len() >= MAX_TIMELINE_ITEMS {
-                truncated = true;
-                break;
-            }
-            items.push(item);
-        }
-
-        if truncated {
-            break;
-        }
+        items.extend(parse_claude_timeline_items(&value, line_no));
     }
 
     Ok(SessionTimeline {
-        items,
+        items: with_todo_items(items),
         turn_contexts: BTreeMap::new(),
         warnings,
         truncated,
+        older: Vec::new(),
     })
 }
 
@@ -189,7 +176,8 @@ pub fn resolve_claude_projects_dir() -> Result<PathBuf, ResolveClaudeProjectsDir
     Ok(home.join(".claude").join("projects"))
 }
 
-pub fn scan_claude_projects_dir(projects_dir: &Path) -> ClaudeScanOutput {
+/// Scans the project folders on a worker pool, one folder per job.
+pub fn scan_claude_projects_dir(projects_dir: &Path, progress: &ScanProgress) -> ClaudeScanOutput {
     if !projects_dir.exists() {
         return ClaudeScanOutput {
             sessions: Vec::new(),
@@ -214,6 +202,7 @@ pub fn scan_claude_projects_dir(projects_dir: &Path) -> ClaudeScanOutput {
 
     let mut warnings = 0usize;
     let mut sessions: Vec<SessionSummary> = Vec::new();
+    let mut project_key_dirs = Vec::new();
 
     for entry in entries {
         let entry = match entry {
@@ -232,8 +221,12 @@ pub fn scan_claude_projects_dir(projects_dir: &Path) -> ClaudeScanOutput {
             continue;
         }
 
-        let project_key_dir = entry.path();
-        let output = scan_claude_project_key_dir(&project_key_dir);
+        project_key_dirs.push(entry.path());
+    }
+
+    for output in scan_in_parallel(&project_key_dirs, progress, |dir| {
+        scan_claude_project_key_dir(dir)
+    }) {
         warnings += output.warnings;
         sessions.extend(output.sessions);
     }
@@ -251,6 +244,14 @@ struct ScanProjectKeyOutput {
 }
 
 fn scan_claude_project_key_dir(project_key_dir: &Path) -> ScanProjectKeyOutput {
+    let mut output = scan_claude_project_key_sessions(project_key_dir);
+    let subagents = scan_project_key_subagent_dirs(project_key_dir);
+    output.warnings += subagents.warnings;
+    output.sessions.extend(subagents.sessions);
+    output
+}
+
+fn scan_claude_project_key_sessions(project_key_dir: &Path) -> ScanProjectKeyOutput {
     let sessions_index_path = project_key_dir.join("sessions-index.json");
     if sessions_index_path.is_file() {
         let parsed = fs::read_to_string(&sessions_index_path)
@@ -268,6 +269,28 @@ fn scan_claude_project_key_dir(project_key_dir: &Path) -> ScanProjectKeyOutput {
     scan_project_key_from_jsonl_files(project_key_dir)
 }
 
+/// Newer Claude Code versions write subagent logs to `<session-id>/subagents/*.jsonl` next to
+/// the parent session's log; older ones put `agent-*.jsonl` in the project dir itself.
+fn scan_project_key_subagent_dirs(project_key_dir: &Path) -> ScanProjectKeyOutput {
+    let mut sessions = Vec::new();
+    let mut warnings = 0usize;
+
+    let Ok(entries) = fs::read_dir(project_key_dir) else {
+        return ScanProjectKeyOutput { sessions, warnings };
+    };
+    for entry in entries.flatten() {
+        let subagents_dir = entry.path().join("subagents");
+        if !subagents_dir.is_dir() {
+            continue;
+        }
+        let output = scan_project_key_from_jsonl_files(&subagents_dir);
+        warnings += output.warnings;
+        sessions.extend(output.sessions);
+    }
+
+    ScanProjectKeyOutput { sessions, warnings }
+}
+
 fn scan_project_key_from_sessions_index(
     project_key_dir: &Path,
     index: crate::domain::ClaudeSessionsIndex,
@@ -312,8 +335,7 @@ fn summary_from_index_entry(
         cwd: hint_cwd,
         session_id: hint_session_id,
         timestamp: hint_timestamp,
-        summary: _,
-        first_prompt: _,
+        ..
     } = scan_claude_session_file_meta_hint(&log_path);
     let cwd = entry
         .project_path
@@ -370,6 +392,7 @@ fn summary_from_index_entry(
             id: session_id,
             cwd,
             started_at_rfc3339,
+            git: None,
         },
         log_path,
         title,
@@ -403,7 +426,7 @@ fn scan_project_key_from_jsonl_files(project_key_dir: &Path) -> ScanProjectKeyOu
         };
 
         let path = entry.path();
-        if path.extension().and_then(|ext| ext.to_str()) != Some("jsonl") {
+        if !super::is_jsonl_log_path(&path) {
             continue;
         }
 
@@ -420,19 +443,12 @@ const MAX_META_SCAN_LINES: usize = 250;
 const MAX_META_SCAN_BYTES: usize = 512 * 1024;
 
 fn scan_claude_session_file_meta_hint(path: &Path) -> crate::domain::ClaudeSessionMetaHint {
-    let file = match File::open(path) {
-        Ok(file) => file,
+    let mut reader = match super::open_log_reader(path) {
+        Ok(reader) => reader,
         Err(_) => {
-            return crate::domain::ClaudeSessionMetaHint {
-                cwd: None,
-                session_id: None,
-                timestamp: None,
-                summary: None,
-                first_prompt: None,
-            };
+            return crate::domain::ClaudeSessionMetaHint::default();
         }
     };
-    let mut reader = BufReader::new(file);
 
     let mut bytes_read = 0usize;
     for _ in 0..MAX_META_SCAN_LINES {
@@ -440,13 +456,7 @@ fn scan_claude_session_file_meta_hint(path: &Path) -> crate::domain::ClaudeSessi
         let bytes = match reader.read_line(&mut line) {
             Ok(bytes) => bytes,
             Err(_) => {
-                return crate::domain::ClaudeSessionMetaHint {
-                    cwd: None,
-                    session_id: None,
-                    timestamp: None,
-                    summary: None,
-                    first_prompt: None,
-                };
+                return crate::domain::ClaudeSessionMetaHint::default();
             }
         };
         if bytes == 0 {
@@ -467,18 +477,11 @@ fn scan_claude_session_file_meta_hint(path: &Path) -> crate::domain::ClaudeSessi
         }
     }
 
-    crate::domain::ClaudeSessionMetaHint {
-        cwd: None,
-        session_id: None,
-        timestamp: None,
-        summary: None,
-        first_prompt: None,
-    }
+    crate::domain::ClaudeSessionMetaHint::default()
 }
 
 fn scan_claude_session_file_title_hint(path: &Path) -> Option<String> {
-    let file = File::open(path).ok()?;
-    let mut reader = BufReader::new(file);
+    let mut reader = super::open_log_reader(path).ok()?;
 
     let mut bytes_read = 0usize;
     for _ in 0..MAX_META_SCAN_LINES {
@@ -509,13 +512,13 @@ fn scan_claude_session_file(path: &Path) -> Result<SessionSummary, ()> {
     let file_size_bytes = metadata.len();
     let file_modified = metadata.modified().ok();
 
-    let file = File::open(path).map_err(|_| ())?;
-    let mut reader = BufReader::new(file);
+    let mut reader = super::open_log_reader(path).map_err(|_| ())?;
 
     let mut cwd: Option<PathBuf> = None;
     let mut session_id: Option<String> = None;
     let mut timestamp: Option<String> = None;
     let mut title: Option<String> = None;
+    let mut is_sidechain = false;
 
     let mut bytes_read = 0usize;
     for _ in 0..MAX_META_SCAN_LINES {
@@ -536,6 +539,7 @@ fn scan_claude_session_file(path: &Path) -> Result<SessionSummary, ()> {
         };
 
         let hint = extract_claude_session_meta_hint(&value);
+        is_sidechain |= hint.is_sidechain;
         if cwd.is_none() {
             cwd = hint.cwd;
         }
@@ -560,6 +564,12 @@ fn scan_claude_session_file(path: &Path) -> Result<SessionSummary, ()> {
     }
 
     let cwd = cwd.ok_or(())?;
+    // A subagent log shares its parent's `sessionId`; its own id is the file name (`agent-<id>`).
+    let (session_id, parent_id) = if is_sidechain {
+        (file_stem_string(path), session_id)
+    } else {
+        (session_id, None)
+    };
     let session_id = session_id
         .or_else(|| file_stem_string(path))
         .unwrap_or_else(|| "(unknown)".to_string());
@@ -572,34 +582,31 @@ fn scan_claude_session_file(path: &Path) -> Result<SessionSummary, ()> {
         });
     let display_title = title.unwrap_or_else(|| "(untitled)".to_string());
 
-    Ok(make_session_summary(
+    let mut summary = make_session_summary(
         SessionMeta {
             id: session_id,
             cwd,
             started_at_rfc3339,
+            git: None,
         },
         path.to_path_buf(),
         display_title,
         file_size_bytes,
         file_modified,
         SessionEngine::Claude,
-    ))
+    );
+    summary.parent_id = parent_id;
+    Ok(summary)
 }
 
 fn file_stem_string(path: &Path) -> Option<String> {
-    path.file_stem()
-        .and_then(|name| name.to_str())
-        .map(|name| name.to_string())
-}
-
-fn system_time_to_rfc3339(value: SystemTime) -> Option<String> {
-    let timestamp = OffsetDateTime::from(value);
-    timestamp.format(&Rfc3339).ok()
+    super::log_file_stem(path)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs::File;
     use std::io::Write;
     use tempfile::tempdir;
 
@@ -634,7 +641,7 @@ mod tests {
         );
         fs::write(&index_path, index_json).expect("write index");
 
-        let output = scan_claude_projects_dir(&projects_dir);
+        let output = scan_claude_projects_dir(&projects_dir, &ScanProgress::default());
         assert!(output.notice.is_none());
         assert_eq!(output.warnings.get(), 0);
         assert_eq!(output.sessions.len(), 1);
@@ -658,7 +665,7 @@ mod tests {
         )
         .expect("write");
 
-        let output = scan_claude_projects_dir(&projects_dir);
+        let output = scan_claude_projects_dir(&projects_dir, &ScanProgress::default());
         assert_eq!(output.warnings.get(), 0);
         assert_eq!(output.sessions.len(), 1);
         assert_eq!(output.sessions[0].meta.cwd, PathBuf::from("/tmp/p2"));
@@ -680,7 +687,7 @@ mod tests {
         )
         .expect("write");
 
-        let output = scan_claude_projects_dir(&projects_dir);
+        let output = scan_claude_projects_dir(&projects_dir, &ScanProgress::default());
         assert_eq!(output.warnings.get(), 0);
         assert_eq!(output.sessions.len(), 1);
         assert_eq!(output.sessions[0].meta.cwd, PathBuf::from("/tmp/p3"));
@@ -688,11 +695,53 @@ mod tests {
         assert_eq!(output.sessions[0].title, "Run tests");
     }
 
+    #[test]
+    fn links_subagent_logs_to_their_parent_session() {
+        let dir = tempdir().expect("tempdir");
+        let projects_dir = dir.path().join("projects");
+        let key_dir = projects_dir.join("k4");
+        let subagents_dir = key_dir.join("s4").join("subagents");
+        fs::create_dir_all(&subagents_dir).expect("create");
+
+        fs::write(
+            key_dir.join("s4.jsonl"),
+            r#"{"type":"user","cwd":"/tmp/p4","sessionId":"s4","timestamp":"2026-02-19T00:00:00Z","message":{"content":"fix the build"}}"#,
+        )
+        .expect("write parent");
+        fs::write(
+            key_dir.join("agent-old1.jsonl"),
+            r#"{"type":"user","isSidechain":true,"cwd":"/tmp/p4","sessionId":"s4","timestamp":"2026-02-19T00:00:05Z","message":{"content":"search for the error"}}"#,
+        )
+        .expect("write old subagent");
+        fs::write(
+            subagents_dir.join("agent-new1.jsonl"),
+            r#"{"type":"user","isSidechain":true,"agentId":"new1","cwd":"/tmp/p4","sessionId":"s4","timestamp":"2026-02-19T00:00:09Z","message":{"content":"run the tests"}}"#,
+        )
+        .expect("write new subagent");
+
+        let mut output = scan_claude_projects_dir(&projects_dir, &ScanProgress::default());
+        output.sessions.sort_by(|a, b| a.meta.id.cmp(&b.meta.id));
+        assert_eq!(output.warnings.get(), 0);
+        let ids = output
+            .sessions
+            .iter()
+            .map(|session| (session.meta.id.as_str(), session.parent_id.as_deref()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ids,
+            vec![
+                ("agent-new1", Some("s4")),
+                ("agent-old1", Some("s4")),
+                ("s4", None)
+            ]
+        );
+    }
+
     #[test]
     fn missing_projects_dir_returns_notice() {
         let dir = tempdir().expect("tempdir");
         let projects_dir = dir.path().join("missing");
-        let output = scan_claude_projects_dir(&projects_dir);
+        let output = scan_claude_projects_dir(&projects_dir, &ScanProgress::default());
         assert!(output.sessions.is_empty());
         assert!(output.notice.is_some());
     }
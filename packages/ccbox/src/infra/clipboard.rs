@@ -1,6 +1,7 @@
+use base64::Engine as _;
 use png::{BitDepth, ColorType, Encoder};
 use std::fs;
-use std::io;
+use std::io::{self, Write};
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 use uuid::Uuid;
@@ -27,11 +28,77 @@ pub enum PasteClipboardImageError {
 
     #[error("failed to encode png: {0}")]
     EncodePng(String),
+
+    #[error(transparent)]
+    Seal(#[from] super::EncryptionError),
+}
+
+/// Terminals cap OSC 52 payloads; larger texts only go through the OS clipboard.
+const OSC52_MAX_ENCODED_BYTES: usize = 100_000;
+
+#[derive(Debug, Error)]
+pub enum CopyClipboardError {
+    #[error("clipboard error: {0}")]
+    Clipboard(String),
+
+    #[error("text too large for the terminal clipboard and {0}")]
+    TooLarge(String),
 }
 
-pub fn paste_clipboard_image_to_task_images_dir() -> Result<PathBuf, PasteClipboardImageError> {
-    let state_dir = super::resolve_ccbox_state_dir()?;
-    let images_dir = state_dir.join("task_images");
+/// Copies text via OSC 52 (which also reaches the local clipboard over SSH, if the terminal
+/// allows it) and the OS clipboard. The OS clipboard handle is kept alive because X11/Wayland
+/// clipboards lose their content when the owning handle is dropped.
+#[derive(Default)]
+pub struct SystemClipboard {
+    clipboard: Option<arboard::Clipboard>,
+}
+
+impl SystemClipboard {
+    /// Succeeds when at least one of the two paths took the text.
+    pub fn copy_text(
+        &mut self,
+        text: &str,
+        terminal: &mut impl Write,
+    ) -> Result<(), CopyClipboardError> {
+        let sent_osc52 = match osc52_sequence(text) {
+            Some(sequence) => terminal
+                .write_all(sequence.as_bytes())
+                .and_then(|()| terminal.flush())
+                .is_ok(),
+            None => false,
+        };
+
+        let os_result = match self.clipboard.as_mut() {
+            Some(clipboard) => clipboard.set_text(text),
+            None => arboard::Clipboard::new().and_then(|mut clipboard| {
+                let result = clipboard.set_text(text);
+                self.clipboard = Some(clipboard);
+                result
+            }),
+        };
+        match os_result {
+            Ok(()) => Ok(()),
+            Err(_) if sent_osc52 => Ok(()),
+            Err(error) if osc52_sequence(text).is_none() => {
+                Err(CopyClipboardError::TooLarge(error.to_string()))
+            }
+            Err(error) => Err(CopyClipboardError::Clipboard(error.to_string())),
+        }
+    }
+}
+
+/// `ESC ] 52 ; c ; <base64> BEL`, or `None` when the payload is over the size cap.
+pub fn osc52_sequence(text: &str) -> Option<String> {
+    let encoded = base64::engine::general_purpose::STANDARD.encode(text);
+    (encoded.len() <= OSC52_MAX_ENCODED_BYTES).then(|| format!("\x1b]52;c;{encoded}\x07"))
+}
+
+/// Saves the clipboard image under the task images dir, sealed to `cipher` when one is given. The
+/// returned path is the plaintext one either way; spawns decrypt sealed images for the run.
+pub fn paste_clipboard_image_to_task_images_dir(
+    cipher: Option<&super::ArtifactCipher>,
+) -> Result<PathBuf, PasteClipboardImageError> {
+    let images_dir = super::resolve_task_images_dir()?;
     fs::create_dir_all(&images_dir).map_err(|error| PasteClipboardImageError::CreateDir {
         path: images_dir.display().to_string(),
         source: error,
@@ -39,6 +106,9 @@ pub fn paste_clipboard_image_to_task_images_dir() -> Result<PathBuf, PasteClipbo
 
     let file_path = images_dir.join(format!("clipboard-{}.png", Uuid::new_v4()));
     write_clipboard_png(&file_path)?;
+    if let Some(cipher) = cipher {
+        cipher.seal_file(&file_path)?;
+    }
     Ok(file_path)
 }
 
@@ -79,3 +149,17 @@ fn write_clipboard_png(dest: &Path) -> Result<(), PasteClipboardImageError> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn osc52_sequence_encodes_text_and_skips_oversized_payloads() {
+        assert_eq!(
+            osc52_sequence("hi ✓").as_deref(),
+            Some("\x1b]52;c;aGkg4pyT\x07")
+        );
+        assert_eq!(osc52_sequence(&"x".repeat(80_000)), None);
+    }
+}
@@ -99,9 +99,15 @@ pub fn fork_codex_session_log_at_cut(
     fs::create_dir_all(&day_dir).map_err(ForkSessionError::CreateForkDir)?;
 
     let log_path = day_dir.join(format!("rollout-{file_stamp}-{session_id}.jsonl"));
-    let fork_file = OpenOptions::new()
-        .create_new(true)
-        .write(true)
+    // Codex resumes from and appends to the fork, so it can't be sealed; it is owner-only instead.
+    let mut options = OpenOptions::new();
+    options.create_new(true).write(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let fork_file = options
         .open(&log_path)
         .map_err(ForkSessionError::CreateForkFile)?;
 
@@ -241,29 +247,7 @@ struct UnixLocalParts {
 
 #[cfg(unix)]
 fn unix_local_parts(now_utc: OffsetDateTime) -> Option<UnixLocalParts> {
-    use std::mem::MaybeUninit;
-
-    #[cfg(target_pointer_width = "64")]
-    let seconds: libc::time_t = now_utc.unix_timestamp();
-
-    #[cfg(target_pointer_width = "32")]
-    let seconds: libc::time_t = {
-        let unix_seconds = now_utc.unix_timestamp();
-        if unix_seconds > i32::MAX as i64 {
-            i32::MAX as libc::time_t
-        } else if unix_seconds < i32::MIN as i64 {
-            i32::MIN as libc::time_t
-        } else {
-            unix_seconds as libc::time_t
-        }
-    };
-
-    let mut tm = MaybeUninit::<libc::tm>::uninit();
-    let tm_ptr = unsafe { libc::localtime_r(&seconds, tm.as_mut_ptr()) };
-    if tm_ptr.is_null() {
-        return None;
-    }
-    let tm = unsafe { tm.assume_init() };
+    let tm = super::local_time::local_tm(now_utc.unix_timestamp())?;
 
     let year = tm.tm_year + 1900;
     let month = (tm.tm_mon + 1).try_into().ok()?;
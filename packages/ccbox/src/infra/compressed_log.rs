@@ -0,0 +1,168 @@
+use flate2::read::MultiGzDecoder;
+use ruzstd::decoding::StreamingDecoder;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::SystemTime;
+
+/// The compressed log last read by [`read_decompressed_log`].
+static DECOMPRESSED_LOG: Mutex<Option<(DecompressedLogKey, Arc<[u8]>)>> = Mutex::new(None);
+
+/// Identifies one version of an archive: a rewritten file has a new mtime or size.
+#[derive(Debug, Eq, PartialEq)]
+struct DecompressedLogKey {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+/// Compression of an archived session log, from its extension.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LogCompression {
+    Gzip,
+    Zstd,
+}
+
+impl LogCompression {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("gz") => Some(Self::Gzip),
+            Some("zst") => Some(Self::Zstd),
+            _ => None,
+        }
+    }
+}
+
+pub fn is_compressed_log(path: &Path) -> bool {
+    LogCompression::from_path(path).is_some()
+}
+
+/// Whether `path` is a JSONL session log: `.jsonl`, `.jsonl.gz` or `.jsonl.zst`.
+pub fn is_jsonl_log_path(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+        return false;
+    };
+    name.ends_with(".jsonl") || name.ends_with(".jsonl.gz") || name.ends_with(".jsonl.zst")
+}
+
+/// The file name without `.jsonl` (and the compression extension), e.g. the session id of a
+/// Claude log.
+pub fn log_file_stem(path: &Path) -> Option<String> {
+    let stem = if is_compressed_log(path) {
+        Path::new(path.file_stem()?).file_stem()?
+    } else {
+        path.file_stem()?
+    };
+    Some(stem.to_string_lossy().to_string())
+}
+
+/// Opens a session log for reading, decompressing `.gz` and `.zst` files on the fly.
+pub fn open_log_reader(path: &Path) -> io::Result<Box<dyn BufRead>> {
+    let file = File::open(path)?;
+    Ok(match LogCompression::from_path(path) {
+        None => Box::new(BufReader::new(file)),
+        Some(LogCompression::Gzip) => Box::new(BufReader::new(MultiGzDecoder::new(file))),
+        Some(LogCompression::Zstd) => {
+            let decoder = StreamingDecoder::new(file).map_err(io::Error::other)?;
+            Box::new(BufReader::new(decoder))
+        }
+    })
+}
+
+/// Reads a whole session log, decompressed.
+pub fn read_log_bytes(path: &Path) -> io::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    open_log_reader(path)?.read_to_end(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// Reads a whole compressed log, decompressed. Tail and offset reads poll the same archive over and
+/// over, so the last one is kept and reused while its path, mtime and size are unchanged.
+pub fn read_decompressed_log(path: &Path) -> io::Result<Arc<[u8]>> {
+    let metadata = fs::metadata(path)?;
+    let key = DecompressedLogKey {
+        path: path.to_path_buf(),
+        modified: metadata.modified().ok(),
+        len: metadata.len(),
+    };
+    if let Some((cached, bytes)) = &*DECOMPRESSED_LOG
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        && *cached == key
+    {
+        return Ok(Arc::clone(bytes));
+    }
+    let bytes: Arc<[u8]> = read_log_bytes(path)?.into();
+    *DECOMPRESSED_LOG
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner) = Some((key, Arc::clone(&bytes)));
+    Ok(bytes)
+}
+
+pub fn read_log_to_string(path: &Path) -> io::Result<String> {
+    String::from_utf8(read_log_bytes(path)?)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
+    use std::fs;
+    use std::io::Write;
+    use tempfile::tempdir;
+
+    #[test]
+    fn reads_gzip_and_zstd_logs_transparently() -> io::Result<()> {
+        let dir = tempdir()?;
+        let text = "{\"a\":1}\n{\"b\":2}\n";
+
+        let gz = dir.path().join("s1.jsonl.gz");
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(text.as_bytes())?;
+        fs::write(&gz, encoder.finish()?)?;
+
+        let zst = dir.path().join("s2.jsonl.zst");
+        fs::write(
+            &zst,
+            ruzstd::encoding::compress_to_vec(
+                text.as_bytes(),
+                ruzstd::encoding::CompressionLevel::Fastest,
+            ),
+        )?;
+
+        let plain = dir.path().join("s3.jsonl");
+        fs::write(&plain, text)?;
+
+        for path in [&gz, &zst, &plain] {
+            assert!(is_jsonl_log_path(path));
+            assert_eq!(read_log_to_string(path)?, text);
+            assert_eq!(open_log_reader(path)?.lines().count(), 2);
+        }
+        assert_eq!(log_file_stem(&gz).as_deref(), Some("s1"));
+        assert_eq!(log_file_stem(&plain).as_deref(), Some("s3"));
+        assert!(!is_jsonl_log_path(Path::new("/tmp/notes.gz")));
+        Ok(())
+    }
+
+    #[test]
+    fn decompressed_log_is_reused_until_the_archive_changes() -> io::Result<()> {
+        let dir = tempdir()?;
+        let gz = dir.path().join("s1.jsonl.gz");
+        let write_gz = |text: &str| -> io::Result<()> {
+            let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(text.as_bytes())?;
+            fs::write(&gz, encoder.finish()?)
+        };
+
+        write_gz("{\"a\":1}\n")?;
+        let first = read_decompressed_log(&gz)?;
+        assert!(Arc::ptr_eq(&first, &read_decompressed_log(&gz)?));
+
+        write_gz("{\"a\":1}\n{\"b\":2}\n")?;
+        assert_eq!(&*read_decompressed_log(&gz)?, b"{\"a\":1}\n{\"b\":2}\n");
+        Ok(())
+    }
+}
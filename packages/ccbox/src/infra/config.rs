@@ -0,0 +1,357 @@
+use crate::domain::{
+    CustomEngineConfig, EngineModels, ListColumnsConfig, ProcessLimitsConfig, ProjectEnvConfig,
+    RemoteSourceConfig, RemoteSpawnPolicy, SessionDirsConfig, SpawnArgsConfig,
+    validate_remote_sources,
+};
+use serde::Deserialize;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use thiserror::Error;
+
+/// Transcript bytes replayed when attaching to a TTY process unless the config overrides it.
+pub const DEFAULT_TTY_SCROLLBACK_BYTES: u64 = 64 * 1024;
+/// Time between SIGTERM and SIGKILL when a process is stopped, unless the config overrides it.
+pub const DEFAULT_KILL_GRACE: Duration = Duration::from_secs(5);
+
+/// User configuration from `~/.ccbox/config.json`. Every section is optional.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq)]
+pub struct CcboxConfig {
+    #[serde(default)]
+    pub process_limits: ProcessLimitsConfig,
+
+    #[serde(default)]
+    pub task_registry: Option<super::TaskRegistryConfig>,
+
+    /// Scrollback replayed on TTY attach, in KiB; 0 disables the replay.
+    #[serde(default)]
+    pub tty_scrollback_kb: Option<u64>,
+
+    /// Seconds a stopped process gets between SIGTERM and SIGKILL; 0 kills outright.
+    #[serde(default)]
+    pub kill_grace_secs: Option<u64>,
+
+    /// Jump from Processes into a spawned agent's session as soon as it reports its log.
+    #[serde(default)]
+    pub open_spawned_sessions: bool,
+
+    #[serde(default)]
+    pub encryption: super::EncryptionConfig,
+
+    #[serde(default)]
+    pub remote_spawn: RemoteSpawnPolicy,
+
+    #[serde(default)]
+    pub webhooks: Vec<super::WebhookConfig>,
+
+    #[serde(default)]
+    pub hooks: Vec<super::HookConfig>,
+
+    #[serde(default)]
+    pub metrics: Option<super::MetricsConfig>,
+
+    /// Program (and arguments) that opens a project directory, e.g. `code` or `idea`.
+    #[serde(default)]
+    pub ide_command: Option<String>,
+
+    /// Extra agents whose sessions are scanned from a glob with a generic parser.
+    #[serde(default)]
+    pub custom_engines: Vec<CustomEngineConfig>,
+
+    /// Columns of the Projects, Sessions and Tasks lists.
+    #[serde(default)]
+    pub list_columns: ListColumnsConfig,
+
+    /// List worktrees and subdirectories of a git repository as one project at its root.
+    #[serde(default)]
+    pub group_projects_by_repo: bool,
+
+    /// Machines whose session logs are mirrored over SSH and listed read-only.
+    #[serde(default)]
+    pub remote_sources: Vec<RemoteSourceConfig>,
+
+    /// Shares aliases, project names and tasks with other machines through the relay.
+    #[serde(default)]
+    pub metadata_sync: Option<super::MetadataSyncConfig>,
+
+    /// Models offered per engine when spawning (F10 in New Session, Tasks and Task Detail).
+    #[serde(default)]
+    pub models: EngineModels,
+
+    /// Extra CLI arguments per engine, globally and per project, added to every spawn.
+    #[serde(default)]
+    pub spawn_args: SpawnArgsConfig,
+
+    /// Environment variables injected into agents spawned for a project and its subdirectories.
+    #[serde(default)]
+    pub project_env: ProjectEnvConfig,
+
+    /// Rescan debounce, a periodic rescan, and turning the filesystem watchers off.
+    #[serde(default)]
+    pub rescan: super::RescanConfig,
+
+    /// More Codex, Claude and Gemini session roots to scan and watch besides the defaults.
+    #[serde(default)]
+    pub extra_session_dirs: SessionDirsConfig,
+}
+
+impl CcboxConfig {
+    pub fn tty_scrollback_bytes(&self) -> u64 {
+        self.tty_scrollback_kb
+            .map_or(DEFAULT_TTY_SCROLLBACK_BYTES, |kb| kb.saturating_mul(1024))
+    }
+
+    pub fn kill_grace(&self) -> Duration {
+        self.kill_grace_secs
+            .map_or(DEFAULT_KILL_GRACE, Duration::from_secs)
+    }
+
+    /// Names of the top-level sections that differ from `other`, in declaration order.
+    pub fn changed_sections(&self, other: &Self) -> Vec<&'static str> {
+        [
+            (
+                "process_limits",
+                self.process_limits != other.process_limits,
+            ),
+            ("task_registry", self.task_registry != other.task_registry),
+            (
+                "tty_scrollback_kb",
+                self.tty_scrollback_kb != other.tty_scrollback_kb,
+            ),
+            (
+                "kill_grace_secs",
+                self.kill_grace_secs != other.kill_grace_secs,
+            ),
+            (
+                "open_spawned_sessions",
+                self.open_spawned_sessions != other.open_spawned_sessions,
+            ),
+            ("encryption", self.encryption != other.encryption),
+            ("remote_spawn", self.remote_spawn != other.remote_spawn),
+            ("webhooks", self.webhooks != other.webhooks),
+            ("hooks", self.hooks != other.hooks),
+            ("metrics", self.metrics != other.metrics),
+            ("ide_command", self.ide_command != other.ide_command),
+            (
+                "custom_engines",
+                self.custom_engines != other.custom_engines,
+            ),
+            ("list_columns", self.list_columns != other.list_columns),
+            (
+                "group_projects_by_repo",
+                self.group_projects_by_repo != other.group_projects_by_repo,
+            ),
+            (
+                "remote_sources",
+                self.remote_sources != other.remote_sources,
+            ),
+            ("metadata_sync", self.metadata_sync != other.metadata_sync),
+            ("models", self.models != other.models),
+            ("spawn_args", self.spawn_args != other.spawn_args),
+            ("project_env", self.project_env != other.project_env),
+            ("rescan", self.rescan != other.rescan),
+            (
+                "extra_session_dirs",
+                self.extra_session_dirs != other.extra_session_dirs,
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(name, is_changed)| is_changed.then_some(name))
+        .collect()
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum LoadCcboxConfigError {
+    #[error("failed to read config {path}: {source}")]
+    Read {
+        path: String,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("failed to parse config {path}: {source}")]
+    Parse {
+        path: String,
+        #[source]
+        source: serde_json::Error,
+    },
+
+    #[error("invalid config {path}: {message}")]
+    Invalid { path: String, message: String },
+}
+
+pub fn resolve_ccbox_config_path() -> Result<PathBuf, super::ResolveCcboxStateDirError> {
+    if let Some(path) = std::env::var_os("CCBOX_CONFIG") {
+        return Ok(PathBuf::from(path));
+    }
+    Ok(super::resolve_ccbox_state_dir()?.join("config.json"))
+}
+
+/// Loads the config file; a missing file yields the defaults.
+pub fn load_ccbox_config(path: &Path) -> Result<CcboxConfig, LoadCcboxConfigError> {
+    let raw = match fs::read_to_string(path) {
+        Ok(raw) => raw,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => {
+            return Ok(CcboxConfig::default());
+        }
+        Err(source) => {
+            return Err(LoadCcboxConfigError::Read {
+                path: path.display().to_string(),
+                source,
+            });
+        }
+    };
+
+    let config: CcboxConfig =
+        serde_json::from_str(&raw).map_err(|source| LoadCcboxConfigError::Parse {
+            path: path.display().to_string(),
+            source,
+        })?;
+    config
+        .process_limits
+        .validate()
+        .and_then(|()| {
+            config
+                .task_registry
+                .as_ref()
+                .map_or(Ok(()), super::TaskRegistryConfig::validate)
+        })
+        .and_then(|()| config.remote_spawn.validate())
+        .and_then(|()| {
+            config
+                .webhooks
+                .iter()
+                .try_for_each(super::WebhookConfig::validate)
+        })
+        .and_then(|()| {
+            config
+                .hooks
+                .iter()
+                .try_for_each(super::HookConfig::validate)
+        })
+        .and_then(|()| {
+            config
+                .metrics
+                .as_ref()
+                .map_or(Ok(()), super::MetricsConfig::validate)
+        })
+        .and_then(|()| match config.ide_command.as_deref() {
+            Some(command) if command.trim().is_empty() => {
+                Err("ide_command must not be empty".to_string())
+            }
+            _ => Ok(()),
+        })
+        .and_then(|()| {
+            config
+                .custom_engines
+                .iter()
+                .try_for_each(CustomEngineConfig::validate)
+        })
+        .and_then(|()| config.list_columns.validate())
+        .and_then(|()| validate_remote_sources(&config.remote_sources))
+        .and_then(|()| {
+            config
+                .metadata_sync
+                .as_ref()
+                .map_or(Ok(()), super::MetadataSyncConfig::validate)
+        })
+        .and_then(|()| config.models.validate())
+        .and_then(|()| config.spawn_args.validate())
+        .and_then(|()| config.project_env.validate())
+        .and_then(|()| config.rescan.validate())
+        .and_then(|()| config.extra_session_dirs.validate())
+        .map_err(|message| LoadCcboxConfigError::Invalid {
+            path: path.display().to_string(),
+            message,
+        })?;
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn missing_config_is_default_and_bad_config_is_reported() -> io::Result<()> {
+        let dir = tempdir()?;
+        let path = dir.path().join("config.json");
+        assert_eq!(load_ccbox_config(&path).ok(), Some(CcboxConfig::default()));
+
+        fs::write(
+            &path,
+            r#"{ "process_limits": { "default": { "nice": 10, "memory_mb": 4096 } } }"#,
+        )?;
+        let config = load_ccbox_config(&path).expect("config");
+        assert_eq!(config.process_limits.default.nice, Some(10));
+        assert_eq!(config.tty_scrollback_bytes(), DEFAULT_TTY_SCROLLBACK_BYTES);
+        assert_eq!(config.kill_grace(), DEFAULT_KILL_GRACE);
+
+        fs::write(&path, r#"{ "tty_scrollback_kb": 0 }"#)?;
+        assert_eq!(
+            load_ccbox_config(&path)
+                .expect("config")
+                .tty_scrollback_bytes(),
+            0
+        );
+
+        fs::write(
+            &path,
+            r#"{ "process_limits": { "default": { "nice": 40 } } }"#,
+        )?;
+        assert!(matches!(
+            load_ccbox_config(&path),
+            Err(LoadCcboxConfigError::Invalid { .. })
+        ));
+
+        fs::write(
+            &path,
+            r#"{ "rescan": { "debounce_ms": 2000, "interval_secs": 0, "disable_watchers": true } }"#,
+        )?;
+        let rescan = load_ccbox_config(&path).expect("config").rescan;
+        assert!(rescan.disable_watchers);
+        assert_eq!(rescan.debounce(), Duration::from_secs(2));
+        assert_eq!(rescan.max_delay(), crate::infra::DEFAULT_RESCAN_MAX_DELAY);
+        assert_eq!(rescan.interval(), None);
+
+        fs::write(&path, r#"{ "rescan": { "debounce_ms": 6000 } }"#)?;
+        assert!(matches!(
+            load_ccbox_config(&path),
+            Err(LoadCcboxConfigError::Invalid { .. })
+        ));
+
+        fs::write(&path, r#"{ "ide_command": "  " }"#)?;
+        assert!(matches!(
+            load_ccbox_config(&path),
+            Err(LoadCcboxConfigError::Invalid { .. })
+        ));
+
+        fs::write(
+            &path,
+            r#"{ "process_limits": { "default": { "cpu": 1 } } }"#,
+        )?;
+        assert!(matches!(
+            load_ccbox_config(&path),
+            Err(LoadCcboxConfigError::Parse { .. })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn reports_changed_sections() {
+        let old = CcboxConfig::default();
+        assert!(old.changed_sections(&old.clone()).is_empty());
+
+        let new: CcboxConfig = serde_json::from_value(serde_json::json!({
+            "tty_scrollback_kb": 8,
+            "webhooks": [{ "url": "https://hooks.example.com/T000" }]
+        }))
+        .expect("config");
+        assert_eq!(
+            old.changed_sections(&new),
+            vec!["tty_scrollback_kb", "webhooks"]
+        );
+    }
+}
@@ -0,0 +1,170 @@
+use crate::domain::{
+    CustomEngineConfig, CustomLogFlavor, SessionEngine, SessionMeta, SessionSummary, expand_home,
+    extract_custom_session_meta, glob_base_dir, glob_matches, make_session_summary,
+    system_time_to_rfc3339,
+};
+use crate::infra::ScanWarningCount;
+use dirs::home_dir;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+use walkdir::WalkDir;
+
+/// JSONL lines read for a session's id, cwd, start time and title.
+const MAX_HEAD_LINES: usize = 200;
+
+/// Single-json documents above this are listed from file metadata alone.
+const MAX_SINGLE_JSON_BYTES: u64 = 10 * 1024 * 1024;
+
+#[derive(Clone, Debug)]
+pub struct CustomEngineScanOutput {
+    pub sessions: Vec<SessionSummary>,
+    pub warnings: ScanWarningCount,
+    pub notice: Option<String>,
+}
+
+/// Sessions of every configured custom engine. Missing id, cwd, start time or title fall back to
+/// the file stem, the file's directory, its mtime and "(untitled)".
+pub fn scan_custom_engines(engines: &[CustomEngineConfig]) -> CustomEngineScanOutput {
+    let mut sessions = Vec::new();
+    let mut warnings = 0usize;
+    let mut notices = Vec::new();
+
+    for engine in engines {
+        let pattern = expand_home(
+            Path::new(engine.sessions_glob.trim()),
+            home_dir().as_deref(),
+        )
+        .to_string_lossy()
+        .to_string();
+        let base_dir = glob_base_dir(&pattern);
+        if !base_dir.is_dir() {
+            notices.push(format!(
+                "Custom engine `{}`: {} not found",
+                engine.name,
+                base_dir.display()
+            ));
+            continue;
+        }
+
+        for entry in WalkDir::new(&base_dir).follow_links(false) {
+            let Ok(entry) = entry else {
+                warnings += 1;
+                continue;
+            };
+            if !entry.file_type().is_file()
+                || !glob_matches(&pattern, &entry.path().to_string_lossy())
+            {
+                continue;
+            }
+            match scan_custom_session_file(entry.path(), engine.flavor) {
+                Ok(summary) => sessions.push(summary),
+                Err(_) => warnings += 1,
+            }
+        }
+    }
+
+    CustomEngineScanOutput {
+        sessions,
+        warnings: ScanWarningCount::from(warnings),
+        notice: (!notices.is_empty()).then(|| notices.join(" | ")),
+    }
+}
+
+fn scan_custom_session_file(path: &Path, flavor: CustomLogFlavor) -> io::Result<SessionSummary> {
+    let metadata = fs::metadata(path)?;
+    let file_size_bytes = metadata.len();
+    let file_modified = metadata.modified().ok();
+
+    let text = match flavor {
+        CustomLogFlavor::JsonlOpenAi | CustomLogFlavor::JsonlAnthropic => {
+            let reader = BufReader::new(File::open(path)?);
+            reader
+                .lines()
+                .take(MAX_HEAD_LINES)
+                .map_while(Result::ok)
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+        CustomLogFlavor::SingleJson if file_size_bytes <= MAX_SINGLE_JSON_BYTES => {
+            fs::read_to_string(path)?
+        }
+        CustomLogFlavor::SingleJson => String::new(),
+    };
+    let meta = extract_custom_session_meta(flavor, &text);
+
+    let id = meta
+        .id
+        .or_else(|| {
+            path.file_stem()
+                .and_then(|stem| stem.to_str())
+                .map(str::to_string)
+        })
+        .unwrap_or_else(|| path.display().to_string());
+    let cwd = meta
+        .cwd
+        .or_else(|| path.parent().map(Path::to_path_buf))
+        .unwrap_or_default();
+    let started_at_rfc3339 = meta
+        .started_at_rfc3339
+        .or_else(|| file_modified.and_then(system_time_to_rfc3339))
+        .unwrap_or_else(|| {
+            OffsetDateTime::now_utc()
+                .format(&Rfc3339)
+                .unwrap_or_default()
+        });
+    let title = meta.title.unwrap_or_else(|| "(untitled)".to_string());
+
+    Ok(make_session_summary(
+        SessionMeta {
+            id,
+            cwd,
+            started_at_rfc3339,
+            git: None,
+        },
+        path.to_path_buf(),
+        title,
+        file_size_bytes,
+        file_modified,
+        SessionEngine::Custom,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn scans_matching_files_and_falls_back_to_the_file() -> io::Result<()> {
+        let dir = tempdir()?;
+        let sessions_dir = dir.path().join("agent").join("2026");
+        fs::create_dir_all(&sessions_dir)?;
+        fs::write(
+            sessions_dir.join("a.jsonl"),
+            r#"{"role":"user","content":"Add a login page","cwd":"/work/app"}"#,
+        )?;
+        fs::write(sessions_dir.join("b.jsonl"), "")?;
+        fs::write(sessions_dir.join("notes.txt"), "skip me")?;
+
+        let engines = vec![CustomEngineConfig {
+            name: "agent".to_string(),
+            sessions_glob: format!("{}/agent/**/*.jsonl", dir.path().display()),
+            flavor: CustomLogFlavor::JsonlOpenAi,
+        }];
+        let mut output = scan_custom_engines(&engines);
+        output.sessions.sort_by(|a, b| a.meta.id.cmp(&b.meta.id));
+
+        assert_eq!(output.notice, None);
+        assert_eq!(output.sessions.len(), 2);
+        assert_eq!(output.sessions[0].meta.id, "a");
+        assert_eq!(output.sessions[0].title, "Add a login page");
+        assert_eq!(output.sessions[0].meta.cwd, Path::new("/work/app"));
+        assert_eq!(output.sessions[1].title, "(untitled)");
+        assert_eq!(output.sessions[1].meta.cwd, sessions_dir);
+        assert_eq!(output.sessions[1].engine, SessionEngine::Custom);
+        Ok(())
+    }
+}
@@ -1,3 +1,4 @@
+use crate::infra::{is_remote_mirror_path, resolve_ccbox_state_dir};
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -6,17 +7,28 @@ pub struct DeleteOutcome {
     pub deleted: usize,
     pub failed: usize,
     pub skipped_outside_sessions_dir: usize,
+    /// Mirrored remote logs, which are read-only.
+    pub skipped_remote: usize,
 }
 
 pub fn delete_session_logs(sessions_dir: &Path, log_paths: &[PathBuf]) -> DeleteOutcome {
     let mut deleted = 0usize;
     let mut failed = 0usize;
     let mut skipped_outside_sessions_dir = 0usize;
+    let mut skipped_remote = 0usize;
+    let state_dir = resolve_ccbox_state_dir().ok();
 
     let sessions_dir_canonical =
         fs::canonicalize(sessions_dir).unwrap_or_else(|_| sessions_dir.to_path_buf());
 
     for path in log_paths {
+        if state_dir
+            .as_deref()
+            .is_some_and(|state_dir| is_remote_mirror_path(state_dir, path))
+        {
+            skipped_remote += 1;
+            continue;
+        }
         let path_canonical = fs::canonicalize(path).unwrap_or_else(|_| path.clone());
         if !path_canonical.starts_with(&sessions_dir_canonical) {
             skipped_outside_sessions_dir += 1;
@@ -33,5 +45,6 @@ pub fn delete_session_logs(sessions_dir: &Path, log_paths: &[PathBuf]) -> Delete
         deleted,
         failed,
         skipped_outside_sessions_dir,
+        skipped_remote,
     }
 }
@@ -0,0 +1,113 @@
+use std::io;
+use std::path::Path;
+use std::process::{Command, ExitStatus, Stdio};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum OpenEditorError {
+    #[error("failed to start editor `{editor}`: {source}")]
+    Spawn {
+        editor: String,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("editor `{editor}` exited with {status}")]
+    Failed { editor: String, status: ExitStatus },
+}
+
+#[cfg(windows)]
+const FALLBACK_EDITOR: &str = "notepad";
+#[cfg(not(windows))]
+const FALLBACK_EDITOR: &str = "vi";
+
+/// Editor program and arguments: `$VISUAL`, then `$EDITOR`, then a platform fallback. Values
+/// like `code --wait` are split on whitespace.
+pub fn editor_command_from(visual: Option<String>, editor: Option<String>) -> Vec<String> {
+    [visual, editor]
+        .into_iter()
+        .flatten()
+        .map(|value| {
+            value
+                .split_whitespace()
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        })
+        .find(|parts| !parts.is_empty())
+        .unwrap_or_else(|| vec![FALLBACK_EDITOR.to_string()])
+}
+
+pub fn resolve_editor_command() -> Vec<String> {
+    editor_command_from(std::env::var("VISUAL").ok(), std::env::var("EDITOR").ok())
+}
+
+/// Runs the editor on `path` and waits for it. The caller owns the terminal; the TUI has to be
+/// suspended first.
+pub fn open_in_editor(path: &Path) -> Result<(), OpenEditorError> {
+    let parts = resolve_editor_command();
+    let editor = parts.join(" ");
+    let status = Command::new(&parts[0])
+        .args(&parts[1..])
+        .arg(path)
+        .status()
+        .map_err(|source| OpenEditorError::Spawn {
+            editor: editor.clone(),
+            source,
+        })?;
+    if !status.success() {
+        return Err(OpenEditorError::Failed { editor, status });
+    }
+    Ok(())
+}
+
+/// Starts `command` (split on whitespace) on `path` without waiting: its stdio is detached
+/// from the TUI and, on Unix, it runs in its own process group so terminal signals skip it.
+pub fn launch_detached(command: &str, path: &Path) -> Result<(), OpenEditorError> {
+    let parts = command.split_whitespace().collect::<Vec<_>>();
+    let Some((program, args)) = parts.split_first() else {
+        return Err(OpenEditorError::Spawn {
+            editor: command.to_string(),
+            source: io::Error::new(io::ErrorKind::InvalidInput, "empty command"),
+        });
+    };
+    let mut process = Command::new(program);
+    process
+        .args(args)
+        .arg(path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        process.process_group(0);
+    }
+    let mut child = process.spawn().map_err(|source| OpenEditorError::Spawn {
+        editor: command.to_string(),
+        source,
+    })?;
+    // Reap it whenever it exits so it doesn't linger as a zombie.
+    std::thread::spawn(move || child.wait());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefers_visual_then_editor_and_splits_arguments() {
+        assert_eq!(
+            editor_command_from(Some("code --wait".to_string()), Some("nano".to_string())),
+            vec!["code", "--wait"]
+        );
+        assert_eq!(
+            editor_command_from(Some("  ".to_string()), Some("nano".to_string())),
+            vec!["nano"]
+        );
+        assert_eq!(
+            editor_command_from(None, None),
+            vec![FALLBACK_EDITOR.to_string()]
+        );
+    }
+}
@@ -0,0 +1,296 @@
+use age::secrecy::ExposeSecret;
+use serde::Deserialize;
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Every age file starts with this header line, armored or not.
+const AGE_MAGIC: &[u8] = b"age-encryption.org/v1";
+
+/// Extension appended to an artifact once it has been sealed.
+const SEALED_EXTENSION: &str = "age";
+
+/// Opt-in encryption at rest for ccbox-managed artifacts.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct EncryptionConfig {
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// age identity file; defaults to `~/.ccbox/age-identity.txt`, created on first use.
+    #[serde(default)]
+    pub identity_file: Option<PathBuf>,
+}
+
+#[derive(Debug, Error)]
+pub enum EncryptionError {
+    #[error(transparent)]
+    ResolveStateDir(#[from] super::ResolveCcboxStateDirError),
+
+    #[error("failed to read age identity {path}: {source}")]
+    ReadIdentity {
+        path: String,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("failed to write age identity {path}: {source}")]
+    WriteIdentity {
+        path: String,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("invalid age identity {path}: {message}")]
+    ParseIdentity { path: String, message: String },
+
+    #[error("failed to encrypt: {0}")]
+    Encrypt(String),
+
+    #[error("failed to decrypt: {0}")]
+    Decrypt(String),
+
+    #[error("failed to seal {path}: {source}")]
+    Seal {
+        path: String,
+        #[source]
+        source: io::Error,
+    },
+}
+
+/// Encrypts artifacts to a local age X25519 identity. Sealed files are plain age files, so
+/// `age -d -i <identity>` can read them without ccbox.
+#[derive(Clone)]
+pub struct ArtifactCipher {
+    identity: age::x25519::Identity,
+}
+
+impl fmt::Debug for ArtifactCipher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ArtifactCipher")
+            .field("recipient", &self.identity.to_public().to_string())
+            .finish()
+    }
+}
+
+impl ArtifactCipher {
+    /// Returns `None` when encryption is disabled.
+    pub fn from_config(config: &EncryptionConfig) -> Result<Option<Self>, EncryptionError> {
+        if !config.enabled {
+            return Ok(None);
+        }
+        let path = match &config.identity_file {
+            Some(path) => path.clone(),
+            None => super::resolve_ccbox_state_dir()?.join("age-identity.txt"),
+        };
+        Self::load_or_create(&path).map(Some)
+    }
+
+    /// Reads an `age-keygen` style identity file, generating one (mode 0600) when missing.
+    pub fn load_or_create(path: &Path) -> Result<Self, EncryptionError> {
+        match fs::read_to_string(path) {
+            Ok(raw) => parse_identity_file(&raw)
+                .map(|identity| Self { identity })
+                .map_err(|message| EncryptionError::ParseIdentity {
+                    path: path.display().to_string(),
+                    message,
+                }),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {
+                let identity = age::x25519::Identity::generate();
+                write_identity_file(path, &identity).map_err(|source| {
+                    EncryptionError::WriteIdentity {
+                        path: path.display().to_string(),
+                        source,
+                    }
+                })?;
+                Ok(Self { identity })
+            }
+            Err(source) => Err(EncryptionError::ReadIdentity {
+                path: path.display().to_string(),
+                source,
+            }),
+        }
+    }
+
+    pub fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+        age::encrypt(&self.identity.to_public(), plaintext)
+            .map_err(|error| EncryptionError::Encrypt(error.to_string()))
+    }
+
+    pub fn decrypt(&self, ciphertext: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+        age::decrypt(&self.identity, ciphertext)
+            .map_err(|error| EncryptionError::Decrypt(error.to_string()))
+    }
+
+    /// Replaces `path` with an encrypted `path.age` and returns the new path.
+    pub fn seal_file(&self, path: &Path) -> Result<PathBuf, EncryptionError> {
+        let seal_error = |source: io::Error| EncryptionError::Seal {
+            path: path.display().to_string(),
+            source,
+        };
+        let plaintext = fs::read(path).map_err(seal_error)?;
+        let ciphertext = self.encrypt(&plaintext)?;
+        let sealed = sealed_artifact_path(path);
+        let tmp = sealed.with_extension(format!("{SEALED_EXTENSION}.tmp"));
+        fs::write(&tmp, ciphertext).map_err(seal_error)?;
+        fs::rename(&tmp, &sealed).map_err(seal_error)?;
+        fs::remove_file(path).map_err(seal_error)?;
+        Ok(sealed)
+    }
+}
+
+pub fn is_encrypted_artifact(bytes: &[u8]) -> bool {
+    bytes.starts_with(AGE_MAGIC)
+}
+
+pub fn sealed_artifact_path(path: &Path) -> PathBuf {
+    let mut sealed = path.as_os_str().to_os_string();
+    sealed.push(".");
+    sealed.push(SEALED_EXTENSION);
+    PathBuf::from(sealed)
+}
+
+/// Reads the last `max_bytes` of an artifact. Falls back to the sealed `path.age` copy once the
+/// plaintext is gone; the returned offset is then the decrypted size.
+pub fn read_artifact_tail(
+    path: &Path,
+    max_bytes: usize,
+    cipher: Option<&ArtifactCipher>,
+) -> io::Result<(String, u64)> {
+    match super::read_tail(path, max_bytes) {
+        Err(error) if error.kind() == io::ErrorKind::NotFound => {
+            let plaintext = read_sealed_artifact(path, cipher, error)?;
+            let size = plaintext.len() as u64;
+            let start = plaintext.len().saturating_sub(max_bytes);
+            Ok((
+                String::from_utf8_lossy(&plaintext[start..]).to_string(),
+                size,
+            ))
+        }
+        other => other,
+    }
+}
+
+/// Reads a whole artifact, falling back to the sealed `path.age` copy once the plaintext is gone.
+pub fn read_artifact(path: &Path, cipher: Option<&ArtifactCipher>) -> io::Result<Vec<u8>> {
+    match fs::read(path) {
+        Err(error) if error.kind() == io::ErrorKind::NotFound => {
+            read_sealed_artifact(path, cipher, error)
+        }
+        other => other,
+    }
+}
+
+/// Whether `path` has been replaced by its sealed copy.
+pub fn is_sealed_artifact(path: &Path) -> bool {
+    !path.exists() && sealed_artifact_path(path).exists()
+}
+
+/// Creates (or truncates) a file only its owner can read, for artifacts that have to stay
+/// plaintext while in use.
+pub fn create_private_file(path: &Path) -> io::Result<File> {
+    let mut options = OpenOptions::new();
+    options.create(true).truncate(true).write(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(path)
+}
+
+/// Creates `path` (and its parents) and limits it to its owner.
+pub fn create_private_dir(path: &Path) -> io::Result<()> {
+    fs::create_dir_all(path)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(0o700))?;
+    }
+    Ok(())
+}
+
+fn read_sealed_artifact(
+    path: &Path,
+    cipher: Option<&ArtifactCipher>,
+    not_found: io::Error,
+) -> io::Result<Vec<u8>> {
+    let sealed = sealed_artifact_path(path);
+    let Some(cipher) = cipher else {
+        if sealed.exists() {
+            return Err(io::Error::other(format!(
+                "{} is encrypted and encryption is not enabled",
+                path.display()
+            )));
+        }
+        return Err(not_found);
+    };
+    let ciphertext = fs::read(&sealed).map_err(|_| not_found)?;
+    cipher.decrypt(&ciphertext).map_err(io::Error::other)
+}
+
+fn parse_identity_file(raw: &str) -> Result<age::x25519::Identity, String> {
+    let line = raw
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with('#'))
+        .ok_or_else(|| "no identity found".to_string())?;
+    age::x25519::Identity::from_str(line).map_err(str::to_string)
+}
+
+fn write_identity_file(path: &Path, identity: &age::x25519::Identity) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(path)?;
+    writeln!(file, "# public key: {}", identity.to_public())?;
+    writeln!(file, "{}", identity.to_string().expose_secret())?;
+    file.sync_all()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn creates_identity_and_seals_files_readably() -> Result<(), Box<dyn std::error::Error>> {
+        let dir = tempdir()?;
+        let identity_path = dir.path().join("keys").join("age-identity.txt");
+        let cipher = ArtifactCipher::load_or_create(&identity_path)?;
+        let reloaded = ArtifactCipher::load_or_create(&identity_path)?;
+
+        let log_path = dir.path().join("process.log");
+        fs::write(&log_path, "line one\nline two\n")?;
+        let sealed = cipher.seal_file(&log_path)?;
+        assert!(!log_path.exists());
+        assert!(is_encrypted_artifact(&fs::read(&sealed)?));
+
+        let (tail, size) = read_artifact_tail(&log_path, 9, Some(&reloaded))?;
+        assert_eq!((tail.as_str(), size), ("line two\n", 18));
+        assert!(read_artifact_tail(&log_path, 9, None).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_malformed_identity_file() -> io::Result<()> {
+        let dir = tempdir()?;
+        let path = dir.path().join("age-identity.txt");
+        fs::write(&path, "# comment only\n\nnot-a-key\n")?;
+        assert!(matches!(
+            ArtifactCipher::load_or_create(&path),
+            Err(EncryptionError::ParseIdentity { .. })
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,145 @@
+use crate::domain::{EngineFilterPrefs, SessionEngine};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum LoadEngineFiltersError {
+    #[error("failed to read engine filters: {0}")]
+    Read(#[from] io::Error),
+
+    #[error("failed to parse engine filters: {0}")]
+    Parse(#[from] serde_json::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum SaveEngineFiltersError {
+    #[error("failed to encode engine filters: {0}")]
+    Encode(#[from] serde_json::Error),
+
+    #[error("failed to write engine filters: {0}")]
+    Write(#[from] io::Error),
+}
+
+fn engine_filters_path(state_dir: &Path) -> PathBuf {
+    state_dir.join("engine_filters.json")
+}
+
+/// Loads the remembered filters; a missing file yields "all" everywhere and unknown engine names
+/// are ignored.
+pub fn load_engine_filters(state_dir: &Path) -> Result<EngineFilterPrefs, LoadEngineFiltersError> {
+    let raw = match fs::read_to_string(engine_filters_path(state_dir)) {
+        Ok(raw) => raw,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => {
+            return Ok(EngineFilterPrefs::default());
+        }
+        Err(error) => return Err(error.into()),
+    };
+
+    let file: EngineFiltersFile = serde_json::from_str(&raw)?;
+    Ok(EngineFilterPrefs {
+        projects: parse_filter_name(&file.projects).flatten(),
+        sessions: parse_filter_name(&file.sessions).flatten(),
+        project_overrides: file
+            .project_overrides
+            .iter()
+            .filter_map(|(path, name)| Some((path.clone(), parse_filter_name(name)?)))
+            .collect(),
+    })
+}
+
+pub fn save_engine_filters(
+    state_dir: &Path,
+    prefs: &EngineFilterPrefs,
+) -> Result<(), SaveEngineFiltersError> {
+    fs::create_dir_all(state_dir)?;
+
+    let path = engine_filters_path(state_dir);
+    let tmp = path.with_extension("json.tmp");
+    let file = EngineFiltersFile {
+        version: 1,
+        projects: filter_name(prefs.projects).to_string(),
+        sessions: filter_name(prefs.sessions).to_string(),
+        project_overrides: prefs
+            .project_overrides
+            .iter()
+            .map(|(path, engine)| (path.clone(), filter_name(*engine).to_string()))
+            .collect(),
+    };
+    let text = serde_json::to_string_pretty(&file)?;
+    fs::write(&tmp, text)?;
+    fs::rename(tmp, path)?;
+    Ok(())
+}
+
+fn filter_name(engine: Option<SessionEngine>) -> &'static str {
+    match engine {
+        None | Some(SessionEngine::Custom) => "all",
+        Some(SessionEngine::Codex) => "codex",
+        Some(SessionEngine::Claude) => "claude",
+        Some(SessionEngine::Gemini) => "gemini",
+        Some(SessionEngine::OpenCode) => "opencode",
+    }
+}
+
+/// `None` for an unknown name; `Some(None)` for "all".
+fn parse_filter_name(name: &str) -> Option<Option<SessionEngine>> {
+    match name {
+        "all" => Some(None),
+        "codex" => Some(Some(SessionEngine::Codex)),
+        "claude" => Some(Some(SessionEngine::Claude)),
+        "gemini" => Some(Some(SessionEngine::Gemini)),
+        "opencode" => Some(Some(SessionEngine::OpenCode)),
+        _ => None,
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct EngineFiltersFile {
+    version: u32,
+    #[serde(default)]
+    projects: String,
+    #[serde(default)]
+    sessions: String,
+    #[serde(default)]
+    project_overrides: BTreeMap<PathBuf, String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn round_trips_filters_and_skips_unknown_engines() -> Result<(), Box<dyn std::error::Error>> {
+        let dir = tempdir()?;
+        assert_eq!(
+            load_engine_filters(dir.path())?,
+            EngineFilterPrefs::default()
+        );
+
+        let mut prefs = EngineFilterPrefs {
+            projects: Some(SessionEngine::Gemini),
+            ..EngineFilterPrefs::default()
+        };
+        prefs.set_sessions(Path::new("/work/app"), Some(SessionEngine::Claude));
+        prefs.set_sessions(Path::new("/work/api"), None);
+        save_engine_filters(dir.path(), &prefs)?;
+        assert_eq!(load_engine_filters(dir.path())?, prefs);
+
+        fs::write(
+            engine_filters_path(dir.path()),
+            r#"{ "version": 1, "projects": "cursor", "project_overrides": { "/work/app": "codex", "/work/x": "cursor" } }"#,
+        )?;
+        let loaded = load_engine_filters(dir.path())?;
+        assert_eq!(loaded.projects, None);
+        assert_eq!(
+            loaded.project_overrides,
+            BTreeMap::from([(PathBuf::from("/work/app"), Some(SessionEngine::Codex))])
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,220 @@
+//! Agent CLIs running outside ccbox, found in the process table (`/proc`, Linux only).
+
+use crate::domain::SessionEngine;
+use crate::infra::{process_parent_pid, process_started_at};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Runtimes that run an agent from a script given as their first non-flag argument.
+const INTERPRETERS: [&str; 5] = ["node", "bun", "deno", "python", "python3"];
+
+/// An agent CLI process that no ccbox spawned.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExternalAgentProcess {
+    pub pid: u32,
+    pub engine: SessionEngine,
+    pub cwd: PathBuf,
+    /// The command line, space-joined.
+    pub command: String,
+    pub started_at: Option<SystemTime>,
+}
+
+#[derive(Clone, Debug)]
+struct ProcEntry {
+    pid: u32,
+    ppid: u32,
+    argv: Vec<String>,
+}
+
+/// Running agents that are not descendants of a ccbox (this one, `ccbox serve`/`run`) or of a
+/// pid in `managed_pids`. An agent launched by another agent process (the `node` wrapper that
+/// starts the native Codex binary) is listed once, as the outermost process. Processes whose cwd
+/// can't be read (other users') are skipped.
+pub fn scan_external_agent_processes(managed_pids: &[u32]) -> Vec<ExternalAgentProcess> {
+    let entries = read_proc_entries();
+    external_agents(&entries, managed_pids, std::process::id())
+        .into_iter()
+        .filter_map(|(entry, engine)| {
+            let cwd = std::fs::read_link(format!("/proc/{}/cwd", entry.pid)).ok()?;
+            Some(ExternalAgentProcess {
+                pid: entry.pid,
+                engine,
+                cwd,
+                command: entry.argv.join(" "),
+                started_at: process_started_at(entry.pid),
+            })
+        })
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn read_proc_entries() -> Vec<ProcEntry> {
+    let Ok(dir) = std::fs::read_dir("/proc") else {
+        return Vec::new();
+    };
+    dir.filter_map(|entry| {
+        let pid = entry.ok()?.file_name().to_str()?.parse::<u32>().ok()?;
+        let cmdline = std::fs::read(format!("/proc/{pid}/cmdline")).ok()?;
+        let argv = cmdline
+            .split(|byte| *byte == 0)
+            .filter(|arg| !arg.is_empty())
+            .map(|arg| String::from_utf8_lossy(arg).into_owned())
+            .collect::<Vec<_>>();
+        // Kernel threads have an empty command line.
+        if argv.is_empty() {
+            return None;
+        }
+        Some(ProcEntry {
+            pid,
+            ppid: process_parent_pid(pid)?,
+            argv,
+        })
+    })
+    .collect()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_proc_entries() -> Vec<ProcEntry> {
+    Vec::new()
+}
+
+fn external_agents<'a>(
+    entries: &'a [ProcEntry],
+    managed_pids: &[u32],
+    own_pid: u32,
+) -> Vec<(&'a ProcEntry, SessionEngine)> {
+    let by_pid = entries
+        .iter()
+        .map(|entry| (entry.pid, entry))
+        .collect::<HashMap<_, _>>();
+    let is_owner = |entry: &ProcEntry| {
+        entry.pid == own_pid
+            || managed_pids.contains(&entry.pid)
+            || entry
+                .argv
+                .first()
+                .is_some_and(|program| file_stem(program) == "ccbox")
+    };
+
+    let mut agents = Vec::new();
+    'entries: for entry in entries {
+        let Some(engine) = agent_engine_of(&entry.argv) else {
+            continue;
+        };
+        if is_owner(entry) {
+            continue;
+        }
+        let mut ppid = entry.ppid;
+        // The depth cap guards against a pid loop in a table read while processes come and go.
+        for _ in 0..64 {
+            let Some(parent) = by_pid.get(&ppid) else {
+                break;
+            };
+            if is_owner(parent) || agent_engine_of(&parent.argv).is_some() {
+                continue 'entries;
+            }
+            ppid = parent.ppid;
+        }
+        agents.push((entry, engine));
+    }
+    agents.sort_by_key(|(entry, _)| entry.pid);
+    agents
+}
+
+/// The agent a command line runs, looking through interpreters (`node …/bin/codex`).
+fn agent_engine_of(argv: &[String]) -> Option<SessionEngine> {
+    let program = argv.first()?;
+    let script = if INTERPRETERS.contains(&file_stem(program)) {
+        argv.iter().skip(1).find(|arg| !arg.starts_with('-'))?
+    } else {
+        program
+    };
+    let normalized = script.replace('\\', "/");
+    let packages = [
+        ("/@openai/codex/", SessionEngine::Codex),
+        ("/@anthropic-ai/claude-code/", SessionEngine::Claude),
+        ("/@google/gemini-cli/", SessionEngine::Gemini),
+        ("/opencode-ai/", SessionEngine::OpenCode),
+    ];
+    if let Some((_, engine)) = packages
+        .iter()
+        .find(|(package, _)| normalized.contains(package))
+    {
+        return Some(*engine);
+    }
+    match file_stem(script) {
+        "claude" => Some(SessionEngine::Claude),
+        "gemini" => Some(SessionEngine::Gemini),
+        name if name == "codex" || name.starts_with("codex-") => Some(SessionEngine::Codex),
+        name if name == "opencode" || name.starts_with("opencode-") => {
+            Some(SessionEngine::OpenCode)
+        }
+        _ => None,
+    }
+}
+
+/// The file name without a script extension (`cli.js` → `cli`).
+fn file_stem(path: &str) -> &str {
+    let name = Path::new(path)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(path);
+    [".js", ".mjs", ".cjs", ".exe"]
+        .iter()
+        .find_map(|extension| name.strip_suffix(extension))
+        .unwrap_or(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_agents_not_started_by_ccbox_once_per_wrapper() {
+        let entry = |pid: u32, ppid: u32, argv: &[&str]| ProcEntry {
+            pid,
+            ppid,
+            argv: argv.iter().map(|arg| arg.to_string()).collect(),
+        };
+        let entries = [
+            entry(10, 1, &["/bin/zsh"]),
+            // A Codex started from a shell: the node wrapper and its native binary.
+            entry(11, 10, &["node", "/usr/local/bin/codex", "--full-auto"]),
+            entry(
+                12,
+                11,
+                &["/usr/lib/node_modules/@openai/codex/vendor/x86_64/codex/codex"],
+            ),
+            entry(13, 10, &["claude"]),
+            // Spawned by this ccbox and by a `ccbox serve`.
+            entry(20, 1, &["ccbox"]),
+            entry(21, 20, &["gemini", "-p", "hi"]),
+            entry(30, 1, &["/home/me/.cargo/bin/ccbox", "serve"]),
+            entry(
+                31,
+                30,
+                &["node", "--no-warnings", "/opt/opencode-ai/bin/opencode"],
+            ),
+            // Restored from an earlier ccbox run, now a child of init.
+            entry(40, 1, &["codex", "exec"]),
+            entry(50, 10, &["vim", "codex.md"]),
+        ];
+
+        let agents = external_agents(&entries, &[40], 20)
+            .into_iter()
+            .map(|(entry, engine)| (entry.pid, engine))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            agents,
+            [(11, SessionEngine::Codex), (13, SessionEngine::Claude)]
+        );
+        assert_eq!(
+            agent_engine_of(&[
+                "node".to_string(),
+                "/x/@anthropic-ai/claude-code/cli.js".to_string()
+            ]),
+            Some(SessionEngine::Claude)
+        );
+    }
+}
@@ -3,7 +3,7 @@ use crate::domain::{
     SessionTimeline, derive_title_from_user_text, extract_gemini_first_user_message,
     extract_gemini_session_id, extract_gemini_session_start_time, infer_gemini_title_from_session,
     is_metadata_prompt, make_session_summary, parse_gemini_logs_entries,
-    parse_gemini_timeline_items,
+    parse_gemini_timeline_items, system_time_to_rfc3339,
 };
 use crate::infra::{LastAssistantOutput, ScanWarningCount};
 use dirs::home_dir;
@@ -11,7 +11,6 @@ use std::collections::BTreeMap;
 use std::fs::{self, File};
 use std::io;
 use std::path::{Path, PathBuf};
-use std::time::SystemTime;
 use time::OffsetDateTime;
 use time::format_description::well_known::Rfc3339;
 
@@ -22,8 +21,6 @@ pub struct GeminiScanOutput {
     pub notice: Option<String>,
 }
 
-const MAX_TIMELINE_ITEMS: usize = 10_000;
-
 #[derive(Debug, thiserror::Error)]
 pub enum ResolveGeminiRootDirError {
     #[error("home directory not found")]
@@ -290,6 +287,7 @@ fn scan_gemini_session_file(
             id: session_id,
             cwd: project_cwd,
             started_at_rfc3339,
+            git: None,
         },
         path.to_path_buf(),
         title,
@@ -304,20 +302,14 @@ pub fn load_gemini_session_timeline(path: &Path) -> io::Result<SessionTimeline>
     let value: serde_json::Value = serde_json::from_reader(file)
         .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
 
-    let GeminiTimelineParseOutput {
-        mut items,
-        warnings,
-    } = parse_gemini_timeline_items(&value);
-    let truncated = items.len() > MAX_TIMELINE_ITEMS;
-    if truncated {
-        items.truncate(MAX_TIMELINE_ITEMS);
-    }
+    let GeminiTimelineParseOutput { items, warnings } = parse_gemini_timeline_items(&value);
 
     Ok(SessionTimeline {
         items,
         turn_contexts: BTreeMap::new(),
         warnings,
-        truncated,
+        truncated: false,
+        older: Vec::new(),
     })
 }
 
@@ -381,11 +373,6 @@ fn file_stem_string(path: &Path) -> Option<String> {
         .map(|name| name.to_string())
 }
 
-fn system_time_to_rfc3339(value: SystemTime) -> Option<String> {
-    let timestamp = OffsetDateTime::from(value);
-    timestamp.format(&Rfc3339).ok()
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,249 @@
+use crate::domain::{SessionEngine, SessionGit, SessionSummary};
+use std::collections::{BTreeMap, BTreeSet};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use thiserror::Error;
+
+/// Parallel `git` calls per scan; one per distinct project directory.
+const GIT_LOOKUP_THREADS: usize = 8;
+
+#[derive(Debug, Error)]
+pub enum GitError {
+    #[error("failed to run git: {0}")]
+    Spawn(#[from] io::Error),
+
+    #[error("{0}")]
+    Failed(String),
+}
+
+/// `git -C <dir>` with no stdin and credential prompts turned off, so a command can't block on
+/// the terminal. Put `--` before remotes, branches and paths that come from config or input, so a
+/// value starting with `-` is not read as an option.
+pub fn git_command(dir: &Path) -> Command {
+    let mut command = Command::new("git");
+    command
+        .arg("-C")
+        .arg(dir)
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .stdin(Stdio::null());
+    command
+}
+
+/// Runs a command from [`git_command`] and returns its stdout; a non-zero exit carries the trimmed
+/// stderr.
+pub fn run_git(mut command: Command) -> Result<String, GitError> {
+    let output = command.output()?;
+    if !output.status.success() {
+        return Err(GitError::Failed(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Fills in branch/commit for Claude, Gemini and custom sessions, which don't record them. This is the
+/// cwd's HEAD at scan time, not at session time; `git` runs once per distinct cwd.
+pub fn enrich_sessions_with_git(sessions: &mut [SessionSummary]) {
+    let needs_git = |session: &SessionSummary| {
+        session.meta.git.is_none()
+            && session.remote.is_none()
+            && matches!(
+                session.engine,
+                SessionEngine::Claude | SessionEngine::Gemini | SessionEngine::Custom
+            )
+    };
+    let cwds = sessions
+        .iter()
+        .filter(|session| needs_git(session))
+        .map(|session| session.meta.cwd.clone())
+        .filter(|cwd| cwd.is_dir())
+        .collect::<BTreeSet<PathBuf>>()
+        .into_iter()
+        .collect::<Vec<_>>();
+    let heads = lookup_per_dir(&cwds, read_git_head);
+
+    for session in sessions.iter_mut() {
+        if needs_git(session)
+            && let Some(git) = heads.get(&session.meta.cwd)
+        {
+            session.meta.git = Some(git.clone());
+        }
+    }
+}
+
+/// Sets `project_root` on sessions whose cwd is a subdirectory or worktree of a git repository, so
+/// they are listed under one project. Cwds that no longer exist fall back to the deepest known
+/// repository root above them. Remote sessions keep their `<remote>:<cwd>` project.
+pub fn group_sessions_by_repo(sessions: &mut [SessionSummary]) {
+    let cwds = sessions
+        .iter()
+        .filter(|session| session.remote.is_none())
+        .map(|session| session.meta.cwd.clone())
+        .filter(|cwd| cwd.is_dir())
+        .collect::<BTreeSet<PathBuf>>()
+        .into_iter()
+        .collect::<Vec<_>>();
+    let roots = lookup_per_dir(&cwds, read_repo_root);
+    let known_roots = roots.values().cloned().collect::<BTreeSet<_>>();
+
+    for session in sessions
+        .iter_mut()
+        .filter(|session| session.remote.is_none())
+    {
+        let root = roots
+            .get(&session.meta.cwd)
+            .cloned()
+            .or_else(|| enclosing_root(&session.meta.cwd, &known_roots));
+        session.project_root = root.filter(|root| *root != session.meta.cwd);
+    }
+}
+
+/// Runs `lookup` for each of `dirs` on up to `GIT_LOOKUP_THREADS` threads.
+fn lookup_per_dir<T: Send>(
+    dirs: &[PathBuf],
+    lookup: fn(&Path) -> Option<T>,
+) -> BTreeMap<PathBuf, T> {
+    if dirs.is_empty() {
+        return BTreeMap::new();
+    }
+    let chunk_size = dirs.len().div_ceil(GIT_LOOKUP_THREADS);
+    thread::scope(|scope| {
+        let handles = dirs
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .filter_map(|dir| lookup(dir).map(|value| (dir.clone(), value)))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect::<Vec<_>>();
+        handles
+            .into_iter()
+            .filter_map(|handle| handle.join().ok())
+            .flatten()
+            .collect()
+    })
+}
+
+/// The deepest of `roots` that contains `cwd`.
+fn enclosing_root(cwd: &Path, roots: &BTreeSet<PathBuf>) -> Option<PathBuf> {
+    roots
+        .iter()
+        .filter(|root| cwd.starts_with(root))
+        .max_by_key(|root| root.components().count())
+        .cloned()
+}
+
+/// The main working tree of the repository containing `dir`: for a linked worktree, the
+/// checkout that owns its `.git` directory.
+fn read_repo_root(dir: &Path) -> Option<PathBuf> {
+    let output = git_command(dir)
+        .args([
+            "rev-parse",
+            "--path-format=absolute",
+            "--show-toplevel",
+            "--git-common-dir",
+        ])
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_repo_root(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Output of `git rev-parse --show-toplevel --git-common-dir`: the worktree, then the shared
+/// `.git` directory, whose parent is the main checkout.
+fn parse_repo_root(stdout: &str) -> Option<PathBuf> {
+    let mut lines = stdout.lines().map(str::trim);
+    let toplevel = lines.next().filter(|line| !line.is_empty())?;
+    let common_dir = lines.next().map(Path::new);
+    match common_dir {
+        Some(common_dir) if common_dir.file_name().is_some_and(|name| name == ".git") => {
+            common_dir.parent().map(Path::to_path_buf)
+        }
+        _ => Some(PathBuf::from(toplevel)),
+    }
+}
+
+/// Branch and commit checked out in `dir`, or `None` outside a repository (or without `git`).
+pub fn read_git_head(dir: &Path) -> Option<SessionGit> {
+    let output = git_command(dir)
+        .args(["rev-parse", "HEAD", "--abbrev-ref", "HEAD"])
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_rev_parse_head(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Output of `git rev-parse HEAD --abbrev-ref HEAD`: the commit, then the branch (`HEAD` when
+/// detached).
+fn parse_rev_parse_head(stdout: &str) -> Option<SessionGit> {
+    let mut lines = stdout.lines().map(str::trim);
+    let commit = lines.next().filter(|commit| !commit.is_empty())?;
+    let branch = lines
+        .next()
+        .filter(|branch| !branch.is_empty() && *branch != "HEAD");
+    Some(SessionGit {
+        branch: branch.map(str::to_string),
+        commit: Some(commit.to_string()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_branch_and_commit_and_drops_detached_head() {
+        let git = parse_rev_parse_head("4f5934caa96e82bc32692c615e74feef28178191\nfeature/x\n")
+            .expect("git");
+        assert_eq!(git.branch.as_deref(), Some("feature/x"));
+        assert_eq!(git.label(), "feature/x@4f5934c");
+
+        let detached = parse_rev_parse_head("4f5934caa96e82bc\nHEAD\n").expect("git");
+        assert_eq!(detached.branch, None);
+        assert_eq!(parse_rev_parse_head(""), None);
+    }
+
+    #[test]
+    fn resolves_worktrees_to_the_main_checkout() {
+        assert_eq!(
+            parse_repo_root("/work/app-feature\n/work/app/.git\n"),
+            Some(PathBuf::from("/work/app"))
+        );
+        assert_eq!(
+            parse_repo_root("/work/app\n/work/app/.git\n"),
+            Some(PathBuf::from("/work/app"))
+        );
+        assert_eq!(
+            parse_repo_root("/work/sub\n/work/modules/sub.git\n"),
+            Some(PathBuf::from("/work/sub"))
+        );
+        assert_eq!(parse_repo_root(""), None);
+
+        let roots = [
+            PathBuf::from("/work/app"),
+            PathBuf::from("/work/app/vendor/lib"),
+        ]
+        .into_iter()
+        .collect::<BTreeSet<_>>();
+        assert_eq!(
+            enclosing_root(Path::new("/work/app/vendor/lib/src"), &roots),
+            Some(PathBuf::from("/work/app/vendor/lib"))
+        );
+        assert_eq!(
+            enclosing_root(Path::new("/work/app/docs"), &roots),
+            Some(PathBuf::from("/work/app"))
+        );
+        assert_eq!(enclosing_root(Path::new("/work/other"), &roots), None);
+    }
+}
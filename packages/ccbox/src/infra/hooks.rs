@@ -0,0 +1,129 @@
+use crate::domain::{LifecycleEvent, LifecycleEventKind};
+use serde::Deserialize;
+use std::io;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+const HOOK_TIMEOUT: Duration = Duration::from_secs(60);
+const HOOK_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// A shell command run on lifecycle events.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct HookConfig {
+    /// Event names to run on (see `LifecycleEventKind`); empty runs on all of them.
+    #[serde(default)]
+    pub events: Vec<String>,
+
+    /// Run with `sh -c`; `{{field}}` placeholders become single-quoted shell words.
+    pub command: String,
+}
+
+#[derive(Debug, Error)]
+pub enum HookError {
+    #[error("hook `{command}` failed to start: {source}")]
+    Spawn {
+        command: String,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("hook `{command}` exited with {status}")]
+    Failed { command: String, status: String },
+
+    #[error("hook `{command}` timed out after {}s", HOOK_TIMEOUT.as_secs())]
+    TimedOut { command: String },
+}
+
+impl HookConfig {
+    pub fn is_subscribed(&self, kind: LifecycleEventKind) -> bool {
+        self.events.is_empty() || self.events.iter().any(|event| event == kind.as_str())
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.command.trim().is_empty() {
+            return Err("hooks: command must not be empty".to_string());
+        }
+        for event in &self.events {
+            if LifecycleEventKind::parse(event).is_none() {
+                return Err(format!("hooks: unknown event `{event}`"));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Runs the hook for `event` and waits for it (killed after 60s). The event and its fields are
+/// also passed as `CCBOX_EVENT` and `CCBOX_<FIELD>` environment variables. Blocking; call it off
+/// the UI thread.
+pub fn run_hook(config: &HookConfig, event: &LifecycleEvent) -> Result<(), HookError> {
+    let command = event.render_shell(&config.command);
+    let mut shell = Command::new("sh");
+    shell
+        .arg("-c")
+        .arg(&command)
+        .env("CCBOX_EVENT", event.kind.as_str())
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    for (name, value) in &event.fields {
+        shell.env(format!("CCBOX_{}", name.to_ascii_uppercase()), value);
+    }
+
+    let mut child = shell.spawn().map_err(|source| HookError::Spawn {
+        command: config.command.clone(),
+        source,
+    })?;
+    let deadline = Instant::now() + HOOK_TIMEOUT;
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) if status.success() => return Ok(()),
+            Ok(Some(status)) => {
+                return Err(HookError::Failed {
+                    command: config.command.clone(),
+                    status: status.to_string(),
+                });
+            }
+            Ok(None) if Instant::now() < deadline => thread::sleep(HOOK_POLL_INTERVAL),
+            Ok(None) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(HookError::TimedOut {
+                    command: config.command.clone(),
+                });
+            }
+            Err(source) => {
+                return Err(HookError::Spawn {
+                    command: config.command.clone(),
+                    source,
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filters_events_and_rejects_unknown_ones() {
+        let config: HookConfig = serde_json::from_value(serde_json::json!({
+            "events": ["session.deleted", "task.completed"],
+            "command": "notify-send ccbox {{session_id}}"
+        }))
+        .expect("config");
+        assert_eq!(config.validate(), Ok(()));
+        assert!(config.is_subscribed(LifecycleEventKind::TaskCompleted));
+        assert!(!config.is_subscribed(LifecycleEventKind::ProcessStarted));
+
+        let bad: HookConfig = serde_json::from_value(serde_json::json!({
+            "events": ["session.created"],
+            "command": "true"
+        }))
+        .expect("config");
+        assert!(bad.validate().is_err());
+    }
+}
@@ -0,0 +1,41 @@
+/// The local broken-down time at `unix_seconds` (`localtime_r`), or `None` when it can't be
+/// converted. Shared by everything in infra that needs the local time zone.
+#[cfg(unix)]
+pub(super) fn local_tm(unix_seconds: i64) -> Option<libc::tm> {
+    use std::mem::MaybeUninit;
+
+    #[cfg(target_pointer_width = "64")]
+    let seconds: libc::time_t = unix_seconds;
+
+    #[cfg(target_pointer_width = "32")]
+    let seconds: libc::time_t =
+        unix_seconds.clamp(i64::from(i32::MIN), i64::from(i32::MAX)) as libc::time_t;
+
+    let mut tm = MaybeUninit::<libc::tm>::uninit();
+    let tm_ptr = unsafe { libc::localtime_r(&seconds, tm.as_mut_ptr()) };
+    if tm_ptr.is_null() {
+        return None;
+    }
+    Some(unsafe { tm.assume_init() })
+}
+
+/// Seconds east of UTC of the local time zone at `unix_ms`; 0 when unknown or off Unix.
+#[cfg(unix)]
+pub(super) fn local_utc_offset_seconds(unix_ms: i64) -> i64 {
+    let Some(tm) = local_tm(unix_ms.div_euclid(1000)) else {
+        return 0;
+    };
+
+    #[cfg(target_pointer_width = "64")]
+    let offset = tm.tm_gmtoff;
+
+    #[cfg(target_pointer_width = "32")]
+    let offset = i64::from(tm.tm_gmtoff);
+
+    offset
+}
+
+#[cfg(not(unix))]
+pub(super) fn local_utc_offset_seconds(_unix_ms: i64) -> i64 {
+    0
+}
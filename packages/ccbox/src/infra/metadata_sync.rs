@@ -0,0 +1,341 @@
+use super::tasks::{system_time_to_unix_ms, unix_ms_to_system_time};
+use super::{
+    LoadProjectPrefsError, LoadSessionAliasesError, LoadSessionProjectsError,
+    SaveProjectPrefsError, SaveSessionAliasesError, SaveSessionProjectsError, TaskStore,
+    TaskStoreError, load_project_prefs, load_session_aliases, load_session_projects,
+    save_project_prefs, save_session_aliases, save_session_projects,
+};
+use crate::domain::{
+    MetadataEntry, SessionKey, Task, TaskId, merge_metadata, metadata_to_apply,
+    stamp_local_metadata,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use thiserror::Error;
+
+/// Syncs ccbox-managed metadata with other machines through the registered relay.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct MetadataSyncConfig {
+    /// Shared sync group id (a UUID); every machine that should see the same names uses the
+    /// same group. Treat it like a password.
+    pub group: String,
+
+    #[serde(default = "default_interval_minutes")]
+    pub interval_minutes: u64,
+}
+
+fn default_interval_minutes() -> u64 {
+    5
+}
+
+impl MetadataSyncConfig {
+    pub fn interval(&self) -> Duration {
+        Duration::from_secs(self.interval_minutes.saturating_mul(60))
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if uuid::Uuid::parse_str(self.group.trim()).is_err() {
+            return Err("metadata_sync.group must be a UUID".to_string());
+        }
+        if self.interval_minutes == 0 {
+            return Err("metadata_sync.interval_minutes must be greater than 0".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum MetadataSyncError {
+    #[error(transparent)]
+    LoadAliases(#[from] LoadSessionAliasesError),
+
+    #[error(transparent)]
+    SaveAliases(#[from] SaveSessionAliasesError),
+
+    #[error(transparent)]
+    LoadSessionProjects(#[from] LoadSessionProjectsError),
+
+    #[error(transparent)]
+    SaveSessionProjects(#[from] SaveSessionProjectsError),
+
+    #[error(transparent)]
+    LoadProjectPrefs(#[from] LoadProjectPrefsError),
+
+    #[error(transparent)]
+    SaveProjectPrefs(#[from] SaveProjectPrefsError),
+
+    #[error(transparent)]
+    Tasks(#[from] TaskStoreError),
+
+    #[error("failed to read metadata sync state: {0}")]
+    ReadState(#[source] io::Error),
+
+    #[error("failed to write metadata sync state: {0}")]
+    WriteState(#[source] io::Error),
+
+    #[error("failed to parse metadata sync state: {0}")]
+    ParseState(#[from] serde_json::Error),
+
+    #[error("metadata sync failed: {0}")]
+    Exchange(String),
+}
+
+/// What the last sync agreed on, used to tell local edits from values pulled from elsewhere.
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct MetadataSyncState {
+    machine: String,
+    #[serde(default)]
+    synced_at_ms: Option<i64>,
+    #[serde(default)]
+    entries: Vec<MetadataEntry>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct SyncedTask {
+    project_path: PathBuf,
+    body: String,
+    created_at_unix_ms: i64,
+}
+
+fn metadata_sync_state_path(state_dir: &Path) -> PathBuf {
+    state_dir.join("metadata_sync.json")
+}
+
+fn load_sync_state(state_dir: &Path) -> Result<MetadataSyncState, MetadataSyncError> {
+    let mut state = match fs::read_to_string(metadata_sync_state_path(state_dir)) {
+        Ok(raw) => serde_json::from_str::<MetadataSyncState>(&raw)?,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => MetadataSyncState::default(),
+        Err(error) => return Err(MetadataSyncError::ReadState(error)),
+    };
+    if state.machine.is_empty() {
+        state.machine = uuid::Uuid::new_v4().to_string();
+    }
+    Ok(state)
+}
+
+fn save_sync_state(state_dir: &Path, state: &MetadataSyncState) -> Result<(), MetadataSyncError> {
+    let raw = serde_json::to_string_pretty(state)?;
+    fs::create_dir_all(state_dir).map_err(MetadataSyncError::WriteState)?;
+    fs::write(metadata_sync_state_path(state_dir), format!("{raw}\n"))
+        .map_err(MetadataSyncError::WriteState)
+}
+
+/// Current values of everything ccbox syncs, keyed by [`crate::domain::METADATA_KEY_PREFIXES`].
+fn collect_local_metadata(
+    state_dir: &Path,
+    tasks: &TaskStore,
+) -> Result<BTreeMap<String, String>, MetadataSyncError> {
+    let mut out = BTreeMap::new();
+    for (key, title) in load_session_aliases(state_dir)?.entries() {
+        out.insert(format!("alias:{key}"), title.to_string());
+    }
+    for (key, project) in load_session_projects(state_dir)?.entries() {
+        out.insert(format!("session_project:{key}"), project.to_string());
+    }
+    for (path, pref) in load_project_prefs(state_dir)?.projects {
+        if let Some(name) = pref.name {
+            out.insert(format!("project_name:{}", path.display()), name);
+        }
+        if pref.hidden {
+            out.insert(
+                format!("project_hidden:{}", path.display()),
+                "1".to_string(),
+            );
+        }
+    }
+    for entry in tasks.list_tasks()? {
+        let task = SyncedTask {
+            project_path: entry.task.project_path,
+            body: entry.task.body,
+            created_at_unix_ms: system_time_to_unix_ms(entry.task.created_at),
+        };
+        out.insert(
+            format!("task:{}", entry.task.id),
+            serde_json::to_string(&task)?,
+        );
+    }
+    Ok(out)
+}
+
+/// Writes merged winners back to the local stores. Returns how many entries changed; entries
+/// that don't parse are skipped.
+fn apply_metadata(
+    state_dir: &Path,
+    tasks: &TaskStore,
+    entries: &[&MetadataEntry],
+) -> Result<usize, MetadataSyncError> {
+    let mut aliases = load_session_aliases(state_dir)?;
+    let mut session_projects = load_session_projects(state_dir)?;
+    let mut prefs = load_project_prefs(state_dir)?;
+    let (mut aliases_changed, mut session_projects_changed, mut prefs_changed) =
+        (false, false, false);
+    let mut applied = 0;
+
+    for entry in entries {
+        let value = entry.value.as_deref().unwrap_or("");
+        let (prefix, rest) = entry
+            .key
+            .split_once(':')
+            .unwrap_or((entry.key.as_str(), ""));
+        match prefix {
+            "alias" => {
+                let Some(key) = SessionKey::parse(rest) else {
+                    continue;
+                };
+                aliases.set(key.engine, &key.id, value);
+                aliases_changed = true;
+            }
+            "session_project" => {
+                let Some(key) = SessionKey::parse(rest) else {
+                    continue;
+                };
+                session_projects.set(key.engine, &key.id, value);
+                session_projects_changed = true;
+            }
+            "project_name" => {
+                prefs.set_name(Path::new(rest), value);
+                prefs_changed = true;
+            }
+            "project_hidden" => {
+                prefs.set_hidden(Path::new(rest), entry.value.is_some());
+                prefs_changed = true;
+            }
+            "task" => {
+                let id = TaskId::new(rest.to_string());
+                match &entry.value {
+                    Some(raw) => {
+                        let Ok(synced) = serde_json::from_str::<SyncedTask>(raw) else {
+                            continue;
+                        };
+                        if tasks.has_task(&id)? {
+                            continue;
+                        }
+                        let created_at = unix_ms_to_system_time(synced.created_at_unix_ms);
+                        let task = Task {
+                            id,
+                            project_path: synced.project_path,
+                            body: synced.body,
+                            created_at,
+                            updated_at: created_at,
+                        };
+                        tasks.import_task(&task, &[], false)?;
+                    }
+                    None => {
+                        tasks.delete_task(&id)?;
+                    }
+                }
+            }
+            _ => continue,
+        }
+        applied += 1;
+    }
+
+    if aliases_changed {
+        save_session_aliases(state_dir, &aliases)?;
+    }
+    if session_projects_changed {
+        save_session_projects(state_dir, &session_projects)?;
+    }
+    if prefs_changed {
+        save_project_prefs(state_dir, &prefs)?;
+    }
+    Ok(applied)
+}
+
+/// Runs one sync round: stamps local edits since the last sync, hands the full document to
+/// `exchange` (which returns the merged document from the relay), and applies whatever other
+/// machines changed. Returns the number of entries applied locally.
+///
+/// On a machine's first sync its existing values are stamped at time zero, so names already
+/// synced from elsewhere win over them.
+pub fn sync_metadata(
+    state_dir: &Path,
+    tasks: &TaskStore,
+    exchange: impl FnOnce(Vec<MetadataEntry>) -> Result<Vec<MetadataEntry>, String>,
+) -> Result<usize, MetadataSyncError> {
+    let mut state = load_sync_state(state_dir)?;
+    let synced = state
+        .entries
+        .drain(..)
+        .map(|entry| (entry.key.clone(), entry))
+        .collect::<BTreeMap<_, _>>();
+    let current = collect_local_metadata(state_dir, tasks)?;
+    let now_ms = system_time_to_unix_ms(SystemTime::now());
+    let stamp_ms = if state.synced_at_ms.is_some() {
+        now_ms
+    } else {
+        0
+    };
+    let mut local = stamp_local_metadata(&synced, &current, &state.machine, stamp_ms);
+
+    let remote =
+        exchange(local.values().cloned().collect()).map_err(MetadataSyncError::Exchange)?;
+    merge_metadata(&mut local, remote);
+    let applied = apply_metadata(state_dir, tasks, &metadata_to_apply(&local, &current))?;
+
+    state.entries = local.into_values().collect();
+    state.synced_at_ms = Some(now_ms);
+    save_sync_state(state_dir, &state)?;
+    Ok(applied)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::SessionEngine;
+    use crate::infra::set_session_alias;
+    use tempfile::tempdir;
+
+    #[test]
+    fn sync_round_trips_aliases_and_project_names_between_machines() {
+        let laptop = tempdir().expect("tempdir");
+        let desktop = tempdir().expect("tempdir");
+        let laptop_tasks = TaskStore::open(laptop.path().join("tasks.db")).expect("tasks");
+        let desktop_tasks = TaskStore::open(desktop.path().join("tasks.db")).expect("tasks");
+        let mut relay: Vec<MetadataEntry> = Vec::new();
+        let mut exchange = |entries: Vec<MetadataEntry>| {
+            let mut merged = relay
+                .drain(..)
+                .map(|entry| (entry.key.clone(), entry))
+                .collect::<BTreeMap<_, _>>();
+            merge_metadata(&mut merged, entries);
+            relay = merged.into_values().collect();
+            Ok(relay.clone())
+        };
+
+        set_session_alias(laptop.path(), SessionEngine::Codex, "s1", "Fix login").expect("alias");
+        let mut prefs = load_project_prefs(laptop.path()).expect("prefs");
+        prefs.set_name(Path::new("/work/app"), "App");
+        save_project_prefs(laptop.path(), &prefs).expect("save prefs");
+        laptop_tasks
+            .create_task(Path::new("/work/app"), "write docs", &[])
+            .expect("task");
+
+        sync_metadata(laptop.path(), &laptop_tasks, &mut exchange).expect("laptop sync");
+        let applied =
+            sync_metadata(desktop.path(), &desktop_tasks, &mut exchange).expect("desktop sync");
+        assert_eq!(applied, 3);
+
+        let aliases = load_session_aliases(desktop.path()).expect("aliases");
+        assert_eq!(
+            aliases.title_for(SessionEngine::Codex, "s1"),
+            Some("Fix login")
+        );
+        let prefs = load_project_prefs(desktop.path()).expect("prefs");
+        assert_eq!(prefs.name_for(Path::new("/work/app")), Some("App"));
+        let tasks = desktop_tasks.list_tasks().expect("tasks");
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].task.body, "write docs");
+
+        set_session_alias(desktop.path(), SessionEngine::Codex, "s1", "").expect("clear alias");
+        sync_metadata(desktop.path(), &desktop_tasks, &mut exchange).expect("desktop sync");
+        sync_metadata(laptop.path(), &laptop_tasks, &mut exchange).expect("laptop sync");
+        let aliases = load_session_aliases(laptop.path()).expect("aliases");
+        assert_eq!(aliases.title_for(SessionEngine::Codex, "s1"), None);
+    }
+}
@@ -0,0 +1,154 @@
+use crate::domain::MetricsSnapshot;
+use serde::Deserialize;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+const METRICS_IO_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Where to serve `GET /metrics`.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct MetricsConfig {
+    /// `host:port`, e.g. `127.0.0.1:9464`.
+    pub listen: String,
+}
+
+impl MetricsConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        self.listen
+            .parse::<SocketAddr>()
+            .map(|_| ())
+            .map_err(|_| format!("metrics: invalid listen address `{}`", self.listen))
+    }
+}
+
+/// Counters shared between the event loop and the metrics server.
+#[derive(Debug, Default)]
+pub struct Metrics {
+    sessions_scanned: AtomicU64,
+    parse_failures: AtomicU64,
+    watcher_events: AtomicU64,
+    running_processes: AtomicU64,
+    tokens_indexed: AtomicU64,
+}
+
+impl Metrics {
+    pub fn record_scan(&self, sessions: usize, parse_failures: usize) {
+        self.sessions_scanned
+            .fetch_add(sessions as u64, Ordering::Relaxed);
+        self.parse_failures
+            .fetch_add(parse_failures as u64, Ordering::Relaxed);
+    }
+
+    pub fn record_watcher_event(&self) {
+        self.watcher_events.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn set_running_processes(&self, count: usize) {
+        self.running_processes
+            .store(count as u64, Ordering::Relaxed);
+    }
+
+    pub fn set_tokens_indexed(&self, tokens: u64) {
+        self.tokens_indexed.store(tokens, Ordering::Relaxed);
+    }
+
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        MetricsSnapshot {
+            sessions_scanned: self.sessions_scanned.load(Ordering::Relaxed),
+            parse_failures: self.parse_failures.load(Ordering::Relaxed),
+            watcher_events: self.watcher_events.load(Ordering::Relaxed),
+            running_processes: self.running_processes.load(Ordering::Relaxed),
+            tokens_indexed: self.tokens_indexed.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Binds `config.listen` and answers scrapes on a background thread. Returns the bound address.
+pub fn start_metrics_server(
+    config: &MetricsConfig,
+    metrics: Arc<Metrics>,
+) -> io::Result<SocketAddr> {
+    let listener = TcpListener::bind(config.listen.as_str())?;
+    let addr = listener.local_addr()?;
+    std::thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let _ = answer_scrape(stream, &metrics);
+        }
+    });
+    Ok(addr)
+}
+
+fn answer_scrape(stream: TcpStream, metrics: &Metrics) -> io::Result<()> {
+    stream.set_read_timeout(Some(METRICS_IO_TIMEOUT))?;
+    stream.set_write_timeout(Some(METRICS_IO_TIMEOUT))?;
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // Drain headers so clients don't see a reset before reading the response.
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 2 {
+        header.clear();
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let is_scrape = parts.next() == Some("GET")
+        && parts
+            .next()
+            .is_some_and(|target| target == "/metrics" || target.starts_with("/metrics?"));
+    let (status, content_type, body) = if is_scrape {
+        (
+            "200 OK",
+            "text/plain; version=0.0.4",
+            metrics.snapshot().render_prometheus(),
+        )
+    } else {
+        ("404 Not Found", "text/plain", "not found\n".to_string())
+    };
+    let mut stream = reader.into_inner();
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )?;
+    stream.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    fn get(addr: SocketAddr, target: &str) -> io::Result<String> {
+        let mut stream = TcpStream::connect(addr)?;
+        write!(stream, "GET {target} HTTP/1.1\r\nHost: localhost\r\n\r\n")?;
+        let mut response = String::new();
+        stream.read_to_string(&mut response)?;
+        Ok(response)
+    }
+
+    #[test]
+    fn serves_counters_on_metrics_path_only() -> io::Result<()> {
+        let metrics = Arc::new(Metrics::default());
+        metrics.record_scan(3, 1);
+        metrics.record_watcher_event();
+        metrics.set_running_processes(2);
+        let config = MetricsConfig {
+            listen: "127.0.0.1:0".to_string(),
+        };
+        assert_eq!(config.validate(), Ok(()));
+        let addr = start_metrics_server(&config, metrics.clone())?;
+
+        let response = get(addr, "/metrics")?;
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("ccbox_sessions_scanned_total 3\n"));
+        assert!(response.contains("ccbox_parse_failures_total 1\n"));
+        assert!(response.contains("ccbox_running_processes 2\n"));
+
+        assert!(get(addr, "/")?.starts_with("HTTP/1.1 404 Not Found\r\n"));
+        Ok(())
+    }
+}
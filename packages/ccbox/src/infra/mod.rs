@@ -1,33 +1,82 @@
+mod audit_log;
 mod claude;
 mod clipboard;
 mod codex_fork;
+mod compressed_log;
+mod config;
+mod custom_engines;
 mod delete;
+mod editor;
+mod encryption;
+mod engine_filters;
+mod external_agents;
 mod gemini;
+mod git;
+mod hooks;
+mod local_time;
+mod metadata_sync;
+mod metrics;
 mod opencode;
+mod path_completion;
+mod proc_usage;
 mod processes;
+mod project_prefs;
+mod prompt_file;
+mod remote_sources;
 mod scan;
 mod scan_all;
+mod scan_pool;
 mod session_aliases;
 mod session_detail;
 mod session_index;
 mod session_projects;
+mod skill_usage;
+mod startup_profile;
+mod task_pack;
+mod task_registry;
 mod tasks;
 mod update;
 mod watch;
+mod webhooks;
+mod worktree;
 
+pub use audit_log::*;
 pub use claude::*;
 pub use clipboard::*;
 pub use codex_fork::*;
+pub use compressed_log::*;
+pub use config::*;
+pub use custom_engines::*;
 pub use delete::*;
+pub use editor::*;
+pub use encryption::*;
+pub use engine_filters::*;
+pub use external_agents::*;
 pub use gemini::*;
+pub use git::*;
+pub use hooks::*;
+pub use metadata_sync::*;
+pub use metrics::*;
 pub use opencode::*;
+pub use path_completion::*;
+pub use proc_usage::*;
 pub use processes::*;
+pub use project_prefs::*;
+pub use prompt_file::*;
+pub use remote_sources::*;
 pub use scan::*;
 pub use scan_all::*;
+pub use scan_pool::*;
 pub use session_aliases::*;
 pub use session_detail::*;
 pub use session_index::*;
 pub use session_projects::*;
+pub use skill_usage::*;
+pub use startup_profile::*;
+pub use task_pack::*;
+pub use task_registry::*;
 pub use tasks::*;
 pub use update::*;
 pub use watch::*;
+pub use webhooks::*;
+pub use worktree::*;
@@ -1,6 +1,6 @@
 use crate::domain::{SessionEngine, SessionMeta, SessionSummary, make_session_summary};
 use crate::infra::{ResolveCcboxStateDirError, ScanWarningCount, resolve_ccbox_state_dir};
-use rusqlite::{Connection, OpenFlags};
+use rusqlite::{Connection, OpenFlags, OptionalExtension};
 use serde_json::Value;
 use std::collections::BTreeMap;
 use std::fs;
@@ -161,6 +161,7 @@ pub fn scan_opencode_db_with_state_dir(db_path: &Path, state_dir: &Path) -> Open
                 id,
                 cwd,
                 started_at_rfc3339,
+                git: None,
             },
             log_path,
             title,
@@ -805,6 +806,103 @@ fn assistant_total_tokens(value: &Value) -> Option<u64> {
     Some(input + output + reasoning + cache_read + cache_write)
 }
 
+#[derive(Debug, Error)]
+pub enum OpenCodeWriteBackError {
+    #[error(transparent)]
+    ResolveOpenCodeDbPath(#[from] ResolveOpenCodeDbPathError),
+
+    #[error("OpenCode DB not found: {0}")]
+    DbMissing(String),
+
+    #[error("SQLite error: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+
+    #[error("OpenCode session not found: {0}")]
+    SessionNotFound(String),
+}
+
+/// What a project write-back changed: the session always gets the new directory; it joins an
+/// OpenCode project only when one already has that worktree.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OpenCodeProjectWriteBack {
+    MovedToProject,
+    DirectoryOnly,
+}
+
+/// Sets the session's title in the OpenCode DB.
+pub fn write_opencode_session_title(
+    db_path: &Path,
+    session_id: &str,
+    title: &str,
+) -> Result<(), OpenCodeWriteBackError> {
+    let conn = open_db_for_write_back(db_path)?;
+    let updated = conn.execute(
+        "UPDATE session SET title = ?1 WHERE id = ?2",
+        rusqlite::params![title, session_id],
+    )?;
+    if updated == 0 {
+        return Err(OpenCodeWriteBackError::SessionNotFound(
+            session_id.to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Points the session at `project_path` in the OpenCode DB. OpenCode derives project ids itself,
+/// so no project row is created.
+pub fn write_opencode_session_project(
+    db_path: &Path,
+    session_id: &str,
+    project_path: &Path,
+) -> Result<OpenCodeProjectWriteBack, OpenCodeWriteBackError> {
+    let mut conn = open_db_for_write_back(db_path)?;
+    let directory = project_path.display().to_string();
+    let tx = conn.transaction()?;
+    let project_id = tx
+        .query_row(
+            "SELECT id FROM project WHERE worktree = ?1 ORDER BY time_updated DESC LIMIT 1",
+            [&directory],
+            |row| row.get::<_, String>(0),
+        )
+        .optional()?;
+    let updated = match &project_id {
+        Some(project_id) => tx.execute(
+            "UPDATE session SET directory = ?1, project_id = ?2 WHERE id = ?3",
+            rusqlite::params![directory, project_id, session_id],
+        )?,
+        None => tx.execute(
+            "UPDATE session SET directory = ?1 WHERE id = ?2",
+            rusqlite::params![directory, session_id],
+        )?,
+    };
+    if updated == 0 {
+        return Err(OpenCodeWriteBackError::SessionNotFound(
+            session_id.to_string(),
+        ));
+    }
+    tx.commit()?;
+    Ok(if project_id.is_some() {
+        OpenCodeProjectWriteBack::MovedToProject
+    } else {
+        OpenCodeProjectWriteBack::DirectoryOnly
+    })
+}
+
+fn open_db_for_write_back(db_path: &Path) -> Result<Connection, OpenCodeWriteBackError> {
+    if !db_path.exists() {
+        return Err(OpenCodeWriteBackError::DbMissing(
+            db_path.display().to_string(),
+        ));
+    }
+    let conn = Connection::open_with_flags(
+        db_path,
+        OpenFlags::SQLITE_OPEN_READ_WRITE | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )?;
+    // OpenCode may hold the DB while running; wait a little for its write lock.
+    conn.busy_timeout(Duration::from_secs(2))?;
+    Ok(conn)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1004,4 +1102,53 @@ mod tests {
         assert!(kinds.contains(&crate::domain::TimelineItemKind::Assistant));
         assert!(kinds.contains(&crate::domain::TimelineItemKind::TokenCount));
     }
+
+    #[test]
+    fn writes_titles_and_projects_back() {
+        let dir = tempdir().expect("tempdir");
+        let db_path = dir.path().join("opencode.db");
+        let conn = create_minimal_db(&db_path);
+        for (id, worktree) in [("p1", "/work/a"), ("p2", "/work/b")] {
+            conn.execute(
+                "INSERT INTO project (id, worktree, name, time_created, time_updated, sandboxes) VALUES (?1, ?2, NULL, 1, 1, '[]')",
+                params![id, worktree],
+            )
+            .expect("project");
+        }
+        conn.execute(
+            "INSERT INTO session (id, project_id, parent_id, slug, directory, title, version, time_created, time_updated, time_archived) VALUES ('s1', 'p1', NULL, 's', '/work/a', 't', 'v', 10, 20, NULL)",
+            [],
+        )
+        .expect("session");
+
+        write_opencode_session_title(&db_path, "s1", "Fix login").expect("title");
+        assert_eq!(
+            write_opencode_session_project(&db_path, "s1", Path::new("/work/b")).expect("move"),
+            OpenCodeProjectWriteBack::MovedToProject
+        );
+        let row: (String, String, String) = conn
+            .query_row(
+                "SELECT title, project_id, directory FROM session WHERE id = 's1'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .expect("row");
+        assert_eq!(
+            row,
+            (
+                "Fix login".to_string(),
+                "p2".to_string(),
+                "/work/b".to_string()
+            )
+        );
+
+        assert_eq!(
+            write_opencode_session_project(&db_path, "s1", Path::new("/work/c")).expect("move"),
+            OpenCodeProjectWriteBack::DirectoryOnly
+        );
+        assert!(matches!(
+            write_opencode_session_title(&db_path, "missing", "x"),
+            Err(OpenCodeWriteBackError::SessionNotFound(_))
+        ));
+    }
 }
@@ -0,0 +1,86 @@
+use crate::domain::expand_home;
+use std::fs;
+use std::path::{MAIN_SEPARATOR, Path, PathBuf};
+
+const MAX_COMPLETIONS: usize = 50;
+
+/// Filesystem view of a typed directory path.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PathCompletion {
+    /// Child directories of the typed parent whose names start with the typed last segment,
+    /// spelled the way the user typed the parent (`~/` stays `~/`), each ending in a separator.
+    pub candidates: Vec<String>,
+    /// The input as an absolute path, when it names an existing directory.
+    pub resolved_dir: Option<PathBuf>,
+}
+
+pub fn complete_dir_path(input: &str) -> PathCompletion {
+    let input = input.trim();
+    if input.is_empty() {
+        return PathCompletion::default();
+    }
+    let home = dirs::home_dir();
+
+    let resolved_dir = Some(expand_home(Path::new(input), home.as_deref()))
+        .filter(|path| path.is_dir())
+        .and_then(|path| std::path::absolute(path).ok());
+
+    let split_at = input
+        .rfind(['/', MAIN_SEPARATOR])
+        .map_or(0, |index| index + 1);
+    let (parent, prefix) = input.split_at(split_at);
+    let parent_dir = if parent.is_empty() {
+        PathBuf::from(".")
+    } else {
+        expand_home(Path::new(parent), home.as_deref())
+    };
+
+    let mut candidates = fs::read_dir(&parent_dir)
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_ok_and(|kind| kind.is_dir()))
+        .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+        .filter(|name| {
+            name.starts_with(prefix) && (prefix.starts_with('.') || !name.starts_with('.'))
+        })
+        .map(|name| format!("{parent}{name}{MAIN_SEPARATOR}"))
+        .collect::<Vec<_>>();
+    candidates.sort();
+    candidates.truncate(MAX_COMPLETIONS);
+
+    PathCompletion {
+        candidates,
+        resolved_dir,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn lists_matching_child_dirs_and_resolves_existing_input() {
+        let dir = tempdir().expect("tempdir");
+        for name in ["app-api", "app-web", ".app-cache", "docs"] {
+            fs::create_dir(dir.path().join(name)).expect("mkdir");
+        }
+        fs::write(dir.path().join("app-notes.txt"), "").expect("write");
+        let root = dir.path().display().to_string();
+
+        let completion = complete_dir_path(&format!("{root}/app"));
+        assert_eq!(
+            completion.candidates,
+            vec![
+                format!("{root}/app-api{MAIN_SEPARATOR}"),
+                format!("{root}/app-web{MAIN_SEPARATOR}"),
+            ]
+        );
+        assert_eq!(completion.resolved_dir, None);
+
+        let completion = complete_dir_path(&format!("{root}/docs"));
+        assert_eq!(completion.resolved_dir, Some(dir.path().join("docs")));
+        assert_eq!(complete_dir_path("  "), PathCompletion::default());
+    }
+}
@@ -0,0 +1,200 @@
+use std::collections::HashMap;
+use std::time::Instant;
+#[cfg(not(target_os = "linux"))]
+use std::time::SystemTime;
+#[cfg(target_os = "linux")]
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// CPU and memory use of a running process, as of the latest sample.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ProcessUsage {
+    /// Share of one core used since the previous sample (can exceed 100 for multi-threaded work).
+    pub cpu_percent: f64,
+    pub rss_bytes: u64,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct ProcStat {
+    ppid: u32,
+    cpu_ticks: u64,
+    rss_pages: u64,
+}
+
+/// Samples `/proc/<pid>/stat` and turns consecutive CPU tick counts into a usage percentage.
+/// Always yields `None` on platforms without procfs.
+#[derive(Debug, Default)]
+pub struct ProcessUsageSampler {
+    previous: HashMap<u32, (u64, Instant)>,
+}
+
+impl ProcessUsageSampler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Samples every pid in `pids`; pids not listed are forgotten. The first sample of a pid
+    /// reports 0% CPU.
+    pub fn sample(&mut self, pids: &[u32]) -> HashMap<u32, ProcessUsage> {
+        self.previous.retain(|pid, _| pids.contains(pid));
+        let now = Instant::now();
+        let mut usage = HashMap::new();
+        for &pid in pids {
+            let Some(stat) = read_proc_stat(pid) else {
+                self.previous.remove(&pid);
+                continue;
+            };
+            let cpu_percent = match self.previous.get(&pid) {
+                Some((ticks, at)) => {
+                    let elapsed = now.duration_since(*at).as_secs_f64();
+                    cpu_percent_between(*ticks, stat.cpu_ticks, elapsed, clock_ticks_per_second())
+                }
+                None => 0.0,
+            };
+            self.previous.insert(pid, (stat.cpu_ticks, now));
+            usage.insert(
+                pid,
+                ProcessUsage {
+                    cpu_percent,
+                    rss_bytes: stat.rss_pages.saturating_mul(page_size()),
+                },
+            );
+        }
+        usage
+    }
+}
+
+fn cpu_percent_between(before: u64, after: u64, elapsed_secs: f64, ticks_per_second: u64) -> f64 {
+    if elapsed_secs <= 0.0 || ticks_per_second == 0 {
+        return 0.0;
+    }
+    let used_secs = after.saturating_sub(before) as f64 / ticks_per_second as f64;
+    used_secs / elapsed_secs * 100.0
+}
+
+/// Parses the parent pid, utime+stime and rss fields of a `/proc/<pid>/stat` line. The command name is
+/// parenthesised and may contain spaces, so fields are counted from the last `)`.
+fn parse_proc_stat(raw: &str) -> Option<ProcStat> {
+    let (_, rest) = raw.rsplit_once(')')?;
+    let fields = rest.split_whitespace().collect::<Vec<_>>();
+    // `fields[0]` is field 3 (state): ppid is field 4, utime 14, stime 15, rss 24.
+    let ppid = fields.get(1)?.parse::<u32>().ok()?;
+    let utime = fields.get(11)?.parse::<u64>().ok()?;
+    let stime = fields.get(12)?.parse::<u64>().ok()?;
+    let rss_pages = fields.get(21)?.parse::<u64>().ok()?;
+    Some(ProcStat {
+        ppid,
+        cpu_ticks: utime.saturating_add(stime),
+        rss_pages,
+    })
+}
+
+/// Field 22 of `/proc/<pid>/stat`: when the process started, in clock ticks since boot. A pid
+/// reused by another program has a different value.
+fn parse_proc_start_ticks(raw: &str) -> Option<u64> {
+    let (_, rest) = raw.rsplit_once(')')?;
+    rest.split_whitespace().nth(19)?.parse().ok()
+}
+
+#[cfg(target_os = "linux")]
+pub fn process_start_ticks(pid: u32) -> Option<u64> {
+    let raw = std::fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+    parse_proc_start_ticks(&raw)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn process_start_ticks(_pid: u32) -> Option<u64> {
+    None
+}
+
+/// Wall-clock start of a process: its start ticks after the boot time in `/proc/stat`.
+#[cfg(target_os = "linux")]
+pub fn process_started_at(pid: u32) -> Option<SystemTime> {
+    let stat = std::fs::read_to_string("/proc/stat").ok()?;
+    let boot_secs = stat
+        .lines()
+        .find_map(|line| line.strip_prefix("btime "))?
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+    let ticks = process_start_ticks(pid)?;
+    let ticks_per_second = clock_ticks_per_second().max(1);
+    Some(
+        UNIX_EPOCH
+            + Duration::from_secs(boot_secs)
+            + Duration::from_millis(ticks.saturating_mul(1000) / ticks_per_second),
+    )
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn process_started_at(_pid: u32) -> Option<SystemTime> {
+    None
+}
+
+/// Parent pid from `/proc/<pid>/stat`. Always `None` on platforms without procfs.
+pub fn process_parent_pid(pid: u32) -> Option<u32> {
+    read_proc_stat(pid).map(|stat| stat.ppid)
+}
+
+#[cfg(target_os = "linux")]
+fn read_proc_stat(pid: u32) -> Option<ProcStat> {
+    let raw = std::fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+    parse_proc_stat(&raw)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_proc_stat(_pid: u32) -> Option<ProcStat> {
+    None
+}
+
+#[cfg(target_os = "linux")]
+fn clock_ticks_per_second() -> u64 {
+    // SAFETY: `sysconf` only reads a configuration value.
+    let ticks = unsafe { libc::sysconf(libc::_SC_CLK_TCK) };
+    u64::try_from(ticks).unwrap_or(100)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn clock_ticks_per_second() -> u64 {
+    100
+}
+
+#[cfg(target_os = "linux")]
+fn page_size() -> u64 {
+    // SAFETY: `sysconf` only reads a configuration value.
+    let size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+    u64::try_from(size).unwrap_or(4096)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn page_size() -> u64 {
+    4096
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_proc_stat_with_spaces_in_command_name() {
+        let raw = "4242 (node (agent) x) S 1 4242 4242 0 -1 4194560 1200 0 0 0 \
+                   350 50 0 0 20 0 11 0 123456 987654321 2560 18446744073709551615";
+        assert_eq!(
+            parse_proc_stat(raw),
+            Some(ProcStat {
+                ppid: 1,
+                cpu_ticks: 400,
+                rss_pages: 2560
+            })
+        );
+        assert_eq!(parse_proc_stat("4242 (node) S 1"), None);
+        assert_eq!(parse_proc_start_ticks(raw), Some(123456));
+    }
+
+    #[test]
+    fn cpu_percent_is_relative_to_elapsed_time() {
+        assert_eq!(cpu_percent_between(100, 150, 1.0, 100), 50.0);
+        assert_eq!(cpu_percent_between(100, 400, 1.5, 100), 200.0);
+        assert_eq!(cpu_percent_between(100, 90, 1.0, 100), 0.0);
+        assert_eq!(cpu_percent_between(100, 150, 0.0, 100), 0.0);
+    }
+}
@@ -1,23 +1,42 @@
 use crate::domain::AgentEngine;
 use crate::domain::SpawnIoMode;
+use crate::domain::{
+    ProcessLimitsConfig, ProjectEnvConfig, ResourceLimits, RetryPolicy, SpawnArgsConfig, TaskId,
+    spawn_args_label,
+};
+use crate::infra::{
+    ArtifactCipher, create_private_dir, create_private_file, is_compressed_log, is_jsonl_log_path,
+    is_sealed_artifact, process_start_ticks, read_artifact, read_decompressed_log,
+};
 use portable_pty::{CommandBuilder, MasterPty, PtySize, native_pty_system};
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs::{self, File, OpenOptions};
 use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
 use std::process::{Child, Command, Stdio};
 use std::sync::mpsc::Sender;
 use std::sync::{Arc, Mutex};
-use std::time::{Duration, SystemTime};
+use std::time::{Duration, Instant, SystemTime};
 use thiserror::Error;
 use time::format_description::well_known::Rfc3339;
 use time::{Duration as TimeDuration, OffsetDateTime};
 
 const SESSION_LOG_WAIT_TIMEOUT: Duration = Duration::from_secs(20);
 const SESSION_LOG_WAIT_POLL: Duration = Duration::from_millis(200);
-
-#[derive(Clone, Debug)]
+/// Written to a process's log dir once it exits for good (no retry pending).
+const EXIT_CODE_FILE: &str = "exit_code";
+/// Spawn metadata, written at spawn (and on each retry), so a restarted ccbox can list the
+/// process again (`restore_processes`) and `ccbox kill` in another shell can find it.
+const RECORD_FILE: &str = "process.json";
+/// `<session id>\n<session log path>\n`, once the agent has reported its session.
+const SESSION_FILE: &str = "session";
+/// Older records stay on disk but are not listed after a restart.
+const RESTORED_PROCESS_LIMIT: usize = 100;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
 pub enum SpawnedAgentIo {
     Pipes {
         stdout_path: PathBuf,
@@ -57,6 +76,124 @@ pub enum ProcessSignal {
 pub struct ProcessExit {
     pub process_id: String,
     pub exit_code: Option<i32>,
+    /// Set when the retry policy re-spawns this process after the given delay.
+    pub retry_in: Option<Duration>,
+}
+
+/// How a spawned process ended, as recorded next to its logs by the ccbox that owned it.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RecordedExit {
+    Code(i32),
+    Signal,
+}
+
+/// A spawned process whose owning ccbox has not recorded an exit yet.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecordedProcess {
+    pub id: String,
+    pub pid: u32,
+    pub project_path: PathBuf,
+}
+
+/// A process from the registry an earlier ccbox left in the process logs dir.
+#[derive(Clone, Debug)]
+pub struct RestoredProcess {
+    pub process: SpawnedAgentProcess,
+    pub session_id: Option<String>,
+    pub session_log_path: Option<PathBuf>,
+    /// The recorded pid is still alive and was not reused by another program.
+    pub is_running: bool,
+    /// `None` for running processes and for ones whose ccbox quit before they ended.
+    pub exit: Option<RecordedExit>,
+    pub links: ProcessLinks,
+}
+
+/// What ties a process to the rest of ccbox (task, batch, A/B pair, label, retries); kept in
+/// its record so a restarted ccbox groups and links it the same way.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProcessLinks {
+    pub task_id: Option<TaskId>,
+    pub task_title: Option<String>,
+    pub batch: Option<u32>,
+    pub compare: Option<u32>,
+    pub label: Option<String>,
+    pub retry_policy: RetryPolicy,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct ProcessRecordFile {
+    pid: u32,
+    /// Kernel start time of the pid (Linux), to tell a reused pid from the agent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    start_ticks: Option<u64>,
+    engine: String,
+    project_path: PathBuf,
+    started_at_ms: i64,
+    prompt_preview: String,
+    io: SpawnedAgentIo,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    task_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    task_title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    batch: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    compare: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    label: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    retry: Option<RetryRecord>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct RetryRecord {
+    max_attempts: u32,
+    backoff_ms: u64,
+    quick_exit_window_ms: u64,
+}
+
+impl ProcessRecordFile {
+    fn set_links(&mut self, links: &ProcessLinks) {
+        self.task_id = links.task_id.as_ref().map(TaskId::to_string);
+        self.task_title = links.task_title.clone();
+        self.batch = links.batch;
+        self.compare = links.compare;
+        self.label = links.label.clone();
+        self.retry = links.retry_policy.is_enabled().then(|| RetryRecord {
+            max_attempts: links.retry_policy.max_attempts,
+            backoff_ms: duration_ms(links.retry_policy.backoff),
+            quick_exit_window_ms: duration_ms(links.retry_policy.quick_exit_window),
+        });
+    }
+
+    fn links(&self) -> ProcessLinks {
+        ProcessLinks {
+            task_id: self.task_id.clone().map(TaskId::new),
+            task_title: self.task_title.clone(),
+            batch: self.batch,
+            compare: self.compare,
+            label: self.label.clone(),
+            retry_policy: self
+                .retry
+                .as_ref()
+                .map_or(RetryPolicy::disabled(), |retry| RetryPolicy {
+                    max_attempts: retry.max_attempts,
+                    backoff: Duration::from_millis(retry.backoff_ms),
+                    quick_exit_window: Duration::from_millis(retry.quick_exit_window_ms),
+                }),
+        }
+    }
+}
+
+fn duration_ms(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
+#[derive(Debug)]
+pub struct ProcessRetry {
+    pub process_id: String,
+    pub attempt: u32,
+    pub result: Result<SpawnedAgentProcess, SpawnAgentProcessError>,
 }
 
 #[derive(Debug, Error)]
@@ -73,6 +210,13 @@ pub enum SpawnAgentProcessError {
     #[error("failed to write prompt file: {0}")]
     WritePrompt(io::Error),
 
+    #[error("failed to decrypt image {path}: {source}")]
+    StageImage {
+        path: String,
+        #[source]
+        source: io::Error,
+    },
+
     #[error("failed to spawn process: {0}")]
     Spawn(io::Error),
 
@@ -117,6 +261,15 @@ pub enum AttachTtyError {
 
     #[error("failed to attach: {0}")]
     Attach(String),
+
+    #[error("failed to read scrollback: {0}")]
+    Scrollback(#[source] io::Error),
+}
+
+/// A live TTY attachment: the transcript tail to replay first, then every chunk written after it.
+pub struct TtyAttachment {
+    pub scrollback: Vec<u8>,
+    pub output: std::sync::mpsc::Receiver<Vec<u8>>,
 }
 
 #[derive(Debug, Error)]
@@ -150,6 +303,43 @@ pub struct ProcessManager {
     next_id: u64,
     pipes_children: HashMap<String, Child>,
     tty_children: HashMap<String, TtyProcess>,
+    retries: HashMap<String, RetryState>,
+    limits: ProcessLimitsConfig,
+    spawn_args: SpawnArgsConfig,
+    project_env: ProjectEnvConfig,
+    tty_scrollback_bytes: u64,
+    artifact_cipher: Option<ArtifactCipher>,
+    kill_grace: Duration,
+    /// Stopped processes and when they get SIGKILL if still running.
+    terminating: HashMap<String, Instant>,
+    /// Pids of processes an earlier ccbox spawned that were still running on restore.
+    adopted: HashMap<String, u32>,
+}
+
+struct RetryState {
+    engine: AgentEngine,
+    project_path: PathBuf,
+    prompt: String,
+    images: Vec<PathBuf>,
+    model: Option<String>,
+    io_mode: SpawnIoMode,
+    policy: RetryPolicy,
+    attempt: u32,
+    started: Instant,
+    due_at: Option<Instant>,
+}
+
+/// What to run for one spawned agent.
+#[derive(Clone, Copy, Debug)]
+pub struct AgentSpawn<'a> {
+    pub engine: AgentEngine,
+    pub project_path: &'a Path,
+    pub prompt: &'a str,
+    /// Passed to engines that take image inputs on the command line (Codex `--image`).
+    pub images: &'a [PathBuf],
+    /// `--model` for the engine; `None` keeps its default.
+    pub model: Option<&'a str>,
+    pub io_mode: SpawnIoMode,
 }
 
 struct TtyProcess {
@@ -157,6 +347,7 @@ struct TtyProcess {
     master: Box<dyn MasterPty + Send>,
     writer: Box<dyn Write + Send>,
     live_tx: Arc<Mutex<Option<Sender<Vec<u8>>>>>,
+    transcript_path: PathBuf,
 }
 
 impl ProcessManager {
@@ -164,7 +355,7 @@ impl ProcessManager {
         sessions_dir: PathBuf,
         tx: Sender<ProcessSignal>,
     ) -> Result<Self, ProcessManagerError> {
-        let logs_dir = sessions_dir.join(".ccbox").join("processes");
+        let logs_dir = process_logs_dir(&sessions_dir);
         fs::create_dir_all(&logs_dir)?;
 
         Ok(Self {
@@ -174,9 +365,113 @@ impl ProcessManager {
             next_id: 1,
             pipes_children: HashMap::new(),
             tty_children: HashMap::new(),
+            retries: HashMap::new(),
+            limits: ProcessLimitsConfig::default(),
+            spawn_args: SpawnArgsConfig::default(),
+            project_env: ProjectEnvConfig::default(),
+            tty_scrollback_bytes: super::DEFAULT_TTY_SCROLLBACK_BYTES,
+            artifact_cipher: None,
+            kill_grace: super::DEFAULT_KILL_GRACE,
+            terminating: HashMap::new(),
+            adopted: HashMap::new(),
         })
     }
 
+    /// The next `pN` whose log dir does not exist yet. Creating the dir claims the id, so ccbox
+    /// instances sharing a sessions dir (the TUI, `ccbox run`, `ccbox serve`) never reuse one and
+    /// `ccbox await` can tell their processes apart.
+    fn claim_process_id(&mut self) -> String {
+        loop {
+            let id = format!("p{}", self.next_id);
+            self.next_id = self.next_id.saturating_add(1);
+            match fs::create_dir(self.logs_dir.join(&id)) {
+                Err(error) if error.kind() == io::ErrorKind::AlreadyExists => continue,
+                // Other errors resurface when the spawn creates its log files.
+                _ => return id,
+            }
+        }
+    }
+
+    fn record_process(&self, spawned: &SpawnedAgentProcess) {
+        let dir = self.logs_dir.join(&spawned.id);
+        let mut record = ProcessRecordFile {
+            pid: spawned.pid,
+            start_ticks: process_start_ticks(spawned.pid),
+            engine: super::tasks::agent_engine_id(spawned.engine).to_string(),
+            project_path: spawned.project_path.clone(),
+            started_at_ms: super::tasks::system_time_to_unix_ms(spawned.started_at),
+            prompt_preview: spawned.prompt_preview.clone(),
+            io: spawned.io.clone(),
+            task_id: None,
+            task_title: None,
+            batch: None,
+            compare: None,
+            label: None,
+            retry: None,
+        };
+        // A retry re-records the new attempt; the links recorded for the first one still hold.
+        if let Some(previous) = read_process_record(&dir) {
+            record.set_links(&previous.links());
+        }
+        write_process_record(&dir, &record);
+    }
+
+    /// Stores `links` in the record of `process_id`, so a restarted ccbox restores them.
+    pub fn record_process_links(&self, process_id: &str, links: &ProcessLinks) {
+        let dir = self.logs_dir.join(process_id);
+        if let Some(mut record) = read_process_record(&dir) {
+            record.set_links(links);
+            write_process_record(&dir, &record);
+        }
+    }
+
+    /// Lists the processes earlier ccbox runs recorded and adopts the ones still running, so
+    /// they can be stopped and their exit is reported by `poll_exits`.
+    pub fn restore_processes(&mut self) -> io::Result<Vec<RestoredProcess>> {
+        let restored = recorded_processes(&self.logs_dir)?;
+        for process in restored.iter().filter(|process| process.is_running) {
+            self.adopted
+                .insert(process.process.id.clone(), process.process.pid);
+        }
+        Ok(restored)
+    }
+
+    /// Replaces the resource limits applied to processes spawned from now on.
+    pub fn set_process_limits(&mut self, limits: ProcessLimitsConfig) {
+        self.limits = limits;
+    }
+
+    /// Replaces the extra engine arguments added to processes spawned from now on.
+    pub fn set_spawn_args(&mut self, spawn_args: SpawnArgsConfig) {
+        self.spawn_args = spawn_args;
+    }
+
+    /// Replaces the per-project environment variables of processes spawned from now on.
+    pub fn set_project_env(&mut self, project_env: ProjectEnvConfig) {
+        self.project_env = project_env;
+    }
+
+    /// `project_env` for `project_path`, with `${NAME}` expanded from ccbox's own environment.
+    fn resolve_project_env(&self, project_path: &Path) -> BTreeMap<String, String> {
+        self.project_env
+            .resolve(project_path, |name| std::env::var(name).ok())
+    }
+
+    pub fn set_kill_grace(&mut self, grace: Duration) {
+        self.kill_grace = grace;
+    }
+
+    /// Sets how much of a TTY transcript is replayed on attach; 0 disables the replay.
+    pub fn set_tty_scrollback_bytes(&mut self, bytes: u64) {
+        self.tty_scrollback_bytes = bytes;
+    }
+
+    /// When set, process logs, transcripts and prompts are owner-only while their process runs
+    /// and sealed to `<file>.age` once it ends; sealed task images are decrypted for the run.
+    pub fn set_artifact_cipher(&mut self, cipher: Option<ArtifactCipher>) {
+        self.artifact_cipher = cipher;
+    }
+
     pub fn spawn_agent_process(
         &mut self,
         engine: AgentEngine,
@@ -184,10 +479,92 @@ impl ProcessManager {
         prompt: &str,
         io_mode: SpawnIoMode,
     ) -> Result<SpawnedAgentProcess, SpawnAgentProcessError> {
-        match io_mode {
-            SpawnIoMode::Pipes => self.spawn_agent_process_pipes(engine, project_path, prompt),
-            SpawnIoMode::Tty => self.spawn_agent_process_tty(engine, project_path, prompt),
+        self.spawn_agent_process_with_retry(
+            AgentSpawn {
+                engine,
+                project_path,
+                prompt,
+                images: &[],
+                model: None,
+                io_mode,
+            },
+            RetryPolicy::disabled(),
+        )
+    }
+
+    /// Spawns an agent process that is re-spawned (same process id, fresh `attempt-N` log dir)
+    /// when it fails quickly, as decided by `retry`. Re-spawns happen in `poll_retries`.
+    pub fn spawn_agent_process_with_retry(
+        &mut self,
+        spawn: AgentSpawn<'_>,
+        retry: RetryPolicy,
+    ) -> Result<SpawnedAgentProcess, SpawnAgentProcessError> {
+        let id = self.claim_process_id();
+        let process_dir = self.logs_dir.join(&id);
+
+        let spawned = self.spawn_agent_process_in_dir(id.clone(), &process_dir, spawn)?;
+        if retry.is_enabled() {
+            self.retries.insert(
+                id,
+                RetryState {
+                    engine: spawn.engine,
+                    project_path: spawn.project_path.to_path_buf(),
+                    prompt: spawn.prompt.to_string(),
+                    images: spawn.images.to_vec(),
+                    model: spawn.model.map(str::to_string),
+                    io_mode: spawn.io_mode,
+                    policy: retry,
+                    attempt: 1,
+                    started: Instant::now(),
+                    due_at: None,
+                },
+            );
         }
+        Ok(spawned)
+    }
+
+    fn spawn_agent_process_in_dir(
+        &mut self,
+        id: String,
+        process_dir: &Path,
+        spawn: AgentSpawn<'_>,
+    ) -> Result<SpawnedAgentProcess, SpawnAgentProcessError> {
+        let staged = stage_sealed_images(process_dir, &spawn, self.artifact_cipher.as_ref())?;
+        let spawn = AgentSpawn {
+            prompt: &staged.prompt,
+            images: &staged.images,
+            ..spawn
+        };
+        match spawn.io_mode {
+            SpawnIoMode::Pipes => {
+                self.spawn_agent_process_pipes(id, process_dir, spawn, staged.copies)
+            }
+            SpawnIoMode::Tty => self.spawn_agent_process_tty(id, process_dir, spawn, staged.copies),
+        }
+    }
+
+    fn create_process_dir(&self, path: &Path) -> io::Result<()> {
+        match self.artifact_cipher {
+            Some(_) => create_private_dir(path),
+            None => fs::create_dir_all(path),
+        }
+    }
+
+    /// Opens a fresh output file. With encryption on it is owner-only, since it stays plaintext
+    /// until the process ends.
+    fn create_output_file(&self, path: &Path) -> io::Result<File> {
+        match self.artifact_cipher {
+            Some(_) => create_private_file(path),
+            None => OpenOptions::new()
+                .create(true)
+                .truncate(true)
+                .write(true)
+                .open(path),
+        }
+    }
+
+    fn write_prompt_file(&self, path: &Path, prompt: &str) -> io::Result<()> {
+        self.create_output_file(path)?.write_all(prompt.as_bytes())
     }
 
     pub fn spawn_codex_resume_process(
@@ -195,38 +572,79 @@ impl ProcessManager {
         project_path: &Path,
         session_id: &str,
         prompt: &str,
+    ) -> Result<SpawnedAgentProcess, SpawnAgentProcessError> {
+        let command = build_codex_exec_resume_command(
+            project_path,
+            session_id,
+            self.spawn_args.resolve(AgentEngine::Codex, project_path),
+            &self.sessions_dir,
+        );
+        self.spawn_resume_process(
+            AgentEngine::Codex,
+            project_path,
+            session_id,
+            prompt,
+            command,
+        )
+    }
+
+    /// Continues an OpenCode session with `opencode run --session`. OpenCode appends the turn to
+    /// its own DB, so the session is known up front and nothing is parsed from stdout.
+    pub fn spawn_opencode_resume_process(
+        &mut self,
+        project_path: &Path,
+        session_id: &str,
+        prompt: &str,
+    ) -> Result<SpawnedAgentProcess, SpawnAgentProcessError> {
+        let command = build_opencode_run_command(
+            project_path,
+            Some(session_id),
+            prompt,
+            self.spawn_args.resolve(AgentEngine::OpenCode, project_path),
+        );
+        self.spawn_resume_process(
+            AgentEngine::OpenCode,
+            project_path,
+            session_id,
+            prompt,
+            command,
+        )
+    }
+
+    fn spawn_resume_process(
+        &mut self,
+        engine: AgentEngine,
+        project_path: &Path,
+        session_id: &str,
+        prompt: &str,
+        mut command: Command,
     ) -> Result<SpawnedAgentProcess, SpawnAgentProcessError> {
         let started_at = SystemTime::now();
-        let id = format!("p{}", self.next_id);
-        self.next_id = self.next_id.saturating_add(1);
+        let id = self.claim_process_id();
+        let limits = self.limits.resolve(engine, project_path);
+        let args = spawn_args_label(self.spawn_args.resolve(engine, project_path));
+        let env = self.resolve_project_env(project_path);
 
         let process_dir = self.logs_dir.join(&id);
-        fs::create_dir_all(&process_dir).map_err(SpawnAgentProcessError::CreateProcessDir)?;
+        self.create_process_dir(&process_dir)
+            .map_err(SpawnAgentProcessError::CreateProcessDir)?;
 
         let prompt_path = process_dir.join("prompt.txt");
-        fs::write(&prompt_path, prompt).map_err(SpawnAgentProcessError::WritePrompt)?;
+        self.write_prompt_file(&prompt_path, prompt)
+            .map_err(SpawnAgentProcessError::WritePrompt)?;
 
         let stdout_path = process_dir.join("stdout.log");
         let stderr_path = process_dir.join("stderr.log");
         let log_path = process_dir.join("process.log");
 
-        let stdout_file = OpenOptions::new()
-            .create(true)
-            .truncate(true)
-            .write(true)
-            .open(&stdout_path)
+        let stdout_file = self
+            .create_output_file(&stdout_path)
             .map_err(SpawnAgentProcessError::OpenStdout)?;
-        let stderr_file = OpenOptions::new()
-            .create(true)
-            .truncate(true)
-            .write(true)
-            .open(&stderr_path)
+        let stderr_file = self
+            .create_output_file(&stderr_path)
             .map_err(SpawnAgentProcessError::OpenStderr)?;
-        let combined_file = OpenOptions::new()
-            .create(true)
-            .truncate(true)
-            .write(true)
-            .open(&log_path)
+        let combined_file = self
+            .create_output_file(&log_path)
             .map_err(SpawnAgentProcessError::OpenLog)?;
 
         let combined_writer = Arc::new(Mutex::new(io::BufWriter::new(combined_file)));
@@ -236,10 +654,13 @@ impl ProcessManager {
             })?;
             let _ = writeln!(
                 writer,
-                "engine: {}\nmode: resume\nresume_session_id: {}\nproject: {}\nstarted_at: {:?}\n---",
-                AgentEngine::Codex.label(),
+                "engine: {}\nmode: resume\nresume_session_id: {}\nproject: {}\nlimits: {}\nargs: {}\nenv: {}\nstarted_at: {:?}\n---",
+                engine.label(),
                 session_id,
                 project_path.display(),
+                limits.label(),
+                args,
+                env_names_label(&env),
                 started_at
             );
         }
@@ -250,8 +671,8 @@ impl ProcessManager {
             .take(120)
             .collect::<String>();
 
-        let mut command =
-            build_codex_exec_resume_command(project_path, session_id, &self.sessions_dir);
+        command.envs(&env);
+        apply_resource_limits(&mut command, limits);
         let mut child = command.spawn().map_err(SpawnAgentProcessError::Spawn)?;
         let pid = child.id();
 
@@ -268,18 +689,29 @@ impl ProcessManager {
         })?;
 
         let sessions_dir = self.sessions_dir.clone();
+        let record_dir = self.logs_dir.join(&id);
         let tx = self.tx.clone();
         let id_for_stdout = id.clone();
         let stdout_combined = combined_writer.clone();
+        let combined_seal = Arc::new(SealOnDrop::new(
+            &[&log_path, &prompt_path],
+            &self.artifact_cipher,
+        ));
+        let stdout_seal = (
+            SealOnDrop::new(&[&stdout_path], &self.artifact_cipher),
+            combined_seal.clone(),
+        );
         std::thread::spawn(move || {
+            let _seal = stdout_seal;
             pipe_reader_thread(
                 stdout,
                 stdout_file,
                 PipeReaderContext {
                     kind: StreamKind::Stdout,
                     combined: stdout_combined,
-                    engine: AgentEngine::Codex,
+                    engine,
                     sessions_dir,
+                    record_dir,
                     process_id: id_for_stdout,
                     tx,
                 },
@@ -287,16 +719,21 @@ impl ProcessManager {
         });
 
         let stderr_combined = combined_writer.clone();
+        let stderr_seal = (
+            SealOnDrop::new(&[&stderr_path], &self.artifact_cipher),
+            combined_seal,
+        );
         std::thread::spawn(move || {
+            let _seal = stderr_seal;
             pipe_reader_thread_simple(StreamKind::Stderr, stderr, stderr_file, stderr_combined);
         });
 
         self.pipes_children.insert(id.clone(), child);
 
-        Ok(SpawnedAgentProcess {
+        let spawned = SpawnedAgentProcess {
             id,
             pid,
-            engine: AgentEngine::Codex,
+            engine,
             project_path: project_path.to_path_buf(),
             started_at,
             prompt_preview,
@@ -305,24 +742,34 @@ impl ProcessManager {
                 stderr_path,
                 log_path,
             },
-        })
+        };
+        self.record_process(&spawned);
+        Ok(spawned)
     }
 
     fn spawn_agent_process_pipes(
         &mut self,
-        engine: AgentEngine,
-        project_path: &Path,
-        prompt: &str,
+        id: String,
+        process_dir: &Path,
+        spawn: AgentSpawn<'_>,
+        staged_images: RemoveOnDrop,
     ) -> Result<SpawnedAgentProcess, SpawnAgentProcessError> {
+        let AgentSpawn {
+            engine,
+            project_path,
+            prompt,
+            ..
+        } = spawn;
         let started_at = SystemTime::now();
-        let id = format!("p{}", self.next_id);
-        self.next_id = self.next_id.saturating_add(1);
-
-        let process_dir = self.logs_dir.join(&id);
-        fs::create_dir_all(&process_dir).map_err(SpawnAgentProcessError::CreateProcessDir)?;
+        let limits = self.limits.resolve(engine, project_path);
+        let extra_args = self.spawn_args.resolve(engine, project_path);
+        let env = self.resolve_project_env(project_path);
+        self.create_process_dir(process_dir)
+            .map_err(SpawnAgentProcessError::CreateProcessDir)?;
 
         let prompt_path = process_dir.join("prompt.txt");
-        fs::write(&prompt_path, prompt).map_err(SpawnAgentProcessError::WritePrompt)?;
+        self.write_prompt_file(&prompt_path, prompt)
+            .map_err(SpawnAgentProcessError::WritePrompt)?;
 
         let stdout_path = process_dir.join("stdout.log");
         let stderr_path = process_dir.join("stderr.log");
@@ -330,26 +777,17 @@ impl ProcessManager {
 
         let last_message_path = match engine {
             AgentEngine::Codex => Some(process_dir.join("last_message.txt")),
-            AgentEngine::Claude => None,
+            AgentEngine::Claude | AgentEngine::OpenCode => None,
         };
 
-        let stdout_file = OpenOptions::new()
-            .create(true)
-            .truncate(true)
-            .write(true)
-            .open(&stdout_path)
+        let stdout_file = self
+            .create_output_file(&stdout_path)
             .map_err(SpawnAgentProcessError::OpenStdout)?;
-        let stderr_file = OpenOptions::new()
-            .create(true)
-            .truncate(true)
-            .write(true)
-            .open(&stderr_path)
+        let stderr_file = self
+            .create_output_file(&stderr_path)
             .map_err(SpawnAgentProcessError::OpenStderr)?;
-        let combined_file = OpenOptions::new()
-            .create(true)
-            .truncate(true)
-            .write(true)
-            .open(&log_path)
+        let combined_file = self
+            .create_output_file(&log_path)
             .map_err(SpawnAgentProcessError::OpenLog)?;
 
         let combined_writer = Arc::new(Mutex::new(io::BufWriter::new(combined_file)));
@@ -359,9 +797,12 @@ impl ProcessManager {
             })?;
             let _ = writeln!(
                 writer,
-                "engine: {}\nproject: {}\nstarted_at: {:?}\n---",
+                "engine: {}\nproject: {}\nlimits: {}\nargs: {}\nenv: {}\nstarted_at: {:?}\n---",
                 engine.label(),
                 project_path.display(),
+                limits.label(),
+                spawn_args_label(extra_args),
+                env_names_label(&env),
                 started_at
             );
         }
@@ -373,12 +814,13 @@ impl ProcessManager {
             .collect::<String>();
 
         let mut command = build_engine_command(
-            engine,
-            project_path,
-            prompt,
+            spawn,
+            extra_args,
             last_message_path.as_deref(),
             &self.sessions_dir,
         );
+        command.envs(&env);
+        apply_resource_limits(&mut command, limits);
         let mut child = command.spawn().map_err(SpawnAgentProcessError::Spawn)?;
         let pid = child.id();
 
@@ -397,10 +839,22 @@ impl ProcessManager {
         })?;
 
         let sessions_dir = self.sessions_dir.clone();
+        let record_dir = self.logs_dir.join(&id);
         let tx = self.tx.clone();
         let id_for_stdout = id.clone();
         let stdout_combined = combined_writer.clone();
+        let mut sealed_at_exit = vec![log_path.as_path(), prompt_path.as_path()];
+        sealed_at_exit.extend(last_message_path.as_deref());
+        let combined_seal = Arc::new((
+            SealOnDrop::new(&sealed_at_exit, &self.artifact_cipher),
+            staged_images,
+        ));
+        let stdout_seal = (
+            SealOnDrop::new(&[&stdout_path], &self.artifact_cipher),
+            combined_seal.clone(),
+        );
         std::thread::spawn(move || {
+            let _seal = stdout_seal;
             pipe_reader_thread(
                 stdout,
                 stdout_file,
@@ -409,6 +863,7 @@ impl ProcessManager {
                     combined: stdout_combined,
                     engine,
                     sessions_dir,
+                    record_dir,
                     process_id: id_for_stdout,
                     tx,
                 },
@@ -416,13 +871,18 @@ impl ProcessManager {
         });
 
         let stderr_combined = combined_writer.clone();
+        let stderr_seal = (
+            SealOnDrop::new(&[&stderr_path], &self.artifact_cipher),
+            combined_seal,
+        );
         std::thread::spawn(move || {
+            let _seal = stderr_seal;
             pipe_reader_thread_simple(StreamKind::Stderr, stderr, stderr_file, stderr_combined);
         });
 
         self.pipes_children.insert(id.clone(), child);
 
-        Ok(SpawnedAgentProcess {
+        let spawned = SpawnedAgentProcess {
             id,
             pid,
             engine,
@@ -434,24 +894,31 @@ impl ProcessManager {
                 stderr_path,
                 log_path,
             },
-        })
+        };
+        self.record_process(&spawned);
+        Ok(spawned)
     }
 
     fn spawn_agent_process_tty(
         &mut self,
-        engine: AgentEngine,
-        project_path: &Path,
-        prompt: &str,
+        id: String,
+        process_dir: &Path,
+        spawn: AgentSpawn<'_>,
+        staged_images: RemoveOnDrop,
     ) -> Result<SpawnedAgentProcess, SpawnAgentProcessError> {
+        let AgentSpawn {
+            engine,
+            project_path,
+            prompt,
+            ..
+        } = spawn;
         let started_at = SystemTime::now();
-        let id = format!("p{}", self.next_id);
-        self.next_id = self.next_id.saturating_add(1);
-
-        let process_dir = self.logs_dir.join(&id);
-        fs::create_dir_all(&process_dir).map_err(SpawnAgentProcessError::CreateProcessDir)?;
+        self.create_process_dir(process_dir)
+            .map_err(SpawnAgentProcessError::CreateProcessDir)?;
 
         let prompt_path = process_dir.join("prompt.txt");
-        fs::write(&prompt_path, prompt).map_err(SpawnAgentProcessError::WritePrompt)?;
+        self.write_prompt_file(&prompt_path, prompt)
+            .map_err(SpawnAgentProcessError::WritePrompt)?;
 
         let log_path = process_dir.join("process.log");
 
@@ -471,7 +938,15 @@ impl ProcessManager {
             })
             .map_err(|error| SpawnAgentProcessError::OpenPty(error.to_string()))?;
 
-        let command = build_engine_command_tty(engine, project_path, prompt, &self.sessions_dir);
+        let mut command = build_engine_command_tty(
+            spawn,
+            self.spawn_args.resolve(engine, project_path),
+            &self.sessions_dir,
+        );
+        for (name, value) in self.resolve_project_env(project_path) {
+            command.env(name, value);
+        }
+        wrap_tty_command_with_limits(&mut command, self.limits.resolve(engine, project_path));
         let child = pair
             .slave
             .spawn_command(command)
@@ -489,26 +964,30 @@ impl ProcessManager {
             .map_err(|error| SpawnAgentProcessError::OpenPtyWriter(error.to_string()))?;
 
         let live_tx = Arc::new(Mutex::new(None));
-        let log_file = OpenOptions::new()
-            .create(true)
-            .truncate(true)
-            .write(true)
-            .open(&log_path)
+        let log_file = self
+            .create_output_file(&log_path)
             .map_err(SpawnAgentProcessError::OpenLog)?;
 
         let sessions_dir = self.sessions_dir.clone();
+        let record_dir = self.logs_dir.join(&id);
         let tx = self.tx.clone();
         let process_id = id.clone();
         let live_tx_thread = live_tx.clone();
         let project_path = project_path.to_path_buf();
         let project_path_for_thread = project_path.clone();
+        let transcript_seal = (
+            SealOnDrop::new(&[&log_path, &prompt_path], &self.artifact_cipher),
+            staged_images,
+        );
         std::thread::spawn(move || {
+            let _seal = transcript_seal;
             pty_reader_thread(
                 reader,
                 log_file,
                 PtyReaderContext {
                     engine,
                     sessions_dir,
+                    record_dir,
                     process_id,
                     tx,
                     live_tx: live_tx_thread,
@@ -525,10 +1004,11 @@ impl ProcessManager {
                 master: pair.master,
                 writer,
                 live_tx,
+                transcript_path: log_path.clone(),
             },
         );
 
-        Ok(SpawnedAgentProcess {
+        let spawned = SpawnedAgentProcess {
             id,
             pid,
             engine,
@@ -539,13 +1019,14 @@ impl ProcessManager {
                 transcript_path: log_path.clone(),
                 log_path,
             },
-        })
+        };
+        self.record_process(&spawned);
+        Ok(spawned)
     }
 
-    pub fn attach_tty_output(
-        &mut self,
-        process_id: &str,
-    ) -> Result<std::sync::mpsc::Receiver<Vec<u8>>, AttachTtyError> {
+    /// Starts forwarding TTY output. The scrollback is read while the reader thread is blocked
+    /// on the live lock, so no output is lost or repeated between the replay and live chunks.
+    pub fn attach_tty_output(&mut self, process_id: &str) -> Result<TtyAttachment, AttachTtyError> {
         if self.pipes_children.contains_key(process_id) {
             return Err(AttachTtyError::NotTty);
         }
@@ -554,13 +1035,16 @@ impl ProcessManager {
         };
 
         let (tx, rx) = std::sync::mpsc::channel::<Vec<u8>>();
-        match process.live_tx.lock() {
-            Ok(mut guard) => {
-                *guard = Some(tx);
-            }
-            Err(_) => return Err(AttachTtyError::Attach("lock poisoned".to_string())),
-        }
-        Ok(rx)
+        let Ok(mut guard) = process.live_tx.lock() else {
+            return Err(AttachTtyError::Attach("lock poisoned".to_string()));
+        };
+        let scrollback = read_transcript_tail(&process.transcript_path, self.tty_scrollback_bytes)
+            .map_err(AttachTtyError::Scrollback)?;
+        *guard = Some(tx);
+        Ok(TtyAttachment {
+            scrollback,
+            output: rx,
+        })
     }
 
     pub fn detach_tty_output(&mut self, process_id: &str) {
@@ -610,6 +1094,17 @@ impl ProcessManager {
     }
 
     pub fn poll_exits(&mut self) -> Vec<ProcessExit> {
+        let now = Instant::now();
+        let overdue = self
+            .terminating
+            .iter()
+            .filter(|(_, deadline)| **deadline <= now)
+            .map(|(id, _)| id.clone())
+            .collect::<Vec<_>>();
+        for id in overdue {
+            let _ = self.force_kill(&id);
+        }
+
         let mut exits = Vec::new();
         let mut finished = Vec::new();
 
@@ -619,12 +1114,14 @@ impl ProcessManager {
                 exits.push(ProcessExit {
                     process_id: id.clone(),
                     exit_code: status.code(),
+                    retry_in: None,
                 });
             }
         }
 
         for id in finished {
             self.pipes_children.remove(&id);
+            self.terminating.remove(&id);
         }
 
         let mut finished_tty = Vec::new();
@@ -635,22 +1132,159 @@ impl ProcessManager {
                 exits.push(ProcessExit {
                     process_id: id.clone(),
                     exit_code,
+                    retry_in: None,
                 });
             }
         }
 
         for id in finished_tty {
             self.tty_children.remove(&id);
+            self.terminating.remove(&id);
+        }
+
+        for exit in &mut exits {
+            exit.retry_in = self.plan_retry(&exit.process_id, exit.exit_code);
+            if exit.retry_in.is_none() {
+                let code = exit
+                    .exit_code
+                    .map_or_else(|| "signal".to_string(), |code| code.to_string());
+                let _ = fs::write(
+                    self.logs_dir.join(&exit.process_id).join(EXIT_CODE_FILE),
+                    format!("{code}\n"),
+                );
+            }
+        }
+
+        // Not our children, so the exit status is unknown; nothing is recorded for them.
+        let gone = self
+            .adopted
+            .iter()
+            .filter(|(_, pid)| !is_pid_alive(**pid))
+            .map(|(id, _)| id.clone())
+            .collect::<Vec<_>>();
+        for id in gone {
+            self.adopted.remove(&id);
+            self.terminating.remove(&id);
+            exits.push(ProcessExit {
+                process_id: id,
+                exit_code: None,
+                retry_in: None,
+            });
         }
 
         exits
     }
 
+    fn plan_retry(&mut self, process_id: &str, exit_code: Option<i32>) -> Option<Duration> {
+        let state = self.retries.get_mut(process_id)?;
+        if !state
+            .policy
+            .should_retry(state.attempt, exit_code, state.started.elapsed())
+        {
+            self.retries.remove(process_id);
+            return None;
+        }
+        let delay = state.policy.backoff_for(state.attempt);
+        state.due_at = Some(Instant::now() + delay);
+        Some(delay)
+    }
+
+    /// Re-spawns processes whose retry backoff has elapsed.
+    pub fn poll_retries(&mut self) -> Vec<ProcessRetry> {
+        let now = Instant::now();
+        let due = self
+            .retries
+            .iter()
+            .filter(|(_, state)| state.due_at.is_some_and(|due_at| due_at <= now))
+            .map(|(id, _)| id.clone())
+            .collect::<Vec<_>>();
+
+        let mut retries = Vec::new();
+        for id in due {
+            let Some(mut state) = self.retries.remove(&id) else {
+                continue;
+            };
+            state.attempt = state.attempt.saturating_add(1);
+            let process_dir = self
+                .logs_dir
+                .join(&id)
+                .join(format!("attempt-{}", state.attempt));
+            let result = self.spawn_agent_process_in_dir(
+                id.clone(),
+                &process_dir,
+                AgentSpawn {
+                    engine: state.engine,
+                    project_path: &state.project_path,
+                    prompt: &state.prompt,
+                    images: &state.images,
+                    model: state.model.as_deref(),
+                    io_mode: state.io_mode,
+                },
+            );
+            let attempt = state.attempt;
+            if result.is_ok() {
+                state.started = Instant::now();
+                state.due_at = None;
+                self.retries.insert(id.clone(), state);
+            }
+            retries.push(ProcessRetry {
+                process_id: id,
+                attempt,
+                result,
+            });
+        }
+        retries
+    }
+
+    /// Stops a process: SIGTERM now, SIGKILL from `poll_exits` if it is still running after the
+    /// grace period. A zero grace period, or a platform without signals, kills outright. Either
+    /// way a pending retry is cancelled.
     pub fn kill(&mut self, process_id: &str) -> Result<(), KillProcessError> {
+        if self.cancel_retry(process_id) {
+            return Ok(());
+        }
+
+        let pid = if let Some(child) = self.pipes_children.get(process_id) {
+            Some(child.id())
+        } else if let Some(process) = self.tty_children.get(process_id) {
+            process.child.process_id()
+        } else if let Some(pid) = self.adopted.get(process_id) {
+            Some(*pid)
+        } else {
+            return Err(KillProcessError::NotFound);
+        };
+        let Some(pid) = pid.filter(|pid| *pid != 0 && cfg!(unix) && !self.kill_grace.is_zero())
+        else {
+            return self.force_kill(process_id);
+        };
+        if !terminate_pid(pid).map_err(KillProcessError::Kill)? {
+            return Ok(());
+        }
+        self.terminating
+            .insert(process_id.to_string(), Instant::now() + self.kill_grace);
+        Ok(())
+    }
+
+    /// SIGTERM was sent and the process hasn't exited or been SIGKILLed yet.
+    pub fn is_stopping(&self, process_id: &str) -> bool {
+        self.terminating.contains_key(process_id)
+    }
+
+    /// SIGKILLs a process without a grace period, cancelling a pending retry.
+    pub fn force_kill(&mut self, process_id: &str) -> Result<(), KillProcessError> {
+        if self.cancel_retry(process_id) {
+            return Ok(());
+        }
+        self.terminating.remove(process_id);
+
         if let Some(child) = self.pipes_children.get_mut(process_id) {
             child.kill().map_err(KillProcessError::Kill)?;
             return Ok(());
         }
+        if let Some(pid) = self.adopted.get(process_id) {
+            kill_pid(*pid).map_err(KillProcessError::Kill)?;
+            return Ok(());
+        }
 
         let Some(process) = self.tty_children.get_mut(process_id) else {
             return Err(KillProcessError::NotFound);
@@ -659,6 +1293,13 @@ impl ProcessManager {
         process.child.kill().map_err(KillProcessError::Kill)?;
         Ok(())
     }
+
+    /// Drops the retry state; `true` when a re-spawn was pending, so there is nothing to kill.
+    fn cancel_retry(&mut self, process_id: &str) -> bool {
+        self.retries
+            .remove(process_id)
+            .is_some_and(|state| state.due_at.is_some())
+    }
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -672,13 +1313,96 @@ struct PipeReaderContext {
     combined: Arc<Mutex<io::BufWriter<File>>>,
     engine: AgentEngine,
     sessions_dir: PathBuf,
+    /// `<logs dir>/pN`, where the reported session is recorded.
+    record_dir: PathBuf,
     process_id: String,
     tx: Sender<ProcessSignal>,
 }
 
+/// Seals log files when their last reader thread is done writing them.
+struct SealOnDrop {
+    paths: Vec<PathBuf>,
+    cipher: Option<ArtifactCipher>,
+}
+
+impl SealOnDrop {
+    fn new(paths: &[&Path], cipher: &Option<ArtifactCipher>) -> Self {
+        Self {
+            paths: paths.iter().map(|path| path.to_path_buf()).collect(),
+            cipher: cipher.clone(),
+        }
+    }
+}
+
+impl Drop for SealOnDrop {
+    fn drop(&mut self) {
+        if let Some(cipher) = &self.cipher {
+            for path in &self.paths {
+                let _ = cipher.seal_file(path);
+            }
+        }
+    }
+}
+
+/// Deletes decrypted copies of sealed inputs once the process is done with them.
+struct RemoveOnDrop(Vec<PathBuf>);
+
+impl Drop for RemoveOnDrop {
+    fn drop(&mut self) {
+        for path in &self.0 {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+/// The images and prompt of a spawn, pointed at decrypted copies of sealed images.
+struct StagedImages {
+    images: Vec<PathBuf>,
+    prompt: String,
+    copies: RemoveOnDrop,
+}
+
+/// Decrypts the images that were sealed at rest into `<process dir>/images` (owner-only) so the
+/// agent can read them, and rewrites their paths in the prompt.
+fn stage_sealed_images(
+    process_dir: &Path,
+    spawn: &AgentSpawn<'_>,
+    cipher: Option<&ArtifactCipher>,
+) -> Result<StagedImages, SpawnAgentProcessError> {
+    let mut staged = StagedImages {
+        images: spawn.images.to_vec(),
+        prompt: spawn.prompt.to_string(),
+        copies: RemoveOnDrop(Vec::new()),
+    };
+    let images_dir = process_dir.join("images");
+    for image in &mut staged.images {
+        if !is_sealed_artifact(image) {
+            continue;
+        }
+        let stage_error = |source: io::Error| SpawnAgentProcessError::StageImage {
+            path: image.display().to_string(),
+            source,
+        };
+        let bytes = read_artifact(image, cipher).map_err(stage_error)?;
+        let file_name = image.file_name().unwrap_or(image.as_os_str());
+        let copy = images_dir.join(file_name);
+        create_private_dir(&images_dir)
+            .and_then(|()| create_private_file(&copy))
+            .and_then(|mut file| file.write_all(&bytes))
+            .map_err(stage_error)?;
+        staged.prompt = staged
+            .prompt
+            .replace(&image.display().to_string(), &copy.display().to_string());
+        staged.copies.0.push(copy.clone());
+        *image = copy;
+    }
+    Ok(staged)
+}
+
 struct PtyReaderContext {
     engine: AgentEngine,
     sessions_dir: PathBuf,
+    record_dir: PathBuf,
     process_id: String,
     tx: Sender<ProcessSignal>,
     live_tx: Arc<Mutex<Option<Sender<Vec<u8>>>>>,
@@ -686,13 +1410,22 @@ struct PtyReaderContext {
     started_at: SystemTime,
 }
 
+/// `extra_args` (from `spawn_args` in the config) go after ccbox's own flags and before the
+/// project and prompt arguments.
 fn build_engine_command(
-    engine: AgentEngine,
-    project_path: &Path,
-    prompt: &str,
+    spawn: AgentSpawn<'_>,
+    extra_args: &[String],
     last_message_path: Option<&Path>,
     sessions_dir: &Path,
 ) -> Command {
+    let AgentSpawn {
+        engine,
+        project_path,
+        prompt,
+        images,
+        model,
+        io_mode: _,
+    } = spawn;
     match engine {
         AgentEngine::Codex => {
             let mut command = Command::new("codex");
@@ -700,7 +1433,14 @@ fn build_engine_command(
             if let Some(path) = last_message_path {
                 command.arg("--output-last-message").arg(path);
             }
+            if let Some(model) = model {
+                command.arg("--model").arg(model);
+            }
+            for image in images {
+                command.arg("--image").arg(image);
+            }
             command
+                .args(extra_args)
                 .arg("-C")
                 .arg(project_path)
                 .stdin(Stdio::piped())
@@ -713,11 +1453,15 @@ fn build_engine_command(
         }
         AgentEngine::Claude => {
             let mut command = Command::new("claude");
+            if let Some(model) = model {
+                command.arg("--model").arg(model);
+            }
             command
                 .arg("--dangerously-skip-permissions")
                 .arg("--verbose")
                 .arg("--output-format")
                 .arg("stream-json")
+                .args(extra_args)
                 .arg("-p")
                 .arg(prompt)
                 .stdin(Stdio::null())
@@ -726,12 +1470,107 @@ fn build_engine_command(
                 .current_dir(project_path);
             command
         }
+        AgentEngine::OpenCode => build_opencode_run_command(project_path, None, prompt, extra_args),
+    }
+}
+
+/// Applies limits in the child between fork and exec, so they hold from the first instruction
+/// and are inherited by everything the agent spawns.
+#[cfg(unix)]
+fn apply_resource_limits(command: &mut Command, limits: ResourceLimits) {
+    use std::os::unix::process::CommandExt;
+
+    if limits.is_empty() {
+        return;
+    }
+    // SAFETY: the hook only calls async-signal-safe libc functions and does not allocate.
+    unsafe {
+        command.pre_exec(move || set_own_resource_limits(limits));
     }
 }
 
+#[cfg(not(unix))]
+fn apply_resource_limits(_command: &mut Command, _limits: ResourceLimits) {}
+
+#[cfg(unix)]
+fn set_own_resource_limits(limits: ResourceLimits) -> io::Result<()> {
+    for (resource, value) in [
+        (libc::RLIMIT_CPU, limits.cpu_seconds),
+        (libc::RLIMIT_AS, limits.memory_bytes()),
+        (libc::RLIMIT_NOFILE, limits.open_files),
+    ] {
+        let Some(value) = value else {
+            continue;
+        };
+        let mut current = libc::rlimit {
+            rlim_cur: 0,
+            rlim_max: 0,
+        };
+        if unsafe { libc::getrlimit(resource, &mut current) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        // Only the soft limit moves, and never above the inherited hard limit.
+        let next = libc::rlimit {
+            rlim_cur: (value as libc::rlim_t).min(current.rlim_max),
+            rlim_max: current.rlim_max,
+        };
+        if unsafe { libc::setrlimit(resource, &next) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    if let Some(nice) = limits.nice
+        && unsafe { libc::setpriority(libc::PRIO_PROCESS, 0, nice) } != 0
+    {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// PTY children are spawned by portable-pty without a pre-exec hook, so limits are set by a
+/// `sh` shim that runs `ulimit`/`nice` and then execs the original command line.
+fn wrap_tty_command_with_limits(command: &mut CommandBuilder, limits: ResourceLimits) {
+    if limits.is_empty() || cfg!(not(unix)) {
+        return;
+    }
+    let mut script = String::new();
+    if let Some(cpu_seconds) = limits.cpu_seconds {
+        script.push_str(&format!("ulimit -S -t {cpu_seconds} && "));
+    }
+    if let Some(memory_mb) = limits.memory_mb {
+        script.push_str(&format!(
+            "ulimit -S -v {} && ",
+            memory_mb.saturating_mul(1024)
+        ));
+    }
+    if let Some(open_files) = limits.open_files {
+        script.push_str(&format!("ulimit -S -n {open_files} && "));
+    }
+    match limits.nice {
+        Some(nice) => script.push_str(&format!("exec nice -n {nice} \"$@\"")),
+        None => script.push_str("exec \"$@\""),
+    }
+
+    let argv = command.get_argv_mut();
+    let original = std::mem::take(argv);
+    argv.extend(["sh", "-c", script.as_str(), "ccbox-limits"].map(Into::into));
+    argv.extend(original);
+}
+
+/// Names (never values, which may be secrets) of injected variables for process log headers.
+fn env_names_label(env: &BTreeMap<String, String>) -> String {
+    if env.is_empty() {
+        return "none".to_string();
+    }
+    env.keys()
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 fn build_codex_exec_resume_command(
     project_path: &Path,
     session_id: &str,
+    extra_args: &[String],
     sessions_dir: &Path,
 ) -> Command {
     let mut command = Command::new("codex");
@@ -739,6 +1578,7 @@ fn build_codex_exec_resume_command(
         .arg("exec")
         .arg("--full-auto")
         .arg("--json")
+        .args(extra_args)
         .arg("-C")
         .arg(project_path)
         .arg("resume")
@@ -752,16 +1592,54 @@ fn build_codex_exec_resume_command(
     command
 }
 
-fn build_engine_command_tty(
-    engine: AgentEngine,
+fn build_opencode_run_command(
     project_path: &Path,
+    session_id: Option<&str>,
     prompt: &str,
+    extra_args: &[String],
+) -> Command {
+    let mut command = Command::new("opencode");
+    command.arg("run").arg("--format").arg("json");
+    if let Some(session_id) = session_id {
+        command.arg("--session").arg(session_id);
+    }
+    command
+        .args(extra_args)
+        .arg(prompt)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .current_dir(project_path);
+    command
+}
+
+/// Claude and OpenCode take no image flags; they read images from the paths listed in the prompt.
+fn build_engine_command_tty(
+    spawn: AgentSpawn<'_>,
+    extra_args: &[String],
     sessions_dir: &Path,
 ) -> CommandBuilder {
+    let AgentSpawn {
+        engine,
+        project_path,
+        prompt,
+        images,
+        model,
+        io_mode: _,
+    } = spawn;
     match engine {
         AgentEngine::Codex => {
             let mut command = CommandBuilder::new("codex");
             command.arg("--full-auto");
+            if let Some(model) = model {
+                command.arg("--model");
+                command.arg(model);
+            }
+            for image in images {
+                command.arg("--image");
+                command.arg(image);
+            }
+            command.args(extra_args);
             command.arg("-C");
             command.arg(project_path);
             if !prompt.trim().is_empty() {
@@ -775,12 +1653,27 @@ fn build_engine_command_tty(
             let mut command = CommandBuilder::new("claude");
             command.arg("--dangerously-skip-permissions");
             command.arg("--verbose");
+            if let Some(model) = model {
+                command.arg("--model");
+                command.arg(model);
+            }
+            command.args(extra_args);
             if !prompt.trim().is_empty() {
                 command.arg(prompt);
             }
             command.cwd(project_path.as_os_str());
             command
         }
+        AgentEngine::OpenCode => {
+            let mut command = CommandBuilder::new("opencode");
+            command.args(extra_args);
+            if !prompt.trim().is_empty() {
+                command.arg("--prompt");
+                command.arg(prompt);
+            }
+            command.cwd(project_path.as_os_str());
+            command
+        }
     }
 }
 
@@ -812,6 +1705,7 @@ fn pipe_reader_thread(pipe: impl Read, mut file: File, ctx: PipeReaderContext) {
                             &session_id,
                             SESSION_LOG_WAIT_TIMEOUT,
                         ) {
+                            record_session_link(&ctx.record_dir, &session_id, &log_path);
                             let _ = ctx.tx.send(ProcessSignal::SessionLogPath {
                                 process_id: ctx.process_id.clone(),
                                 log_path,
@@ -848,10 +1742,10 @@ fn pty_reader_thread(mut reader: Box<dyn Read + Send>, file: File, ctx: PtyReade
         };
 
         let chunk = &buf[..n];
-        let _ = writer.write_all(chunk);
-        let _ = writer.flush();
-
+        // Holding the live lock while writing keeps attach-time scrollback reads consistent.
         if let Ok(mut guard) = ctx.live_tx.lock() {
+            let _ = writer.write_all(chunk);
+            let _ = writer.flush();
             if let Some(tx) = guard.as_ref() {
                 if tx.send(chunk.to_vec()).is_err() {
                     *guard = None;
@@ -879,6 +1773,7 @@ fn pty_reader_thread(mut reader: Box<dyn Read + Send>, file: File, ctx: PtyReade
                         &session_id,
                         SESSION_LOG_WAIT_TIMEOUT,
                     ) {
+                        record_session_link(&ctx.record_dir, &session_id, &log_path);
                         let _ = ctx.tx.send(ProcessSignal::SessionLogPath {
                             process_id: ctx.process_id.clone(),
                             log_path,
@@ -898,6 +1793,60 @@ fn pty_reader_thread(mut reader: Box<dyn Read + Send>, file: File, ctx: PtyReade
     }
 }
 
+/// Reads up to `max_bytes` from the end of a TTY transcript, skipping the ccbox header. When the
+/// tail is cut, it starts after the first newline so replay doesn't begin mid escape sequence.
+fn read_transcript_tail(path: &Path, max_bytes: u64) -> io::Result<Vec<u8>> {
+    if max_bytes == 0 {
+        return Ok(Vec::new());
+    }
+    let mut file = File::open(path)?;
+    let mut head = vec![0u8; TRANSCRIPT_HEADER_SCAN_BYTES];
+    let head_len = read_up_to(&mut file, &mut head)?;
+    let body_start = transcript_body_start(&head[..head_len]) as u64;
+
+    let len = file.metadata()?.len();
+    let start = len.saturating_sub(max_bytes).max(body_start);
+    file.seek(SeekFrom::Start(start))?;
+    let mut tail = Vec::new();
+    file.read_to_end(&mut tail)?;
+    if start > body_start {
+        return Ok(tail_from_line_start(tail));
+    }
+    Ok(tail)
+}
+
+const TRANSCRIPT_HEADER_SCAN_BYTES: usize = 4096;
+
+fn read_up_to(file: &mut File, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match file.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+            Err(error) => return Err(error),
+        }
+    }
+    Ok(filled)
+}
+
+fn transcript_body_start(head: &[u8]) -> usize {
+    const SEPARATOR: &[u8] = b"\n---\n";
+    head.windows(SEPARATOR.len())
+        .position(|window| window == SEPARATOR)
+        .map_or(0, |pos| pos + SEPARATOR.len())
+}
+
+fn tail_from_line_start(mut tail: Vec<u8>) -> Vec<u8> {
+    match tail.iter().position(|byte| *byte == b'\n') {
+        Some(pos) if pos + 1 < tail.len() => {
+            tail.drain(..=pos);
+            tail
+        }
+        _ => tail,
+    }
+}
+
 fn pipe_reader_thread_simple(
     kind: StreamKind,
     pipe: impl Read,
@@ -1004,7 +1953,7 @@ fn find_session_log_in_day_dir(
     let entries = fs::read_dir(day_dir).ok()?;
     for entry in entries.flatten() {
         let path = entry.path();
-        if path.extension().and_then(|ext| ext.to_str()) != Some("jsonl") {
+        if !is_jsonl_log_path(&path) {
             continue;
         }
         let file_name = path
@@ -1026,7 +1975,221 @@ fn first_non_empty_line(text: &str) -> Option<String> {
         .map(|line| line.to_string())
 }
 
+/// Where `ProcessManager` keeps one log dir per spawned process (`<dir>/pN`).
+pub fn process_logs_dir(sessions_dir: &Path) -> PathBuf {
+    sessions_dir.join(".ccbox").join("processes")
+}
+
+/// The exit recorded in `process_dir`; `None` while the process runs, or when the ccbox that
+/// spawned it quit before it ended.
+pub fn read_recorded_exit(process_dir: &Path) -> io::Result<Option<RecordedExit>> {
+    let text = match fs::read_to_string(process_dir.join(EXIT_CODE_FILE)) {
+        Ok(text) => text,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(error) => return Err(error),
+    };
+    match text.trim() {
+        "signal" => Ok(Some(RecordedExit::Signal)),
+        // A half-written file reads as still running; the next poll sees the whole code.
+        code => Ok(code.parse().ok().map(RecordedExit::Code)),
+    }
+}
+
+/// Processes under `logs_dir` with a recorded pid and no recorded exit, in spawn order. A pid
+/// that no longer runs the recorded process (the ccbox that spawned it quit first and the pid
+/// was reused) is left out.
+pub fn running_recorded_processes(logs_dir: &Path) -> io::Result<Vec<RecordedProcess>> {
+    let entries = match fs::read_dir(logs_dir) {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(error) => return Err(error),
+    };
+    let mut processes = Vec::new();
+    for entry in entries {
+        let dir = entry?.path();
+        let Some(id) = dir.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        if read_recorded_exit(&dir)?.is_some() {
+            continue;
+        }
+        let Some(record) = read_process_record(&dir) else {
+            continue;
+        };
+        // portable-pty reports 0 when it has no pid; signalling 0 would hit our own group.
+        if record.pid == 0 || !is_recorded_pid_running(record.pid, record.start_ticks) {
+            continue;
+        }
+        processes.push(RecordedProcess {
+            id: id.to_string(),
+            pid: record.pid,
+            project_path: record.project_path,
+        });
+    }
+    processes.sort_by_key(|process| process_number(&process.id));
+    Ok(processes)
+}
+
+/// The newest `RESTORED_PROCESS_LIMIT` processes recorded under `logs_dir`, in spawn order.
+/// Processes spawned before the registry existed have no record and are skipped.
+pub fn recorded_processes(logs_dir: &Path) -> io::Result<Vec<RestoredProcess>> {
+    let entries = match fs::read_dir(logs_dir) {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(error) => return Err(error),
+    };
+    let mut dirs = Vec::new();
+    for entry in entries {
+        let dir = entry?.path();
+        if let Some(number) = dir
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(process_number)
+        {
+            dirs.push((number, dir));
+        }
+    }
+    dirs.sort_by_key(|(number, _)| *number);
+
+    let mut processes = Vec::new();
+    for (_, dir) in dirs.iter().rev() {
+        if processes.len() == RESTORED_PROCESS_LIMIT {
+            break;
+        }
+        let Some(id) = dir.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        let Some(record) = read_process_record(dir) else {
+            continue;
+        };
+        let Some(engine) = super::tasks::parse_agent_engine_id(&record.engine) else {
+            continue;
+        };
+        let exit = read_recorded_exit(dir)?;
+        let is_running = exit.is_none() && is_recorded_pid_running(record.pid, record.start_ticks);
+        let (session_id, session_log_path) = match fs::read_to_string(dir.join(SESSION_FILE)) {
+            Ok(text) => {
+                let mut lines = text.lines();
+                (
+                    lines.next().map(str::to_string),
+                    lines.next().map(PathBuf::from),
+                )
+            }
+            Err(_) => (None, None),
+        };
+        let links = record.links();
+        processes.push(RestoredProcess {
+            process: SpawnedAgentProcess {
+                id: id.to_string(),
+                pid: record.pid,
+                engine,
+                project_path: record.project_path,
+                started_at: super::tasks::unix_ms_to_system_time(record.started_at_ms),
+                prompt_preview: record.prompt_preview,
+                io: record.io,
+            },
+            session_id,
+            session_log_path,
+            is_running,
+            exit,
+            links,
+        });
+    }
+    processes.reverse();
+    Ok(processes)
+}
+
+fn read_process_record(dir: &Path) -> Option<ProcessRecordFile> {
+    fs::read_to_string(dir.join(RECORD_FILE))
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+}
+
+fn write_process_record(dir: &Path, record: &ProcessRecordFile) {
+    if let Ok(json) = serde_json::to_string_pretty(record) {
+        let _ = fs::write(dir.join(RECORD_FILE), json);
+    }
+}
+
+/// Whether `pid` still runs the process recorded with `start_ticks`. Without a start time to
+/// compare (no record, or a platform that can't read it) the pid is not trusted.
+fn is_recorded_pid_running(pid: u32, start_ticks: Option<u64>) -> bool {
+    is_pid_alive(pid) && start_ticks.is_some() && start_ticks == process_start_ticks(pid)
+}
+
+fn process_number(id: &str) -> Option<u64> {
+    id.strip_prefix('p')
+        .and_then(|number| number.parse::<u64>().ok())
+}
+
+fn record_session_link(record_dir: &Path, session_id: &str, log_path: &Path) {
+    let _ = fs::write(
+        record_dir.join(SESSION_FILE),
+        format!("{session_id}\n{}\n", log_path.display()),
+    );
+}
+
+/// Sends SIGTERM; `Ok(false)` when no such process exists any more.
+#[cfg(unix)]
+pub fn terminate_pid(pid: u32) -> io::Result<bool> {
+    signal_pid(pid, libc::SIGTERM)
+}
+
+#[cfg(unix)]
+fn kill_pid(pid: u32) -> io::Result<bool> {
+    signal_pid(pid, libc::SIGKILL)
+}
+
+/// Whether `pid` exists; one owned by another user counts as alive.
+#[cfg(unix)]
+pub fn is_pid_alive(pid: u32) -> bool {
+    pid != 0 && signal_pid(pid, 0).unwrap_or(true)
+}
+
+#[cfg(unix)]
+fn signal_pid(pid: u32, signal: libc::c_int) -> io::Result<bool> {
+    let Ok(pid) = libc::pid_t::try_from(pid) else {
+        return Ok(false);
+    };
+    if unsafe { libc::kill(pid, signal) } == 0 {
+        return Ok(true);
+    }
+    let error = io::Error::last_os_error();
+    if error.raw_os_error() == Some(libc::ESRCH) {
+        return Ok(false);
+    }
+    Err(error)
+}
+
+#[cfg(not(unix))]
+pub fn terminate_pid(_pid: u32) -> io::Result<bool> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "killing by pid is only supported on unix",
+    ))
+}
+
+#[cfg(not(unix))]
+fn kill_pid(pid: u32) -> io::Result<bool> {
+    terminate_pid(pid)
+}
+
+#[cfg(not(unix))]
+pub fn is_pid_alive(_pid: u32) -> bool {
+    false
+}
+
+/// Reads the last `max_bytes` of a file. Compressed logs are decompressed first (see
+/// [`read_decompressed_log`]), and the returned size is then the decompressed size.
 pub fn read_tail(path: &Path, max_bytes: usize) -> io::Result<(String, u64)> {
+    if is_compressed_log(path) {
+        let bytes = read_decompressed_log(path)?;
+        let start = bytes.len().saturating_sub(max_bytes);
+        return Ok((
+            String::from_utf8_lossy(&bytes[start..]).to_string(),
+            bytes.len() as u64,
+        ));
+    }
     let mut file = File::open(path)?;
     let size = file.metadata()?.len();
     let start = size.saturating_sub(max_bytes as u64);
@@ -1037,6 +2200,17 @@ pub fn read_tail(path: &Path, max_bytes: usize) -> io::Result<(String, u64)> {
 }
 
 pub fn read_from_offset(path: &Path, offset: u64, max_bytes: usize) -> io::Result<(String, u64)> {
+    if is_compressed_log(path) {
+        let bytes = read_decompressed_log(path)?;
+        let start = usize::try_from(offset)
+            .unwrap_or(usize::MAX)
+            .min(bytes.len());
+        let end = start.saturating_add(max_bytes).min(bytes.len());
+        return Ok((
+            String::from_utf8_lossy(&bytes[start..end]).to_string(),
+            end as u64,
+        ));
+    }
     let mut file = File::open(path)?;
     let size = file.metadata()?.len();
     if offset >= size {
@@ -1055,6 +2229,217 @@ mod tests {
     use std::ffi::OsStr;
     use tempfile::tempdir;
 
+    #[test]
+    fn transcript_tail_skips_header_and_partial_first_line() -> io::Result<()> {
+        let temp = tempdir()?;
+        let path = temp.path().join("transcript.log");
+        fs::write(
+            &path,
+            "engine: codex\nproject: /tmp/p\nstarted_at: x\n---\nfirst\nsecond\nthird\n",
+        )?;
+
+        assert_eq!(
+            read_transcript_tail(&path, 1024)?,
+            b"first\nsecond\nthird\n"
+        );
+        assert_eq!(read_transcript_tail(&path, 10)?, b"third\n");
+        assert!(read_transcript_tail(&path, 0)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn sealed_images_are_decrypted_into_the_process_dir_for_the_run() -> io::Result<()> {
+        let temp = tempdir()?;
+        let cipher = ArtifactCipher::load_or_create(&temp.path().join("identity"))
+            .map_err(io::Error::other)?;
+        let sealed = temp.path().join("clipboard-1.png");
+        let plain = temp.path().join("shot.png");
+        fs::write(&sealed, b"png-bytes")?;
+        fs::write(&plain, b"plain")?;
+        cipher.seal_file(&sealed).map_err(io::Error::other)?;
+
+        let images = [sealed.clone(), plain.clone()];
+        let prompt = format!("Look at {} and {}", sealed.display(), plain.display());
+        let spawn = AgentSpawn {
+            engine: AgentEngine::Codex,
+            project_path: temp.path(),
+            prompt: &prompt,
+            images: &images,
+            model: None,
+            io_mode: SpawnIoMode::Pipes,
+        };
+        let process_dir = temp.path().join("p1");
+        let staged =
+            stage_sealed_images(&process_dir, &spawn, Some(&cipher)).map_err(io::Error::other)?;
+
+        let copy = process_dir.join("images").join("clipboard-1.png");
+        assert_eq!(staged.images, [copy.clone(), plain.clone()]);
+        assert_eq!(
+            staged.prompt,
+            format!("Look at {} and {}", copy.display(), plain.display())
+        );
+        assert_eq!(fs::read(&copy)?, b"png-bytes");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(fs::metadata(&copy)?.permissions().mode() & 0o777, 0o600);
+        }
+
+        drop(staged);
+        assert!(!copy.exists());
+        assert!(stage_sealed_images(&process_dir, &spawn, None).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn process_ids_skip_claimed_dirs_and_exits_are_read_back() -> io::Result<()> {
+        let temp = tempdir()?;
+        let (tx, _rx) = std::sync::mpsc::channel();
+        let mut manager =
+            ProcessManager::new(temp.path().to_path_buf(), tx).map_err(io::Error::other)?;
+        let logs_dir = process_logs_dir(temp.path());
+        fs::create_dir(logs_dir.join("p1"))?;
+
+        assert_eq!(manager.claim_process_id(), "p2");
+        assert_eq!(manager.claim_process_id(), "p3");
+        assert_eq!(read_recorded_exit(&logs_dir.join("p2"))?, None);
+        fs::write(logs_dir.join("p2").join(EXIT_CODE_FILE), "2\n")?;
+        fs::write(logs_dir.join("p3").join(EXIT_CODE_FILE), "signal\n")?;
+        assert_eq!(
+            read_recorded_exit(&logs_dir.join("p2"))?,
+            Some(RecordedExit::Code(2))
+        );
+        assert_eq!(
+            read_recorded_exit(&logs_dir.join("p3"))?,
+            Some(RecordedExit::Signal)
+        );
+
+        // p1 never recorded a pid and p2/p3 have exited; only p4 is still running.
+        let id = manager.claim_process_id();
+        let pid = std::process::id();
+        manager.record_process(&SpawnedAgentProcess {
+            id,
+            pid,
+            engine: AgentEngine::Codex,
+            project_path: PathBuf::from("/tmp/project"),
+            started_at: SystemTime::UNIX_EPOCH,
+            prompt_preview: String::new(),
+            io: SpawnedAgentIo::Tty {
+                transcript_path: PathBuf::from("/tmp/t.log"),
+                log_path: PathBuf::from("/tmp/t.log"),
+            },
+        });
+        let expected = [RecordedProcess {
+            id: "p4".to_string(),
+            pid,
+            project_path: PathBuf::from("/tmp/project"),
+        }];
+        assert_eq!(
+            running_recorded_processes(&logs_dir)?,
+            if cfg!(target_os = "linux") {
+                &expected[..]
+            } else {
+                &[]
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn skips_recorded_pids_reused_by_other_processes() -> io::Result<()> {
+        let temp = tempdir()?;
+        let logs_dir = process_logs_dir(temp.path());
+        let pid = std::process::id();
+        // p1's record has no start time to confirm the pid; p2's record started at another
+        // time; p3 only has the bare `pid` file older versions wrote. All pids are alive (this
+        // test process) but belong to something else now.
+        for (id, start_ticks) in [("p1", None), ("p2", Some(1))] {
+            let dir = logs_dir.join(id);
+            fs::create_dir_all(&dir)?;
+            let mut record = serde_json::json!({
+                "pid": pid,
+                "engine": "codex",
+                "project_path": "/tmp/project",
+                "started_at_ms": 0,
+                "prompt_preview": "",
+                "io": {"mode": "tty", "transcript_path": "/tmp/t", "log_path": "/tmp/t"},
+            });
+            if let Some(start_ticks) = start_ticks {
+                record["start_ticks"] = start_ticks.into();
+            }
+            fs::write(dir.join(RECORD_FILE), record.to_string())?;
+        }
+        fs::create_dir_all(logs_dir.join("p3"))?;
+        fs::write(logs_dir.join("p3").join("pid"), format!("{pid}\n"))?;
+
+        assert_eq!(running_recorded_processes(&logs_dir)?, []);
+        Ok(())
+    }
+
+    #[test]
+    fn restores_recorded_processes_and_adopts_live_ones() -> io::Result<()> {
+        let temp = tempdir()?;
+        let (tx, _rx) = std::sync::mpsc::channel();
+        let mut manager =
+            ProcessManager::new(temp.path().to_path_buf(), tx).map_err(io::Error::other)?;
+        let logs_dir = process_logs_dir(temp.path());
+        let spawned = |id: String, pid: u32| SpawnedAgentProcess {
+            id,
+            pid,
+            engine: AgentEngine::Claude,
+            project_path: PathBuf::from("/tmp/project"),
+            started_at: SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000),
+            prompt_preview: "Fix CI".to_string(),
+            io: SpawnedAgentIo::Tty {
+                transcript_path: PathBuf::from("/tmp/t.log"),
+                log_path: PathBuf::from("/tmp/t.log"),
+            },
+        };
+
+        // p1: this test process, still alive. p2: exited with a recorded code.
+        let links = ProcessLinks {
+            task_id: Some(TaskId::new("t-1".to_string())),
+            task_title: Some("Fix CI".to_string()),
+            batch: Some(2),
+            compare: Some(3),
+            label: Some("nightly".to_string()),
+            retry_policy: RetryPolicy::standard(),
+        };
+        let id = manager.claim_process_id();
+        manager.record_process(&spawned(id.clone(), std::process::id()));
+        manager.record_process_links(&id, &links);
+        // A retry re-records the process with its new pid and keeps the links.
+        manager.record_process(&spawned(id, std::process::id()));
+        record_session_link(&logs_dir.join("p1"), "s-1", Path::new("/tmp/s.jsonl"));
+        let id = manager.claim_process_id();
+        manager.record_process(&spawned(id, u32::MAX));
+        fs::write(logs_dir.join("p2").join(EXIT_CODE_FILE), "3\n")?;
+
+        let restored = manager.restore_processes()?;
+        assert_eq!(restored.len(), 2);
+        assert_eq!(restored[0].process.id, "p1");
+        // Only Linux can confirm the pid still runs the recorded process.
+        assert_eq!(restored[0].is_running, cfg!(target_os = "linux"));
+        assert_eq!(restored[0].links, links);
+        assert_eq!(restored[0].session_id.as_deref(), Some("s-1"));
+        assert_eq!(
+            restored[0].session_log_path,
+            Some(PathBuf::from("/tmp/s.jsonl"))
+        );
+        assert_eq!(restored[0].process.engine, AgentEngine::Claude);
+        assert_eq!(
+            restored[0].process.started_at,
+            SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000)
+        );
+        assert!(!restored[1].is_running);
+        assert_eq!(restored[1].exit, Some(RecordedExit::Code(3)));
+        assert_eq!(restored[1].links.label, None);
+        assert_eq!(restored[1].links.retry_policy, RetryPolicy::disabled());
+        // The adopted process is alive, so there is no exit to report.
+        assert!(manager.poll_exits().is_empty());
+        Ok(())
+    }
+
     #[test]
     fn finds_session_log_in_adjacent_day_dir_when_meta_is_utc() {
         let temp = tempdir().expect("tempdir");
@@ -1074,13 +2459,49 @@ mod tests {
         assert_eq!(found, Some(expected));
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn wraps_tty_command_with_ulimit_shim() {
+        let mut command = CommandBuilder::new("claude");
+        command.arg("--verbose");
+        wrap_tty_command_with_limits(
+            &mut command,
+            ResourceLimits {
+                nice: Some(10),
+                memory_mb: Some(2),
+                ..ResourceLimits::default()
+            },
+        );
+        let argv = command
+            .get_argv()
+            .iter()
+            .map(|arg| arg.to_string_lossy().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            argv,
+            vec![
+                "sh",
+                "-c",
+                "ulimit -S -v 2048 && exec nice -n 10 \"$@\"",
+                "ccbox-limits",
+                "claude",
+                "--verbose"
+            ]
+        );
+
+        let mut untouched = CommandBuilder::new("codex");
+        wrap_tty_command_with_limits(&mut untouched, ResourceLimits::default());
+        assert_eq!(untouched.get_argv().len(), 1);
+    }
+
     #[test]
     fn builds_codex_exec_resume_command_with_expected_args_and_env() {
         let project_path = PathBuf::from("/tmp/project");
         let sessions_dir = PathBuf::from("/tmp/sessions");
         let session_id = "019c72c9-e13d-71b3-b853-5ff79aa22102";
 
-        let command = build_codex_exec_resume_command(&project_path, session_id, &sessions_dir);
+        let command =
+            build_codex_exec_resume_command(&project_path, session_id, &[], &sessions_dir);
 
         assert_eq!(command.get_program(), OsStr::new("codex"));
 
@@ -1121,6 +2542,88 @@ mod tests {
         );
     }
 
+    #[test]
+    fn spawns_pass_model_images_and_extra_args_before_the_project_flag() {
+        let project_path = PathBuf::from("/tmp/project");
+        let images = [PathBuf::from("/tmp/a.png"), PathBuf::from("/tmp/b.jpg")];
+        let extra_args = ["--sandbox".to_string(), "workspace-write".to_string()];
+        let spawn = AgentSpawn {
+            engine: AgentEngine::Codex,
+            project_path: &project_path,
+            prompt: "look",
+            images: &images,
+            model: Some("gpt-5"),
+            io_mode: SpawnIoMode::Pipes,
+        };
+
+        let command = build_engine_command(spawn, &extra_args, None, Path::new("/tmp/sessions"));
+        let args = command
+            .get_args()
+            .map(|arg| arg.to_string_lossy().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            args,
+            [
+                "exec",
+                "--full-auto",
+                "--json",
+                "--model",
+                "gpt-5",
+                "--image",
+                "/tmp/a.png",
+                "--image",
+                "/tmp/b.jpg",
+                "--sandbox",
+                "workspace-write",
+                "-C",
+                "/tmp/project",
+                "-",
+            ]
+        );
+
+        let command = build_engine_command(
+            AgentSpawn {
+                engine: AgentEngine::Claude,
+                ..spawn
+            },
+            &extra_args,
+            None,
+            Path::new("/tmp/sessions"),
+        );
+        let args = command
+            .get_args()
+            .map(|arg| arg.to_string_lossy().to_string())
+            .collect::<Vec<_>>();
+        assert!(!args.iter().any(|arg| arg == "--image"));
+        assert!(args.iter().any(|arg| arg == "--model"));
+        assert!(args.ends_with(&["--sandbox", "workspace-write", "-p", "look"].map(String::from)));
+    }
+
+    #[test]
+    fn builds_opencode_run_command_for_the_resumed_session() {
+        let project_path = PathBuf::from("/tmp/project");
+        let command =
+            build_opencode_run_command(&project_path, Some("ses_4f2a"), "Now add tests", &[]);
+
+        assert_eq!(command.get_program(), OsStr::new("opencode"));
+        let args = command
+            .get_args()
+            .map(|arg| arg.to_string_lossy().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            args,
+            vec![
+                "run",
+                "--format",
+                "json",
+                "--session",
+                "ses_4f2a",
+                "Now add tests"
+            ]
+        );
+        assert_eq!(command.get_current_dir(), Some(project_path.as_path()));
+    }
+
     #[test]
     fn finds_session_log_in_previous_day_dir_when_meta_date_is_ahead() {
         let temp = tempdir().expect("tempdir");
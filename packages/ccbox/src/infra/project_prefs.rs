@@ -0,0 +1,117 @@
+use crate::domain::{ProjectPref, ProjectPrefs};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum LoadProjectPrefsError {
+    #[error("failed to read project prefs: {0}")]
+    Read(#[from] io::Error),
+
+    #[error("failed to parse project prefs: {0}")]
+    Parse(#[from] serde_json::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum SaveProjectPrefsError {
+    #[error("failed to encode project prefs: {0}")]
+    Encode(#[from] serde_json::Error),
+
+    #[error("failed to write project prefs: {0}")]
+    Write(#[from] io::Error),
+}
+
+fn project_prefs_path(state_dir: &Path) -> PathBuf {
+    state_dir.join("project_prefs.json")
+}
+
+/// Loads project names and hidden flags; a missing file yields none.
+pub fn load_project_prefs(state_dir: &Path) -> Result<ProjectPrefs, LoadProjectPrefsError> {
+    let raw = match fs::read_to_string(project_prefs_path(state_dir)) {
+        Ok(raw) => raw,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => {
+            return Ok(ProjectPrefs::default());
+        }
+        Err(error) => return Err(error.into()),
+    };
+
+    let file: ProjectPrefsFile = serde_json::from_str(&raw)?;
+    Ok(ProjectPrefs {
+        projects: file
+            .projects
+            .into_iter()
+            .map(|(path, entry)| {
+                let pref = ProjectPref {
+                    name: entry.name,
+                    hidden: entry.hidden,
+                };
+                (path, pref)
+            })
+            .collect(),
+    })
+}
+
+pub fn save_project_prefs(
+    state_dir: &Path,
+    prefs: &ProjectPrefs,
+) -> Result<(), SaveProjectPrefsError> {
+    fs::create_dir_all(state_dir)?;
+
+    let path = project_prefs_path(state_dir);
+    let tmp = path.with_extension("json.tmp");
+    let file = ProjectPrefsFile {
+        version: 1,
+        projects: prefs
+            .projects
+            .iter()
+            .map(|(path, pref)| {
+                let entry = ProjectPrefEntry {
+                    name: pref.name.clone(),
+                    hidden: pref.hidden,
+                };
+                (path.clone(), entry)
+            })
+            .collect(),
+    };
+    let text = serde_json::to_string_pretty(&file)?;
+    fs::write(&tmp, text)?;
+    fs::rename(tmp, path)?;
+    Ok(())
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct ProjectPrefsFile {
+    version: u32,
+    #[serde(default)]
+    projects: BTreeMap<PathBuf, ProjectPrefEntry>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct ProjectPrefEntry {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(default)]
+    hidden: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn round_trips_names_and_hidden_flags() -> Result<(), Box<dyn std::error::Error>> {
+        let dir = tempdir()?;
+        assert_eq!(load_project_prefs(dir.path())?, ProjectPrefs::default());
+
+        let mut prefs = ProjectPrefs::default();
+        prefs.set_name(Path::new("/work/app"), "Main app");
+        prefs.set_hidden(Path::new("/tmp/scratch"), true);
+        save_project_prefs(dir.path(), &prefs)?;
+        assert_eq!(load_project_prefs(dir.path())?, prefs);
+        Ok(())
+    }
+}
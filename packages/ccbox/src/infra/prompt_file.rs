@@ -0,0 +1,154 @@
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Prompts are sent as a single argument; keep them well under typical `ARG_MAX` limits.
+pub const MAX_PROMPT_FILE_BYTES: u64 = 512 * 1024;
+
+#[derive(Debug, Error)]
+pub enum ReadPromptFileError {
+    #[error("failed to read prompt file {path}: {source}")]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("prompt file {path} is larger than {max} bytes")]
+    TooLarge { path: PathBuf, max: u64 },
+
+    #[error("prompt file {path} is not UTF-8 text")]
+    NotText { path: PathBuf },
+}
+
+/// Reads a prompt from `path`, or from stdin when `path` is `-`. Trailing newlines are trimmed.
+pub fn read_prompt_file(path: &Path) -> Result<String, ReadPromptFileError> {
+    let read_error = |source| ReadPromptFileError::Read {
+        path: path.to_path_buf(),
+        source,
+    };
+    let mut bytes = Vec::new();
+    let limit = MAX_PROMPT_FILE_BYTES + 1;
+    if path == Path::new("-") {
+        io::stdin()
+            .lock()
+            .take(limit)
+            .read_to_end(&mut bytes)
+            .map_err(read_error)?;
+    } else {
+        File::open(path)
+            .and_then(|file| file.take(limit).read_to_end(&mut bytes))
+            .map_err(read_error)?;
+    }
+    prompt_from_bytes(path, bytes)
+}
+
+fn prompt_from_bytes(path: &Path, bytes: Vec<u8>) -> Result<String, ReadPromptFileError> {
+    if bytes.len() as u64 > MAX_PROMPT_FILE_BYTES {
+        return Err(ReadPromptFileError::TooLarge {
+            path: path.to_path_buf(),
+            max: MAX_PROMPT_FILE_BYTES,
+        });
+    }
+    let text = String::from_utf8(bytes).map_err(|_| ReadPromptFileError::NotText {
+        path: path.to_path_buf(),
+    })?;
+    Ok(text.trim_end_matches(['\r', '\n']).to_string())
+}
+
+#[derive(Debug, Error)]
+pub enum ReadAttachmentError {
+    #[error("failed to read {path}: {source}")]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("{path} is outside the project")]
+    OutsideProject { path: PathBuf },
+
+    #[error("{path} is larger than {max} bytes")]
+    TooLarge { path: PathBuf, max: u64 },
+
+    #[error("{path} is not UTF-8 text")]
+    NotText { path: PathBuf },
+}
+
+/// Reads `relative` inside `project` for a prompt attachment, refusing paths that resolve outside
+/// the project (`..`, absolute paths, symlinks) and files over `max` bytes.
+pub fn read_project_file(
+    project: &Path,
+    relative: &Path,
+    max: u64,
+) -> Result<String, ReadAttachmentError> {
+    let read_error = |source| ReadAttachmentError::Read {
+        path: relative.to_path_buf(),
+        source,
+    };
+    let root = project.canonicalize().map_err(read_error)?;
+    let resolved = root.join(relative).canonicalize().map_err(read_error)?;
+    if !resolved.starts_with(&root) {
+        return Err(ReadAttachmentError::OutsideProject {
+            path: relative.to_path_buf(),
+        });
+    }
+    let mut bytes = Vec::new();
+    File::open(&resolved)
+        .and_then(|file| file.take(max + 1).read_to_end(&mut bytes))
+        .map_err(read_error)?;
+    if bytes.len() as u64 > max {
+        return Err(ReadAttachmentError::TooLarge {
+            path: relative.to_path_buf(),
+            max,
+        });
+    }
+    String::from_utf8(bytes).map_err(|_| ReadAttachmentError::NotText {
+        path: relative.to_path_buf(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prompt_from_bytes_trims_trailing_newlines_and_rejects_binary_or_huge_input() {
+        let path = Path::new("task.md");
+        assert_eq!(
+            prompt_from_bytes(path, b"# Task\n\nFix it.\r\n\n".to_vec()).expect("text"),
+            "# Task\n\nFix it."
+        );
+        assert!(matches!(
+            prompt_from_bytes(path, vec![0xff, 0xfe]),
+            Err(ReadPromptFileError::NotText { .. })
+        ));
+        assert!(matches!(
+            prompt_from_bytes(path, vec![b'a'; MAX_PROMPT_FILE_BYTES as usize + 1]),
+            Err(ReadPromptFileError::TooLarge { .. })
+        ));
+    }
+
+    #[test]
+    fn read_project_file_stays_inside_the_project_and_under_the_limit() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let project = dir.path().join("app");
+        std::fs::create_dir_all(project.join("src")).expect("mkdir");
+        std::fs::write(project.join("src/lib.rs"), "pub fn a() {}\n").expect("write");
+        std::fs::write(dir.path().join("secret.txt"), "token").expect("write");
+
+        assert_eq!(
+            read_project_file(&project, Path::new("src/lib.rs"), 64).expect("attach"),
+            "pub fn a() {}\n"
+        );
+        assert!(matches!(
+            read_project_file(&project, Path::new("../secret.txt"), 64),
+            Err(ReadAttachmentError::OutsideProject { .. })
+        ));
+        assert!(matches!(
+            read_project_file(&project, Path::new("src/lib.rs"), 4),
+            Err(ReadAttachmentError::TooLarge { .. })
+        ));
+    }
+}
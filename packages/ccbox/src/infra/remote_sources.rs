@@ -0,0 +1,310 @@
+use crate::domain::{
+    REMOTE_CLAUDE_MARKER, REMOTE_CODEX_MARKER, RemoteFile, RemoteSourceConfig, SessionSummary,
+    SshTarget, parse_remote_listing, shell_quote_remote_path,
+};
+use crate::infra::{ScanProgress, ScanWarningCount, scan_claude_projects_dir, scan_sessions_dir};
+use std::collections::BTreeSet;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::Duration;
+use thiserror::Error;
+use walkdir::WalkDir;
+
+/// Session log names mirrored from a remote, matching `is_jsonl_log_path`.
+const REMOTE_LOG_FIND_ARGS: &str =
+    "\\( -name '*.jsonl' -o -name '*.jsonl.gz' -o -name '*.jsonl.zst' \\)";
+
+#[derive(Debug, Error)]
+pub enum RemoteSourceError {
+    #[error("invalid url {0}")]
+    InvalidUrl(String),
+
+    #[error("failed to run ssh: {0}")]
+    Spawn(#[source] io::Error),
+
+    #[error("ssh failed: {0}")]
+    Ssh(String),
+
+    #[error("failed to mirror session logs: {0}")]
+    Mirror(#[from] io::Error),
+
+    #[error("unexpected response from the remote")]
+    Protocol,
+}
+
+/// How often the TUI and `ccbox serve` refresh the mirrors in the background.
+pub const REMOTE_SYNC_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Clone, Debug)]
+pub struct RemoteScanOutput {
+    pub sessions: Vec<SessionSummary>,
+    pub warnings: ScanWarningCount,
+}
+
+/// The mirror of a remote's session logs, under `~/.ccbox/remote/<name>`.
+pub fn remote_mirror_dir(state_dir: &Path, name: &str) -> PathBuf {
+    state_dir.join("remote").join(name)
+}
+
+/// Whether `path` lies under `~/.ccbox/remote`, where the mirrors live. Those logs are copies of
+/// another machine's and are never deleted or forked.
+pub fn is_remote_mirror_path(state_dir: &Path, path: &Path) -> bool {
+    let root = state_dir.join("remote");
+    let root = fs::canonicalize(&root).unwrap_or(root);
+    fs::canonicalize(path)
+        .unwrap_or_else(|_| path.to_path_buf())
+        .starts_with(root)
+}
+
+/// Scans the mirrors as the last `sync_remote_sources` left them, without touching the network.
+/// Sessions are tagged with the remote and listed under `<name>:<cwd>`.
+pub fn scan_remote_mirrors(sources: &[RemoteSourceConfig], state_dir: &Path) -> RemoteScanOutput {
+    let mut sessions = Vec::new();
+    let mut warnings = 0usize;
+
+    for source in sources {
+        let mirror_dir = remote_mirror_dir(state_dir, &source.name);
+        let mut remote_sessions = Vec::new();
+        let codex_dir = mirror_dir.join("codex");
+        if codex_dir.is_dir()
+            && let Ok(output) = scan_sessions_dir(&codex_dir, &ScanProgress::default())
+        {
+            warnings += output.warnings.get();
+            remote_sessions.extend(output.sessions);
+        }
+        let claude_dir = mirror_dir.join("claude");
+        if claude_dir.is_dir() {
+            let output = scan_claude_projects_dir(&claude_dir, &ScanProgress::default());
+            warnings += output.warnings.get();
+            remote_sessions.extend(output.sessions);
+        }
+
+        for session in &mut remote_sessions {
+            session.project_root = Some(PathBuf::from(format!(
+                "{}:{}",
+                source.name,
+                session.meta.cwd.display()
+            )));
+            session.remote = Some(source.name.clone());
+        }
+        sessions.extend(remote_sessions);
+    }
+
+    RemoteScanOutput {
+        sessions,
+        warnings: ScanWarningCount::from(warnings),
+    }
+}
+
+/// Refreshes each remote's mirror over `ssh`. An unreachable host can take the full connect
+/// timeout, so this runs on a worker thread; a host that fails keeps its last mirror and is
+/// named in the returned notice.
+pub fn sync_remote_sources(sources: &[RemoteSourceConfig], state_dir: &Path) -> Option<String> {
+    let notices = sources
+        .iter()
+        .filter_map(|source| {
+            sync_remote_source(source, &remote_mirror_dir(state_dir, &source.name))
+                .err()
+                .map(|error| format!("Remote `{}`: {error}", source.name))
+        })
+        .collect::<Vec<_>>();
+    (!notices.is_empty()).then(|| notices.join(" | "))
+}
+
+/// Lists the remote's session logs in one `ssh` call, then fetches those whose size differs from
+/// the mirror in one call per sessions dir. Logs gone from the remote are removed.
+fn sync_remote_source(
+    source: &RemoteSourceConfig,
+    mirror_dir: &Path,
+) -> Result<(), RemoteSourceError> {
+    let target = SshTarget::parse(&source.url)
+        .ok_or_else(|| RemoteSourceError::InvalidUrl(source.url.clone()))?;
+    let listing = parse_remote_listing(&String::from_utf8_lossy(&run_ssh(
+        &target,
+        &listing_script(source),
+    )?));
+
+    for (kind, root, files) in [
+        ("codex", source.codex_dir(), &listing.codex),
+        ("claude", source.claude_dir(), &listing.claude),
+    ] {
+        let dir = mirror_dir.join(kind);
+        let changed = files
+            .iter()
+            .filter(|file| {
+                !fs::metadata(dir.join(&file.relative_path))
+                    .is_ok_and(|metadata| metadata.len() == file.size)
+            })
+            .map(|file| file.relative_path.clone())
+            .collect::<Vec<_>>();
+        if !changed.is_empty() {
+            let stream = run_ssh(&target, &fetch_script(root, &changed))?;
+            read_fetch_stream(&mut BufReader::new(stream.as_slice()), &changed, &dir)?;
+        }
+        remove_unlisted_logs(&dir, files)?;
+    }
+    Ok(())
+}
+
+/// Prints each sessions dir's logs as `wc -c` lines after its section marker.
+fn listing_script(source: &RemoteSourceConfig) -> String {
+    [
+        (REMOTE_CODEX_MARKER, source.codex_dir()),
+        (REMOTE_CLAUDE_MARKER, source.claude_dir()),
+    ]
+    .iter()
+    .map(|(marker, root)| {
+        format!(
+            "printf '%s\\n' '{marker}'\n(cd {} 2>/dev/null && find . -type f {REMOTE_LOG_FIND_ARGS} -exec wc -c {{}} +)\n",
+            shell_quote_remote_path(root)
+        )
+    })
+    .collect()
+}
+
+/// For each file: its size on one line, then exactly that many bytes; `-` when it is gone.
+fn fetch_script(root: &str, files: &[String]) -> String {
+    let mut script = format!("cd {} || exit 1\n", shell_quote_remote_path(root));
+    for file in files {
+        script.push_str(&format!(
+            "f={}\nif [ -f \"$f\" ]; then s=$(wc -c <\"$f\" | tr -d ' '); printf '%s\\n' \"$s\"; head -c \"$s\" \"$f\"; else printf -- '-\\n'; fi\n",
+            shell_quote_remote_path(&format!("./{file}"))
+        ));
+    }
+    script
+}
+
+/// Runs `script` with `sh` on the remote; `BatchMode` fails fast instead of prompting.
+fn run_ssh(target: &SshTarget, script: &str) -> Result<Vec<u8>, RemoteSourceError> {
+    let mut command = Command::new("ssh");
+    command.args(["-o", "BatchMode=yes", "-o", "ConnectTimeout=10"]);
+    if let Some(port) = target.port {
+        command.arg("-p").arg(port.to_string());
+    }
+    let mut child = command
+        .arg(&target.destination)
+        .arg("sh -s")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(RemoteSourceError::Spawn)?;
+    // `sh` runs the script while reading it, so feed it from a thread to keep both pipes moving.
+    let writer = child.stdin.take().map(|mut stdin| {
+        let script = script.to_string();
+        thread::spawn(move || stdin.write_all(script.as_bytes()))
+    });
+    let output = child.wait_with_output().map_err(RemoteSourceError::Spawn)?;
+    // A script cut short shows up as a failed exit or a short response below.
+    if let Some(writer) = writer {
+        let _ = writer.join();
+    }
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let message = stderr.lines().last().unwrap_or("").trim();
+        return Err(RemoteSourceError::Ssh(if message.is_empty() {
+            output.status.to_string()
+        } else {
+            message.to_string()
+        }));
+    }
+    Ok(output.stdout)
+}
+
+/// Writes the files of a `fetch_script` response under `dir`, each through a temp file.
+fn read_fetch_stream(
+    reader: &mut impl BufRead,
+    files: &[String],
+    dir: &Path,
+) -> Result<(), RemoteSourceError> {
+    for file in files {
+        let mut header = String::new();
+        reader.read_line(&mut header)?;
+        let header = header.trim_end_matches('\n');
+        if header == "-" {
+            continue;
+        }
+        let size = header
+            .parse::<u64>()
+            .map_err(|_| RemoteSourceError::Protocol)?;
+
+        let path = dir.join(file);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp = path.with_extension("ccbox-tmp");
+        let copied = io::copy(&mut reader.by_ref().take(size), &mut File::create(&tmp)?)?;
+        if copied != size {
+            let _ = fs::remove_file(&tmp);
+            return Err(RemoteSourceError::Protocol);
+        }
+        fs::rename(tmp, path)?;
+    }
+    Ok(())
+}
+
+/// Deletes mirrored logs (and leftover temp files) the remote no longer lists.
+fn remove_unlisted_logs(dir: &Path, files: &[RemoteFile]) -> io::Result<()> {
+    if !dir.is_dir() {
+        return Ok(());
+    }
+    let listed = files
+        .iter()
+        .map(|file| dir.join(&file.relative_path))
+        .collect::<BTreeSet<_>>();
+    for entry in WalkDir::new(dir).into_iter().filter_map(Result::ok) {
+        if entry.file_type().is_file() && !listed.contains(entry.path()) {
+            fs::remove_file(entry.path())?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn mirrors_fetched_logs_and_drops_unlisted_ones() -> Result<(), RemoteSourceError> {
+        let dir = tempdir()?;
+        let files = vec![
+            "2025/01/a.jsonl".to_string(),
+            "gone.jsonl".to_string(),
+            "b.jsonl".to_string(),
+        ];
+        let stream = b"6\n{\"a\"}\n-\n3\n{}\nextra";
+        read_fetch_stream(&mut BufReader::new(&stream[..]), &files, dir.path())?;
+        assert_eq!(fs::read(dir.path().join("2025/01/a.jsonl"))?, b"{\"a\"}\n");
+        assert_eq!(fs::read(dir.path().join("b.jsonl"))?, b"{}\n");
+        assert!(!dir.path().join("gone.jsonl").exists());
+
+        let truncated = b"9\n{}";
+        assert!(matches!(
+            read_fetch_stream(&mut BufReader::new(&truncated[..]), &files[..1], dir.path()),
+            Err(RemoteSourceError::Protocol)
+        ));
+
+        let listed = [RemoteFile {
+            relative_path: "b.jsonl".to_string(),
+            size: 3,
+        }];
+        remove_unlisted_logs(dir.path(), &listed)?;
+        assert!(!dir.path().join("2025/01/a.jsonl").exists());
+        assert!(dir.path().join("b.jsonl").exists());
+
+        let mirror = remote_mirror_dir(dir.path(), "box");
+        assert!(is_remote_mirror_path(
+            dir.path(),
+            &mirror.join("codex/a.jsonl")
+        ));
+        assert!(!is_remote_mirror_path(
+            dir.path(),
+            &dir.path().join("b.jsonl")
+        ));
+        Ok(())
+    }
+}
@@ -2,9 +2,10 @@ use crate::domain::{
     SessionEngine, SessionSummary, derive_title_from_user_text, is_metadata_prompt,
     make_session_summary, parse_session_meta_line, parse_user_message_text,
 };
+use crate::infra::{ScanProgress, scan_in_parallel};
 use dirs::home_dir;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::fs;
+use std::io::BufRead;
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 use walkdir::WalkDir;
@@ -57,7 +58,11 @@ pub struct ScanOutput {
     pub warnings: ScanWarningCount,
 }
 
-pub fn scan_sessions_dir(sessions_dir: &Path) -> Result<ScanOutput, ScanError> {
+/// Walks the sessions dir, then reads the session heads on a worker pool.
+pub fn scan_sessions_dir(
+    sessions_dir: &Path,
+    progress: &ScanProgress,
+) -> Result<ScanOutput, ScanError> {
     if !sessions_dir.exists() {
         return Err(ScanError::SessionsDirMissing(
             sessions_dir.display().to_string(),
@@ -66,6 +71,7 @@ pub fn scan_sessions_dir(sessions_dir: &Path) -> Result<ScanOutput, ScanError> {
 
     let mut warnings = 0usize;
     let mut sessions: Vec<SessionSummary> = Vec::new();
+    let mut paths = Vec::new();
 
     let walker = WalkDir::new(sessions_dir).follow_links(false).into_iter();
     for entry in walker {
@@ -80,11 +86,14 @@ pub fn scan_sessions_dir(sessions_dir: &Path) -> Result<ScanOutput, ScanError> {
         if !entry.file_type().is_file() {
             continue;
         }
-        if entry.path().extension().and_then(|ext| ext.to_str()) != Some("jsonl") {
+        if !super::is_jsonl_log_path(entry.path()) {
             continue;
         }
+        paths.push(entry.into_path());
+    }
 
-        match scan_session_file(entry.path()) {
+    for result in scan_in_parallel(&paths, progress, |path| scan_session_file(path)) {
+        match result {
             Ok(summary) => sessions.push(summary),
             Err(_) => warnings += 1,
         }
@@ -99,15 +108,13 @@ pub fn scan_sessions_dir(sessions_dir: &Path) -> Result<ScanOutput, ScanError> {
 const MAX_TITLE_SCAN_LINES: usize = 250;
 
 fn scan_session_file(path: &Path) -> Result<SessionSummary, ScanError> {
-    let file = File::open(path)
-        .map_err(|error| ScanError::ReadFile(format!("{}: {error}", path.display())))?;
-    let metadata = file
-        .metadata()
+    let metadata = fs::metadata(path)
         .map_err(|error| ScanError::ReadFile(format!("{}: {error}", path.display())))?;
     let file_size_bytes = metadata.len();
     let file_modified = metadata.modified().ok();
 
-    let mut reader = BufReader::new(file);
+    let mut reader = super::open_log_reader(path)
+        .map_err(|error| ScanError::ReadFile(format!("{}: {error}", path.display())))?;
     let mut first_line = String::new();
     let bytes = reader
         .read_line(&mut first_line)
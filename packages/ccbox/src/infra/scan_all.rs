@@ -1,12 +1,16 @@
-use crate::domain::SessionSummary;
+use crate::domain::{SessionDirsConfig, SessionSummary};
 use crate::infra::{
-    ResolveClaudeProjectsDirError, ResolveGeminiRootDirError, ResolveOpenCodeDbPathError,
-    ScanError, ScanWarningCount, apply_session_aliases, apply_session_projects,
-    load_session_aliases, load_session_projects, resolve_ccbox_state_dir,
-    resolve_claude_projects_dir, resolve_gemini_root_dir, resolve_opencode_db_path,
-    scan_claude_projects_dir, scan_gemini_root_dir, scan_opencode_db, scan_sessions_dir,
+    CcboxConfig, ResolveClaudeProjectsDirError, ResolveGeminiRootDirError,
+    ResolveOpenCodeDbPathError, ScanError, ScanProgress, ScanWarningCount, apply_session_aliases,
+    apply_session_projects, enrich_sessions_with_git, group_sessions_by_repo, load_ccbox_config,
+    load_session_aliases, load_session_projects, resolve_ccbox_config_path,
+    resolve_ccbox_state_dir, resolve_claude_projects_dir, resolve_gemini_root_dir,
+    resolve_opencode_db_path, save_session_aliases, save_session_projects,
+    scan_claude_projects_dir, scan_custom_engines, scan_gemini_root_dir, scan_opencode_db,
+    scan_remote_mirrors, scan_sessions_dir,
 };
-use std::path::Path;
+use dirs::home_dir;
+use std::path::{Path, PathBuf};
 
 #[derive(Clone, Debug)]
 pub struct MultiEngineScanOutput {
@@ -16,110 +20,174 @@ pub struct MultiEngineScanOutput {
 }
 
 pub fn scan_all_sessions(codex_sessions_dir: &Path) -> MultiEngineScanOutput {
-    let (claude_projects_dir, claude_resolve_notice) = match resolve_claude_projects_dir() {
-        Ok(dir) => (Some(dir), None),
-        Err(ResolveClaudeProjectsDirError::HomeDirNotFound) => (
-            None,
-            Some("Claude projects dir disabled: home directory not found".to_string()),
-        ),
+    scan_all_sessions_with_progress(codex_sessions_dir, &ScanProgress::default())
+}
+
+/// Like `scan_all_sessions`, counting Codex session files and Claude project folders in
+/// `progress` as they are read.
+pub fn scan_all_sessions_with_progress(
+    codex_sessions_dir: &Path,
+    progress: &ScanProgress,
+) -> MultiEngineScanOutput {
+    let mut notices = Vec::new();
+
+    let claude_projects_dir = match resolve_claude_projects_dir() {
+        Ok(dir) => Some(dir),
+        Err(ResolveClaudeProjectsDirError::HomeDirNotFound) => {
+            notices.push("Claude projects dir disabled: home directory not found".to_string());
+            None
+        }
     };
 
-    let (gemini_root_dir, gemini_resolve_notice) = match resolve_gemini_root_dir() {
-        Ok(dir) => (Some(dir), None),
-        Err(ResolveGeminiRootDirError::HomeDirNotFound) => (
-            None,
-            Some("Gemini root dir disabled: home directory not found".to_string()),
-        ),
+    let gemini_root_dir = match resolve_gemini_root_dir() {
+        Ok(dir) => Some(dir),
+        Err(ResolveGeminiRootDirError::HomeDirNotFound) => {
+            notices.push("Gemini root dir disabled: home directory not found".to_string());
+            None
+        }
     };
 
-    let (opencode_db_path, opencode_resolve_notice) = match resolve_opencode_db_path() {
-        Ok(path) => (Some(path), None),
-        Err(ResolveOpenCodeDbPathError::HomeDirNotFound) => (
-            None,
-            Some("OpenCode DB disabled: home directory not found".to_string()),
-        ),
+    let opencode_db_path = match resolve_opencode_db_path() {
+        Ok(path) => Some(path),
+        Err(ResolveOpenCodeDbPathError::HomeDirNotFound) => {
+            notices.push("OpenCode DB disabled: home directory not found".to_string());
+            None
+        }
+    };
+
+    let config = match resolve_ccbox_config_path() {
+        Ok(path) => match load_ccbox_config(&path) {
+            Ok(config) => config,
+            Err(error) => {
+                notices.push(format!("Custom engines disabled: {error}"));
+                CcboxConfig::default()
+            }
+        },
+        Err(_) => CcboxConfig::default(),
     };
 
     scan_all_sessions_with_dirs(
         codex_sessions_dir,
         claude_projects_dir.as_deref(),
-        claude_resolve_notice,
         gemini_root_dir.as_deref(),
-        gemini_resolve_notice,
         opencode_db_path.as_deref(),
-        opencode_resolve_notice,
+        &config,
+        notices,
+        progress,
     )
 }
 
+/// `resolve_notices` explain sources that couldn't be located; they lead the scan's notice.
+/// `config` supplies the extra session dirs, the custom engines and whether projects are grouped
+/// by repository.
 fn scan_all_sessions_with_dirs(
     codex_sessions_dir: &Path,
     claude_projects_dir: Option<&Path>,
-    claude_resolve_notice: Option<String>,
     gemini_root_dir: Option<&Path>,
-    gemini_resolve_notice: Option<String>,
     opencode_db_path: Option<&Path>,
-    opencode_resolve_notice: Option<String>,
+    config: &CcboxConfig,
+    resolve_notices: Vec<String>,
+    progress: &ScanProgress,
 ) -> MultiEngineScanOutput {
     let mut sessions: Vec<SessionSummary> = Vec::new();
     let mut warnings = 0usize;
-    let mut notices: Vec<String> = Vec::new();
+    let mut notices: Vec<String> = resolve_notices;
+    let extra_dirs = resolve_extra_session_dirs(config);
 
-    match scan_sessions_dir(codex_sessions_dir) {
-        Ok(output) => {
-            warnings += output.warnings.get();
-            sessions.extend(output.sessions);
-        }
-        Err(ScanError::SessionsDirMissing(path)) => {
-            notices.push(format!("Codex sessions dir not found: {path}"));
+    for sessions_dir in session_roots(Some(codex_sessions_dir), &extra_dirs.codex) {
+        match scan_sessions_dir(sessions_dir, progress) {
+            Ok(output) => {
+                warnings += output.warnings.get();
+                sessions.extend(output.sessions);
+            }
+            Err(ScanError::SessionsDirMissing(path)) => {
+                notices.push(format!("Codex sessions dir not found: {path}"));
+            }
+            Err(error) => {
+                warnings = warnings.saturating_add(1);
+                notices.push(format!("Failed to scan Codex sessions: {error}"));
+            }
         }
-        Err(error) => {
-            warnings = warnings.saturating_add(1);
-            notices.push(format!("Failed to scan Codex sessions: {error}"));
+    }
+    progress.finish_engine("Codex", &sessions);
+
+    let claude_dirs = session_roots(claude_projects_dir, &extra_dirs.claude);
+    if !claude_dirs.is_empty() {
+        let mut claude_sessions = Vec::new();
+        for projects_dir in claude_dirs {
+            let output = scan_claude_projects_dir(projects_dir, progress);
+            warnings += output.warnings.get();
+            claude_sessions.extend(output.sessions);
+            if let Some(notice) = output.notice {
+                notices.push(notice);
+            }
         }
+        progress.finish_engine("Claude", &claude_sessions);
+        sessions.extend(claude_sessions);
     }
 
-    if let Some(notice) = claude_resolve_notice {
-        notices.push(notice);
+    let gemini_dirs = session_roots(gemini_root_dir, &extra_dirs.gemini);
+    if !gemini_dirs.is_empty() {
+        let mut gemini_sessions = Vec::new();
+        for root_dir in gemini_dirs {
+            let output = scan_gemini_root_dir(root_dir);
+            warnings += output.warnings.get();
+            gemini_sessions.extend(output.sessions);
+            if let Some(notice) = output.notice {
+                notices.push(notice);
+            }
+        }
+        progress.finish_engine("Gemini", &gemini_sessions);
+        sessions.extend(gemini_sessions);
     }
 
-    if let Some(projects_dir) = claude_projects_dir {
-        let output = scan_claude_projects_dir(projects_dir);
+    if let Some(db_path) = opencode_db_path {
+        let output = scan_opencode_db(db_path);
         warnings += output.warnings.get();
+        progress.finish_engine("OpenCode", &output.sessions);
         sessions.extend(output.sessions);
         if let Some(notice) = output.notice {
             notices.push(notice);
         }
     }
 
-    if let Some(notice) = gemini_resolve_notice {
-        notices.push(notice);
-    }
-
-    if let Some(root_dir) = gemini_root_dir {
-        let output = scan_gemini_root_dir(root_dir);
+    if !config.custom_engines.is_empty() {
+        let output = scan_custom_engines(&config.custom_engines);
         warnings += output.warnings.get();
+        progress.finish_engine("Custom", &output.sessions);
         sessions.extend(output.sessions);
         if let Some(notice) = output.notice {
             notices.push(notice);
         }
     }
 
-    if let Some(notice) = opencode_resolve_notice {
-        notices.push(notice);
+    if !config.remote_sources.is_empty() {
+        match resolve_ccbox_state_dir() {
+            Ok(state_dir) => {
+                let output = scan_remote_mirrors(&config.remote_sources, &state_dir);
+                warnings += output.warnings.get();
+                progress.finish_engine("Remote", &output.sessions);
+                sessions.extend(output.sessions);
+            }
+            Err(error) => notices.push(format!("Remote sources disabled: {error}")),
+        }
     }
 
-    if let Some(db_path) = opencode_db_path {
-        let output = scan_opencode_db(db_path);
-        warnings += output.warnings.get();
-        sessions.extend(output.sessions);
-        if let Some(notice) = output.notice {
-            notices.push(notice);
-        }
+    enrich_sessions_with_git(&mut sessions);
+    if config.group_projects_by_repo {
+        group_sessions_by_repo(&mut sessions);
     }
 
     if let Ok(state_dir) = resolve_ccbox_state_dir() {
         match load_session_aliases(&state_dir) {
-            Ok(aliases) => apply_session_aliases(&mut sessions, &aliases),
+            Ok(mut aliases) => {
+                if aliases.migrate_legacy(&sessions)
+                    && let Err(error) = save_session_aliases(&state_dir, &aliases)
+                {
+                    notices.push(format!("Failed to migrate session aliases: {error}"));
+                }
+                apply_session_aliases(&mut sessions, &aliases);
+            }
             Err(error) => {
                 warnings = warnings.saturating_add(1);
                 notices.push(format!("Failed to load session aliases: {error}"));
@@ -127,7 +195,14 @@ fn scan_all_sessions_with_dirs(
         }
 
         match load_session_projects(&state_dir) {
-            Ok(projects) => apply_session_projects(&mut sessions, &projects),
+            Ok(mut projects) => {
+                if projects.migrate_legacy(&sessions)
+                    && let Err(error) = save_session_projects(&state_dir, &projects)
+                {
+                    notices.push(format!("Failed to migrate session projects: {error}"));
+                }
+                apply_session_projects(&mut sessions, &projects);
+            }
             Err(error) => {
                 warnings = warnings.saturating_add(1);
                 notices.push(format!("Failed to load session projects: {error}"));
@@ -142,6 +217,25 @@ fn scan_all_sessions_with_dirs(
     }
 }
 
+/// The `extra_session_dirs` from the config with `~/` expanded.
+pub fn resolve_extra_session_dirs(config: &CcboxConfig) -> SessionDirsConfig {
+    config.extra_session_dirs.resolve(home_dir().as_deref())
+}
+
+/// The engine's default root (when it resolved) followed by the configured extras, without
+/// repeating the default.
+fn session_roots<'a>(default: Option<&'a Path>, extras: &'a [PathBuf]) -> Vec<&'a Path> {
+    default
+        .into_iter()
+        .chain(
+            extras
+                .iter()
+                .map(PathBuf::as_path)
+                .filter(|dir| Some(*dir) != default),
+        )
+        .collect()
+}
+
 fn join_notices(notices: Vec<String>) -> Option<String> {
     let text = notices
         .into_iter()
@@ -175,17 +269,70 @@ mod tests {
         )
         .expect("write");
 
+        let progress = ScanProgress::default();
         let output = scan_all_sessions_with_dirs(
             &codex_sessions_dir,
             Some(&claude_projects),
             None,
             None,
+            &CcboxConfig::default(),
+            Vec::new(),
+            &progress,
+        );
+
+        assert_eq!(output.sessions.len(), 1);
+        assert!(output.notice.is_some());
+        assert_eq!(progress.finished_engines(), ["Codex", "Claude"]);
+        assert_eq!(progress.finished_sessions().len(), 1);
+    }
+
+    #[test]
+    fn scans_extra_session_dirs_after_the_defaults() {
+        let dir = tempdir().expect("tempdir");
+        let codex_default = dir.path().join("codex");
+        let codex_extra = dir.path().join("codex-work");
+        fs::create_dir_all(&codex_default).expect("create");
+        fs::create_dir_all(codex_extra.join("2026")).expect("create");
+        fs::write(
+            codex_extra.join("2026").join("rollout.jsonl"),
+            r#"{"type":"session_meta","payload":{"id":"abc","timestamp":"2026-02-18T21:39:39.022Z","cwd":"/tmp/work"}}"#,
+        )
+        .expect("write");
+
+        let claude_extra = dir.path().join("claude-mirror");
+        fs::create_dir_all(claude_extra.join("k")).expect("create");
+        fs::write(
+            claude_extra.join("k").join("s.jsonl"),
+            r#"{"type":"user","cwd":"/tmp/p","sessionId":"s","timestamp":"2026-02-19T00:00:00Z","message":{"content":"hello"}}"#,
+        )
+        .expect("write");
+
+        let config = CcboxConfig {
+            extra_session_dirs: SessionDirsConfig {
+                // Listing the default again doesn't scan it twice.
+                codex: vec![codex_extra, codex_default.clone()],
+                claude: vec![claude_extra],
+                gemini: Vec::new(),
+            },
+            ..CcboxConfig::default()
+        };
+        let progress = ScanProgress::default();
+        let output = scan_all_sessions_with_dirs(
+            &codex_default,
             None,
             None,
             None,
+            &config,
+            Vec::new(),
+            &progress,
         );
 
-        assert_eq!(output.sessions.len(), 1);
-        assert!(output.notice.is_some());
+        let ids = output
+            .sessions
+            .iter()
+            .map(|session| session.meta.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, ["abc", "s"]);
+        assert_eq!(progress.finished_engines(), ["Codex", "Claude"]);
     }
 }
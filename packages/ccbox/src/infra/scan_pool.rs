@@ -0,0 +1,125 @@
+use crate::domain::SessionSummary;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+/// Inputs below this are scanned on the calling thread; spawning workers costs more than it saves.
+const MIN_PARALLEL_INPUTS: usize = 32;
+
+const MAX_SCAN_WORKERS: usize = 16;
+
+/// How far a scan has got: inputs (session files or project folders) found and finished so far,
+/// and the sessions of each engine whose scan is complete. Shared with whoever shows progress
+/// while the scan runs.
+#[derive(Debug, Default)]
+pub struct ScanProgress {
+    total: AtomicUsize,
+    done: AtomicUsize,
+    finished: Mutex<FinishedEngines>,
+}
+
+#[derive(Debug, Default)]
+struct FinishedEngines {
+    engines: Vec<&'static str>,
+    sessions: Vec<SessionSummary>,
+}
+
+impl ScanProgress {
+    /// `(done, total)`; `total` grows while directories are still being walked.
+    pub fn snapshot(&self) -> (usize, usize) {
+        (
+            self.done.load(Ordering::Relaxed),
+            self.total.load(Ordering::Relaxed),
+        )
+    }
+
+    pub fn finish_engine(&self, engine: &'static str, sessions: &[SessionSummary]) {
+        if let Ok(mut finished) = self.finished.lock() {
+            finished.engines.push(engine);
+            finished.sessions.extend_from_slice(sessions);
+        }
+    }
+
+    pub fn finished_engines(&self) -> Vec<&'static str> {
+        self.finished
+            .lock()
+            .map(|finished| finished.engines.clone())
+            .unwrap_or_default()
+    }
+
+    /// Sessions of the engines finished so far, before aliases, moves and git info are applied.
+    pub fn finished_sessions(&self) -> Vec<SessionSummary> {
+        self.finished
+            .lock()
+            .map(|finished| finished.sessions.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// Runs `scan` over `inputs` on a pool of scoped worker threads (one per core, at most 16) and
+/// returns the results in input order.
+pub fn scan_in_parallel<I, T, F>(inputs: &[I], progress: &ScanProgress, scan: F) -> Vec<T>
+where
+    I: Sync,
+    T: Send,
+    F: Fn(&I) -> T + Sync,
+{
+    progress.total.fetch_add(inputs.len(), Ordering::Relaxed);
+    let run = |input: &I| {
+        let result = scan(input);
+        progress.done.fetch_add(1, Ordering::Relaxed);
+        result
+    };
+
+    let workers = thread::available_parallelism()
+        .map(|count| count.get())
+        .unwrap_or(1)
+        .min(MAX_SCAN_WORKERS);
+    if workers <= 1 || inputs.len() < MIN_PARALLEL_INPUTS {
+        return inputs.iter().map(run).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let mut indexed = thread::scope(|scope| {
+        let handles = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut out = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(input) = inputs.get(index) else {
+                            break;
+                        };
+                        out.push((index, run(input)));
+                    }
+                    out
+                })
+            })
+            .collect::<Vec<_>>();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap_or_default())
+            .collect::<Vec<_>>()
+    });
+    indexed.sort_by_key(|(index, _)| *index);
+    indexed.into_iter().map(|(_, result)| result).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_input_order_and_counts_progress() {
+        let inputs = (0..500).collect::<Vec<usize>>();
+        let progress = ScanProgress::default();
+
+        let doubled = scan_in_parallel(&inputs, &progress, |value| value * 2);
+
+        assert_eq!(
+            doubled,
+            inputs.iter().map(|value| value * 2).collect::<Vec<_>>()
+        );
+        assert_eq!(progress.snapshot(), (500, 500));
+    }
+}
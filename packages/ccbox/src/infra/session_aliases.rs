@@ -1,4 +1,4 @@
-use crate::domain::{SessionEngine, SessionSummary};
+use crate::domain::{SessionEngine, SessionKey, SessionSummary, migrate_legacy_session_keys};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 use std::fs;
@@ -8,17 +8,19 @@ use thiserror::Error;
 
 #[derive(Clone, Debug, Default)]
 pub struct SessionAliases {
-    aliases: BTreeMap<String, String>,
+    aliases: BTreeMap<SessionKey, String>,
+    /// Entries from before keys carried the engine; see [`SessionAliases::migrate_legacy`].
+    legacy: BTreeMap<String, String>,
 }
 
 impl SessionAliases {
     pub fn title_for(&self, engine: SessionEngine, session_id: &str) -> Option<&str> {
-        let key = session_alias_key(engine, session_id);
+        let key = SessionKey::new(engine, session_id);
         self.aliases.get(&key).map(|s| s.as_str())
     }
 
     pub fn set(&mut self, engine: SessionEngine, session_id: &str, title: &str) {
-        let key = session_alias_key(engine, session_id);
+        let key = SessionKey::new(engine, session_id);
         let title = title.trim();
         if title.is_empty() {
             self.aliases.remove(&key);
@@ -26,6 +28,18 @@ impl SessionAliases {
             self.aliases.insert(key, title.to_string());
         }
     }
+
+    pub fn entries(&self) -> impl Iterator<Item = (&SessionKey, &str)> {
+        self.aliases
+            .iter()
+            .map(|(key, value)| (key, value.as_str()))
+    }
+
+    /// Re-keys bare-id entries by engine using the scanned sessions. Returns whether the store
+    /// changed and should be saved.
+    pub fn migrate_legacy(&mut self, sessions: &[SessionSummary]) -> bool {
+        migrate_legacy_session_keys(&mut self.legacy, &mut self.aliases, sessions)
+    }
 }
 
 #[derive(Debug, Error)]
@@ -70,9 +84,18 @@ pub fn load_session_aliases(state_dir: &Path) -> Result<SessionAliases, LoadSess
     };
 
     let file: SessionAliasesFile = serde_json::from_str(&raw)?;
-    Ok(SessionAliases {
-        aliases: file.aliases,
-    })
+    let mut store = SessionAliases::default();
+    for (key, value) in file.aliases {
+        match SessionKey::parse(&key) {
+            Some(key) => {
+                store.aliases.insert(key, value);
+            }
+            None => {
+                store.legacy.insert(key, value);
+            }
+        }
+    }
+    Ok(store)
 }
 
 pub fn save_session_aliases(
@@ -84,8 +107,13 @@ pub fn save_session_aliases(
     let path = session_aliases_path(state_dir);
     let tmp = path.with_extension("json.tmp");
     let file = SessionAliasesFile {
-        version: 1,
-        aliases: aliases.aliases.clone(),
+        version: 2,
+        aliases: aliases
+            .aliases
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.clone()))
+            .chain(aliases.legacy.clone())
+            .collect(),
     };
     let text = serde_json::to_string_pretty(&file)?;
     fs::write(&tmp, text)?;
@@ -115,19 +143,7 @@ pub fn apply_session_aliases(sessions: &mut [SessionSummary], aliases: &SessionA
     }
 }
 
-pub fn session_alias_key(engine: SessionEngine, session_id: &str) -> String {
-    format!("{}:{}", engine_prefix(engine), session_id)
-}
-
-fn engine_prefix(engine: SessionEngine) -> &'static str {
-    match engine {
-        SessionEngine::Codex => "codex",
-        SessionEngine::Claude => "claude",
-        SessionEngine::Gemini => "gemini",
-        SessionEngine::OpenCode => "opencode",
-    }
-}
-
+/// Version 2 keys every entry as `engine:id`; version 1 files may still hold bare session ids.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 struct SessionAliasesFile {
     version: u32,
@@ -159,6 +175,7 @@ mod tests {
                 id: "s1".to_string(),
                 cwd: PathBuf::from("/tmp/p"),
                 started_at_rfc3339: "2026-02-20T00:00:00Z".to_string(),
+                git: None,
             },
             PathBuf::from("/tmp/log.jsonl"),
             "auto".to_string(),
@@ -182,4 +199,42 @@ mod tests {
         let loaded = load_session_aliases(state).expect("load");
         assert_eq!(loaded.title_for(SessionEngine::Codex, "s1"), None);
     }
+
+    #[test]
+    fn migrates_bare_id_aliases_from_version_1() {
+        let dir = tempdir().expect("tempdir");
+        let state = dir.path();
+        fs::write(
+            session_aliases_path(state),
+            r#"{ "version": 1, "aliases": { "s1": "Old", "codex:s2": "Keyed" } }"#,
+        )
+        .expect("write");
+
+        let mut loaded = load_session_aliases(state).expect("load");
+        assert_eq!(loaded.title_for(SessionEngine::Claude, "s1"), None);
+        assert_eq!(loaded.title_for(SessionEngine::Codex, "s2"), Some("Keyed"));
+
+        let sessions = vec![make_session_summary(
+            SessionMeta {
+                id: "s1".to_string(),
+                cwd: PathBuf::from("/tmp/p"),
+                started_at_rfc3339: "2026-02-20T00:00:00Z".to_string(),
+                git: None,
+            },
+            PathBuf::from("/tmp/log.jsonl"),
+            "auto".to_string(),
+            0,
+            Some(SystemTime::now()),
+            SessionEngine::Claude,
+        )];
+        assert!(loaded.migrate_legacy(&sessions));
+        save_session_aliases(state, &loaded).expect("save");
+
+        let raw = fs::read_to_string(session_aliases_path(state)).expect("read");
+        assert!(raw.contains(r#""version": 2"#));
+        assert!(raw.contains(r#""claude:s1": "Old""#));
+        let reloaded = load_session_aliases(state).expect("reload");
+        assert_eq!(reloaded.title_for(SessionEngine::Claude, "s1"), Some("Old"));
+        assert_eq!(reloaded.title_for(SessionEngine::Codex, "s1"), None);
+    }
 }
@@ -1,14 +1,14 @@
 use crate::domain::{
-    ParsedLogLine, SessionTimeline, TimelineItem, TimelineItemKind, parse_log_value,
+    CustomLogFlavor, ParsedLogLine, SessionTimeline, TimelineItem, TimelineItemKind,
+    custom_log_messages, is_chat_message_line, parse_chat_message_items,
+    parse_claude_timeline_items, parse_log_value,
 };
 use std::collections::BTreeMap;
 use std::fs::File;
-use std::io::{self, BufRead, BufReader};
+use std::io::{self, BufRead, Read, Seek, SeekFrom};
 use std::path::Path;
 use thiserror::Error;
 
-const MAX_TIMELINE_ITEMS: usize = 10_000;
-
 #[derive(Debug, Error)]
 pub enum LoadSessionTimelineError {
     #[error("failed to open session file: {0}")]
@@ -45,11 +45,23 @@ pub fn load_last_assistant_output(
         LogFormat::Claude => {
             return Ok(super::claude::load_claude_last_assistant_output(path)?);
         }
+        LogFormat::Chat(flavor) => {
+            let timeline = load_chat_session_timeline(path, flavor)?;
+            let output = timeline
+                .items
+                .into_iter()
+                .rev()
+                .find(|item| item.kind == TimelineItemKind::Assistant)
+                .map(|item| item.detail);
+            return Ok(LastAssistantOutput {
+                output,
+                warnings: timeline.warnings,
+            });
+        }
         LogFormat::Codex => {}
     }
 
-    let file = File::open(path)?;
-    let reader = BufReader::new(file);
+    let reader = super::open_log_reader(path)?;
 
     let mut warnings = 0usize;
     let mut current_turn_id: Option<String> = None;
@@ -110,11 +122,13 @@ pub fn load_session_timeline(path: &Path) -> Result<SessionTimeline, LoadSession
         LogFormat::Claude => {
             return Ok(super::claude::load_claude_session_timeline(path)?);
         }
+        LogFormat::Chat(flavor) => {
+            return Ok(load_chat_session_timeline(path, flavor)?);
+        }
         LogFormat::Codex => {}
     }
 
-    let file = File::open(path)?;
-    let reader = BufReader::new(file);
+    let reader = super::open_log_reader(path)?;
 
     let mut warnings = 0usize;
     let mut truncated = false;
@@ -212,10 +226,6 @@ pub fn load_session_timeline(path: &Path) -> Result<SessionTimeline, LoadSession
 
                 if let Some(turn_id) = item.turn_id.as_deref() {
                     if last_emitted_turn_id.as_deref() != Some(turn_id) {
-                        if items.len() >= MAX_TIMELINE_ITEMS {
-                            truncated = true;
-                            break;
-                        }
                         items.push(make_turn_item(
                             turn_id,
                             turn_context_line_nos.get(turn_id).copied(),
@@ -224,10 +234,6 @@ pub fn load_session_timeline(path: &Path) -> Result<SessionTimeline, LoadSession
                     }
                 }
 
-                if items.len() >= MAX_TIMELINE_ITEMS {
-                    truncated = true;
-                    break;
-                }
                 items.push(item);
                 if is_user_prompt {
                     if let Some(last) = items.last() {
@@ -252,9 +258,62 @@ pub fn load_session_timeline(path: &Path) -> Result<SessionTimeline, LoadSession
         turn_contexts,
         warnings,
         truncated,
+        older: Vec::new(),
     })
 }
 
+const APPENDED_ITEMS_MAX_BYTES: u64 = 512 * 1024;
+
+/// Reads complete JSONL lines appended to a Codex or Claude session log since `offset` and
+/// returns their timeline items plus the offset to resume from. A trailing partial line is left
+/// for the next call.
+pub fn read_appended_timeline_items(
+    path: &Path,
+    offset: u64,
+) -> io::Result<(Vec<TimelineItem>, u64)> {
+    if super::is_compressed_log(path) {
+        // Archived logs are not appended to.
+        return Ok((Vec::new(), offset));
+    }
+    let mut file = File::open(path)?;
+    let size = file.metadata()?.len();
+    if offset >= size {
+        return Ok((Vec::new(), size.min(offset)));
+    }
+    file.seek(SeekFrom::Start(offset))?;
+    let mut buf = Vec::new();
+    file.take(APPENDED_ITEMS_MAX_BYTES).read_to_end(&mut buf)?;
+
+    let Some(last_newline) = buf.iter().rposition(|byte| *byte == b'\n') else {
+        // A single line larger than the read window cannot be parsed; skip past it.
+        let skipped = u64::try_from(buf.len()).unwrap_or(u64::MAX);
+        let next = if skipped >= APPENDED_ITEMS_MAX_BYTES {
+            offset.saturating_add(skipped)
+        } else {
+            offset
+        };
+        return Ok((Vec::new(), next));
+    };
+
+    let complete = &buf[..=last_newline];
+    let mut items = Vec::new();
+    for line in String::from_utf8_lossy(complete).lines() {
+        let Ok(value) = serde_json::from_str::<serde_json::Value>(line.trim()) else {
+            continue;
+        };
+        match parse_log_value(&value, None) {
+            ParsedLogLine::Item(item) => items.push(item),
+            _ if is_chat_message_line(&value) => {
+                items.extend(parse_chat_message_items(&value, None));
+            }
+            _ => items.extend(parse_claude_timeline_items(&value, 0)),
+        }
+    }
+
+    let consumed = u64::try_from(complete.len()).unwrap_or(u64::MAX);
+    Ok((items, offset.saturating_add(consumed)))
+}
+
 fn make_turn_item(turn_id: &str, source_line_no: Option<u64>) -> TimelineItem {
     TimelineItem {
         kind: TimelineItemKind::Turn,
@@ -278,37 +337,71 @@ enum LogFormat {
     Codex,
     Claude,
     Gemini,
+    /// Plain chat messages written by a custom engine.
+    Chat(CustomLogFlavor),
 }
 
 fn detect_log_format(path: &Path) -> LogFormat {
     if super::gemini::is_gemini_session_path(path) {
         return LogFormat::Gemini;
     }
-    if looks_like_claude_jsonl(path) {
+    if path.extension().and_then(|ext| ext.to_str()) == Some("json") {
+        return LogFormat::Chat(CustomLogFlavor::SingleJson);
+    }
+    let values = read_jsonl_values(path, 50);
+    if values.iter().any(is_claude_line) {
         return LogFormat::Claude;
     }
+    if values.iter().any(is_chat_message_line) {
+        // Both JSONL flavors go through the same message parser.
+        return LogFormat::Chat(CustomLogFlavor::JsonlOpenAi);
+    }
     LogFormat::Codex
 }
 
-fn looks_like_claude_jsonl(path: &Path) -> bool {
-    for value in read_jsonl_values(path, 50) {
-        let line_type = value.get("type").and_then(|v| v.as_str()).unwrap_or("");
-        if matches!(
-            line_type,
-            "user" | "assistant" | "summary" | "progress" | "file-history-snapshot"
-        ) {
-            return true;
+fn is_claude_line(value: &serde_json::Value) -> bool {
+    let line_type = value.get("type").and_then(|v| v.as_str()).unwrap_or("");
+    matches!(
+        line_type,
+        "user" | "assistant" | "summary" | "progress" | "file-history-snapshot"
+    )
+}
+
+fn load_chat_session_timeline(path: &Path, flavor: CustomLogFlavor) -> io::Result<SessionTimeline> {
+    let text = super::read_log_to_string(path)?;
+    let mut warnings = 0usize;
+    let items = if flavor == CustomLogFlavor::SingleJson {
+        custom_log_messages(flavor, &text)
+            .iter()
+            .flat_map(|value| parse_chat_message_items(value, None))
+            .collect::<Vec<_>>()
+    } else {
+        let mut items = Vec::new();
+        for (index, line) in text.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<serde_json::Value>(line) {
+                Ok(value) => items.extend(parse_chat_message_items(&value, Some(index as u64 + 1))),
+                Err(_) => warnings += 1,
+            }
         }
-    }
-    false
+        items
+    };
+    Ok(SessionTimeline {
+        items,
+        turn_contexts: BTreeMap::new(),
+        warnings,
+        truncated: false,
+        older: Vec::new(),
+    })
 }
 
 fn read_jsonl_values(path: &Path, limit: usize) -> Vec<serde_json::Value> {
-    let file = match File::open(path) {
-        Ok(file) => file,
+    let reader = match super::open_log_reader(path) {
+        Ok(reader) => reader,
         Err(_) => return Vec::new(),
     };
-    let reader = BufReader::new(file);
     let mut out: Vec<serde_json::Value> = Vec::new();
     for line_result in reader.lines().take(limit.saturating_mul(2)) {
         let Ok(line) = line_result else {
@@ -332,10 +425,90 @@ fn read_jsonl_values(path: &Path, limit: usize) -> Vec<serde_json::Value> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::latest_plan_snapshot;
     use std::fs;
     use std::io;
     use tempfile::tempdir;
 
+    #[test]
+    fn reads_plan_updates_incrementally_and_keeps_partial_lines() -> io::Result<()> {
+        let dir = tempdir()?;
+        let path = dir.path().join("rollout.jsonl");
+        let codex_plan = serde_json::json!({
+            "type": "response_item",
+            "payload": {
+                "type": "function_call",
+                "name": "update_plan",
+                "arguments": serde_json::json!({
+                    "plan": [{ "step": "a", "status": "completed" }, { "step": "b", "status": "pending" }]
+                }).to_string()
+            }
+        });
+        let claude_plan = serde_json::json!({
+            "type": "assistant",
+            "message": { "content": [{
+                "type": "tool_use", "id": "t1", "name": "TodoWrite",
+                "input": { "todos": [{ "content": "a", "status": "completed" }] }
+            }] }
+        });
+        fs::write(&path, format!("{codex_plan}\n{{\"type\":"))?;
+
+        let (items, offset) = read_appended_timeline_items(&path, 0)?;
+        assert_eq!(
+            latest_plan_snapshot(&items)
+                .map(|plan| plan.progress_label())
+                .as_deref(),
+            Some("1/2 steps")
+        );
+        assert_eq!(offset, codex_plan.to_string().len() as u64 + 1);
+
+        let (items, same) = read_appended_timeline_items(&path, offset)?;
+        assert_eq!((items.len(), same), (0, offset));
+
+        fs::write(&path, format!("{codex_plan}\n{claude_plan}\n"))?;
+        let (items, _) = read_appended_timeline_items(&path, offset)?;
+        assert_eq!(
+            latest_plan_snapshot(&items)
+                .map(|plan| plan.progress_label())
+                .as_deref(),
+            Some("1/1 steps")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn loads_plain_chat_logs_from_custom_engines() -> io::Result<()> {
+        let dir = tempdir()?;
+        let path = dir.path().join("chat.jsonl");
+        fs::write(
+            &path,
+            "{\"role\":\"user\",\"content\":\"hi\"}\n\n{\"role\":\"assistant\",\"content\":\"hello\"}\n",
+        )?;
+        let timeline = load_session_timeline(&path).expect("timeline");
+        let lines = timeline
+            .items
+            .iter()
+            .map(|item| (item.kind, item.source_line_no))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            vec![
+                (TimelineItemKind::User, Some(1)),
+                (TimelineItemKind::Assistant, Some(3)),
+            ]
+        );
+
+        let path = dir.path().join("chat.json");
+        fs::write(&path, r#"[{"role":"user","content":"hi"}]"#)?;
+        let output = load_last_assistant_output(&path).expect("output");
+        assert_eq!(output.output, None);
+        assert_eq!(
+            load_session_timeline(&path).expect("timeline").items.len(),
+            1
+        );
+        Ok(())
+    }
+
     #[test]
     fn merges_duplicate_token_count_items_by_replacing_previous() {
         let dir = tempdir().expect("tempdir");
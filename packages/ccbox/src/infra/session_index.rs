@@ -1,17 +1,21 @@
 use crate::domain::{
     ParsedLogLine, SessionEngine, SessionSummary, TimelineItemKind, ToolOutputOutcome,
-    classify_tool_output_detail, parse_claude_timeline_items, parse_gemini_timeline_items,
-    parse_log_value,
+    build_search_terms, classify_tool_output_detail, parse_claude_timeline_items,
+    parse_gemini_timeline_items, parse_log_value, parse_rfc3339_to_unix_ms, recent_token_activity,
+    session_total_tokens,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::BTreeMap;
 use std::fs;
-use std::io;
+use std::io::{self, BufRead};
 use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 use thiserror::Error;
 
+/// Token-count events kept per session for the Sessions list sparkline.
+const TOKEN_ACTIVITY_EVENTS: usize = 12;
+
 #[derive(Debug, Error)]
 pub enum ResolveCcboxStateDirError {
     #[error("home directory not found")]
@@ -31,12 +35,79 @@ pub struct SessionIndex {
 }
 
 impl SessionIndex {
+    /// Sum of `total_tokens` over every indexed session.
+    pub fn tokens_indexed(&self) -> u64 {
+        self.entries
+            .values()
+            .filter_map(|entry| entry.total_tokens)
+            .sum()
+    }
+
+    /// Sum of `total_tokens` over `sessions`; `None` when none of them is indexed yet.
+    pub fn sessions_tokens(&self, sessions: &[SessionSummary]) -> Option<u64> {
+        sessions
+            .iter()
+            .filter_map(|session| self.total_tokens(&session.log_path))
+            .reduce(u64::saturating_add)
+    }
+
     pub fn total_tokens(&self, log_path: &Path) -> Option<u64> {
         self.entries
             .get(log_path)
             .and_then(|entry| entry.total_tokens)
     }
 
+    /// Tokens of the most recent token-count events, oldest first (see `recent_token_activity`).
+    pub fn token_activity(&self, log_path: &Path) -> Option<&[u64]> {
+        self.entries
+            .get(log_path)
+            .and_then(|entry| entry.token_activity.as_deref())
+    }
+
+    pub fn item_count(&self, log_path: &Path) -> Option<u64> {
+        self.entries
+            .get(log_path)
+            .and_then(|entry| entry.item_count)
+    }
+
+    /// Words of the session's prompts and outputs (see `build_search_terms`).
+    pub fn search_terms(&self, log_path: &Path) -> Option<&str> {
+        self.entries
+            .get(log_path)
+            .and_then(|entry| entry.search_terms.as_deref())
+    }
+
+    /// From the first to the last timestamped timeline item; falls back to the session start and
+    /// the last timestamped event in the log.
+    pub fn duration_ms(&self, session: &SessionSummary) -> Option<u64> {
+        let entry = self.entries.get(&session.log_path)?;
+        if let Some(duration) = entry.duration_ms {
+            return Some(duration);
+        }
+        let last = entry.last_event_unix_ms?;
+        let start = parse_rfc3339_to_unix_ms(&session.meta.started_at_rfc3339)?;
+        u64::try_from(last.checked_sub(start)?).ok()
+    }
+
+    pub fn tool_calls(&self, log_path: &Path) -> Option<u32> {
+        self.entries
+            .get(log_path)
+            .and_then(|entry| entry.tool_calls)
+    }
+
+    pub fn assistant_turns(&self, log_path: &Path) -> Option<u32> {
+        self.entries
+            .get(log_path)
+            .and_then(|entry| entry.assistant_turns)
+    }
+
+    /// Latest timestamp of any timeline item.
+    pub fn last_activity_unix_ms(&self, log_path: &Path) -> Option<i64> {
+        self.entries
+            .get(log_path)
+            .and_then(|entry| entry.last_activity_unix_ms)
+    }
+
     pub fn tool_failures(&self, log_path: &Path) -> Option<ToolFailureCounts> {
         let entry = self.entries.get(log_path)?;
         Some(ToolFailureCounts {
@@ -54,6 +125,14 @@ pub struct SessionIndexEntry {
     pub last_tokens: Option<u64>,
     pub tool_calls_invalid: Option<u32>,
     pub tool_calls_error: Option<u32>,
+    pub item_count: Option<u64>,
+    pub last_event_unix_ms: Option<i64>,
+    pub search_terms: Option<String>,
+    pub token_activity: Option<Vec<u64>>,
+    pub duration_ms: Option<u64>,
+    pub tool_calls: Option<u32>,
+    pub assistant_turns: Option<u32>,
+    pub last_activity_unix_ms: Option<i64>,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -124,8 +203,15 @@ pub fn refresh_session_index(sessions: &[SessionSummary], prior: &SessionIndex)
         let size_bytes = session.file_size_bytes;
         let modified_unix_ms = session.file_modified.and_then(system_time_to_unix_ms);
 
+        // Entries written before item counts, search terms, token activity and tool/turn counts
+        // existed are rebuilt once.
         let reuse = prior.entries.get(&log_path).is_some_and(|entry| {
-            entry.size_bytes == size_bytes && entry.modified_unix_ms == modified_unix_ms
+            entry.size_bytes == size_bytes
+                && entry.modified_unix_ms == modified_unix_ms
+                && entry.item_count.is_some()
+                && entry.search_terms.is_some()
+                && entry.token_activity.is_some()
+                && entry.tool_calls.is_some()
         });
         if reuse {
             if let Some(entry) = prior.entries.get(&log_path).cloned() {
@@ -134,7 +220,7 @@ pub fn refresh_session_index(sessions: &[SessionSummary], prior: &SessionIndex)
             }
         }
 
-        let (total_tokens, last_tokens) = if session.engine == SessionEngine::Codex {
+        let (mut total_tokens, last_tokens) = if session.engine == SessionEngine::Codex {
             extract_last_token_usage(&session.log_path)
         } else {
             (None, None)
@@ -142,6 +228,10 @@ pub fn refresh_session_index(sessions: &[SessionSummary], prior: &SessionIndex)
 
         let (tool_calls_invalid, tool_calls_error) =
             extract_tool_failure_counts(&session.log_path, session.engine);
+        let (item_count, last_event_unix_ms) =
+            extract_log_extent(&session.log_path, session.engine);
+        let facts = extract_timeline_facts(&session.log_path);
+        total_tokens = total_tokens.or(facts.total_tokens);
         next_entries.insert(
             log_path,
             SessionIndexEntry {
@@ -151,6 +241,14 @@ pub fn refresh_session_index(sessions: &[SessionSummary], prior: &SessionIndex)
                 last_tokens,
                 tool_calls_invalid,
                 tool_calls_error,
+                item_count,
+                last_event_unix_ms,
+                search_terms: facts.search_terms,
+                token_activity: facts.token_activity,
+                duration_ms: facts.duration_ms,
+                tool_calls: facts.tool_calls,
+                assistant_turns: facts.assistant_turns,
+                last_activity_unix_ms: facts.last_activity_unix_ms,
             },
         );
     }
@@ -184,12 +282,120 @@ fn extract_last_token_usage(path: &Path) -> (Option<u64>, Option<u64>) {
     (None, None)
 }
 
+/// Record count and last event time. JSONL logs are counted line by line without parsing;
+/// Gemini stores one JSON document with a `messages` array and a `lastUpdated` stamp.
+fn extract_log_extent(path: &Path, engine: SessionEngine) -> (Option<u64>, Option<i64>) {
+    const TAIL_BYTES: usize = 256 * 1024;
+    const MAX_GEMINI_BYTES: u64 = 10 * 1024 * 1024;
+
+    if engine == SessionEngine::Gemini {
+        let value = fs::metadata(path)
+            .ok()
+            .filter(|meta| meta.len() <= MAX_GEMINI_BYTES)
+            .and_then(|_| fs::read_to_string(path).ok())
+            .and_then(|text| serde_json::from_str::<Value>(&text).ok());
+        let Some(value) = value else {
+            return (None, None);
+        };
+        let count = value
+            .get("messages")
+            .and_then(|messages| messages.as_array())
+            .map(|messages| messages.len() as u64);
+        let last = value
+            .get("lastUpdated")
+            .and_then(|stamp| stamp.as_str())
+            .and_then(parse_rfc3339_to_unix_ms);
+        return (count, last);
+    }
+
+    let count = super::open_log_reader(path).ok().and_then(|reader| {
+        let mut count = 0u64;
+        for line in reader.split(b'\n') {
+            if !line.ok()?.trim_ascii().is_empty() {
+                count = count.saturating_add(1);
+            }
+        }
+        Some(count)
+    });
+    let last = super::read_tail(path, TAIL_BYTES)
+        .ok()
+        .and_then(|(tail, _size)| find_last_event_unix_ms(&tail));
+    (count, last)
+}
+
+#[derive(Debug, Default)]
+struct TimelineFacts {
+    search_terms: Option<String>,
+    token_activity: Option<Vec<u64>>,
+    total_tokens: Option<u64>,
+    duration_ms: Option<u64>,
+    tool_calls: Option<u32>,
+    assistant_turns: Option<u32>,
+    last_activity_unix_ms: Option<i64>,
+}
+
+/// Facts that need a full parse of the log; only runs when the file changed since it was last
+/// indexed.
+fn extract_timeline_facts(path: &Path) -> TimelineFacts {
+    let Ok(timeline) = super::load_session_timeline(path) else {
+        return TimelineFacts::default();
+    };
+    let count_kind = |kind: TimelineItemKind| {
+        let count = timeline
+            .items
+            .iter()
+            .filter(|item| item.kind == kind)
+            .count();
+        u32::try_from(count).unwrap_or(u32::MAX)
+    };
+    let stamps = timeline.items.iter().filter_map(|item| item.timestamp_ms);
+    let first_activity = stamps.clone().min();
+    let last_activity = stamps.max();
+    let duration_ms = first_activity
+        .zip(last_activity)
+        .and_then(|(first, last)| u64::try_from(last.checked_sub(first)?).ok());
+
+    let texts = timeline
+        .items
+        .iter()
+        .filter(|item| {
+            matches!(
+                item.kind,
+                TimelineItemKind::User | TimelineItemKind::Assistant
+            )
+        })
+        .map(|item| item.detail.as_str());
+    TimelineFacts {
+        search_terms: Some(build_search_terms(texts)),
+        token_activity: Some(recent_token_activity(
+            &timeline.items,
+            TOKEN_ACTIVITY_EVENTS,
+        )),
+        total_tokens: session_total_tokens(&timeline.items),
+        duration_ms,
+        tool_calls: Some(count_kind(TimelineItemKind::ToolCall)),
+        assistant_turns: Some(count_kind(TimelineItemKind::Assistant)),
+        last_activity_unix_ms: last_activity,
+    }
+}
+
+fn find_last_event_unix_ms(text: &str) -> Option<i64> {
+    text.lines().rev().find_map(|line| {
+        let value = serde_json::from_str::<Value>(line.trim()).ok()?;
+        value
+            .get("timestamp")
+            .and_then(|stamp| stamp.as_str())
+            .and_then(parse_rfc3339_to_unix_ms)
+    })
+}
+
 fn extract_tool_failure_counts(path: &Path, engine: SessionEngine) -> (Option<u32>, Option<u32>) {
     match engine {
         SessionEngine::Codex | SessionEngine::Claude | SessionEngine::OpenCode => {
             extract_tool_failure_counts_jsonl_tail(path, engine)
         }
         SessionEngine::Gemini => extract_tool_failure_counts_gemini_json(path),
+        SessionEngine::Custom => (None, None),
     }
 }
 
@@ -255,7 +461,7 @@ fn scan_tool_failures_jsonl(text: &str, engine: SessionEngine) -> (u32, u32) {
                     }
                 }
             }
-            SessionEngine::Gemini => {}
+            SessionEngine::Gemini | SessionEngine::Custom => {}
         }
     }
 
@@ -355,6 +561,22 @@ struct SessionIndexFileEntry {
     tool_calls_invalid: Option<u32>,
     #[serde(default)]
     tool_calls_error: Option<u32>,
+    #[serde(default)]
+    item_count: Option<u64>,
+    #[serde(default)]
+    last_event_unix_ms: Option<i64>,
+    #[serde(default)]
+    search_terms: Option<String>,
+    #[serde(default)]
+    token_activity: Option<Vec<u64>>,
+    #[serde(default)]
+    duration_ms: Option<u64>,
+    #[serde(default)]
+    tool_calls: Option<u32>,
+    #[serde(default)]
+    assistant_turns: Option<u32>,
+    #[serde(default)]
+    last_activity_unix_ms: Option<i64>,
 }
 
 impl SessionIndexFile {
@@ -370,11 +592,19 @@ impl SessionIndexFile {
                 last_tokens: entry.last_tokens,
                 tool_calls_invalid: entry.tool_calls_invalid,
                 tool_calls_error: entry.tool_calls_error,
+                item_count: entry.item_count,
+                last_event_unix_ms: entry.last_event_unix_ms,
+                search_terms: entry.search_terms.clone(),
+                token_activity: entry.token_activity.clone(),
+                duration_ms: entry.duration_ms,
+                tool_calls: entry.tool_calls,
+                assistant_turns: entry.assistant_turns,
+                last_activity_unix_ms: entry.last_activity_unix_ms,
             })
             .collect();
 
         Self {
-            version: 2,
+            version: 6,
             entries,
         }
     }
@@ -391,6 +621,14 @@ impl SessionIndexFile {
                     last_tokens: entry.last_tokens,
                     tool_calls_invalid: entry.tool_calls_invalid,
                     tool_calls_error: entry.tool_calls_error,
+                    item_count: entry.item_count,
+                    last_event_unix_ms: entry.last_event_unix_ms,
+                    search_terms: entry.search_terms,
+                    token_activity: entry.token_activity,
+                    duration_ms: entry.duration_ms,
+                    tool_calls: entry.tool_calls,
+                    assistant_turns: entry.assistant_turns,
+                    last_activity_unix_ms: entry.last_activity_unix_ms,
                 },
             );
         }
@@ -452,16 +690,142 @@ mod tests {
                 id: "s1".to_string(),
                 cwd: dir.path().to_path_buf(),
                 started_at_rfc3339: "2026-01-01T00:00:00Z".to_string(),
+                git: None,
             },
             log_path: log_path.clone(),
             title: "test".to_string(),
             file_size_bytes: meta_fs.len(),
             file_modified: meta_fs.modified().ok(),
+            parent_id: None,
+            project_root: None,
+            remote: None,
         };
 
-        let index = refresh_session_index(&[session], &SessionIndex::default());
+        let index = refresh_session_index(std::slice::from_ref(&session), &SessionIndex::default());
         let failures = index.tool_failures(&log_path).expect("tool failures");
         assert_eq!(failures.invalid, 1);
         assert_eq!(failures.error, 1);
+        assert_eq!(index.item_count(&log_path), Some(4));
+        assert_eq!(index.duration_ms(&session), Some(2_000));
+    }
+
+    #[test]
+    fn refresh_session_index_collects_prompt_and_output_words() {
+        let dir = tempdir().expect("tempdir");
+        let log_path = dir.path().join("session.jsonl");
+        let message = |role: &str, kind: &str, text: &str| {
+            serde_json::json!({
+                "type": "response_item",
+                "timestamp": "2026-01-01T00:00:00Z",
+                "payload": {
+                    "type": "message",
+                    "role": role,
+                    "content": [{ "type": kind, "text": text }]
+                }
+            })
+            .to_string()
+        };
+        let lines = [
+            message("user", "input_text", "Fix the flaky migration"),
+            message("assistant", "output_text", "Patched schema.rs"),
+        ];
+        std::fs::write(&log_path, lines.join("\n")).expect("write log");
+
+        let meta_fs = std::fs::metadata(&log_path).expect("metadata");
+        let session = SessionSummary {
+            engine: SessionEngine::Codex,
+            meta: SessionMeta {
+                id: "s1".to_string(),
+                cwd: dir.path().to_path_buf(),
+                started_at_rfc3339: "2026-01-01T00:00:00Z".to_string(),
+                git: None,
+            },
+            log_path: log_path.clone(),
+            title: "test".to_string(),
+            file_size_bytes: meta_fs.len(),
+            file_modified: meta_fs.modified().ok(),
+            parent_id: None,
+            project_root: None,
+            remote: None,
+        };
+
+        let index = refresh_session_index(std::slice::from_ref(&session), &SessionIndex::default());
+        assert_eq!(
+            index.search_terms(&log_path),
+            Some("fix flaky migration patched rs schema the")
+        );
+        assert_eq!(index.assistant_turns(&log_path), Some(1));
+        assert_eq!(index.tool_calls(&log_path), Some(0));
+    }
+
+    #[test]
+    fn refresh_session_index_persists_tool_calls_and_activity_span() {
+        let dir = tempdir().expect("tempdir");
+        let log_path = dir.path().join("session.jsonl");
+        let lines = [
+            serde_json::json!({
+                "type": "response_item",
+                "timestamp": "2026-01-01T00:00:05Z",
+                "payload": {
+                    "type": "message",
+                    "role": "user",
+                    "content": [{ "type": "input_text", "text": "List files" }]
+                }
+            })
+            .to_string(),
+            serde_json::json!({
+                "type": "response_item",
+                "timestamp": "2026-01-01T00:00:06Z",
+                "payload": {
+                    "type": "function_call",
+                    "call_id": "c1",
+                    "name": "shell",
+                    "arguments": "{\"command\":[\"ls\"]}"
+                }
+            })
+            .to_string(),
+            serde_json::json!({
+                "type": "response_item",
+                "timestamp": "2026-01-01T00:00:15Z",
+                "payload": {
+                    "type": "message",
+                    "role": "assistant",
+                    "content": [{ "type": "output_text", "text": "Done" }]
+                }
+            })
+            .to_string(),
+        ];
+        std::fs::write(&log_path, lines.join("\n")).expect("write log");
+
+        let meta_fs = std::fs::metadata(&log_path).expect("metadata");
+        let session = SessionSummary {
+            engine: SessionEngine::Codex,
+            meta: SessionMeta {
+                id: "s1".to_string(),
+                cwd: dir.path().to_path_buf(),
+                started_at_rfc3339: "2026-01-01T00:00:00Z".to_string(),
+                git: None,
+            },
+            log_path: log_path.clone(),
+            title: "test".to_string(),
+            file_size_bytes: meta_fs.len(),
+            file_modified: meta_fs.modified().ok(),
+            parent_id: None,
+            project_root: None,
+            remote: None,
+        };
+
+        let index = refresh_session_index(std::slice::from_ref(&session), &SessionIndex::default());
+        let dir_state = tempdir().expect("tempdir");
+        save_session_index(dir_state.path(), &index).expect("save");
+        let loaded = load_session_index(dir_state.path()).expect("load");
+
+        assert_eq!(loaded.tool_calls(&log_path), Some(1));
+        assert_eq!(loaded.assistant_turns(&log_path), Some(1));
+        assert_eq!(loaded.duration_ms(&session), Some(10_000));
+        assert_eq!(
+            loaded.last_activity_unix_ms(&log_path),
+            parse_rfc3339_to_unix_ms("2026-01-01T00:00:15Z")
+        );
     }
 }
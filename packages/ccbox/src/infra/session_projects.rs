@@ -1,4 +1,4 @@
-use crate::domain::{SessionEngine, SessionSummary};
+use crate::domain::{SessionEngine, SessionKey, SessionSummary, migrate_legacy_session_keys};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 use std::fs;
@@ -8,17 +8,19 @@ use thiserror::Error;
 
 #[derive(Clone, Debug, Default)]
 pub struct SessionProjects {
-    projects: BTreeMap<String, String>,
+    projects: BTreeMap<SessionKey, String>,
+    /// Entries from before keys carried the engine; see [`SessionProjects::migrate_legacy`].
+    legacy: BTreeMap<String, String>,
 }
 
 impl SessionProjects {
     pub fn project_for(&self, engine: SessionEngine, session_id: &str) -> Option<&str> {
-        let key = session_project_key(engine, session_id);
+        let key = SessionKey::new(engine, session_id);
         self.projects.get(&key).map(|s| s.as_str())
     }
 
     pub fn set(&mut self, engine: SessionEngine, session_id: &str, project_path: &str) {
-        let key = session_project_key(engine, session_id);
+        let key = SessionKey::new(engine, session_id);
         let project_path = project_path.trim();
         if project_path.is_empty() {
             self.projects.remove(&key);
@@ -26,6 +28,18 @@ impl SessionProjects {
             self.projects.insert(key, project_path.to_string());
         }
     }
+
+    pub fn entries(&self) -> impl Iterator<Item = (&SessionKey, &str)> {
+        self.projects
+            .iter()
+            .map(|(key, value)| (key, value.as_str()))
+    }
+
+    /// Re-keys bare-id entries by engine using the scanned sessions. Returns whether the store
+    /// changed and should be saved.
+    pub fn migrate_legacy(&mut self, sessions: &[SessionSummary]) -> bool {
+        migrate_legacy_session_keys(&mut self.legacy, &mut self.projects, sessions)
+    }
 }
 
 #[derive(Debug, Error)]
@@ -72,9 +86,18 @@ pub fn load_session_projects(
     };
 
     let file: SessionProjectsFile = serde_json::from_str(&raw)?;
-    Ok(SessionProjects {
-        projects: file.projects,
-    })
+    let mut store = SessionProjects::default();
+    for (key, value) in file.projects {
+        match SessionKey::parse(&key) {
+            Some(key) => {
+                store.projects.insert(key, value);
+            }
+            None => {
+                store.legacy.insert(key, value);
+            }
+        }
+    }
+    Ok(store)
 }
 
 pub fn save_session_projects(
@@ -86,8 +109,13 @@ pub fn save_session_projects(
     let path = session_projects_path(state_dir);
     let tmp = path.with_extension("json.tmp");
     let file = SessionProjectsFile {
-        version: 1,
-        projects: projects.projects.clone(),
+        version: 2,
+        projects: projects
+            .projects
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.clone()))
+            .chain(projects.legacy.clone())
+            .collect(),
     };
     let text = serde_json::to_string_pretty(&file)?;
     fs::write(&tmp, text)?;
@@ -115,23 +143,12 @@ pub fn apply_session_projects(sessions: &mut [SessionSummary], projects: &Sessio
                 continue;
             }
             session.meta.cwd = PathBuf::from(trimmed);
+            session.project_root = None;
         }
     }
 }
 
-pub fn session_project_key(engine: SessionEngine, session_id: &str) -> String {
-    format!("{}:{}", engine_prefix(engine), session_id)
-}
-
-fn engine_prefix(engine: SessionEngine) -> &'static str {
-    match engine {
-        SessionEngine::Codex => "codex",
-        SessionEngine::Claude => "claude",
-        SessionEngine::Gemini => "gemini",
-        SessionEngine::OpenCode => "opencode",
-    }
-}
-
+/// Version 2 keys every entry as `engine:id`; version 1 files may still hold bare session ids.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 struct SessionProjectsFile {
     version: u32,
@@ -163,6 +180,7 @@ mod tests {
                 id: "s1".to_string(),
                 cwd: PathBuf::from("/tmp/old"),
                 started_at_rfc3339: "2026-02-20T00:00:00Z".to_string(),
+                git: None,
             },
             PathBuf::from("/tmp/log.jsonl"),
             "auto".to_string(),
@@ -0,0 +1,25 @@
+use crate::domain::{
+    SessionSummary, SkillUsageReport, compute_skill_metrics, detect_skill_loops, detect_skill_spans,
+};
+use crate::infra::load_session_timeline;
+
+/// Skill usage across `sessions`, loading one timeline at a time. Logs that fail to load are
+/// skipped and not counted as scanned.
+pub fn project_skill_usage<'a>(
+    sessions: impl IntoIterator<Item = &'a SessionSummary>,
+) -> SkillUsageReport {
+    let mut report = SkillUsageReport::default();
+    for session in sessions {
+        let Ok(timeline) = load_session_timeline(&session.log_path) else {
+            continue;
+        };
+        let spans = detect_skill_spans(&timeline.items);
+        let loops = detect_skill_loops(&spans);
+        let metrics = spans
+            .iter()
+            .map(|span| compute_skill_metrics(span, &timeline.items))
+            .collect::<Vec<_>>();
+        report.add_session(&spans, &loops, &metrics);
+    }
+    report
+}
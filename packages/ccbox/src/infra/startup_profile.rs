@@ -0,0 +1,142 @@
+use serde::Serialize;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use thiserror::Error;
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+
+#[derive(Debug, Error)]
+pub enum SaveStartupProfileError {
+    #[error("failed to encode startup profile: {0}")]
+    Encode(#[from] serde_json::Error),
+
+    #[error("failed to write startup profile: {0}")]
+    Write(#[from] io::Error),
+}
+
+pub fn startup_profile_path(state_dir: &Path) -> PathBuf {
+    state_dir.join("startup_profile.json")
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct StartupPhase {
+    pub name: String,
+    pub ms: f64,
+}
+
+/// Wall-clock timings of TUI startup for `--profile-startup`. Each `mark` records the time
+/// since the previous one.
+#[derive(Clone, Debug)]
+pub struct StartupProfiler {
+    started: Instant,
+    last: Instant,
+    phases: Vec<StartupPhase>,
+}
+
+impl StartupProfiler {
+    pub fn start() -> Self {
+        let now = Instant::now();
+        Self {
+            started: now,
+            last: now,
+            phases: Vec::new(),
+        }
+    }
+
+    pub fn mark(&mut self, name: &str) {
+        let now = Instant::now();
+        self.phases.push(StartupPhase {
+            name: name.to_string(),
+            ms: elapsed_ms(self.last, now),
+        });
+        self.last = now;
+    }
+
+    pub fn phases(&self) -> &[StartupPhase] {
+        &self.phases
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+struct StartupProfileFile<'a> {
+    version: u32,
+    ccbox_version: &'static str,
+    os: &'static str,
+    recorded_at: String,
+    total_ms: f64,
+    project_count: usize,
+    session_count: usize,
+    phases: &'a [StartupPhase],
+}
+
+/// Writes `startup_profile.json` (replacing the previous report) and returns its path.
+pub fn save_startup_profile(
+    state_dir: &Path,
+    profiler: &StartupProfiler,
+    project_count: usize,
+    session_count: usize,
+) -> Result<PathBuf, SaveStartupProfileError> {
+    fs::create_dir_all(state_dir)?;
+
+    let path = startup_profile_path(state_dir);
+    let tmp = path.with_extension("json.tmp");
+    let file = StartupProfileFile {
+        version: 1,
+        ccbox_version: env!("CARGO_PKG_VERSION"),
+        os: std::env::consts::OS,
+        recorded_at: OffsetDateTime::now_utc()
+            .format(&Rfc3339)
+            .unwrap_or_default(),
+        total_ms: elapsed_ms(profiler.started, profiler.last),
+        project_count,
+        session_count,
+        phases: profiler.phases(),
+    };
+    let text = serde_json::to_string_pretty(&file)?;
+    fs::write(&tmp, text)?;
+    fs::rename(tmp, &path)?;
+    Ok(path)
+}
+
+/// Milliseconds with microsecond precision.
+fn elapsed_ms(from: Instant, to: Instant) -> f64 {
+    let micros = to.saturating_duration_since(from).as_micros();
+    micros as f64 / 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn writes_phases_in_order_with_counts() -> Result<(), Box<dyn std::error::Error>> {
+        let dir = tempdir()?;
+        let mut profiler = StartupProfiler::start();
+        profiler.mark("scan");
+        profiler.mark("first_draw");
+
+        let path = save_startup_profile(dir.path(), &profiler, 3, 42)?;
+        assert_eq!(path, startup_profile_path(dir.path()));
+
+        let report: serde_json::Value = serde_json::from_str(&fs::read_to_string(&path)?)?;
+        assert_eq!(report["version"], 1);
+        assert_eq!(report["project_count"], 3);
+        assert_eq!(report["session_count"], 42);
+        let names = report["phases"]
+            .as_array()
+            .map(|phases| {
+                phases
+                    .iter()
+                    .filter_map(|phase| phase["name"].as_str())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        assert_eq!(names, vec!["scan", "first_draw"]);
+        let phase_total = profiler.phases().iter().map(|phase| phase.ms).sum::<f64>();
+        assert!(report["total_ms"].as_f64().unwrap_or_default() >= phase_total - 0.01);
+        Ok(())
+    }
+}
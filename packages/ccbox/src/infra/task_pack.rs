@@ -0,0 +1,543 @@
+use super::tasks::{system_time_to_unix_ms, unix_ms_to_system_time};
+use crate::domain::{Task, TaskId};
+use base64::Engine as _;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+use uuid::Uuid;
+
+const TASK_PACK_FORMAT: &str = "ccbox-task-pack";
+const TASK_PACK_VERSION: u32 = 1;
+
+/// Shareable set of tasks. Images are embedded so the pack is self-contained.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct TaskPack {
+    pub format: String,
+    pub version: u32,
+    pub tasks: Vec<TaskPackEntry>,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct TaskPackEntry {
+    pub id: String,
+    pub project_path: PathBuf,
+    pub body: String,
+    pub created_at_unix_ms: i64,
+    pub updated_at_unix_ms: i64,
+    #[serde(default)]
+    pub images: Vec<TaskPackImage>,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct TaskPackImage {
+    pub file_name: String,
+    pub data_base64: String,
+}
+
+/// What to do when an imported task id already exists locally.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum TaskImportConflict {
+    #[default]
+    Skip,
+    Overwrite,
+    Duplicate,
+}
+
+impl TaskImportConflict {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "skip" => Some(Self::Skip),
+            "overwrite" => Some(Self::Overwrite),
+            "duplicate" => Some(Self::Duplicate),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct TaskExportSummary {
+    pub tasks: usize,
+    pub images: usize,
+    /// Image files that no longer exist on disk and were left out of the pack.
+    pub missing_images: usize,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct TaskImportSummary {
+    pub imported: usize,
+    pub overwritten: usize,
+    pub duplicated: usize,
+    pub skipped: usize,
+}
+
+#[derive(Debug, Error)]
+pub enum TaskPackError {
+    #[error(transparent)]
+    Store(#[from] super::TaskStoreError),
+
+    #[error("failed to read image {path}: {source}")]
+    ReadImage { path: String, source: io::Error },
+
+    #[error("failed to write image {path}: {source}")]
+    WriteImage { path: String, source: io::Error },
+
+    #[error("invalid image data for task {task_id}: {message}")]
+    DecodeImage { task_id: String, message: String },
+
+    #[error("failed to read task pack {path}: {source}")]
+    ReadPack { path: String, source: io::Error },
+
+    #[error("failed to write task pack {path}: {source}")]
+    WritePack { path: String, source: io::Error },
+
+    #[error("failed to parse task pack {path}: {source}")]
+    ParsePack {
+        path: String,
+        source: serde_json::Error,
+    },
+
+    #[error("unsupported task pack {path}: expected {TASK_PACK_FORMAT} v{TASK_PACK_VERSION}")]
+    UnsupportedPack { path: String },
+
+    #[error("invalid task id {id:?} in task pack {path}: expected a UUID")]
+    InvalidTaskId { path: String, id: String },
+
+    #[error("task pack {path} is encrypted; enable `encryption` in the config to read it")]
+    EncryptedPack { path: String },
+
+    #[error(transparent)]
+    Encryption(#[from] super::EncryptionError),
+}
+
+/// Directory holding task image files (pasted or imported).
+pub fn resolve_task_images_dir() -> Result<PathBuf, super::ResolveCcboxStateDirError> {
+    Ok(super::resolve_ccbox_state_dir()?.join("task_images"))
+}
+
+/// Packs every task with its images; sealed images are decrypted with `cipher`.
+pub fn export_task_pack(
+    store: &super::TaskStore,
+    cipher: Option<&super::ArtifactCipher>,
+) -> Result<(TaskPack, TaskExportSummary), TaskPackError> {
+    let mut summary = TaskExportSummary::default();
+    let mut tasks = Vec::new();
+    for entry in store.list_tasks()? {
+        let Some((task, images)) = store.load_task(&entry.task.id)? else {
+            continue;
+        };
+        let mut packed_images = Vec::new();
+        for image in images {
+            let bytes = match super::read_artifact(&image.source_path, cipher) {
+                Ok(bytes) => bytes,
+                Err(error) if error.kind() == io::ErrorKind::NotFound => {
+                    summary.missing_images += 1;
+                    continue;
+                }
+                Err(source) => {
+                    return Err(TaskPackError::ReadImage {
+                        path: image.source_path.display().to_string(),
+                        source,
+                    });
+                }
+            };
+            let file_name = image
+                .source_path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_else(|| format!("image-{}.png", image.ordinal));
+            packed_images.push(TaskPackImage {
+                file_name,
+                data_base64: base64::engine::general_purpose::STANDARD.encode(bytes),
+            });
+        }
+        summary.images += packed_images.len();
+        tasks.push(TaskPackEntry {
+            id: task.id.to_string(),
+            project_path: task.project_path,
+            body: task.body,
+            created_at_unix_ms: system_time_to_unix_ms(task.created_at),
+            updated_at_unix_ms: system_time_to_unix_ms(task.updated_at),
+            images: packed_images,
+        });
+    }
+    summary.tasks = tasks.len();
+    Ok((
+        TaskPack {
+            format: TASK_PACK_FORMAT.to_string(),
+            version: TASK_PACK_VERSION,
+            tasks,
+        },
+        summary,
+    ))
+}
+
+/// Serializes a pack as pretty JSON, encrypted to `cipher` when one is given.
+pub fn encode_task_pack(
+    pack: &TaskPack,
+    cipher: Option<&super::ArtifactCipher>,
+) -> Result<Vec<u8>, TaskPackError> {
+    let json = serde_json::to_string_pretty(pack).unwrap_or_default();
+    let bytes = format!("{json}\n").into_bytes();
+    match cipher {
+        Some(cipher) => Ok(cipher.encrypt(&bytes)?),
+        None => Ok(bytes),
+    }
+}
+
+pub fn write_task_pack(
+    path: &Path,
+    pack: &TaskPack,
+    cipher: Option<&super::ArtifactCipher>,
+) -> Result<(), TaskPackError> {
+    let bytes = encode_task_pack(pack, cipher)?;
+    fs::write(path, bytes).map_err(|source| TaskPackError::WritePack {
+        path: path.display().to_string(),
+        source,
+    })
+}
+
+/// Reads a plain or age-encrypted pack; encrypted packs need `cipher`.
+pub fn read_task_pack(
+    path: &Path,
+    cipher: Option<&super::ArtifactCipher>,
+) -> Result<TaskPack, TaskPackError> {
+    let read_error = |source: io::Error| TaskPackError::ReadPack {
+        path: path.display().to_string(),
+        source,
+    };
+    let mut bytes = fs::read(path).map_err(read_error)?;
+    if super::is_encrypted_artifact(&bytes) {
+        let Some(cipher) = cipher else {
+            return Err(TaskPackError::EncryptedPack {
+                path: path.display().to_string(),
+            });
+        };
+        bytes = cipher.decrypt(&bytes)?;
+    }
+    let raw = String::from_utf8(bytes)
+        .map_err(|error| read_error(io::Error::new(io::ErrorKind::InvalidData, error)))?;
+    parse_task_pack(&raw, &path.display().to_string())
+}
+
+/// Parses pack JSON; `source` names the file or URL in errors.
+pub(super) fn parse_task_pack(raw: &str, source: &str) -> Result<TaskPack, TaskPackError> {
+    let pack: TaskPack = serde_json::from_str(raw).map_err(|error| TaskPackError::ParsePack {
+        path: source.to_string(),
+        source: error,
+    })?;
+    if pack.format != TASK_PACK_FORMAT || pack.version != TASK_PACK_VERSION {
+        return Err(TaskPackError::UnsupportedPack {
+            path: source.to_string(),
+        });
+    }
+    // Ids become task ids and image file names, so only the UUIDs ccbox generates are accepted.
+    if let Some(entry) = pack.tasks.iter().find(|entry| !is_task_pack_id(&entry.id)) {
+        return Err(TaskPackError::InvalidTaskId {
+            path: source.to_string(),
+            id: entry.id.clone(),
+        });
+    }
+    Ok(pack)
+}
+
+fn is_task_pack_id(id: &str) -> bool {
+    Uuid::try_parse(id).is_ok_and(|uuid| uuid.hyphenated().to_string() == id)
+}
+
+/// Imports every task in `pack`. Embedded images are written under `images_dir`, sealed to
+/// `cipher` when one is given. `project_path` replaces the exported project path of every task
+/// when given.
+pub fn import_task_pack(
+    store: &super::TaskStore,
+    pack: &TaskPack,
+    images_dir: &Path,
+    conflict: TaskImportConflict,
+    project_path: Option<&Path>,
+    cipher: Option<&super::ArtifactCipher>,
+) -> Result<TaskImportSummary, TaskPackError> {
+    let mut summary = TaskImportSummary::default();
+    for entry in &pack.tasks {
+        let mut id = TaskId::new(entry.id.clone());
+        let mut replace = false;
+        if store.has_task(&id)? {
+            match conflict {
+                TaskImportConflict::Skip => {
+                    summary.skipped += 1;
+                    continue;
+                }
+                TaskImportConflict::Overwrite => {
+                    replace = true;
+                    summary.overwritten += 1;
+                }
+                TaskImportConflict::Duplicate => {
+                    id = TaskId::new(Uuid::new_v4().to_string());
+                    summary.duplicated += 1;
+                }
+            }
+        } else {
+            summary.imported += 1;
+        }
+
+        let image_paths = write_pack_images(images_dir, &id, &entry.images, cipher)?;
+        let task = Task {
+            id,
+            project_path: project_path
+                .map(Path::to_path_buf)
+                .unwrap_or_else(|| entry.project_path.clone()),
+            body: entry.body.clone(),
+            created_at: unix_ms_to_system_time(entry.created_at_unix_ms),
+            updated_at: unix_ms_to_system_time(entry.updated_at_unix_ms),
+        };
+        store.import_task(&task, &image_paths, replace)?;
+    }
+    Ok(summary)
+}
+
+/// Writes the images of a pack entry; the returned paths stay the recorded ones when `cipher`
+/// replaces the files with sealed copies.
+pub(super) fn write_pack_images(
+    images_dir: &Path,
+    task_id: &TaskId,
+    images: &[TaskPackImage],
+    cipher: Option<&super::ArtifactCipher>,
+) -> Result<Vec<PathBuf>, TaskPackError> {
+    if images.is_empty() {
+        return Ok(Vec::new());
+    }
+    fs::create_dir_all(images_dir).map_err(|source| TaskPackError::WriteImage {
+        path: images_dir.display().to_string(),
+        source,
+    })?;
+
+    let mut paths = Vec::new();
+    for (idx, image) in images.iter().enumerate() {
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(image.data_base64.as_bytes())
+            .map_err(|error| TaskPackError::DecodeImage {
+                task_id: task_id.to_string(),
+                message: error.to_string(),
+            })?;
+        // Only the final component is kept so a crafted pack cannot write outside `images_dir`.
+        let file_name = Path::new(&image.file_name)
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| "image.png".to_string());
+        let path = images_dir.join(format!("import-{task_id}-{}-{file_name}", idx + 1));
+        fs::write(&path, bytes).map_err(|source| TaskPackError::WriteImage {
+            path: path.display().to_string(),
+            source,
+        })?;
+        if let Some(cipher) = cipher {
+            cipher.seal_file(&path)?;
+        }
+        paths.push(path);
+    }
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::TaskStore;
+    use tempfile::tempdir;
+
+    #[test]
+    fn export_then_import_roundtrips_tasks_with_images() {
+        let dir = tempdir().expect("tempdir");
+        let image_path = dir.path().join("shot.png");
+        fs::write(&image_path, b"png-bytes").expect("write image");
+        let source = TaskStore::open(dir.path().join("source.db")).expect("open source");
+        let task_id = source
+            .create_task(
+                Path::new("/work/app"),
+                "Fix login",
+                &[image_path, dir.path().join("gone.png")],
+            )
+            .expect("create");
+
+        let (pack, summary) = export_task_pack(&source, None).expect("export");
+        assert_eq!(
+            summary,
+            TaskExportSummary {
+                tasks: 1,
+                images: 1,
+                missing_images: 1
+            }
+        );
+        let pack_path = dir.path().join("pack.json");
+        write_task_pack(&pack_path, &pack, None).expect("write pack");
+        let pack = read_task_pack(&pack_path, None).expect("read pack");
+
+        let cipher = crate::infra::ArtifactCipher::load_or_create(&dir.path().join("identity"))
+            .expect("identity");
+        let sealed_path = dir.path().join("pack.json.age");
+        write_task_pack(&sealed_path, &pack, Some(&cipher)).expect("write sealed pack");
+        assert!(matches!(
+            read_task_pack(&sealed_path, None),
+            Err(TaskPackError::EncryptedPack { .. })
+        ));
+        assert_eq!(
+            read_task_pack(&sealed_path, Some(&cipher)).expect("read sealed pack"),
+            pack
+        );
+
+        let target = TaskStore::open(dir.path().join("target.db")).expect("open target");
+        let images_dir = dir.path().join("images");
+        let summary = import_task_pack(
+            &target,
+            &pack,
+            &images_dir,
+            TaskImportConflict::Skip,
+            Some(Path::new("/home/me/app")),
+            None,
+        )
+        .expect("import");
+        assert_eq!(summary.imported, 1);
+
+        let (task, images) = target.load_task(&task_id).expect("load").expect("task");
+        assert_eq!(task.body, "Fix login");
+        assert_eq!(task.project_path, PathBuf::from("/home/me/app"));
+        assert_eq!(images.len(), 1);
+        assert_eq!(
+            fs::read(&images[0].source_path).expect("image"),
+            b"png-bytes"
+        );
+    }
+
+    #[test]
+    fn imported_images_are_sealed_and_exported_decrypted() {
+        let dir = tempdir().expect("tempdir");
+        let cipher = crate::infra::ArtifactCipher::load_or_create(&dir.path().join("identity"))
+            .expect("identity");
+        let image_path = dir.path().join("shot.png");
+        fs::write(&image_path, b"png-bytes").expect("write image");
+        let source = TaskStore::open(dir.path().join("source.db")).expect("open source");
+        source
+            .create_task(Path::new("/work/app"), "Fix login", &[image_path])
+            .expect("create");
+        let (pack, _) = export_task_pack(&source, None).expect("export");
+
+        let target = TaskStore::open(dir.path().join("target.db")).expect("open target");
+        let images_dir = dir.path().join("images");
+        import_task_pack(
+            &target,
+            &pack,
+            &images_dir,
+            TaskImportConflict::Skip,
+            None,
+            Some(&cipher),
+        )
+        .expect("import");
+
+        let entry = &target.list_tasks().expect("list")[0];
+        let (_, images) = target
+            .load_task(&entry.task.id)
+            .expect("load")
+            .expect("task");
+        assert!(!images[0].source_path.exists());
+        assert!(crate::infra::is_sealed_artifact(&images[0].source_path));
+
+        let (repacked, summary) = export_task_pack(&target, Some(&cipher)).expect("re-export");
+        assert_eq!(summary.images, 1);
+        assert_eq!(
+            repacked.tasks[0].images[0].data_base64,
+            pack.tasks[0].images[0].data_base64
+        );
+    }
+
+    #[test]
+    fn import_conflicts_skip_overwrite_or_duplicate() {
+        let dir = tempdir().expect("tempdir");
+        let store = TaskStore::open(dir.path().join("tasks.db")).expect("open");
+        let task_id = store
+            .create_task(Path::new("/work/app"), "Original", &[])
+            .expect("create");
+        let (mut pack, _) = export_task_pack(&store, None).expect("export");
+        pack.tasks[0].body = "Edited".to_string();
+        let images_dir = dir.path().join("images");
+
+        let skipped = import_task_pack(
+            &store,
+            &pack,
+            &images_dir,
+            TaskImportConflict::Skip,
+            None,
+            None,
+        )
+        .expect("skip");
+        assert_eq!(skipped.skipped, 1);
+        let body = |store: &TaskStore| {
+            store
+                .load_task(&task_id)
+                .expect("load")
+                .expect("task")
+                .0
+                .body
+        };
+        assert_eq!(body(&store), "Original");
+
+        let overwritten = import_task_pack(
+            &store,
+            &pack,
+            &images_dir,
+            TaskImportConflict::Overwrite,
+            None,
+            None,
+        )
+        .expect("overwrite");
+        assert_eq!(overwritten.overwritten, 1);
+        assert_eq!(body(&store), "Edited");
+
+        let duplicated = import_task_pack(
+            &store,
+            &pack,
+            &images_dir,
+            TaskImportConflict::Duplicate,
+            None,
+            None,
+        )
+        .expect("duplicate");
+        assert_eq!(duplicated.duplicated, 1);
+        assert_eq!(store.list_tasks().expect("list").len(), 2);
+    }
+
+    #[test]
+    fn rejects_foreign_json() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("pack.json");
+        fs::write(&path, r#"{ "format": "other", "version": 1, "tasks": [] }"#).expect("write");
+        assert!(matches!(
+            read_task_pack(&path, None),
+            Err(TaskPackError::UnsupportedPack { .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_task_ids_that_are_not_uuids() {
+        let dir = tempdir().expect("tempdir");
+        let store = TaskStore::open(dir.path().join("tasks.db")).expect("open");
+        store
+            .create_task(Path::new("/work/app"), "Fix login", &[])
+            .expect("create");
+        let (mut pack, _) = export_task_pack(&store, None).expect("export");
+        let path = dir.path().join("pack.json");
+        for id in [
+            "../../escape",
+            "team:other",
+            "shared-task",
+            "{00000000-0000-0000-0000-000000000000}",
+        ] {
+            pack.tasks[0].id = id.to_string();
+            write_task_pack(&path, &pack, None).expect("write");
+            assert!(
+                matches!(
+                    read_task_pack(&path, None),
+                    Err(TaskPackError::InvalidTaskId { id: rejected, .. }) if rejected == id
+                ),
+                "{id} was accepted"
+            );
+        }
+    }
+}
@@ -0,0 +1,398 @@
+use super::git::{GitError, git_command, run_git};
+use super::task_pack::{parse_task_pack, write_pack_images};
+use super::tasks::unix_ms_to_system_time;
+use crate::domain::{Task, TaskId, TaskImage};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use thiserror::Error;
+
+const TEAM_TASKS_FILE: &str = "tasks.json";
+const REGISTRY_HTTP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Read-only source of shared tasks: a task pack served over HTTP(S) or kept in a git repo.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct TaskRegistryConfig {
+    /// URL of a task pack JSON (as written by `ccbox tasks export`).
+    #[serde(default)]
+    pub url: Option<String>,
+
+    /// Git remote holding the pack; cloned shallowly and reset to the remote on every sync.
+    #[serde(default)]
+    pub git: Option<String>,
+
+    /// Pack path inside the git repo.
+    #[serde(default = "default_registry_file")]
+    pub file: String,
+
+    #[serde(default = "default_refresh_minutes")]
+    pub refresh_minutes: u64,
+}
+
+fn default_registry_file() -> String {
+    "ccbox-tasks.json".to_string()
+}
+
+fn default_refresh_minutes() -> u64 {
+    60
+}
+
+impl TaskRegistryConfig {
+    pub fn refresh_interval(&self) -> Duration {
+        Duration::from_secs(self.refresh_minutes.saturating_mul(60))
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        match (&self.url, &self.git) {
+            (Some(_), Some(_)) => {
+                return Err("task_registry: set either `url` or `git`, not both".to_string());
+            }
+            (None, None) => return Err("task_registry: `url` or `git` is required".to_string()),
+            _ => {}
+        }
+        if self.git.as_deref().is_some_and(|git| git.starts_with('-')) {
+            return Err("task_registry.git must not start with `-`".to_string());
+        }
+        if self.refresh_minutes == 0 {
+            return Err("task_registry.refresh_minutes must be greater than 0".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum TaskRegistryError {
+    #[error("failed to fetch task registry {url}: {message}")]
+    Fetch { url: String, message: String },
+
+    #[error("git {action} failed for {remote}: {message}")]
+    Git {
+        action: &'static str,
+        remote: String,
+        message: String,
+    },
+
+    #[error(transparent)]
+    Pack(#[from] super::TaskPackError),
+
+    #[error("failed to write team tasks cache {path}: {source}")]
+    WriteCache { path: String, source: io::Error },
+
+    #[error("failed to read team tasks cache {path}: {source}")]
+    ReadCache { path: String, source: io::Error },
+
+    #[error("failed to parse team tasks cache {path}: {source}")]
+    ParseCache {
+        path: String,
+        source: serde_json::Error,
+    },
+}
+
+/// Team tasks as last pulled, with embedded images already extracted next to the cache.
+#[derive(Debug, Deserialize, Serialize)]
+struct TeamTasksCache {
+    tasks: Vec<CachedTeamTask>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct CachedTeamTask {
+    id: String,
+    project_path: PathBuf,
+    body: String,
+    created_at_unix_ms: i64,
+    updated_at_unix_ms: i64,
+    image_paths: Vec<PathBuf>,
+}
+
+pub fn resolve_task_registry_dir() -> Result<PathBuf, super::ResolveCcboxStateDirError> {
+    Ok(super::resolve_ccbox_state_dir()?.join("team_tasks"))
+}
+
+/// Pulls the registry into `dir` and returns the number of team tasks.
+pub fn sync_task_registry(
+    config: &TaskRegistryConfig,
+    dir: &Path,
+) -> Result<usize, TaskRegistryError> {
+    let (raw, source) = match (&config.url, &config.git) {
+        (Some(url), _) => (fetch_registry_url(url)?, url.clone()),
+        (None, Some(remote)) => {
+            let repo_dir = dir.join("repo");
+            sync_git_checkout(remote, &repo_dir)?;
+            let pack_path = repo_dir.join(&config.file);
+            let raw = fs::read_to_string(&pack_path).map_err(|source| {
+                super::TaskPackError::ReadPack {
+                    path: pack_path.display().to_string(),
+                    source,
+                }
+            })?;
+            (raw, format!("{remote}:{}", config.file))
+        }
+        (None, None) => return Ok(0),
+    };
+    let pack = parse_task_pack(&raw, &source)?;
+
+    let images_dir = dir.join("images");
+    match fs::remove_dir_all(&images_dir) {
+        Ok(()) => {}
+        Err(error) if error.kind() == io::ErrorKind::NotFound => {}
+        Err(source) => {
+            return Err(TaskRegistryError::WriteCache {
+                path: images_dir.display().to_string(),
+                source,
+            });
+        }
+    }
+    let mut tasks = Vec::new();
+    for entry in pack.tasks {
+        let image_paths = write_pack_images(
+            &images_dir,
+            &TaskId::new(entry.id.clone()),
+            &entry.images,
+            None,
+        )?;
+        tasks.push(CachedTeamTask {
+            id: entry.id,
+            project_path: entry.project_path,
+            body: entry.body,
+            created_at_unix_ms: entry.created_at_unix_ms,
+            updated_at_unix_ms: entry.updated_at_unix_ms,
+            image_paths,
+        });
+    }
+
+    let count = tasks.len();
+    write_team_tasks_cache(dir, &TeamTasksCache { tasks })?;
+    Ok(count)
+}
+
+/// Loads the team tasks pulled by the last successful sync. Ids are namespaced with
+/// [`TaskId::team`]; a missing cache yields no tasks.
+pub fn load_team_tasks(dir: &Path) -> Result<Vec<(Task, Vec<TaskImage>)>, TaskRegistryError> {
+    let path = dir.join(TEAM_TASKS_FILE);
+    let raw = match fs::read_to_string(&path) {
+        Ok(raw) => raw,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(source) => {
+            return Err(TaskRegistryError::ReadCache {
+                path: path.display().to_string(),
+                source,
+            });
+        }
+    };
+    let cache: TeamTasksCache =
+        serde_json::from_str(&raw).map_err(|source| TaskRegistryError::ParseCache {
+            path: path.display().to_string(),
+            source,
+        })?;
+
+    let synced_at = fs::metadata(&path)
+        .and_then(|meta| meta.modified())
+        .unwrap_or(SystemTime::UNIX_EPOCH);
+    Ok(cache
+        .tasks
+        .into_iter()
+        .map(|cached| {
+            let id = TaskId::team(&cached.id);
+            let images = cached
+                .image_paths
+                .into_iter()
+                .enumerate()
+                .map(|(idx, source_path)| TaskImage {
+                    task_id: id.clone(),
+                    ordinal: u32::try_from(idx + 1).unwrap_or(u32::MAX),
+                    source_path,
+                    added_at: synced_at,
+                })
+                .collect();
+            let task = Task {
+                id,
+                project_path: cached.project_path,
+                body: cached.body,
+                created_at: unix_ms_to_system_time(cached.created_at_unix_ms),
+                updated_at: unix_ms_to_system_time(cached.updated_at_unix_ms),
+            };
+            (task, images)
+        })
+        .collect())
+}
+
+/// Drops the pulled tasks, e.g. once the registry is no longer configured.
+pub fn clear_task_registry(dir: &Path) -> io::Result<()> {
+    match fs::remove_dir_all(dir) {
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(()),
+        other => other,
+    }
+}
+
+fn write_team_tasks_cache(dir: &Path, cache: &TeamTasksCache) -> Result<(), TaskRegistryError> {
+    let path = dir.join(TEAM_TASKS_FILE);
+    let tmp_path = dir.join(format!("{TEAM_TASKS_FILE}.tmp"));
+    let json = serde_json::to_string_pretty(cache).unwrap_or_default();
+    fs::create_dir_all(dir)
+        .and_then(|()| fs::write(&tmp_path, json))
+        .and_then(|()| fs::rename(&tmp_path, &path))
+        .map_err(|source| TaskRegistryError::WriteCache {
+            path: path.display().to_string(),
+            source,
+        })
+}
+
+fn fetch_registry_url(url: &str) -> Result<String, TaskRegistryError> {
+    let config = ureq::Agent::config_builder()
+        .timeout_global(Some(REGISTRY_HTTP_TIMEOUT))
+        .build();
+    let agent: ureq::Agent = config.into();
+    let fetch_error = |message: String| TaskRegistryError::Fetch {
+        url: url.to_string(),
+        message,
+    };
+    agent
+        .get(url)
+        .header(
+            "User-Agent",
+            &format!("{}/{}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION")),
+        )
+        .call()
+        .map_err(|error| fetch_error(error.to_string()))?
+        .into_body()
+        .read_to_string()
+        .map_err(|error| fetch_error(error.to_string()))
+}
+
+fn sync_git_checkout(remote: &str, repo_dir: &Path) -> Result<(), TaskRegistryError> {
+    let git_error = |action: &'static str| {
+        move |error: GitError| TaskRegistryError::Git {
+            action,
+            remote: remote.to_string(),
+            message: error.to_string(),
+        }
+    };
+    if repo_dir.join(".git").is_dir() {
+        let mut fetch = git_command(repo_dir);
+        fetch.args(["fetch", "--depth", "1", "origin"]);
+        run_git(fetch).map_err(git_error("fetch"))?;
+        let mut reset = git_command(repo_dir);
+        reset.args(["reset", "--hard", "FETCH_HEAD"]);
+        run_git(reset).map_err(git_error("reset"))?;
+        return Ok(());
+    }
+    if let Some(parent) = repo_dir.parent() {
+        fs::create_dir_all(parent).map_err(|source| TaskRegistryError::WriteCache {
+            path: parent.display().to_string(),
+            source,
+        })?;
+    }
+    let parent = repo_dir.parent().unwrap_or(repo_dir);
+    let mut clone = git_command(parent);
+    clone
+        .args(["clone", "--depth", "1", "--", remote])
+        .arg(repo_dir);
+    run_git(clone).map_err(git_error("clone"))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::{TaskStore, export_task_pack, write_task_pack};
+    use std::process::Command;
+    use tempfile::tempdir;
+
+    #[test]
+    fn syncs_git_registry_into_namespaced_team_tasks() {
+        let dir = tempdir().expect("tempdir");
+        let image_path = dir.path().join("diagram.png");
+        fs::write(&image_path, b"png").expect("image");
+        let store = TaskStore::open(dir.path().join("tasks.db")).expect("open");
+        let task_id = store
+            .create_task(Path::new("/work/app"), "Review PR", &[image_path])
+            .expect("create");
+        let (pack, _) = export_task_pack(&store, None).expect("export");
+
+        let remote = dir.path().join("remote");
+        fs::create_dir_all(&remote).expect("remote dir");
+        write_task_pack(&remote.join("ccbox-tasks.json"), &pack, None).expect("write pack");
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .args(args)
+                .current_dir(&remote)
+                .output()
+                .is_ok_and(|output| output.status.success())
+        };
+        if !git(&["init", "-q"])
+            || !git(&["add", "."])
+            || !git(&[
+                "-c",
+                "user.name=ccbox",
+                "-c",
+                "user.email=ccbox@example.com",
+                "commit",
+                "-qm",
+                "tasks",
+            ])
+        {
+            // git is not available in this environment.
+            return;
+        }
+
+        let config: TaskRegistryConfig = serde_json::from_value(serde_json::json!({
+            "git": remote.display().to_string()
+        }))
+        .expect("config");
+        assert_eq!(config.validate(), Ok(()));
+
+        let registry_dir = dir.path().join("team_tasks");
+        assert_eq!(sync_task_registry(&config, &registry_dir).expect("sync"), 1);
+        // A second sync updates the existing checkout.
+        assert_eq!(
+            sync_task_registry(&config, &registry_dir).expect("resync"),
+            1
+        );
+
+        let team = load_team_tasks(&registry_dir).expect("load");
+        assert_eq!(team.len(), 1);
+        let (task, images) = &team[0];
+        assert!(task.id.is_team());
+        assert_eq!(task.id, TaskId::team(&task_id.to_string()));
+        assert_eq!(task.body, "Review PR");
+        assert_eq!(fs::read(&images[0].source_path).expect("image"), b"png");
+
+        clear_task_registry(&registry_dir).expect("clear");
+        assert!(
+            load_team_tasks(&registry_dir)
+                .expect("load cleared")
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn config_requires_exactly_one_source() {
+        let config: TaskRegistryConfig =
+            serde_json::from_value(serde_json::json!({ "url": "https://example.com/tasks.json" }))
+                .expect("config");
+        assert_eq!(config.validate(), Ok(()));
+        assert_eq!(config.refresh_interval(), Duration::from_secs(3600));
+
+        let both = TaskRegistryConfig {
+            git: Some("git@example.com:team/prompts.git".to_string()),
+            ..config.clone()
+        };
+        assert!(both.validate().is_err());
+
+        let option = TaskRegistryConfig {
+            url: None,
+            git: Some("--upload-pack=touch /tmp/pwned".to_string()),
+            ..config.clone()
+        };
+        assert!(option.validate().is_err());
+
+        let neither = TaskRegistryConfig {
+            url: None,
+            ..config
+        };
+        assert!(neither.validate().is_err());
+    }
+}
@@ -1,4 +1,7 @@
-use crate::domain::{Task, TaskId, TaskImage};
+use crate::domain::{
+    AgentEngine, RetryPolicy, ScheduleSpec, Task, TaskId, TaskImage, TaskSchedule,
+    resolve_schedule_due_unix_ms,
+};
 use rusqlite::{Connection, OptionalExtension, Transaction, params};
 use sqlx::Connection as _;
 use std::fs;
@@ -10,6 +13,13 @@ use uuid::Uuid;
 
 static TASKS_MIGRATOR: sqlx::migrate::Migrator = sqlx::migrate!("./migrations");
 
+/// A scheduled spawn that fails is tried again after 30s, 1m, 2m and 4m, then dropped.
+const SCHEDULE_RETRY: RetryPolicy = RetryPolicy {
+    max_attempts: 5,
+    backoff: Duration::from_secs(30),
+    quick_exit_window: Duration::ZERO,
+};
+
 #[derive(Debug, Error)]
 pub enum TaskStoreError {
     #[error(transparent)]
@@ -35,6 +45,15 @@ pub enum TaskStoreError {
 pub struct TaskListEntry {
     pub task: Task,
     pub image_count: u32,
+    pub schedule: Option<TaskSchedule>,
+}
+
+/// A scheduled spawn that failed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TaskScheduleFailure<E> {
+    pub error: E,
+    /// When the spawn is tried again; `None` once the schedule was dropped after its last attempt.
+    pub retry_at: Option<SystemTime>,
 }
 
 #[derive(Clone, Debug)]
@@ -76,6 +95,75 @@ impl TaskStore {
     pub fn delete_task(&self, task_id: &TaskId) -> Result<bool, TaskStoreError> {
         delete_task_with_db(&self.db_path, task_id)
     }
+
+    pub fn has_task(&self, task_id: &TaskId) -> Result<bool, TaskStoreError> {
+        has_task_with_db(&self.db_path, task_id)
+    }
+
+    /// Inserts a task keeping its id and timestamps. With `replace`, an existing task with the
+    /// same id (and its images and schedule) is removed first.
+    pub fn import_task(
+        &self,
+        task: &Task,
+        image_paths: &[PathBuf],
+        replace: bool,
+    ) -> Result<(), TaskStoreError> {
+        import_task_with_db(&self.db_path, task, image_paths, replace)
+    }
+
+    pub fn schedule_task(
+        &self,
+        task_id: &TaskId,
+        engine: AgentEngine,
+        due_at: SystemTime,
+    ) -> Result<bool, TaskStoreError> {
+        schedule_task_with_db(&self.db_path, task_id, engine, due_at)
+    }
+
+    pub fn cancel_task_schedule(&self, task_id: &TaskId) -> Result<bool, TaskStoreError> {
+        cancel_task_schedule_with_db(&self.db_path, task_id)
+    }
+
+    /// Hands every schedule due at `now` to `spawn`, removing a schedule once its spawn
+    /// succeeded. A failed spawn is pushed out with `SCHEDULE_RETRY` backoff and dropped after
+    /// its last attempt; rows with an unknown engine stay queued.
+    pub fn run_due_task_schedules<T, E>(
+        &self,
+        now: SystemTime,
+        mut spawn: impl FnMut(&TaskSchedule) -> Result<T, E>,
+    ) -> Result<Vec<Result<T, TaskScheduleFailure<E>>>, TaskStoreError> {
+        let due = due_task_schedules_with_db(&self.db_path, now)?;
+        let mut outcomes = Vec::with_capacity(due.len());
+        for (schedule, failed_attempts) in &due {
+            let outcome = match spawn(schedule) {
+                Ok(spawned) => {
+                    complete_task_schedule_with_db(&self.db_path, schedule)?;
+                    Ok(spawned)
+                }
+                Err(error) => Err(TaskScheduleFailure {
+                    error,
+                    retry_at: fail_task_schedule_with_db(
+                        &self.db_path,
+                        schedule,
+                        failed_attempts.saturating_add(1),
+                        now,
+                    )?,
+                }),
+            };
+            outcomes.push(outcome);
+        }
+        Ok(outcomes)
+    }
+}
+
+pub fn resolve_task_schedule_due_at(spec: ScheduleSpec, now: SystemTime) -> SystemTime {
+    let now_ms = system_time_to_unix_ms(now);
+    let due_ms = resolve_schedule_due_unix_ms(
+        spec,
+        now_ms,
+        super::local_time::local_utc_offset_seconds(now_ms),
+    );
+    unix_ms_to_system_time(due_ms)
 }
 
 pub fn resolve_tasks_db_path() -> Result<PathBuf, TaskStoreError> {
@@ -146,8 +234,11 @@ fn list_tasks_with_db(db_path: &Path) -> Result<Vec<TaskListEntry>, TaskStoreErr
             tasks.body, \
             tasks.created_at_unix_ms, \
             tasks.updated_at_unix_ms, \
-            (SELECT COUNT(1) FROM task_images WHERE task_images.task_id = tasks.id) AS image_count \
+            (SELECT COUNT(1) FROM task_images WHERE task_images.task_id = tasks.id) AS image_count, \
+            task_schedules.engine, \
+            task_schedules.due_at_unix_ms \
          FROM tasks \
+         LEFT JOIN task_schedules ON task_schedules.task_id = tasks.id \
          ORDER BY tasks.updated_at_unix_ms DESC",
     )?;
 
@@ -158,6 +249,19 @@ fn list_tasks_with_db(db_path: &Path) -> Result<Vec<TaskListEntry>, TaskStoreErr
         let created_at_ms: i64 = row.get(3)?;
         let updated_at_ms: i64 = row.get(4)?;
         let image_count: i64 = row.get(5)?;
+        let schedule_engine: Option<String> = row.get(6)?;
+        let schedule_due_ms: Option<i64> = row.get(7)?;
+
+        let schedule = match (schedule_engine.as_deref(), schedule_due_ms) {
+            (Some(engine), Some(due_ms)) => {
+                parse_agent_engine_id(engine).map(|engine| TaskSchedule {
+                    task_id: TaskId::new(id.clone()),
+                    engine,
+                    due_at: unix_ms_to_system_time(due_ms),
+                })
+            }
+            _ => None,
+        };
 
         Ok(TaskListEntry {
             task: Task {
@@ -168,6 +272,7 @@ fn list_tasks_with_db(db_path: &Path) -> Result<Vec<TaskListEntry>, TaskStoreErr
                 updated_at: unix_ms_to_system_time(updated_at_ms),
             },
             image_count: u32::try_from(image_count).unwrap_or(0),
+            schedule,
         })
     })?;
 
@@ -259,6 +364,199 @@ fn delete_task_with_db(db_path: &Path, task_id: &TaskId) -> Result<bool, TaskSto
     Ok(affected > 0)
 }
 
+fn has_task_with_db(db_path: &Path, task_id: &TaskId) -> Result<bool, TaskStoreError> {
+    ensure_tasks_db_ready(db_path)?;
+    let conn = open_tasks_connection(db_path)?;
+    let exists = conn
+        .query_row(
+            "SELECT 1 FROM tasks WHERE id = ?1",
+            [task_id.to_string()],
+            |_row| Ok(()),
+        )
+        .optional()?
+        .is_some();
+    Ok(exists)
+}
+
+fn import_task_with_db(
+    db_path: &Path,
+    task: &Task,
+    image_paths: &[PathBuf],
+    replace: bool,
+) -> Result<(), TaskStoreError> {
+    ensure_tasks_db_ready(db_path)?;
+    let mut conn = open_tasks_connection(db_path)?;
+    let tx = conn.transaction()?;
+    if replace {
+        tx.execute("DELETE FROM tasks WHERE id = ?1", [task.id.to_string()])?;
+    }
+    tx.execute(
+        "INSERT INTO tasks (id, project_path, body, created_at_unix_ms, updated_at_unix_ms) \
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![
+            task.id.to_string(),
+            task.project_path.display().to_string(),
+            task.body,
+            system_time_to_unix_ms(task.created_at),
+            system_time_to_unix_ms(task.updated_at)
+        ],
+    )?;
+    let now_ms = system_time_to_unix_ms(SystemTime::now());
+    insert_task_images(&tx, &task.id, image_paths, now_ms)?;
+    tx.commit()?;
+    Ok(())
+}
+
+fn schedule_task_with_db(
+    db_path: &Path,
+    task_id: &TaskId,
+    engine: AgentEngine,
+    due_at: SystemTime,
+) -> Result<bool, TaskStoreError> {
+    ensure_tasks_db_ready(db_path)?;
+    let conn = open_tasks_connection(db_path)?;
+    let exists = conn
+        .query_row(
+            "SELECT 1 FROM tasks WHERE id = ?1",
+            [task_id.to_string()],
+            |_row| Ok(()),
+        )
+        .optional()?
+        .is_some();
+    if !exists {
+        return Ok(false);
+    }
+
+    let now_ms = system_time_to_unix_ms(SystemTime::now());
+    conn.execute(
+        "INSERT INTO task_schedules (task_id, engine, due_at_unix_ms, created_at_unix_ms) \
+         VALUES (?1, ?2, ?3, ?4) \
+         ON CONFLICT(task_id) DO UPDATE SET \
+            engine = excluded.engine, \
+            due_at_unix_ms = excluded.due_at_unix_ms, \
+            created_at_unix_ms = excluded.created_at_unix_ms, \
+            failed_attempts = 0",
+        params![
+            task_id.to_string(),
+            agent_engine_id(engine),
+            system_time_to_unix_ms(due_at),
+            now_ms
+        ],
+    )?;
+    Ok(true)
+}
+
+fn cancel_task_schedule_with_db(db_path: &Path, task_id: &TaskId) -> Result<bool, TaskStoreError> {
+    ensure_tasks_db_ready(db_path)?;
+    let conn = open_tasks_connection(db_path)?;
+    let affected = conn.execute(
+        "DELETE FROM task_schedules WHERE task_id = ?1",
+        [task_id.to_string()],
+    )?;
+    Ok(affected > 0)
+}
+
+/// Due schedules with the number of their failed spawns so far.
+fn due_task_schedules_with_db(
+    db_path: &Path,
+    now: SystemTime,
+) -> Result<Vec<(TaskSchedule, u32)>, TaskStoreError> {
+    ensure_tasks_db_ready(db_path)?;
+    let conn = open_tasks_connection(db_path)?;
+    let now_ms = system_time_to_unix_ms(now);
+
+    let mut stmt = conn.prepare(
+        "SELECT task_id, engine, due_at_unix_ms, failed_attempts FROM task_schedules \
+         WHERE due_at_unix_ms <= ?1 ORDER BY due_at_unix_ms ASC",
+    )?;
+    let due = stmt
+        .query_map([now_ms], |row| {
+            let task_id: String = row.get(0)?;
+            let engine: String = row.get(1)?;
+            let due_ms: i64 = row.get(2)?;
+            let failed_attempts: u32 = row.get(3)?;
+            Ok((task_id, engine, due_ms, failed_attempts))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(due
+        .into_iter()
+        .filter_map(|(task_id, engine, due_ms, failed_attempts)| {
+            parse_agent_engine_id(&engine).map(|engine| {
+                (
+                    TaskSchedule {
+                        task_id: TaskId::new(task_id),
+                        engine,
+                        due_at: unix_ms_to_system_time(due_ms),
+                    },
+                    failed_attempts,
+                )
+            })
+        })
+        .collect())
+}
+
+/// Records the `failed_attempts`-th failed spawn of a schedule: pushes it out with backoff and
+/// returns the new due time, or drops it once `SCHEDULE_RETRY` has no attempts left. A schedule
+/// rescheduled in the meantime is left alone.
+fn fail_task_schedule_with_db(
+    db_path: &Path,
+    schedule: &TaskSchedule,
+    failed_attempts: u32,
+    now: SystemTime,
+) -> Result<Option<SystemTime>, TaskStoreError> {
+    if failed_attempts >= SCHEDULE_RETRY.max_attempts {
+        complete_task_schedule_with_db(db_path, schedule)?;
+        return Ok(None);
+    }
+    let retry_at = now + SCHEDULE_RETRY.backoff_for(failed_attempts);
+    let conn = open_tasks_connection(db_path)?;
+    conn.execute(
+        "UPDATE task_schedules SET due_at_unix_ms = ?3, failed_attempts = ?4 \
+         WHERE task_id = ?1 AND due_at_unix_ms = ?2",
+        params![
+            schedule.task_id.to_string(),
+            system_time_to_unix_ms(schedule.due_at),
+            system_time_to_unix_ms(retry_at),
+            failed_attempts
+        ],
+    )?;
+    Ok(Some(retry_at))
+}
+
+/// Removes a schedule that has been run, unless it was rescheduled in the meantime.
+fn complete_task_schedule_with_db(
+    db_path: &Path,
+    schedule: &TaskSchedule,
+) -> Result<(), TaskStoreError> {
+    let conn = open_tasks_connection(db_path)?;
+    conn.execute(
+        "DELETE FROM task_schedules WHERE task_id = ?1 AND due_at_unix_ms = ?2",
+        params![
+            schedule.task_id.to_string(),
+            system_time_to_unix_ms(schedule.due_at)
+        ],
+    )?;
+    Ok(())
+}
+
+pub(super) fn agent_engine_id(engine: AgentEngine) -> &'static str {
+    match engine {
+        AgentEngine::Codex => "codex",
+        AgentEngine::Claude => "claude",
+        AgentEngine::OpenCode => "opencode",
+    }
+}
+
+pub(super) fn parse_agent_engine_id(value: &str) -> Option<AgentEngine> {
+    match value {
+        "codex" => Some(AgentEngine::Codex),
+        "claude" => Some(AgentEngine::Claude),
+        "opencode" => Some(AgentEngine::OpenCode),
+        _ => None,
+    }
+}
+
 fn insert_task_row(
     tx: &Transaction<'_>,
     id: &TaskId,
@@ -297,14 +595,14 @@ fn insert_task_images(
     Ok(())
 }
 
-fn unix_ms_to_system_time(ms: i64) -> SystemTime {
+pub(super) fn unix_ms_to_system_time(ms: i64) -> SystemTime {
     if ms <= 0 {
         return UNIX_EPOCH;
     }
     UNIX_EPOCH + Duration::from_millis(ms as u64)
 }
 
-fn system_time_to_unix_ms(time: SystemTime) -> i64 {
+pub(super) fn system_time_to_unix_ms(time: SystemTime) -> i64 {
     let delta = time.duration_since(UNIX_EPOCH).unwrap_or_default();
     i64::try_from(delta.as_millis()).unwrap_or(0)
 }
@@ -342,4 +640,121 @@ mod tests {
         assert!(store.delete_task(&task_id).expect("delete"));
         assert!(store.list_tasks().expect("list after").is_empty());
     }
+
+    #[test]
+    fn schedule_shows_in_list_and_is_taken_once_due() {
+        let dir = tempdir().expect("tempdir");
+        let store = TaskStore::open(dir.path().join("tasks.db")).expect("open");
+
+        let task_id = store
+            .create_task(Path::new("/tmp/project"), "Nightly run", &[])
+            .expect("create");
+        let due_at = UNIX_EPOCH + Duration::from_secs(1_000);
+        assert!(
+            store
+                .schedule_task(&task_id, AgentEngine::Claude, due_at)
+                .expect("schedule")
+        );
+        assert!(
+            !store
+                .schedule_task(
+                    &TaskId::new("missing".to_string()),
+                    AgentEngine::Codex,
+                    due_at
+                )
+                .expect("schedule missing")
+        );
+
+        let tasks = store.list_tasks().expect("list");
+        let schedule = tasks[0].schedule.clone().expect("schedule listed");
+        assert_eq!(schedule.engine, AgentEngine::Claude);
+        assert_eq!(schedule.due_at, due_at);
+
+        let early = store
+            .run_due_task_schedules(UNIX_EPOCH + Duration::from_secs(999), |_| {
+                Ok::<_, String>(())
+            })
+            .expect("run early");
+        assert!(early.is_empty());
+
+        let mut spawned = Vec::new();
+        let due = store
+            .run_due_task_schedules(UNIX_EPOCH + Duration::from_secs(1_000), |schedule| {
+                spawned.push(schedule.task_id.clone());
+                Ok::<_, String>(())
+            })
+            .expect("run due");
+        assert_eq!(due.len(), 1);
+        assert_eq!(spawned, vec![task_id.clone()]);
+        assert!(
+            store.list_tasks().expect("list after")[0]
+                .schedule
+                .is_none()
+        );
+        assert!(!store.cancel_task_schedule(&task_id).expect("cancel"));
+    }
+
+    #[test]
+    fn failed_spawn_backs_off_then_drops_the_schedule() {
+        let dir = tempdir().expect("tempdir");
+        let store = TaskStore::open(dir.path().join("tasks.db")).expect("open");
+
+        let task_id = store
+            .create_task(Path::new("/tmp/project"), "Nightly run", &[])
+            .expect("create");
+        let due_at = UNIX_EPOCH + Duration::from_secs(1_000);
+        store
+            .schedule_task(&task_id, AgentEngine::Codex, due_at)
+            .expect("schedule");
+
+        let fail = |now: SystemTime| {
+            store
+                .run_due_task_schedules(now, |_| Err::<(), _>("spawn failed".to_string()))
+                .expect("run failing")
+        };
+        let retry_at = due_at + Duration::from_secs(30);
+        assert_eq!(
+            fail(due_at),
+            vec![Err(TaskScheduleFailure {
+                error: "spawn failed".to_string(),
+                retry_at: Some(retry_at),
+            })]
+        );
+        let schedule = store.list_tasks().expect("list")[0]
+            .schedule
+            .clone()
+            .expect("schedule kept");
+        assert_eq!(schedule.due_at, retry_at);
+        // Not due again until the backoff has passed.
+        assert!(fail(due_at + Duration::from_secs(10)).is_empty());
+        let retry_at_after_failure = |now: SystemTime| match fail(now).as_slice() {
+            [Err(failure)] => failure.retry_at,
+            other => panic!("expected one failure, got {other:?}"),
+        };
+
+        // The backoff doubles, and the fifth failure drops the schedule.
+        let mut now = retry_at;
+        for backoff_secs in [60, 120, 240] {
+            let next = now + Duration::from_secs(backoff_secs);
+            assert_eq!(retry_at_after_failure(now), Some(next));
+            now = next;
+        }
+        assert_eq!(retry_at_after_failure(now), None);
+        assert!(store.list_tasks().expect("list")[0].schedule.is_none());
+
+        // Rescheduling starts over, and a successful spawn removes the schedule.
+        store
+            .schedule_task(&task_id, AgentEngine::Codex, due_at)
+            .expect("reschedule");
+        assert_eq!(retry_at_after_failure(due_at), Some(retry_at));
+        let retried = store
+            .run_due_task_schedules(retry_at, |_| Ok::<_, String>(()))
+            .expect("run retry");
+        assert_eq!(retried.len(), 1);
+        assert!(
+            store.list_tasks().expect("list after")[0]
+                .schedule
+                .is_none()
+        );
+    }
 }
@@ -1,26 +1,44 @@
+use base64::Engine as _;
+use ed25519_dalek::{Signature, Verifier as _, VerifyingKey};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
+use std::cmp::Ordering;
 use std::fmt;
 use std::fs;
 use std::io::{self, Read, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use thiserror::Error;
 
 const REPO: &str = "diskd-ai/ccbox";
 const BIN_NAME: &str = "ccbox";
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
+/// Base64 Ed25519 key that signs release archives, baked in by release builds. Builds without
+/// it (from source) install updates checked by sha256 only.
+const RELEASE_PUBLIC_KEY_B64: Option<&str> = option_env!("CCBOX_RELEASE_PUBLIC_KEY");
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct Version {
     pub major: u64,
     pub minor: u64,
     pub patch: u64,
+    /// `N` of a `-beta.N` pre-release (`-beta` alone is 0); sorts before the release itself.
+    pub beta: Option<u64>,
 }
 
 impl Version {
     pub fn parse(value: &str) -> Option<Self> {
         let trimmed = value.trim().trim_start_matches('v');
-        let mut parts = trimmed.split('.');
+        let (core, pre) = match trimmed.split_once('-') {
+            Some((core, pre)) => (core, Some(pre)),
+            None => (trimmed, None),
+        };
+        let beta = match pre {
+            None => None,
+            Some("beta") => Some(0),
+            Some(pre) => Some(pre.strip_prefix("beta.")?.parse::<u64>().ok()?),
+        };
+        let mut parts = core.split('.');
         let major = parts.next()?.parse::<u64>().ok()?;
         let minor = parts.next()?.parse::<u64>().ok()?;
         let patch = parts.next()?.parse::<u64>().ok()?;
@@ -31,16 +49,76 @@ impl Version {
             major,
             minor,
             patch,
+            beta,
         })
     }
+
+    fn sort_key(&self) -> (u64, u64, u64, bool, u64) {
+        (
+            self.major,
+            self.minor,
+            self.patch,
+            self.beta.is_none(),
+            self.beta.unwrap_or(0),
+        )
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.sort_key().cmp(&other.sort_key())
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
 }
 
 impl fmt::Display for Version {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        match self.beta {
+            Some(n) => write!(f, "-beta.{n}"),
+            None => Ok(()),
+        }
     }
 }
 
+/// Which releases `ccbox update` follows: `stable` skips GitHub pre-releases, `beta` takes the
+/// newest release of either kind.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum UpdateChannel {
+    #[default]
+    Stable,
+    Beta,
+}
+
+impl UpdateChannel {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim() {
+            "stable" => Some(Self::Stable),
+            "beta" => Some(Self::Beta),
+            _ => None,
+        }
+    }
+}
+
+/// How an installed archive was authenticated.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ReleaseSignature {
+    Verified,
+    /// This build carries no release key, so only the sha256 was checked.
+    Unchecked,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InstalledUpdate {
+    pub update: UpdateAvailable,
+    pub signature: ReleaseSignature,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct UpdateAvailable {
     pub current: Version,
@@ -74,6 +152,15 @@ pub enum UpdateError {
     #[error("sha256 mismatch for {asset}")]
     ShaMismatch { asset: String },
 
+    #[error("release signature check failed for {asset}: {message}")]
+    Signature { asset: String, message: String },
+
+    #[error("no previous binary to roll back to (it is kept after `ccbox update` installs one)")]
+    NoPreviousBinary,
+
+    #[error("failed to keep the previous binary at {path}: {source}")]
+    Backup { path: String, source: io::Error },
+
     #[error("failed to extract binary from archive: {0}")]
     Extract(String),
 
@@ -89,12 +176,29 @@ struct GitHubLatestRelease {
     tag_name: String,
 }
 
+#[derive(Debug, Deserialize)]
+struct GitHubRelease {
+    tag_name: String,
+    #[serde(default)]
+    draft: bool,
+}
+
 pub fn check_for_update(current_version: &str) -> Result<Option<UpdateAvailable>, UpdateError> {
+    check_for_update_on(UpdateChannel::Stable, current_version)
+}
+
+pub fn check_for_update_on(
+    channel: UpdateChannel,
+    current_version: &str,
+) -> Result<Option<UpdateAvailable>, UpdateError> {
     let current = Version::parse(current_version).ok_or_else(|| {
         UpdateError::InvalidLatestTag(format!("current version is invalid: {current_version}"))
     })?;
 
-    let latest = fetch_latest_release_info(Duration::from_secs(4))?;
+    let latest = match channel {
+        UpdateChannel::Stable => fetch_latest_release_info(Duration::from_secs(4))?,
+        UpdateChannel::Beta => fetch_newest_release_info(Duration::from_secs(4))?,
+    };
     if latest.version <= current {
         return Ok(None);
     }
@@ -134,16 +238,122 @@ pub fn fetch_latest_release_info(timeout: Duration) -> Result<LatestReleaseInfo,
     })
 }
 
-pub fn self_update() -> Result<Option<UpdateAvailable>, UpdateError> {
-    let Some(update) = check_for_update(env!("CARGO_PKG_VERSION"))? else {
+/// Newest release including pre-releases, for the beta channel.
+fn fetch_newest_release_info(timeout: Duration) -> Result<LatestReleaseInfo, UpdateError> {
+    let agent = make_agent(timeout);
+
+    let url = format!("https://api.github.com/repos/{REPO}/releases?per_page=30");
+    let mut response = agent
+        .get(&url)
+        .header(
+            "User-Agent",
+            &format!("{BIN_NAME}/{}", env!("CARGO_PKG_VERSION")),
+        )
+        .header("Accept", "application/vnd.github+json")
+        .call()
+        .map_err(|error| UpdateError::FetchLatest(error.to_string()))?;
+
+    let releases: Vec<GitHubRelease> = response
+        .body_mut()
+        .read_json::<Vec<GitHubRelease>>()
+        .map_err(|error| UpdateError::FetchLatest(error.to_string()))?;
+
+    newest_release(&releases)
+        .ok_or_else(|| UpdateError::FetchLatest("no published releases".to_string()))
+}
+
+fn newest_release(releases: &[GitHubRelease]) -> Option<LatestReleaseInfo> {
+    releases
+        .iter()
+        .filter(|release| !release.draft)
+        .filter_map(|release| {
+            Version::parse(&release.tag_name).map(|version| LatestReleaseInfo {
+                version,
+                tag: release.tag_name.clone(),
+            })
+        })
+        .max_by_key(|info| info.version)
+}
+
+/// Installs the newest release on `channel`, keeping the running binary for
+/// [`rollback_update`].
+pub fn self_update(channel: UpdateChannel) -> Result<Option<InstalledUpdate>, UpdateError> {
+    let Some(update) = check_for_update_on(channel, env!("CARGO_PKG_VERSION"))? else {
         return Ok(None);
     };
 
-    let archive = download_release_archive(&update.latest_tag, &update.latest)?;
+    let (archive, signature) = download_release_archive(&update.latest_tag, &update.latest)?;
     let extracted = extract_binary_from_tar_gz(&archive).map_err(UpdateError::Extract)?;
 
+    let current_exe = resolve_current_exe()?;
+    let current_bytes = fs::read(&current_exe).map_err(|error| UpdateError::Install {
+        path: current_exe.display().to_string(),
+        source: error,
+    })?;
+    save_previous_binary(&current_bytes, env!("CARGO_PKG_VERSION"))?;
     install_binary(&extracted)?;
-    Ok(Some(update))
+    Ok(Some(InstalledUpdate { update, signature }))
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct PreviousBinaryFile {
+    version: String,
+}
+
+fn previous_binary_dir() -> Result<PathBuf, UpdateError> {
+    super::resolve_ccbox_state_dir()
+        .map(|dir| dir.join("update"))
+        .map_err(|error| UpdateError::CurrentExe(error.to_string()))
+}
+
+fn save_previous_binary(bytes: &[u8], version: &str) -> Result<(), UpdateError> {
+    let dir = previous_binary_dir()?;
+    let backup_error = |path: &Path| {
+        let path = path.display().to_string();
+        move |source| UpdateError::Backup { path, source }
+    };
+    fs::create_dir_all(&dir).map_err(backup_error(&dir))?;
+    let bin_path = dir.join(format!("{BIN_NAME}.previous"));
+    fs::write(&bin_path, bytes).map_err(backup_error(&bin_path))?;
+    let meta_path = dir.join("previous.json");
+    let meta = PreviousBinaryFile {
+        version: version.to_string(),
+    };
+    let text = serde_json::to_string_pretty(&meta).unwrap_or_else(|_| "{}".to_string());
+    fs::write(&meta_path, text).map_err(backup_error(&meta_path))
+}
+
+/// Reinstalls the binary kept by the last update and keeps the replaced one in its place, so a
+/// second rollback undoes the first. Returns the restored version.
+pub fn rollback_update() -> Result<String, UpdateError> {
+    let dir = previous_binary_dir()?;
+    let bin_path = dir.join(format!("{BIN_NAME}.previous"));
+    let previous = match fs::read(&bin_path) {
+        Ok(bytes) if !bytes.is_empty() => bytes,
+        Ok(_) => return Err(UpdateError::NoPreviousBinary),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => {
+            return Err(UpdateError::NoPreviousBinary);
+        }
+        Err(error) => {
+            return Err(UpdateError::Backup {
+                path: bin_path.display().to_string(),
+                source: error,
+            });
+        }
+    };
+    let version = fs::read_to_string(dir.join("previous.json"))
+        .ok()
+        .and_then(|raw| serde_json::from_str::<PreviousBinaryFile>(&raw).ok())
+        .map_or_else(|| "unknown".to_string(), |meta| meta.version);
+
+    let current_exe = resolve_current_exe()?;
+    let current_bytes = fs::read(&current_exe).map_err(|error| UpdateError::Install {
+        path: current_exe.display().to_string(),
+        source: error,
+    })?;
+    install_binary(&previous)?;
+    save_previous_binary(&current_bytes, env!("CARGO_PKG_VERSION"))?;
+    Ok(version)
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
@@ -196,7 +406,10 @@ pub fn save_update_check_cache(state_dir: &Path, latest_tag: &str) -> Result<(),
     Ok(())
 }
 
-fn download_release_archive(tag: &str, version: &Version) -> Result<Vec<u8>, UpdateError> {
+fn download_release_archive(
+    tag: &str,
+    version: &Version,
+) -> Result<(Vec<u8>, ReleaseSignature), UpdateError> {
     let target = resolve_target_triple()?;
     let version = version.to_string();
     let artifact = format!("{BIN_NAME}-{version}-{target}.tar.gz");
@@ -214,7 +427,48 @@ fn download_release_archive(tag: &str, version: &Version) -> Result<Vec<u8>, Upd
         return Err(UpdateError::ShaMismatch { asset: artifact });
     }
 
-    Ok(archive_bytes)
+    let signature = match release_public_key(RELEASE_PUBLIC_KEY_B64) {
+        Some(public_key_b64) => {
+            let sig_bytes = http_get_bytes(&agent, &format!("{archive_url}.sig"))?;
+            verify_release_signature(&archive_bytes, &sig_bytes, public_key_b64).map_err(
+                |message| UpdateError::Signature {
+                    asset: artifact.clone(),
+                    message,
+                },
+            )?;
+            ReleaseSignature::Verified
+        }
+        None => ReleaseSignature::Unchecked,
+    };
+
+    Ok((archive_bytes, signature))
+}
+
+/// The baked-in key, if any; a blank value (an unset CI variable exported as `""`) counts as
+/// no key.
+fn release_public_key(raw: Option<&'static str>) -> Option<&'static str> {
+    raw.map(str::trim).filter(|key| !key.is_empty())
+}
+
+/// Checks a raw 64-byte Ed25519 signature (as written by `openssl pkeyutl -sign -rawin`) over
+/// the archive bytes.
+fn verify_release_signature(
+    archive: &[u8],
+    signature: &[u8],
+    public_key_b64: &str,
+) -> Result<(), String> {
+    let key_bytes = base64::engine::general_purpose::STANDARD
+        .decode(public_key_b64.trim())
+        .map_err(|error| format!("invalid release key: {error}"))?;
+    let key_bytes: [u8; 32] = key_bytes
+        .try_into()
+        .map_err(|_| "invalid release key length".to_string())?;
+    let key = VerifyingKey::from_bytes(&key_bytes)
+        .map_err(|error| format!("invalid release key: {error}"))?;
+    let signature =
+        Signature::from_slice(signature).map_err(|_| "malformed signature".to_string())?;
+    key.verify(archive, &signature)
+        .map_err(|_| "signature does not match".to_string())
 }
 
 fn resolve_target_triple() -> Result<&'static str, UpdateError> {
@@ -326,9 +580,12 @@ fn extract_binary_from_tar_gz(archive_bytes: &[u8]) -> Result<Vec<u8>, String> {
     Err(format!("missing {BIN_NAME} in archive"))
 }
 
+fn resolve_current_exe() -> Result<PathBuf, UpdateError> {
+    std::env::current_exe().map_err(|error| UpdateError::CurrentExe(error.to_string()))
+}
+
 fn install_binary(bytes: &[u8]) -> Result<(), UpdateError> {
-    let current_exe =
-        std::env::current_exe().map_err(|error| UpdateError::CurrentExe(error.to_string()))?;
+    let current_exe = resolve_current_exe()?;
     let Some(dir) = current_exe.parent() else {
         return Err(UpdateError::CurrentExe(format!(
             "executable has no parent directory: {}",
@@ -377,3 +634,56 @@ fn install_binary(bytes: &[u8]) -> Result<(), UpdateError> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer as _, SigningKey};
+
+    #[test]
+    fn betas_sort_before_their_release_and_the_beta_channel_picks_the_newest() {
+        let beta = Version::parse("v1.3.0-beta.2").expect("beta");
+        assert_eq!(beta.beta, Some(2));
+        assert_eq!(beta.to_string(), "1.3.0-beta.2");
+        assert!(Version::parse("1.3.0-beta.1").expect("beta") < beta);
+        assert!(beta < Version::parse("1.3.0").expect("release"));
+        assert!(Version::parse("1.2.9").expect("release") < beta);
+        assert_eq!(Version::parse("1.3.0-rc.1"), None);
+
+        let release = |tag: &str, draft: bool| GitHubRelease {
+            tag_name: tag.to_string(),
+            draft,
+        };
+        let newest = newest_release(&[
+            release("v1.2.0", false),
+            release("v1.3.0-beta.1", false),
+            release("v1.3.0-beta.2", true),
+            release("nightly", false),
+        ])
+        .expect("newest");
+        assert_eq!(newest.tag, "v1.3.0-beta.1");
+        assert_eq!(UpdateChannel::parse("beta"), Some(UpdateChannel::Beta));
+        assert_eq!(UpdateChannel::parse("nightly"), None);
+    }
+
+    #[test]
+    fn release_signature_must_match_the_archive() {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let public_key_b64 =
+            base64::engine::general_purpose::STANDARD.encode(key.verifying_key().to_bytes());
+        let archive = b"archive bytes";
+        let signature = key.sign(archive).to_bytes();
+
+        assert_eq!(
+            verify_release_signature(archive, &signature, &public_key_b64),
+            Ok(())
+        );
+        assert!(verify_release_signature(b"tampered", &signature, &public_key_b64).is_err());
+        assert!(verify_release_signature(archive, &signature[..10], &public_key_b64).is_err());
+
+        assert_eq!(release_public_key(Some("")), None);
+        assert_eq!(release_public_key(Some(" \n")), None);
+        assert_eq!(release_public_key(Some(" abc= ")), Some("abc="));
+        assert_eq!(release_public_key(None), None);
+    }
+}
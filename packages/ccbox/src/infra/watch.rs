@@ -1,9 +1,64 @@
 use notify::event::EventKind;
 use notify::{Config, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Deserialize;
 use std::path::Path;
 use std::sync::mpsc::{Receiver, channel};
+use std::time::Duration;
 use thiserror::Error;
 
+/// Quiet time after the last session change before rescanning, unless the config overrides it.
+pub const DEFAULT_RESCAN_DEBOUNCE: Duration = Duration::from_millis(900);
+/// Longest a rescan waits while changes keep arriving, unless the config overrides it.
+pub const DEFAULT_RESCAN_MAX_DELAY: Duration = Duration::from_secs(5);
+
+/// When session changes trigger a rescan (`rescan` in the config).
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RescanConfig {
+    /// Milliseconds without a change before rescanning (default 900).
+    #[serde(default)]
+    pub debounce_ms: Option<u64>,
+
+    /// Milliseconds after the first change when a rescan runs even if changes keep coming
+    /// (default 5000).
+    #[serde(default)]
+    pub max_delay_ms: Option<u64>,
+
+    /// Rescan every this many seconds, with or without watchers; 0 or unset turns it off.
+    #[serde(default)]
+    pub interval_secs: Option<u64>,
+
+    /// Don't watch session directories and files; rely on `interval_secs` or Ctrl+R.
+    #[serde(default)]
+    pub disable_watchers: bool,
+}
+
+impl RescanConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.max_delay() < self.debounce() {
+            return Err("rescan: max_delay_ms must not be less than debounce_ms".to_string());
+        }
+        Ok(())
+    }
+
+    pub fn debounce(&self) -> Duration {
+        self.debounce_ms
+            .map_or(DEFAULT_RESCAN_DEBOUNCE, Duration::from_millis)
+    }
+
+    pub fn max_delay(&self) -> Duration {
+        self.max_delay_ms
+            .map_or(DEFAULT_RESCAN_MAX_DELAY, Duration::from_millis)
+    }
+
+    /// The periodic rescan interval, if one is set.
+    pub fn interval(&self) -> Option<Duration> {
+        self.interval_secs
+            .filter(|secs| *secs > 0)
+            .map(Duration::from_secs)
+    }
+}
+
 #[derive(Clone, Debug)]
 pub enum WatchSignal {
     Changed,
@@ -32,6 +87,11 @@ impl SessionFileWatcher {
     pub fn try_recv(&self) -> Option<WatchSignal> {
         self.rx.try_recv().ok()
     }
+
+    /// Blocks until the next signal or until `timeout` elapses.
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<WatchSignal> {
+        self.rx.recv_timeout(timeout).ok()
+    }
 }
 
 #[derive(Debug, Error)]
@@ -162,8 +222,7 @@ fn should_trigger_rescan(event: &notify::Event) -> bool {
     }
 
     event.paths.iter().any(|path| {
-        let extension = path.extension().and_then(|ext| ext.to_str());
-        if extension == Some("jsonl") {
+        if super::is_jsonl_log_path(path) {
             return true;
         }
 
@@ -175,7 +234,8 @@ fn should_trigger_rescan(event: &notify::Event) -> bool {
             return true;
         }
 
-        extension == Some("json") && file_name.starts_with("session-")
+        path.extension().and_then(|ext| ext.to_str()) == Some("json")
+            && file_name.starts_with("session-")
     })
 }
 
@@ -0,0 +1,119 @@
+use crate::domain::{LifecycleEvent, LifecycleEventKind};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::time::Duration;
+use thiserror::Error;
+
+const WEBHOOK_HTTP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A URL that receives lifecycle events as HTTP POSTs.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct WebhookConfig {
+    pub url: String,
+
+    /// Event names to send (`process.started`, `process.exited`, `process.failed`,
+    /// `process.needs_attention`, `session.detected`, `session.deleted`, `task.completed`);
+    /// empty sends all of them.
+    #[serde(default)]
+    pub events: Vec<String>,
+
+    /// Request body with `{{field}}` placeholders; defaults to the event as JSON.
+    #[serde(default)]
+    pub template: Option<String>,
+
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Error)]
+#[error("webhook {url} failed: {message}")]
+pub struct WebhookError {
+    pub url: String,
+    pub message: String,
+}
+
+impl WebhookConfig {
+    pub fn is_subscribed(&self, kind: LifecycleEventKind) -> bool {
+        self.events.is_empty() || self.events.iter().any(|event| event == kind.as_str())
+    }
+
+    pub fn body(&self, event: &LifecycleEvent) -> String {
+        match &self.template {
+            Some(template) => event.render(template),
+            None => event.to_json().to_string(),
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if !(self.url.starts_with("https://") || self.url.starts_with("http://")) {
+            return Err(format!(
+                "webhooks: url must start with http:// or https:// (got `{}`)",
+                self.url
+            ));
+        }
+        for event in &self.events {
+            if LifecycleEventKind::parse(event).is_none() {
+                return Err(format!("webhooks: unknown event `{event}`"));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// POSTs `event` to the webhook. Blocking; call it off the UI thread.
+pub fn deliver_webhook(config: &WebhookConfig, event: &LifecycleEvent) -> Result<(), WebhookError> {
+    let agent: ureq::Agent = ureq::Agent::config_builder()
+        .timeout_global(Some(WEBHOOK_HTTP_TIMEOUT))
+        .build()
+        .into();
+    let mut request = agent
+        .post(&config.url)
+        .header("Content-Type", "application/json")
+        .header(
+            "User-Agent",
+            &format!("ccbox/{}", env!("CARGO_PKG_VERSION")),
+        );
+    for (name, value) in &config.headers {
+        request = request.header(name, value);
+    }
+    request
+        .send(config.body(event))
+        .map(|_| ())
+        .map_err(|error| WebhookError {
+            url: config.url.clone(),
+            message: error.to_string(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filters_events_and_renders_template_body() {
+        let config: WebhookConfig = serde_json::from_value(serde_json::json!({
+            "url": "https://hooks.example.com/T000",
+            "events": ["process.exited", "process.failed"],
+            "template": "{\"text\":\"{{engine}} {{event}} ({{exit_code}})\"}"
+        }))
+        .expect("config");
+        assert_eq!(config.validate(), Ok(()));
+        assert!(config.is_subscribed(LifecycleEventKind::ProcessFailed));
+        assert!(!config.is_subscribed(LifecycleEventKind::SessionDetected));
+
+        let event = LifecycleEvent::new(LifecycleEventKind::ProcessExited)
+            .with("engine", "Claude")
+            .with("exit_code", "0");
+        assert_eq!(
+            config.body(&event),
+            r#"{"text":"Claude process.exited (0)"}"#
+        );
+
+        let bad: WebhookConfig = serde_json::from_value(serde_json::json!({
+            "url": "ftp://example.com",
+        }))
+        .expect("config");
+        assert!(bad.validate().is_err());
+    }
+}
@@ -0,0 +1,238 @@
+use super::git::{GitError, git_command, run_git};
+use crate::domain::{WORKTREE_BRANCH_PREFIX, worktree_slug};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum WorktreeError {
+    #[error("not a git repository: {0}")]
+    NotARepository(String),
+
+    #[error("failed to run git: {0}")]
+    Spawn(#[from] io::Error),
+
+    #[error("git {command} failed: {stderr}")]
+    Git { command: String, stderr: String },
+
+    #[error("worktree not found: {0}\nHint: run `ccbox worktrees` to list agent worktrees.")]
+    NotFound(String),
+
+    #[error(
+        "branch {0} is not merged into the current checkout\nHint: merge it first, or pass --force to discard it."
+    )]
+    Unmerged(String),
+}
+
+impl WorktreeError {
+    fn from_git(command: &str, error: GitError) -> Self {
+        match error {
+            GitError::Spawn(error) => Self::Spawn(error),
+            GitError::Failed(stderr) => Self::Git {
+                command: command.to_string(),
+                stderr,
+            },
+        }
+    }
+}
+
+/// A git worktree created for one agent, on branch `ccbox/<name>`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AgentWorktree {
+    pub name: String,
+    pub path: PathBuf,
+    pub branch: String,
+}
+
+/// Creates a worktree for an agent next to the repository (`<repo>-worktrees/<name>`) on a new
+/// `ccbox/<name>` branch from the current HEAD. The name comes from `title`, with `-2`, `-3`, …
+/// appended when taken. Returns the worktree and the directory to run the agent in (the same
+/// subfolder of the checkout as `project_path`).
+pub fn create_agent_worktree(
+    project_path: &Path,
+    title: &str,
+) -> Result<(AgentWorktree, PathBuf), WorktreeError> {
+    let toplevel = repository_toplevel(project_path)?;
+    let repo_name = toplevel
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "repo".to_string());
+    let root = toplevel
+        .parent()
+        .unwrap_or(&toplevel)
+        .join(format!("{repo_name}-worktrees"));
+
+    let slug = worktree_slug(title);
+    let mut suffix = 1usize;
+    let (name, path, branch) = loop {
+        let name = if suffix == 1 {
+            slug.clone()
+        } else {
+            format!("{slug}-{suffix}")
+        };
+        let path = root.join(&name);
+        let branch = format!("{WORKTREE_BRANCH_PREFIX}{name}");
+        if !path.exists() && !branch_exists(&toplevel, &branch)? {
+            break (name, path, branch);
+        }
+        suffix += 1;
+    };
+
+    let mut command = git_command(&toplevel);
+    command
+        .args(["worktree", "add", "-b", &branch, "--"])
+        .arg(&path)
+        .arg("HEAD");
+    run_git(command).map_err(|error| WorktreeError::from_git("worktree add", error))?;
+
+    let cwd = project_path
+        .canonicalize()
+        .ok()
+        .and_then(|project| {
+            project
+                .strip_prefix(&toplevel)
+                .ok()
+                .map(|relative| path.join(relative))
+        })
+        .unwrap_or_else(|| path.clone());
+    Ok((AgentWorktree { name, path, branch }, cwd))
+}
+
+/// Agent worktrees (branches under `ccbox/`) of the repository containing `project_path`.
+pub fn list_agent_worktrees(project_path: &Path) -> Result<Vec<AgentWorktree>, WorktreeError> {
+    let toplevel = repository_toplevel(project_path)?;
+    let mut command = git_command(&toplevel);
+    command.args(["worktree", "list", "--porcelain"]);
+    let stdout =
+        run_git(command).map_err(|error| WorktreeError::from_git("worktree list", error))?;
+    Ok(parse_worktree_list(&stdout))
+}
+
+/// Merges `ccbox/<name>` into the branch checked out at `project_path` (`git merge --no-ff`).
+pub fn merge_agent_worktree(project_path: &Path, name: &str) -> Result<String, WorktreeError> {
+    let branch = agent_branch(name);
+    if !branch_exists(project_path, &branch)? {
+        return Err(WorktreeError::NotFound(name.to_string()));
+    }
+    let mut command = git_command(project_path);
+    command.args(["merge", "--no-ff", "--no-edit", "--", &branch]);
+    run_git(command).map_err(|error| WorktreeError::from_git("merge", error))
+}
+
+/// Removes the worktree (if it still exists) and deletes its branch; returns the branch. Without
+/// `force`, an unmerged branch or a worktree with uncommitted changes is left alone.
+pub fn remove_agent_worktree(
+    project_path: &Path,
+    name: &str,
+    force: bool,
+) -> Result<String, WorktreeError> {
+    let branch = agent_branch(name);
+    let worktree = list_agent_worktrees(project_path)?
+        .into_iter()
+        .find(|worktree| worktree.branch == branch);
+    let has_branch = branch_exists(project_path, &branch)?;
+    if worktree.is_none() && !has_branch {
+        return Err(WorktreeError::NotFound(name.to_string()));
+    }
+    if has_branch && !force && !is_merged(project_path, &branch)? {
+        return Err(WorktreeError::Unmerged(branch));
+    }
+
+    if let Some(worktree) = worktree {
+        let mut command = git_command(project_path);
+        command.args(["worktree", "remove"]);
+        if force {
+            command.arg("--force");
+        }
+        command.arg("--").arg(&worktree.path);
+        run_git(command).map_err(|error| WorktreeError::from_git("worktree remove", error))?;
+    }
+    if has_branch {
+        // Merged (checked above) or forced, so `-D` only skips git's upstream check.
+        let mut command = git_command(project_path);
+        command.args(["branch", "-D", "--", &branch]);
+        run_git(command).map_err(|error| WorktreeError::from_git("branch delete", error))?;
+    }
+    Ok(branch)
+}
+
+fn agent_branch(name: &str) -> String {
+    let name = name.strip_prefix(WORKTREE_BRANCH_PREFIX).unwrap_or(name);
+    format!("{WORKTREE_BRANCH_PREFIX}{name}")
+}
+
+fn is_merged(project_path: &Path, branch: &str) -> Result<bool, WorktreeError> {
+    let status = git_command(project_path)
+        .args(["merge-base", "--is-ancestor", "--", branch, "HEAD"])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()?;
+    Ok(status.success())
+}
+
+fn repository_toplevel(project_path: &Path) -> Result<PathBuf, WorktreeError> {
+    let mut command = git_command(project_path);
+    command.args(["rev-parse", "--show-toplevel"]);
+    match run_git(command) {
+        Ok(stdout) => Ok(PathBuf::from(stdout.trim())),
+        Err(GitError::Failed(_)) => Err(WorktreeError::NotARepository(
+            project_path.display().to_string(),
+        )),
+        Err(GitError::Spawn(error)) => Err(WorktreeError::Spawn(error)),
+    }
+}
+
+fn branch_exists(dir: &Path, branch: &str) -> Result<bool, WorktreeError> {
+    let status = git_command(dir)
+        .args(["rev-parse", "--verify", "--quiet"])
+        .arg(format!("refs/heads/{branch}"))
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()?;
+    Ok(status.success())
+}
+
+/// Parses `git worktree list --porcelain`, keeping worktrees on `ccbox/` branches.
+fn parse_worktree_list(stdout: &str) -> Vec<AgentWorktree> {
+    let mut worktrees = Vec::new();
+    for block in stdout.split("\n\n") {
+        let mut path = None;
+        let mut branch = None;
+        for line in block.lines() {
+            if let Some(value) = line.strip_prefix("worktree ") {
+                path = Some(PathBuf::from(value));
+            } else if let Some(value) = line.strip_prefix("branch refs/heads/") {
+                branch = Some(value.to_string());
+            }
+        }
+        if let (Some(path), Some(branch)) = (path, branch)
+            && let Some(name) = branch.strip_prefix(WORKTREE_BRANCH_PREFIX)
+        {
+            worktrees.push(AgentWorktree {
+                name: name.to_string(),
+                path,
+                branch,
+            });
+        }
+    }
+    worktrees
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists_only_worktrees_on_ccbox_branches() {
+        let stdout = "worktree /work/app\nHEAD 4f5934c\nbranch refs/heads/main\n\nworktree /work/app-worktrees/fix-ci\nHEAD 4f5934c\nbranch refs/heads/ccbox/fix-ci\n\nworktree /work/app-detached\nHEAD 4f5934c\ndetached\n";
+        assert_eq!(
+            parse_worktree_list(stdout),
+            vec![AgentWorktree {
+                name: "fix-ci".to_string(),
+                path: PathBuf::from("/work/app-worktrees/fix-ci"),
+                branch: "ccbox/fix-ci".to_string(),
+            }]
+        );
+    }
+}
@@ -9,18 +9,29 @@ use crate::app::ProcessOutputKind;
 use crate::app::{AppCommand, AppEvent, AppModel};
 use crate::cli::CliInvocation;
 use crate::domain::{
-    compute_session_stats, derive_task_title, format_task_spawn_prompt, make_session_summary,
+    AgentEngine, AuditAction, AuditEntry, LifecycleEvent, LifecycleEventKind, RemoteSourceConfig,
+    RetryPolicy, Task, TaskId, TaskImage, compute_session_stats, derive_task_title,
+    diff_session_timelines, format_task_spawn_prompt, make_session_summary,
     parse_session_meta_line,
 };
 use crate::infra::{
-    AttachTtyError, KillProcessError, ProcessExit, ProcessManager, ProcessSignal, ResizeTtyError,
-    ResolveClaudeProjectsDirError, ResolveGeminiRootDirError, ResolveOpenCodeDbPathError,
-    SessionIndex, TaskStore, WatchSignal, WriteTtyError, delete_session_logs,
-    fork_codex_session_log_at_cut, load_last_assistant_output, load_session_index,
-    load_session_timeline, read_from_offset, read_tail, refresh_session_index,
-    resolve_ccbox_state_dir, resolve_claude_projects_dir, resolve_gemini_root_dir,
-    resolve_opencode_db_path, resolve_sessions_dir, save_session_index, scan_all_sessions,
-    set_session_alias, set_session_project, watch_session_file, watch_sessions_dir,
+    AgentSpawn, ArtifactCipher, AttachTtyError, CcboxConfig, HookConfig, KillProcessError,
+    MetadataSyncConfig, Metrics, ProcessExit, ProcessManager, ProcessRetry, ProcessSignal,
+    ProcessUsageSampler, REMOTE_SYNC_INTERVAL, ResizeTtyError, ResolveClaudeProjectsDirError,
+    ResolveGeminiRootDirError, ResolveOpenCodeDbPathError, ScanProgress, SessionIndex,
+    SessionsDirWatcher, SpawnedAgentProcess, StartupProfiler, SystemClipboard, TaskListEntry,
+    TaskRegistryConfig, TaskStore, WatchSignal, WebhookConfig, WriteTtyError, audit_entry_now,
+    clear_task_registry, complete_dir_path, delete_session_logs, deliver_webhook,
+    fork_codex_session_log_at_cut, load_audit_entries, load_ccbox_config, load_engine_filters,
+    load_last_assistant_output, load_project_prefs, load_session_index, load_session_timeline,
+    load_team_tasks, read_appended_timeline_items, read_artifact_tail, read_from_offset,
+    record_audit_entry, refresh_session_index, resolve_ccbox_config_path, resolve_ccbox_state_dir,
+    resolve_claude_projects_dir, resolve_extra_session_dirs, resolve_gemini_root_dir,
+    resolve_opencode_db_path, resolve_sessions_dir, resolve_task_registry_dir,
+    resolve_task_schedule_due_at, resolve_tasks_db_path, run_hook, save_engine_filters,
+    save_project_prefs, save_session_index, save_startup_profile, scan_all_sessions,
+    scan_all_sessions_with_progress, set_session_alias, set_session_project, start_metrics_server,
+    sync_metadata, sync_remote_sources, sync_task_registry, watch_session_file, watch_sessions_dir,
     watch_sqlite_db_family,
 };
 use crossterm::event::{
@@ -35,11 +46,13 @@ use crossterm::terminal::{
 use crossterm::{ExecutableCommand, execute};
 use ratatui::Terminal;
 use ratatui::backend::CrosstermBackend;
+use std::collections::{HashMap, HashSet};
 use std::io::{self, Stdout, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::{Sender, channel};
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -59,6 +72,64 @@ enum UpdateSignal {
     UpdateAvailable { latest_tag: String },
 }
 
+#[derive(Clone, Debug)]
+enum TaskRegistrySignal {
+    Synced(Result<usize, String>),
+}
+
+#[derive(Clone, Debug)]
+enum MetadataSyncSignal {
+    Synced(Result<usize, String>),
+}
+
+#[derive(Clone, Debug)]
+enum RemoteSyncSignal {
+    Synced(Option<String>),
+}
+
+#[derive(Clone, Debug)]
+enum PairingSignal {
+    Finished(Result<crate::domain::PairingOffer, String>),
+}
+
+#[derive(Clone, Debug)]
+enum DevicesSignal {
+    Loaded(Result<Vec<crate::domain::RelayDevice>, String>),
+    /// A revoke, restore or forget finished; `devices` is the list afterwards.
+    Changed {
+        devices: Option<Vec<crate::domain::RelayDevice>>,
+        notice: String,
+    },
+}
+
+#[derive(Clone, Debug)]
+enum LifecycleSignal {
+    WebhookFailed(String),
+    HookFailed(String),
+}
+
+/// The loaded config and the workers built from it, replaced when the file changes.
+struct LiveConfig {
+    path: Option<PathBuf>,
+    modified_at: Option<SystemTime>,
+    config: CcboxConfig,
+    artifact_cipher: Option<ArtifactCipher>,
+    webhook_events: Option<Sender<LifecycleEvent>>,
+    hook_events: Option<Sender<LifecycleEvent>>,
+    /// Set to stop the running task registry sync thread.
+    task_registry_stop: Option<Arc<AtomicBool>>,
+}
+
+impl LiveConfig {
+    /// Workers that receive lifecycle events; empty when neither webhooks nor hooks are set.
+    fn lifecycle_sinks(&self) -> Vec<&Sender<LifecycleEvent>> {
+        self.webhook_events
+            .iter()
+            .chain(self.hook_events.iter())
+            .collect()
+    }
+}
+
 #[derive(Clone, Debug)]
 struct SessionIndexRequest {
     sessions: Vec<crate::domain::SessionSummary>,
@@ -89,7 +160,11 @@ fn main() {
     if let Err(error) = run_main() {
         let mut err = io::stderr().lock();
         let _ = writeln!(err, "{error}");
-        std::process::exit(1);
+        let code = match &error {
+            MainError::Cli(error) => error.exit_code(),
+            _ => 1,
+        };
+        std::process::exit(code);
     }
 }
 
@@ -117,7 +192,16 @@ fn run_main() -> Result<(), MainError> {
             Ok(())
         }
         CliInvocation::Serve(opts) => Ok(crate::remote::run_serve(opts)?),
-        CliInvocation::Tui { engine } => Ok(run_tui(engine)?),
+        CliInvocation::Tui {
+            engine,
+            project,
+            profile_startup,
+            session,
+            item,
+        } => {
+            let start_session = session.map(|id| StartSession { id, item });
+            Ok(run_tui(engine, project, profile_startup, start_session)?)
+        }
         CliInvocation::Command(command) => {
             let sessions_dir = resolve_sessions_dir().map_err(app::AppError::from)?;
             crate::cli::run(command, &sessions_dir)?;
@@ -128,52 +212,252 @@ fn run_main() -> Result<(), MainError> {
 
 fn print_help() {
     let text = format!(
-        "{name} — manage coding-agent sessions (Codex + Claude + Gemini + OpenCode)\n\nUSAGE:\n  {name} [--engine ENGINE]                Start the TUI\n  {name} projects [--engine ENGINE]       List discovered projects\n  {name} sessions [project-path] [--engine ENGINE]  List sessions (defaults to current folder)\n  {name} history [log|project] [session-id] [--engine ENGINE]  Print timeline (defaults to latest for current folder)\n  {name} skills [log|project] [session-id] [--engine ENGINE] [--json] [--full]  Analyze skill spans (defaults to latest for current folder)\n  {name} serve                            Run as a remote CCBox behind NAT\n  {name} update                           Self-update from GitHub Releases (macOS/Linux)\n  {name} --help | --version\n\nENGINE:\n  --engine NAME  Filter by engine: all|codex|claude|gemini|opencode (default: all)\n\nSERVE FLAGS:\n  --label NAME         Optional label (max 64 chars)\n  --relay-domain DOM   Relay domain (default: ccbox.app)\n  --relay-url URL      Full wss:// URL (dev override)\n  --relay BASE         Relay base wss:// URL (connects to BASE/ccbox?guid=<ccbox_id>)\n  --pairing-code CODE  Optional pairing code override for QR URL\n  --no-relay           Run local mode (no public relay)\n  --listen ADDR        Local bind addr (default: 127.0.0.1:8787)\n  --enable-shell       Enable shell-v1 (disabled by default)\n  --print-identity     Print ccbox_id + public key then exit\n\nSESSIONS FLAGS:\n  --limit N      Max sessions to print (default: 10)\n  --offset N     Skip first N sessions (default: 0)\n  --size         Include file size bytes column\n\nHISTORY FLAGS:\n  --limit N      Max timeline items to print (default: 10)\n  --offset N     Skip first N timeline items to print (default: 0)\n  --id ID        Select a session id (positional session-id also supported)\n  --full         Include full details (tool outputs, long messages)\n  --size         Print stats to stderr (bytes + item counts)\n\nSKILLS FLAGS:\n  --id ID        Select a session id (positional session-id also supported)\n  --json         Output structured JSON\n  --full         Include per-span tool call summaries\n\nOUTPUT:\n  projects: project_name<TAB>project_path<TAB>session_count\n  sessions: started_at<TAB>session_id<TAB>title<TAB>log_path  (with --size adds file_size_bytes before log_path)\n\nENV:\n  CODEX_SESSIONS_DIR    Override Codex sessions dir (default: ~/.codex/sessions; Windows: %USERPROFILE%\\.codex\\sessions)\n  CLAUDE_PROJECTS_DIR   Override Claude projects dir (default: ~/.claude/projects)\n",
+        "{name} — manage coding-agent sessions (Codex + Claude + Gemini + OpenCode)\n\nUSAGE:\n  {name} [--engine ENGINE] [--project PATH] [--profile-startup]  Start the TUI (opens the project containing PATH or the current folder)\n  {name} tui [--session ID [--item N]]  Start the TUI on a session, with timeline item N selected\n  {name} projects [--engine ENGINE] [--format FMT]  List discovered projects\n  {name} sessions [project-path] [--engine ENGINE] [--format FMT]  List sessions (defaults to current folder)\n  {name} sessions --all [--engine ENGINE] [--format FMT]  List sessions of every project, newest first\n  {name} history [log|project] [session-id] [--engine ENGINE] [--follow]  Print timeline (defaults to latest for current folder)\n  {name} skills [log|project] [session-id] [--engine ENGINE] [--json] [--full]  Analyze skill spans (defaults to latest for current folder)\n  {name} skills --project [project-path] [--engine ENGINE] [--json]  Aggregate skill usage over every session of a project\n  {name} open [log|project] [session-id] [--engine ENGINE]  Open the raw session log in $VISUAL/$EDITOR\n  {name} result [session-id] [--project PATH] [--engine ENGINE]  Print the last assistant output (defaults to latest for current folder)\n  {name} diff <session-a> <session-b> [--engine ENGINE] [--full]  Compare prompts, tool calls and final output of two sessions\n  {name} serve                            Run as a remote CCBox behind NAT\n  {name} serve --port N                   Serve a local REST/JSON API on 127.0.0.1:N\n  {name} tasks export [--out FILE]       Export tasks (images embedded) as a JSON pack (default: stdout)\n  {name} tasks import FILE [--on-conflict skip|overwrite|duplicate] [--project PATH]  Import a task pack\n  {name} audit-log [--limit N] [--offset N] [--json]  Show recorded delete/rename/fork/kill/spawn actions (newest first)\n  {name} worktrees [list] [project-path]  List agent git worktrees (ccbox/<name> branches)\n  {name} worktrees merge NAME [--project PATH]  Merge ccbox/NAME into the current checkout\n  {name} worktrees remove NAME [--force] [--project PATH]  Remove the worktree and delete its branch\n  {name} run [--engine codex|claude] [--project PATH] PROMPT  Run an agent headless, stream its output, then print the session id and log path\n  {name} run [--engine codex|claude] [--project PATH] --prompt-file FILE|-  Same, with the prompt read from FILE (- = stdin)\n  {name} run [--engine codex|claude] --projects A,B,C PROMPT  Run the same prompt in several projects at once (output lines prefixed with [project])\n  {name} await PROCESS_ID [--timeout SECS]  Wait for a ccbox-spawned process (pN) to exit and exit with its code (124 on timeout)\n  {name} kill [--project PATH] [--yes]  Kill every running ccbox-spawned process of a project (asks first unless --yes)\n  {name} update [--channel stable|beta]   Self-update from GitHub Releases (macOS/Linux); keeps the previous binary\n  {name} update --rollback                Reinstall the binary the last update replaced\n  {name} --help | --version\n\nENGINE:\n  --engine NAME  Filter by engine: all|codex|claude|gemini|opencode (default: all)\n\nTUI FLAGS:\n  --project PATH     Open the project containing PATH\n  --profile-startup  Write startup timings (scan, index load, first draw) to ~/.ccbox/startup_profile.json\n  --session ID       (tui) Open this session (engine:id pins the engine)\n  --item N           (tui) Select timeline item N of --session, as numbered by history\n\nSERVE FLAGS:\n  --label NAME         Optional label (max 64 chars)\n  --relay-domain DOM   Relay domain (default: ccbox.app)\n  --relay-url URL      Full wss:// URL (dev override)\n  --relay BASE         Relay base wss:// URL (connects to BASE/ccbox?guid=<ccbox_id>)\n  --pairing-code CODE  Optional pairing code override for QR URL\n  --no-relay           Run local mode (no public relay)\n  --listen ADDR        Local bind addr (default: 127.0.0.1:8787)\n  --port N             Serve the local HTTP API on 127.0.0.1:N instead (no relay)\n  --enable-shell       Enable shell-v1 (disabled by default)\n  --print-identity     Print ccbox_id + public key then exit\n\nSESSIONS FLAGS:\n  --all, -a      Every project's sessions, newest first (adds a project_path column after started_at)\n  --limit N      Max sessions to print (default: 10)\n  --offset N     Skip first N sessions (default: 0)\n  --size         Include file size bytes column (same as adding size to --columns)\n  --columns LIST Extra columns before log_path, in order: size,items,duration,tokens (bytes, log records, ms, total tokens)\n  --format FMT   Output tsv (default), csv (with a header row) or json; projects accepts it too\n  --json         Same as --format json\n\nHISTORY FLAGS:\n  --limit N      Max timeline items to print (default: 10)\n  --offset N     Skip first N timeline items to print (default: 0)\n  --id ID        Select a session id (positional session-id also supported)\n  --full         Include full details (tool outputs, long messages)\n  --size         Print stats to stderr (bytes + item counts)\n  --follow, -f   Start at the last --limit items and keep printing new ones as the log grows\n  --item N       Print only timeline item N (the #N prefix of each line), in full\n\nSKILLS FLAGS:\n  --id ID        Select a session id (positional session-id also supported)\n  --json         Output structured JSON\n  --full         Include per-span tool call summaries\n  --project      Aggregate over all sessions of the project (path positional; no session id)\n\nDIFF FLAGS:\n  session-a/b    A session .jsonl path, a project directory (its latest session) or a session id (engine:id)\n  --full         Print whole prompts, tool arguments and outputs instead of their first line\n\nTASKS IMPORT FLAGS:\n  --on-conflict MODE  When a task id already exists: skip (default), overwrite, or duplicate\n  --project PATH      Assign imported tasks to PATH instead of the exported project\n\nOUTPUT:\n  projects: project_name<TAB>project_path<TAB>session_count\n  sessions: started_at<TAB>session_id<TAB>title<TAB>log_path  (--size/--columns add file_size_bytes, item_count, duration_ms, total_tokens before log_path)\n  audit-log: at<TAB>source<TAB>actor<TAB>device_id<TAB>action<TAB>target<TAB>detail\n\nENV:\n  CODEX_SESSIONS_DIR    Override Codex sessions dir (default: ~/.codex/sessions; Windows: %USERPROFILE%\\.codex\\sessions)\n  CLAUDE_PROJECTS_DIR   Override Claude projects dir (default: ~/.claude/projects)\n",
         name = env!("CARGO_PKG_NAME")
     );
     let mut out = io::stdout().lock();
     let _ = write!(out, "{text}");
 }
 
-fn run_tui(engine: Option<crate::domain::SessionEngine>) -> Result<(), crate::app::AppError> {
+/// `ccbox tui --session ID [--item N]`: the session (and 1-based timeline item) to open once the
+/// startup scan is in.
+struct StartSession {
+    id: String,
+    item: Option<usize>,
+}
+
+fn run_tui(
+    engine: Option<crate::domain::SessionEngine>,
+    project: Option<PathBuf>,
+    profile_startup: bool,
+    start_session: Option<StartSession>,
+) -> Result<(), crate::app::AppError> {
+    let mut profiler = profile_startup.then(StartupProfiler::start);
+    let mut mark = |name: &str| {
+        if let Some(profiler) = profiler.as_mut() {
+            profiler.mark(name);
+        }
+    };
     let sessions_dir = resolve_sessions_dir()?;
-    let scan = scan_all_sessions(&sessions_dir);
-    let initial_data =
-        app::build_index_from_sessions(sessions_dir.clone(), scan.sessions, scan.warnings);
-    let mut model = AppModel::new(initial_data).with_notice(scan.notice);
+    mark("resolve_sessions_dir");
+    // The scan runs in the background (see `spawn_startup_scan`); projects stream in as each
+    // engine finishes.
+    let mut initial_data = app::build_index_from_sessions(
+        sessions_dir,
+        Vec::new(),
+        crate::infra::ScanWarningCount::from(0usize),
+    );
+    initial_data.scan_status = Some(crate::app::ScanStatus::default());
+    let mut model = AppModel::new(initial_data);
+    if let Ok(state_dir) = resolve_ccbox_state_dir() {
+        match load_engine_filters(&state_dir) {
+            Ok(prefs) => model = model.with_engine_prefs(prefs),
+            Err(error) => {
+                model = model.with_notice(Some(format!("Engine filters reset: {error}")));
+            }
+        }
+        match load_project_prefs(&state_dir) {
+            Ok(prefs) => model = model.with_project_prefs(prefs),
+            Err(error) => {
+                model = model.with_notice(Some(format!("Project names reset: {error}")));
+            }
+        }
+    }
     if let Some(engine) = engine {
-        let filter = match engine {
-            crate::domain::SessionEngine::Codex => crate::app::EngineFilter::Codex,
-            crate::domain::SessionEngine::Claude => crate::app::EngineFilter::Claude,
-            crate::domain::SessionEngine::Gemini => crate::app::EngineFilter::Gemini,
-            crate::domain::SessionEngine::OpenCode => crate::app::EngineFilter::OpenCode,
-        };
-        model = model.with_engine_filter(filter);
+        model = model.with_engine_filter(crate::app::EngineFilter::from_engine(Some(engine)));
     }
+    mark("load_preferences");
     let mut terminal = setup_terminal()?;
     if let Ok((width, height)) = terminal_size() {
         model = model.with_terminal_size(width, height);
     }
-    let result = run(&mut terminal, &mut model);
+    mark("terminal_setup");
+    let result = run(&mut terminal, &mut model, profiler, project, start_session);
     restore_terminal(&mut terminal)?;
     result
 }
 
+/// Scans every engine on a background thread and sends the result as a regular `Scanned`
+/// signal. The returned progress lists each engine's sessions as soon as that engine is done.
+fn spawn_startup_scan(
+    sessions_dir: PathBuf,
+    tx: Sender<SessionsDirScanSignal>,
+) -> Arc<ScanProgress> {
+    let progress = Arc::new(ScanProgress::default());
+    let worker_progress = progress.clone();
+    std::thread::spawn(move || {
+        let output = scan_all_sessions_with_progress(&sessions_dir, &worker_progress);
+        let data =
+            app::build_index_from_sessions(sessions_dir.clone(), output.sessions, output.warnings);
+        let _ = tx.send(SessionsDirScanSignal::Scanned {
+            data,
+            notice: output.notice,
+        });
+    });
+    progress
+}
+
+/// Updates the scanning indicator and, when another engine has finished, lists the sessions
+/// found so far. Does nothing once a full scan has replaced the partial data.
+fn apply_startup_scan_progress(model: &mut AppModel, progress: &ScanProgress) {
+    let Some(status) = model.data.scan_status.as_ref() else {
+        return;
+    };
+    let (done, total) = progress.snapshot();
+    let engines_done = progress.finished_engines();
+    let has_new_engines = engines_done.len() > status.engines_done.len();
+    let status = crate::app::ScanStatus {
+        done,
+        total,
+        engines_done,
+    };
+    if has_new_engines {
+        let mut data = app::build_index_from_sessions(
+            model.data.sessions_dir.clone(),
+            progress.finished_sessions(),
+            model.data.warnings,
+        );
+        data.scan_status = Some(status);
+        *model = model.with_data(data);
+    } else {
+        model.data.scan_status = Some(status);
+    }
+}
+
+fn write_startup_profile(model: &mut AppModel, profiler: &StartupProfiler) {
+    let session_count = model
+        .data
+        .projects
+        .iter()
+        .map(|project| project.sessions.len())
+        .sum();
+    let result = resolve_ccbox_state_dir()
+        .map_err(|error| error.to_string())
+        .and_then(|state_dir| {
+            save_startup_profile(
+                &state_dir,
+                profiler,
+                model.data.projects.len(),
+                session_count,
+            )
+            .map_err(|error| error.to_string())
+        });
+    let notice = match result {
+        Ok(path) => format!("Startup profile written to {}", path.display()),
+        Err(error) => format!("Failed to write startup profile: {error}"),
+    };
+    *model = model.with_notice(Some(notice));
+}
+
+/// Opens the project containing `--project` (or the current folder) when it is a known one; an
+/// explicit path that matches no project leaves the Projects list open with a notice.
+fn open_start_project(model: AppModel, project: Option<PathBuf>) -> AppModel {
+    let cwd = std::env::current_dir().ok();
+    let explicit = project.is_some();
+    let Some(dir) = project
+        .map(|path| match &cwd {
+            Some(cwd) => cwd.join(path),
+            None => path,
+        })
+        .or(cwd)
+    else {
+        return model;
+    };
+    let canonical = dir.canonicalize().ok();
+    let opened = model.with_project_for_dir(&dir).or_else(|| {
+        canonical
+            .as_deref()
+            .and_then(|canonical| model.with_project_for_dir(canonical))
+    });
+    match opened {
+        Some(opened) => opened,
+        None if explicit => model.with_notice(Some(format!(
+            "No sessions found for project: {}",
+            dir.display()
+        ))),
+        None => model,
+    }
+}
+
+/// Opens the `tui --session` session and selects its `--item`. A linked item older than the
+/// newest page loads every item from it onwards, so the selection lands on it directly.
+fn open_start_session(model: AppModel, start: StartSession) -> AppModel {
+    let (id, engine) = match crate::domain::SessionKey::parse(&start.id) {
+        Some(key) => (key.id, Some(key.engine)),
+        None => (start.id.clone(), None),
+    };
+    let found = model.data.projects.iter().find_map(|project| {
+        let session = project.sessions.iter().find(|session| {
+            session.meta.id == id && engine.is_none_or(|engine| session.engine == engine)
+        })?;
+        Some((
+            project.project_path.clone(),
+            project.sessions.len(),
+            session.clone(),
+        ))
+    });
+    let Some((project_path, session_count, mut session)) = found else {
+        return model.with_notice(Some(format!("Session not found: {}", start.id)));
+    };
+    let timeline = match crate::infra::prepare_session_log_path(&session)
+        .map_err(|error| error.to_string())
+        .and_then(|log_path| {
+            session.log_path = log_path;
+            load_session_timeline(&session.log_path).map_err(|error| error.to_string())
+        }) {
+        Ok(timeline) => timeline,
+        Err(error) => return model.with_notice(Some(format!("Failed to load session: {error}"))),
+    };
+
+    let total = timeline.items.len();
+    let index = start.item.map(|item| item - 1);
+    let page = index.map_or(crate::domain::TIMELINE_PAGE_ITEMS, |index| {
+        crate::domain::TIMELINE_PAGE_ITEMS.max(total.saturating_sub(index))
+    });
+    let timeline = timeline.into_newest_page(page);
+    let mut model = model.open_session_detail(
+        crate::app::SessionsView::new(project_path, session_count),
+        session,
+        timeline.items,
+        timeline.turn_contexts,
+        timeline.warnings,
+        timeline.truncated,
+    );
+    let older_count = timeline.older.len();
+    set_session_detail_older_items(&mut model, timeline.older);
+    match index {
+        Some(index) if index >= total => model.with_notice(Some(format!(
+            "Item {} is out of range: the session has {total} timeline items.",
+            index + 1
+        ))),
+        Some(index) => {
+            if let crate::app::View::SessionDetail(view) = &mut model.view {
+                view.selected = index - older_count;
+            }
+            model
+        }
+        None => model,
+    }
+}
+
 fn setup_terminal() -> Result<Terminal<CrosstermBackend<Stdout>>, app::AppError> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     stdout.execute(EnterAlternateScreen)?;
     let _ = stdout.execute(EnableBracketedPaste);
     let _ = stdout.execute(EnableMouseCapture);
-    let keyboard_flags = KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES
-        | KeyboardEnhancementFlags::REPORT_ALL_KEYS_AS_ESCAPE_CODES
-        | KeyboardEnhancementFlags::REPORT_ALTERNATE_KEYS
-        | KeyboardEnhancementFlags::REPORT_EVENT_TYPES;
-    let _ = stdout.execute(PushKeyboardEnhancementFlags(keyboard_flags));
+    let _ = stdout.execute(PushKeyboardEnhancementFlags(tui_keyboard_flags()));
     let backend = CrosstermBackend::new(stdout);
     Ok(Terminal::new(backend)?)
 }
 
+fn tui_keyboard_flags() -> KeyboardEnhancementFlags {
+    KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES
+        | KeyboardEnhancementFlags::REPORT_ALL_KEYS_AS_ESCAPE_CODES
+        | KeyboardEnhancementFlags::REPORT_ALTERNATE_KEYS
+        | KeyboardEnhancementFlags::REPORT_EVENT_TYPES
+}
+
 fn restore_terminal(
     terminal: &mut Terminal<CrosstermBackend<Stdout>>,
 ) -> Result<(), app::AppError> {
@@ -192,6 +476,9 @@ fn restore_terminal(
 fn run(
     terminal: &mut Terminal<CrosstermBackend<Stdout>>,
     model: &mut AppModel,
+    mut profiler: Option<StartupProfiler>,
+    start_project: Option<PathBuf>,
+    mut start_session: Option<StartSession>,
 ) -> Result<(), app::AppError> {
     let (update_tx, update_rx) = channel::<UpdateSignal>();
     spawn_update_check(update_tx);
@@ -219,20 +506,37 @@ fn run(
             (None, None)
         }
     };
+    if let Some(profiler) = profiler.as_mut() {
+        profiler.mark("index_load");
+    }
+
+    let config_path = resolve_ccbox_config_path().ok();
+    let config_modified_at = config_path.as_deref().and_then(file_modified_at);
+    let config = match config_path.as_deref().map(load_ccbox_config) {
+        Some(Ok(config)) => config,
+        Some(Err(error)) => {
+            *model = model.with_notice(Some(format!("Config ignored: {error}")));
+            CcboxConfig::default()
+        }
+        None => CcboxConfig::default(),
+    };
+    // Off in the config for setups where watcher event storms keep rescanning.
+    let is_watching = !config.rescan.disable_watchers;
 
-    let codex_watcher = match watch_sessions_dir(&model.data.sessions_dir) {
-        Ok(watcher) => Some(watcher),
-        Err(error) => {
+    let codex_watcher = match is_watching.then(|| watch_sessions_dir(&model.data.sessions_dir)) {
+        Some(Ok(watcher)) => Some(watcher),
+        Some(Err(error)) => {
             *model = model.with_notice(Some(format!(
                 "Auto-rescan disabled: {error} (Ctrl+R to rescan)"
             )));
             None
         }
+        None => None,
     };
 
     let claude_watcher = match resolve_claude_projects_dir() {
         Ok(dir) => {
-            if dir.exists() {
+            if is_watching && dir.exists() {
                 match watch_sessions_dir(&dir) {
                     Ok(watcher) => Some(watcher),
                     Err(error) => {
@@ -252,7 +556,7 @@ fn run(
     let gemini_watcher = match resolve_gemini_root_dir() {
         Ok(root) => {
             let tmp_dir = root.join("tmp");
-            if tmp_dir.exists() {
+            if is_watching && tmp_dir.exists() {
                 match watch_sessions_dir(&tmp_dir) {
                     Ok(watcher) => Some(watcher),
                     Err(error) => {
@@ -271,7 +575,7 @@ fn run(
 
     let opencode_watcher = match resolve_opencode_db_path() {
         Ok(db_path) => {
-            if db_path.is_file() {
+            if is_watching && db_path.is_file() {
                 match watch_sqlite_db_family(&db_path) {
                     Ok(watcher) => watcher,
                     Err(error) => {
@@ -287,10 +591,19 @@ fn run(
         }
         Err(ResolveOpenCodeDbPathError::HomeDirNotFound) => None,
     };
+    let extra_watchers = if is_watching {
+        watch_extra_session_dirs(model, &config)
+    } else {
+        Vec::new()
+    };
     let (sessions_scan_tx, sessions_scan_rx) = channel::<SessionsDirScanSignal>();
-    let mut sessions_scan_in_flight = false;
-    let debounce = Duration::from_millis(900);
-    let max_delay = Duration::from_secs(5);
+    let mut startup_scan = Some(spawn_startup_scan(
+        model.data.sessions_dir.clone(),
+        sessions_scan_tx.clone(),
+    ));
+    let mut start_project = Some(start_project);
+    let mut first_drawn = false;
+    let mut sessions_scan_in_flight = true;
     let mut pending_rescan = false;
     let mut first_change_at: Option<Instant> = None;
     let mut rescan_deadline: Option<Instant> = None;
@@ -314,8 +627,223 @@ fn run(
             None
         }
     };
+    if let Some(manager) = process_manager.as_mut() {
+        manager.set_process_limits(config.process_limits.clone());
+        manager.set_spawn_args(config.spawn_args.clone());
+        manager.set_project_env(config.project_env.clone());
+        manager.set_tty_scrollback_bytes(config.tty_scrollback_bytes());
+        manager.set_kill_grace(config.kill_grace());
+        match manager.restore_processes() {
+            Ok(restored) => model
+                .processes
+                .extend(restored.into_iter().map(restored_process_info)),
+            Err(error) => {
+                *model = model.with_notice(Some(format!("Failed to restore processes: {error}")));
+            }
+        }
+    }
+    model.list_columns = config.list_columns.clone();
+    model.engine_models = config.models.clone();
+    model.spawn_args = config.spawn_args.clone();
+    let artifact_cipher = match ArtifactCipher::from_config(&config.encryption) {
+        Ok(cipher) => cipher,
+        Err(error) => {
+            *model = model.with_notice(Some(format!("Encryption disabled: {error}")));
+            None
+        }
+    };
+    if let Some(manager) = process_manager.as_mut() {
+        manager.set_artifact_cipher(artifact_cipher.clone());
+    }
+
+    let mut clipboard = SystemClipboard::default();
+
+    let (lifecycle_tx, lifecycle_rx) = channel::<LifecycleSignal>();
+    let webhook_events = spawn_webhook_worker(config.webhooks.clone(), lifecycle_tx.clone());
+    let hook_events = spawn_hook_worker(config.hooks.clone(), lifecycle_tx.clone());
+    // Restored processes are not news to webhooks and hooks.
+    let mut lifecycle_process_status: HashMap<String, crate::app::ProcessStatus> = model
+        .processes
+        .iter()
+        .map(|process| (process.id.clone(), process.status.clone()))
+        .collect();
+    let mut lifecycle_waiting_processes: HashSet<String> = HashSet::new();
+    let mut opened_comparisons: HashSet<u32> = HashSet::new();
+
+    let (task_registry_tx, task_registry_rx) = channel::<TaskRegistrySignal>();
+    let (pairing_tx, pairing_rx) = channel::<PairingSignal>();
+    let (devices_tx, devices_rx) = channel::<DevicesSignal>();
+    let (remote_sync_tx, remote_sync_rx) = channel::<RemoteSyncSignal>();
+    let mut remote_sync_in_flight = false;
+    let mut next_remote_sync_at = Instant::now();
+    let (metadata_sync_tx, metadata_sync_rx) = channel::<MetadataSyncSignal>();
+    if let Some(sync_config) = config.metadata_sync.clone() {
+        spawn_metadata_sync(sync_config, metadata_sync_tx);
+    }
+    let task_registry_stop =
+        restart_task_registry_sync(None, config.task_registry.clone(), task_registry_tx.clone());
+
+    let mut live_config = LiveConfig {
+        path: config_path,
+        modified_at: config_modified_at,
+        config,
+        artifact_cipher,
+        webhook_events,
+        hook_events,
+        task_registry_stop,
+    };
+    let metrics = Arc::new(Metrics::default());
+    record_scan_metrics(&metrics, &model.data);
+    metrics.set_tokens_indexed(model.session_index.tokens_indexed());
+    if let Some(metrics_config) = &live_config.config.metrics
+        && let Err(error) = start_metrics_server(metrics_config, metrics.clone())
+    {
+        *model = model.with_notice(Some(format!(
+            "Metrics disabled ({}): {error}",
+            metrics_config.listen
+        )));
+    }
+
+    let config_check_interval = Duration::from_secs(2);
+    let mut next_config_check_at = Instant::now() + config_check_interval;
+    let mut next_periodic_rescan_at =
+        Instant::now() + live_config.config.rescan.interval().unwrap_or_default();
+    let mut saved_engine_prefs = model.engine_prefs.clone();
+    let mut saved_project_prefs = model.project_prefs.clone();
+
+    let task_schedule_check_interval = Duration::from_secs(10);
+    let mut next_task_schedule_check_at = Instant::now();
+    let process_plan_refresh_interval = Duration::from_secs(2);
+    let mut next_process_plan_refresh_at = Instant::now();
+    let mut process_plan_offsets: HashMap<PathBuf, u64> = HashMap::new();
+    // Transcript sizes at the last check, per TTY process id.
+    let mut tty_transcript_sizes: HashMap<String, u64> = HashMap::new();
+    let process_usage_interval = Duration::from_secs(1);
+    let mut next_process_usage_at = Instant::now();
+    let external_scan_interval = Duration::from_secs(3);
+    let mut next_external_scan_at = Instant::now();
+    let activity_refresh_interval = Duration::from_secs(1);
+    let mut next_activity_refresh_at = Instant::now();
+    let mut process_usage_sampler = ProcessUsageSampler::new();
 
     loop {
+        while let Ok(signal) = task_registry_rx.try_recv() {
+            match signal {
+                TaskRegistrySignal::Synced(Ok(_count)) => {
+                    if let crate::app::View::Tasks(tasks_view) = &model.view
+                        && let Ok(store) = TaskStore::open_default()
+                        && let Ok(tasks) = store.list_tasks()
+                    {
+                        model.view = crate::app::View::Tasks(
+                            tasks_view
+                                .clone()
+                                .with_reloaded_tasks(task_summary_rows(tasks)),
+                        );
+                    }
+                }
+                TaskRegistrySignal::Synced(Err(error)) => {
+                    *model = model.with_notice(Some(format!("Team tasks sync failed: {error}")));
+                }
+            }
+        }
+
+        while let Ok(RemoteSyncSignal::Synced(notice)) = remote_sync_rx.try_recv() {
+            remote_sync_in_flight = false;
+            if let Some(notice) = notice {
+                *model = model.with_notice(Some(notice));
+            }
+            let now = Instant::now();
+            pending_rescan = true;
+            rescan_deadline = Some(now);
+            first_change_at.get_or_insert(now);
+        }
+
+        if !remote_sync_in_flight
+            && !live_config.config.remote_sources.is_empty()
+            && Instant::now() >= next_remote_sync_at
+        {
+            next_remote_sync_at = Instant::now() + REMOTE_SYNC_INTERVAL;
+            remote_sync_in_flight = spawn_remote_sync(
+                live_config.config.remote_sources.clone(),
+                remote_sync_tx.clone(),
+            );
+        }
+
+        while let Ok(signal) = metadata_sync_rx.try_recv() {
+            match signal {
+                MetadataSyncSignal::Synced(Ok(0)) => {}
+                MetadataSyncSignal::Synced(Ok(_applied)) => {
+                    if let Ok(state_dir) = resolve_ccbox_state_dir()
+                        && let Ok(prefs) = load_project_prefs(&state_dir)
+                    {
+                        saved_project_prefs = prefs.clone();
+                        *model = model.with_project_prefs(prefs);
+                    }
+                    if let crate::app::View::Tasks(tasks_view) = &model.view
+                        && let Ok(store) = TaskStore::open_default()
+                        && let Ok(tasks) = store.list_tasks()
+                    {
+                        model.view = crate::app::View::Tasks(
+                            tasks_view
+                                .clone()
+                                .with_reloaded_tasks(task_summary_rows(tasks)),
+                        );
+                    }
+                    let now = Instant::now();
+                    pending_rescan = true;
+                    rescan_deadline = Some(now);
+                    first_change_at.get_or_insert(now);
+                }
+                MetadataSyncSignal::Synced(Err(error)) => {
+                    *model = model.with_notice(Some(format!("Metadata sync failed: {error}")));
+                }
+            }
+        }
+
+        while let Ok(signal) = pairing_rx.try_recv() {
+            match signal {
+                PairingSignal::Finished(result) => {
+                    if let Some(overlay) = model.devices_overlay.as_mut()
+                        && overlay.pairing == Some(crate::app::PairingState::Requesting)
+                    {
+                        overlay.pairing = Some(match result {
+                            Ok(offer) => crate::app::PairingState::Ready(offer),
+                            Err(error) => crate::app::PairingState::Failed(error),
+                        });
+                    }
+                }
+            }
+        }
+
+        while let Ok(signal) = devices_rx.try_recv() {
+            match signal {
+                DevicesSignal::Loaded(Ok(devices)) => show_devices(model, devices),
+                DevicesSignal::Loaded(Err(error)) => {
+                    if let Some(overlay) = model.devices_overlay.as_mut() {
+                        overlay.loading = false;
+                    }
+                    *model = model.with_notice(Some(format!("Failed to load devices: {error}")));
+                }
+                DevicesSignal::Changed { devices, notice } => {
+                    if let Some(devices) = devices {
+                        show_devices(model, devices);
+                    }
+                    *model = model.with_notice(Some(notice));
+                }
+            }
+        }
+
+        while let Ok(signal) = lifecycle_rx.try_recv() {
+            match signal {
+                LifecycleSignal::WebhookFailed(error) => {
+                    *model = model.with_notice(Some(format!("Webhook failed: {error}")));
+                }
+                LifecycleSignal::HookFailed(error) => {
+                    *model = model.with_notice(Some(format!("Hook failed: {error}")));
+                }
+            }
+        }
+
         while let Ok(signal) = update_rx.try_recv() {
             match signal {
                 UpdateSignal::UpdateAvailable { latest_tag } => {
@@ -331,6 +859,25 @@ fn run(
             match signal {
                 SessionsDirScanSignal::Scanned { data, notice } => {
                     sessions_scan_in_flight = false;
+                    record_scan_metrics(&metrics, &data);
+                    if startup_scan.take().is_some() {
+                        if let Some(profiler) = profiler.as_mut() {
+                            profiler.mark("scan");
+                        }
+                        let prior_notice = model.notice.clone();
+                        *model = model.with_data(data).with_notice(prior_notice.or(notice));
+                        if let Some(project) = start_project.take()
+                            && matches!(model.view, crate::app::View::Projects(_))
+                        {
+                            *model = open_start_project(model.clone(), project);
+                        }
+                        if let Some(start) = start_session.take() {
+                            *model = open_start_session(model.clone(), start);
+                        }
+                        request_session_index_refresh_optional(&session_index_req_tx, model);
+                        continue;
+                    }
+                    emit_session_events(&model.data, &data, &live_config.lifecycle_sinks());
                     let prior_notice = model.notice.clone();
                     let updated = model.with_data(data);
                     let next_notice = prior_notice
@@ -347,6 +894,7 @@ fn run(
             while let Ok(signal) = rx.try_recv() {
                 match signal {
                     SessionIndexSignal::Updated { index } => {
+                        metrics.set_tokens_indexed(index.tokens_indexed());
                         *model = model.with_session_index(index);
                         refresh_open_project_stats_overlay(model);
                     }
@@ -380,20 +928,26 @@ fn run(
             }
         }
 
-        ensure_session_detail_watcher(
-            model,
-            &mut session_detail_watcher,
-            &mut session_detail_watcher_path,
-            &mut pending_session_detail_reload,
-            &mut session_detail_first_change_at,
-            &mut session_detail_reload_deadline,
-            &mut session_detail_reload_in_flight_for,
-        );
+        if live_config.config.rescan.disable_watchers {
+            session_detail_watcher = None;
+            session_detail_watcher_path = None;
+        } else {
+            ensure_session_detail_watcher(
+                model,
+                &mut session_detail_watcher,
+                &mut session_detail_watcher_path,
+                &mut pending_session_detail_reload,
+                &mut session_detail_first_change_at,
+                &mut session_detail_reload_deadline,
+                &mut session_detail_reload_in_flight_for,
+            );
+        }
 
         if let Some(watcher) = &session_detail_watcher {
             while let Some(signal) = watcher.try_recv() {
                 match signal {
                     WatchSignal::Changed => {
+                        metrics.record_watcher_event();
                         let now = Instant::now();
                         pending_session_detail_reload = true;
                         session_detail_reload_deadline = Some(now + session_detail_debounce);
@@ -451,9 +1005,10 @@ fn run(
             while let Some(signal) = watcher.try_recv() {
                 match signal {
                     WatchSignal::Changed => {
+                        metrics.record_watcher_event();
                         let now = Instant::now();
                         pending_rescan = true;
-                        rescan_deadline = Some(now + debounce);
+                        rescan_deadline = Some(now + live_config.config.rescan.debounce());
                         if first_change_at.is_none() {
                             first_change_at = Some(now);
                         }
@@ -469,9 +1024,10 @@ fn run(
             while let Some(signal) = watcher.try_recv() {
                 match signal {
                     WatchSignal::Changed => {
+                        metrics.record_watcher_event();
                         let now = Instant::now();
                         pending_rescan = true;
-                        rescan_deadline = Some(now + debounce);
+                        rescan_deadline = Some(now + live_config.config.rescan.debounce());
                         if first_change_at.is_none() {
                             first_change_at = Some(now);
                         }
@@ -488,9 +1044,10 @@ fn run(
             while let Some(signal) = watcher.try_recv() {
                 match signal {
                     WatchSignal::Changed => {
+                        metrics.record_watcher_event();
                         let now = Instant::now();
                         pending_rescan = true;
-                        rescan_deadline = Some(now + debounce);
+                        rescan_deadline = Some(now + live_config.config.rescan.debounce());
                         if first_change_at.is_none() {
                             first_change_at = Some(now);
                         }
@@ -507,9 +1064,10 @@ fn run(
             while let Some(signal) = watcher.try_recv() {
                 match signal {
                     WatchSignal::Changed => {
+                        metrics.record_watcher_event();
                         let now = Instant::now();
                         pending_rescan = true;
-                        rescan_deadline = Some(now + debounce);
+                        rescan_deadline = Some(now + live_config.config.rescan.debounce());
                         if first_change_at.is_none() {
                             first_change_at = Some(now);
                         }
@@ -534,21 +1092,129 @@ fn run(
             }
         }
 
+        for watcher in &extra_watchers {
+            while let Some(signal) = watcher.try_recv() {
+                match signal {
+                    WatchSignal::Changed => {
+                        metrics.record_watcher_event();
+                        let now = Instant::now();
+                        pending_rescan = true;
+                        rescan_deadline = Some(now + live_config.config.rescan.debounce());
+                        if first_change_at.is_none() {
+                            first_change_at = Some(now);
+                        }
+                    }
+                    WatchSignal::Error(message) => {
+                        *model = model.with_notice(Some(format!("Watcher error: {message}")));
+                    }
+                }
+            }
+        }
+
         while let Ok(signal) = process_rx.try_recv() {
-            apply_process_signal(model, signal);
+            apply_process_signal(model, signal, live_config.config.open_spawned_sessions);
         }
 
         if let Some(manager) = process_manager.as_mut() {
             for exit in manager.poll_exits() {
                 apply_process_exit(model, exit);
             }
+            for retry in manager.poll_retries() {
+                apply_process_retry(model, retry);
+            }
         }
+        open_finished_comparisons(model, &mut opened_comparisons);
+        emit_process_events(
+            model,
+            &mut lifecycle_process_status,
+            &mut lifecycle_waiting_processes,
+            &live_config.lifecycle_sinks(),
+        );
+        metrics.set_running_processes(
+            model
+                .processes
+                .iter()
+                .filter(|process| process.status.is_running())
+                .count(),
+        );
 
         refresh_process_output_view(model);
 
+        if Instant::now() >= next_process_usage_at {
+            next_process_usage_at = Instant::now() + process_usage_interval;
+            refresh_process_usage(model, &mut process_usage_sampler);
+        }
+
+        // Only scanned while Processes or Activity is open; opening either scans right away.
+        if !matches!(
+            model.view,
+            crate::app::View::Processes(_) | crate::app::View::Activity(_)
+        ) {
+            next_external_scan_at = Instant::now();
+        } else if Instant::now() >= next_external_scan_at {
+            next_external_scan_at = Instant::now() + external_scan_interval;
+            refresh_external_processes(model);
+            next_activity_refresh_at = Instant::now();
+        }
+
+        if let crate::app::View::Activity(view) = &mut model.view {
+            if Instant::now() >= next_activity_refresh_at {
+                next_activity_refresh_at = Instant::now() + activity_refresh_interval;
+                view.refresh(
+                    &model.processes,
+                    &model.external_processes,
+                    &model.data.projects,
+                    SystemTime::now(),
+                );
+            }
+        } else {
+            next_activity_refresh_at = Instant::now();
+        }
+
+        if Instant::now() >= next_process_plan_refresh_at {
+            next_process_plan_refresh_at = Instant::now() + process_plan_refresh_interval;
+            refresh_process_session_state(model, &mut process_plan_offsets);
+            refresh_tty_input_waits(model, &mut tty_transcript_sizes);
+        }
+
+        if Instant::now() >= next_config_check_at {
+            next_config_check_at = Instant::now() + config_check_interval;
+            if live_config.path.as_deref().and_then(file_modified_at) != live_config.modified_at {
+                reload_config(
+                    model,
+                    &mut live_config,
+                    process_manager.as_mut(),
+                    &lifecycle_tx,
+                    &task_registry_tx,
+                    false,
+                );
+            }
+        }
+
+        if Instant::now() >= next_task_schedule_check_at {
+            next_task_schedule_check_at = Instant::now() + task_schedule_check_interval;
+            if let Some(manager) = process_manager.as_mut() {
+                run_due_task_schedules(model, manager);
+            }
+        }
+
+        if let Some(interval) = live_config.config.rescan.interval()
+            && Instant::now() >= next_periodic_rescan_at
+        {
+            let now = Instant::now();
+            next_periodic_rescan_at = now + interval;
+            pending_rescan = true;
+            rescan_deadline = Some(now);
+            if matches!(model.view, crate::app::View::SessionDetail(_)) {
+                pending_session_detail_reload = true;
+                session_detail_reload_deadline = Some(now);
+            }
+        }
+
         if pending_rescan && !sessions_scan_in_flight {
             let now = Instant::now();
             let due_by_debounce = rescan_deadline.is_some_and(|due| now >= due);
+            let max_delay = live_config.config.rescan.max_delay();
             let due_by_max_delay =
                 first_change_at.is_some_and(|first| now.duration_since(first) >= max_delay);
             if due_by_debounce || due_by_max_delay {
@@ -573,8 +1239,49 @@ fn run(
             }
         }
 
+        if model.engine_prefs != saved_engine_prefs {
+            saved_engine_prefs = model.engine_prefs.clone();
+            if let Err(error) = resolve_ccbox_state_dir()
+                .map_err(|error| error.to_string())
+                .and_then(|state_dir| {
+                    save_engine_filters(&state_dir, &saved_engine_prefs)
+                        .map_err(|error| error.to_string())
+                })
+            {
+                *model = model.with_notice(Some(format!("Failed to save engine filters: {error}")));
+            }
+        }
+
+        if model.project_prefs != saved_project_prefs {
+            saved_project_prefs = model.project_prefs.clone();
+            if let Err(error) = resolve_ccbox_state_dir()
+                .map_err(|error| error.to_string())
+                .and_then(|state_dir| {
+                    save_project_prefs(&state_dir, &saved_project_prefs)
+                        .map_err(|error| error.to_string())
+                })
+            {
+                *model = model.with_notice(Some(format!("Failed to save project names: {error}")));
+            }
+        }
+
+        if let Some(progress) = &startup_scan {
+            apply_startup_scan_progress(model, progress);
+        }
+
         ui::clamp_scroll_state(model);
         terminal.draw(|frame| ui::render(frame, model))?;
+        if !first_drawn {
+            first_drawn = true;
+            if let Some(profiler) = profiler.as_mut() {
+                profiler.mark("first_draw");
+            }
+        }
+        if startup_scan.is_none()
+            && let Some(profiler) = profiler.take()
+        {
+            write_startup_profile(model, &profiler);
+        }
 
         if event::poll(Duration::from_millis(200))? {
             match event::read()? {
@@ -588,6 +1295,8 @@ fn run(
                         AppCommand::None => {}
                         AppCommand::Quit => return Ok(()),
                         AppCommand::Rescan => {
+                            // Remote mirrors refresh in the background and rescan again.
+                            next_remote_sync_at = Instant::now();
                             let sessions_dir = model.data.sessions_dir.clone();
                             let output = scan_all_sessions(&sessions_dir);
                             let new_data = app::build_index_from_sessions(
@@ -596,10 +1305,31 @@ fn run(
                                 output.warnings,
                             );
                             let notice = model.notice.clone().or(output.notice);
+                            record_scan_metrics(&metrics, &new_data);
+                            emit_session_events(
+                                &model.data,
+                                &new_data,
+                                &live_config.lifecycle_sinks(),
+                            );
                             *model = model.with_data(new_data).with_notice(notice);
                             refresh_open_project_stats_overlay(model);
                             request_session_index_refresh_optional(&session_index_req_tx, model);
                         }
+                        AppCommand::RefreshPathPicker => {
+                            if let Some(picker) = model.path_picker_mut() {
+                                picker.completion = complete_dir_path(&picker.editor.text);
+                            }
+                        }
+                        AppCommand::ReloadConfig => {
+                            reload_config(
+                                model,
+                                &mut live_config,
+                                process_manager.as_mut(),
+                                &lifecycle_tx,
+                                &task_registry_tx,
+                                true,
+                            );
+                        }
                         AppCommand::OpenTasks { return_to } => {
                             let store = match TaskStore::open_default() {
                                 Ok(store) => store,
@@ -621,16 +1351,7 @@ fn run(
                                 }
                             };
 
-                            let summaries = tasks
-                                .into_iter()
-                                .map(|entry| crate::app::TaskSummaryRow {
-                                    id: entry.task.id,
-                                    title: derive_task_title(&entry.task.body),
-                                    project_path: entry.task.project_path,
-                                    updated_at: entry.task.updated_at,
-                                    image_count: entry.image_count,
-                                })
-                                .collect::<Vec<_>>();
+                            let summaries = task_summary_rows(tasks);
 
                             model.view = crate::app::View::Tasks(crate::app::TasksView::new(
                                 return_to, summaries,
@@ -662,16 +1383,7 @@ fn run(
                                 }
                             };
 
-                            let summaries = tasks
-                                .into_iter()
-                                .map(|entry| crate::app::TaskSummaryRow {
-                                    id: entry.task.id,
-                                    title: derive_task_title(&entry.task.body),
-                                    project_path: entry.task.project_path,
-                                    updated_at: entry.task.updated_at,
-                                    image_count: entry.image_count,
-                                })
-                                .collect::<Vec<_>>();
+                            let summaries = task_summary_rows(tasks);
 
                             let tasks_view = crate::app::TasksView::new(return_to, summaries);
                             let project_path = project_path
@@ -697,15 +1409,29 @@ fn run(
                                 }
                             };
 
-                            match store.load_task(&task_id) {
+                            match load_task_or_team(&store, &task_id) {
                                 Ok(Some((task, images))) => {
+                                    // Opened from elsewhere (Processes): back lands on the task list.
+                                    let mut from_tasks = from_tasks;
+                                    if from_tasks.tasks.is_empty()
+                                        && let Ok(tasks) = store.list_tasks()
+                                    {
+                                        from_tasks = from_tasks
+                                            .with_reloaded_tasks(task_summary_rows(tasks));
+                                        from_tasks.select_task(&task_id);
+                                    }
                                     let engine = from_tasks.engine;
+                                    let worktree = from_tasks.worktree;
+                                    let agent_model = from_tasks.agent_model.clone();
                                     model.view =
                                         crate::app::View::TaskDetail(crate::app::TaskDetailView {
                                             from_tasks,
                                             task,
                                             images,
                                             engine,
+                                            worktree,
+                                            ab_compare: false,
+                                            agent_model,
                                             scroll: 0,
                                         });
                                     model.help_open = false;
@@ -753,16 +1479,7 @@ fn run(
                                     continue;
                                 }
                             };
-                            let summaries = tasks
-                                .into_iter()
-                                .map(|entry| crate::app::TaskSummaryRow {
-                                    id: entry.task.id,
-                                    title: derive_task_title(&entry.task.body),
-                                    project_path: entry.task.project_path,
-                                    updated_at: entry.task.updated_at,
-                                    image_count: entry.image_count,
-                                })
-                                .collect::<Vec<_>>();
+                            let summaries = task_summary_rows(tasks);
 
                             model.view =
                                 crate::app::View::Tasks(from_tasks.with_reloaded_tasks(summaries));
@@ -805,20 +1522,15 @@ fn run(
                                     continue;
                                 }
                             };
-                            let summaries = tasks
-                                .into_iter()
-                                .map(|entry| crate::app::TaskSummaryRow {
-                                    id: entry.task.id,
-                                    title: derive_task_title(&entry.task.body),
-                                    project_path: entry.task.project_path,
-                                    updated_at: entry.task.updated_at,
-                                    image_count: entry.image_count,
-                                })
-                                .collect::<Vec<_>>();
+                            let summaries = task_summary_rows(tasks);
 
                             model.view =
                                 crate::app::View::Tasks(from_tasks.with_reloaded_tasks(summaries));
                             *model = model.with_notice(Some("Deleted task.".to_string()));
+                            record_audit(
+                                model,
+                                audit_entry_now(AuditAction::Delete, format!("task {task_id}")),
+                            );
                         }
                         AppCommand::DeleteTasksBatch {
                             from_tasks,
@@ -837,9 +1549,13 @@ fn run(
                             let mut deleted = 0usize;
                             let mut not_found = 0usize;
                             let mut failed = 0usize;
+                            let mut deleted_ids = Vec::new();
                             for task_id in &task_ids {
                                 match store.delete_task(task_id) {
-                                    Ok(true) => deleted = deleted.saturating_add(1),
+                                    Ok(true) => {
+                                        deleted = deleted.saturating_add(1);
+                                        deleted_ids.push(task_id);
+                                    }
                                     Ok(false) => not_found = not_found.saturating_add(1),
                                     Err(_) => failed = failed.saturating_add(1),
                                 }
@@ -854,16 +1570,7 @@ fn run(
                                     continue;
                                 }
                             };
-                            let summaries = tasks
-                                .into_iter()
-                                .map(|entry| crate::app::TaskSummaryRow {
-                                    id: entry.task.id,
-                                    title: derive_task_title(&entry.task.body),
-                                    project_path: entry.task.project_path,
-                                    updated_at: entry.task.updated_at,
-                                    image_count: entry.image_count,
-                                })
-                                .collect::<Vec<_>>();
+                            let summaries = task_summary_rows(tasks);
 
                             model.view =
                                 crate::app::View::Tasks(from_tasks.with_reloaded_tasks(summaries));
@@ -876,14 +1583,56 @@ fn run(
                                 message.push_str(&format!(" {failed} failed."));
                             }
                             *model = model.with_notice(Some(message));
+                            for task_id in deleted_ids {
+                                record_audit(
+                                    model,
+                                    audit_entry_now(AuditAction::Delete, format!("task {task_id}")),
+                                );
+                            }
                         }
-                        AppCommand::SpawnTask { engine, task_id } => {
+                        AppCommand::SpawnTask {
+                            engine,
+                            task_id,
+                            worktree,
+                            agent_model,
+                        } => {
                             let Some(manager) = process_manager.as_mut() else {
                                 *model = model
                                     .with_notice(Some("Process spawning is disabled.".to_string()));
                                 continue;
                             };
 
+                            match spawn_task(
+                                model,
+                                manager,
+                                TaskSpawn {
+                                    engine,
+                                    task_id: &task_id,
+                                    agent_model: agent_model.as_deref(),
+                                    retry: RetryPolicy::disabled(),
+                                    worktree,
+                                    compare: None,
+                                },
+                            ) {
+                                Ok(spawned) => {
+                                    *model = model.with_notice(Some(format!(
+                                        "Spawned {} ({}){}",
+                                        spawned.engine.label(),
+                                        spawned.id,
+                                        worktree_notice_suffix(worktree, &spawned.project_path)
+                                    )));
+                                }
+                                Err(message) => {
+                                    *model = model.with_notice(Some(message));
+                                }
+                            }
+                        }
+                        AppCommand::ScheduleTask {
+                            from_tasks,
+                            task_id,
+                            engine,
+                            spec,
+                        } => {
                             let store = match TaskStore::open_default() {
                                 Ok(store) => store,
                                 Err(error) => {
@@ -894,72 +1643,81 @@ fn run(
                                 }
                             };
 
-                            let Some((task, images)) = (match store.load_task(&task_id) {
-                                Ok(task) => task,
-                                Err(error) => {
-                                    *model = model
-                                        .with_notice(Some(format!("Failed to load task: {error}")));
+                            let due_at = resolve_task_schedule_due_at(spec, SystemTime::now());
+                            match store.schedule_task(&task_id, engine, due_at) {
+                                Ok(true) => {}
+                                Ok(false) => {
+                                    *model = model.with_notice(Some("Task not found.".to_string()));
                                     continue;
                                 }
-                            }) else {
-                                *model = model.with_notice(Some("Task not found.".to_string()));
-                                continue;
-                            };
+                                Err(error) => {
+                                    *model = model.with_notice(Some(format!(
+                                        "Failed to schedule task: {error}"
+                                    )));
+                                    continue;
+                                }
+                            }
 
-                            let prompt = format_task_spawn_prompt(&task, &images);
+                            let tasks = match store.list_tasks() {
+                                Ok(tasks) => tasks,
+                                Err(error) => {
+                                    *model = model.with_notice(Some(format!(
+                                        "Failed to load tasks: {error}"
+                                    )));
+                                    continue;
+                                }
+                            };
+                            let summaries = task_summary_rows(tasks);
 
-                            match manager.spawn_agent_process(
-                                engine,
-                                &task.project_path,
-                                &prompt,
-                                crate::domain::SpawnIoMode::Pipes,
-                            ) {
-                                Ok(spawned) => {
-                                    let io_mode = match spawned.io {
-                                        crate::infra::SpawnedAgentIo::Pipes {
-                                            stdout_path,
-                                            stderr_path,
-                                            log_path,
-                                        } => crate::app::ProcessIoMode::Pipes {
-                                            stdout_path,
-                                            stderr_path,
-                                            log_path,
-                                        },
-                                        crate::infra::SpawnedAgentIo::Tty {
-                                            transcript_path,
-                                            log_path,
-                                        } => crate::app::ProcessIoMode::Tty {
-                                            transcript_path,
-                                            log_path,
-                                        },
-                                    };
+                            model.view =
+                                crate::app::View::Tasks(from_tasks.with_reloaded_tasks(summaries));
+                            *model = model.with_notice(Some(format!(
+                                "Scheduled {} spawn {}.",
+                                engine.label(),
+                                spec.label()
+                            )));
+                        }
+                        AppCommand::CancelTaskSchedule {
+                            from_tasks,
+                            task_id,
+                        } => {
+                            let store = match TaskStore::open_default() {
+                                Ok(store) => store,
+                                Err(error) => {
+                                    *model = model.with_notice(Some(format!(
+                                        "Failed to open tasks DB: {error}"
+                                    )));
+                                    continue;
+                                }
+                            };
 
-                                    model.processes.push(crate::app::ProcessInfo {
-                                        id: spawned.id.clone(),
-                                        pid: spawned.pid,
-                                        engine: spawned.engine,
-                                        project_path: spawned.project_path.clone(),
-                                        prompt_preview: spawned.prompt_preview.clone(),
-                                        started_at: spawned.started_at,
-                                        status: crate::app::ProcessStatus::Running,
-                                        io_mode,
-                                        session_id: None,
-                                        session_log_path: None,
-                                    });
+                            let notice = match store.cancel_task_schedule(&task_id) {
+                                Ok(true) => "Cancelled schedule.".to_string(),
+                                Ok(false) => "Task is not scheduled.".to_string(),
+                                Err(error) => {
                                     *model = model.with_notice(Some(format!(
-                                        "Spawned {} ({})",
-                                        spawned.engine.label(),
-                                        spawned.id
+                                        "Failed to cancel schedule: {error}"
                                     )));
+                                    continue;
                                 }
+                            };
+
+                            let tasks = match store.list_tasks() {
+                                Ok(tasks) => tasks,
                                 Err(error) => {
                                     *model = model.with_notice(Some(format!(
-                                        "Failed to spawn process: {error}"
+                                        "Failed to load tasks: {error}"
                                     )));
+                                    continue;
                                 }
-                            }
+                            };
+                            let summaries = task_summary_rows(tasks);
+
+                            model.view =
+                                crate::app::View::Tasks(from_tasks.with_reloaded_tasks(summaries));
+                            *model = model.with_notice(Some(notice));
                         }
-                        AppCommand::TaskCreateInsertImage { path } => {
+                        AppCommand::InsertPromptImage { path } => {
                             let Ok(metadata) = std::fs::metadata(&path) else {
                                 *model = model.with_notice(Some(format!(
                                     "Image not found: {}",
@@ -979,51 +1737,73 @@ fn run(
                             }
 
                             let path = std::fs::canonicalize(&path).unwrap_or(path);
-
-                            if let crate::app::View::TaskCreate(task_create) = &mut model.view {
-                                let ordinal =
-                                    u32::try_from(task_create.image_paths.len().saturating_add(1))
-                                        .unwrap_or(u32::MAX);
-                                task_create.image_paths.push(path);
-                                task_create.editor.insert_str(&format!("[Image {ordinal}]"));
-                                task_create.overlay = None;
+                            if let Some(ordinal) = insert_prompt_image(&mut model.view, path) {
                                 model.notice = Some(format!("Inserted [Image {ordinal}]."));
                             }
                         }
-                        AppCommand::TaskCreatePasteImageFromClipboard => {
-                            if !matches!(&model.view, crate::app::View::TaskCreate(_)) {
+                        AppCommand::NewSessionInsertFile { path } => {
+                            let text = match crate::infra::read_prompt_file(&path) {
+                                Ok(text) => text,
+                                Err(error) => {
+                                    *model = model.with_notice(Some(error.to_string()));
+                                    continue;
+                                }
+                            };
+                            if let crate::app::View::NewSession(new_session) = &mut model.view {
+                                new_session.editor.insert_str(&text);
+                                new_session.insert_file = None;
+                                model.notice = Some(format!(
+                                    "Inserted {} ({} lines).",
+                                    path.display(),
+                                    text.lines().count()
+                                ));
+                            }
+                        }
+                        AppCommand::NewSessionAttachFile { path } => {
+                            if let crate::app::View::NewSession(new_session) = &mut model.view {
+                                let notice = attach_prompt_file(new_session, &path);
+                                model.notice = Some(notice);
+                            }
+                        }
+                        AppCommand::CopyToClipboard { text, what } => {
+                            let notice = match clipboard.copy_text(&text, &mut io::stdout()) {
+                                Ok(()) => format!("Copied {what} to the clipboard."),
+                                Err(error) => format!("Failed to copy {what}: {error}"),
+                            };
+                            *model = model.with_notice(Some(notice));
+                        }
+                        AppCommand::PastePromptImageFromClipboard => {
+                            if !matches!(
+                                &model.view,
+                                crate::app::View::TaskCreate(_) | crate::app::View::NewSession(_)
+                            ) {
                                 *model = model.with_notice(Some(
-                                    "Open New Task to paste an image from the clipboard."
+                                    "Open New Task or New Session to paste an image from the clipboard."
                                         .to_string(),
                                 ));
                                 continue;
                             }
 
-                            let path =
-                                match crate::infra::paste_clipboard_image_to_task_images_dir() {
-                                    Ok(path) => path,
-                                    Err(crate::infra::PasteClipboardImageError::NoImage) => {
-                                        *model = model.with_notice(Some(
-                                            "Clipboard has no image to paste.".to_string(),
-                                        ));
-                                        continue;
-                                    }
-                                    Err(error) => {
-                                        *model = model.with_notice(Some(format!(
-                                            "Failed to paste clipboard image: {error}"
-                                        )));
-                                        continue;
-                                    }
-                                };
+                            let path = match crate::infra::paste_clipboard_image_to_task_images_dir(
+                                live_config.artifact_cipher.as_ref(),
+                            ) {
+                                Ok(path) => path,
+                                Err(crate::infra::PasteClipboardImageError::NoImage) => {
+                                    *model = model.with_notice(Some(
+                                        "Clipboard has no image to paste.".to_string(),
+                                    ));
+                                    continue;
+                                }
+                                Err(error) => {
+                                    *model = model.with_notice(Some(format!(
+                                        "Failed to paste clipboard image: {error}"
+                                    )));
+                                    continue;
+                                }
+                            };
 
                             let path = std::fs::canonicalize(&path).unwrap_or(path);
-                            if let crate::app::View::TaskCreate(task_create) = &mut model.view {
-                                let ordinal =
-                                    u32::try_from(task_create.image_paths.len().saturating_add(1))
-                                        .unwrap_or(u32::MAX);
-                                task_create.image_paths.push(path);
-                                task_create.editor.insert_str(&format!("[Image {ordinal}]"));
-                                task_create.overlay = None;
+                            if let Some(ordinal) = insert_prompt_image(&mut model.view, path) {
                                 model.notice =
                                     Some(format!("Inserted [Image {ordinal}] from clipboard."));
                             }
@@ -1046,6 +1826,8 @@ fn run(
 
                             match load_session_timeline(&log_path) {
                                 Ok(timeline) => {
+                                    let timeline = timeline
+                                        .into_newest_page(crate::domain::TIMELINE_PAGE_ITEMS);
                                     *model = model.open_session_detail(
                                         from_sessions,
                                         session,
@@ -1054,6 +1836,7 @@ fn run(
                                         timeline.warnings,
                                         timeline.truncated,
                                     );
+                                    set_session_detail_older_items(model, timeline.older);
                                 }
                                 Err(error) => {
                                     *model = model.with_notice(Some(format!(
@@ -1062,6 +1845,23 @@ fn run(
                                 }
                             }
                         }
+                        AppCommand::OpenInEditor { session } => {
+                            let log_path = match crate::infra::prepare_session_log_path(&session) {
+                                Ok(path) => path,
+                                Err(error) => {
+                                    *model = model.with_notice(Some(format!(
+                                        "Failed to prepare session log: {error}"
+                                    )));
+                                    continue;
+                                }
+                            };
+
+                            if let Err(error) = open_log_in_editor(terminal, &log_path) {
+                                *model = model.with_notice(Some(format!(
+                                    "Failed to open session log: {error}"
+                                )));
+                            }
+                        }
                         AppCommand::OpenSessionStats { session } => {
                             let log_path = match crate::infra::prepare_session_log_path(&session) {
                                 Ok(path) => path,
@@ -1075,8 +1875,11 @@ fn run(
 
                             match load_session_timeline(&log_path) {
                                 Ok(timeline) => {
-                                    let stats =
-                                        compute_session_stats(&session.meta, &timeline.items);
+                                    let stats = compute_session_stats(
+                                        &session.meta,
+                                        &timeline.items,
+                                        &timeline.turn_contexts,
+                                    );
                                     model.session_stats_overlay =
                                         Some(crate::app::SessionStatsOverlay {
                                             session,
@@ -1093,6 +1896,89 @@ fn run(
                                 }
                             }
                         }
+                        AppCommand::OpenProjectResultPreview { sessions, index } => {
+                            let mut last_error = None;
+                            let shown = (0..sessions.len())
+                                .map(|offset| (index + offset) % sessions.len())
+                                .find_map(|index| match load_session_output(&sessions[index]) {
+                                    Ok(output) => output.map(|output| (index, output)),
+                                    Err(error) => {
+                                        last_error = Some(error);
+                                        None
+                                    }
+                                });
+                            match (shown, last_error) {
+                                (Some((index, output)), _) => {
+                                    model.session_result_preview =
+                                        Some(crate::app::SessionResultPreviewOverlay {
+                                            session_title: sessions[index].title.clone(),
+                                            output,
+                                            scroll: 0,
+                                            cycle: sessions,
+                                            cycle_index: index,
+                                        });
+                                }
+                                (None, Some(error)) => {
+                                    *model = model.with_notice(Some(format!(
+                                        "Failed to load result: {error}"
+                                    )));
+                                }
+                                (None, None) => {
+                                    *model = model.with_notice(Some(
+                                        "No assistant output in the project's recent sessions."
+                                            .to_string(),
+                                    ));
+                                }
+                            }
+                        }
+                        AppCommand::OpenSessionDiff { a, b } => {
+                            let load = |session: &crate::domain::SessionSummary| {
+                                let log_path = crate::infra::prepare_session_log_path(session)
+                                    .map_err(|error| error.to_string())?;
+                                load_session_timeline(&log_path)
+                                    .map(|timeline| timeline.items)
+                                    .map_err(|error| error.to_string())
+                            };
+                            match load(&a).and_then(|items_a| Ok((items_a, load(&b)?))) {
+                                Ok((items_a, items_b)) => {
+                                    model.session_diff_overlay =
+                                        Some(crate::app::SessionDiffOverlay {
+                                            title_a: a.title,
+                                            title_b: b.title,
+                                            diff: diff_session_timelines(&items_a, &items_b),
+                                            scroll: 0,
+                                        });
+                                    model.help_open = false;
+                                    model.system_menu = None;
+                                }
+                                Err(error) => {
+                                    *model = model.with_notice(Some(format!(
+                                        "Failed to compare sessions: {error}"
+                                    )));
+                                }
+                            }
+                        }
+                        AppCommand::OpenProjectInIde { project_path } => {
+                            let Some(command) = live_config.config.ide_command.clone() else {
+                                *model = model.with_notice(Some(format!(
+                                    "Set \"ide_command\" (e.g. \"code\") in {} to open projects.",
+                                    live_config
+                                        .path
+                                        .as_deref()
+                                        .unwrap_or(Path::new("~/.ccbox/config.json"))
+                                        .display()
+                                )));
+                                continue;
+                            };
+                            let notice =
+                                match crate::infra::launch_detached(&command, &project_path) {
+                                    Ok(()) => {
+                                        format!("Opened {} in {command}.", project_path.display())
+                                    }
+                                    Err(error) => format!("Failed to open project: {error}"),
+                                };
+                            *model = model.with_notice(Some(notice));
+                        }
                         AppCommand::OpenSessionResultPreview { session } => {
                             let log_path = match crate::infra::prepare_session_log_path(&session) {
                                 Ok(path) => path,
@@ -1114,6 +2000,8 @@ fn run(
                                             session_title: session.title,
                                             output,
                                             scroll: 0,
+                                            cycle: Vec::new(),
+                                            cycle_index: 0,
                                         });
                                 }
                                 Err(error) => {
@@ -1176,6 +2064,7 @@ fn run(
                                 projects,
                                 warnings: model.data.warnings,
                                 load_error: model.data.load_error.clone(),
+                                scan_status: model.data.scan_status.clone(),
                             };
                             *model = model.with_data(new_data);
 
@@ -1192,6 +2081,28 @@ fn run(
                                 "Renamed session.".to_string()
                             };
                             *model = model.with_notice(Some(notice));
+                            let detail = if title.is_empty() {
+                                "cleared title".to_string()
+                            } else {
+                                format!("title: {title}")
+                            };
+                            record_audit(
+                                model,
+                                audit_entry_now(
+                                    AuditAction::Rename,
+                                    session.log_path.display().to_string(),
+                                )
+                                .with_detail(detail),
+                            );
+                            if session.engine == crate::domain::SessionEngine::OpenCode
+                                && !title.is_empty()
+                            {
+                                model.opencode_write_back =
+                                    Some(crate::app::OpenCodeWriteBackConfirm {
+                                        session,
+                                        change: crate::app::OpenCodeWriteBack::Title(title),
+                                    });
+                            }
                         }
                         AppCommand::MoveSessionProject {
                             session,
@@ -1235,6 +2146,7 @@ fn run(
                                         project_path.clone().or_else(|| fallback_project.clone())
                                     {
                                         detail_view.session.meta.cwd = cwd.clone();
+                                        detail_view.session.project_root = None;
                                         detail_view.from_sessions.project_path = cwd;
                                     }
                                 }
@@ -1256,6 +2168,7 @@ fn run(
                                 }
                                 if let Some(cwd) = next_cwd.clone() {
                                     entry.meta.cwd = cwd;
+                                    entry.project_root = None;
                                 }
                                 updated = true;
                             }
@@ -1280,6 +2193,25 @@ fn run(
                                 "Moved session.".to_string()
                             };
                             *model = model.with_notice(Some(notice));
+                            if updated
+                                && session.engine == crate::domain::SessionEngine::OpenCode
+                                && let Some(project_path) = project_path
+                            {
+                                model.opencode_write_back =
+                                    Some(crate::app::OpenCodeWriteBackConfirm {
+                                        session,
+                                        change: crate::app::OpenCodeWriteBack::Project(
+                                            project_path,
+                                        ),
+                                    });
+                            }
+                        }
+                        AppCommand::WriteBackOpenCode { session, change } => {
+                            let notice = match write_back_opencode(&session, &change) {
+                                Ok(notice) => notice,
+                                Err(error) => format!("OpenCode write-back failed: {error}"),
+                            };
+                            *model = model.with_notice(Some(notice));
                         }
                         AppCommand::DeleteProjectLogs { project_path } => {
                             let Some(project) = model
@@ -1310,6 +2242,11 @@ fn run(
                                 output.sessions,
                                 output.warnings,
                             );
+                            emit_session_events(
+                                &model.data,
+                                &new_data,
+                                &live_config.lifecycle_sinks(),
+                            );
                             *model = model.with_data(new_data);
                             refresh_open_project_stats_overlay(model);
                             request_session_index_refresh_optional(&session_index_req_tx, model);
@@ -1325,7 +2262,19 @@ fn run(
                                     outcome.skipped_outside_sessions_dir
                                 ));
                             }
+                            if outcome.skipped_remote > 0 {
+                                message.push_str(&format!(
+                                    " {} skipped (remote, read-only).",
+                                    outcome.skipped_remote
+                                ));
+                            }
                             *model = model.with_notice(Some(message));
+                            audit_deleted_session_logs(
+                                model,
+                                project_path.display().to_string(),
+                                &outcome,
+                                log_paths.len(),
+                            );
                         }
                         AppCommand::DeleteProjectLogsBatch { project_paths } => {
                             let mut log_paths = Vec::new();
@@ -1359,6 +2308,11 @@ fn run(
                                 output.sessions,
                                 output.warnings,
                             );
+                            emit_session_events(
+                                &model.data,
+                                &new_data,
+                                &live_config.lifecycle_sinks(),
+                            );
                             *model = model.with_data(new_data);
                             refresh_open_project_stats_overlay(model);
                             request_session_index_refresh_optional(&session_index_req_tx, model);
@@ -1374,7 +2328,21 @@ fn run(
                                     outcome.skipped_outside_sessions_dir
                                 ));
                             }
+                            if outcome.skipped_remote > 0 {
+                                message.push_str(&format!(
+                                    " {} skipped (remote, read-only).",
+                                    outcome.skipped_remote
+                                ));
+                            }
                             *model = model.with_notice(Some(message));
+                            for project_path in &project_paths {
+                                audit_deleted_session_logs(
+                                    model,
+                                    project_path.display().to_string(),
+                                    &outcome,
+                                    log_paths.len(),
+                                );
+                            }
                         }
                         AppCommand::DeleteSessionLog { log_path } => {
                             let outcome =
@@ -1391,6 +2359,11 @@ fn run(
                                 output.sessions,
                                 output.warnings,
                             );
+                            emit_session_events(
+                                &model.data,
+                                &new_data,
+                                &live_config.lifecycle_sinks(),
+                            );
                             *model = model.with_data(new_data);
                             refresh_open_project_stats_overlay(model);
                             request_session_index_refresh_optional(&session_index_req_tx, model);
@@ -1406,7 +2379,19 @@ fn run(
                                     outcome.skipped_outside_sessions_dir
                                 ));
                             }
+                            if outcome.skipped_remote > 0 {
+                                message.push_str(&format!(
+                                    " {} skipped (remote, read-only).",
+                                    outcome.skipped_remote
+                                ));
+                            }
                             *model = model.with_notice(Some(message));
+                            audit_deleted_session_logs(
+                                model,
+                                log_path.display().to_string(),
+                                &outcome,
+                                1,
+                            );
                         }
                         AppCommand::DeleteSessionLogsBatch { log_paths } => {
                             let outcome = delete_session_logs(&model.data.sessions_dir, &log_paths);
@@ -1422,6 +2407,11 @@ fn run(
                                 output.sessions,
                                 output.warnings,
                             );
+                            emit_session_events(
+                                &model.data,
+                                &new_data,
+                                &live_config.lifecycle_sinks(),
+                            );
                             *model = model.with_data(new_data);
                             refresh_open_project_stats_overlay(model);
                             request_session_index_refresh_optional(&session_index_req_tx, model);
@@ -1437,70 +2427,205 @@ fn run(
                                     outcome.skipped_outside_sessions_dir
                                 ));
                             }
+                            if outcome.skipped_remote > 0 {
+                                message.push_str(&format!(
+                                    " {} skipped (remote, read-only).",
+                                    outcome.skipped_remote
+                                ));
+                            }
                             *model = model.with_notice(Some(message));
+                            for log_path in &log_paths {
+                                audit_deleted_session_logs(
+                                    model,
+                                    log_path.display().to_string(),
+                                    &outcome,
+                                    log_paths.len(),
+                                );
+                            }
                         }
                         AppCommand::SpawnAgentSession {
                             engine,
                             project_path,
                             prompt,
+                            image_paths,
                             io_mode,
+                            retry,
+                            worktree,
+                            agent_model,
                         } => {
                             let Some(manager) = process_manager.as_mut() else {
                                 *model = model
                                     .with_notice(Some("Process spawning is disabled.".to_string()));
                                 continue;
                             };
-
-                            match manager.spawn_agent_process(
-                                engine,
-                                &project_path,
-                                &prompt,
-                                io_mode,
+                            match spawn_prompt(
+                                model,
+                                manager,
+                                PromptSpawn {
+                                    engine,
+                                    project_path: &project_path,
+                                    prompt: &prompt,
+                                    images: &image_paths,
+                                    agent_model: agent_model.as_deref(),
+                                    io_mode,
+                                    retry,
+                                    worktree,
+                                    batch: None,
+                                    compare: None,
+                                },
                             ) {
                                 Ok(spawned) => {
-                                    let io_mode = match spawned.io {
-                                        crate::infra::SpawnedAgentIo::Pipes {
-                                            stdout_path,
-                                            stderr_path,
-                                            log_path,
-                                        } => crate::app::ProcessIoMode::Pipes {
-                                            stdout_path,
-                                            stderr_path,
-                                            log_path,
-                                        },
-                                        crate::infra::SpawnedAgentIo::Tty {
-                                            transcript_path,
-                                            log_path,
-                                        } => crate::app::ProcessIoMode::Tty {
-                                            transcript_path,
-                                            log_path,
-                                        },
-                                    };
-
-                                    model.processes.push(crate::app::ProcessInfo {
-                                        id: spawned.id.clone(),
-                                        pid: spawned.pid,
-                                        engine: spawned.engine,
-                                        project_path: spawned.project_path.clone(),
-                                        prompt_preview: spawned.prompt_preview.clone(),
-                                        started_at: spawned.started_at,
-                                        status: crate::app::ProcessStatus::Running,
-                                        io_mode,
-                                        session_id: None,
-                                        session_log_path: None,
-                                    });
                                     *model = model.with_notice(Some(format!(
-                                        "Spawned {} ({})",
+                                        "Spawned {} ({}){}",
                                         spawned.engine.label(),
-                                        spawned.id
+                                        spawned.id,
+                                        worktree_notice_suffix(worktree, &spawned.project_path)
                                     )));
                                 }
-                                Err(error) => {
-                                    *model = model.with_notice(Some(format!(
-                                        "Failed to spawn process: {error}"
-                                    )));
+                                Err(message) => {
+                                    *model = model.with_notice(Some(message));
+                                }
+                            }
+                        }
+                        AppCommand::SpawnAgentComparison {
+                            engines,
+                            project_path,
+                            prompt,
+                            image_paths,
+                            io_mode,
+                            retry,
+                            worktree,
+                            agent_model,
+                        } => {
+                            let Some(manager) = process_manager.as_mut() else {
+                                *model = model
+                                    .with_notice(Some("Process spawning is disabled.".to_string()));
+                                continue;
+                            };
+                            let compare = next_comparison_number(model);
+                            let mut spawned = Vec::new();
+                            let agent_models = [agent_model.as_deref(), None];
+                            for (engine, agent_model) in engines.into_iter().zip(agent_models) {
+                                match spawn_prompt(
+                                    model,
+                                    manager,
+                                    PromptSpawn {
+                                        engine,
+                                        project_path: &project_path,
+                                        prompt: &prompt,
+                                        images: &image_paths,
+                                        agent_model,
+                                        io_mode,
+                                        retry,
+                                        worktree,
+                                        batch: None,
+                                        compare: Some(compare),
+                                    },
+                                ) {
+                                    Ok(process) => spawned.push(process),
+                                    Err(message) => {
+                                        *model = model.with_notice(Some(message));
+                                        break;
+                                    }
+                                }
+                            }
+                            if let [a, b] = spawned.as_slice() {
+                                *model = model
+                                    .with_notice(Some(comparison_spawned_notice(compare, a, b)));
+                            }
+                        }
+                        AppCommand::SpawnTaskComparison {
+                            engines,
+                            task_id,
+                            worktree,
+                            agent_model,
+                        } => {
+                            let Some(manager) = process_manager.as_mut() else {
+                                *model = model
+                                    .with_notice(Some("Process spawning is disabled.".to_string()));
+                                continue;
+                            };
+                            let compare = next_comparison_number(model);
+                            let mut spawned = Vec::new();
+                            let agent_models = [agent_model.as_deref(), None];
+                            for (engine, agent_model) in engines.into_iter().zip(agent_models) {
+                                match spawn_task(
+                                    model,
+                                    manager,
+                                    TaskSpawn {
+                                        engine,
+                                        task_id: &task_id,
+                                        agent_model,
+                                        retry: RetryPolicy::disabled(),
+                                        worktree,
+                                        compare: Some(compare),
+                                    },
+                                ) {
+                                    Ok(process) => spawned.push(process),
+                                    Err(message) => {
+                                        *model = model.with_notice(Some(message));
+                                        break;
+                                    }
+                                }
+                            }
+                            if let [a, b] = spawned.as_slice() {
+                                *model = model
+                                    .with_notice(Some(comparison_spawned_notice(compare, a, b)));
+                            }
+                        }
+                        AppCommand::SpawnAgentBatch {
+                            engine,
+                            project_paths,
+                            prompt,
+                            image_paths,
+                            io_mode,
+                            retry,
+                            worktree,
+                            agent_model,
+                        } => {
+                            let Some(manager) = process_manager.as_mut() else {
+                                *model = model
+                                    .with_notice(Some("Process spawning is disabled.".to_string()));
+                                continue;
+                            };
+                            let batch = model
+                                .processes
+                                .iter()
+                                .filter_map(|process| process.batch)
+                                .max()
+                                .unwrap_or(0)
+                                .saturating_add(1);
+                            let mut failures = Vec::new();
+                            for project_path in &project_paths {
+                                if let Err(message) = spawn_prompt(
+                                    model,
+                                    manager,
+                                    PromptSpawn {
+                                        engine,
+                                        project_path,
+                                        prompt: &prompt,
+                                        images: &image_paths,
+                                        agent_model: agent_model.as_deref(),
+                                        io_mode,
+                                        retry,
+                                        worktree,
+                                        batch: Some(batch),
+                                        compare: None,
+                                    },
+                                ) {
+                                    failures.push(format!("{}: {message}", project_path.display()));
                                 }
                             }
+                            let spawned = project_paths.len() - failures.len();
+                            let mut notice = format!(
+                                "Batch {batch}: spawned {} in {spawned}/{} projects.",
+                                engine.label(),
+                                project_paths.len()
+                            );
+                            if !failures.is_empty() {
+                                notice.push_str(&format!(" {}", failures.join("; ")));
+                            }
+                            *model = model.with_notice(Some(notice));
                         }
                         AppCommand::ForkResumeCodexFromTimeline { fork, prompt } => {
                             let Some(manager) = process_manager.as_mut() else {
@@ -1529,25 +2654,6 @@ fn run(
                                 &prompt,
                             ) {
                                 Ok(spawned) => {
-                                    let io_mode = match spawned.io {
-                                        crate::infra::SpawnedAgentIo::Pipes {
-                                            stdout_path,
-                                            stderr_path,
-                                            log_path,
-                                        } => crate::app::ProcessIoMode::Pipes {
-                                            stdout_path,
-                                            stderr_path,
-                                            log_path,
-                                        },
-                                        crate::infra::SpawnedAgentIo::Tty {
-                                            transcript_path,
-                                            log_path,
-                                        } => crate::app::ProcessIoMode::Tty {
-                                            transcript_path,
-                                            log_path,
-                                        },
-                                    };
-
                                     model.processes.push(crate::app::ProcessInfo {
                                         id: spawned.id.clone(),
                                         pid: spawned.pid,
@@ -1556,15 +2662,38 @@ fn run(
                                         prompt_preview: spawned.prompt_preview.clone(),
                                         started_at: spawned.started_at,
                                         status: crate::app::ProcessStatus::Running,
-                                        io_mode,
+                                        io_mode: process_io_mode(spawned.io.clone()),
                                         session_id: Some(forked.session_id.clone()),
                                         session_log_path: Some(forked.log_path.clone()),
+                                        retry_policy: RetryPolicy::disabled(),
+                                        attempts: Vec::new(),
+                                        plan: None,
+                                        input_wait: None,
+                                        usage: None,
+                                        task_id: None,
+                                        task_title: None,
+                                        batch: None,
+                                        compare: None,
+                                        label: None,
                                     });
 
                                     *model = model.with_notice(Some(format!(
                                         "Forked and resumed Codex ({})",
                                         spawned.id
                                     )));
+                                    record_audit(
+                                        model,
+                                        audit_entry_now(
+                                            AuditAction::Fork,
+                                            fork.parent_log_path.display().to_string(),
+                                        )
+                                        .with_detail(
+                                            format!(
+                                                "new session {} resumed as {}",
+                                                forked.session_id, spawned.id
+                                            ),
+                                        ),
+                                    );
                                 }
                                 Err(error) => {
                                     *model = model.with_notice(Some(format!(
@@ -1575,25 +2704,107 @@ fn run(
                                 }
                             }
                         }
-                        AppCommand::KillProcess { process_id } => {
+                        AppCommand::ResumeOpenCodeSession { session, prompt } => {
                             let Some(manager) = process_manager.as_mut() else {
                                 *model = model
-                                    .with_notice(Some("Process manager disabled.".to_string()));
+                                    .with_notice(Some("Process spawning is disabled.".to_string()));
                                 continue;
                             };
 
-                            match manager.kill(&process_id) {
-                                Ok(()) => {
-                                    if let Some(process) = model
-                                        .processes
-                                        .iter_mut()
-                                        .find(|process| process.id == process_id)
-                                    {
-                                        process.status = crate::app::ProcessStatus::Killed;
-                                    }
+                            match manager.spawn_opencode_resume_process(
+                                &session.meta.cwd,
+                                &session.meta.id,
+                                &prompt,
+                            ) {
+                                Ok(spawned) => {
+                                    // Links the process to the session's cache; opening the
+                                    // session refreshes it from the DB.
+                                    let session_log_path =
+                                        crate::infra::prepare_session_log_path(&session).ok();
+                                    model.processes.push(crate::app::ProcessInfo {
+                                        id: spawned.id.clone(),
+                                        pid: spawned.pid,
+                                        engine: spawned.engine,
+                                        project_path: spawned.project_path.clone(),
+                                        prompt_preview: spawned.prompt_preview.clone(),
+                                        started_at: spawned.started_at,
+                                        status: crate::app::ProcessStatus::Running,
+                                        io_mode: process_io_mode(spawned.io.clone()),
+                                        session_id: Some(session.meta.id.clone()),
+                                        session_log_path,
+                                        retry_policy: RetryPolicy::disabled(),
+                                        attempts: Vec::new(),
+                                        plan: None,
+                                        input_wait: None,
+                                        usage: None,
+                                        task_id: None,
+                                        task_title: None,
+                                        batch: None,
+                                        compare: None,
+                                        label: None,
+                                    });
+
+                                    *model = model.with_notice(Some(format!(
+                                        "Resumed OpenCode ({})",
+                                        spawned.id
+                                    )));
+                                    record_audit(model, spawn_audit_entry(&spawned));
+                                }
+                                Err(error) => {
+                                    *model = model.with_notice(Some(format!(
+                                        "Failed to spawn resume process: {error}"
+                                    )));
+                                }
+                            }
+                        }
+                        AppCommand::KillProcessGroup {
+                            label,
+                            process_ids,
+                            force,
+                        } => {
+                            let Some(manager) = process_manager.as_mut() else {
+                                *model = model
+                                    .with_notice(Some("Process manager disabled.".to_string()));
+                                continue;
+                            };
+
+                            let mut killed = 0usize;
+                            let mut failures = Vec::new();
+                            for process_id in &process_ids {
+                                match kill_tracked_process(model, manager, process_id, force) {
+                                    Ok(()) => killed += 1,
+                                    Err(KillProcessError::NotFound) => {}
+                                    Err(error) => failures.push(format!("{process_id}: {error}")),
+                                }
+                            }
+                            let verb = if force { "Killed" } else { "Stopping" };
+                            let notice = if failures.is_empty() {
+                                format!("{verb} {killed} process(es) in {label}.")
+                            } else {
+                                format!(
+                                    "{verb} {killed} process(es) in {label}; failed: {}",
+                                    failures.join(", ")
+                                )
+                            };
+                            *model = model.with_notice(Some(notice));
+                        }
+                        AppCommand::KillProcess { process_id, force } => {
+                            let Some(manager) = process_manager.as_mut() else {
+                                *model = model
+                                    .with_notice(Some("Process manager disabled.".to_string()));
+                                continue;
+                            };
+
+                            match kill_tracked_process(model, manager, &process_id, force) {
+                                Ok(()) if force => {
                                     *model =
                                         model.with_notice(Some(format!("Killed {process_id}.")));
                                 }
+                                Ok(()) => {
+                                    *model = model.with_notice(Some(format!(
+                                        "Stopping {process_id} (SIGTERM; K force-kills)."
+                                    )));
+                                }
                                 Err(KillProcessError::NotFound) => {
                                     *model = model.with_notice(Some(format!(
                                         "Process not running: {process_id}"
@@ -1606,8 +2817,136 @@ fn run(
                                 }
                             }
                         }
+                        AppCommand::WriteProcessTty { process_id, text } => {
+                            let Some(manager) = process_manager.as_mut() else {
+                                *model = model
+                                    .with_notice(Some("Process manager disabled.".to_string()));
+                                continue;
+                            };
+
+                            // The terminal sends Enter as a carriage return.
+                            let notice = match manager
+                                .write_tty(&process_id, format!("{text}\r").as_bytes())
+                            {
+                                Ok(()) => format!("Sent to {process_id}."),
+                                Err(error) => format!("Failed to write to {process_id}: {error}"),
+                            };
+                            *model = model.with_notice(Some(notice));
+                        }
+                        AppCommand::OpenQuickSwitcher => {
+                            // Tasks are optional here; the switcher still lists everything else.
+                            let tasks = TaskStore::open_default()
+                                .ok()
+                                .and_then(|store| store.list_tasks().ok())
+                                .map(task_summary_rows)
+                                .unwrap_or_default();
+                            model.quick_switcher =
+                                Some(crate::app::QuickSwitcherOverlay::new(tasks));
+                            model.help_open = false;
+                            model.system_menu = None;
+                        }
+                        AppCommand::LoadProjectSkills { project_path } => {
+                            let report = model
+                                .data
+                                .projects
+                                .iter()
+                                .find(|project| project.project_path == project_path)
+                                .map(|project| {
+                                    crate::infra::project_skill_usage(&project.sessions)
+                                });
+                            if let Some(overlay) = model.project_stats_overlay.as_mut()
+                                && overlay.project_path == project_path
+                            {
+                                overlay.skills = report;
+                            }
+                        }
+                        AppCommand::OpenAuditLog => {
+                            let entries = resolve_ccbox_state_dir()
+                                .map_err(|error| error.to_string())
+                                .and_then(|state_dir| {
+                                    load_audit_entries(&state_dir)
+                                        .map_err(|error| error.to_string())
+                                });
+                            match entries {
+                                Ok(mut entries) => {
+                                    entries.reverse();
+                                    model.audit_log_overlay =
+                                        Some(crate::app::AuditLogOverlay { entries, scroll: 0 });
+                                }
+                                Err(error) => {
+                                    *model = model.with_notice(Some(format!(
+                                        "Failed to load audit log: {error}"
+                                    )));
+                                }
+                            }
+                        }
+                        AppCommand::OpenDevices => {
+                            model
+                                .devices_overlay
+                                .get_or_insert_with(|| crate::app::DevicesOverlay {
+                                    devices: Vec::new(),
+                                    selected: 0,
+                                    loading: true,
+                                    pairing: None,
+                                })
+                                .loading = true;
+                            spawn_devices_load(devices_tx.clone());
+                        }
+                        AppCommand::SetDeviceRevoked { device_id, revoked } => {
+                            let done = if revoked {
+                                format!("Revoked device {device_id}.")
+                            } else {
+                                format!("Restored device {device_id}.")
+                            };
+                            spawn_device_change(devices_tx.clone(), done, move |state_dir| {
+                                crate::remote::set_relay_device_revoked(
+                                    state_dir, &device_id, revoked,
+                                )
+                            });
+                        }
+                        AppCommand::ForgetDevice { device_id } => {
+                            let done =
+                                format!("Forgot device {device_id}; press n to pair it again.");
+                            spawn_device_change(devices_tx.clone(), done, move |state_dir| {
+                                crate::remote::forget_relay_device(state_dir, &device_id)
+                            });
+                        }
+                        AppCommand::PairDevice => {
+                            spawn_device_pairing(pairing_tx.clone());
+                        }
+                        AppCommand::ExportProcessOutput {
+                            process_id,
+                            kind,
+                            destination,
+                        } => {
+                            export_process_output(
+                                model,
+                                &process_id,
+                                kind,
+                                destination,
+                                live_config.artifact_cipher.as_ref(),
+                                &mut clipboard,
+                            );
+                        }
+                        AppCommand::RecordProcessLabels { process_ids } => {
+                            if let Some(manager) = process_manager.as_ref() {
+                                for process in model
+                                    .processes
+                                    .iter()
+                                    .filter(|process| process_ids.contains(&process.id))
+                                {
+                                    manager
+                                        .record_process_links(&process.id, &process_links(process));
+                                }
+                            }
+                        }
                         AppCommand::OpenProcessOutput { process_id, kind } => {
-                            open_process_output_view(model, &process_id, kind);
+                            open_process_output_view(
+                                model,
+                                &process_id,
+                                kind,
+                                live_config.artifact_cipher.as_ref(),
+                            );
                         }
                         AppCommand::AttachProcessTty { process_id } => {
                             let Some(manager) = process_manager.as_mut() else {
@@ -1660,6 +2999,364 @@ fn spawn_update_check(tx: Sender<UpdateSignal>) {
     });
 }
 
+fn spawn_task_registry_sync(
+    config: TaskRegistryConfig,
+    dir: PathBuf,
+    tx: Sender<TaskRegistrySignal>,
+) -> Arc<AtomicBool> {
+    let stop = Arc::new(AtomicBool::new(false));
+    let stopped = stop.clone();
+    std::thread::spawn(move || {
+        loop {
+            let result = sync_task_registry(&config, &dir).map_err(|error| error.to_string());
+            if tx.send(TaskRegistrySignal::Synced(result)).is_err() {
+                return;
+            }
+            std::thread::sleep(config.refresh_interval());
+            if stopped.load(Ordering::Relaxed) {
+                return;
+            }
+        }
+    });
+    stop
+}
+
+/// Stops the current sync thread (if any) and starts one for `registry`, or clears the cached
+/// team tasks when the registry was removed.
+fn restart_task_registry_sync(
+    running: Option<Arc<AtomicBool>>,
+    registry: Option<TaskRegistryConfig>,
+    tx: Sender<TaskRegistrySignal>,
+) -> Option<Arc<AtomicBool>> {
+    if let Some(stop) = running {
+        stop.store(true, Ordering::Relaxed);
+    }
+    let registry_dir = resolve_task_registry_dir().ok()?;
+    match registry {
+        Some(registry) => Some(spawn_task_registry_sync(registry, registry_dir, tx)),
+        None => {
+            let _ = clear_task_registry(&registry_dir);
+            None
+        }
+    }
+}
+
+fn record_scan_metrics(metrics: &Metrics, data: &crate::app::AppData) {
+    let sessions = data
+        .projects
+        .iter()
+        .map(|project| project.sessions.len())
+        .sum();
+    metrics.record_scan(sessions, data.warnings.get());
+}
+
+fn file_modified_at(path: &std::path::Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// Re-reads the config file and applies the sections the TUI uses. An invalid file keeps the
+/// previous settings; `remote_spawn` is only read when `ccbox serve` starts.
+fn reload_config(
+    model: &mut AppModel,
+    live: &mut LiveConfig,
+    process_manager: Option<&mut ProcessManager>,
+    lifecycle_tx: &Sender<LifecycleSignal>,
+    task_registry_tx: &Sender<TaskRegistrySignal>,
+    is_manual: bool,
+) {
+    let Some(path) = live.path.clone() else {
+        if is_manual {
+            *model = model.with_notice(Some("No config path to reload.".to_string()));
+        }
+        return;
+    };
+    live.modified_at = file_modified_at(&path);
+    let config = match load_ccbox_config(&path) {
+        Ok(config) => config,
+        Err(error) => {
+            *model = model.with_notice(Some(format!(
+                "Config reload failed (keeping previous settings): {error}"
+            )));
+            return;
+        }
+    };
+
+    let changed = live.config.changed_sections(&config);
+    if changed.is_empty() {
+        if is_manual {
+            *model = model.with_notice(Some("Config unchanged.".to_string()));
+        }
+        return;
+    }
+
+    let mut notice = format!("Config reloaded: {}.", changed.join(", "));
+    if changed.contains(&"encryption") {
+        live.artifact_cipher = match ArtifactCipher::from_config(&config.encryption) {
+            Ok(cipher) => cipher,
+            Err(error) => {
+                notice.push_str(&format!(" Encryption disabled: {error}"));
+                None
+            }
+        };
+    }
+    if let Some(manager) = process_manager {
+        manager.set_process_limits(config.process_limits.clone());
+        manager.set_spawn_args(config.spawn_args.clone());
+        manager.set_project_env(config.project_env.clone());
+        manager.set_tty_scrollback_bytes(config.tty_scrollback_bytes());
+        manager.set_kill_grace(config.kill_grace());
+        manager.set_artifact_cipher(live.artifact_cipher.clone());
+    }
+    if changed.contains(&"webhooks") {
+        live.webhook_events = spawn_webhook_worker(config.webhooks.clone(), lifecycle_tx.clone());
+    }
+    if changed.contains(&"hooks") {
+        live.hook_events = spawn_hook_worker(config.hooks.clone(), lifecycle_tx.clone());
+    }
+    if changed.contains(&"task_registry") {
+        live.task_registry_stop = restart_task_registry_sync(
+            live.task_registry_stop.take(),
+            config.task_registry.clone(),
+            task_registry_tx.clone(),
+        );
+    }
+    if changed.contains(&"remote_spawn") {
+        notice.push_str(" remote_spawn applies when ccbox serve restarts.");
+    }
+    if changed.contains(&"metrics") {
+        notice.push_str(" metrics applies after a restart.");
+    }
+    if changed.contains(&"metadata_sync") {
+        notice.push_str(" metadata_sync applies after a restart.");
+    }
+    if changed.contains(&"custom_engines") {
+        notice.push_str(" custom_engines applies on the next rescan (Ctrl+R).");
+    }
+    if changed.contains(&"group_projects_by_repo") {
+        notice.push_str(" group_projects_by_repo applies on the next rescan (Ctrl+R).");
+    }
+    if changed.contains(&"remote_sources") {
+        notice.push_str(" remote_sources applies on the next rescan (Ctrl+R).");
+    }
+    if changed.contains(&"extra_session_dirs") {
+        notice.push_str(
+            " extra_session_dirs are scanned on the next rescan (Ctrl+R) and watched after a restart.",
+        );
+    }
+    if live.config.rescan.disable_watchers != config.rescan.disable_watchers {
+        notice.push_str(" disable_watchers applies to session folders after a restart.");
+    }
+    model.list_columns = config.list_columns.clone();
+    model.engine_models = config.models.clone();
+    model.spawn_args = config.spawn_args.clone();
+    live.config = config;
+    *model = model.with_notice(Some(notice));
+}
+
+/// Delivers lifecycle events on a background thread. Returns `None` when no webhooks are
+/// configured so callers can skip building events.
+fn spawn_webhook_worker(
+    configs: Vec<WebhookConfig>,
+    tx: Sender<LifecycleSignal>,
+) -> Option<Sender<LifecycleEvent>> {
+    if configs.is_empty() {
+        return None;
+    }
+    let (events_tx, events_rx) = channel::<LifecycleEvent>();
+    std::thread::spawn(move || {
+        while let Ok(event) = events_rx.recv() {
+            for config in configs
+                .iter()
+                .filter(|config| config.is_subscribed(event.kind))
+            {
+                if let Err(error) = deliver_webhook(config, &event)
+                    && tx
+                        .send(LifecycleSignal::WebhookFailed(error.to_string()))
+                        .is_err()
+                {
+                    return;
+                }
+            }
+        }
+    });
+    Some(events_tx)
+}
+
+/// Runs hook commands on a background thread, one at a time. Returns `None` when no hooks are
+/// configured.
+fn spawn_hook_worker(
+    configs: Vec<HookConfig>,
+    tx: Sender<LifecycleSignal>,
+) -> Option<Sender<LifecycleEvent>> {
+    if configs.is_empty() {
+        return None;
+    }
+    let (events_tx, events_rx) = channel::<LifecycleEvent>();
+    std::thread::spawn(move || {
+        while let Ok(event) = events_rx.recv() {
+            for config in configs
+                .iter()
+                .filter(|config| config.is_subscribed(event.kind))
+            {
+                if let Err(error) = run_hook(config, &event)
+                    && tx
+                        .send(LifecycleSignal::HookFailed(error.to_string()))
+                        .is_err()
+                {
+                    return;
+                }
+            }
+        }
+    });
+    Some(events_tx)
+}
+
+fn send_lifecycle_event(sinks: &[&Sender<LifecycleEvent>], event: LifecycleEvent) {
+    for sink in sinks {
+        let _ = sink.send(event.clone());
+    }
+}
+
+/// Compares process statuses with the last tick and sends start/exit/failure events, plus
+/// `task.completed` when a process spawned from a task exits with code 0 and
+/// `process.needs_attention` when a running process starts waiting on a human.
+fn emit_process_events(
+    model: &AppModel,
+    known: &mut HashMap<String, crate::app::ProcessStatus>,
+    waiting: &mut HashSet<String>,
+    sinks: &[&Sender<LifecycleEvent>],
+) {
+    if sinks.is_empty() {
+        return;
+    }
+    for process in &model.processes {
+        match &process.input_wait {
+            Some(wait) if waiting.insert(process.id.clone()) => {
+                let event = LifecycleEvent::new(LifecycleEventKind::ProcessNeedsAttention)
+                    .with("process_id", process.id.clone())
+                    .with("engine", process.engine.label())
+                    .with("project", process.project_path.display().to_string())
+                    .with("prompt", process.prompt_preview.clone())
+                    .with("reason", wait.reason.label())
+                    .with("detail", wait.detail.clone())
+                    .with("session_id", process.session_id.clone().unwrap_or_default())
+                    .with(
+                        "log_path",
+                        process
+                            .session_log_path
+                            .as_ref()
+                            .map(|path| path.display().to_string())
+                            .unwrap_or_default(),
+                    );
+                send_lifecycle_event(sinks, event);
+            }
+            Some(_) => {}
+            None => {
+                waiting.remove(&process.id);
+            }
+        }
+
+        let previous = known.insert(process.id.clone(), process.status.clone());
+        if previous.as_ref() == Some(&process.status) {
+            continue;
+        }
+        let (kind, exit_code) = match &process.status {
+            crate::app::ProcessStatus::Running => (LifecycleEventKind::ProcessStarted, None),
+            crate::app::ProcessStatus::Exited(Some(0)) => {
+                (LifecycleEventKind::ProcessExited, Some(0))
+            }
+            crate::app::ProcessStatus::Killed => (LifecycleEventKind::ProcessExited, None),
+            // Reported as exited once it is gone.
+            crate::app::ProcessStatus::Stopping => continue,
+            crate::app::ProcessStatus::Exited(code)
+            | crate::app::ProcessStatus::RetryPending(code) => {
+                (LifecycleEventKind::ProcessFailed, *code)
+            }
+        };
+        let log_path = process
+            .session_log_path
+            .as_ref()
+            .map(|path| path.display().to_string())
+            .unwrap_or_default();
+        let event = LifecycleEvent::new(kind)
+            .with("process_id", process.id.clone())
+            .with("engine", process.engine.label())
+            .with("project", process.project_path.display().to_string())
+            .with("prompt", process.prompt_preview.clone())
+            .with("status", process.status.label())
+            .with(
+                "exit_code",
+                exit_code.map(|code| code.to_string()).unwrap_or_default(),
+            )
+            .with("session_id", process.session_id.clone().unwrap_or_default())
+            .with("log_path", log_path.clone());
+        send_lifecycle_event(sinks, event);
+
+        if exit_code == Some(0)
+            && let Some(task_id) = &process.task_id
+        {
+            let event = LifecycleEvent::new(LifecycleEventKind::TaskCompleted)
+                .with("task_id", task_id.to_string())
+                .with("process_id", process.id.clone())
+                .with("engine", process.engine.label())
+                .with("project", process.project_path.display().to_string())
+                .with("session_id", process.session_id.clone().unwrap_or_default())
+                .with("log_path", log_path);
+            send_lifecycle_event(sinks, event);
+        }
+    }
+}
+
+/// Sends `session.detected` for sessions present in `next` but not in `previous`, and
+/// `session.deleted` for the reverse.
+fn emit_session_events(
+    previous: &crate::app::AppData,
+    next: &crate::app::AppData,
+    sinks: &[&Sender<LifecycleEvent>],
+) {
+    if sinks.is_empty() {
+        return;
+    }
+    let log_paths = |data: &crate::app::AppData| {
+        data.projects
+            .iter()
+            .flat_map(|project| project.sessions.iter())
+            .map(|session| session.log_path.clone())
+            .collect::<std::collections::HashSet<_>>()
+    };
+    let (before, after) = (log_paths(previous), log_paths(next));
+    let changes = [
+        (LifecycleEventKind::SessionDetected, next, &before),
+        (LifecycleEventKind::SessionDeleted, previous, &after),
+    ];
+    for (kind, data, known) in changes {
+        for project in &data.projects {
+            for session in project
+                .sessions
+                .iter()
+                .filter(|session| !known.contains(&session.log_path))
+            {
+                let engine = match session.engine {
+                    crate::domain::SessionEngine::Codex => "Codex",
+                    crate::domain::SessionEngine::Claude => "Claude",
+                    crate::domain::SessionEngine::Gemini => "Gemini",
+                    crate::domain::SessionEngine::OpenCode => "OpenCode",
+                    crate::domain::SessionEngine::Custom => "Custom",
+                };
+                let event = LifecycleEvent::new(kind)
+                    .with("session_id", session.meta.id.clone())
+                    .with("engine", engine)
+                    .with("project", project.project_path.display().to_string())
+                    .with("title", session.title.clone())
+                    .with("log_path", session.log_path.display().to_string());
+                send_lifecycle_event(sinks, event);
+            }
+        }
+    }
+}
+
 fn spawn_session_indexer(
     rx: std::sync::mpsc::Receiver<SessionIndexRequest>,
     tx: Sender<SessionIndexSignal>,
@@ -1687,47 +3384,612 @@ fn spawn_session_indexer(
     });
 }
 
-fn request_session_index_refresh(tx: &Sender<SessionIndexRequest>, model: &AppModel) {
-    let sessions = model
-        .data
-        .projects
-        .iter()
-        .flat_map(|project| project.sessions.iter().cloned())
-        .collect::<Vec<_>>();
-    let _ = tx.send(SessionIndexRequest { sessions });
+fn request_session_index_refresh(tx: &Sender<SessionIndexRequest>, model: &AppModel) {
+    let sessions = model
+        .data
+        .projects
+        .iter()
+        .flat_map(|project| project.sessions.iter().cloned())
+        .collect::<Vec<_>>();
+    let _ = tx.send(SessionIndexRequest { sessions });
+}
+
+fn request_session_index_refresh_optional(
+    tx: &Option<Sender<SessionIndexRequest>>,
+    model: &AppModel,
+) {
+    let Some(tx) = tx else {
+        return;
+    };
+    request_session_index_refresh(tx, model);
+}
+
+fn refresh_open_project_stats_overlay(model: &mut AppModel) {
+    let Some(current) = model.project_stats_overlay.clone() else {
+        return;
+    };
+    let Some(project) = model
+        .data
+        .projects
+        .iter()
+        .find(|project| project.project_path == current.project_path)
+    else {
+        model.project_stats_overlay = None;
+        return;
+    };
+
+    let mut refreshed =
+        crate::app::ProjectStatsOverlay::from_project(project, model.session_index.as_ref());
+    refreshed.skills = current.skills;
+    refreshed.scroll = current.scroll;
+    model.project_stats_overlay = Some(refreshed);
+}
+
+/// Local tasks (newest first) followed by the team tasks from the last registry sync.
+fn task_summary_rows(tasks: Vec<TaskListEntry>) -> Vec<crate::app::TaskSummaryRow> {
+    let mut rows = tasks
+        .into_iter()
+        .map(|entry| crate::app::TaskSummaryRow {
+            id: entry.task.id,
+            title: derive_task_title(&entry.task.body),
+            project_path: entry.task.project_path,
+            updated_at: entry.task.updated_at,
+            image_count: entry.image_count,
+            schedule: entry.schedule,
+        })
+        .collect::<Vec<_>>();
+    rows.extend(load_all_team_tasks().into_iter().map(|(task, images)| {
+        crate::app::TaskSummaryRow {
+            title: derive_task_title(&task.body),
+            id: task.id,
+            project_path: task.project_path,
+            updated_at: task.updated_at,
+            image_count: u32::try_from(images.len()).unwrap_or(u32::MAX),
+            schedule: None,
+        }
+    }));
+    rows
+}
+
+fn load_all_team_tasks() -> Vec<(Task, Vec<TaskImage>)> {
+    resolve_task_registry_dir()
+        .ok()
+        .and_then(|dir| load_team_tasks(&dir).ok())
+        .unwrap_or_default()
+}
+
+/// Loads a local task from the DB, or a team task from the registry cache.
+fn load_task_or_team(
+    store: &TaskStore,
+    task_id: &TaskId,
+) -> Result<Option<(Task, Vec<TaskImage>)>, String> {
+    if task_id.is_team() {
+        return Ok(load_all_team_tasks()
+            .into_iter()
+            .find(|(task, _)| task.id == *task_id));
+    }
+    store.load_task(task_id).map_err(|error| error.to_string())
+}
+
+/// Kills `process_id`, marks it killed in the Processes list and records the kill.
+fn kill_tracked_process(
+    model: &mut AppModel,
+    manager: &mut ProcessManager,
+    process_id: &str,
+    force: bool,
+) -> Result<(), KillProcessError> {
+    if force {
+        manager.force_kill(process_id)?;
+    } else {
+        manager.kill(process_id)?;
+    }
+    if let Some(process) = model
+        .processes
+        .iter_mut()
+        .find(|process| process.id == process_id)
+    {
+        // After SIGTERM the process runs until it exits; `apply_process_exit` records the kill.
+        process.status = if manager.is_stopping(process_id) {
+            crate::app::ProcessStatus::Stopping
+        } else {
+            crate::app::ProcessStatus::Killed
+        };
+    }
+    record_audit(
+        model,
+        audit_entry_now(AuditAction::Kill, process_id.to_string()),
+    );
+    Ok(())
+}
+
+struct TaskSpawn<'a> {
+    engine: AgentEngine,
+    task_id: &'a TaskId,
+    agent_model: Option<&'a str>,
+    retry: RetryPolicy,
+    worktree: bool,
+    compare: Option<u32>,
+}
+
+fn spawn_task(
+    model: &mut AppModel,
+    manager: &mut ProcessManager,
+    spawn: TaskSpawn<'_>,
+) -> Result<SpawnedAgentProcess, String> {
+    let TaskSpawn {
+        engine,
+        task_id,
+        agent_model,
+        retry,
+        worktree,
+        compare,
+    } = spawn;
+    let store =
+        TaskStore::open_default().map_err(|error| format!("Failed to open tasks DB: {error}"))?;
+    let (task, images) = load_task_or_team(&store, task_id)
+        .map_err(|error| format!("Failed to load task: {error}"))?
+        .ok_or_else(|| "Task not found.".to_string())?;
+
+    let prompt = format_task_spawn_prompt(&task, &images);
+    let image_paths = images
+        .iter()
+        .map(|image| image.source_path.clone())
+        .collect::<Vec<_>>();
+    let title = derive_task_title(&task.body);
+    let spawn_dir = spawn_dir_for(&task.project_path, &title, worktree)?;
+    let spawned = manager
+        .spawn_agent_process_with_retry(
+            AgentSpawn {
+                engine,
+                project_path: &spawn_dir,
+                prompt: &prompt,
+                images: &image_paths,
+                model: agent_model,
+                io_mode: crate::domain::SpawnIoMode::Pipes,
+            },
+            retry,
+        )
+        .map_err(|error| format!("Failed to spawn process: {error}"))?;
+
+    let process = crate::app::ProcessInfo {
+        id: spawned.id.clone(),
+        pid: spawned.pid,
+        engine: spawned.engine,
+        project_path: spawned.project_path.clone(),
+        prompt_preview: spawned.prompt_preview.clone(),
+        started_at: spawned.started_at,
+        status: crate::app::ProcessStatus::Running,
+        io_mode: process_io_mode(spawned.io.clone()),
+        session_id: None,
+        session_log_path: None,
+        retry_policy: retry,
+        attempts: Vec::new(),
+        plan: None,
+        input_wait: None,
+        usage: None,
+        task_id: Some(task_id.clone()),
+        task_title: Some(title.clone()),
+        batch: None,
+        compare,
+        label: Some(title),
+    };
+    manager.record_process_links(&process.id, &process_links(&process));
+    model.processes.push(process);
+    record_audit(
+        model,
+        spawn_audit_entry(&spawned).with_detail(format!(
+            "{} {} from task {task_id}",
+            spawned.engine.label(),
+            spawned.id
+        )),
+    );
+    Ok(spawned)
+}
+
+struct PromptSpawn<'a> {
+    engine: AgentEngine,
+    project_path: &'a Path,
+    prompt: &'a str,
+    images: &'a [PathBuf],
+    agent_model: Option<&'a str>,
+    io_mode: crate::domain::SpawnIoMode,
+    retry: RetryPolicy,
+    worktree: bool,
+    batch: Option<u32>,
+    compare: Option<u32>,
+}
+
+/// Spawns a prompt from New Session, tracks the process in the model and records the spawn.
+fn spawn_prompt(
+    model: &mut AppModel,
+    manager: &mut ProcessManager,
+    spawn: PromptSpawn<'_>,
+) -> Result<SpawnedAgentProcess, String> {
+    let spawn_dir = spawn_dir_for(
+        spawn.project_path,
+        &derive_task_title(spawn.prompt),
+        spawn.worktree,
+    )?;
+    let spawned = manager
+        .spawn_agent_process_with_retry(
+            AgentSpawn {
+                engine: spawn.engine,
+                project_path: &spawn_dir,
+                prompt: spawn.prompt,
+                images: spawn.images,
+                model: spawn.agent_model,
+                io_mode: spawn.io_mode,
+            },
+            spawn.retry,
+        )
+        .map_err(|error| format!("Failed to spawn process: {error}"))?;
+
+    let process = crate::app::ProcessInfo {
+        id: spawned.id.clone(),
+        pid: spawned.pid,
+        engine: spawned.engine,
+        project_path: spawned.project_path.clone(),
+        prompt_preview: spawned.prompt_preview.clone(),
+        started_at: spawned.started_at,
+        status: crate::app::ProcessStatus::Running,
+        io_mode: process_io_mode(spawned.io.clone()),
+        session_id: None,
+        session_log_path: None,
+        retry_policy: spawn.retry,
+        attempts: Vec::new(),
+        plan: None,
+        input_wait: None,
+        usage: None,
+        task_id: None,
+        task_title: None,
+        batch: spawn.batch,
+        compare: spawn.compare,
+        label: None,
+    };
+    manager.record_process_links(&process.id, &process_links(&process));
+    model.processes.push(process);
+    record_audit(model, spawn_audit_entry(&spawned));
+    Ok(spawned)
+}
+
+/// Adds `path` to the New Task or New Session images, writes `[Image N]` at the cursor and closes
+/// the image path dialog. Returns `N`, or `None` when neither screen is open.
+fn insert_prompt_image(view: &mut crate::app::View, path: PathBuf) -> Option<u32> {
+    let (image_paths, editor) = match view {
+        crate::app::View::TaskCreate(task_create) => {
+            task_create.overlay = None;
+            (&mut task_create.image_paths, &mut task_create.editor)
+        }
+        crate::app::View::NewSession(new_session) => {
+            new_session.image_path = None;
+            (&mut new_session.image_paths, &mut new_session.editor)
+        }
+        _ => return None,
+    };
+    image_paths.push(path);
+    let ordinal = u32::try_from(image_paths.len()).unwrap_or(u32::MAX);
+    editor.insert_str(&format!("[Image {ordinal}]"));
+    Some(ordinal)
+}
+
+/// Reads `path` from the New Session project into the prompt's attachments and mentions it as
+/// `@path` at the cursor. Returns the notice to show.
+fn attach_prompt_file(view: &mut crate::app::NewSessionView, path: &Path) -> String {
+    let project = view.from_sessions.project_path.clone();
+    let text = match crate::infra::read_project_file(
+        &project,
+        path,
+        crate::domain::MAX_ATTACHMENT_BYTES,
+    ) {
+        Ok(text) => text,
+        Err(error) => return error.to_string(),
+    };
+    let mention = path
+        .components()
+        .map(|part| part.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+    let mut attachments = view.attachments.clone();
+    attachments.retain(|attachment| attachment.path != mention);
+    let total = crate::domain::attachments_total_bytes(&attachments) + text.len() as u64;
+    if total > crate::domain::MAX_ATTACHMENTS_TOTAL_BYTES {
+        return format!(
+            "Attachments are limited to {} KiB per prompt.",
+            crate::domain::MAX_ATTACHMENTS_TOTAL_BYTES / 1024
+        );
+    }
+    view.attachments = attachments;
+    let lines = text.lines().count();
+    view.attachments.push(crate::domain::PromptAttachment {
+        path: mention.clone(),
+        text,
+    });
+    view.editor.insert_str(&format!("@{mention} "));
+    view.attach_file = None;
+    format!("Attached {mention} ({lines} lines).")
+}
+
+fn next_comparison_number(model: &AppModel) -> u32 {
+    model
+        .processes
+        .iter()
+        .filter_map(|process| process.compare)
+        .max()
+        .unwrap_or(0)
+        .saturating_add(1)
+}
+
+fn comparison_spawned_notice(
+    compare: u32,
+    a: &SpawnedAgentProcess,
+    b: &SpawnedAgentProcess,
+) -> String {
+    format!(
+        "A/B {compare}: spawned {} ({}) and {} ({}); the results open side by side when both finish.",
+        a.engine.label(),
+        a.id,
+        b.engine.label(),
+        b.id
+    )
+}
+
+/// Opens the session diff for each A/B run whose processes have both finished, once.
+fn open_finished_comparisons(model: &mut AppModel, opened: &mut HashSet<u32>) {
+    let ready = crate::app::finished_comparisons(&model.processes)
+        .into_iter()
+        .filter(|(compare, _)| !opened.contains(compare))
+        // A known session whose log path hasn't been resolved yet: try again next tick.
+        .filter(|(_, pair)| {
+            !pair
+                .iter()
+                .any(|process| process.session_id.is_some() && process.session_log_path.is_none())
+        })
+        .map(|(compare, [a, b])| (compare, a.clone(), b.clone()))
+        .collect::<Vec<_>>();
+    for (compare, a, b) in ready {
+        opened.insert(compare);
+        let load = |process: &crate::app::ProcessInfo| {
+            let log_path = process.session_log_path.as_ref().ok_or_else(|| {
+                format!(
+                    "{} ({}) wrote no session log",
+                    process.engine.label(),
+                    process.id
+                )
+            })?;
+            load_session_timeline(log_path)
+                .map(|timeline| timeline.items)
+                .map_err(|error| error.to_string())
+        };
+        match load(&a).and_then(|items_a| Ok((items_a, load(&b)?))) {
+            Ok((items_a, items_b)) => {
+                let title = |process: &crate::app::ProcessInfo| {
+                    format!(
+                        "{} ({}, {})",
+                        process.engine.label(),
+                        process.id,
+                        process.status.label()
+                    )
+                };
+                model.session_diff_overlay = Some(crate::app::SessionDiffOverlay {
+                    title_a: title(&a),
+                    title_b: title(&b),
+                    diff: diff_session_timelines(&items_a, &items_b),
+                    scroll: 0,
+                });
+                model.help_open = false;
+                model.system_menu = None;
+            }
+            Err(error) => {
+                *model = model.with_notice(Some(format!("A/B {compare}: {error}")));
+            }
+        }
+    }
+}
+
+/// Where to run a spawned agent: the project itself, or a new git worktree named after `title`.
+fn spawn_dir_for(project_path: &Path, title: &str, worktree: bool) -> Result<PathBuf, String> {
+    if !worktree {
+        return Ok(project_path.to_path_buf());
+    }
+    crate::infra::create_agent_worktree(project_path, title)
+        .map(|(_worktree, cwd)| cwd)
+        .map_err(|error| format!("Failed to create worktree: {error}"))
+}
+
+fn worktree_notice_suffix(worktree: bool, spawn_dir: &Path) -> String {
+    if worktree {
+        format!(" in worktree {}", spawn_dir.display())
+    } else {
+        String::new()
+    }
+}
+
+fn spawn_audit_entry(spawned: &SpawnedAgentProcess) -> AuditEntry {
+    audit_entry_now(
+        AuditAction::Spawn,
+        spawned.project_path.display().to_string(),
+    )
+    .with_detail(format!(
+        "{} {}: {}",
+        spawned.engine.label(),
+        spawned.id,
+        spawned.prompt_preview
+    ))
 }
 
-fn request_session_index_refresh_optional(
-    tx: &Option<Sender<SessionIndexRequest>>,
-    model: &AppModel,
+/// Audit writes are best-effort: a failure is surfaced as a notice, never blocks the action.
+fn record_audit(model: &mut AppModel, entry: AuditEntry) {
+    if let Err(error) = record_audit_entry(&entry) {
+        *model = model.with_notice(Some(format!("Audit log write failed: {error}")));
+    }
+}
+
+fn audit_deleted_session_logs(
+    model: &mut AppModel,
+    target: String,
+    outcome: &crate::infra::DeleteOutcome,
+    requested: usize,
 ) {
-    let Some(tx) = tx else {
+    if outcome.deleted == 0 {
         return;
+    }
+    let entry = audit_entry_now(AuditAction::Delete, target).with_detail(format!(
+        "{} of {requested} session log(s) deleted",
+        outcome.deleted
+    ));
+    record_audit(model, entry);
+}
+
+fn restored_process_info(restored: crate::infra::RestoredProcess) -> crate::app::ProcessInfo {
+    let crate::infra::RestoredProcess {
+        process,
+        session_id,
+        session_log_path,
+        is_running,
+        exit,
+        links,
+    } = restored;
+    let status = match (is_running, exit) {
+        (true, _) => crate::app::ProcessStatus::Running,
+        (false, Some(crate::infra::RecordedExit::Code(code))) => {
+            crate::app::ProcessStatus::Exited(Some(code))
+        }
+        (false, _) => crate::app::ProcessStatus::Exited(None),
     };
-    request_session_index_refresh(tx, model);
+    crate::app::ProcessInfo {
+        id: process.id,
+        pid: process.pid,
+        engine: process.engine,
+        project_path: process.project_path,
+        prompt_preview: process.prompt_preview,
+        started_at: process.started_at,
+        status,
+        io_mode: process_io_mode(process.io),
+        session_id,
+        session_log_path,
+        retry_policy: links.retry_policy,
+        attempts: Vec::new(),
+        plan: None,
+        input_wait: None,
+        usage: None,
+        task_id: links.task_id,
+        task_title: links.task_title,
+        batch: links.batch,
+        compare: links.compare,
+        label: links.label,
+    }
 }
 
-fn refresh_open_project_stats_overlay(model: &mut AppModel) {
-    let Some(current) = model.project_stats_overlay.clone() else {
+fn process_links(process: &crate::app::ProcessInfo) -> crate::infra::ProcessLinks {
+    crate::infra::ProcessLinks {
+        task_id: process.task_id.clone(),
+        task_title: process.task_title.clone(),
+        batch: process.batch,
+        compare: process.compare,
+        label: process.label.clone(),
+        retry_policy: process.retry_policy,
+    }
+}
+
+fn process_io_mode(io: crate::infra::SpawnedAgentIo) -> crate::app::ProcessIoMode {
+    match io {
+        crate::infra::SpawnedAgentIo::Pipes {
+            stdout_path,
+            stderr_path,
+            log_path,
+        } => crate::app::ProcessIoMode::Pipes {
+            stdout_path,
+            stderr_path,
+            log_path,
+        },
+        crate::infra::SpawnedAgentIo::Tty {
+            transcript_path,
+            log_path,
+        } => crate::app::ProcessIoMode::Tty {
+            transcript_path,
+            log_path,
+        },
+    }
+}
+
+fn run_due_task_schedules(model: &mut AppModel, manager: &mut ProcessManager) {
+    let Ok(db_path) = resolve_tasks_db_path() else {
         return;
     };
-    let Some(project) = model
-        .data
-        .projects
-        .iter()
-        .find(|project| project.project_path == current.project_path)
-    else {
-        model.project_stats_overlay = None;
+    if !db_path.is_file() {
         return;
+    }
+    let store = match TaskStore::open(db_path) {
+        Ok(store) => store,
+        Err(error) => {
+            *model = model.with_notice(Some(format!("Task scheduler disabled: {error}")));
+            return;
+        }
+    };
+    let outcomes = match store.run_due_task_schedules(SystemTime::now(), |schedule| {
+        spawn_task(
+            model,
+            manager,
+            TaskSpawn {
+                engine: schedule.engine,
+                task_id: &schedule.task_id,
+                agent_model: None,
+                retry: RetryPolicy::standard(),
+                worktree: false,
+                compare: None,
+            },
+        )
+    }) {
+        Ok(outcomes) => outcomes,
+        Err(error) => {
+            *model = model.with_notice(Some(format!("Failed to run task schedules: {error}")));
+            return;
+        }
     };
+    if outcomes.is_empty() {
+        return;
+    }
 
-    let mut refreshed =
-        crate::app::ProjectStatsOverlay::from_project(project, model.session_index.as_ref());
-    refreshed.scroll = current.scroll;
-    model.project_stats_overlay = Some(refreshed);
+    let mut started = Vec::new();
+    let mut failures = Vec::new();
+    for outcome in outcomes {
+        match outcome {
+            Ok(spawned) => started.push(format!("{} ({})", spawned.engine.label(), spawned.id)),
+            Err(message) => failures.push(message),
+        }
+    }
+
+    let mut message = format!("Started {} scheduled task(s)", started.len());
+    if !started.is_empty() {
+        message.push_str(&format!(": {}", started.join(", ")));
+    }
+    message.push('.');
+    if let Some(first) = failures.first() {
+        let dropped = failures
+            .iter()
+            .filter(|failure| failure.retry_at.is_none())
+            .count();
+        message.push_str(&format!(" {} failed: {}", failures.len(), first.error));
+        if dropped > 0 {
+            message.push_str(&format!(" Gave up on {dropped} schedule(s)."));
+        } else {
+            message.push_str(" Retrying with backoff.");
+        }
+    }
+    *model = model.with_notice(Some(message));
+
+    if let crate::app::View::Tasks(tasks_view) = &model.view
+        && let Ok(tasks) = store.list_tasks()
+    {
+        model.view = crate::app::View::Tasks(
+            tasks_view
+                .clone()
+                .with_reloaded_tasks(task_summary_rows(tasks)),
+        );
+    }
 }
 
-fn apply_process_signal(model: &mut AppModel, signal: ProcessSignal) {
+fn apply_process_signal(model: &mut AppModel, signal: ProcessSignal, open_spawned_sessions: bool) {
     match signal {
         ProcessSignal::SessionMeta {
             process_id,
@@ -1750,7 +4012,32 @@ fn apply_process_signal(model: &mut AppModel, signal: ProcessSignal) {
                 .iter_mut()
                 .find(|process| process.id == process_id)
             {
-                process.session_log_path = Some(log_path);
+                process.session_log_path = Some(log_path.clone());
+            }
+            let Some(process) = model
+                .processes
+                .iter()
+                .find(|process| process.id == process_id)
+            else {
+                return;
+            };
+            let is_watched = match &model.view {
+                crate::app::View::Processes(view) => view
+                    .selected_process(&model.processes)
+                    .is_some_and(|selected| selected.id == process_id),
+                crate::app::View::ProcessOutput(view) => view.process_id == process_id,
+                _ => false,
+            };
+            if !is_watched {
+                return;
+            }
+            if open_spawned_sessions {
+                let project_path = process.project_path.clone();
+                open_session_detail_by_log_path(model, project_path, log_path);
+            } else {
+                *model = model.with_notice(Some(format!(
+                    "{process_id} started its session; Enter opens the timeline."
+                )));
             }
         }
     }
@@ -1762,8 +4049,158 @@ fn apply_process_exit(model: &mut AppModel, exit: ProcessExit) {
         .iter_mut()
         .find(|process| process.id == exit.process_id)
     {
-        if process.status == crate::app::ProcessStatus::Running {
-            process.status = crate::app::ProcessStatus::Exited(exit.exit_code);
+        process.status = process
+            .status
+            .on_exit(exit.exit_code, exit.retry_in.is_some());
+    }
+}
+
+fn refresh_external_processes(model: &mut AppModel) {
+    let managed_pids = model
+        .processes
+        .iter()
+        .filter(|process| process.status.is_running())
+        .map(|process| process.pid)
+        .collect::<Vec<_>>();
+    model.external_processes = crate::infra::scan_external_agent_processes(&managed_pids)
+        .into_iter()
+        .map(|agent| crate::app::ExternalProcess {
+            session: crate::app::external_agent_session(&model.data, &agent),
+            agent,
+        })
+        .collect();
+}
+
+fn refresh_process_usage(model: &mut AppModel, sampler: &mut ProcessUsageSampler) {
+    let running_pids = model
+        .processes
+        .iter()
+        .filter(|process| process.status.is_running())
+        .map(|process| process.pid)
+        .collect::<Vec<_>>();
+    let usage = sampler.sample(&running_pids);
+    for process in &mut model.processes {
+        process.usage = if process.status.is_running() {
+            usage.get(&process.pid).copied()
+        } else {
+            None
+        };
+    }
+}
+
+/// Follows each process's session log: keeps its plan current and detects when the agent is
+/// waiting on a human. Newly waiting processes are announced with a notice.
+fn refresh_process_session_state(model: &mut AppModel, offsets: &mut HashMap<PathBuf, u64>) {
+    let mut newly_waiting = Vec::new();
+    for process in &mut model.processes {
+        if !process.status.is_running() {
+            process.input_wait = None;
+        }
+        let Some(log_path) = process.session_log_path.as_ref() else {
+            continue;
+        };
+        let offset = offsets.get(log_path).copied().unwrap_or(0);
+        let Ok((items, next_offset)) = read_appended_timeline_items(log_path, offset) else {
+            continue;
+        };
+        offsets.insert(log_path.clone(), next_offset);
+        if let Some(plan) = crate::domain::latest_plan_snapshot(&items) {
+            process.plan = Some(plan).filter(|plan| !plan.steps.is_empty());
+        }
+        if !process.status.is_running() {
+            continue;
+        }
+        let was_waiting = process.input_wait.is_some();
+        // TTY prompts are tracked from the transcript, not the session log.
+        let tty_wait = process.input_wait.take_if(|wait| wait.is_from_tty);
+        process.input_wait =
+            crate::domain::next_input_wait(process.input_wait.take(), &items).or(tty_wait);
+        if let Some(wait) = process.input_wait.as_ref()
+            && !was_waiting
+        {
+            newly_waiting.push(format!("{} {}", process.id, wait.label()));
+        }
+    }
+    if !newly_waiting.is_empty() {
+        *model = model.with_notice(Some(newly_waiting.join("  ·  ")));
+    }
+}
+
+/// Checks the end of each running TTY process's transcript. A transcript that stopped growing
+/// since the last check and ends in a prompt (a permission or confirmation dialog) marks the
+/// process as waiting on a human; new output clears that.
+fn refresh_tty_input_waits(model: &mut AppModel, sizes: &mut HashMap<String, u64>) {
+    const TAIL_BYTES: usize = 8 * 1024;
+    let mut newly_waiting = Vec::new();
+    for process in &mut model.processes {
+        let crate::app::ProcessIoMode::Tty {
+            transcript_path, ..
+        } = &process.io_mode
+        else {
+            continue;
+        };
+        if !process.status.is_running() {
+            sizes.remove(&process.id);
+            continue;
+        }
+        let Ok((tail, size)) = crate::infra::read_tail(transcript_path, TAIL_BYTES) else {
+            continue;
+        };
+        let is_idle = sizes.insert(process.id.clone(), size) == Some(size);
+        if !is_idle {
+            process.input_wait.take_if(|wait| wait.is_from_tty);
+            continue;
+        }
+        if process.input_wait.is_some() {
+            continue;
+        }
+        if let Some(wait) = crate::domain::tty_input_wait(&tail) {
+            newly_waiting.push(format!("{} {}", process.id, wait.label()));
+            process.input_wait = Some(wait);
+        }
+    }
+    if !newly_waiting.is_empty() {
+        *model = model.with_notice(Some(newly_waiting.join("  ·  ")));
+    }
+}
+
+fn apply_process_retry(model: &mut AppModel, retry: ProcessRetry) {
+    let Some(process) = model
+        .processes
+        .iter_mut()
+        .find(|process| process.id == retry.process_id)
+    else {
+        return;
+    };
+    let crate::app::ProcessStatus::RetryPending(exit_code) = process.status else {
+        return;
+    };
+
+    match retry.result {
+        Ok(spawned) => {
+            process.attempts.push(crate::app::ProcessAttempt {
+                pid: process.pid,
+                started_at: process.started_at,
+                exit_code,
+            });
+            process.pid = spawned.pid;
+            process.started_at = spawned.started_at;
+            process.io_mode = process_io_mode(spawned.io);
+            process.status = crate::app::ProcessStatus::Running;
+            process.session_id = None;
+            process.session_log_path = None;
+            process.plan = None;
+            process.input_wait = None;
+            let message = format!(
+                "Retrying {} (attempt {}/{}).",
+                retry.process_id, retry.attempt, process.retry_policy.max_attempts
+            );
+            *model = model.with_notice(Some(message));
+        }
+        Err(error) => {
+            process.status = crate::app::ProcessStatus::Exited(exit_code);
+            let message = format!("Retry of {} failed: {error}", retry.process_id);
+            *model = model.with_notice(Some(message));
         }
     }
 }
@@ -1789,24 +4226,75 @@ enum AttachTtyProcessError {
 
 struct SuspendTuiGuard<'a> {
     terminal: &'a mut Terminal<CrosstermBackend<Stdout>>,
+    /// Set when raw mode, paste, mouse and keyboard modes were turned off too.
+    restores_modes: bool,
 }
 
 impl<'a> SuspendTuiGuard<'a> {
     fn suspend(terminal: &'a mut Terminal<CrosstermBackend<Stdout>>) -> io::Result<Self> {
         execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
         terminal.show_cursor()?;
-        Ok(Self { terminal })
+        Ok(Self {
+            terminal,
+            restores_modes: false,
+        })
+    }
+
+    /// Hands the terminal to a child program (an editor) in cooked mode.
+    fn suspend_for_child(terminal: &'a mut Terminal<CrosstermBackend<Stdout>>) -> io::Result<Self> {
+        // Built first so a failure below still restores the TUI on drop.
+        let guard = Self {
+            terminal,
+            restores_modes: true,
+        };
+        let _ = execute!(
+            guard.terminal.backend_mut(),
+            DisableBracketedPaste,
+            DisableMouseCapture,
+            PopKeyboardEnhancementFlags,
+            LeaveAlternateScreen
+        );
+        guard.terminal.show_cursor()?;
+        disable_raw_mode()?;
+        Ok(guard)
     }
 }
 
 impl Drop for SuspendTuiGuard<'_> {
     fn drop(&mut self) {
+        if self.restores_modes {
+            let _ = enable_raw_mode();
+            let _ = execute!(
+                self.terminal.backend_mut(),
+                EnableBracketedPaste,
+                EnableMouseCapture,
+                PushKeyboardEnhancementFlags(tui_keyboard_flags())
+            );
+        }
         let _ = execute!(self.terminal.backend_mut(), EnterAlternateScreen);
         let _ = self.terminal.hide_cursor();
         let _ = self.terminal.clear();
     }
 }
 
+#[derive(Debug, Error)]
+enum OpenLogInEditorError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+
+    #[error(transparent)]
+    Editor(#[from] crate::infra::OpenEditorError),
+}
+
+fn open_log_in_editor(
+    terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    log_path: &Path,
+) -> Result<(), OpenLogInEditorError> {
+    let _suspended = SuspendTuiGuard::suspend_for_child(terminal)?;
+    crate::infra::open_in_editor(log_path)?;
+    Ok(())
+}
+
 fn attach_tty_process(
     terminal: &mut Terminal<CrosstermBackend<Stdout>>,
     model: &mut AppModel,
@@ -1822,7 +4310,7 @@ fn attach_tty_process(
         return Err(io::Error::other("process not found").into());
     };
 
-    let rx = manager.attach_tty_output(process_id)?;
+    let attachment = manager.attach_tty_output(process_id)?;
     let _suspended = match SuspendTuiGuard::suspend(terminal) {
         Ok(guard) => guard,
         Err(error) => {
@@ -1839,10 +4327,11 @@ fn attach_tty_process(
             process.id,
             process.engine.label()
         );
+        let _ = out.write_all(&attachment.scrollback);
         let _ = out.flush();
     }
 
-    let outcome = attach_tty_loop(model, manager, process_id, rx);
+    let outcome = attach_tty_loop(model, manager, process_id, attachment.output);
     manager.detach_tty_output(process_id);
     let outcome = outcome?;
 
@@ -1980,7 +4469,188 @@ fn attach_tty_loop(
     Err(AttachTtyProcessError::Unsupported)
 }
 
-fn open_process_output_view(model: &mut AppModel, process_id: &str, kind: ProcessOutputKind) {
+/// The session's last assistant output, if it has any.
+fn load_session_output(session: &crate::domain::SessionSummary) -> Result<Option<String>, String> {
+    let log_path =
+        crate::infra::prepare_session_log_path(session).map_err(|error| error.to_string())?;
+    load_last_assistant_output(&log_path)
+        .map(|result| result.output)
+        .map_err(|error| error.to_string())
+}
+
+/// Syncs aliases, project names and tasks through the relay every `interval_minutes`, starting
+/// right away. Stops when the UI goes away.
+fn spawn_metadata_sync(config: MetadataSyncConfig, tx: Sender<MetadataSyncSignal>) {
+    std::thread::spawn(move || {
+        loop {
+            let result = resolve_ccbox_state_dir()
+                .map_err(|error| error.to_string())
+                .and_then(|state_dir| {
+                    let tasks = TaskStore::open_default().map_err(|error| error.to_string())?;
+                    sync_metadata(&state_dir, &tasks, |entries| {
+                        crate::remote::sync_metadata_via_relay(&state_dir, &config.group, entries)
+                            .map_err(|error| error.to_string())
+                    })
+                    .map_err(|error| error.to_string())
+                });
+            if tx.send(MetadataSyncSignal::Synced(result)).is_err() {
+                return;
+            }
+            std::thread::sleep(config.interval());
+        }
+    });
+}
+
+/// Refreshes the remote mirrors off the UI thread; a finished round triggers a rescan that
+/// picks them up. Returns whether a sync was started.
+fn spawn_remote_sync(sources: Vec<RemoteSourceConfig>, tx: Sender<RemoteSyncSignal>) -> bool {
+    let Ok(state_dir) = resolve_ccbox_state_dir() else {
+        return false;
+    };
+    std::thread::spawn(move || {
+        let notice = sync_remote_sources(&sources, &state_dir);
+        let _ = tx.send(RemoteSyncSignal::Synced(notice));
+    });
+    true
+}
+
+fn spawn_device_pairing(tx: Sender<PairingSignal>) {
+    std::thread::spawn(move || {
+        let result = resolve_ccbox_state_dir()
+            .map_err(|error| error.to_string())
+            .and_then(|state_dir| {
+                crate::remote::pair_device_via_relay(&state_dir).map_err(|error| error.to_string())
+            });
+        let _ = tx.send(PairingSignal::Finished(result));
+    });
+}
+
+/// Lists the relay's devices off the UI thread; it is a websocket round trip.
+fn spawn_devices_load(tx: Sender<DevicesSignal>) {
+    std::thread::spawn(move || {
+        let result = resolve_ccbox_state_dir()
+            .map_err(|error| error.to_string())
+            .and_then(|state_dir| {
+                crate::remote::load_relay_devices(&state_dir).map_err(|error| error.to_string())
+            });
+        let _ = tx.send(DevicesSignal::Loaded(result));
+    });
+}
+
+fn spawn_device_change<F>(tx: Sender<DevicesSignal>, done: String, change: F)
+where
+    F: FnOnce(&Path) -> Result<Vec<crate::domain::RelayDevice>, crate::remote::ServeError>
+        + Send
+        + 'static,
+{
+    std::thread::spawn(move || {
+        let result = resolve_ccbox_state_dir()
+            .map_err(|error| error.to_string())
+            .and_then(|state_dir| change(&state_dir).map_err(|error| error.to_string()));
+        let signal = match result {
+            Ok(devices) => DevicesSignal::Changed {
+                devices: Some(devices),
+                notice: done,
+            },
+            Err(error) => DevicesSignal::Changed {
+                devices: None,
+                notice: format!("Failed to update device: {error}"),
+            },
+        };
+        let _ = tx.send(signal);
+    });
+}
+
+/// Shows the relay's devices in an open Devices overlay, keeping the selection in range.
+fn show_devices(model: &mut AppModel, devices: Vec<crate::domain::RelayDevice>) {
+    let Some(overlay) = model.devices_overlay.as_mut() else {
+        return;
+    };
+    overlay.selected = overlay.selected.min(devices.len().saturating_sub(1));
+    overlay.devices = devices;
+    overlay.loading = false;
+}
+
+/// The capture file behind `kind`; TTY processes have a transcript in place of stdout.
+fn process_output_path(
+    process: &crate::app::ProcessInfo,
+    kind: ProcessOutputKind,
+) -> Result<PathBuf, String> {
+    match kind {
+        ProcessOutputKind::Stdout => Ok(process
+            .io_mode
+            .stdout_path()
+            .cloned()
+            .unwrap_or_else(|| process.io_mode.log_path().clone())),
+        ProcessOutputKind::Stderr => process.io_mode.stderr_path().cloned().ok_or_else(|| {
+            format!(
+                "No stderr for {} process {}.",
+                process.io_mode.label(),
+                process.id
+            )
+        }),
+        ProcessOutputKind::Log => Ok(process.io_mode.log_path().clone()),
+    }
+}
+
+/// Saves (or copies) the whole capture file, decrypting sealed output, for `w`/`y` in
+/// Processes and the output view.
+fn export_process_output(
+    model: &mut AppModel,
+    process_id: &str,
+    kind: ProcessOutputKind,
+    destination: Option<PathBuf>,
+    cipher: Option<&ArtifactCipher>,
+    clipboard: &mut SystemClipboard,
+) {
+    let Some(process) = model
+        .processes
+        .iter()
+        .find(|process| process.id == process_id)
+    else {
+        *model = model.with_notice(Some("Process not found.".to_string()));
+        return;
+    };
+    let what = format!("{process_id} {}", kind.label());
+    let text = match process_output_path(process, kind).and_then(|path| {
+        read_artifact_tail(&path, usize::MAX, cipher)
+            .map_err(|error| format!("Failed to read {what}: {error}"))
+    }) {
+        Ok((text, _size)) => text,
+        Err(message) => {
+            *model = model.with_notice(Some(message));
+            return;
+        }
+    };
+    let notice = match destination {
+        None => match clipboard.copy_text(&text, &mut io::stdout()) {
+            Ok(()) => format!("Copied {what} to the clipboard."),
+            Err(error) => format!("Failed to copy {what}: {error}"),
+        },
+        Some(destination) => {
+            let destination = crate::domain::expand_home(&destination, dirs::home_dir().as_deref());
+            match std::fs::write(&destination, &text) {
+                Ok(()) => format!(
+                    "Saved {what} ({}) to {}.",
+                    humansize::format_size(text.len(), humansize::DECIMAL),
+                    destination.display()
+                ),
+                Err(error) => format!(
+                    "Failed to save {what} to {}: {error}",
+                    destination.display()
+                ),
+            }
+        }
+    };
+    *model = model.with_notice(Some(notice));
+}
+
+fn open_process_output_view(
+    model: &mut AppModel,
+    process_id: &str,
+    kind: ProcessOutputKind,
+    cipher: Option<&ArtifactCipher>,
+) {
     let Some(process) = model
         .processes
         .iter()
@@ -1991,32 +4661,24 @@ fn open_process_output_view(model: &mut AppModel, process_id: &str, kind: Proces
         return;
     };
 
-    let file_path = match kind {
-        ProcessOutputKind::Stdout => process
-            .io_mode
-            .stdout_path()
-            .cloned()
-            .unwrap_or_else(|| process.io_mode.log_path().clone()),
-        ProcessOutputKind::Stderr => {
-            let Some(path) = process.io_mode.stderr_path() else {
-                *model = model.with_notice(Some(format!(
-                    "No stderr for {} process {}.",
-                    process.io_mode.label(),
-                    process.id
-                )));
-                return;
-            };
-            path.clone()
+    let file_path = match process_output_path(&process, kind) {
+        Ok(path) => path,
+        Err(message) => {
+            *model = model.with_notice(Some(message));
+            return;
         }
-        ProcessOutputKind::Log => process.io_mode.log_path().clone(),
     };
 
     let return_to = match &model.view {
         crate::app::View::ProcessOutput(output) => output.return_to.clone(),
         _ => Box::new(model.view.clone()),
     };
+    let (raw, follow) = match &model.view {
+        crate::app::View::ProcessOutput(output) => (output.raw, output.follow),
+        _ => (false, false),
+    };
 
-    let (buffer, file_offset) = match read_tail(&file_path, 200_000) {
+    let (buffer, file_offset) = match read_artifact_tail(&file_path, 200_000, cipher) {
         Ok((text, offset)) => (text, offset),
         Err(error) => {
             let message = format!("Failed to read output: {error}");
@@ -2033,6 +4695,11 @@ fn open_process_output_view(model: &mut AppModel, process_id: &str, kind: Proces
         buffer: Arc::new(buffer),
         file_offset,
         scroll: 0,
+        raw,
+        follow,
+        search: None,
+        search_input: None,
+        export_input: None,
     });
 }
 
@@ -2091,6 +4758,7 @@ fn open_session_detail_by_log_path(model: &mut AppModel, project_path: PathBuf,
 
     match load_session_timeline(&log_path) {
         Ok(timeline) => {
+            let timeline = timeline.into_newest_page(crate::domain::TIMELINE_PAGE_ITEMS);
             let session_count = model
                 .data
                 .projects
@@ -2107,6 +4775,7 @@ fn open_session_detail_by_log_path(model: &mut AppModel, project_path: PathBuf,
                 timeline.warnings,
                 timeline.truncated,
             );
+            set_session_detail_older_items(model, timeline.older);
         }
         Err(error) => {
             *model = model.with_notice(Some(format!("Failed to load session: {error}")));
@@ -2114,16 +4783,22 @@ fn open_session_detail_by_log_path(model: &mut AppModel, project_path: PathBuf,
     }
 }
 
+fn set_session_detail_older_items(model: &mut AppModel, older: Vec<crate::domain::TimelineItem>) {
+    if let crate::app::View::SessionDetail(detail_view) = &mut model.view {
+        detail_view.older_items = older.len();
+        detail_view.older = Arc::new(older);
+    }
+}
+
 fn build_session_summary_from_log_path(
     log_path: &std::path::Path,
 ) -> Option<crate::domain::SessionSummary> {
     use std::io::BufRead;
-    let file = std::fs::File::open(log_path).ok()?;
-    let metadata = file.metadata().ok()?;
+    let metadata = std::fs::metadata(log_path).ok()?;
     let file_size_bytes = metadata.len();
     let file_modified = metadata.modified().ok();
 
-    let mut reader = std::io::BufReader::new(file);
+    let mut reader = crate::infra::open_log_reader(log_path).ok()?;
     let mut first_line = String::new();
     let bytes = reader.read_line(&mut first_line).ok()?;
     if bytes == 0 {
@@ -2146,12 +4821,41 @@ fn build_session_summary_from_log_path(
     ))
 }
 
+/// Copies a rename or project move into the OpenCode DB; returns the notice to show.
+fn write_back_opencode(
+    session: &crate::domain::SessionSummary,
+    change: &crate::app::OpenCodeWriteBack,
+) -> Result<String, crate::infra::OpenCodeWriteBackError> {
+    let db_path = crate::infra::resolve_opencode_db_path()?;
+    match change {
+        crate::app::OpenCodeWriteBack::Title(title) => {
+            crate::infra::write_opencode_session_title(&db_path, &session.meta.id, title)?;
+            Ok("Renamed the session in OpenCode too.".to_string())
+        }
+        crate::app::OpenCodeWriteBack::Project(project_path) => {
+            match crate::infra::write_opencode_session_project(
+                &db_path,
+                &session.meta.id,
+                project_path,
+            )? {
+                crate::infra::OpenCodeProjectWriteBack::MovedToProject => {
+                    Ok("Moved the session in OpenCode too.".to_string())
+                }
+                crate::infra::OpenCodeProjectWriteBack::DirectoryOnly => Ok(format!(
+                    "OpenCode has no project at {}; updated the session directory only.",
+                    project_path.display()
+                )),
+            }
+        }
+    }
+}
+
 fn infer_session_title_from_log(session: &crate::domain::SessionSummary) -> Option<String> {
     match session.engine {
         crate::domain::SessionEngine::Codex => infer_codex_session_title(&session.log_path),
         crate::domain::SessionEngine::Claude => infer_claude_session_title(&session.log_path),
         crate::domain::SessionEngine::Gemini => infer_gemini_session_title(&session.log_path),
-        crate::domain::SessionEngine::OpenCode => None,
+        crate::domain::SessionEngine::OpenCode | crate::domain::SessionEngine::Custom => None,
     }
 }
 
@@ -2160,8 +4864,7 @@ fn infer_codex_session_title(log_path: &std::path::Path) -> Option<String> {
 
     const MAX_TITLE_SCAN_LINES: usize = 250;
 
-    let file = std::fs::File::open(log_path).ok()?;
-    let mut reader = std::io::BufReader::new(file);
+    let mut reader = crate::infra::open_log_reader(log_path).ok()?;
 
     let mut first_line = String::new();
     let bytes = reader.read_line(&mut first_line).ok()?;
@@ -2195,8 +4898,7 @@ fn infer_claude_session_title(log_path: &std::path::Path) -> Option<String> {
     const MAX_TITLE_SCAN_LINES: usize = 250;
     const MAX_TITLE_SCAN_BYTES: usize = 200_000;
 
-    let file = std::fs::File::open(log_path).ok()?;
-    let mut reader = std::io::BufReader::new(file);
+    let mut reader = crate::infra::open_log_reader(log_path).ok()?;
 
     let mut bytes_read = 0usize;
     for _ in 0..MAX_TITLE_SCAN_LINES {
@@ -2239,15 +4941,14 @@ fn infer_session_project_from_log(session: &crate::domain::SessionSummary) -> Op
         crate::domain::SessionEngine::Codex => infer_codex_session_project(&session.log_path),
         crate::domain::SessionEngine::Claude => infer_claude_session_project(&session.log_path),
         crate::domain::SessionEngine::Gemini => infer_gemini_session_project(&session.log_path),
-        crate::domain::SessionEngine::OpenCode => None,
+        crate::domain::SessionEngine::OpenCode | crate::domain::SessionEngine::Custom => None,
     }
 }
 
 fn infer_codex_session_project(log_path: &std::path::Path) -> Option<PathBuf> {
     use std::io::BufRead;
 
-    let file = std::fs::File::open(log_path).ok()?;
-    let mut reader = std::io::BufReader::new(file);
+    let mut reader = crate::infra::open_log_reader(log_path).ok()?;
 
     let mut first_line = String::new();
     let bytes = reader.read_line(&mut first_line).ok()?;
@@ -2265,8 +4966,7 @@ fn infer_claude_session_project(log_path: &std::path::Path) -> Option<PathBuf> {
     const MAX_SCAN_LINES: usize = 250;
     const MAX_SCAN_BYTES: usize = 200_000;
 
-    let file = std::fs::File::open(log_path).ok()?;
-    let mut reader = std::io::BufReader::new(file);
+    let mut reader = crate::infra::open_log_reader(log_path).ok()?;
 
     let mut bytes_read = 0usize;
     for _ in 0..MAX_SCAN_LINES {
@@ -2297,6 +4997,49 @@ fn infer_gemini_session_project(log_path: &std::path::Path) -> Option<PathBuf> {
     log_path.parent()?.parent().map(|path| path.to_path_buf())
 }
 
+/// Watches the `extra_session_dirs` that exist, skipping any that is also an engine's default
+/// (already watched). A Gemini root is watched through its `tmp` folder, like the default.
+fn watch_extra_session_dirs(model: &mut AppModel, config: &CcboxConfig) -> Vec<SessionsDirWatcher> {
+    let extra_dirs = resolve_extra_session_dirs(config);
+    let claude_default = resolve_claude_projects_dir().ok();
+    let gemini_default = resolve_gemini_root_dir().ok();
+    let dirs = extra_dirs
+        .codex
+        .iter()
+        .filter(|dir| **dir != model.data.sessions_dir)
+        .cloned()
+        .chain(
+            extra_dirs
+                .claude
+                .iter()
+                .filter(|dir| Some(*dir) != claude_default.as_ref())
+                .cloned(),
+        )
+        .chain(
+            extra_dirs
+                .gemini
+                .iter()
+                .filter(|dir| Some(*dir) != gemini_default.as_ref())
+                .map(|dir| dir.join("tmp")),
+        )
+        .filter(|dir| dir.exists())
+        .collect::<Vec<_>>();
+
+    let mut watchers = Vec::new();
+    for dir in dirs {
+        match watch_sessions_dir(&dir) {
+            Ok(watcher) => watchers.push(watcher),
+            Err(error) => {
+                *model = model.with_notice(Some(format!(
+                    "Auto-rescan disabled for {}: {error} (Ctrl+R to rescan)",
+                    dir.display()
+                )));
+            }
+        }
+    }
+    watchers
+}
+
 fn ensure_session_detail_watcher(
     model: &mut AppModel,
     watcher: &mut Option<crate::infra::SessionFileWatcher>,
@@ -2356,7 +5099,13 @@ fn refresh_open_session_detail(
         return;
     }
 
-    detail_view.items = timeline.items;
+    // Keep the pages loaded so far: items before `older_items` stay unloaded.
+    let mut items = timeline.older;
+    items.extend(timeline.items);
+    let older_items = detail_view.older_items.min(items.len());
+    detail_view.items = items.split_off(older_items);
+    detail_view.older_items = older_items;
+    detail_view.older = Arc::new(items);
     detail_view.turn_contexts = timeline.turn_contexts;
     detail_view.warnings = timeline.warnings;
     detail_view.truncated = timeline.truncated;
@@ -2367,6 +5116,7 @@ fn refresh_open_session_detail(
         .rev()
         .find(|item| item.kind == crate::domain::TimelineItemKind::Assistant)
         .map(|item| item.detail.clone());
+    detail_view.plan = crate::domain::latest_plan_state(&detail_view.items);
 
     if detail_view.items.is_empty() {
         detail_view.selected = 0;
@@ -1,18 +1,27 @@
-use crate::domain::{AgentEngine, SpawnIoMode};
-use crate::infra::{ProcessManager, ProcessSignal, SpawnedAgentIo};
+use crate::domain::{
+    AgentEngine, AuditAction, AuditEntry, RemoteSourceConfig, RemoteSpawnPolicy, RemoteSpawnRule,
+    SpawnIoMode, remote_source_of_project,
+};
+use crate::infra::{Metrics, ProcessManager, ProcessSignal, SpawnedAgentIo};
 use base64::Engine as _;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
 use std::fs;
 use std::io;
-use std::io::{Read, Seek, SeekFrom};
+use std::io::{BufRead, IsTerminal, Read, Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
+use std::sync::mpsc::Receiver;
+use std::sync::{Arc, Mutex, PoisonError};
 use std::time::{Duration, Instant, SystemTime};
 use time::OffsetDateTime;
 use time::format_description::well_known::Rfc3339;
+use tokio::sync::oneshot;
 use uuid::Uuid;
 
+/// How long a spawn that needs local confirmation waits for an answer before it is denied.
+const SPAWN_CONFIRMATION_TIMEOUT: Duration = Duration::from_secs(30);
+
 pub struct ControlPlane {
     sessions_dir: PathBuf,
     process_manager: ProcessManager,
@@ -20,11 +29,63 @@ pub struct ControlPlane {
     processes: HashMap<String, ProcessEntry>,
     log_subscriptions: HashMap<String, LogSubscription>,
     timeline_subscriptions: HashMap<String, TimelineSubscription>,
+    /// Client session per `processes.subscribeStatus` subscription id.
+    status_subscriptions: HashMap<String, String>,
+    /// Processes spawned, exited or matched to a session since the last tick; only tracked
+    /// while someone is subscribed.
+    status_changes: Vec<String>,
+    /// Transcripts from `sessions.share` waiting to be published to the relay on the next tick.
+    pending_transcripts: Vec<TranscriptPublishPayload>,
+    spawn_policy: RemoteSpawnPolicy,
+    /// Remote sources are read-only: nothing is spawned or created in their projects.
+    remote_sources: Vec<RemoteSourceConfig>,
+    metrics: Arc<Metrics>,
+    /// Terminal lines for the `Allow? [y/N]` prompt, read by one prompt at a time.
+    confirmation_lines: Option<Arc<Mutex<Receiver<String>>>>,
+    /// Spawns waiting on that prompt; run or refused from `poll_processes` once it resolves.
+    pending_spawns: Vec<PendingSpawn>,
+    /// Answers to relay calls, sent right after the call or on the next tick.
+    relay_replies: Vec<RelayReply>,
+}
+
+/// Where the answer to a control call goes.
+pub(super) enum CallReply {
+    /// An `rpc/response` frame to this relay session.
+    Relay { session_id: String, rpc_id: String },
+    /// The HTTP handler waiting on this channel.
+    Api(oneshot::Sender<Result<Value, RpcMethodError>>),
+}
+
+struct RelayReply {
+    session_id: String,
+    rpc_id: String,
+    result: Result<Value, RpcMethodError>,
+}
+
+/// A spawn that passed the requesting device's rule.
+struct SpawnRequest {
+    device_id: Option<String>,
+    engine: AgentEngine,
+    project_path: PathBuf,
+    prompt: String,
+    io_mode: SpawnIoMode,
+    max_runtime: Option<Duration>,
+    require_confirmation: bool,
+}
+
+struct PendingSpawn {
+    request: SpawnRequest,
+    reply: CallReply,
+    answer: oneshot::Receiver<bool>,
 }
 
 struct ProcessEntry {
     process: crate::infra::SpawnedAgentProcess,
     status: ProcessStatus,
+    /// Killed once reached (`max_runtime_minutes` of the requesting device).
+    deadline: Option<Instant>,
+    /// Relay device that requested the spawn.
+    device_id: Option<String>,
     exit_code: Option<i32>,
     session_id: Option<String>,
     session_log_path: Option<PathBuf>,
@@ -63,8 +124,24 @@ struct TimelineSubscription {
 impl ControlPlane {
     pub fn new(sessions_dir: PathBuf) -> Result<Self, super::ServeError> {
         let (tx, rx) = std::sync::mpsc::channel::<ProcessSignal>();
-        let process_manager = ProcessManager::new(sessions_dir.clone(), tx)
+        let mut process_manager = ProcessManager::new(sessions_dir.clone(), tx)
             .map_err(|error| super::ServeError::ProcessManager(error.to_string()))?;
+        let config = crate::infra::load_ccbox_config(&crate::infra::resolve_ccbox_config_path()?)?;
+        process_manager.set_artifact_cipher(crate::infra::ArtifactCipher::from_config(
+            &config.encryption,
+        )?);
+        let metrics = Arc::new(Metrics::default());
+        if let Some(metrics_config) = &config.metrics {
+            match crate::infra::start_metrics_server(metrics_config, metrics.clone()) {
+                Ok(addr) => eprintln!("ccbox serve: metrics on http://{addr}/metrics"),
+                Err(error) => eprintln!("ccbox serve: metrics: {error}"),
+            }
+        }
+        process_manager.set_kill_grace(config.kill_grace());
+        process_manager.set_process_limits(config.process_limits);
+        process_manager.set_spawn_args(config.spawn_args);
+        process_manager.set_project_env(config.project_env);
+        spawn_remote_mirror_sync(config.remote_sources.clone());
 
         Ok(Self {
             sessions_dir,
@@ -73,70 +150,66 @@ impl ControlPlane {
             processes: HashMap::new(),
             log_subscriptions: HashMap::new(),
             timeline_subscriptions: HashMap::new(),
+            status_subscriptions: HashMap::new(),
+            status_changes: Vec::new(),
+            pending_transcripts: Vec::new(),
+            spawn_policy: config.remote_spawn,
+            remote_sources: config.remote_sources,
+            metrics,
+            confirmation_lines: None,
+            pending_spawns: Vec::new(),
+            relay_replies: Vec::new(),
         })
     }
 
     pub fn on_connected(&mut self) {
         self.log_subscriptions.clear();
         self.timeline_subscriptions.clear();
+        self.status_subscriptions.clear();
+        self.status_changes.clear();
+        self.relay_replies.clear();
     }
 
     pub async fn tick(&mut self, ws: &mut super::WsStream) -> Result<(), super::ServeError> {
-        self.drain_process_signals();
-        self.drain_process_exits();
+        self.poll_processes();
+        self.drain_relay_replies(ws).await?;
         self.drain_log_subscriptions(ws).await?;
         self.drain_timeline_subscriptions(ws).await?;
+        self.drain_status_changes(ws).await?;
+        self.drain_pending_transcripts(ws).await?;
         Ok(())
     }
 
+    /// Process bookkeeping; the relay loop runs it every tick, the HTTP API on a timer.
+    pub fn poll_processes(&mut self) {
+        self.resolve_pending_spawns();
+        self.drain_process_signals();
+        self.drain_process_exits();
+        self.enforce_max_runtime();
+        self.metrics.set_running_processes(
+            self.processes
+                .values()
+                .filter(|entry| entry.status == ProcessStatus::Running)
+                .count(),
+        );
+    }
+
     pub async fn handle_rpc(
         &mut self,
         ws: &mut super::WsStream,
         session_id: String,
+        device_id: Option<&str>,
         opts: &super::ServeOptions,
         connection_guid: &str,
         req: &super::RpcRequestPayload,
     ) -> Result<(), super::ServeError> {
-        let result = self
-            .dispatch_rpc(opts, connection_guid, &session_id, req)
-            .await;
-        let id = req.id.clone();
-
-        let response_payload = match result {
-            Ok(value) => serde_json::json!({
-                "id": id.clone(),
-                "ok": true,
-                "result": value,
-            }),
-            Err(error) => serde_json::json!({
-                "id": id.clone(),
-                "ok": false,
-                "error": { "code": error.code, "message": error.message },
-            }),
-        };
-
-        let response_env = super::EnvelopeOut {
-            v: super::REMOTE_PROTOCOL_VERSION,
-            type_: "rpc/response",
-            ts: super::now_iso(),
-            payload: response_payload,
-        };
-
-        let response_text = serde_json::to_string(&response_env)?;
-
-        let out_frame = super::EnvelopeOut {
-            v: super::REMOTE_PROTOCOL_VERSION,
-            type_: "mux/frame",
-            ts: super::now_iso(),
-            payload: super::MuxFramePayloadOut {
-                session_id,
-                stream_id: super::CONTROL_V1_STREAM_ID,
-                payload_b64: base64::engine::general_purpose::STANDARD
-                    .encode(response_text.as_bytes()),
-            },
+        let reply = CallReply::Relay {
+            session_id: session_id.clone(),
+            rpc_id: req.id.clone(),
         };
-
-        super::send_json(ws, &out_frame).await
+        self.dispatch_rpc(opts, connection_guid, &session_id, device_id, req, reply)
+            .await;
+        self.drain_relay_replies(ws).await
     }
 
     async fn dispatch_rpc(
@@ -144,35 +217,95 @@ impl ControlPlane {
         opts: &super::ServeOptions,
         connection_guid: &str,
         session_id: &str,
+        device_id: Option<&str>,
         req: &super::RpcRequestPayload,
-    ) -> Result<Value, RpcMethodError> {
-        match req.method.as_str() {
-            "projects.list" => self.handle_projects_list().await,
-            "sessions.list" => self.handle_sessions_list(req).await,
-            "sessions.getTimeline" => self.handle_sessions_get_timeline(req).await,
+        reply: CallReply,
+    ) {
+        let result = match req.method.as_str() {
             "sessions.subscribeTimeline" => {
                 self.handle_sessions_subscribe_timeline(session_id, req)
                     .await
             }
-            "tasks.list" => self.handle_tasks_list().await,
-            "tasks.get" => self.handle_tasks_get(req).await,
-            "tasks.create" => self.handle_tasks_create(req).await,
-            "tasks.delete" => self.handle_tasks_delete(req).await,
-            "tasks.spawn" => self.handle_tasks_spawn(req).await,
             "ccbox.getInfo" => Ok(self.handle_get_info(opts, connection_guid)),
-            "agents.spawn" => self.handle_agents_spawn(req).await,
-            "processes.list" => Ok(self.handle_processes_list()),
-            "processes.kill" => self.handle_processes_kill(req).await,
             "processes.subscribeLogs" => {
                 self.handle_processes_subscribe_logs(session_id, req).await
             }
+            "processes.subscribeStatus" => Ok(self.handle_processes_subscribe_status(session_id)),
+            "sessions.share" => match self.check_command(device_id, &req.method) {
+                Ok(()) => {
+                    self.handle_sessions_share(connection_guid, device_id, req)
+                        .await
+                }
+                Err(error) => Err(error),
+            },
+            _ => return self.call(device_id, req, reply).await,
+        };
+        self.reply(reply, result);
+    }
+
+    /// Request/response methods, shared with the local HTTP API. Subscriptions push frames over
+    /// the relay websocket, so they stay in `dispatch_rpc`. The answer goes to `reply`; a spawn
+    /// that needs local confirmation answers once the prompt resolves, without holding up other
+    /// calls.
+    pub(super) async fn call(
+        &mut self,
+        device_id: Option<&str>,
+        req: &super::RpcRequestPayload,
+        reply: CallReply,
+    ) {
+        if let Err(error) = self.check_command(device_id, &req.method) {
+            return self.reply(reply, Err(error));
+        }
+        let result = match req.method.as_str() {
+            "projects.list" => self.handle_projects_list().await,
+            "sessions.list" => self.handle_sessions_list(req).await,
+            "sessions.getTimeline" => self.handle_sessions_get_timeline(req).await,
+            "tasks.list" => self.handle_tasks_list().await,
+            "tasks.get" => self.handle_tasks_get(req).await,
+            "tasks.create" => self.handle_tasks_create(req).await,
+            "tasks.delete" => self.handle_tasks_delete(device_id, req).await,
+            "tasks.spawn" => match self.handle_tasks_spawn(device_id, req).await {
+                Ok(request) => return self.start_spawn(request, reply),
+                Err(error) => Err(error),
+            },
+            "agents.spawn" => match self.handle_agents_spawn(device_id, req).await {
+                Ok(request) => return self.start_spawn(request, reply),
+                Err(error) => Err(error),
+            },
+            "processes.list" => Ok(self.handle_processes_list()),
+            "processes.kill" => self.handle_processes_kill(device_id, req).await,
             _ => Err(RpcMethodError {
                 code: "UnsupportedCapability".to_string(),
                 message: format!("unsupported method: {}", req.method),
             }),
+        };
+        self.reply(reply, result);
+    }
+
+    fn reply(&mut self, reply: CallReply, result: Result<Value, RpcMethodError>) {
+        match reply {
+            CallReply::Relay { session_id, rpc_id } => self.relay_replies.push(RelayReply {
+                session_id,
+                rpc_id,
+                result,
+            }),
+            CallReply::Api(tx) => {
+                let _ = tx.send(result);
+            }
         }
     }
 
+    /// `allowed_commands` of the requesting device.
+    fn check_command(&self, device_id: Option<&str>, method: &str) -> Result<(), RpcMethodError> {
+        self.spawn_policy
+            .rule_for(device_id)
+            .check_command(method)
+            .map_err(|denied| RpcMethodError {
+                code: "Forbidden".to_string(),
+                message: denied.to_string(),
+            })
+    }
+
     async fn handle_projects_list(&self) -> Result<Value, RpcMethodError> {
         let sessions_dir = self.sessions_dir.clone();
         tokio::task::spawn_blocking(move || build_projects_list(&sessions_dir))
@@ -254,6 +387,56 @@ impl ControlPlane {
         })?
     }
 
+    /// Publishes a read-only snapshot of the session to the relay, viewable at
+    /// `/view/{share_id}` with a link signed by a paired device. The session's project must pass
+    /// the device's `allowed_projects`.
+    async fn handle_sessions_share(
+        &mut self,
+        connection_guid: &str,
+        device_id: Option<&str>,
+        req: &super::RpcRequestPayload,
+    ) -> Result<Value, RpcMethodError> {
+        #[derive(Debug, Deserialize)]
+        struct Params {
+            session_id: String,
+            limit: Option<u32>,
+        }
+
+        let params: Params =
+            serde_json::from_value(req.params.clone()).map_err(|_| RpcMethodError {
+                code: "InvalidParams".to_string(),
+                message: "invalid params".to_string(),
+            })?;
+
+        let session_id = params.session_id.trim().to_string();
+        if session_id.is_empty() {
+            return Err(RpcMethodError {
+                code: "InvalidParams".to_string(),
+                message: "missing session_id".to_string(),
+            });
+        }
+
+        let limit = params.limit.unwrap_or(500).clamp(1, 2000) as usize;
+        let sessions_dir = self.sessions_dir.clone();
+        let rule = self.spawn_policy.rule_for(device_id).clone();
+        let transcript = tokio::task::spawn_blocking(move || {
+            build_session_transcript(&sessions_dir, &session_id, limit, &rule)
+        })
+        .await
+        .map_err(|error| RpcMethodError {
+            code: "Error".to_string(),
+            message: error.to_string(),
+        })??;
+
+        let share_id = transcript.share_id.clone();
+        self.pending_transcripts.push(transcript);
+        Ok(serde_json::json!({
+            "share_id": share_id,
+            "guid": connection_guid,
+            "view_path": format!("/view/{share_id}"),
+        }))
+    }
+
     async fn handle_sessions_subscribe_timeline(
         &mut self,
         client_session_id: &str,
@@ -375,6 +558,7 @@ impl ControlPlane {
                 message: "missing project_path".to_string(),
             });
         }
+        self.check_not_remote(Path::new(&project_path))?;
         let body = params.body.trim().to_string();
         if body.is_empty() {
             return Err(RpcMethodError {
@@ -394,6 +578,7 @@ impl ControlPlane {
 
     async fn handle_tasks_delete(
         &self,
+        device_id: Option<&str>,
         req: &super::RpcRequestPayload,
     ) -> Result<Value, RpcMethodError> {
         #[derive(Debug, Deserialize)]
@@ -415,18 +600,25 @@ impl ControlPlane {
             });
         }
 
-        tokio::task::spawn_blocking(move || build_tasks_delete(&task_id))
+        let audit = crate::infra::audit_entry_now(AuditAction::Delete, format!("task {task_id}"))
+            .with_device(device_id);
+        let result = tokio::task::spawn_blocking(move || build_tasks_delete(&task_id))
             .await
             .map_err(|error| RpcMethodError {
                 code: "Error".to_string(),
                 message: error.to_string(),
-            })?
+            })??;
+        if result["deleted"] == Value::Bool(true) {
+            record_audit(&audit);
+        }
+        Ok(result)
     }
 
     async fn handle_tasks_spawn(
         &mut self,
+        device_id: Option<&str>,
         req: &super::RpcRequestPayload,
-    ) -> Result<Value, RpcMethodError> {
+    ) -> Result<SpawnRequest, RpcMethodError> {
         #[derive(Debug, Deserialize)]
         struct Params {
             task_id: String,
@@ -457,27 +649,13 @@ impl ControlPlane {
                 })??;
 
         let prompt = crate::domain::format_task_spawn_prompt(&task, &images);
-        let spawned = self
-            .process_manager
-            .spawn_agent_process(engine, &task.project_path, &prompt, SpawnIoMode::Pipes)
-            .map_err(|error| RpcMethodError {
-                code: "Error".to_string(),
-                message: error.to_string(),
-            })?;
-
-        let process_id = spawned.id.clone();
-        self.processes.insert(
-            process_id.clone(),
-            ProcessEntry {
-                process: spawned,
-                status: ProcessStatus::Running,
-                exit_code: None,
-                session_id: None,
-                session_log_path: None,
-            },
-        );
-
-        Ok(serde_json::json!({ "process_id": process_id }))
+        self.authorize_spawn(
+            device_id,
+            engine,
+            task.project_path,
+            prompt,
+            SpawnIoMode::Pipes,
+        )
     }
 
     fn handle_get_info(&self, opts: &super::ServeOptions, connection_guid: &str) -> Value {
@@ -496,8 +674,9 @@ impl ControlPlane {
 
     async fn handle_agents_spawn(
         &mut self,
+        device_id: Option<&str>,
         req: &super::RpcRequestPayload,
-    ) -> Result<Value, RpcMethodError> {
+    ) -> Result<SpawnRequest, RpcMethodError> {
         #[derive(Debug, Deserialize)]
         struct Params {
             engine: String,
@@ -533,52 +712,213 @@ impl ControlPlane {
             });
         }
 
-        let prompt = params.prompt;
+        self.authorize_spawn(device_id, engine, project_path, params.prompt, io_mode)
+    }
+
+    /// Refuses projects of a `remote_sources` host, whose sessions are mirrored read-only.
+    fn check_not_remote(&self, project_path: &Path) -> Result<(), RpcMethodError> {
+        match remote_source_of_project(&self.remote_sources, project_path) {
+            Some(source) => Err(RpcMethodError {
+                code: "Forbidden".to_string(),
+                message: format!("`{}` is a read-only remote source", source.name),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Applies the requesting device's spawn rule; the request carries its runtime cap and whether
+    /// to ask at the terminal first.
+    fn authorize_spawn(
+        &self,
+        device_id: Option<&str>,
+        engine: AgentEngine,
+        project_path: PathBuf,
+        prompt: String,
+        io_mode: SpawnIoMode,
+    ) -> Result<SpawnRequest, RpcMethodError> {
+        self.check_not_remote(&project_path)?;
+        let rule = self.spawn_policy.rule_for(device_id);
+        rule.check(engine, &project_path, canonical_path)
+            .map_err(|denied| RpcMethodError {
+                code: "Forbidden".to_string(),
+                message: denied.to_string(),
+            })?;
+        Ok(SpawnRequest {
+            device_id: device_id.map(str::to_string),
+            engine,
+            project_path,
+            prompt,
+            io_mode,
+            max_runtime: rule.max_runtime(),
+            require_confirmation: rule.require_confirmation,
+        })
+    }
+
+    /// Runs an authorized spawn now, or once `Allow? [y/N]` is answered when the rule asks for it.
+    fn start_spawn(&mut self, request: SpawnRequest, reply: CallReply) {
+        if request.require_confirmation {
+            let answer = self.confirm_spawn_locally(&request);
+            self.pending_spawns.push(PendingSpawn {
+                request,
+                reply,
+                answer,
+            });
+            return;
+        }
+        let result = self.run_spawn(request);
+        self.reply(reply, result);
+    }
+
+    /// Answers spawns whose prompt has resolved; the rest keep waiting.
+    fn resolve_pending_spawns(&mut self) {
+        for mut pending in std::mem::take(&mut self.pending_spawns) {
+            let allowed = match pending.answer.try_recv() {
+                Ok(allowed) => allowed,
+                Err(oneshot::error::TryRecvError::Empty) => {
+                    self.pending_spawns.push(pending);
+                    continue;
+                }
+                Err(oneshot::error::TryRecvError::Closed) => false,
+            };
+            let result = if allowed {
+                self.run_spawn(pending.request)
+            } else {
+                Err(RpcMethodError {
+                    code: "Forbidden".to_string(),
+                    message: "spawn was not confirmed on the ccbox host".to_string(),
+                })
+            };
+            self.reply(pending.reply, result);
+        }
+    }
+
+    fn run_spawn(&mut self, request: SpawnRequest) -> Result<Value, RpcMethodError> {
         let spawned = self
             .process_manager
-            .spawn_agent_process(engine, &project_path, &prompt, io_mode)
+            .spawn_agent_process(
+                request.engine,
+                &request.project_path,
+                &request.prompt,
+                request.io_mode,
+            )
             .map_err(|error| RpcMethodError {
                 code: "Error".to_string(),
                 message: error.to_string(),
             })?;
 
+        let device_id = request.device_id.as_deref();
+        record_spawn_audit(device_id, &spawned);
+        let process_id = self.track_spawned_process(spawned, request.max_runtime, device_id);
+        Ok(serde_json::json!({ "process_id": process_id }))
+    }
+
+    /// Asks at the terminal running `ccbox serve`, on a blocking thread so the control loop keeps
+    /// running. The answer is `false` when there is no terminal or no answer within the timeout;
+    /// concurrent spawns are asked one after another.
+    fn confirm_spawn_locally(&mut self, request: &SpawnRequest) -> oneshot::Receiver<bool> {
+        let (tx, rx) = oneshot::channel();
+        if !io::stdin().is_terminal() {
+            let _ = tx.send(false);
+            return rx;
+        }
+        let lines = Arc::clone(
+            self.confirmation_lines
+                .get_or_insert_with(|| Arc::new(Mutex::new(spawn_stdin_line_reader()))),
+        );
+
+        let preview = request
+            .prompt
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .unwrap_or("(empty prompt)")
+            .chars()
+            .take(120)
+            .collect::<String>();
+        let summary = format!(
+            "spawn_request\tdevice={}\tengine={}\tproject={}\tprompt={preview}",
+            request.device_id.as_deref().unwrap_or("unknown"),
+            agent_engine_label(request.engine),
+            request.project_path.display()
+        );
+
+        tokio::task::spawn_blocking(move || {
+            let lines = lines.lock().unwrap_or_else(PoisonError::into_inner);
+            while lines.try_recv().is_ok() {}
+            {
+                let mut out = io::stdout().lock();
+                let _ = writeln!(out, "{summary}");
+                let _ = write!(
+                    out,
+                    "Allow? [y/N] ({}s) ",
+                    SPAWN_CONFIRMATION_TIMEOUT.as_secs()
+                );
+                let _ = out.flush();
+            }
+            let allowed = lines
+                .recv_timeout(SPAWN_CONFIRMATION_TIMEOUT)
+                .is_ok_and(|answer| {
+                    matches!(answer.trim().to_ascii_lowercase().as_str(), "y" | "yes")
+                });
+            let _ = tx.send(allowed);
+        });
+        rx
+    }
+
+    fn track_spawned_process(
+        &mut self,
+        spawned: crate::infra::SpawnedAgentProcess,
+        max_runtime: Option<Duration>,
+        device_id: Option<&str>,
+    ) -> String {
         let process_id = spawned.id.clone();
         self.processes.insert(
             process_id.clone(),
             ProcessEntry {
                 process: spawned,
                 status: ProcessStatus::Running,
+                deadline: max_runtime.map(|runtime| Instant::now() + runtime),
+                device_id: device_id.map(str::to_string),
                 exit_code: None,
                 session_id: None,
                 session_log_path: None,
             },
         );
-
-        Ok(serde_json::json!({ "process_id": process_id }))
+        self.record_status_change(&process_id);
+        process_id
     }
 
     fn handle_processes_list(&self) -> Value {
         let processes = self
             .processes
             .values()
-            .map(|entry| {
-                let started_ts = system_time_to_iso(entry.process.started_at);
-                serde_json::json!({
-                    "process_id": entry.process.id.clone(),
-                    "engine": agent_engine_label(entry.process.engine),
-                    "status": match entry.status { ProcessStatus::Running => "running", ProcessStatus::Exited => "exited" },
-                    "started_ts": started_ts,
-                    "project_path": entry.process.project_path.display().to_string(),
-                    "session_id": entry.session_id.clone(),
-                    "exit_code": entry.exit_code,
-                })
-            })
+            .map(process_json)
             .collect::<Vec<_>>();
         serde_json::json!({ "processes": processes })
     }
 
+    /// Streams a `processes.status` event with the process (as in `processes.list`) whenever one
+    /// is spawned, exits or learns its session id. The response carries the current list.
+    fn handle_processes_subscribe_status(&mut self, session_id: &str) -> Value {
+        let subscription_id = Uuid::new_v4().to_string();
+        self.status_subscriptions
+            .insert(subscription_id.clone(), session_id.to_string());
+        let mut result = self.handle_processes_list();
+        result["subscription_id"] = Value::String(subscription_id);
+        result
+    }
+
+    fn record_status_change(&mut self, process_id: &str) {
+        if !self.status_subscriptions.is_empty()
+            && !self.status_changes.iter().any(|id| id == process_id)
+        {
+            self.status_changes.push(process_id.to_string());
+        }
+    }
+
     async fn handle_processes_kill(
         &mut self,
+        device_id: Option<&str>,
         req: &super::RpcRequestPayload,
     ) -> Result<Value, RpcMethodError> {
         #[derive(Debug, Deserialize)]
@@ -591,8 +931,9 @@ impl ControlPlane {
                 message: "invalid params".to_string(),
             })?;
 
+        let process_id = params.process_id.trim();
         self.process_manager
-            .kill(params.process_id.trim())
+            .kill(process_id)
             .map_err(|error| match error {
                 crate::infra::KillProcessError::NotFound => RpcMethodError {
                     code: "NotFound".to_string(),
@@ -603,6 +944,9 @@ impl ControlPlane {
                     message: err.to_string(),
                 },
             })?;
+        record_audit(
+            &crate::infra::audit_entry_now(AuditAction::Kill, process_id).with_device(device_id),
+        );
 
         Ok(serde_json::json!({ "killed": true }))
     }
@@ -668,6 +1012,7 @@ impl ControlPlane {
                 } => {
                     if let Some(entry) = self.processes.get_mut(&process_id) {
                         entry.session_id = Some(session_id);
+                        self.record_status_change(&process_id);
                     }
                 }
                 ProcessSignal::SessionLogPath {
@@ -682,11 +1027,30 @@ impl ControlPlane {
         }
     }
 
+    fn enforce_max_runtime(&mut self) {
+        let now = Instant::now();
+        for entry in self.processes.values_mut() {
+            if entry.status == ProcessStatus::Running
+                && entry.deadline.is_some_and(|deadline| now >= deadline)
+            {
+                entry.deadline = None;
+                if self.process_manager.kill(&entry.process.id).is_ok() {
+                    record_audit(
+                        &crate::infra::audit_entry_now(AuditAction::Kill, entry.process.id.clone())
+                            .with_device(entry.device_id.as_deref())
+                            .with_detail("max_runtime_minutes exceeded"),
+                    );
+                }
+            }
+        }
+    }
+
     fn drain_process_exits(&mut self) {
         for exit in self.process_manager.poll_exits() {
             if let Some(entry) = self.processes.get_mut(&exit.process_id) {
                 entry.status = ProcessStatus::Exited;
                 entry.exit_code = exit.exit_code;
+                self.record_status_change(&exit.process_id);
             }
         }
     }
@@ -804,6 +1168,93 @@ impl ControlPlane {
         Ok(())
     }
 
+    async fn drain_status_changes(
+        &mut self,
+        ws: &mut super::WsStream,
+    ) -> Result<(), super::ServeError> {
+        let changes = std::mem::take(&mut self.status_changes);
+        let mut session_ids = self
+            .status_subscriptions
+            .values()
+            .cloned()
+            .collect::<Vec<_>>();
+        session_ids.sort();
+        session_ids.dedup();
+
+        for process_id in changes {
+            let Some(entry) = self.processes.get(&process_id) else {
+                continue;
+            };
+            let payload = process_json(entry);
+            for session_id in &session_ids {
+                self.send_event(ws, session_id, "processes.status", &payload)
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn drain_relay_replies(
+        &mut self,
+        ws: &mut super::WsStream,
+    ) -> Result<(), super::ServeError> {
+        for reply in std::mem::take(&mut self.relay_replies) {
+            let response_payload = match reply.result {
+                Ok(value) => serde_json::json!({
+                    "id": reply.rpc_id,
+                    "ok": true,
+                    "result": value,
+                }),
+                Err(error) => serde_json::json!({
+                    "id": reply.rpc_id,
+                    "ok": false,
+                    "error": { "code": error.code, "message": error.message },
+                }),
+            };
+
+            let response_env = super::EnvelopeOut {
+                v: super::REMOTE_PROTOCOL_VERSION,
+                type_: "rpc/response",
+                ts: super::now_iso(),
+                payload: response_payload,
+            };
+
+            let response_text = serde_json::to_string(&response_env)?;
+
+            let out_frame = super::EnvelopeOut {
+                v: super::REMOTE_PROTOCOL_VERSION,
+                type_: "mux/frame",
+                ts: super::now_iso(),
+                payload: super::MuxFramePayloadOut {
+                    session_id: reply.session_id,
+                    stream_id: super::CONTROL_V1_STREAM_ID,
+                    payload_b64: base64::engine::general_purpose::STANDARD
+                        .encode(response_text.as_bytes()),
+                },
+            };
+
+            super::send_json(ws, &out_frame).await?;
+        }
+        Ok(())
+    }
+
+    async fn drain_pending_transcripts(
+        &mut self,
+        ws: &mut super::WsStream,
+    ) -> Result<(), super::ServeError> {
+        for transcript in std::mem::take(&mut self.pending_transcripts) {
+            let env = super::EnvelopeOut {
+                v: super::REMOTE_PROTOCOL_VERSION,
+                type_: "ccbox/transcript/publish",
+                ts: super::now_iso(),
+                payload: transcript,
+            };
+            super::send_json(ws, &env).await?;
+        }
+        Ok(())
+    }
+
     async fn send_event<P: Serialize>(
         &self,
         ws: &mut super::WsStream,
@@ -836,9 +1287,9 @@ impl ControlPlane {
 }
 
 #[derive(Debug)]
-struct RpcMethodError {
-    code: String,
-    message: String,
+pub(super) struct RpcMethodError {
+    pub(super) code: String,
+    pub(super) message: String,
 }
 
 #[derive(Serialize)]
@@ -873,6 +1324,14 @@ struct SessionsTimelineEvent {
 }
 
 #[derive(Serialize)]
+struct TranscriptPublishPayload {
+    share_id: String,
+    session_id: String,
+    title: String,
+    items: Vec<TimelineItemOut>,
+}
+
+#[derive(Debug, Serialize)]
 struct TimelineItemOut {
     kind: &'static str,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -889,6 +1348,57 @@ struct TimelineItemOut {
     detail: String,
 }
 
+/// A process as listed by `processes.list` and streamed by `processes.status`.
+fn process_json(entry: &ProcessEntry) -> Value {
+    serde_json::json!({
+        "process_id": entry.process.id.clone(),
+        "engine": agent_engine_label(entry.process.engine),
+        "status": match entry.status { ProcessStatus::Running => "running", ProcessStatus::Exited => "exited" },
+        "started_ts": system_time_to_iso(entry.process.started_at),
+        "project_path": entry.process.project_path.display().to_string(),
+        "session_id": entry.session_id.clone(),
+        "exit_code": entry.exit_code,
+    })
+}
+
+fn spawn_stdin_line_reader() -> Receiver<String> {
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        for line in io::stdin().lock().lines() {
+            let Ok(line) = line else {
+                break;
+            };
+            if tx.send(line).is_err() {
+                break;
+            }
+        }
+    });
+    rx
+}
+
+/// Keeps the `remote_sources` mirrors fresh for the list handlers, which only read them.
+fn spawn_remote_mirror_sync(sources: Vec<RemoteSourceConfig>) {
+    if sources.is_empty() {
+        return;
+    }
+    let Ok(state_dir) = crate::infra::resolve_ccbox_state_dir() else {
+        return;
+    };
+    std::thread::spawn(move || {
+        loop {
+            if let Some(notice) = crate::infra::sync_remote_sources(&sources, &state_dir) {
+                eprintln!("ccbox serve: {notice}");
+            }
+            std::thread::sleep(crate::infra::REMOTE_SYNC_INTERVAL);
+        }
+    });
+}
+
+/// The path with `..` and symlinks resolved, for checks against `allowed_projects`.
+fn canonical_path(path: &Path) -> Option<PathBuf> {
+    fs::canonicalize(path).ok()
+}
+
 fn parse_agent_engine(raw: &str) -> Result<AgentEngine, RpcMethodError> {
     match raw.trim().to_lowercase().as_str() {
         "codex" => Ok(AgentEngine::Codex),
@@ -916,6 +1426,7 @@ fn agent_engine_label(engine: AgentEngine) -> &'static str {
     match engine {
         AgentEngine::Codex => "codex",
         AgentEngine::Claude => "claude",
+        AgentEngine::OpenCode => "opencode",
     }
 }
 
@@ -977,6 +1488,7 @@ fn session_engine_label(engine: crate::domain::SessionEngine) -> &'static str {
         crate::domain::SessionEngine::Claude => "claude",
         crate::domain::SessionEngine::Gemini => "gemini",
         crate::domain::SessionEngine::OpenCode => "opencode",
+        crate::domain::SessionEngine::Custom => "custom",
     }
 }
 
@@ -1005,18 +1517,40 @@ fn build_sessions_list(sessions_dir: &Path, project_id: &str) -> Value {
         .unwrap_or_else(|_| serde_json::json!({ "sessions": [] }))
 }
 
-fn resolve_session_log_path_and_size(
+fn resolve_session(
     sessions_dir: &Path,
     session_id: &str,
-) -> Result<(PathBuf, u64), RpcMethodError> {
+) -> Result<crate::domain::SessionSummary, RpcMethodError> {
     let scan = crate::infra::scan_all_sessions(sessions_dir);
-    let Some(session) = scan.sessions.into_iter().find(|s| s.meta.id == session_id) else {
+    let key = crate::domain::SessionKey::parse(session_id);
+    let matches = scan
+        .sessions
+        .into_iter()
+        .filter(|session| match &key {
+            Some(key) => key.matches(session),
+            None => session.meta.id == session_id,
+        })
+        .collect::<Vec<_>>();
+    if matches
+        .iter()
+        .any(|session| session.engine != matches[0].engine)
+    {
         return Err(RpcMethodError {
-            code: "NotFound".to_string(),
-            message: "session not found".to_string(),
+            code: "InvalidParams".to_string(),
+            message: "session id exists for several engines; pass it as engine:id".to_string(),
         });
-    };
+    }
+    matches.into_iter().next().ok_or_else(|| RpcMethodError {
+        code: "NotFound".to_string(),
+        message: "session not found".to_string(),
+    })
+}
 
+fn resolve_session_log_path_and_size(
+    sessions_dir: &Path,
+    session_id: &str,
+) -> Result<(PathBuf, u64), RpcMethodError> {
+    let session = resolve_session(sessions_dir, session_id)?;
     let size = match fs::metadata(&session.log_path) {
         Ok(meta) => meta.len(),
         Err(error) if error.kind() == io::ErrorKind::NotFound => {
@@ -1045,6 +1579,8 @@ fn timeline_kind_label(kind: crate::domain::TimelineItemKind) -> &'static str {
         crate::domain::TimelineItemKind::ToolCall => "tool_call",
         crate::domain::TimelineItemKind::ToolOutput => "tool_output",
         crate::domain::TimelineItemKind::TokenCount => "token_count",
+        crate::domain::TimelineItemKind::Todo => "todo",
+        crate::domain::TimelineItemKind::Compaction => "compaction",
         crate::domain::TimelineItemKind::Note => "note",
     }
 }
@@ -1100,6 +1636,47 @@ fn build_session_timeline(
     }))
 }
 
+/// Conversation items of the session (newest `limit`), without turn markers and token counts.
+fn build_session_transcript(
+    sessions_dir: &Path,
+    session_id: &str,
+    limit: usize,
+    rule: &RemoteSpawnRule,
+) -> Result<TranscriptPublishPayload, RpcMethodError> {
+    let session = resolve_session(sessions_dir, session_id)?;
+    rule.check_project(&session.meta.cwd, canonical_path)
+        .map_err(|denied| RpcMethodError {
+            code: "Forbidden".to_string(),
+            message: denied.to_string(),
+        })?;
+    let timeline =
+        crate::infra::load_session_timeline(&session.log_path).map_err(|error| RpcMethodError {
+            code: "Error".to_string(),
+            message: error.to_string(),
+        })?;
+
+    let mut items = timeline
+        .items
+        .into_iter()
+        .filter(|item| {
+            !matches!(
+                item.kind,
+                crate::domain::TimelineItemKind::Turn | crate::domain::TimelineItemKind::TokenCount
+            )
+        })
+        .collect::<Vec<_>>();
+    if items.len() > limit {
+        items = items.split_off(items.len().saturating_sub(limit));
+    }
+
+    Ok(TranscriptPublishPayload {
+        share_id: Uuid::new_v4().to_string(),
+        session_id: crate::domain::SessionKey::new(session.engine, session.meta.id).to_string(),
+        title: session.title,
+        items: items.into_iter().map(timeline_item_to_out).collect(),
+    })
+}
+
 fn compute_timeline_update(
     log_path: &Path,
     cursor_bytes: u64,
@@ -1233,6 +1810,29 @@ fn build_tasks_create(
     Ok(serde_json::json!({ "task_id": id.to_string() }))
 }
 
+/// Audit writes never fail the RPC; problems are reported on the `ccbox serve` terminal.
+fn record_audit(entry: &AuditEntry) {
+    if let Err(error) = crate::infra::record_audit_entry(entry) {
+        eprintln!("ccbox serve: audit log: {error}");
+    }
+}
+
+fn record_spawn_audit(device_id: Option<&str>, spawned: &crate::infra::SpawnedAgentProcess) {
+    record_audit(
+        &crate::infra::audit_entry_now(
+            AuditAction::Spawn,
+            spawned.project_path.display().to_string(),
+        )
+        .with_device(device_id)
+        .with_detail(format!(
+            "{} {}: {}",
+            agent_engine_label(spawned.engine),
+            spawned.id,
+            spawned.prompt_preview
+        )),
+    );
+}
+
 fn build_tasks_delete(task_id: &str) -> Result<Value, RpcMethodError> {
     let store = crate::infra::TaskStore::open_default().map_err(|error| RpcMethodError {
         code: "Error".to_string(),
@@ -1317,3 +1917,33 @@ fn system_time_to_iso(value: SystemTime) -> Option<String> {
     let timestamp = OffsetDateTime::from(value);
     timestamp.format(&Rfc3339).ok()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn allowed_projects_are_checked_after_resolving_dot_dot_and_symlinks() -> io::Result<()> {
+        let dir = tempdir()?;
+        let app = dir.path().join("work").join("app");
+        let home = dir.path().join("home");
+        fs::create_dir_all(app.join("api"))?;
+        fs::create_dir_all(&home)?;
+        let rule = RemoteSpawnRule {
+            allowed_projects: Some(vec![app.clone()]),
+            ..RemoteSpawnRule::default()
+        };
+        let check = |path: &Path| rule.check(AgentEngine::Codex, path, canonical_path);
+
+        assert_eq!(check(&app.join("api")), Ok(()));
+        assert!(check(&app.join("..").join("..").join("home")).is_err());
+        assert!(check(&app.join("missing")).is_err());
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(&home, app.join("link"))?;
+            assert!(check(&app.join("link")).is_err());
+        }
+        Ok(())
+    }
+}
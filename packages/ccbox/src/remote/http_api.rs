@@ -0,0 +1,378 @@
+use super::control::{CallReply, ControlPlane, RpcMethodError};
+use super::{RpcRequestPayload, ServeError};
+use axum::extract::{Path as UrlPath, Query, Request, State};
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{delete, get, post};
+use axum::{Json, Router};
+use base64::Engine as _;
+use rand_core::{OsRng, RngCore as _};
+use serde::Deserialize;
+use serde_json::Value;
+use std::fs;
+use std::io;
+use std::io::Write as _;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
+use tokio::time::MissedTickBehavior;
+
+/// How often finished processes and runtime limits are checked between requests.
+const PROCESS_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+pub(super) struct ApiOptions {
+    pub(super) port: u16,
+    pub(super) ccbox_id: String,
+    pub(super) label: Option<String>,
+    pub(super) token_path: PathBuf,
+}
+
+/// A request handed from an HTTP handler to the task that owns the `ControlPlane`.
+struct ApiCall {
+    method: &'static str,
+    params: Value,
+    reply: oneshot::Sender<Result<Value, RpcMethodError>>,
+}
+
+#[derive(Clone)]
+struct ApiState {
+    calls: mpsc::Sender<ApiCall>,
+    token: Arc<str>,
+    ccbox_id: Arc<str>,
+    label: Option<Arc<str>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SessionsQuery {
+    project_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct TimelineQuery {
+    limit: Option<u32>,
+    cursor: Option<u64>,
+}
+
+/// Serves the control-v1 methods as REST routes on loopback until Ctrl+C. Every request needs
+/// `Authorization: Bearer <token>` with the token from `api.token_path`.
+///
+/// The `ControlPlane` is not `Sync`, so it stays on this (non-spawned) future and handlers reach
+/// it over a channel; calls run one at a time, as they do over the relay.
+pub(super) async fn serve_http_api(
+    api: ApiOptions,
+    sessions_dir: PathBuf,
+) -> Result<(), ServeError> {
+    let token = load_or_create_api_token(&api.token_path)
+        .map_err(|error| ServeError::HttpApi(format!("api token: {error}")))?;
+    let mut control = ControlPlane::new(sessions_dir)?;
+    let (calls_tx, mut calls_rx) = mpsc::channel::<ApiCall>(32);
+    let state = ApiState {
+        calls: calls_tx,
+        token: token.into(),
+        ccbox_id: api.ccbox_id.into(),
+        label: api.label.map(Into::into),
+    };
+
+    let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, api.port));
+    let listener = tokio::net::TcpListener::bind(addr)
+        .await
+        .map_err(|error| ServeError::HttpApi(format!("bind {addr}: {error}")))?;
+    let local_addr = listener
+        .local_addr()
+        .map_err(|error| ServeError::HttpApi(error.to_string()))?;
+
+    let mut out = io::stdout().lock();
+    let _ = writeln!(out, "ccbox serve");
+    let _ = writeln!(out, "ccbox_id={}", state.ccbox_id);
+    let _ = writeln!(out, "mode=http-api");
+    let _ = writeln!(out, "api_url=http://{local_addr}/v1");
+    let _ = writeln!(out, "api_token_path={}", api.token_path.display());
+    drop(out);
+
+    let server = tokio::spawn(async move {
+        axum::serve(listener, build_router(state))
+            .with_graceful_shutdown(async {
+                let _ = tokio::signal::ctrl_c().await;
+            })
+            .await
+    });
+
+    let mut interval = tokio::time::interval(PROCESS_POLL_INTERVAL);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    loop {
+        tokio::select! {
+            call = calls_rx.recv() => {
+                // All senders live in the router; `None` means the server has shut down.
+                let Some(call) = call else { break };
+                let req = RpcRequestPayload {
+                    id: String::new(),
+                    method: call.method.to_string(),
+                    params: call.params,
+                };
+                control.call(None, &req, CallReply::Api(call.reply)).await;
+            }
+            _ = interval.tick() => control.poll_processes(),
+        }
+    }
+
+    match server.await {
+        Ok(Ok(())) => Ok(()),
+        Ok(Err(error)) => Err(ServeError::HttpApi(error.to_string())),
+        Err(error) => Err(ServeError::HttpApi(error.to_string())),
+    }
+}
+
+fn build_router(state: ApiState) -> Router {
+    Router::new()
+        .route("/v1/info", get(info))
+        .route("/v1/projects", get(projects_list))
+        .route("/v1/sessions", get(sessions_list))
+        .route("/v1/sessions/{session_id}/timeline", get(sessions_timeline))
+        .route("/v1/tasks", get(tasks_list).post(tasks_create))
+        .route("/v1/tasks/{task_id}", get(tasks_get).delete(tasks_delete))
+        .route("/v1/tasks/{task_id}/spawn", post(tasks_spawn))
+        .route("/v1/processes", get(processes_list).post(agents_spawn))
+        .route("/v1/processes/{process_id}", delete(processes_kill))
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            require_bearer_token,
+        ))
+        .with_state(state)
+}
+
+async fn require_bearer_token(
+    State(state): State<ApiState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if is_authorized(request.headers(), &state.token) {
+        next.run(request).await
+    } else {
+        error_response(
+            StatusCode::UNAUTHORIZED,
+            "Unauthorized",
+            "missing or invalid bearer token",
+        )
+    }
+}
+
+fn is_authorized(headers: &HeaderMap, token: &str) -> bool {
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|presented| constant_time_eq(presented.trim().as_bytes(), token.as_bytes()))
+}
+
+fn constant_time_eq(left: &[u8], right: &[u8]) -> bool {
+    left.len() == right.len()
+        && left
+            .iter()
+            .zip(right)
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
+async fn info(State(state): State<ApiState>) -> Response {
+    Json(serde_json::json!({
+        "ccbox_id": &*state.ccbox_id,
+        "label": state.label.as_deref(),
+        "version": env!("CARGO_PKG_VERSION"),
+        "now_ts": super::now_iso(),
+    }))
+    .into_response()
+}
+
+async fn projects_list(State(state): State<ApiState>) -> Response {
+    call(&state, "projects.list", Value::Null).await
+}
+
+async fn sessions_list(
+    State(state): State<ApiState>,
+    Query(query): Query<SessionsQuery>,
+) -> Response {
+    let params = serde_json::json!({ "project_id": query.project_id });
+    call(&state, "sessions.list", params).await
+}
+
+async fn sessions_timeline(
+    State(state): State<ApiState>,
+    UrlPath(session_id): UrlPath<String>,
+    Query(query): Query<TimelineQuery>,
+) -> Response {
+    let params = serde_json::json!({
+        "session_id": session_id,
+        "limit": query.limit,
+        "cursor": query.cursor,
+    });
+    call(&state, "sessions.getTimeline", params).await
+}
+
+async fn tasks_list(State(state): State<ApiState>) -> Response {
+    call(&state, "tasks.list", Value::Null).await
+}
+
+async fn tasks_create(State(state): State<ApiState>, Json(body): Json<Value>) -> Response {
+    call(&state, "tasks.create", body).await
+}
+
+async fn tasks_get(State(state): State<ApiState>, UrlPath(task_id): UrlPath<String>) -> Response {
+    call(
+        &state,
+        "tasks.get",
+        serde_json::json!({ "task_id": task_id }),
+    )
+    .await
+}
+
+async fn tasks_delete(
+    State(state): State<ApiState>,
+    UrlPath(task_id): UrlPath<String>,
+) -> Response {
+    call(
+        &state,
+        "tasks.delete",
+        serde_json::json!({ "task_id": task_id }),
+    )
+    .await
+}
+
+async fn tasks_spawn(
+    State(state): State<ApiState>,
+    UrlPath(task_id): UrlPath<String>,
+    Json(body): Json<Value>,
+) -> Response {
+    call(&state, "tasks.spawn", with_field(body, "task_id", task_id)).await
+}
+
+async fn processes_list(State(state): State<ApiState>) -> Response {
+    call(&state, "processes.list", Value::Null).await
+}
+
+async fn agents_spawn(State(state): State<ApiState>, Json(body): Json<Value>) -> Response {
+    call(&state, "agents.spawn", body).await
+}
+
+async fn processes_kill(
+    State(state): State<ApiState>,
+    UrlPath(process_id): UrlPath<String>,
+) -> Response {
+    let params = serde_json::json!({ "process_id": process_id });
+    call(&state, "processes.kill", params).await
+}
+
+/// Path segments win over body fields of the same name.
+fn with_field(body: Value, name: &str, value: String) -> Value {
+    let mut object = match body {
+        Value::Object(object) => object,
+        _ => serde_json::Map::new(),
+    };
+    object.insert(name.to_string(), Value::String(value));
+    Value::Object(object)
+}
+
+async fn call(state: &ApiState, method: &'static str, params: Value) -> Response {
+    let (reply, response) = oneshot::channel();
+    let call = ApiCall {
+        method,
+        params,
+        reply,
+    };
+    if state.calls.send(call).await.is_err() {
+        return error_response(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Error",
+            "ccbox serve is shutting down",
+        );
+    }
+    let Ok(result) = response.await else {
+        return error_response(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Error",
+            "ccbox serve is shutting down",
+        );
+    };
+    match result {
+        Ok(value) => Json(value).into_response(),
+        Err(RpcMethodError { code, message }) => {
+            error_response(status_for_error_code(&code), &code, &message)
+        }
+    }
+}
+
+fn status_for_error_code(code: &str) -> StatusCode {
+    match code {
+        "InvalidParams" => StatusCode::BAD_REQUEST,
+        "Forbidden" => StatusCode::FORBIDDEN,
+        "NotFound" | "UnsupportedCapability" => StatusCode::NOT_FOUND,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+fn error_response(status: StatusCode, code: &str, message: &str) -> Response {
+    let body = serde_json::json!({ "error": { "code": code, "message": message } });
+    (status, Json(body)).into_response()
+}
+
+/// Reads the bearer token, generating a random one (mode 0600) on first use.
+fn load_or_create_api_token(path: &Path) -> io::Result<String> {
+    match fs::read_to_string(path) {
+        Ok(raw) if !raw.trim().is_empty() => return Ok(raw.trim().to_string()),
+        Ok(_) => {}
+        Err(error) if error.kind() == io::ErrorKind::NotFound => {}
+        Err(error) => return Err(error),
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    let token = base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(bytes);
+    super::write_secret_file(path, &token)?;
+    Ok(token)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+    use tempfile::tempdir;
+
+    #[test]
+    fn api_token_is_generated_once_and_checked_from_the_bearer_header() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("remote").join("api_token");
+        let token = load_or_create_api_token(&path).expect("create token");
+        assert_eq!(token.len(), 43);
+        assert_eq!(
+            load_or_create_api_token(&path).expect("reload token"),
+            token
+        );
+
+        let mut headers = HeaderMap::new();
+        assert!(!is_authorized(&headers, &token));
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_str(&format!("Bearer {token}")).expect("header"),
+        );
+        assert!(is_authorized(&headers, &token));
+        assert!(!is_authorized(&headers, "other-token"));
+    }
+
+    #[test]
+    fn maps_control_error_codes_to_http_status() {
+        assert_eq!(
+            status_for_error_code("InvalidParams"),
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(status_for_error_code("NotFound"), StatusCode::NOT_FOUND);
+        assert_eq!(status_for_error_code("Forbidden"), StatusCode::FORBIDDEN);
+        assert_eq!(
+            status_for_error_code("Error"),
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+    }
+}
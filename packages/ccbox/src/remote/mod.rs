@@ -24,6 +24,7 @@ use url::Url;
 use uuid::Uuid;
 
 mod control;
+mod http_api;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ServeOptions {
@@ -36,6 +37,8 @@ pub struct ServeOptions {
     pub print_identity: bool,
     pub no_relay: bool,
     pub listen_addr: Option<String>,
+    /// Serve the local HTTP API on `127.0.0.1:<port>` instead of connecting to a relay.
+    pub api_port: Option<u16>,
 }
 
 #[derive(Debug, Error)]
@@ -58,6 +61,9 @@ pub enum ServeError {
     #[error("invalid listen addr: {0}")]
     ListenAddr(String),
 
+    #[error("http api error: {0}")]
+    HttpApi(String),
+
     #[error("local relay error: {0}")]
     LocalRelay(String),
 
@@ -73,12 +79,27 @@ pub enum ServeError {
     #[error("pairing bootstrap failed: {0}")]
     PairingBootstrap(String),
 
+    #[error("metadata sync failed: {0}")]
+    MetadataSync(String),
+
+    #[error("relay devices request failed: {0}")]
+    RelayDevices(String),
+
+    #[error("failed to access relay registration {path}: {source}")]
+    Registration { path: String, source: io::Error },
+
     #[error("invalid base64 payload: {0}")]
     Base64(String),
 
     #[error("process manager error: {0}")]
     ProcessManager(String),
 
+    #[error(transparent)]
+    Config(#[from] crate::infra::LoadCcboxConfigError),
+
+    #[error(transparent)]
+    Encryption(#[from] crate::infra::EncryptionError),
+
     #[error(transparent)]
     Json(#[from] serde_json::Error),
 }
@@ -186,11 +207,118 @@ pub fn load_or_create_ccbox_identity(state_dir: &Path) -> Result<CcboxIdentity,
     })
 }
 
+/// The relay this machine registered with from the TUI pairing flow, stored at
+/// `<state>/remote/relay.json`. `ccbox serve` reuses its guid so paired clients keep their link.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct RelayRegistration {
+    pub guid: String,
+    pub relay_domain: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub relay_base_url: Option<String>,
+}
+
+impl RelayRegistration {
+    fn guid(&self) -> Result<Uuid, ServeError> {
+        Uuid::parse_str(self.guid.trim())
+            .map_err(|_| ServeError::RelayUrl(format!("invalid guid in relay.json: {}", self.guid)))
+    }
+
+    fn serve_options(&self) -> ServeOptions {
+        ServeOptions {
+            label: None,
+            relay_domain: self.relay_domain.clone(),
+            relay_url: None,
+            relay_base_url: self.relay_base_url.clone(),
+            pairing_code: None,
+            enable_shell: false,
+            print_identity: false,
+            no_relay: false,
+            listen_addr: None,
+            api_port: None,
+        }
+    }
+}
+
+/// Data dir of the relay `ccbox serve --no-relay` runs in-process.
+fn relay_store_dir(state_dir: &Path) -> std::path::PathBuf {
+    state_dir.join("remote").join("relay")
+}
+
+fn relay_registration_path(state_dir: &Path) -> std::path::PathBuf {
+    state_dir.join("remote").join("relay.json")
+}
+
+pub fn load_relay_registration(state_dir: &Path) -> Result<Option<RelayRegistration>, ServeError> {
+    let path = relay_registration_path(state_dir);
+    let raw = match fs::read_to_string(&path) {
+        Ok(raw) => raw,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(error) => {
+            return Err(ServeError::Registration {
+                path: path.display().to_string(),
+                source: error,
+            });
+        }
+    };
+    Ok(Some(serde_json::from_str(&raw)?))
+}
+
+fn save_relay_registration(
+    state_dir: &Path,
+    registration: &RelayRegistration,
+) -> Result<(), ServeError> {
+    let path = relay_registration_path(state_dir);
+    let text = serde_json::to_string_pretty(registration)?;
+    fs::write(&path, format!("{text}\n")).map_err(|error| ServeError::Registration {
+        path: path.display().to_string(),
+        source: error,
+    })
+}
+
+/// Registers this machine with the relay (the default `ccbox.app` one unless `relay.json` says
+/// otherwise) and asks it for a pairing code. Blocks on the network; run it off the UI thread.
+pub fn pair_device_via_relay(state_dir: &Path) -> Result<crate::domain::PairingOffer, ServeError> {
+    let identity = load_or_create_ccbox_identity(state_dir)?;
+    let registration = load_relay_registration(state_dir)?.unwrap_or_else(|| RelayRegistration {
+        guid: Uuid::new_v4().to_string(),
+        relay_domain: "ccbox.app".to_string(),
+        relay_base_url: None,
+    });
+    let opts = registration.serve_options();
+    let guid = registration.guid()?;
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|error| ServeError::Runtime(error.to_string()))?;
+    let pairing = runtime.block_on(fetch_or_create_remote_pairing_code(&opts, &identity, guid))?;
+    save_relay_registration(state_dir, &registration)?;
+
+    let client_ws_url = resolve_client_ws_url(&opts, guid)?.to_string();
+    let web_url = resolve_web_url(&opts, guid, &client_ws_url, Some(&pairing.pairing_code))
+        .map_err(ServeError::RelayUrl)?
+        .to_string();
+    let qr_rows = render_qr(&web_url)
+        .map(|qr| qr.lines().map(str::to_string).collect())
+        .unwrap_or_default();
+
+    Ok(crate::domain::PairingOffer {
+        pairing_code: pairing.pairing_code,
+        expires_at: pairing.expires_at,
+        web_url,
+        qr_rows,
+    })
+}
+
 pub fn run_serve(opts: ServeOptions) -> Result<(), ServeError> {
     let state_dir = crate::infra::resolve_ccbox_state_dir()?;
     let sessions_dir = crate::infra::resolve_sessions_dir()?;
     let identity = load_or_create_ccbox_identity(&state_dir)?;
-    let connection_guid = Uuid::new_v4();
+    let registration = load_relay_registration(&state_dir)?;
+    let connection_guid = match &registration {
+        Some(registration) => registration.guid()?,
+        None => Uuid::new_v4(),
+    };
     let connection_guid_text = connection_guid.to_string();
 
     if opts.print_identity {
@@ -205,6 +333,16 @@ pub fn run_serve(opts: ServeOptions) -> Result<(), ServeError> {
         .build()
         .map_err(|error| ServeError::Runtime(error.to_string()))?;
 
+    if let Some(port) = opts.api_port {
+        let api = http_api::ApiOptions {
+            port,
+            ccbox_id: identity.ccbox_id.to_string(),
+            label: opts.label.clone(),
+            token_path: state_dir.join("remote").join("api_token"),
+        };
+        return runtime.block_on(http_api::serve_http_api(api, sessions_dir));
+    }
+
     let mut out = io::stdout().lock();
     let _ = writeln!(out, "ccbox serve");
     let _ = writeln!(out, "ccbox_id={}", identity.ccbox_id);
@@ -227,7 +365,7 @@ pub fn run_serve(opts: ServeOptions) -> Result<(), ServeError> {
             "ccbox_ws_url=ws://{connect_addr}/ccbox?guid={connection_guid_text}"
         );
 
-        let relay_data_dir = state_dir.join("remote").join("relay");
+        let relay_data_dir = relay_store_dir(&state_dir);
         let store_paths = ccbox_relay::store::make_store_paths(&relay_data_dir);
         match ensure_pairing_code(&store_paths, &connection_guid_text, 120) {
             Ok(Some(pairing)) => {
@@ -240,6 +378,17 @@ pub fn run_serve(opts: ServeOptions) -> Result<(), ServeError> {
             }
         }
     } else {
+        if registration.is_none() {
+            // Keeps the guid for the next start, pairing from the TUI and metadata sync.
+            save_relay_registration(
+                &state_dir,
+                &RelayRegistration {
+                    guid: connection_guid_text.clone(),
+                    relay_domain: opts.relay_domain.clone(),
+                    relay_base_url: opts.relay_base_url.clone(),
+                },
+            )?;
+        }
         let relay_ws_url = resolve_ccbox_ws_url(&opts, connection_guid)?.to_string();
         let client_ws_url = resolve_client_ws_url(&opts, connection_guid)?.to_string();
         let pair_url = resolve_pair_url(&client_ws_url)
@@ -310,16 +459,11 @@ fn print_qr_to_stderr(url: &str) {
         return;
     }
 
-    let Ok(code) = qrcode::QrCode::new(url.as_bytes()) else {
+    let Some(qr) = render_qr(url) else {
         let _ = writeln!(io::stderr().lock(), "qr_error=encode_failed");
         return;
     };
 
-    let qr = code
-        .render::<qrcode::render::unicode::Dense1x2>()
-        .quiet_zone(true)
-        .build();
-
     let mut err = io::stderr().lock();
     let _ = writeln!(err);
     let _ = writeln!(err, "scan_qr_url={url}");
@@ -327,6 +471,15 @@ fn print_qr_to_stderr(url: &str) {
     let _ = writeln!(err);
 }
 
+fn render_qr(url: &str) -> Option<String> {
+    let code = qrcode::QrCode::new(url.as_bytes()).ok()?;
+    Some(
+        code.render::<qrcode::render::unicode::Dense1x2>()
+            .quiet_zone(true)
+            .build(),
+    )
+}
+
 fn resolve_pair_url(client_ws_url: &str) -> Result<Url, String> {
     let mut url = Url::parse(client_ws_url).map_err(|error| error.to_string())?;
     match url.scheme() {
@@ -409,6 +562,45 @@ async fn fetch_or_create_remote_pairing_code(
     identity: &CcboxIdentity,
     connection_guid: Uuid,
 ) -> Result<RemotePairingCode, ServeError> {
+    let reply = request_ccbox_envelope(
+        opts,
+        identity,
+        connection_guid,
+        "ccbox/pairing/create",
+        CcboxPairingCreatePayload { ttl_seconds: 120 },
+        ("ccbox/pairing/ok", "ccbox/pairing/err"),
+        ServeError::PairingBootstrap,
+    )
+    .await?;
+    let payload = match reply {
+        Ok(payload) => serde_json::from_value::<CcboxPairingOkPayload>(payload)?,
+        Err(payload) => {
+            let payload: CcboxPairingErrPayload = serde_json::from_value(payload)?;
+            return Err(ServeError::PairingBootstrap(payload.code));
+        }
+    };
+    if payload.pairing_code.trim().is_empty() {
+        return Err(ServeError::PairingBootstrap(
+            "relay returned empty pairing code".to_string(),
+        ));
+    }
+    Ok(RemotePairingCode {
+        pairing_code: payload.pairing_code,
+        expires_at: payload.expires_at,
+    })
+}
+
+/// Sends one request over a fresh authenticated ccbox socket and waits for the matching reply.
+/// Returns the `ok` payload, or the `err` payload as `Err`; `fail` wraps transport failures.
+async fn request_ccbox_envelope<P: Serialize>(
+    opts: &ServeOptions,
+    identity: &CcboxIdentity,
+    connection_guid: Uuid,
+    type_: &'static str,
+    payload: P,
+    (ok_type, err_type): (&str, &str),
+    fail: fn(String) -> ServeError,
+) -> Result<Result<Value, Value>, ServeError> {
     let connection_guid_text = connection_guid.to_string();
     let url = resolve_ccbox_ws_url(opts, connection_guid)?;
     let (mut ws, _response) = tokio_tungstenite::connect_async(url.as_str())
@@ -418,18 +610,16 @@ async fn fetch_or_create_remote_pairing_code(
     authenticate_ccbox(&mut ws, identity, &connection_guid_text).await?;
     let request = EnvelopeOut {
         v: REMOTE_PROTOCOL_VERSION,
-        type_: "ccbox/pairing/create",
+        type_,
         ts: now_iso(),
-        payload: CcboxPairingCreatePayload { ttl_seconds: 120 },
+        payload,
     };
     send_json(&mut ws, &request).await?;
 
     let result = tokio::time::timeout(Duration::from_secs(5), async {
         loop {
             let Some(msg) = ws.next().await else {
-                return Err(ServeError::PairingBootstrap(
-                    "socket closed before pairing response".to_string(),
-                ));
+                return Err(fail(format!("socket closed before {ok_type}")));
             };
             let msg = msg.map_err(|error| ServeError::Ws(error.to_string()))?;
 
@@ -447,44 +637,189 @@ async fn fetch_or_create_remote_pairing_code(
                     if env.v != REMOTE_PROTOCOL_VERSION {
                         continue;
                     }
-                    match env.type_.as_str() {
-                        "ccbox/pairing/ok" => {
-                            let payload: CcboxPairingOkPayload =
-                                serde_json::from_value(env.payload)?;
-                            if payload.pairing_code.trim().is_empty() {
-                                return Err(ServeError::PairingBootstrap(
-                                    "relay returned empty pairing code".to_string(),
-                                ));
-                            }
-                            return Ok(RemotePairingCode {
-                                pairing_code: payload.pairing_code,
-                                expires_at: payload.expires_at,
-                            });
-                        }
-                        "ccbox/pairing/err" => {
-                            let payload: CcboxPairingErrPayload =
-                                serde_json::from_value(env.payload)?;
-                            return Err(ServeError::PairingBootstrap(payload.code));
-                        }
-                        _ => {}
+                    if env.type_ == ok_type {
+                        return Ok(Ok(env.payload));
+                    }
+                    if env.type_ == err_type {
+                        return Ok(Err(env.payload));
                     }
                 }
                 Message::Close(_) => {
-                    return Err(ServeError::PairingBootstrap(
-                        "socket closed before pairing response".to_string(),
-                    ));
+                    return Err(fail(format!("socket closed before {ok_type}")));
                 }
                 _ => {}
             }
         }
     })
     .await
-    .map_err(|_| ServeError::PairingBootstrap("pairing request timeout".to_string()))??;
+    .map_err(|_| fail(format!("{type_} request timeout")))??;
 
     let _ = ws.send(Message::Close(None)).await;
     Ok(result)
 }
 
+#[derive(Debug, Serialize)]
+struct CcboxMetadataSyncPayload {
+    group: String,
+    entries: Vec<crate::domain::MetadataEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CcboxMetadataOkPayload {
+    entries: Vec<crate::domain::MetadataEntry>,
+}
+
+/// Uploads this machine's metadata document to the relay's sync `group` and returns the merged
+/// document. Needs a relay registration (`ccbox serve` with a relay or pairing from the TUI writes
+/// one).
+/// Blocks on the network; run it off the UI thread.
+pub fn sync_metadata_via_relay(
+    state_dir: &Path,
+    group: &str,
+    entries: Vec<crate::domain::MetadataEntry>,
+) -> Result<Vec<crate::domain::MetadataEntry>, ServeError> {
+    let Some(registration) = load_relay_registration(state_dir)? else {
+        return Err(ServeError::MetadataSync(
+            "not registered with a relay yet; run `ccbox serve` or pair a device first".to_string(),
+        ));
+    };
+    let identity = load_or_create_ccbox_identity(state_dir)?;
+    let opts = registration.serve_options();
+    let guid = registration.guid()?;
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|error| ServeError::Runtime(error.to_string()))?;
+    let reply = runtime.block_on(request_ccbox_envelope(
+        &opts,
+        &identity,
+        guid,
+        "ccbox/metadata/sync",
+        CcboxMetadataSyncPayload {
+            group: group.trim().to_lowercase(),
+            entries,
+        },
+        ("ccbox/metadata/ok", "ccbox/metadata/err"),
+        ServeError::MetadataSync,
+    ))?;
+    match reply {
+        Ok(payload) => Ok(serde_json::from_value::<CcboxMetadataOkPayload>(payload)?.entries),
+        Err(payload) => {
+            let payload: CcboxPairingErrPayload = serde_json::from_value(payload)?;
+            Err(ServeError::MetadataSync(payload.code))
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct CcboxDevicesListPayload {}
+
+#[derive(Debug, Serialize)]
+struct CcboxDevicesUpdatePayload<'a> {
+    device_id: &'a str,
+    action: &'static str,
+}
+
+#[derive(Debug, Deserialize)]
+struct CcboxDevicesOkPayload {
+    devices: Vec<PairedDevicePayload>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PairedDevicePayload {
+    device_id: String,
+    label: Option<String>,
+    created_at: String,
+    last_seen_at: Option<String>,
+    revoked: bool,
+}
+
+/// Client devices paired with this machine through the relay in `relay.json`, most recently seen
+/// first. Empty before the first pairing. Blocks on the network; run it off the UI thread.
+pub fn load_relay_devices(state_dir: &Path) -> Result<Vec<crate::domain::RelayDevice>, ServeError> {
+    if load_relay_registration(state_dir)?.is_none() {
+        return Ok(Vec::new());
+    }
+    request_relay_devices(state_dir, "ccbox/devices/list", CcboxDevicesListPayload {})
+}
+
+/// Revoked devices are refused at authentication until restored. Returns the devices afterwards.
+pub fn set_relay_device_revoked(
+    state_dir: &Path,
+    device_id: &str,
+    revoked: bool,
+) -> Result<Vec<crate::domain::RelayDevice>, ServeError> {
+    let action = if revoked { "revoke" } else { "restore" };
+    request_relay_devices(
+        state_dir,
+        "ccbox/devices/update",
+        CcboxDevicesUpdatePayload { device_id, action },
+    )
+}
+
+/// Drops the device's key on the relay, so it has to pair again. Returns the devices afterwards.
+pub fn forget_relay_device(
+    state_dir: &Path,
+    device_id: &str,
+) -> Result<Vec<crate::domain::RelayDevice>, ServeError> {
+    request_relay_devices(
+        state_dir,
+        "ccbox/devices/update",
+        CcboxDevicesUpdatePayload {
+            device_id,
+            action: "forget",
+        },
+    )
+}
+
+fn request_relay_devices<P: Serialize>(
+    state_dir: &Path,
+    type_: &'static str,
+    payload: P,
+) -> Result<Vec<crate::domain::RelayDevice>, ServeError> {
+    let Some(registration) = load_relay_registration(state_dir)? else {
+        return Err(ServeError::RelayDevices(
+            "not registered with a relay yet; pair a device first".to_string(),
+        ));
+    };
+    let identity = load_or_create_ccbox_identity(state_dir)?;
+    let opts = registration.serve_options();
+    let guid = registration.guid()?;
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|error| ServeError::Runtime(error.to_string()))?;
+    let reply = runtime.block_on(request_ccbox_envelope(
+        &opts,
+        &identity,
+        guid,
+        type_,
+        payload,
+        ("ccbox/devices/ok", "ccbox/devices/err"),
+        ServeError::RelayDevices,
+    ))?;
+    let payload = match reply {
+        Ok(payload) => serde_json::from_value::<CcboxDevicesOkPayload>(payload)?,
+        Err(payload) => {
+            let payload: CcboxPairingErrPayload = serde_json::from_value(payload)?;
+            return Err(ServeError::RelayDevices(payload.code));
+        }
+    };
+    Ok(payload
+        .devices
+        .into_iter()
+        .map(|device| crate::domain::RelayDevice {
+            device_id: device.device_id,
+            label: device.label,
+            created_at: device.created_at,
+            last_seen_at: device.last_seen_at,
+            revoked: device.revoked,
+        })
+        .collect())
+}
+
 fn parse_listen_addr(raw: Option<&str>) -> Result<SocketAddr, ServeError> {
     let raw = raw.unwrap_or("127.0.0.1:8787").trim();
     raw.parse::<SocketAddr>()
@@ -531,7 +866,7 @@ async fn serve_loop_local(
         let _ = shutdown_tx.send(true);
     });
 
-    let relay_data_dir = state_dir.join("remote").join("relay");
+    let relay_data_dir = relay_store_dir(&state_dir);
     let store_paths = ccbox_relay::store::make_store_paths(&relay_data_dir);
 
     let relay_task = tokio::spawn({
@@ -932,7 +1267,14 @@ async fn handle_incoming_text(
     };
 
     control
-        .handle_rpc(ws, mux.session_id, opts, connection_guid, &req)
+        .handle_rpc(
+            ws,
+            mux.session_id,
+            mux.device_id.as_deref(),
+            opts,
+            connection_guid,
+            &req,
+        )
         .await
 }
 
@@ -1018,6 +1360,9 @@ struct CcboxRegisterPayload {
 #[derive(Debug, Deserialize)]
 struct MuxFramePayload {
     session_id: String,
+    /// Set by the relay to the authenticated client device that sent the frame.
+    #[serde(default)]
+    device_id: Option<String>,
     stream_id: u64,
     payload_b64: String,
 }
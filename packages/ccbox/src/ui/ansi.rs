@@ -0,0 +1,191 @@
+//! Terminal output (agent stdout, TTY transcripts) to styled ratatui text.
+//!
+//! Only SGR sequences (`ESC [ … m`) become styles; cursor movement, OSC titles/links and other
+//! escapes are dropped. A lone `\r` starts the line over, so spinners and progress bars show
+//! their last frame instead of every frame glued together.
+
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span, Text};
+
+const ESC: char = '\u{1b}';
+const BEL: char = '\u{7}';
+
+pub(super) fn ansi_text(input: &str) -> Text<'static> {
+    let mut lines = Vec::new();
+    let mut line: Vec<Span<'static>> = Vec::new();
+    let mut run = String::new();
+    let mut style = Style::default();
+    let mut chars = input.chars().peekable();
+
+    fn flush(run: &mut String, style: Style, line: &mut Vec<Span<'static>>) {
+        if !run.is_empty() {
+            line.push(Span::styled(std::mem::take(run), style));
+        }
+    }
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            ESC => match chars.next() {
+                Some('[') => {
+                    let mut params = String::new();
+                    let mut final_byte = None;
+                    for next in chars.by_ref() {
+                        if ('\u{40}'..='\u{7e}').contains(&next) {
+                            final_byte = Some(next);
+                            break;
+                        }
+                        params.push(next);
+                    }
+                    if final_byte == Some('m') {
+                        flush(&mut run, style, &mut line);
+                        style = apply_sgr(style, &params);
+                    }
+                }
+                Some(']') => {
+                    // OSC runs to BEL or ST (`ESC \`).
+                    while let Some(next) = chars.next() {
+                        if next == BEL {
+                            break;
+                        }
+                        if next == ESC {
+                            chars.next_if_eq(&'\\');
+                            break;
+                        }
+                    }
+                }
+                // Charset selection and the like take one more byte.
+                Some('(' | ')' | '*' | '+' | '#') => {
+                    chars.next();
+                }
+                _ => {}
+            },
+            '\n' => {
+                flush(&mut run, style, &mut line);
+                lines.push(Line::from(std::mem::take(&mut line)));
+            }
+            '\r' => {
+                if chars.peek() != Some(&'\n') {
+                    run.clear();
+                    line.clear();
+                }
+            }
+            '\t' => run.push(ch),
+            _ if ch.is_control() => {}
+            _ => run.push(ch),
+        }
+    }
+    flush(&mut run, style, &mut line);
+    if !line.is_empty() {
+        lines.push(Line::from(line));
+    }
+    Text::from(lines)
+}
+
+/// The raw text with escapes made visible (`␛[31m`) instead of sent to the terminal.
+pub(super) fn visible_escapes(input: &str) -> String {
+    input.replace(ESC, "␛")
+}
+
+fn apply_sgr(mut style: Style, params: &str) -> Style {
+    // `ESC[m` is a reset, like `ESC[0m`.
+    if params.is_empty() {
+        return Style::default();
+    }
+    let mut codes = params
+        .split([';', ':'])
+        .map(|code| code.parse::<u16>().unwrap_or(0));
+    while let Some(code) = codes.next() {
+        style = match code {
+            0 => Style::default(),
+            1 => style.add_modifier(Modifier::BOLD),
+            2 => style.add_modifier(Modifier::DIM),
+            3 => style.add_modifier(Modifier::ITALIC),
+            4 => style.add_modifier(Modifier::UNDERLINED),
+            5 | 6 => style.add_modifier(Modifier::SLOW_BLINK),
+            7 => style.add_modifier(Modifier::REVERSED),
+            8 => style.add_modifier(Modifier::HIDDEN),
+            9 => style.add_modifier(Modifier::CROSSED_OUT),
+            22 => style.remove_modifier(Modifier::BOLD | Modifier::DIM),
+            23 => style.remove_modifier(Modifier::ITALIC),
+            24 => style.remove_modifier(Modifier::UNDERLINED),
+            25 => style.remove_modifier(Modifier::SLOW_BLINK),
+            27 => style.remove_modifier(Modifier::REVERSED),
+            28 => style.remove_modifier(Modifier::HIDDEN),
+            29 => style.remove_modifier(Modifier::CROSSED_OUT),
+            30..=37 => style.fg(basic_color(code - 30)),
+            38 => match extended_color(&mut codes) {
+                Some(color) => style.fg(color),
+                None => style,
+            },
+            39 => Style { fg: None, ..style },
+            40..=47 => style.bg(basic_color(code - 40)),
+            48 => match extended_color(&mut codes) {
+                Some(color) => style.bg(color),
+                None => style,
+            },
+            49 => Style { bg: None, ..style },
+            90..=97 => style.fg(basic_color(code - 90 + 8)),
+            100..=107 => style.bg(basic_color(code - 100 + 8)),
+            _ => style,
+        };
+    }
+    style
+}
+
+/// The rest of a `38;5;n` / `38;2;r;g;b` sequence.
+fn extended_color(codes: &mut impl Iterator<Item = u16>) -> Option<Color> {
+    match codes.next()? {
+        5 => Some(Color::Indexed(u8::try_from(codes.next()?).ok()?)),
+        2 => {
+            let mut channel = || u8::try_from(codes.next()?).ok();
+            Some(Color::Rgb(channel()?, channel()?, channel()?))
+        }
+        _ => None,
+    }
+}
+
+fn basic_color(index: u16) -> Color {
+    match index {
+        0 => Color::Black,
+        1 => Color::Red,
+        2 => Color::Green,
+        3 => Color::Yellow,
+        4 => Color::Blue,
+        5 => Color::Magenta,
+        6 => Color::Cyan,
+        7 => Color::Gray,
+        8 => Color::DarkGray,
+        9 => Color::LightRed,
+        10 => Color::LightGreen,
+        11 => Color::LightYellow,
+        12 => Color::LightBlue,
+        13 => Color::LightMagenta,
+        14 => Color::LightCyan,
+        _ => Color::White,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sgr_becomes_styles_and_other_escapes_are_dropped() {
+        let text = ansi_text(
+            "\u{1b}]0;title\u{7}\u{1b}[1;31merror\u{1b}[0m: \u{1b}[38;5;208mwarn\u{1b}[m\r\n\
+             50%\r100%\u{1b}[K\n",
+        );
+        assert_eq!(text.lines.len(), 2);
+        let first = &text.lines[0].spans;
+        assert_eq!(first[0].content, "error");
+        assert_eq!(
+            first[0].style,
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+        );
+        assert_eq!(first[1].content, ": ");
+        assert_eq!(first[1].style, Style::default());
+        assert_eq!(first[2].style, Style::default().fg(Color::Indexed(208)));
+        assert_eq!(text.lines[1].spans[0].content, "100%");
+        assert_eq!(visible_escapes("\u{1b}[1m"), "␛[1m");
+    }
+}
@@ -5,9 +5,10 @@ use humansize::{DECIMAL, format_size};
 use ratatui::prelude::*;
 use ratatui::widgets::block::{BorderType, Title};
 use ratatui::widgets::*;
+mod ansi;
 mod theme;
 use std::cmp::Ordering;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::time::{Duration, SystemTime};
 use time::OffsetDateTime;
 use time::format_description::well_known::Rfc3339;
@@ -61,6 +62,9 @@ pub fn render(frame: &mut Frame, model: &AppModel) {
     match &model.view {
         View::Projects(projects_view) => render_projects(frame, content_area, model, projects_view),
         View::Sessions(sessions_view) => render_sessions(frame, content_area, model, sessions_view),
+        View::AllSessions(all_sessions_view) => {
+            render_all_sessions(frame, content_area, model, all_sessions_view)
+        }
         View::NewSession(new_session_view) => {
             render_new_session(frame, content_area, model, new_session_view)
         }
@@ -80,6 +84,7 @@ pub fn render(frame: &mut Frame, model: &AppModel) {
         View::ProcessOutput(output_view) => {
             render_process_output(frame, content_area, model, output_view)
         }
+        View::Activity(activity_view) => render_activity(frame, content_area, model, activity_view),
         View::Error => render_error(frame, content_area, model),
     }
 
@@ -119,6 +124,12 @@ pub fn render(frame: &mut Frame, model: &AppModel) {
         render_delete_task_confirm_overlay(frame, content_area, model, confirm);
     }
 
+    if let View::Processes(processes_view) = &model.view
+        && let Some(confirm) = &processes_view.kill_project_confirm
+    {
+        render_kill_project_confirm_overlay(frame, content_area, confirm);
+    }
+
     if let Some(preview) = &model.session_result_preview {
         render_session_result_preview_overlay(frame, content_area, preview);
     }
@@ -131,13 +142,41 @@ pub fn render(frame: &mut Frame, model: &AppModel) {
         render_project_stats_overlay(frame, content_area, overlay);
     }
 
+    if let Some(overlay) = &model.audit_log_overlay {
+        render_audit_log_overlay(frame, content_area, overlay);
+    }
+
+    if let Some(overlay) = &model.devices_overlay {
+        render_devices_overlay(frame, content_area, overlay);
+    }
+
+    if let Some(overlay) = &model.session_diff_overlay {
+        render_session_diff_overlay(frame, content_area, overlay);
+    }
+
+    if let Some(overlay) = &model.recent_sessions_overlay {
+        render_recent_sessions_overlay(frame, content_area, model, overlay);
+    }
+
+    if let Some(overlay) = &model.quick_switcher {
+        render_quick_switcher_overlay(frame, content_area, model, overlay);
+    }
+
     if let Some(dialog) = &model.session_rename {
         render_session_rename_overlay(frame, content_area, dialog);
     }
 
+    if let Some(dialog) = &model.project_rename {
+        render_project_rename_overlay(frame, content_area, dialog);
+    }
+
     if let Some(dialog) = &model.session_move {
         render_session_move_overlay(frame, content_area, dialog);
     }
+
+    if let Some(confirm) = &model.opencode_write_back {
+        render_opencode_write_back_overlay(frame, content_area, confirm);
+    }
 }
 
 fn should_dim_background_for_modal(model: &AppModel) -> bool {
@@ -149,10 +188,17 @@ fn should_dim_background_for_modal(model: &AppModel) -> bool {
         || model.delete_task_confirm.is_some()
         || model.delete_tasks_confirm.is_some()
         || model.session_rename.is_some()
+        || model.project_rename.is_some()
         || model.session_move.is_some()
+        || model.opencode_write_back.is_some()
         || model.session_result_preview.is_some()
         || model.session_stats_overlay.is_some()
         || model.project_stats_overlay.is_some()
+        || model.audit_log_overlay.is_some()
+        || model.devices_overlay.is_some()
+        || model.session_diff_overlay.is_some()
+        || model.recent_sessions_overlay.is_some()
+        || model.quick_switcher.is_some()
 }
 
 fn dim_area(frame: &mut Frame, area: Rect) {
@@ -293,16 +339,38 @@ fn render_menu_bar(frame: &mut Frame, area: Rect, model: &AppModel) {
         .iter()
         .map(|span| UnicodeWidthStr::width(span.content.as_ref()))
         .sum();
-    let hint_spans = vec![
-        Span::styled("F1", hint_key_style),
-        Span::styled(" Help", hint_label_style),
-        Span::styled("  ", base_style),
-        Span::styled("F2", hint_key_style),
-        Span::styled(" System", hint_label_style),
-        Span::styled("  ", base_style),
-        Span::styled("F3", hint_key_style),
-        Span::styled(" Statistics", hint_label_style),
-    ];
+    let awaiting = crate::app::awaiting_approval(&model.processes);
+    // Pending permission requests take the place of the key hints until answered.
+    let hint_spans = if awaiting.is_empty() {
+        vec![
+            Span::styled("F1", hint_key_style),
+            Span::styled(" Help", hint_label_style),
+            Span::styled("  ", base_style),
+            Span::styled("F2", hint_key_style),
+            Span::styled(" System", hint_label_style),
+            Span::styled("  ", base_style),
+            Span::styled("F3", hint_key_style),
+            Span::styled(" Statistics", hint_label_style),
+        ]
+    } else {
+        let ids = awaiting
+            .iter()
+            .map(|process| process.id.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let banner = truncate_end(
+            &format!(" ⚠ awaiting approval: {ids} "),
+            (bar_area.width as usize)
+                .saturating_sub(left_width + 12)
+                .max(1),
+        );
+        vec![
+            Span::styled(banner, active_style),
+            Span::styled("  ", base_style),
+            Span::styled("F12", hint_key_style),
+            Span::styled(" attach", hint_label_style),
+        ]
+    };
     let hint_width: usize = hint_spans
         .iter()
         .map(|span| UnicodeWidthStr::width(span.content.as_ref()))
@@ -517,8 +585,24 @@ fn render_projects(
     let projects = &model.data.projects;
     let filtered_indices = &projects_view.filtered_indices;
 
+    let order_label = model.projects_order.label();
+    let hidden_count = projects.iter().filter(|project| project.hidden).count();
+    let hidden_label = match (hidden_count, projects_view.show_hidden) {
+        (0, _) => String::new(),
+        (_, true) => " · showing hidden".to_string(),
+        (count, false) => format!(" · {count} hidden"),
+    };
+    let list_title = match &model.data.scan_status {
+        Some(status) => format!(
+            "Recent Projects · sort: {order_label}{hidden_label} · {}",
+            status.label()
+        ),
+        None => format!("Recent Projects · sort: {order_label}{hidden_label}"),
+    };
     if filtered_indices.is_empty() {
-        let message = if projects_view.query.trim().is_empty() {
+        let message = if model.data.scan_status.is_some() && projects_view.query.trim().is_empty() {
+            "Scanning sessions…"
+        } else if projects_view.query.trim().is_empty() {
             "No projects found."
         } else {
             "No matching projects. Press Esc to clear the filter."
@@ -528,22 +612,45 @@ fn render_projects(
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(theme::BORDER))
                 .padding(Padding::horizontal(1))
-                .title("Recent Projects")
+                .title(list_title)
                 .style(Style::default().bg(theme::SURFACE).fg(theme::FG)),
         );
         frame.render_widget(empty, chunks[1]);
     } else {
         let list_area = chunks[1];
         let max_width = (list_area.width as usize).saturating_sub(6);
-        let (sessions_col_width, modified_col_width) =
-            project_right_columns_width(projects, filtered_indices);
+        let columns = model
+            .list_columns
+            .columns(crate::domain::ListKind::Projects);
+        let show_engine = columns.contains(&crate::domain::ListColumn::Engine);
+        let right_columns = columns
+            .into_iter()
+            .filter(|column| *column != crate::domain::ListColumn::Engine)
+            .collect::<Vec<_>>();
+        let mut rows = filtered_indices
+            .iter()
+            .filter_map(|index| projects.get(*index))
+            .map(|project| {
+                let cells = project_right_cells(project, &model.session_index, &right_columns);
+                (project, cells)
+            })
+            .collect::<Vec<_>>();
 
-        let prefix_width = UnicodeWidthStr::width("CX ");
+        let prefix_width = if show_engine {
+            UnicodeWidthStr::width("CX ")
+        } else {
+            0
+        };
         let sep_width = UnicodeWidthStr::width(" │ ");
+        let column_widths = fit_right_cells(
+            &right_columns,
+            &mut rows,
+            max_width.saturating_sub(prefix_width + sep_width + MIN_LIST_TITLE_WIDTH),
+            " │ ",
+        );
         let fixed_width = prefix_width
-            .saturating_add(sep_width.saturating_mul(3))
-            .saturating_add(sessions_col_width)
-            .saturating_add(modified_col_width);
+            .saturating_add(sep_width.saturating_mul(column_widths.len() + 1))
+            .saturating_add(column_widths.iter().sum::<usize>());
         let left_available = max_width.saturating_sub(fixed_width);
 
         let min_name_col = 12usize;
@@ -567,39 +674,38 @@ fn render_projects(
             (0, 0)
         };
 
-        let list_items: Vec<ListItem> = filtered_indices
-            .iter()
-            .copied()
-            .filter_map(|project_index| {
-                projects.get(project_index).map(|project| {
-                    let is_selected = projects_view
-                        .selected_project_paths
-                        .contains(&project.project_path);
-                    if name_col_width > 0 && path_col_width > 0 {
-                        project_table_list_item(
-                            project,
-                            is_selected,
-                            ProjectTableColumnWidths {
-                                name: name_col_width,
-                                path: path_col_width,
-                                sessions: sessions_col_width,
-                                modified: modified_col_width,
-                            },
-                            model.engine_filter,
-                            &projects_view.query,
-                        )
-                    } else {
-                        project_list_item(
-                            project,
-                            is_selected,
-                            max_width,
-                            sessions_col_width,
-                            modified_col_width,
-                            model.engine_filter,
-                            &projects_view.query,
-                        )
-                    }
-                })
+        let layout = ProjectRowLayout {
+            name: name_col_width,
+            path: path_col_width,
+            right: &column_widths,
+            show_engine,
+        };
+        let list_items: Vec<ListItem> = rows
+            .into_iter()
+            .map(|(project, cells)| {
+                let is_selected = projects_view
+                    .selected_project_paths
+                    .contains(&project.project_path);
+                if name_col_width > 0 && path_col_width > 0 {
+                    project_table_list_item(
+                        project,
+                        is_selected,
+                        cells,
+                        layout,
+                        model.engine_filter,
+                        &projects_view.query,
+                    )
+                } else {
+                    project_list_item(
+                        project,
+                        is_selected,
+                        max_width,
+                        cells,
+                        layout,
+                        model.engine_filter,
+                        &projects_view.query,
+                    )
+                }
             })
             .collect();
 
@@ -609,7 +715,7 @@ fn render_projects(
                     .borders(Borders::ALL)
                     .border_style(Style::default().fg(theme::BORDER))
                     .padding(Padding::horizontal(1))
-                    .title("Recent Projects")
+                    .title(list_title)
                     .style(Style::default().bg(theme::SURFACE).fg(theme::FG)),
             )
             .style(Style::default().bg(theme::SURFACE_2).fg(theme::FG))
@@ -667,9 +773,14 @@ fn render_sessions(
         .unwrap_or_else(|| "Find Sessions".to_string());
     let title_budget = (chunks[0].width as usize).saturating_sub(4);
     let search_title = truncate_end(&search_title, title_budget);
+    let placeholder = if sessions_view.search_content {
+        "Type to filter sessions, prompts and outputs (branch:NAME for a git branch)…"
+    } else {
+        "Type to filter sessions (branch:NAME for a git branch)…"
+    };
     let search_text = if sessions_view.query.is_empty() {
         Text::from(Line::from(Span::styled(
-            "Type to filter sessions…",
+            placeholder,
             Style::default().fg(theme::DIM),
         )))
     } else {
@@ -721,15 +832,20 @@ fn render_sessions(
     } else {
         ""
     };
-    let order_label = sessions_view.order.label();
+    let content_fragment = if sessions_view.search_content {
+        " · Full text"
+    } else {
+        ""
+    };
+    let order_label = format!("sort: {}", sessions_view.order.label());
     let list_title = if !has_filter {
         format!(
-            "Sessions · {} total · {order_label}{engine_fragment}{errors_fragment}",
+            "Sessions · {} total · {order_label}{engine_fragment}{errors_fragment}{content_fragment}",
             project.sessions.len()
         )
     } else {
         format!(
-            "Sessions · {}/{} shown · {order_label}{engine_fragment}{errors_fragment}",
+            "Sessions · {}/{} shown · {order_label}{engine_fragment}{errors_fragment}{content_fragment}",
             filtered_indices.len(),
             project.sessions.len()
         )
@@ -755,22 +871,42 @@ fn render_sessions(
     } else {
         let list_area = chunks[1];
         let max_width = (list_area.width as usize).saturating_sub(6);
-        let (size_col_width, modified_col_width) =
-            session_right_columns_width_filtered(&project.sessions, filtered_indices);
-        let items: Vec<ListItem> = filtered_indices
+        let show_git = project
+            .sessions
             .iter()
-            .copied()
-            .filter_map(|index| {
-                project.sessions.get(index).map(|session| {
-                    session_list_item(
-                        session,
-                        sessions_view.selected_log_paths.contains(&session.log_path),
-                        max_width,
-                        size_col_width,
-                        modified_col_width,
-                        &sessions_view.query,
-                    )
-                })
+            .any(|session| session.meta.git.is_some());
+        let columns = model
+            .list_columns
+            .session_columns(model.show_session_columns);
+        let show_engine = columns.contains(&crate::domain::ListColumn::Engine);
+        let right_columns = session_right_columns(&columns, show_git);
+        let mut rows = filtered_indices
+            .iter()
+            .filter_map(|index| project.sessions.get(*index))
+            .map(|session| {
+                let cells =
+                    session_right_cells(session, model.session_index.as_ref(), &right_columns);
+                (session, cells)
+            })
+            .collect::<Vec<_>>();
+        let column_widths = fit_right_cells(
+            &right_columns,
+            &mut rows,
+            session_columns_room(max_width, show_engine),
+            "  ·  ",
+        );
+        let items: Vec<ListItem> = rows
+            .into_iter()
+            .map(|(session, cells)| {
+                session_list_item(
+                    session,
+                    sessions_view.selected_log_paths.contains(&session.log_path),
+                    max_width,
+                    cells,
+                    &column_widths,
+                    &sessions_view.query,
+                    show_engine,
+                )
             })
             .collect();
 
@@ -812,6 +948,165 @@ fn render_sessions(
     );
 }
 
+fn render_all_sessions(
+    frame: &mut Frame,
+    area: Rect,
+    model: &AppModel,
+    view: &crate::app::AllSessionsView,
+) {
+    let area = inner_area(area);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .split(area);
+
+    let search_text = if view.query.is_empty() {
+        Text::from(Line::from(Span::styled(
+            "Type to filter by title, id or project (branch:NAME for a git branch)…",
+            Style::default().fg(theme::DIM),
+        )))
+    } else {
+        Text::from(view.query.as_str())
+    };
+    let search = Paragraph::new(search_text).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme::BORDER))
+            .padding(Padding::horizontal(1))
+            .title("Find Sessions · all projects")
+            .style(Style::default().bg(theme::SURFACE).fg(theme::FG)),
+    );
+    frame.render_widget(search, chunks[0]);
+
+    let total = model
+        .data
+        .projects
+        .iter()
+        .map(|project| project.sessions.len())
+        .sum::<usize>();
+    let has_filter = !view.query.trim().is_empty() || model.engine_filter != EngineFilter::All;
+    let engine_fragment = if model.engine_filter == EngineFilter::All {
+        String::new()
+    } else {
+        format!(" · Engine: {}", model.engine_filter.label())
+    };
+    let list_title = if has_filter {
+        format!(
+            "All Sessions · {}/{total} shown · newest first{engine_fragment}",
+            view.filtered_indices.len()
+        )
+    } else {
+        format!("All Sessions · {total} total · newest first")
+    };
+    let list_title = truncate_end(&list_title, (chunks[1].width as usize).saturating_sub(4));
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme::BORDER))
+        .padding(Padding::horizontal(1))
+        .title(list_title)
+        .style(Style::default().bg(theme::SURFACE).fg(theme::FG));
+
+    if view.filtered_indices.is_empty() {
+        let message = if has_filter {
+            "No matching sessions. Press Esc to clear the filter."
+        } else {
+            "No sessions found."
+        };
+        frame.render_widget(Paragraph::new(message).block(block), chunks[1]);
+    } else {
+        let max_width = (chunks[1].width as usize).saturating_sub(6);
+        let rows = view
+            .filtered_indices
+            .iter()
+            .filter_map(|(project_index, session_index)| {
+                let project = model.data.projects.get(*project_index)?;
+                Some((project, project.sessions.get(*session_index)?))
+            })
+            .collect::<Vec<_>>();
+        let show_git = rows.iter().any(|(_, session)| session.meta.git.is_some());
+        let columns = model
+            .list_columns
+            .session_columns(model.show_session_columns);
+        let show_engine = columns.contains(&crate::domain::ListColumn::Engine);
+        let right_columns = session_right_columns(&columns, show_git);
+        let mut rows = rows
+            .into_iter()
+            .map(|(project, session)| {
+                let project_cell = Span::styled(
+                    truncate_end(&project.name, 24),
+                    Style::default().fg(theme::ACCENT),
+                );
+                let cells =
+                    session_right_cells(session, model.session_index.as_ref(), &right_columns);
+                ((session, project_cell), cells)
+            })
+            .collect::<Vec<_>>();
+        let project_width = rows
+            .iter()
+            .map(|((_, project_cell), _)| project_cell.width())
+            .max()
+            .unwrap_or_default();
+        let mut column_widths = fit_right_cells(
+            &right_columns,
+            &mut rows,
+            session_columns_room(max_width, show_engine)
+                .saturating_sub(project_width + UnicodeWidthStr::width("  ·  ")),
+            "  ·  ",
+        );
+        column_widths.insert(0, project_width);
+        let items = rows
+            .into_iter()
+            .map(|((session, project_cell), mut cells)| {
+                cells.insert(0, project_cell);
+                session_list_item(
+                    session,
+                    false,
+                    max_width,
+                    cells,
+                    &column_widths,
+                    &view.query,
+                    show_engine,
+                )
+            })
+            .collect::<Vec<_>>();
+        let list = List::new(items)
+            .block(block)
+            .style(Style::default().bg(theme::SURFACE_2).fg(theme::FG))
+            .highlight_style(
+                Style::default()
+                    .bg(theme::ACCENT_BG)
+                    .fg(theme::FG)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .highlight_symbol("▸ ");
+        let mut state = ListState::default();
+        state.select(Some(
+            view.selected
+                .min(view.filtered_indices.len().saturating_sub(1)),
+        ));
+        frame.render_stateful_widget(list, chunks[1], &mut state);
+    }
+
+    let warnings = model.data.warnings.get();
+    let mut text = "Keys: type=filter  arrows=move  PgUp/PgDn=page  Enter=open  F6=columns  F7=recent  F9=switch  Ctrl+N/Cmd+N=new  Esc=clear/back  Ctrl+R=rescan  Ctrl+Q/Ctrl+C=quit  F1/?=help".to_string();
+    if warnings > 0 {
+        text.push_str(&format!("  ·  warnings: {warnings}"));
+    }
+    frame.render_widget(
+        footer_paragraph(
+            text,
+            model.notice.as_deref(),
+            model.update_hint.as_deref(),
+            processes_running(model),
+        ),
+        chunks[2],
+    );
+}
+
 fn render_tasks(
     frame: &mut Frame,
     area: Rect,
@@ -867,26 +1162,51 @@ fn render_tasks(
     } else {
         let list_area = chunks[1];
         let max_width = (list_area.width as usize).saturating_sub(6);
-        let (images_col_width, modified_col_width) =
-            tasks_right_columns_width(tasks, filtered_indices);
-        let list_items: Vec<ListItem> = filtered_indices
+        let has_schedule = filtered_indices
             .iter()
-            .copied()
-            .filter_map(|task_index| {
-                tasks.get(task_index).map(|task| {
-                    task_list_item(
-                        task,
-                        tasks_view.selected_task_ids.contains(&task.id),
-                        max_width,
-                        images_col_width,
-                        modified_col_width,
-                        &tasks_view.query,
-                    )
-                })
+            .filter_map(|index| tasks.get(*index))
+            .any(|task| task.schedule.is_some());
+        let columns = model
+            .list_columns
+            .columns(crate::domain::ListKind::Tasks)
+            .into_iter()
+            .filter(|column| has_schedule || *column != crate::domain::ListColumn::Schedule)
+            .collect::<Vec<_>>();
+        let mut rows = filtered_indices
+            .iter()
+            .filter_map(|index| tasks.get(*index))
+            .map(|task| (task, task_right_cells(task, &columns)))
+            .collect::<Vec<_>>();
+        let column_widths = fit_right_cells(
+            &columns,
+            &mut rows,
+            max_width.saturating_sub(UnicodeWidthStr::width("TEAM ") + 2 + MIN_LIST_TITLE_WIDTH),
+            "  ·  ",
+        );
+        let list_items: Vec<ListItem> = rows
+            .into_iter()
+            .map(|(task, cells)| {
+                task_list_item(
+                    task,
+                    tasks_view.selected_task_ids.contains(&task.id),
+                    max_width,
+                    cells,
+                    &column_widths,
+                    &tasks_view.query,
+                )
             })
             .collect();
 
-        let list_title = format!("Tasks · {} total · newest first", tasks.len());
+        let scheduled = tasks.iter().filter(|task| task.schedule.is_some()).count();
+        let team = tasks.iter().filter(|task| task.id.is_team()).count();
+        let mut list_title = format!("Tasks · {} total", tasks.len());
+        if scheduled > 0 {
+            list_title.push_str(&format!(" · {scheduled} scheduled"));
+        }
+        if team > 0 {
+            list_title.push_str(&format!(" · {team} team"));
+        }
+        list_title.push_str(" · newest first");
         let list = List::new(list_items)
             .block(
                 Block::default()
@@ -914,7 +1234,7 @@ fn render_tasks(
         frame.render_stateful_widget(list, list_area, &mut state);
     }
 
-    let footer_text = "Keys: type=filter  arrows=move  PgUp/PgDn=page  Enter=open  Tab=select  Ctrl+Enter/Cmd+Enter=spawn  n=new  Del=delete  Backspace=edit  Esc=clear/back  Ctrl+4/Cmd+4=tasks  Ctrl+T/Cmd+T=new  F3=stats";
+    let footer_text = "Keys: type=filter  arrows=move  PgUp/PgDn=page  Enter=open  Tab=select  Ctrl+Enter/Cmd+Enter=spawn  Ctrl+S/Cmd+S=schedule  F8=worktree  F10=model  n=new  Del=delete  Backspace=edit  Esc=clear/back  Ctrl+4/Cmd+4=tasks  Ctrl+T/Cmd+T=new  F3=stats";
     let mut spans: Vec<Span<'static>> = Vec::new();
     spans.push(Span::raw(footer_text.to_string()));
     if let Some(notice) = model.notice.as_deref() {
@@ -944,6 +1264,8 @@ fn render_tasks(
         " (Shift+Tab)".to_string(),
         Style::default().fg(theme::ACCENT),
     ));
+    spans.extend(worktree_status_spans(tasks_view.worktree));
+    spans.extend(agent_model_status_spans(tasks_view.agent_model.as_deref()));
     if processes_running(model) {
         spans.push(Span::raw("  ·  "));
         spans.push(Span::styled(
@@ -955,6 +1277,115 @@ fn render_tasks(
     }
     let footer = Paragraph::new(Line::from(spans)).style(Style::default().fg(theme::DIM));
     frame.render_widget(footer, chunks[2]);
+
+    if let Some(dialog) = &tasks_view.schedule_dialog {
+        dim_area(frame, frame.area());
+        render_task_schedule_overlay(frame, area, dialog);
+    }
+}
+
+fn render_task_schedule_overlay(
+    frame: &mut Frame,
+    area: Rect,
+    dialog: &crate::app::TaskScheduleDialog,
+) {
+    let popup = centered_rect(72, 30, area);
+    frame.render_widget(Clear, popup);
+
+    let title = format!("Schedule · {}", dialog.task_title);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme::BORDER))
+        .padding(Padding::horizontal(1))
+        .title(truncate_end(
+            &title,
+            (popup.width as usize).saturating_sub(4),
+        ))
+        .style(Style::default().bg(theme::SURFACE).fg(theme::FG));
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(0),
+        ])
+        .split(inner);
+
+    let input_text = if dialog.editor.text.is_empty() {
+        Text::from(Line::from(Span::styled(
+            "at 02:00  ·  in 2h  ·  in 1h 30m",
+            Style::default().fg(theme::DIM),
+        )))
+    } else {
+        Text::from(dialog.editor.text.as_str())
+    };
+    let input = Paragraph::new(input_text).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme::BORDER))
+            .padding(Padding::horizontal(1))
+            .title("Run")
+            .style(Style::default().bg(theme::SURFACE).fg(theme::FG)),
+    );
+    frame.render_widget(input, chunks[0]);
+
+    let status = match dialog.error.as_deref() {
+        Some(error) => Line::from(Span::styled(
+            error.to_string(),
+            Style::default().fg(theme::ERROR),
+        )),
+        None => Line::from(vec![
+            Span::styled(
+                format!("Engine: {}", dialog.engine.label()),
+                Style::default()
+                    .fg(theme::ACCENT)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(" (Shift+Tab)", Style::default().fg(theme::ACCENT)),
+        ]),
+    };
+    frame.render_widget(
+        Paragraph::new(status).alignment(Alignment::Center),
+        chunks[1],
+    );
+
+    let hint = if dialog.is_scheduled {
+        "Keys: Enter=reschedule (empty=cancel schedule)  Esc=close  Backspace=delete"
+    } else {
+        "Keys: Enter=schedule  Esc=close  Backspace=delete"
+    };
+    let hint = Paragraph::new(hint)
+        .style(Style::default().fg(theme::DIM))
+        .alignment(Alignment::Center);
+    frame.render_widget(hint, chunks[2]);
+
+    let input_inner = Block::default()
+        .borders(Borders::ALL)
+        .padding(Padding::horizontal(1))
+        .title("Run")
+        .inner(chunks[0]);
+    if input_inner.width > 0 {
+        let mut x_offset = 0u16;
+        for (idx, ch) in dialog.editor.text.chars().enumerate() {
+            if idx >= dialog.editor.cursor_col {
+                break;
+            }
+            x_offset = x_offset.saturating_add(UnicodeWidthChar::width(ch).unwrap_or(0) as u16);
+        }
+        let x = input_inner.x.saturating_add(x_offset).min(
+            input_inner
+                .x
+                .saturating_add(input_inner.width.saturating_sub(1)),
+        );
+        frame.set_cursor_position(Position {
+            x,
+            y: input_inner.y,
+        });
+    }
 }
 
 fn render_new_session(
@@ -964,8 +1395,8 @@ fn render_new_session(
     new_session_view: &crate::app::NewSessionView,
 ) {
     let area = inner_area(area);
-    let is_fork = new_session_view.fork.is_some();
-    let header_height = if is_fork { 4 } else { 3 };
+    let is_resume = new_session_view.is_resume();
+    let header_height = if is_resume { 4 } else { 3 };
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -980,14 +1411,41 @@ fn render_new_session(
         .projects
         .iter()
         .find(|project| project.project_path == new_session_view.from_sessions.project_path);
-    let title = match (is_fork, project) {
-        (true, Some(project)) => format!(
-            "Fork Resume · {} ({})",
-            project.name,
-            project.project_path.display()
-        ),
+    let mode = if new_session_view.fork.is_some() {
+        "Fork Resume"
+    } else {
+        "Resume"
+    };
+    let title = match (is_resume, project) {
+        _ if let Some(batch) = &new_session_view.batch => {
+            let names = batch
+                .project_paths
+                .iter()
+                .map(|path| {
+                    model
+                        .data
+                        .projects
+                        .iter()
+                        .find(|project| project.project_path == *path)
+                        .map_or_else(
+                            || path.display().to_string(),
+                            |project| project.name.clone(),
+                        )
+                })
+                .collect::<Vec<_>>();
+            format!(
+                "New Session · {} projects ({})",
+                batch.project_paths.len(),
+                names.join(", ")
+            )
+        }
+        (true, Some(project)) => format!(
+            "{mode} · {} ({})",
+            project.name,
+            project.project_path.display()
+        ),
         (true, None) => format!(
-            "Fork Resume · {}",
+            "{mode} · {}",
             new_session_view.from_sessions.project_path.display()
         ),
         (false, Some(project)) => format!(
@@ -1002,12 +1460,23 @@ fn render_new_session(
     };
 
     let header_inner_width = (chunks[0].width as usize).saturating_sub(4);
-    let header_content = if let Some(fork) = new_session_view.fork.as_ref() {
-        let fork_line = format!(
+    let resume_line = if let Some(fork) = new_session_view.fork.as_ref() {
+        Some(format!(
             "Forking from: {}  (parent {})",
             fork.label,
             short_id(&fork.parent_session_id)
-        );
+        ))
+    } else {
+        new_session_view.resume.as_ref().map(|session| {
+            format!(
+                "Resuming {}: {}  ({})",
+                new_session_view.engine.label(),
+                session.title,
+                short_id(&session.meta.id)
+            )
+        })
+    };
+    let header_content = if let Some(fork_line) = resume_line {
         let hint_line =
             "Edit the prompt, then press Ctrl+Enter (or Cmd+Enter) to resume. Esc cancels.";
         Paragraph::new(vec![
@@ -1015,7 +1484,7 @@ fn render_new_session(
             Line::from(truncate_end(hint_line, header_inner_width)),
         ])
     } else {
-        let header_hint = "Write a prompt, then press Ctrl+Enter (or Cmd+Enter if supported) to send. Shift+Tab switches engine. F4 switches I/O mode.";
+        let header_hint = "Write a prompt, then press Ctrl+Enter (or Cmd+Enter if supported) to send. Shift+Tab switches engine. F4 switches I/O mode. F5 toggles auto-retry. F8 toggles a git worktree. F6 also runs it on the other engine (A/B).";
         Paragraph::new(truncate_end(header_hint, header_inner_width))
     };
 
@@ -1091,10 +1560,10 @@ fn render_new_session(
         }
     }
 
-    let footer_text = if is_fork {
-        "Keys: edit text  Ctrl+Enter/Cmd+Enter=send  Esc=cancel  Ctrl+R=rescan  Ctrl+Q/Ctrl+C=quit  F1/?=help"
+    let footer_text = if is_resume {
+        "Keys: edit text  Ctrl+Enter/Cmd+Enter=send  Ctrl+O/Cmd+O=insert file  @=attach file  Ctrl+I/Cmd+I=insert image  Ctrl+V=paste image  Esc=cancel  Ctrl+R=rescan  Ctrl+Q/Ctrl+C=quit  F1/?=help"
     } else {
-        "Keys: edit text  Ctrl+Enter/Cmd+Enter=send  Shift+Tab=engine  F4=I/O mode  Esc=cancel  Ctrl+R=rescan  Ctrl+Q/Ctrl+C=quit  F1/?=help"
+        "Keys: edit text  Ctrl+Enter/Cmd+Enter=send  Shift+Tab=engine  F10=model  F4=I/O mode  F5=retry  F8=worktree  F6=A/B  Ctrl+P/Cmd+P=directory  Ctrl+O/Cmd+O=insert file  @=attach file  Ctrl+I/Cmd+I=insert image  Ctrl+V=paste image  Esc=cancel  Ctrl+R=rescan  Ctrl+Q/Ctrl+C=quit  F1/?=help"
     };
     let mut spans: Vec<Span<'static>> = Vec::new();
     spans.push(Span::raw(footer_text.to_string()));
@@ -1121,7 +1590,7 @@ fn render_new_session(
             .fg(theme::ACCENT)
             .add_modifier(Modifier::BOLD),
     ));
-    if is_fork {
+    if is_resume {
         spans.push(Span::styled(
             " (locked)".to_string(),
             Style::default().fg(theme::DIM),
@@ -1139,7 +1608,7 @@ fn render_new_session(
             .fg(theme::ACCENT)
             .add_modifier(Modifier::BOLD),
     ));
-    if is_fork {
+    if is_resume {
         spans.push(Span::styled(
             " (locked)".to_string(),
             Style::default().fg(theme::DIM),
@@ -1149,6 +1618,74 @@ fn render_new_session(
             " (F4)".to_string(),
             Style::default().fg(theme::ACCENT),
         ));
+        spans.push(Span::raw("  ·  "));
+        spans.push(Span::styled(
+            format!("Retry: {}", new_session_view.retry.label()),
+            Style::default()
+                .fg(theme::ACCENT)
+                .add_modifier(Modifier::BOLD),
+        ));
+        spans.push(Span::styled(
+            " (F5)".to_string(),
+            Style::default().fg(theme::ACCENT),
+        ));
+        spans.extend(agent_model_status_spans(
+            new_session_view.agent_model.as_deref(),
+        ));
+        if let Some(dir) = &new_session_view.working_dir {
+            let shown = dir
+                .strip_prefix(&new_session_view.from_sessions.project_path)
+                .unwrap_or(dir);
+            spans.push(Span::raw("  ·  "));
+            spans.push(Span::styled(
+                format!("Dir: {}", shown.display()),
+                Style::default()
+                    .fg(theme::ACCENT)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+        spans.extend(spawn_args_status_spans(
+            model.spawn_args.resolve(
+                new_session_view.engine,
+                new_session_view
+                    .working_dir
+                    .as_deref()
+                    .unwrap_or(&new_session_view.from_sessions.project_path),
+            ),
+        ));
+        spans.extend(worktree_status_spans(new_session_view.worktree));
+        spans.extend(ab_compare_status_spans(
+            new_session_view.ab_compare,
+            new_session_view.engine,
+        ));
+    }
+    let prompt = new_session_view.editor.text();
+    let attached = new_session_view
+        .attachments
+        .iter()
+        .filter(|attachment| crate::domain::is_attachment_mentioned(&prompt, &attachment.path))
+        .count();
+    if attached > 0 {
+        spans.push(Span::raw("  ·  "));
+        spans.push(Span::styled(
+            format!(
+                "Attached: {attached} file{}",
+                if attached == 1 { "" } else { "s" }
+            ),
+            Style::default()
+                .fg(theme::ACCENT)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+    let images = new_session_view.image_paths.len();
+    if images > 0 {
+        spans.push(Span::raw("  ·  "));
+        spans.push(Span::styled(
+            format!("Images: {images}"),
+            Style::default()
+                .fg(theme::ACCENT)
+                .add_modifier(Modifier::BOLD),
+        ));
     }
     if processes_running(model) {
         spans.push(Span::raw("  ·  "));
@@ -1161,6 +1698,120 @@ fn render_new_session(
     }
     let footer = Paragraph::new(Line::from(spans)).style(Style::default().fg(theme::DIM));
     frame.render_widget(footer, chunks[2]);
+
+    if let Some(editor) = &new_session_view.insert_file {
+        dim_area(frame, frame.area());
+        render_path_input_overlay(
+            frame,
+            area,
+            "Insert File",
+            editor,
+            "Keys: Enter=insert text at the cursor  Esc=cancel  Backspace=delete",
+            "Type the path of a text file (e.g. task.md)…",
+            None,
+        );
+    }
+    if let Some(editor) = &new_session_view.attach_file {
+        dim_area(frame, frame.area());
+        render_path_input_overlay(
+            frame,
+            area,
+            "Attach File",
+            editor,
+            "Keys: Enter=attach as @path (fenced onto the prompt on send)  Esc=type a plain @",
+            "Type a path inside the project (e.g. src/main.rs)…",
+            None,
+        );
+    }
+    if let Some(editor) = &new_session_view.image_path {
+        dim_area(frame, frame.area());
+        render_path_input_overlay(
+            frame,
+            area,
+            "Insert Image",
+            editor,
+            "Keys: Enter=confirm (empty=clipboard)  Ctrl+V=paste image  Esc=cancel  Backspace=delete",
+            "Type a path, or press Ctrl+V…",
+            None,
+        );
+    }
+    if let Some(picker) = &new_session_view.working_dir_picker {
+        dim_area(frame, frame.area());
+        render_path_input_overlay(
+            frame,
+            area,
+            "Working Directory",
+            &picker.editor,
+            "Keys: Enter=run the agent here (empty=project root)  Tab=complete  Up/Down=suggestions  Esc=cancel",
+            "Type a directory…",
+            Some(picker),
+        );
+    }
+}
+
+/// `  ·  Model: default (F10)` footer segment for spawn screens.
+fn agent_model_status_spans(agent_model: Option<&str>) -> [Span<'static>; 3] {
+    [
+        Span::raw("  ·  "),
+        Span::styled(
+            format!("Model: {}", agent_model.unwrap_or("default")),
+            Style::default()
+                .fg(theme::ACCENT)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(" (F10)".to_string(), Style::default().fg(theme::ACCENT)),
+    ]
+}
+
+/// `  ·  Args: --sandbox read-only` footer segment previewing the configured `spawn_args`; empty
+/// when the engine has none.
+fn spawn_args_status_spans(args: &[String]) -> Vec<Span<'static>> {
+    if args.is_empty() {
+        return Vec::new();
+    }
+    vec![
+        Span::raw("  ·  "),
+        Span::styled(
+            format!("Args: {}", crate::domain::spawn_args_label(args)),
+            Style::default().fg(theme::DIM),
+        ),
+    ]
+}
+
+/// `  ·  Worktree: on (F8)` footer segment for spawn screens.
+fn worktree_status_spans(worktree: bool) -> [Span<'static>; 3] {
+    [
+        Span::raw("  ·  "),
+        Span::styled(
+            format!("Worktree: {}", if worktree { "on" } else { "off" }),
+            Style::default()
+                .fg(theme::ACCENT)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(" (F8)".to_string(), Style::default().fg(theme::ACCENT)),
+    ]
+}
+
+/// `  ·  A/B: Codex vs Claude (F6)` footer segment for spawn screens.
+fn ab_compare_status_spans(
+    ab_compare: bool,
+    engine: crate::domain::AgentEngine,
+) -> [Span<'static>; 3] {
+    let label = if ab_compare {
+        format!("A/B: {} vs {}", engine.label(), engine.toggle().label())
+    } else {
+        "A/B: off".to_string()
+    };
+    [
+        Span::raw("  ·  "),
+        Span::styled(
+            label,
+            Style::default()
+                .fg(theme::ACCENT)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(" (F6)".to_string(), Style::default().fg(theme::ACCENT)),
+    ]
 }
 
 fn render_task_create(
@@ -1314,22 +1965,35 @@ fn render_task_create_overlay(
     area: Rect,
     overlay: &crate::app::TaskCreateOverlay,
 ) {
-    let (title, editor, hint, placeholder) = match overlay {
+    let (title, editor, hint, placeholder, picker) = match overlay {
         crate::app::TaskCreateOverlay::ImagePath(editor) => (
             "Insert Image",
             editor,
             "Keys: Enter=confirm (empty=clipboard)  Ctrl+V=paste image  Esc=cancel  Backspace=delete",
             "Type a path, or press Ctrl+V…",
+            None,
         ),
-        crate::app::TaskCreateOverlay::ProjectPath(editor) => (
+        crate::app::TaskCreateOverlay::ProjectPath(picker) => (
             "Project Path",
-            editor,
-            "Keys: Enter=confirm  Esc=cancel  Backspace=delete",
+            &picker.editor,
+            "Keys: Enter=confirm  Tab=complete  Up/Down=suggestions  Esc=cancel",
             "Type a path…",
+            Some(picker),
         ),
     };
+    render_path_input_overlay(frame, area, title, editor, hint, placeholder, picker);
+}
 
-    let popup = centered_rect(72, 24, area);
+fn render_path_input_overlay(
+    frame: &mut Frame,
+    area: Rect,
+    title: &str,
+    editor: &crate::app::LineEditor,
+    hint: &str,
+    placeholder: &str,
+    picker: Option<&crate::app::PathPicker>,
+) {
+    let popup = centered_rect(72, if picker.is_some() { 44 } else { 24 }, area);
     frame.render_widget(Clear, popup);
 
     let block = Block::default()
@@ -1373,6 +2037,9 @@ fn render_task_create_overlay(
         .style(Style::default().fg(theme::DIM))
         .alignment(Alignment::Center);
     frame.render_widget(hint, chunks[1]);
+    if let Some(picker) = picker {
+        render_path_picker_suggestions(frame, chunks[2], picker);
+    }
 
     let input_inner = Block::default()
         .borders(Borders::ALL)
@@ -1419,7 +2086,7 @@ fn render_task_detail(
         task_detail_view.task.project_path.display()
     );
     let title_hint = truncate_end(&title_hint, (chunks[0].width as usize).saturating_sub(4));
-    let header_hint = "Ctrl+Enter spawns. Shift+Tab switches engine. Del deletes. Esc closes.";
+    let header_hint = "Ctrl+Enter spawns. Shift+Tab switches engine. F8 toggles a git worktree. F6 runs it on both engines (A/B). Del deletes. Esc closes.";
     let header = Paragraph::new(truncate_end(
         header_hint,
         (chunks[0].width as usize).saturating_sub(4),
@@ -1451,6 +2118,30 @@ fn render_task_detail(
             ));
         }
     }
+    let spawns = model
+        .processes
+        .iter()
+        .filter(|process| process.task_id.as_ref() == Some(&task_detail_view.task.id))
+        .collect::<Vec<_>>();
+    if !spawns.is_empty() {
+        content.push_str("\nSpawns:\n");
+        for process in spawns {
+            content.push_str(&format!(
+                "{}  {}  {}  {}",
+                process.id,
+                process.engine.label(),
+                relative_time_ago(Some(process.started_at)),
+                process.status.label()
+            ));
+            if !process.attempts.is_empty() {
+                content.push_str(&format!(
+                    "  (earlier: {})",
+                    process_attempt_history(&process.attempts)
+                ));
+            }
+            content.push('\n');
+        }
+    }
 
     let body = Paragraph::new(content)
         .wrap(Wrap { trim: false })
@@ -1465,7 +2156,7 @@ fn render_task_detail(
         );
     frame.render_widget(body, chunks[1]);
 
-    let footer_text = "Keys: arrows=scroll  PgUp/PgDn=page  Ctrl+Enter/Cmd+Enter=spawn  Shift+Tab=engine  Del=delete  Esc/Backspace=back  Ctrl+Q/Ctrl+C=quit  F1/?=help  F3=stats";
+    let footer_text = "Keys: arrows=scroll  PgUp/PgDn=page  Ctrl+Enter/Cmd+Enter=spawn  Shift+Tab=engine  F10=model  F8=worktree  F6=A/B  Del=delete  Esc/Backspace=back  Ctrl+Q/Ctrl+C=quit  F1/?=help  F3=stats";
     let mut spans: Vec<Span<'static>> = Vec::new();
     spans.push(Span::raw(footer_text.to_string()));
     if let Some(notice) = model.notice.as_deref() {
@@ -1495,6 +2186,18 @@ fn render_task_detail(
         " (Shift+Tab)".to_string(),
         Style::default().fg(theme::ACCENT),
     ));
+    spans.extend(agent_model_status_spans(
+        task_detail_view.agent_model.as_deref(),
+    ));
+    spans.extend(spawn_args_status_spans(model.spawn_args.resolve(
+        task_detail_view.engine,
+        &task_detail_view.task.project_path,
+    )));
+    spans.extend(worktree_status_spans(task_detail_view.worktree));
+    spans.extend(ab_compare_status_spans(
+        task_detail_view.ab_compare,
+        task_detail_view.engine,
+    ));
     if processes_running(model) {
         spans.push(Span::raw("  ·  "));
         spans.push(Span::styled(
@@ -1529,11 +2232,74 @@ fn render_processes(
         .iter()
         .filter(|process| process.status.is_running())
         .count();
-    let header_hint = format!(
+    let waiting_count = model
+        .processes
+        .iter()
+        .filter(|process| process.input_wait.is_some())
+        .count();
+    let mut header_hint = format!(
         "{} process(es)  ·  running: {}",
         model.processes.len(),
         running_count
     );
+    if waiting_count > 0 {
+        header_hint.push_str(&format!("  ·  waiting on you: {waiting_count}"));
+    }
+    if !model.external_processes.is_empty() {
+        header_hint.push_str(&format!(
+            "  ·  external: {}",
+            model.external_processes.len()
+        ));
+    }
+    if let Some(external) =
+        processes_view.selected_external(&model.processes, &model.external_processes)
+    {
+        header_hint.push_str(&format!(
+            "  ·  started outside ccbox (read-only): {}",
+            external.agent.command
+        ));
+    }
+    let selected_process = processes_view.selected_process(&model.processes);
+    if let Some(batch) = selected_process.and_then(|process| process.batch) {
+        header_hint.push_str(&format!(
+            "  ·  batch {batch}: {}",
+            batch_status_summary(&model.processes, batch)
+        ));
+    }
+    if let Some(compare) = selected_process.and_then(|process| process.compare) {
+        let states = model
+            .processes
+            .iter()
+            .filter(|process| process.compare == Some(compare))
+            .map(|process| format!("{} {}", process.engine.label(), process.status.label()))
+            .collect::<Vec<_>>();
+        header_hint.push_str(&format!("  ·  A/B {compare}: {}", states.join(" vs ")));
+    }
+    if let Some(title) = selected_process.and_then(|process| process.task_title.as_deref()) {
+        header_hint.push_str(&format!("  ·  task: {title} (t opens it)"));
+    }
+    if let Some(process) = selected_process
+        && !process.attempts.is_empty()
+    {
+        header_hint.push_str(&format!(
+            "  ·  {} earlier attempts: {}",
+            process.id,
+            process_attempt_history(&process.attempts)
+        ));
+    }
+    if let Some(input) = &processes_view.label_input {
+        header_hint = format!("Label: {input}▏  (Enter=apply, empty=ungroup, Esc=cancel)");
+    }
+    if let Some(input) = &processes_view.export_input {
+        header_hint = format!("Save output to: {input}▏  (Enter=save, Esc=cancel)");
+    }
+    if let Some(input) = &processes_view.reply_input {
+        let process_id = processes_view
+            .selected_process(&model.processes)
+            .map(|process| process.id.as_str())
+            .unwrap_or("-");
+        header_hint = format!("Reply to {process_id}: {input}▏  (Enter=send, Esc=cancel)");
+    }
     let header = Paragraph::new(truncate_end(
         &header_hint,
         (chunks[0].width as usize).saturating_sub(4),
@@ -1550,12 +2316,32 @@ fn render_processes(
 
     let list_area = chunks[1];
     let max_width = (list_area.width as usize).saturating_sub(6);
-    let (status_col_width, started_col_width) = process_right_columns_width(&model.processes);
-    let items: Vec<ListItem> = model
-        .processes
+    let columns = process_right_columns_width(&model.processes);
+    let rows = processes_view.rows(&model.processes);
+    let items: Vec<ListItem> = rows
         .iter()
-        .map(|process| process_list_item(process, max_width, status_col_width, started_col_width))
+        .map(|row| match row {
+            crate::app::ProcessRow::Group {
+                label,
+                count,
+                running,
+                collapsed,
+            } => process_group_list_item(label, *count, *running, *collapsed, max_width),
+            crate::app::ProcessRow::Process { index, grouped } => process_list_item(
+                &model.processes[*index],
+                max_width,
+                columns,
+                if *grouped { 2 } else { 0 },
+            ),
+        })
+        .chain(
+            model
+                .external_processes
+                .iter()
+                .map(|external| external_process_list_item(external, max_width)),
+        )
         .collect();
+    let row_count = items.len();
 
     let list = List::new(items)
         .block(
@@ -1576,16 +2362,12 @@ fn render_processes(
         .highlight_symbol("▸ ");
 
     let mut state = ListState::default();
-    if !model.processes.is_empty() {
-        state.select(Some(
-            processes_view
-                .selected
-                .min(model.processes.len().saturating_sub(1)),
-        ));
+    if row_count > 0 {
+        state.select(Some(processes_view.selected.min(row_count - 1)));
     }
     frame.render_stateful_widget(list, list_area, &mut state);
 
-    let footer_text = "Keys: arrows=move  Enter=session  a=attach (TTY)  s=stdout  e=stderr  l=log  k=kill  Esc/Backspace=back  Ctrl+Q/Ctrl+C=quit  F1/?=help";
+    let footer_text = "Keys: arrows=move  Enter=session/collapse  a=attach (TTY)  r=reply (TTY)  s=stdout  e=stderr  l=log  w=save  y=copy  k=stop (group)  K=force kill  Ctrl+K/Cmd+K=kill project  t=task  g=label  Space=collapse  Ctrl+L/Cmd+L=audit  Ctrl+B/Cmd+B=devices  Esc/Backspace=back  Ctrl+Q/Ctrl+C=quit  F1/?=help";
     frame.render_widget(
         footer_paragraph(
             footer_text.to_string(),
@@ -1597,11 +2379,11 @@ fn render_processes(
     );
 }
 
-fn render_process_output(
+fn render_activity(
     frame: &mut Frame,
     area: Rect,
     model: &AppModel,
-    output_view: &crate::app::ProcessOutputView,
+    activity_view: &crate::app::ActivityView,
 ) {
     let area = inner_area(area);
     let chunks = Layout::default()
@@ -1613,30 +2395,24 @@ fn render_process_output(
         ])
         .split(area);
 
-    let process = model
+    let processes = activity_view
+        .rows
+        .iter()
+        .filter(|row| !matches!(row, crate::app::ActivityRow::Session { .. }))
+        .count();
+    let waiting = model
         .processes
         .iter()
-        .find(|process| process.id == output_view.process_id);
-    let title = match process {
-        Some(process) => format!(
-            "Process · {} · {} · pid {} · {}",
-            process.id,
-            process.engine.label(),
-            process.pid,
-            output_view.kind.label()
-        ),
-        None => format!(
-            "Process · {} · {}",
-            output_view.process_id,
-            output_view.kind.label()
-        ),
-    };
-    let file_name = output_view
-        .file_path
-        .file_name()
-        .map(|name| name.to_string_lossy().to_string())
-        .unwrap_or_else(|| output_view.file_path.display().to_string());
-    let header_hint = format!("file: {file_name}");
+        .filter(|process| process.status.is_running() && process.input_wait.is_some())
+        .count();
+    let mut header_hint = format!(
+        "running: {processes}  ·  sessions written in the last {} min: {}",
+        activity_view.window_mins(),
+        activity_view.rows.len() - processes
+    );
+    if waiting > 0 {
+        header_hint.push_str(&format!("  ·  waiting on you: {waiting}"));
+    }
     let header = Paragraph::new(truncate_end(
         &header_hint,
         (chunks[0].width as usize).saturating_sub(4),
@@ -1646,25 +2422,50 @@ fn render_process_output(
             .borders(Borders::ALL)
             .border_style(Style::default().fg(theme::BORDER))
             .padding(Padding::horizontal(1))
-            .title(title)
+            .title("Activity")
             .style(Style::default().bg(theme::SURFACE).fg(theme::FG)),
     );
     frame.render_widget(header, chunks[0]);
 
-    let body = Paragraph::new(output_view.buffer.as_str())
-        .wrap(Wrap { trim: false })
-        .scroll((output_view.scroll, 0))
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(theme::BORDER))
-                .padding(Padding::horizontal(1))
-                .title("Output")
-                .style(Style::default().bg(theme::SURFACE).fg(theme::FG)),
-        );
-    frame.render_widget(body, chunks[1]);
+    let list_area = chunks[1];
+    let max_width = (list_area.width as usize).saturating_sub(6);
+    let items: Vec<ListItem> = activity_view
+        .rows
+        .iter()
+        .map(|row| activity_list_item(model, activity_view, row, max_width))
+        .collect();
+    let row_count = items.len();
+    let list = if items.is_empty() {
+        List::new(vec![ListItem::new(Line::from(Span::styled(
+            "Nothing running and no session written recently. + widens the window.",
+            Style::default().fg(theme::DIM),
+        )))])
+    } else {
+        List::new(items)
+    }
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme::BORDER))
+            .padding(Padding::horizontal(1))
+            .style(Style::default().bg(theme::SURFACE).fg(theme::FG)),
+    )
+    .style(Style::default().bg(theme::SURFACE_2).fg(theme::FG))
+    .highlight_style(
+        Style::default()
+            .bg(theme::ACCENT_BG)
+            .fg(theme::FG)
+            .add_modifier(Modifier::BOLD),
+    )
+    .highlight_symbol("▸ ");
 
-    let footer_text = "Keys: arrows=scroll  s=stdout  e=stderr  l=log  k=kill  Esc/Backspace=back  Ctrl+Q/Ctrl+C=quit  F1/?=help";
+    let mut state = ListState::default();
+    if row_count > 0 {
+        state.select(Some(activity_view.selected.min(row_count - 1)));
+    }
+    frame.render_stateful_widget(list, list_area, &mut state);
+
+    let footer_text = "Keys: arrows=move  Enter=open session/output  +/-=window  Esc/Backspace=back  Ctrl+3/Cmd+3=processes  Ctrl+5/Cmd+5=all sessions  Ctrl+Q/Ctrl+C=quit  F1/?=help";
     frame.render_widget(
         footer_paragraph(
             footer_text.to_string(),
@@ -1676,35 +2477,284 @@ fn render_process_output(
     );
 }
 
-fn projects_footer_line(
-    warnings: usize,
-    notice: Option<&str>,
-    update_hint: Option<&str>,
-    processes_running: bool,
-) -> Paragraph<'static> {
-    let text = if warnings == 0 {
-        "Keys: arrows=move  PgUp/PgDn=page  Enter=open  Tab=select  Space=result  Del=delete  Esc=clear  Ctrl+R=rescan  Ctrl+Q/Ctrl+C=quit  F1/?=help"
-            .to_string()
-    } else {
-        format!(
-            "Keys: arrows=move  PgUp/PgDn=page  Enter=open  Tab=select  Space=result  Del=delete  Esc=clear  Ctrl+R=rescan  Ctrl+Q/Ctrl+C=quit  F1/?=help  ·  warnings: {warnings}"
+/// Status badge, engine and one line of detail for an Activity row.
+fn activity_list_item(
+    model: &AppModel,
+    activity_view: &crate::app::ActivityView,
+    row: &crate::app::ActivityRow,
+    max_width: usize,
+) -> ListItem<'static> {
+    let status = |label: &str, color| {
+        Span::styled(
+            format!("{label:<12}"),
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
         )
     };
-    footer_paragraph(text, notice, update_hint, processes_running)
+    let project_name = |project_path: &std::path::Path| {
+        model
+            .data
+            .projects
+            .iter()
+            .find(|project| project.project_path == project_path)
+            .map(|project| project.name.clone())
+            .unwrap_or_else(|| project_path.display().to_string())
+    };
+    let (status, engine, text) = match row {
+        crate::app::ActivityRow::Process { process_id } => {
+            let Some(process) = model
+                .processes
+                .iter()
+                .find(|process| process.id == *process_id)
+            else {
+                return ListItem::new(Line::from(""));
+            };
+            let status = match process.input_wait {
+                Some(_) => status("! attention", theme::ACCENT),
+                None => status("● running", theme::SUCCESS),
+            };
+            let engine = engine_badge_span(match process.engine {
+                crate::domain::AgentEngine::Codex => crate::domain::SessionEngine::Codex,
+                crate::domain::AgentEngine::Claude => crate::domain::SessionEngine::Claude,
+                crate::domain::AgentEngine::OpenCode => crate::domain::SessionEngine::OpenCode,
+            });
+            let text = format!(
+                "{}  {}  {}  ·  {}",
+                process.id,
+                project_name(&process.project_path),
+                process.prompt_preview,
+                relative_time_ago(Some(process.started_at))
+            );
+            (status, engine, text)
+        }
+        crate::app::ActivityRow::External { pid } => {
+            let Some(external) = model
+                .external_processes
+                .iter()
+                .find(|external| external.agent.pid == *pid)
+            else {
+                return ListItem::new(Line::from(""));
+            };
+            let session = match &external.session {
+                Some(session) => session.title.clone(),
+                None => "no session yet".to_string(),
+            };
+            let text = format!(
+                "pid {pid}  {}  {session}",
+                project_name(&external.agent.cwd)
+            );
+            (
+                status("◌ external", theme::DIM),
+                engine_badge_span(external.agent.engine),
+                text,
+            )
+        }
+        crate::app::ActivityRow::Session {
+            project_path,
+            log_path,
+        } => {
+            let Some(session) = model
+                .data
+                .projects
+                .iter()
+                .find(|project| project.project_path == *project_path)
+                .and_then(|project| {
+                    project
+                        .sessions
+                        .iter()
+                        .find(|session| session.log_path == *log_path)
+                })
+            else {
+                return ListItem::new(Line::from(""));
+            };
+            let status = if activity_view.is_active_session(session.file_modified) {
+                status("◉ active", theme::INFO)
+            } else {
+                status("○ idle", theme::DIM)
+            };
+            let text = format!(
+                "{}  {}  ·  {}",
+                project_name(project_path),
+                session.title,
+                relative_time_ago(session.file_modified)
+            );
+            (status, engine_badge_span(session.engine), text)
+        }
+    };
+    let used = status.content.chars().count() + engine.content.chars().count();
+    ListItem::new(Line::from(vec![
+        status,
+        engine,
+        Span::raw(truncate_end(&text, max_width.saturating_sub(used))),
+    ]))
 }
 
-fn sessions_footer_line(
-    warnings: usize,
-    notice: Option<&str>,
-    update_hint: Option<&str>,
-    processes_running: bool,
-) -> Paragraph<'static> {
+/// First line to show so the last lines, wrapped to `width`, fill `height` rows.
+fn tail_start(lines: &[Line], width: usize, height: usize) -> usize {
+    let mut rows = 0usize;
+    for (index, line) in lines.iter().enumerate().rev() {
+        rows += line.width().max(1).div_ceil(width);
+        if rows > height {
+            return index + 1;
+        }
+    }
+    0
+}
+
+fn render_process_output(
+    frame: &mut Frame,
+    area: Rect,
+    model: &AppModel,
+    output_view: &crate::app::ProcessOutputView,
+) {
+    let area = inner_area(area);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .split(area);
+
+    let process = model
+        .processes
+        .iter()
+        .find(|process| process.id == output_view.process_id);
+    let title = match process {
+        Some(process) => format!(
+            "Process · {} · {} · pid {} · {}",
+            process.id,
+            process.engine.label(),
+            process.pid,
+            output_view.kind.label()
+        ),
+        None => format!(
+            "Process · {} · {}",
+            output_view.process_id,
+            output_view.kind.label()
+        ),
+    };
+    let file_name = output_view
+        .file_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| output_view.file_path.display().to_string());
+    let mut header_hint = format!("file: {file_name}");
+    if let Some(process) = process
+        && !process.attempts.is_empty()
+    {
+        header_hint.push_str(&format!(
+            "  ·  attempt {}  ·  earlier: {}",
+            process.attempt(),
+            process_attempt_history(&process.attempts)
+        ));
+    }
+    if output_view.follow {
+        header_hint.push_str("  ·  following");
+    }
+    let matches = output_view.search_matches();
+    if let Some(query) = &output_view.search {
+        let current = matches
+            .iter()
+            .position(|line| *line == output_view.scroll as usize)
+            .map_or_else(|| "-".to_string(), |index| (index + 1).to_string());
+        header_hint.push_str(&format!("  ·  \"{query}\": {current}/{}", matches.len()));
+    }
+    if let Some(input) = &output_view.search_input {
+        header_hint = format!("Search: {input}▏  (Enter=find, empty=clear, Esc=cancel)");
+    }
+    if let Some(input) = &output_view.export_input {
+        header_hint = format!("Save output to: {input}▏  (Enter=save, Esc=cancel)");
+    }
+    let header = Paragraph::new(truncate_end(
+        &header_hint,
+        (chunks[0].width as usize).saturating_sub(4),
+    ))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme::BORDER))
+            .padding(Padding::horizontal(1))
+            .title(title)
+            .style(Style::default().bg(theme::SURFACE).fg(theme::FG)),
+    );
+    frame.render_widget(header, chunks[0]);
+
+    let mut lines = if output_view.raw {
+        Text::raw(ansi::visible_escapes(&output_view.buffer)).lines
+    } else {
+        ansi::ansi_text(&output_view.buffer).lines
+    };
+    for line in &matches {
+        if let Some(line) = lines.get_mut(*line) {
+            line.style = Style::default().bg(theme::ACCENT_BG);
+        }
+    }
+    // Border + horizontal padding on each side.
+    let body_width = chunks[1].width.saturating_sub(4).max(1) as usize;
+    let body_height = chunks[1].height.saturating_sub(2) as usize;
+    let top = if output_view.follow {
+        tail_start(&lines, body_width, body_height)
+    } else {
+        (output_view.scroll as usize).min(lines.len().saturating_sub(1))
+    };
+    let body = Paragraph::new(lines.split_off(top.min(lines.len())))
+        .wrap(Wrap { trim: false })
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme::BORDER))
+                .padding(Padding::horizontal(1))
+                .title(if output_view.raw {
+                    "Output (raw)"
+                } else {
+                    "Output"
+                })
+                .style(Style::default().bg(theme::SURFACE).fg(theme::FG)),
+        );
+    frame.render_widget(body, chunks[1]);
+
+    let footer_text = "Keys: arrows=scroll  s=stdout  e=stderr  l=log  /=search  n/N=next/prev  f=follow  r=raw/colors  w=save  y=copy  k=stop  K=force kill  Enter=session  Esc/Backspace=back  Ctrl+Q/Ctrl+C=quit  F1/?=help";
+    frame.render_widget(
+        footer_paragraph(
+            footer_text.to_string(),
+            model.notice.as_deref(),
+            model.update_hint.as_deref(),
+            processes_running(model),
+        ),
+        chunks[2],
+    );
+}
+
+fn projects_footer_line(
+    warnings: usize,
+    notice: Option<&str>,
+    update_hint: Option<&str>,
+    processes_running: bool,
+) -> Paragraph<'static> {
+    let text = if warnings == 0 {
+        "Keys: arrows=move  PgUp/PgDn=page  Enter=open  Tab=select  Space=result  Ctrl+N/Cmd+N=new (all selected)  Ctrl+W=IDE  F4=sort  Ctrl+E/Cmd+E=rename  Ctrl+X/Cmd+X=hide  F6=hidden  Del=delete  Esc=clear  Ctrl+L/Cmd+L=audit  Ctrl+B/Cmd+B=devices  Ctrl+R=rescan  Ctrl+G=reload config  Ctrl+Q/Ctrl+C=quit  F1/?=help"
+            .to_string()
+    } else {
+        format!(
+            "Keys: arrows=move  PgUp/PgDn=page  Enter=open  Tab=select  Space=result  Ctrl+N/Cmd+N=new (all selected)  Ctrl+W=IDE  F4=sort  Ctrl+E/Cmd+E=rename  Ctrl+X/Cmd+X=hide  F6=hidden  Del=delete  Esc=clear  Ctrl+L/Cmd+L=audit  Ctrl+B/Cmd+B=devices  Ctrl+R=rescan  Ctrl+G=reload config  Ctrl+Q/Ctrl+C=quit  F1/?=help  ·  warnings: {warnings}"
+        )
+    };
+    footer_paragraph(text, notice, update_hint, processes_running)
+}
+
+fn sessions_footer_line(
+    warnings: usize,
+    notice: Option<&str>,
+    update_hint: Option<&str>,
+    processes_running: bool,
+) -> Paragraph<'static> {
     let text = if warnings == 0 {
-        "Keys: type=filter  arrows=move  PgUp/PgDn=page  Enter=open  Tab=select  Space=result  F3=stats  Ctrl+X/Cmd+X=tool-errors  Ctrl+O/Cmd+O=order-errors  Ctrl+E/Cmd+E=rename  Ctrl+P/Cmd+P=move  Ctrl+N/Cmd+N=new  Del=delete  Esc=clear/back  Ctrl+R=rescan  Ctrl+Q/Ctrl+C=quit  F1/?=help"
+        "Keys: type=filter  arrows=move  PgUp/PgDn=page  Enter=open  Tab=select  Space=result  F3=stats  Ctrl+X/Cmd+X=tool-errors  Ctrl+O/Cmd+O=order-errors  F4=sort  Ctrl+K/Cmd+K=compare  Ctrl+U/Cmd+U=parent  Ctrl+F/Cmd+F=full-text  F6=columns  F7=recent  F9=switch  Ctrl+E/Cmd+E=rename  Ctrl+P/Cmd+P=move  Ctrl+W=IDE  Ctrl+N/Cmd+N=new  Del=delete  Esc=clear/back  Ctrl+R=rescan  Ctrl+Q/Ctrl+C=quit  F1/?=help"
             .to_string()
     } else {
         format!(
-            "Keys: type=filter  arrows=move  PgUp/PgDn=page  Enter=open  Tab=select  Space=result  F3=stats  Ctrl+X/Cmd+X=tool-errors  Ctrl+O/Cmd+O=order-errors  Ctrl+E/Cmd+E=rename  Ctrl+P/Cmd+P=move  Ctrl+N/Cmd+N=new  Del=delete  Esc=clear/back  Ctrl+R=rescan  Ctrl+Q/Ctrl+C=quit  F1/?=help  ·  warnings: {warnings}"
+            "Keys: type=filter  arrows=move  PgUp/PgDn=page  Enter=open  Tab=select  Space=result  F3=stats  Ctrl+X/Cmd+X=tool-errors  Ctrl+O/Cmd+O=order-errors  F4=sort  Ctrl+K/Cmd+K=compare  Ctrl+U/Cmd+U=parent  Ctrl+F/Cmd+F=full-text  F6=columns  F7=recent  F9=switch  Ctrl+E/Cmd+E=rename  Ctrl+P/Cmd+P=move  Ctrl+W=IDE  Ctrl+N/Cmd+N=new  Del=delete  Esc=clear/back  Ctrl+R=rescan  Ctrl+Q/Ctrl+C=quit  F1/?=help  ·  warnings: {warnings}"
         )
     };
     footer_paragraph(text, notice, update_hint, processes_running)
@@ -1763,98 +2813,374 @@ fn processes_running(model: &AppModel) -> bool {
         .any(|process| process.status.is_running())
 }
 
-fn project_right_columns_width(
-    projects: &[crate::domain::ProjectSummary],
-    indices: &[usize],
-) -> (usize, usize) {
-    let mut sessions_col_width = 0usize;
-    let mut modified_col_width = 0usize;
+/// Session count, combined log size and (once indexed) total tokens of a Projects row.
+/// Right-hand cells of a Projects row, one per column (the engine badge goes before the name).
+fn project_right_cells(
+    project: &crate::domain::ProjectSummary,
+    index: &crate::infra::SessionIndex,
+    columns: &[crate::domain::ListColumn],
+) -> Vec<Span<'static>> {
+    use crate::domain::ListColumn;
 
-    for project_index in indices {
-        let Some(project) = projects.get(*project_index) else {
-            continue;
-        };
+    let dim = Style::default().fg(theme::DIM);
+    columns
+        .iter()
+        .map(|column| match column {
+            ListColumn::Sessions => {
+                let sessions_count = project.sessions.len();
+                let session_word = if sessions_count == 1 {
+                    "session"
+                } else {
+                    "sessions"
+                };
+                Span::styled(
+                    format!("{} {session_word}", format_commas_usize(sessions_count)),
+                    dim,
+                )
+            }
+            ListColumn::Size => Span::styled(format_size(project.total_size_bytes(), DECIMAL), dim),
+            ListColumn::Tokens => Span::styled(
+                index.sessions_tokens(&project.sessions).map_or_else(
+                    || "- tok".to_string(),
+                    |tokens| format!("{} tok", format_tokens_compact(tokens)),
+                ),
+                dim,
+            ),
+            ListColumn::Modified if project.last_modified.is_some() => {
+                Span::styled(relative_time_ago(project.last_modified), dim)
+            }
+            _ => Span::styled("-", dim),
+        })
+        .collect()
+}
 
-        let sessions_count = project.sessions.len();
-        let session_word = if sessions_count == 1 {
-            "session"
-        } else {
-            "sessions"
-        };
-        let sessions_col = format!("{sessions_count} {session_word}");
-        sessions_col_width = sessions_col_width.max(UnicodeWidthStr::width(sessions_col.as_str()));
+/// Right-hand cells of a Sessions row: item count and duration (when shown), size, then age.
+/// Metric cells turn orange/red past the `SessionColumn` thresholds.
+/// Room kept for a list row's name or title before right-hand columns are dropped.
+const MIN_LIST_TITLE_WIDTH: usize = 20;
 
-        let modified = if project.last_modified.is_some() {
-            relative_time_ago(project.last_modified)
-        } else {
-            "-".to_string()
+/// Width left for the right-hand columns of a Sessions row: the online dot, the engine badge,
+/// a gap and `MIN_LIST_TITLE_WIDTH` come first.
+fn session_columns_room(max_width: usize, show_engine: bool) -> usize {
+    let badge_width = if show_engine {
+        UnicodeWidthStr::width("CX ")
+    } else {
+        0
+    };
+    max_width.saturating_sub(UnicodeWidthStr::width("● ") + badge_width + 2 + MIN_LIST_TITLE_WIDTH)
+}
+
+/// Right-hand cells of a Sessions row, one per column of `columns` (see
+/// `session_right_columns`). Metric cells turn orange/red past the `SessionColumn` thresholds.
+fn session_right_cells(
+    session: &crate::domain::SessionSummary,
+    index: &crate::infra::SessionIndex,
+    columns: &[crate::domain::ListColumn],
+) -> Vec<Span<'static>> {
+    use crate::domain::{ListColumn, SessionColumn};
+
+    let metric_style = |column: SessionColumn, value: Option<u64>| {
+        let color = match value.map(|value| column.level(value)) {
+            Some(crate::domain::MetricLevel::Excessive) => theme::ERROR,
+            Some(crate::domain::MetricLevel::High) => theme::ACCENT,
+            _ => theme::DIM,
         };
-        modified_col_width = modified_col_width.max(UnicodeWidthStr::width(modified.as_str()));
-    }
+        Style::default().fg(color)
+    };
 
-    (sessions_col_width, modified_col_width)
+    columns
+        .iter()
+        .map(|column| match column {
+            ListColumn::Branch => {
+                let label = session
+                    .meta
+                    .git
+                    .as_ref()
+                    .map(|git| truncate_end(&git.label(), 32))
+                    .unwrap_or_default();
+                Span::styled(label, Style::default().fg(theme::MUTED))
+            }
+            ListColumn::Items => {
+                let items = index.item_count(&session.log_path);
+                let text =
+                    items.map_or_else(|| "- items".to_string(), |count| format!("{count} items"));
+                Span::styled(text, metric_style(SessionColumn::Items, items))
+            }
+            ListColumn::Duration => {
+                let duration = index.duration_ms(session);
+                let text = format_duration_ms(duration.and_then(|ms| i64::try_from(ms).ok()));
+                Span::styled(text, metric_style(SessionColumn::Duration, duration))
+            }
+            ListColumn::Tokens => {
+                let tokens = index.total_tokens(&session.log_path);
+                let text = tokens.map_or_else(
+                    || "- tok".to_string(),
+                    |tokens| format!("{} tok", format_tokens_compact(tokens)),
+                );
+                Span::styled(text, metric_style(SessionColumn::Tokens, tokens))
+            }
+            ListColumn::Activity => {
+                let activity = index
+                    .token_activity(&session.log_path)
+                    .map(crate::domain::sparkline)
+                    .unwrap_or_default();
+                Span::styled(activity, Style::default().fg(theme::MUTED))
+            }
+            ListColumn::Size => Span::styled(
+                format_size(session.file_size_bytes, DECIMAL),
+                metric_style(SessionColumn::Size, Some(session.file_size_bytes)),
+            ),
+            ListColumn::Modified => Span::styled(
+                relative_time_ago(session.file_modified),
+                Style::default().fg(theme::DIM),
+            ),
+            ListColumn::Engine
+            | ListColumn::Sessions
+            | ListColumn::Schedule
+            | ListColumn::Images => Span::raw(""),
+        })
+        .collect()
 }
 
-fn session_right_columns_width_filtered(
-    sessions: &[crate::domain::SessionSummary],
-    indices: &[usize],
-) -> (usize, usize) {
-    let mut size_col_width = 0usize;
-    let mut modified_col_width = 0usize;
+/// The right-hand Sessions columns: the engine badge is drawn before the title, and the branch
+/// only when some session has git info.
+fn session_right_columns(
+    columns: &[crate::domain::ListColumn],
+    show_git: bool,
+) -> Vec<crate::domain::ListColumn> {
+    use crate::domain::{ListColumn, ListKind};
 
-    for session_index in indices {
-        let Some(session) = sessions.get(*session_index) else {
-            continue;
-        };
+    columns
+        .iter()
+        .copied()
+        .filter(|column| ListKind::Sessions.supported().contains(column))
+        .filter(|column| *column != ListColumn::Engine)
+        .filter(|column| show_git || *column != ListColumn::Branch)
+        .collect()
+}
 
-        let size = format_size(session.file_size_bytes, DECIMAL);
-        size_col_width = size_col_width.max(UnicodeWidthStr::width(size.as_str()));
+/// `950`, `12.3k`, `4.1M`.
+fn format_tokens_compact(tokens: u64) -> String {
+    match tokens {
+        0..1_000 => tokens.to_string(),
+        1_000..999_950 => format!("{:.1}k", tokens as f64 / 1_000.0),
+        _ => format!("{:.1}M", tokens as f64 / 1_000_000.0),
+    }
+}
 
-        let modified = relative_time_ago(session.file_modified);
-        modified_col_width = modified_col_width.max(UnicodeWidthStr::width(modified.as_str()));
+fn right_columns_width<T>(rows: &[(T, Vec<Span<'static>>)]) -> Vec<usize> {
+    let mut widths: Vec<usize> = Vec::new();
+    for (_, cells) in rows {
+        for (column, cell) in cells.iter().enumerate() {
+            let width = cell.width();
+            match widths.get_mut(column) {
+                Some(current) => *current = (*current).max(width),
+                None => widths.push(width),
+            }
+        }
     }
+    widths
+}
 
-    (size_col_width, modified_col_width)
+/// Drops the columns that don't fit in `available` (lowest priority first) from every row and
+/// returns the widths of the ones kept.
+fn fit_right_cells<T>(
+    columns: &[crate::domain::ListColumn],
+    rows: &mut [(T, Vec<Span<'static>>)],
+    available: usize,
+    separator: &str,
+) -> Vec<usize> {
+    let widths = right_columns_width(rows);
+    let keep = crate::domain::fit_list_columns(
+        columns,
+        &widths,
+        available,
+        UnicodeWidthStr::width(separator),
+    );
+    for (_, cells) in rows.iter_mut() {
+        let mut is_kept = keep.iter();
+        cells.retain(|_| is_kept.next().copied().unwrap_or(false));
+    }
+    widths
+        .into_iter()
+        .zip(keep)
+        .filter_map(|(width, is_kept)| is_kept.then_some(width))
+        .collect()
 }
 
-fn tasks_right_columns_width(
-    tasks: &[crate::app::TaskSummaryRow],
-    indices: &[usize],
-) -> (usize, usize) {
-    let mut images_col_width = 0usize;
-    let mut modified_col_width = 0usize;
+/// Appends `cells` padded to `widths`, `separator` apart.
+fn push_right_cells(
+    spans: &mut Vec<Span<'static>>,
+    cells: Vec<Span<'static>>,
+    widths: &[usize],
+    separator: &'static str,
+) {
+    for (column, cell) in cells.into_iter().enumerate() {
+        if column > 0 {
+            spans.push(Span::styled(separator, Style::default().fg(theme::DIM)));
+        }
+        let width = widths.get(column).copied().unwrap_or_default();
+        spans.push(Span::styled(pad_left(&cell.content, width), cell.style));
+    }
+}
 
-    for task_index in indices {
-        let Some(task) = tasks.get(*task_index) else {
-            continue;
-        };
+/// Right-hand cells of a Tasks row, one per column.
+fn task_right_cells(
+    task: &crate::app::TaskSummaryRow,
+    columns: &[crate::domain::ListColumn],
+) -> Vec<Span<'static>> {
+    use crate::domain::ListColumn;
 
-        let images = format!("{} img", task.image_count);
-        images_col_width = images_col_width.max(UnicodeWidthStr::width(images.as_str()));
+    columns
+        .iter()
+        .map(|column| match column {
+            ListColumn::Schedule => Span::styled(
+                task.schedule
+                    .as_ref()
+                    .map(task_schedule_label)
+                    .unwrap_or_default(),
+                Style::default().fg(theme::ACCENT),
+            ),
+            ListColumn::Images => Span::styled(
+                format!("{} img", task.image_count),
+                Style::default().fg(theme::DIM),
+            ),
+            ListColumn::Modified => Span::styled(
+                relative_time_ago(Some(task.updated_at)),
+                Style::default().fg(theme::DIM),
+            ),
+            _ => Span::raw(""),
+        })
+        .collect()
+}
 
-        let modified = relative_time_ago(Some(task.updated_at));
-        modified_col_width = modified_col_width.max(UnicodeWidthStr::width(modified.as_str()));
+fn task_schedule_label(schedule: &crate::domain::TaskSchedule) -> String {
+    let remaining = match schedule.due_at.duration_since(SystemTime::now()) {
+        Ok(remaining) => remaining,
+        Err(_) => return "⏰ due".to_string(),
+    };
+    let minutes = remaining.as_secs().div_ceil(60);
+    if minutes < 60 {
+        return format!("⏰ in {minutes}m");
     }
+    let hours = minutes / 60;
+    if hours < 24 {
+        return format!("⏰ in {hours}h {:02}m", minutes % 60);
+    }
+    format!("⏰ in {}d {}h", hours / 24, hours % 24)
+}
 
-    (images_col_width, modified_col_width)
+fn process_attempt_history(attempts: &[crate::app::ProcessAttempt]) -> String {
+    attempts
+        .iter()
+        .enumerate()
+        .map(|(index, attempt)| {
+            let exit = match attempt.exit_code {
+                Some(code) => format!("exit {code}"),
+                None => "signal".to_string(),
+            };
+            format!("#{} pid {} {exit}", index + 1, attempt.pid)
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
 }
 
-fn process_right_columns_width(processes: &[crate::app::ProcessInfo]) -> (usize, usize) {
-    let mut status_col_width = 0usize;
-    let mut started_col_width = 0usize;
+#[derive(Clone, Copy, Debug, Default)]
+struct ProcessColumnWidths {
+    cpu: usize,
+    rss: usize,
+    status: usize,
+    started: usize,
+}
+
+fn process_right_columns_width(processes: &[crate::app::ProcessInfo]) -> ProcessColumnWidths {
+    let mut widths = ProcessColumnWidths::default();
 
     for process in processes {
-        let status = process.status.label();
-        status_col_width = status_col_width.max(UnicodeWidthStr::width(status.as_str()));
+        let (cpu, rss) = process_usage_labels(process);
+        widths.cpu = widths.cpu.max(UnicodeWidthStr::width(cpu.as_str()));
+        widths.rss = widths.rss.max(UnicodeWidthStr::width(rss.as_str()));
+
+        let status = process_status_label(process);
+        widths.status = widths.status.max(UnicodeWidthStr::width(status.as_str()));
 
         let started = relative_time_ago(Some(process.started_at));
-        started_col_width = started_col_width.max(UnicodeWidthStr::width(started.as_str()));
+        widths.started = widths.started.max(UnicodeWidthStr::width(started.as_str()));
+    }
+
+    widths
+}
+
+/// The status cell: `needs attention` while a running agent waits on a human.
+fn process_status_label(process: &crate::app::ProcessInfo) -> String {
+    if process.status == crate::app::ProcessStatus::Running && process.input_wait.is_some() {
+        return "needs attention".to_string();
     }
+    process.status.label()
+}
 
-    (status_col_width, started_col_width)
+/// CPU and RSS cells for a process row; blank once the process is no longer running.
+fn process_usage_labels(process: &crate::app::ProcessInfo) -> (String, String) {
+    match process.usage {
+        Some(usage) => (
+            format!("{:.1}% cpu", usage.cpu_percent),
+            format_size(usage.rss_bytes, DECIMAL),
+        ),
+        None => (String::new(), String::new()),
+    }
 }
 
+/// Highlights the characters of `text` that the list filter's fuzzy match picked (`branch:`
+/// terms are not part of it).
 fn highlight_query_spans(text: &str, query: &str, base_style: Style) -> Vec<Span<'static>> {
+    let query = query
+        .split_whitespace()
+        .filter(|word| !word.starts_with("branch:"))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let positions = if query.is_empty() || text.is_empty() {
+        None
+    } else {
+        crate::domain::fuzzy_match(&query, text).map(|found| found.positions)
+    };
+    let Some(positions) = positions.filter(|positions| !positions.is_empty()) else {
+        return vec![Span::styled(text.to_string(), base_style)];
+    };
+
+    let highlighted_style = base_style
+        .bg(theme::ACCENT_BG)
+        .fg(theme::FG)
+        .add_modifier(Modifier::BOLD);
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    let mut run = String::new();
+    let mut run_highlighted = false;
+    let mut next = positions.iter().peekable();
+    for (index, ch) in text.chars().enumerate() {
+        let highlighted = next.next_if(|position| **position == index).is_some();
+        if highlighted != run_highlighted && !run.is_empty() {
+            let style = if run_highlighted {
+                highlighted_style
+            } else {
+                base_style
+            };
+            spans.push(Span::styled(std::mem::take(&mut run), style));
+        }
+        run_highlighted = highlighted;
+        run.push(ch);
+    }
+    if !run.is_empty() {
+        let style = if run_highlighted {
+            highlighted_style
+        } else {
+            base_style
+        };
+        spans.push(Span::styled(run, style));
+    }
+    spans
+}
+
+/// Highlights every occurrence of `query` in `text` (paths, where the filter matches substrings).
+fn highlight_substring_spans(text: &str, query: &str, base_style: Style) -> Vec<Span<'static>> {
     let query = query.trim();
     if query.is_empty() || text.is_empty() {
         return vec![Span::styled(text.to_string(), base_style)];
@@ -1920,11 +3246,86 @@ fn pad_right(text: &str, width: usize) -> String {
     format!("{}{}", text, " ".repeat(width.saturating_sub(current)))
 }
 
+/// `3 projects, 1 running, 2 exit 0` for the processes of one batch.
+fn batch_status_summary(processes: &[crate::app::ProcessInfo], batch: u32) -> String {
+    let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+    let mut total = 0;
+    for process in processes
+        .iter()
+        .filter(|process| process.batch == Some(batch))
+    {
+        total += 1;
+        *counts.entry(process.status.label()).or_default() += 1;
+    }
+    let mut parts = vec![format!("{total} projects")];
+    parts.extend(
+        counts
+            .into_iter()
+            .map(|(status, count)| format!("{count} {status}")),
+    );
+    parts.join(", ")
+}
+
+/// Header row of a process label group: `▾` when expanded, `▹` when collapsed.
+fn process_group_list_item(
+    label: &str,
+    count: usize,
+    running: usize,
+    collapsed: bool,
+    max_width: usize,
+) -> ListItem<'static> {
+    let marker = if collapsed { "▹ " } else { "▾ " };
+    let summary = format!("  ·  {count} process(es)  ·  running: {running}");
+    let label_width = max_width
+        .saturating_sub(UnicodeWidthStr::width(marker) + UnicodeWidthStr::width(summary.as_str()));
+    ListItem::new(Line::from(vec![
+        Span::styled(marker, Style::default().fg(theme::DIM)),
+        Span::styled(
+            truncate_end(label, label_width),
+            Style::default()
+                .fg(theme::ACCENT)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(summary, Style::default().fg(theme::DIM)),
+    ]))
+}
+
+fn external_process_list_item(
+    external: &crate::app::ExternalProcess,
+    max_width: usize,
+) -> ListItem<'static> {
+    let agent = &external.agent;
+    let session = match &external.session {
+        Some(session) => format!("session: {}", session.title),
+        None => "no session yet".to_string(),
+    };
+    let started = agent
+        .started_at
+        .map(|started_at| format!("  ·  {}", relative_time_ago(Some(started_at))))
+        .unwrap_or_default();
+    let text = format!(
+        "ext  pid {}  {}  ·  {session}{started}",
+        agent.pid,
+        agent.cwd.display(),
+    );
+    let badge = engine_badge_span(agent.engine);
+    let badge_width = badge.content.chars().count();
+    ListItem::new(Line::from(vec![
+        Span::styled("◌ ", Style::default().fg(theme::DIM)),
+        badge,
+        Span::styled(
+            truncate_end(&text, max_width.saturating_sub(2 + badge_width)),
+            Style::default().fg(theme::DIM),
+        ),
+    ]))
+}
+
+/// `indent` shifts grouped rows under their label header.
 fn process_list_item(
     process: &crate::app::ProcessInfo,
     max_width: usize,
-    status_col_width: usize,
-    started_col_width: usize,
+    columns: ProcessColumnWidths,
+    indent: usize,
 ) -> ListItem<'static> {
     if max_width == 0 {
         return ListItem::new(Line::from(""));
@@ -1932,42 +3333,81 @@ fn process_list_item(
 
     let running = process.status.is_running();
     let online_dot_width = UnicodeWidthStr::width("● ");
-    let dot = if running {
+    let dot = if process.input_wait.is_some() {
+        Span::styled("! ", Style::default().fg(theme::ACCENT))
+    } else if running {
         Span::styled("● ", Style::default().fg(theme::SUCCESS))
     } else {
         Span::raw("  ")
     };
+    let dot = Line::from(vec![Span::raw(" ".repeat(indent)), dot]);
 
-    let content_width = max_width.saturating_sub(online_dot_width);
+    let content_width = max_width.saturating_sub(online_dot_width + indent);
     if content_width == 0 {
-        return ListItem::new(Line::from(vec![dot]));
+        return ListItem::new(dot);
     }
 
-    let status = pad_left(&process.status.label(), status_col_width);
+    let (cpu, rss) = process_usage_labels(process);
+    let cpu = pad_left(&cpu, columns.cpu);
+    let rss = pad_left(&rss, columns.rss);
+    let status = pad_left(&process_status_label(process), columns.status);
     let started = pad_left(
         &relative_time_ago(Some(process.started_at)),
-        started_col_width,
+        columns.started,
     );
 
     let column_sep = "  ·  ";
-    let right_width = status_col_width + UnicodeWidthStr::width(column_sep) + started_col_width;
+    let sep_width = UnicodeWidthStr::width(column_sep);
+    let usage_width = if columns.cpu > 0 {
+        columns.cpu + columns.rss + 2 * sep_width
+    } else {
+        0
+    };
+    let right_width = usage_width + columns.status + sep_width + columns.started;
 
+    let attempt = if process.retry_policy.is_enabled() {
+        format!(
+            "  attempt {}/{}",
+            process.attempt(),
+            process.retry_policy.max_attempts
+        )
+    } else {
+        String::new()
+    };
+    let plan = process
+        .plan
+        .as_ref()
+        .map(|plan| format!("  {}", plan.progress_label()))
+        .unwrap_or_default();
+    let waiting = process
+        .input_wait
+        .as_ref()
+        .map(|wait| format!("  [{}]", wait.label()))
+        .unwrap_or_default();
+    let batch = match (process.batch, process.compare) {
+        (Some(batch), _) => format!("  batch {batch}"),
+        (None, Some(compare)) => format!("  A/B {compare}"),
+        (None, None) => String::new(),
+    };
     let left = format!(
-        "{}  {}  {}  pid {}  {}",
+        "{}  {}  {}  pid {}{}{}{}{}  {}",
         process.id,
         process.engine.label(),
         process.io_mode.label(),
         process.pid,
+        batch,
+        attempt,
+        plan,
+        waiting,
         process.prompt_preview
     );
 
     let min_left = 8usize;
     let gap = 2usize;
     if right_width + gap + min_left >= content_width {
-        return ListItem::new(Line::from(vec![
-            dot,
-            Span::raw(truncate_end(&left, content_width)),
-        ]));
+        let mut spans = dot.spans;
+        spans.push(Span::raw(truncate_end(&left, content_width)));
+        return ListItem::new(Line::from(spans));
     }
 
     let left_available = content_width.saturating_sub(right_width + gap);
@@ -1975,14 +3415,18 @@ fn process_list_item(
     let left_width = UnicodeWidthStr::width(left.as_str());
     let padding_width = content_width.saturating_sub(left_width + right_width);
 
-    ListItem::new(Line::from(vec![
-        dot,
-        Span::raw(left),
-        Span::raw(" ".repeat(padding_width)),
-        Span::styled(status, Style::default().fg(theme::DIM)),
-        Span::styled(column_sep, Style::default().fg(theme::DIM)),
-        Span::styled(started, Style::default().fg(theme::DIM)),
-    ]))
+    let mut spans = dot.spans;
+    spans.extend([Span::raw(left), Span::raw(" ".repeat(padding_width))]);
+    if usage_width > 0 {
+        spans.push(Span::styled(cpu, Style::default().fg(theme::INFO)));
+        spans.push(Span::styled(column_sep, Style::default().fg(theme::DIM)));
+        spans.push(Span::styled(rss, Style::default().fg(theme::INFO)));
+        spans.push(Span::styled(column_sep, Style::default().fg(theme::DIM)));
+    }
+    spans.push(Span::styled(status, Style::default().fg(theme::DIM)));
+    spans.push(Span::styled(column_sep, Style::default().fg(theme::DIM)));
+    spans.push(Span::styled(started, Style::default().fg(theme::DIM)));
+    ListItem::new(Line::from(spans))
 }
 
 fn apply_multi_select_style(item: ListItem<'static>, is_selected: bool) -> ListItem<'static> {
@@ -1999,6 +3443,7 @@ fn engine_badge_span(engine: crate::domain::SessionEngine) -> Span<'static> {
         crate::domain::SessionEngine::Claude => ("CL ", theme::ACCENT),
         crate::domain::SessionEngine::Gemini => ("GM ", theme::MUTED),
         crate::domain::SessionEngine::OpenCode => ("OC ", theme::INFO),
+        crate::domain::SessionEngine::Custom => ("CU ", theme::DIM),
     };
     Span::styled(
         badge,
@@ -2068,6 +3513,7 @@ mod project_engine_badge_tests {
                         id: format!("s{index}"),
                         cwd: cwd.clone(),
                         started_at_rfc3339: "2026-02-20T00:00:00Z".to_string(),
+                        git: None,
                     },
                     PathBuf::from(format!("/tmp/log{index}.jsonl")),
                     "t".to_string(),
@@ -2083,6 +3529,7 @@ mod project_engine_badge_tests {
             project_path: cwd,
             sessions,
             last_modified: Some(now),
+            hidden: false,
         }
     }
 
@@ -2128,8 +3575,8 @@ fn project_list_item(
     project: &crate::domain::ProjectSummary,
     is_selected: bool,
     max_width: usize,
-    sessions_col_width: usize,
-    modified_col_width: usize,
+    cells: Vec<Span<'static>>,
+    layout: ProjectRowLayout<'_>,
     engine_filter: EngineFilter,
     query: &str,
 ) -> ListItem<'static> {
@@ -2137,8 +3584,14 @@ fn project_list_item(
         return ListItem::new(Line::from(""));
     }
 
-    let badge = project_engine_badge_span(project, engine_filter);
-    let badge_width = UnicodeWidthStr::width("CX ");
+    let (badge, badge_width) = if layout.show_engine {
+        (
+            project_engine_badge_span(project, engine_filter),
+            UnicodeWidthStr::width("CX "),
+        )
+    } else {
+        (Span::raw(""), 0)
+    };
     let content_width = max_width.saturating_sub(badge_width);
     if content_width == 0 {
         return apply_multi_select_style(ListItem::new(Line::from(vec![badge])), is_selected);
@@ -2146,25 +3599,15 @@ fn project_list_item(
 
     let name = project.name.as_str();
     let path = project.project_path.display().to_string();
-
-    let sessions_count = project.sessions.len();
-    let session_word = if sessions_count == 1 {
-        "session"
-    } else {
-        "sessions"
-    };
-    let sessions_col = format!("{sessions_count} {session_word}");
-    let sessions_col = pad_left(&sessions_col, sessions_col_width);
-
-    let modified = if project.last_modified.is_some() {
-        relative_time_ago(project.last_modified)
+    let name_style = if project.hidden {
+        Style::default().fg(theme::DIM)
     } else {
-        "-".to_string()
+        Style::default().add_modifier(Modifier::BOLD)
     };
-    let modified = pad_left(&modified, modified_col_width);
 
     let column_sep = "  ·  ";
-    let right_width = sessions_col_width + UnicodeWidthStr::width(column_sep) + modified_col_width;
+    let right_width = layout.right.iter().sum::<usize>()
+        + UnicodeWidthStr::width(column_sep) * layout.right.len().saturating_sub(1);
 
     let min_left = 8usize;
     let gap = 2usize;
@@ -2172,11 +3615,7 @@ fn project_list_item(
         let name = truncate_end(name, content_width);
         let mut spans = Vec::new();
         spans.push(badge);
-        spans.extend(highlight_query_spans(
-            &name,
-            query,
-            Style::default().add_modifier(Modifier::BOLD),
-        ));
+        spans.extend(highlight_query_spans(&name, query, name_style));
         return apply_multi_select_style(ListItem::new(Line::from(spans)), is_selected);
     }
 
@@ -2194,11 +3633,7 @@ fn project_list_item(
         let name_budget = left_available.saturating_sub(separator_width + min_path);
         let name = truncate_end(name, name_budget.max(min_left));
         let name_width = UnicodeWidthStr::width(name.as_str());
-        spans.extend(highlight_query_spans(
-            &name,
-            query,
-            Style::default().add_modifier(Modifier::BOLD),
-        ));
+        spans.extend(highlight_query_spans(&name, query, name_style));
         left_width += name_width;
 
         let path_budget = left_available
@@ -2208,7 +3643,7 @@ fn project_list_item(
         let path_width = UnicodeWidthStr::width(path.as_str());
         if !path.is_empty() {
             spans.push(Span::raw(separator));
-            spans.extend(highlight_query_spans(
+            spans.extend(highlight_substring_spans(
                 &path,
                 query,
                 Style::default().fg(theme::DIM),
@@ -2218,80 +3653,67 @@ fn project_list_item(
     } else {
         let name = truncate_end(name, left_available);
         let name_width = UnicodeWidthStr::width(name.as_str());
-        spans.extend(highlight_query_spans(
-            &name,
-            query,
-            Style::default().add_modifier(Modifier::BOLD),
-        ));
+        spans.extend(highlight_query_spans(&name, query, name_style));
         left_width += name_width;
     }
 
     let padding_width = content_width.saturating_sub(left_width + right_width);
     spans.push(Span::raw(" ".repeat(padding_width)));
-    spans.push(Span::styled(sessions_col, Style::default().fg(theme::DIM)));
-    spans.push(Span::styled(column_sep, Style::default().fg(theme::DIM)));
-    spans.push(Span::styled(modified, Style::default().fg(theme::DIM)));
+    push_right_cells(&mut spans, cells, layout.right, column_sep);
 
     apply_multi_select_style(ListItem::new(Line::from(spans)), is_selected)
 }
 
+/// Column widths of a Projects row; `name` and `path` are 0 outside the table layout.
 #[derive(Clone, Copy, Debug)]
-struct ProjectTableColumnWidths {
+struct ProjectRowLayout<'a> {
     name: usize,
     path: usize,
-    sessions: usize,
-    modified: usize,
+    right: &'a [usize],
+    show_engine: bool,
 }
 
 fn project_table_list_item(
     project: &crate::domain::ProjectSummary,
     is_selected: bool,
-    widths: ProjectTableColumnWidths,
+    cells: Vec<Span<'static>>,
+    layout: ProjectRowLayout<'_>,
     engine_filter: EngineFilter,
     query: &str,
 ) -> ListItem<'static> {
-    let name = truncate_end(project.name.as_str(), widths.name);
-    let name = pad_right(&name, widths.name);
-    let name_spans = highlight_query_spans(
-        &name,
-        query,
-        Style::default().fg(theme::FG).add_modifier(Modifier::BOLD),
-    );
-
-    let path = project.project_path.display().to_string();
-    let path = truncate_middle(&path, widths.path);
-    let path = pad_right(&path, widths.path);
-    let path_spans = highlight_query_spans(&path, query, Style::default().fg(theme::DIM));
-
-    let sessions_count = project.sessions.len();
-    let session_word = if sessions_count == 1 {
-        "session"
-    } else {
-        "sessions"
-    };
-    let sessions_col = format!("{} {session_word}", format_commas_usize(sessions_count));
-    let sessions_col = pad_left(&sessions_col, widths.sessions);
-
-    let modified = if project.last_modified.is_some() {
-        relative_time_ago(project.last_modified)
+    let name = truncate_end(project.name.as_str(), layout.name);
+    let name = pad_right(&name, layout.name);
+    let name_style = if project.hidden {
+        Style::default().fg(theme::DIM)
     } else {
-        "-".to_string()
+        Style::default().fg(theme::FG).add_modifier(Modifier::BOLD)
     };
-    let modified = pad_left(&modified, widths.modified);
+    let name_spans = highlight_query_spans(&name, query, name_style);
 
-    let sep = Span::styled(" │ ", Style::default().fg(theme::BORDER));
+    let path = project.project_path.display().to_string();
+    let path = truncate_middle(&path, layout.path);
+    let path = pad_right(&path, layout.path);
+    let path_spans = highlight_substring_spans(&path, query, Style::default().fg(theme::DIM));
 
-    let badge = project_engine_badge_span(project, engine_filter);
+    let sep = " │ ";
 
     let mut spans = Vec::new();
-    spans.push(badge);
+    if layout.show_engine {
+        spans.push(project_engine_badge_span(project, engine_filter));
+    }
     spans.extend(name_spans);
-    spans.push(sep.clone());
+    spans.push(Span::styled(sep, Style::default().fg(theme::BORDER)));
     spans.extend(path_spans);
-    spans.push(sep.clone());
-    spans.push(Span::styled(sessions_col, Style::default().fg(theme::DIM)));
-    spans.push(sep);
-    spans.push(Span::styled(modified, Style::default().fg(theme::DIM)));
+    if !cells.is_empty() {
+        spans.push(Span::styled(sep, Style::default().fg(theme::BORDER)));
+    }
+    for (column, cell) in cells.into_iter().enumerate() {
+        if column > 0 {
+            spans.push(Span::styled(sep, Style::default().fg(theme::BORDER)));
+        }
+        let width = layout.right.get(column).copied().unwrap_or_default();
+        spans.push(Span::styled(pad_left(&cell.content, width), cell.style));
+    }
 
     apply_multi_select_style(ListItem::new(Line::from(spans)), is_selected)
 }
@@ -2313,9 +3735,10 @@ fn session_list_item(
     session: &crate::domain::SessionSummary,
     is_selected: bool,
     max_width: usize,
-    size_col_width: usize,
-    modified_col_width: usize,
+    cells: Vec<Span<'static>>,
+    column_widths: &[usize],
     query: &str,
+    show_engine: bool,
 ) -> ListItem<'static> {
     if max_width == 0 {
         return ListItem::new(Line::from(""));
@@ -2329,26 +3752,32 @@ fn session_list_item(
         Span::raw("  ")
     };
 
-    let badge = engine_badge_span(session.engine);
-    let badge_width = UnicodeWidthStr::width("CX ");
+    let (badge, badge_width) = if show_engine {
+        (
+            engine_badge_span(session.engine),
+            UnicodeWidthStr::width("CX "),
+        )
+    } else {
+        (Span::raw(""), 0)
+    };
     let content_width = max_width.saturating_sub(online_dot_width.saturating_add(badge_width));
+    let display_title = if session.parent_id.is_some() {
+        format!("↳ {}", session.title)
+    } else {
+        session.title.clone()
+    };
     if content_width == 0 {
         return apply_multi_select_style(ListItem::new(Line::from(vec![dot, badge])), is_selected);
     }
 
-    let size = format_size(session.file_size_bytes, DECIMAL);
-    let size = pad_left(&size, size_col_width);
-
-    let modified = relative_time_ago(session.file_modified);
-    let modified = pad_left(&modified, modified_col_width);
-
     let column_sep = "  ·  ";
-    let right_width = size_col_width + UnicodeWidthStr::width(column_sep) + modified_col_width;
+    let right_width = column_widths.iter().sum::<usize>()
+        + UnicodeWidthStr::width(column_sep) * column_widths.len().saturating_sub(1);
 
     let min_left = 8usize;
     let gap = 2usize;
     if right_width + gap + min_left >= content_width {
-        let title = truncate_end(&session.title, content_width);
+        let title = truncate_end(&display_title, content_width);
         let mut spans = Vec::new();
         spans.push(dot);
         spans.push(badge);
@@ -2357,18 +3786,29 @@ fn session_list_item(
     }
 
     let left_available = content_width.saturating_sub(right_width + gap);
-    let title = truncate_end(&session.title, left_available);
+    let title = truncate_end(&display_title, left_available);
     let title_width = UnicodeWidthStr::width(title.as_str());
-    let padding_width = content_width.saturating_sub(title_width + right_width);
+    // Sessions grouped under a repository root keep their own cwd visible after the title.
+    let cwd_room = left_available.saturating_sub(title_width + 2);
+    let cwd_label = session
+        .grouped_cwd_label()
+        .filter(|_| cwd_room >= 4)
+        .map(|label| format!("  {}", truncate_middle(&label, cwd_room)));
+    let cwd_width = cwd_label
+        .as_deref()
+        .map(UnicodeWidthStr::width)
+        .unwrap_or_default();
+    let padding_width = content_width.saturating_sub(title_width + cwd_width + right_width);
 
     let mut spans = Vec::new();
     spans.push(dot);
     spans.push(badge);
     spans.extend(highlight_query_spans(&title, query, Style::default()));
+    if let Some(cwd_label) = cwd_label {
+        spans.push(Span::styled(cwd_label, Style::default().fg(theme::DIM)));
+    }
     spans.push(Span::raw(" ".repeat(padding_width)));
-    spans.push(Span::styled(size, Style::default().fg(theme::DIM)));
-    spans.push(Span::styled(column_sep, Style::default().fg(theme::DIM)));
-    spans.push(Span::styled(modified, Style::default().fg(theme::DIM)));
+    push_right_cells(&mut spans, cells, column_widths, column_sep);
 
     apply_multi_select_style(ListItem::new(Line::from(spans)), is_selected)
 }
@@ -2377,32 +3817,42 @@ fn task_list_item(
     task: &crate::app::TaskSummaryRow,
     is_selected: bool,
     max_width: usize,
-    images_col_width: usize,
-    modified_col_width: usize,
+    cells: Vec<Span<'static>>,
+    column_widths: &[usize],
     query: &str,
 ) -> ListItem<'static> {
     if max_width == 0 {
         return ListItem::new(Line::from(""));
     }
 
+    let team_badge = if task.id.is_team() { "TEAM " } else { "" };
+    let max_width = max_width.saturating_sub(UnicodeWidthStr::width(team_badge));
+    let mut spans = Vec::new();
+    if !team_badge.is_empty() {
+        spans.push(Span::styled(
+            team_badge,
+            Style::default()
+                .fg(theme::INFO)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+
     let title = task.title.as_str();
     let path = task.project_path.display().to_string();
 
-    let images = format!("{} img", task.image_count);
-    let images = pad_left(&images, images_col_width);
-
-    let modified = relative_time_ago(Some(task.updated_at));
-    let modified = pad_left(&modified, modified_col_width);
-
     let column_sep = "  ·  ";
-    let right_width = images_col_width + UnicodeWidthStr::width(column_sep) + modified_col_width;
+    let right_width = column_widths.iter().sum::<usize>()
+        + UnicodeWidthStr::width(column_sep) * column_widths.len().saturating_sub(1);
 
     let min_left = 8usize;
     let gap = 2usize;
     if right_width + gap + min_left >= max_width {
         let title = truncate_end(title, max_width);
-        let spans =
-            highlight_query_spans(&title, query, Style::default().add_modifier(Modifier::BOLD));
+        spans.extend(highlight_query_spans(
+            &title,
+            query,
+            Style::default().add_modifier(Modifier::BOLD),
+        ));
         return apply_multi_select_style(ListItem::new(Line::from(spans)), is_selected);
     }
 
@@ -2412,7 +3862,6 @@ fn task_list_item(
     let separator_width = UnicodeWidthStr::width(separator);
     let min_path = 8usize;
 
-    let mut spans = Vec::new();
     let mut left_width = 0usize;
 
     if left_available >= min_left + separator_width + min_path {
@@ -2433,7 +3882,7 @@ fn task_list_item(
         let path_width = UnicodeWidthStr::width(path.as_str());
         if !path.is_empty() {
             spans.push(Span::raw(separator));
-            spans.extend(highlight_query_spans(
+            spans.extend(highlight_substring_spans(
                 &path,
                 query,
                 Style::default().fg(theme::DIM),
@@ -2453,9 +3902,7 @@ fn task_list_item(
 
     let padding_width = max_width.saturating_sub(left_width + right_width);
     spans.push(Span::raw(" ".repeat(padding_width)));
-    spans.push(Span::styled(images, Style::default().fg(theme::DIM)));
-    spans.push(Span::styled(column_sep, Style::default().fg(theme::DIM)));
-    spans.push(Span::styled(modified, Style::default().fg(theme::DIM)));
+    push_right_cells(&mut spans, cells, column_widths, column_sep);
 
     apply_multi_select_style(ListItem::new(Line::from(spans)), is_selected)
 }
@@ -2558,6 +4005,54 @@ fn humanize_duration(duration: Duration) -> String {
     format!("{days}d ago")
 }
 
+const CONTEXT_GAUGE_CELLS: usize = 10;
+
+/// `ctx ███████░░░ 72% · peak 91% of 272.0k`, orange once the session came near the limit and
+/// red when a compaction likely happened. `None` without a known context window.
+fn context_usage_gauge_line(usage: &crate::domain::ContextUsage) -> Option<Line<'static>> {
+    let last = usage.last_ratio()?;
+    let peak = usage.peak_ratio()?;
+    let window = usage.context_window?;
+    let filled =
+        ((last.min(1.0) * CONTEXT_GAUGE_CELLS as f64).round() as usize).min(CONTEXT_GAUGE_CELLS);
+    let color = if usage.likely_compactions > 0 {
+        theme::ERROR
+    } else if usage.is_near_limit() {
+        theme::ACCENT
+    } else {
+        theme::MUTED
+    };
+
+    let mut spans = vec![
+        Span::styled(" ctx ", Style::default().fg(theme::DIM)),
+        Span::styled("█".repeat(filled), Style::default().fg(color)),
+        Span::styled(
+            "░".repeat(CONTEXT_GAUGE_CELLS - filled),
+            Style::default().fg(theme::DIM),
+        ),
+        Span::styled(
+            format!(
+                " {:.0}% · peak {:.0}% of {} ",
+                last * 100.0,
+                peak * 100.0,
+                format_tokens_compact(window)
+            ),
+            Style::default().fg(color),
+        ),
+    ];
+    if usage.likely_compactions > 0 {
+        let count = usage.likely_compactions;
+        let suffix = if count == 1 { "" } else { "s" };
+        spans.push(Span::styled(
+            format!("[! {count} likely compaction{suffix}] "),
+            Style::default()
+                .fg(theme::ERROR)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+    Some(Line::from(spans))
+}
+
 fn render_session_detail(
     frame: &mut Frame,
     area: Rect,
@@ -2605,22 +4100,50 @@ fn render_session_detail(
         .map(|name| name.to_string_lossy().to_string())
         .unwrap_or_else(|| detail_view.session.log_path.display().to_string());
     let size = format_size(detail_view.session.file_size_bytes, DECIMAL);
-    let header_line = format!("cwd: {cwd}  ·  log: {file_name}  ·  {size}");
+    let mut header_line = format!("cwd: {cwd}  ·  log: {file_name}  ·  {size}");
+    if let Some(plan) = detail_view.plan.as_ref() {
+        header_line.push_str(&format!("  ·  plan: {}", plan.progress_label()));
+    }
+    if let Some(wait) = model
+        .processes
+        .iter()
+        .filter(|process| process.session_log_path.as_ref() == Some(&detail_view.session.log_path))
+        .find_map(|process| process.input_wait.as_ref())
+    {
+        header_line.push_str(&format!("  ·  {}", wait.label()));
+    }
+    let mut header_block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme::BORDER))
+        .padding(Padding::horizontal(1))
+        .title(Title::from(title_line))
+        .style(Style::default().bg(theme::SURFACE).fg(theme::FG));
+    if let Some(gauge) = detail_view
+        .context_usage
+        .as_ref()
+        .and_then(context_usage_gauge_line)
+    {
+        header_block = header_block.title_top(gauge.right_aligned());
+    }
     let header = Paragraph::new(truncate_end(
         &header_line,
         (chunks[0].width as usize).saturating_sub(4),
     ))
-    .block(
-        Block::default()
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(theme::BORDER))
-            .padding(Padding::horizontal(1))
-            .title(Title::from(title_line))
-            .style(Style::default().bg(theme::SURFACE).fg(theme::FG)),
-    );
+    .block(header_block);
     frame.render_widget(header, chunks[0]);
 
-    let body = chunks[1];
+    let mut body = chunks[1];
+    if let Some(plan) = detail_view.plan.as_ref()
+        && body.width >= 110
+    {
+        let sidebar_width = (body.width / 4).clamp(28, 44);
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Min(0), Constraint::Length(sidebar_width)])
+            .split(body);
+        body = columns[0];
+        render_plan_sidebar(frame, columns[1], plan);
+    }
     let panels = if body.width >= 90 {
         Layout::default()
             .direction(Direction::Horizontal)
@@ -2809,9 +4332,7 @@ fn render_session_detail(
 
     let footer = session_detail_footer_line(
         model.data.warnings.get(),
-        detail_view.warnings,
-        detail_view.items.len(),
-        detail_view.truncated,
+        detail_view,
         model.notice.as_deref(),
         model.update_hint.as_deref(),
         processes_running(model),
@@ -2838,28 +4359,89 @@ fn render_session_detail(
     }
 }
 
+fn render_plan_sidebar(frame: &mut Frame, area: Rect, plan: &crate::domain::PlanState) {
+    let mut lines = Vec::new();
+    if let Some(explanation) = plan.explanation.as_deref() {
+        lines.push(Line::from(Span::styled(
+            explanation.to_string(),
+            Style::default().fg(theme::DIM),
+        )));
+        lines.push(Line::from(""));
+    }
+    lines.extend(plan_checklist_lines(plan));
+
+    let title = format!("Plan · {}", plan.progress_label());
+    let sidebar = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme::BORDER))
+            .padding(Padding::horizontal(1))
+            .title(Title::from(Span::styled(
+                title,
+                Style::default()
+                    .fg(theme::BORDER)
+                    .add_modifier(Modifier::BOLD),
+            )))
+            .style(Style::default().bg(theme::SURFACE).fg(theme::FG)),
+    );
+    frame.render_widget(sidebar, area);
+}
+
+fn plan_checklist_lines(plan: &crate::domain::PlanState) -> Vec<Line<'static>> {
+    plan.steps
+        .iter()
+        .map(|step| {
+            let (marker, style) = match step.status {
+                crate::domain::PlanStepStatus::Completed => {
+                    ("✓ ", Style::default().fg(theme::SUCCESS))
+                }
+                crate::domain::PlanStepStatus::InProgress => (
+                    "▸ ",
+                    Style::default()
+                        .fg(theme::ACCENT)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                crate::domain::PlanStepStatus::Pending => ("○ ", Style::default().fg(theme::FG)),
+            };
+            let text_style = if step.status.is_completed() {
+                Style::default().fg(theme::DIM)
+            } else {
+                style
+            };
+            Line::from(vec![
+                Span::styled(marker, style),
+                Span::styled(step.text.clone(), text_style),
+            ])
+        })
+        .collect()
+}
+
 fn session_detail_footer_line(
     scan_warnings: usize,
-    detail_warnings: usize,
-    item_count: usize,
-    truncated: bool,
+    detail_view: &crate::app::SessionDetailView,
     notice: Option<&str>,
     update_hint: Option<&str>,
     processes_running: bool,
 ) -> Paragraph<'static> {
     let mut parts = vec![
-        "Keys: Tab=focus  arrows=move/scroll  PgUp/PgDn=page  Enter=ToolOut (Tool)  f=fork  o=result  F3=stats  Ctrl+E/Cmd+E=rename  Ctrl+P/Cmd+P=move  c=context  S=skills  Esc/Backspace=back  Ctrl+R=rescan  Ctrl+Q/Ctrl+C=quit  F1/?=help"
+        "Keys: Tab=focus  arrows=move/scroll  PgUp/PgDn=page  Enter=ToolOut (Tool)  [/]=user msg  {/}=assistant msg  e=expand output  y/Y/Ctrl+Y=copy item/output/transcript  v=open in $EDITOR  f=fork  o=result  F3=stats  Ctrl+E/Cmd+E=rename  Ctrl+P/Cmd+P=move  c=context  S=skills  Alt+Left/Right=history  F7=recent  F9=switch  Esc/Backspace=back  Ctrl+R=rescan  Ctrl+Q/Ctrl+C=quit  F1/?=help"
             .to_string(),
-        format!("items: {item_count}"),
+        format!("items: {}", detail_view.items.len()),
     ];
-    if truncated {
+    if detail_view.older_items > 0 {
+        parts.push(format!(
+            "older: {} (Up at the top loads more)",
+            detail_view.older_items
+        ));
+    }
+    if detail_view.truncated {
         parts.push("truncated".to_string());
     }
     if scan_warnings > 0 {
         parts.push(format!("scan warnings: {scan_warnings}"));
     }
-    if detail_warnings > 0 {
-        parts.push(format!("parse warnings: {detail_warnings}"));
+    if detail_view.warnings > 0 {
+        parts.push(format!("parse warnings: {}", detail_view.warnings));
     }
     let base = parts.join("  ·  ");
     footer_paragraph(base, notice, update_hint, processes_running)
@@ -2874,6 +4456,8 @@ fn kind_label(kind: TimelineItemKind) -> &'static str {
         TimelineItemKind::ToolCall => "Tool",
         TimelineItemKind::ToolOutput => "ToolOut",
         TimelineItemKind::TokenCount => "Tokens",
+        TimelineItemKind::Todo => "Todo",
+        TimelineItemKind::Compaction => "Compact",
         TimelineItemKind::Note => "Note",
     }
 }
@@ -2889,6 +4473,10 @@ fn kind_style(kind: TimelineItemKind) -> Style {
         TimelineItemKind::ToolCall => Style::default().fg(theme::ACCENT),
         TimelineItemKind::ToolOutput => Style::default().fg(theme::ACCENT),
         TimelineItemKind::TokenCount => Style::default().fg(theme::MUTED),
+        TimelineItemKind::Todo => Style::default().fg(theme::SUCCESS),
+        TimelineItemKind::Compaction => Style::default()
+            .fg(theme::ERROR)
+            .add_modifier(Modifier::BOLD),
         TimelineItemKind::Note => Style::default().fg(theme::DIM),
     }
 }
@@ -2913,19 +4501,7 @@ fn build_timeline_render_columns(
     let session_start_ms =
         parse_rfc3339_to_unix_ms(session_start_rfc3339).or_else(|| earliest_timestamp_ms(items));
 
-    let mut tool_out_by_call_id: HashMap<String, i64> = HashMap::new();
-    for item in items {
-        if item.kind != TimelineItemKind::ToolOutput {
-            continue;
-        }
-        let Some(call_id) = item.call_id.as_deref() else {
-            continue;
-        };
-        let Some(ts) = item.timestamp_ms else {
-            continue;
-        };
-        tool_out_by_call_id.entry(call_id.to_string()).or_insert(ts);
-    }
+    let tool_durations = crate::domain::tool_call_durations(items);
 
     let mut rows = Vec::with_capacity(items.len());
     let mut offset_col_width = 0usize;
@@ -2941,21 +4517,18 @@ fn build_timeline_render_columns(
         };
 
         let duration_ms = if item.kind == TimelineItemKind::ToolCall {
-            match (item.call_id.as_deref(), item.timestamp_ms) {
-                (Some(call_id), Some(call_ts)) => tool_out_by_call_id
-                    .get(call_id)
-                    .and_then(|out_ts| out_ts.checked_sub(call_ts)),
-                _ => None,
-            }
+            item.call_id
+                .as_deref()
+                .and_then(|call_id| tool_durations.get(call_id).copied())
         } else {
             match (item.timestamp_ms, prev_ts_ms) {
-                (Some(ts), Some(prev)) => ts.checked_sub(prev),
+                (Some(ts), Some(prev)) => ts.checked_sub(prev).map(|ms| ms as u64),
                 _ => None,
             }
         };
 
         let duration = match duration_ms {
-            Some(ms) => format_duration(Duration::from_millis(ms as u64)),
+            Some(ms) => format_duration(Duration::from_millis(ms)),
             None => "-".to_string(),
         };
 
@@ -3212,44 +4785,111 @@ fn build_item_detail_text(detail_view: &crate::app::SessionDetailView) -> Text<'
     }
     text.lines.push(Line::from(""));
 
+    if let Some(plan) = crate::domain::parse_plan_timeline_item(item) {
+        text.lines.push(Line::from(Span::styled(
+            format!("Plan ({}):", plan.progress_label()),
+            Style::default()
+                .fg(theme::ACCENT)
+                .add_modifier(Modifier::BOLD),
+        )));
+        if let Some(explanation) = plan.explanation.as_deref() {
+            text.lines.push(Line::from(Span::styled(
+                explanation.to_string(),
+                Style::default().fg(theme::DIM),
+            )));
+        }
+        text.lines.extend(plan_checklist_lines(&plan));
+        text.lines.push(Line::from(""));
+    }
+
     let max = 12_000;
 
-    if item.kind == TimelineItemKind::ToolCall {
-        if let Some(call_id) = item.call_id.as_deref() {
-            if let Some(tool_out) = find_tool_output_for_call(&detail_view.items, selected, call_id)
-            {
-                text.lines.push(Line::from(Span::styled(
-                    "Output:",
-                    Style::default()
-                        .fg(theme::ACCENT)
-                        .add_modifier(Modifier::BOLD),
-                )));
-                text.lines.extend(render_plain_highlight_lines(
-                    truncate_chars(&tool_out.detail, max).as_str(),
-                ));
-                text.lines.push(Line::from(""));
-                text.lines.push(Line::from(Span::styled(
-                    "Input:",
-                    Style::default()
-                        .fg(theme::ACCENT)
-                        .add_modifier(Modifier::BOLD),
-                )));
-                text.lines.extend(render_plain_highlight_lines(
-                    truncate_chars(&item.detail, max).as_str(),
-                ));
-                add_detail_bottom_padding(&mut text);
-                return text;
-            }
-        }
+    let output_index = detail_view.selected_output_index();
+    let is_expanded = output_index.is_some_and(|index| detail_view.is_output_expanded(index));
+
+    if item.kind == TimelineItemKind::ToolCall
+        && let Some(tool_out) = output_index.and_then(|index| detail_view.items.get(index))
+    {
+        text.lines.push(Line::from(Span::styled(
+            "Output:",
+            Style::default()
+                .fg(theme::ACCENT)
+                .add_modifier(Modifier::BOLD),
+        )));
+        text.lines.extend(collapse_output_lines(
+            render_plain_highlight_lines(truncate_chars(&tool_out.detail, max).as_str()),
+            is_expanded,
+        ));
+        text.lines.push(Line::from(""));
+        text.lines.push(Line::from(Span::styled(
+            "Input:",
+            Style::default()
+                .fg(theme::ACCENT)
+                .add_modifier(Modifier::BOLD),
+        )));
+        text.lines.extend(render_plain_highlight_lines(
+            truncate_chars(&item.detail, max).as_str(),
+        ));
+        add_detail_bottom_padding(&mut text);
+        return text;
     }
 
     let truncated = truncate_chars(&item.detail, max);
-    text.lines
-        .extend(render_detail_lines_for_kind(item.kind, &truncated));
+    let lines = render_detail_lines_for_kind(item.kind, &truncated);
+    if item.kind == TimelineItemKind::ToolOutput {
+        text.lines.extend(collapse_output_lines(lines, is_expanded));
+    } else {
+        text.lines.extend(lines);
+    }
     add_detail_bottom_padding(&mut text);
     text
 }
 
+/// Long tool outputs show their first lines until expanded with `e`.
+fn collapse_output_lines(mut lines: Vec<Line<'static>>, is_expanded: bool) -> Vec<Line<'static>> {
+    const COLLAPSED_OUTPUT_LINES: usize = 8;
+    if lines.len() <= COLLAPSED_OUTPUT_LINES {
+        return lines;
+    }
+    let hint = if is_expanded {
+        "e collapses".to_string()
+    } else {
+        let hidden = lines.len() - COLLAPSED_OUTPUT_LINES;
+        lines.truncate(COLLAPSED_OUTPUT_LINES);
+        let suffix = if hidden == 1 { "" } else { "s" };
+        format!("… {hidden} more line{suffix} · e expands")
+    };
+    lines.push(Line::from(Span::styled(
+        hint,
+        Style::default().fg(theme::DIM),
+    )));
+    lines
+}
+
+#[cfg(test)]
+mod collapse_output_lines_tests {
+    use super::*;
+
+    fn numbered_lines(count: usize) -> Vec<Line<'static>> {
+        (0..count)
+            .map(|index| Line::from(index.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn keeps_first_lines_until_expanded() {
+        let collapsed = collapse_output_lines(numbered_lines(20), false);
+        assert_eq!(collapsed.len(), 9);
+        assert_eq!(collapsed[8].to_string(), "… 12 more lines · e expands");
+
+        let expanded = collapse_output_lines(numbered_lines(20), true);
+        assert_eq!(expanded.len(), 21);
+        assert_eq!(expanded[20].to_string(), "e collapses");
+
+        assert_eq!(collapse_output_lines(numbered_lines(8), false).len(), 8);
+    }
+}
+
 fn add_detail_bottom_padding(text: &mut Text<'static>) {
     const PAD_LINES: usize = 2;
     for _ in 0..PAD_LINES {
@@ -3269,11 +4909,13 @@ fn render_detail_lines_for_kind(kind: TimelineItemKind, text: &str) -> Vec<Line<
         TimelineItemKind::Assistant
         | TimelineItemKind::User
         | TimelineItemKind::Thinking
+        | TimelineItemKind::Compaction
         | TimelineItemKind::Note => render_markdownish_lines(text),
         TimelineItemKind::Turn
         | TimelineItemKind::ToolCall
         | TimelineItemKind::ToolOutput
         | TimelineItemKind::TokenCount => render_plain_highlight_lines(text),
+        TimelineItemKind::Todo => plan_checklist_lines(&crate::domain::parse_todo_checklist(text)),
     }
 }
 
@@ -3813,24 +5455,6 @@ fn markdownish_inline_spans(text: &str, base_style: Style) -> Vec<Span<'static>>
     spans
 }
 
-fn find_tool_output_for_call<'a>(
-    items: &'a [TimelineItem],
-    selected_index: usize,
-    call_id: &str,
-) -> Option<&'a TimelineItem> {
-    if selected_index + 1 < items.len() {
-        if let Some(hit) = items.iter().skip(selected_index + 1).find(|item| {
-            item.kind == TimelineItemKind::ToolOutput && item.call_id.as_deref() == Some(call_id)
-        }) {
-            return Some(hit);
-        }
-    }
-
-    items.iter().find(|item| {
-        item.kind == TimelineItemKind::ToolOutput && item.call_id.as_deref() == Some(call_id)
-    })
-}
-
 fn render_context_overlay(
     frame: &mut Frame,
     area: Rect,
@@ -4091,9 +5715,17 @@ fn render_session_result_preview_overlay(
 
     let title_budget = (popup.width as usize).saturating_sub(4);
     let session_title = truncate_end(&preview.session_title, title_budget.saturating_sub(60));
-    let title = format!(
-        "Result (last Out) · {session_title} · Esc/Enter/Space=close · arrows/PgUp/PgDn=scroll"
-    );
+    let title = if preview.cycle.len() > 1 {
+        format!(
+            "Result (last Out) · {session_title} · {}/{} · Space=next · Esc/Enter=close · arrows/PgUp/PgDn=scroll",
+            preview.cycle_index + 1,
+            preview.cycle.len()
+        )
+    } else {
+        format!(
+            "Result (last Out) · {session_title} · Esc/Enter/Space=close · arrows/PgUp/PgDn=scroll"
+        )
+    };
     let title = truncate_end(&title, title_budget);
 
     let paragraph = Paragraph::new(text)
@@ -4108,6 +5740,9 @@ fn render_session_result_preview_overlay(
     frame.render_widget(paragraph, popup);
 }
 
+/// Skills listed in the project stats overlay; the CLI prints them all.
+const PROJECT_STATS_MAX_SKILLS: usize = 10;
+
 fn render_project_stats_overlay(
     frame: &mut Frame,
     area: Rect,
@@ -4225,24 +5860,662 @@ fn render_project_stats_overlay(
     }
 
     lines.push(Line::from(""));
-    lines.push(Line::from(vec![Span::styled("Cache", section_style)]));
+    lines.push(Line::from(vec![Span::styled("Activity", section_style)]));
     lines.push(Line::from(vec![
         Span::raw("  "),
-        Span::styled("Index: ", label_style),
-        Span::styled("~/.ccbox/session_index.json", dim_style),
+        Span::styled("Tool calls:      ", label_style),
+        Span::styled(format_commas_u64(overlay.tool_calls), value_style),
+    ]));
+    lines.push(Line::from(vec![
+        Span::raw("  "),
+        Span::styled("Assistant turns: ", label_style),
+        Span::styled(format_commas_u64(overlay.assistant_turns), value_style),
+    ]));
+    lines.push(Line::from(vec![
+        Span::raw("  "),
+        Span::styled("Active time:     ", label_style),
+        Span::styled(
+            format_duration(Duration::from_millis(overlay.active_ms)),
+            value_style,
+        ),
+    ]));
+    let last_activity = overlay
+        .last_activity_unix_ms
+        .and_then(|ms| u64::try_from(ms).ok())
+        .map(|ms| SystemTime::UNIX_EPOCH + Duration::from_millis(ms));
+    lines.push(Line::from(vec![
+        Span::raw("  "),
+        Span::styled("Last activity:   ", label_style),
+        Span::styled(
+            last_activity.map_or_else(|| "-".to_string(), |time| relative_time_ago(Some(time))),
+            value_style,
+        ),
     ]));
 
-    let paragraph = Paragraph::new(lines)
-        .wrap(Wrap { trim: false })
-        .scroll((overlay.scroll, 0));
-    frame.render_widget(paragraph, chunks[0]);
-
-    let hint = Paragraph::new("Keys: arrows/PgUp/PgDn=scroll  Esc/Backspace=close")
-        .style(Style::default().fg(theme::DIM))
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![Span::styled("Skills", section_style)]));
+    match overlay.skills.as_ref() {
+        None => {
+            lines.push(Line::from(vec![
+                Span::raw("  "),
+                Span::styled("Press s to scan every session for skill spans.", dim_style),
+            ]));
+        }
+        Some(report) if report.skills.is_empty() => {
+            lines.push(Line::from(vec![
+                Span::raw("  "),
+                Span::styled(
+                    format!(
+                        "No skill spans in {} sessions.",
+                        format_commas_usize(report.sessions_scanned)
+                    ),
+                    dim_style,
+                ),
+            ]));
+        }
+        Some(report) => {
+            lines.push(Line::from(vec![
+                Span::raw("  "),
+                Span::styled("Sessions with skills: ", label_style),
+                Span::styled(
+                    format_commas_usize(report.sessions_with_skills),
+                    value_style.add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(" / ", dim_style),
+                Span::styled(format_commas_usize(report.sessions_scanned), dim_style),
+            ]));
+            for usage in report.skills.iter().take(PROJECT_STATS_MAX_SKILLS) {
+                let loops = if usage.loops > 0 {
+                    format!(" · {} loops", usage.loops)
+                } else {
+                    String::new()
+                };
+                lines.push(Line::from(vec![
+                    Span::raw("  "),
+                    Span::styled(
+                        format!("{:<24} ", truncate_end(&usage.name, 24)),
+                        value_style,
+                    ),
+                    Span::styled(format!("{:>5}x", usage.invocations), token_style),
+                    Span::styled(
+                        format!(
+                            "  {} sessions · {} tools · {}{loops}",
+                            usage.sessions,
+                            usage.tool_calls,
+                            format_duration_ms(Some(usage.duration_ms))
+                        ),
+                        dim_style,
+                    ),
+                ]));
+            }
+            let hidden = report.skills.len().saturating_sub(PROJECT_STATS_MAX_SKILLS);
+            if hidden > 0 {
+                lines.push(Line::from(vec![
+                    Span::raw("  "),
+                    Span::styled(
+                        format!("… {hidden} more (ccbox skills --project)"),
+                        dim_style,
+                    ),
+                ]));
+            }
+        }
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![Span::styled("Cache", section_style)]));
+    lines.push(Line::from(vec![
+        Span::raw("  "),
+        Span::styled("Index: ", label_style),
+        Span::styled("~/.ccbox/session_index.json", dim_style),
+    ]));
+
+    let paragraph = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .scroll((overlay.scroll, 0));
+    frame.render_widget(paragraph, chunks[0]);
+
+    let hint = if overlay.skills.is_none() {
+        "Keys: arrows/PgUp/PgDn=scroll  s=skills  Esc/Backspace=close"
+    } else {
+        "Keys: arrows/PgUp/PgDn=scroll  Esc/Backspace=close"
+    };
+    let hint = Paragraph::new(hint)
+        .style(Style::default().fg(theme::DIM))
+        .alignment(Alignment::Center);
+    frame.render_widget(hint, chunks[1]);
+}
+
+fn render_devices_overlay(frame: &mut Frame, area: Rect, overlay: &crate::app::DevicesOverlay) {
+    if let Some(pairing) = &overlay.pairing {
+        render_pairing_panel(frame, area, pairing);
+        return;
+    }
+
+    let popup = centered_rect(76, 60, area);
+    frame.render_widget(Clear, popup);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme::BORDER))
+        .padding(Padding::horizontal(1))
+        .title(if overlay.loading {
+            "Devices · loading…".to_string()
+        } else {
+            format!("Devices · {} paired", overlay.devices.len())
+        })
+        .style(Style::default().bg(theme::SURFACE).fg(theme::FG));
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(inner);
+
+    let dim_style = Style::default().fg(theme::DIM);
+    if overlay.devices.is_empty() {
+        let empty = Paragraph::new(if overlay.loading {
+            "Loading devices from the relay…"
+        } else {
+            "No paired devices. Press n to pair one through the relay, or run `ccbox serve` and enter its pairing code on the client."
+        })
+        .style(dim_style)
+        .wrap(Wrap { trim: true });
+        frame.render_widget(empty, chunks[0]);
+    } else {
+        let name_width = (chunks[0].width as usize).saturating_sub(44).max(8);
+        let items = overlay
+            .devices
+            .iter()
+            .map(|device| {
+                let (status, status_style) = if device.revoked {
+                    ("revoked", Style::default().fg(theme::ERROR))
+                } else {
+                    ("trusted", Style::default().fg(theme::SUCCESS))
+                };
+                let last_seen = device.last_seen_at.as_deref().unwrap_or("never");
+                ListItem::new(Line::from(vec![
+                    Span::raw(format!(
+                        "{:<name_width$}",
+                        truncate_end(device.display_name(), name_width)
+                    )),
+                    Span::raw("  "),
+                    Span::styled(format!("{status:<7}"), status_style),
+                    Span::raw("  "),
+                    Span::styled(format!("last seen {last_seen}"), dim_style),
+                ]))
+            })
+            .collect::<Vec<_>>();
+        let list = List::new(items).highlight_style(
+            Style::default()
+                .bg(theme::ACCENT)
+                .fg(theme::BG)
+                .add_modifier(Modifier::BOLD),
+        );
+        let mut state = ListState::default();
+        state.select(Some(overlay.selected));
+        frame.render_stateful_widget(list, chunks[0], &mut state);
+    }
+
+    let hint =
+        Paragraph::new("Keys: arrows=select  r=revoke/restore  p=re-pair  n=pair new  Esc=close")
+            .style(dim_style)
+            .alignment(Alignment::Center);
+    frame.render_widget(hint, chunks[1]);
+}
+
+fn render_pairing_panel(frame: &mut Frame, area: Rect, pairing: &crate::app::PairingState) {
+    let popup = centered_rect(80, 90, area);
+    frame.render_widget(Clear, popup);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme::BORDER))
+        .padding(Padding::horizontal(1))
+        .title("Pair device")
+        .style(Style::default().bg(theme::SURFACE).fg(theme::FG));
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(inner);
+
+    let dim_style = Style::default().fg(theme::DIM);
+    let lines = match pairing {
+        crate::app::PairingState::Requesting => vec![Line::from(Span::styled(
+            "Registering with the relay and requesting a pairing code…",
+            dim_style,
+        ))],
+        crate::app::PairingState::Failed(error) => vec![Line::from(Span::styled(
+            format!("Pairing failed: {error}"),
+            Style::default().fg(theme::ERROR),
+        ))],
+        crate::app::PairingState::Ready(offer) => {
+            let mut lines = vec![
+                Line::from(vec![
+                    Span::raw("Code: "),
+                    Span::styled(
+                        offer.pairing_code.clone(),
+                        Style::default()
+                            .fg(theme::ACCENT)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(format!("  expires {}", offer.expires_at), dim_style),
+                ]),
+                Line::from(Span::styled(offer.web_url.clone(), dim_style)),
+                Line::raw(""),
+            ];
+            lines.extend(offer.qr_rows.iter().map(|row| Line::raw(row.clone())));
+            lines
+        }
+    };
+    let body = Paragraph::new(lines).alignment(Alignment::Center);
+    frame.render_widget(body, chunks[0]);
+
+    let hint = Paragraph::new("Scan the QR code or open the link, then enter the code · Esc=back")
+        .style(dim_style)
+        .alignment(Alignment::Center);
+    frame.render_widget(hint, chunks[1]);
+}
+
+fn render_audit_log_overlay(frame: &mut Frame, area: Rect, overlay: &crate::app::AuditLogOverlay) {
+    let popup = centered_rect(86, 78, area);
+    frame.render_widget(Clear, popup);
+
+    let title = format!(
+        "Audit Log · {} entries · newest first",
+        overlay.entries.len()
+    );
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme::BORDER))
+        .padding(Padding::horizontal(1))
+        .title(title)
+        .style(Style::default().bg(theme::SURFACE).fg(theme::FG));
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(inner);
+
+    let max_line_width = (chunks[0].width as usize).saturating_sub(1);
+    let dim_style = Style::default().fg(theme::DIM);
+    let label_style = Style::default().fg(theme::MUTED);
+    let path_style = Style::default().fg(theme::ACCENT);
+
+    let mut lines: Vec<Line<'static>> = Vec::new();
+    if overlay.entries.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No actions recorded yet.",
+            dim_style,
+        )));
+    }
+    for entry in &overlay.entries {
+        let action_style = match entry.action {
+            crate::domain::AuditAction::Delete | crate::domain::AuditAction::Kill => {
+                Style::default()
+                    .fg(theme::ERROR)
+                    .add_modifier(Modifier::BOLD)
+            }
+            _ => Style::default()
+                .fg(theme::INFO)
+                .add_modifier(Modifier::BOLD),
+        };
+        let who = match entry.device_id.as_deref() {
+            Some(device_id) => format!("{} via relay ({device_id})", entry.actor),
+            None => format!("{} via {}", entry.actor, entry.source.label()),
+        };
+        lines.push(Line::from(vec![
+            Span::styled(entry.at_rfc3339.clone(), dim_style),
+            Span::raw("  "),
+            Span::styled(format!("{:<6}", entry.action.label()), action_style),
+            Span::raw("  "),
+            Span::styled(who, label_style),
+        ]));
+
+        let target = truncate_middle(&entry.target, max_line_width.saturating_sub(2));
+        lines.push(Line::from(vec![
+            Span::raw("  "),
+            Span::styled(target, path_style),
+        ]));
+        if !entry.detail.is_empty() {
+            let detail = truncate_end(&entry.detail, max_line_width.saturating_sub(2));
+            lines.push(Line::from(vec![
+                Span::raw("  "),
+                Span::styled(detail, label_style),
+            ]));
+        }
+    }
+
+    let paragraph = Paragraph::new(lines).scroll((overlay.scroll, 0));
+    frame.render_widget(paragraph, chunks[0]);
+
+    let hint = Paragraph::new("Keys: arrows/PgUp/PgDn=scroll  Esc/Backspace=close")
+        .style(Style::default().fg(theme::DIM))
+        .alignment(Alignment::Center);
+    frame.render_widget(hint, chunks[1]);
+}
+
+fn render_recent_sessions_overlay(
+    frame: &mut Frame,
+    area: Rect,
+    model: &AppModel,
+    overlay: &crate::app::RecentSessionsOverlay,
+) {
+    let popup = centered_rect(80, 60, area);
+    frame.render_widget(Clear, popup);
+
+    let recent = model.session_history.recent();
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme::BORDER))
+        .padding(Padding::horizontal(1))
+        .title(format!("Recent Sessions · {}", recent.len()))
+        .style(Style::default().bg(theme::SURFACE).fg(theme::FG));
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(inner);
+
+    let max_width = (chunks[0].width as usize).saturating_sub(2);
+    let items = recent
+        .iter()
+        .map(|entry| {
+            let session = &entry.session;
+            let project = entry
+                .from_sessions
+                .project_path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_else(|| entry.from_sessions.project_path.display().to_string());
+            let started = format!("  {}  ", session.meta.started_at_rfc3339);
+            let title_width = max_width
+                .saturating_sub(3)
+                .saturating_sub(started.chars().count())
+                .saturating_sub(project.chars().count());
+            ListItem::new(Line::from(vec![
+                engine_badge_span(session.engine),
+                Span::raw(truncate_end(&session.title, title_width)),
+                Span::styled(started, Style::default().fg(theme::DIM)),
+                Span::styled(project, Style::default().fg(theme::MUTED)),
+            ]))
+        })
+        .collect::<Vec<_>>();
+    let list = List::new(items)
+        .highlight_style(
+            Style::default()
+                .bg(theme::ACCENT_BG)
+                .fg(theme::FG)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("▸ ");
+    let mut state = ListState::default();
+    state.select(Some(overlay.selected.min(recent.len().saturating_sub(1))));
+    frame.render_stateful_widget(list, chunks[0], &mut state);
+
+    let hint = Paragraph::new("Keys: arrows=move  Enter=open  Esc/F7=close")
+        .style(Style::default().fg(theme::DIM))
+        .alignment(Alignment::Center);
+    frame.render_widget(hint, chunks[1]);
+}
+
+fn render_quick_switcher_overlay(
+    frame: &mut Frame,
+    area: Rect,
+    model: &AppModel,
+    overlay: &crate::app::QuickSwitcherOverlay,
+) {
+    let popup = centered_rect(80, 60, area);
+    frame.render_widget(Clear, popup);
+
+    let items = crate::app::quick_switch_items(model, overlay);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme::BORDER))
+        .padding(Padding::horizontal(1))
+        .title("Go to · projects, sessions, tasks, processes")
+        .style(Style::default().bg(theme::SURFACE).fg(theme::FG));
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+    let query = if overlay.query.is_empty() {
+        Line::from(Span::styled(
+            "Type to fuzzy-match…",
+            Style::default().fg(theme::DIM),
+        ))
+    } else {
+        Line::from(vec![
+            Span::styled("> ", Style::default().fg(theme::ACCENT)),
+            Span::raw(overlay.query.clone()),
+        ])
+    };
+    frame.render_widget(Paragraph::new(query), chunks[0]);
+
+    if items.is_empty() {
+        frame.render_widget(
+            Paragraph::new("No matches.").style(Style::default().fg(theme::DIM)),
+            chunks[1],
+        );
+    } else {
+        let max_width = (chunks[1].width as usize).saturating_sub(2);
+        let rows = items
+            .iter()
+            .map(|item| {
+                let kind = format!("{:<8} ", item.target.kind_label());
+                let label_width = max_width
+                    .saturating_sub(kind.chars().count())
+                    .saturating_mul(3)
+                    / 5;
+                let label = truncate_end(&item.label, label_width);
+                let detail_width = max_width
+                    .saturating_sub(kind.chars().count())
+                    .saturating_sub(label.chars().count())
+                    .saturating_sub(2);
+                ListItem::new(Line::from(vec![
+                    Span::styled(kind, Style::default().fg(theme::DIM)),
+                    Span::raw(label),
+                    Span::raw("  "),
+                    Span::styled(
+                        truncate_end(&item.detail, detail_width),
+                        Style::default().fg(theme::MUTED),
+                    ),
+                ]))
+            })
+            .collect::<Vec<_>>();
+        let list = List::new(rows)
+            .highlight_style(
+                Style::default()
+                    .bg(theme::ACCENT_BG)
+                    .fg(theme::FG)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .highlight_symbol("▸ ");
+        let mut state = ListState::default();
+        state.select(Some(overlay.selected.min(items.len().saturating_sub(1))));
+        frame.render_stateful_widget(list, chunks[1], &mut state);
+    }
+
+    let hint = Paragraph::new("Keys: type=filter  arrows=move  Enter=go  Esc/F9=close")
+        .style(Style::default().fg(theme::DIM))
+        .alignment(Alignment::Center);
+    frame.render_widget(hint, chunks[2]);
+}
+
+fn render_session_diff_overlay(
+    frame: &mut Frame,
+    area: Rect,
+    overlay: &crate::app::SessionDiffOverlay,
+) {
+    use crate::domain::SessionDiffStatus;
+
+    let popup = centered_rect(90, 82, area);
+    frame.render_widget(Clear, popup);
+
+    let diff = &overlay.diff;
+    let title = format!(
+        "Compare · {} same · {} changed · {} only a · {} only b",
+        diff.count(SessionDiffStatus::Same),
+        diff.count(SessionDiffStatus::Changed),
+        diff.count(SessionDiffStatus::OnlyA),
+        diff.count(SessionDiffStatus::OnlyB)
+    );
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme::BORDER))
+        .padding(Padding::horizontal(1))
+        .title(title)
+        .style(Style::default().bg(theme::SURFACE).fg(theme::FG));
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(inner);
+
+    let max_line_width = (chunks[0].width as usize).saturating_sub(1);
+    let a_style = Style::default().fg(theme::ERROR);
+    let b_style = Style::default().fg(theme::SUCCESS);
+    let same_style = Style::default().fg(theme::MUTED);
+    let label_style = Style::default().fg(theme::DIM);
+
+    let diff_line = |marker: &'static str, label: &str, text: &str, style: Style| {
+        let label = format!("{label:<5} ");
+        let first = text.lines().next().unwrap_or("");
+        let width = max_line_width.saturating_sub(2 + label.len());
+        Line::from(vec![
+            Span::styled(marker, style),
+            Span::styled(label, label_style),
+            Span::styled(truncate_end(first, width), style),
+        ])
+    };
+
+    let mut lines: Vec<Line<'static>> = vec![
+        Line::from(vec![
+            Span::styled("a ", a_style),
+            Span::styled(
+                truncate_end(&overlay.title_a, max_line_width.saturating_sub(2)),
+                a_style,
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("b ", b_style),
+            Span::styled(
+                truncate_end(&overlay.title_b, max_line_width.saturating_sub(2)),
+                b_style,
+            ),
+        ]),
+        Line::from(""),
+    ];
+    for step in &diff.steps {
+        let label = kind_label(step.kind);
+        if step.status == SessionDiffStatus::Same {
+            let text = step.a.as_deref().unwrap_or("");
+            lines.push(diff_line("  ", label, text, same_style));
+            continue;
+        }
+        if let Some(text) = step.a.as_deref() {
+            lines.push(diff_line("- ", label, text, a_style));
+        }
+        if let Some(text) = step.b.as_deref() {
+            lines.push(diff_line("+ ", label, text, b_style));
+        }
+    }
+
+    lines.push(Line::from(""));
+    let no_output = "(no assistant output)";
+    if diff.is_same_final_output() {
+        let text = diff.final_a.as_deref().unwrap_or(no_output);
+        lines.push(diff_line("  ", "Final", text, same_style));
+    } else {
+        let text_a = diff.final_a.as_deref().unwrap_or(no_output);
+        let text_b = diff.final_b.as_deref().unwrap_or(no_output);
+        lines.push(diff_line("- ", "Final", text_a, a_style));
+        lines.push(diff_line("+ ", "Final", text_b, b_style));
+    }
+
+    let paragraph = Paragraph::new(lines).scroll((overlay.scroll, 0));
+    frame.render_widget(paragraph, chunks[0]);
+
+    let hint = Paragraph::new("Keys: arrows/PgUp/PgDn=scroll  Esc/Backspace=close")
+        .style(Style::default().fg(theme::DIM))
         .alignment(Alignment::Center);
     frame.render_widget(hint, chunks[1]);
 }
 
+fn render_opencode_write_back_overlay(
+    frame: &mut Frame,
+    area: Rect,
+    confirm: &crate::app::OpenCodeWriteBackConfirm,
+) {
+    let popup = centered_rect(72, 30, area);
+    frame.render_widget(Clear, popup);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme::BORDER))
+        .padding(Padding::horizontal(1))
+        .title(Title::from(Span::styled(
+            "Update OpenCode",
+            Style::default()
+                .fg(theme::ACCENT)
+                .add_modifier(Modifier::BOLD),
+        )))
+        .style(Style::default().bg(theme::SURFACE).fg(theme::FG));
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    let max_line_width = (inner.width as usize).saturating_sub(1);
+    let label_style = Style::default().fg(theme::MUTED);
+    let (label, value) = match &confirm.change {
+        crate::app::OpenCodeWriteBack::Title(title) => ("Title: ", title.clone()),
+        crate::app::OpenCodeWriteBack::Project(path) => ("Project: ", path.display().to_string()),
+    };
+    let value_budget = max_line_width.saturating_sub(UnicodeWidthStr::width(label));
+    let session_budget = max_line_width.saturating_sub(UnicodeWidthStr::width("Session: "));
+
+    let lines = vec![
+        Line::from("Saved in ccbox. Also write this change to the OpenCode DB?"),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("Session: ", label_style),
+            Span::styled(
+                truncate_end(&confirm.session.meta.id, session_budget),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled(label, label_style),
+            Span::styled(
+                truncate_middle(&value, value_budget),
+                Style::default().fg(theme::ACCENT),
+            ),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(
+            "OpenCode will show the change too. Keys: y=write  n/Enter/Esc=skip",
+            Style::default().fg(theme::DIM),
+        )),
+    ];
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), inner);
+}
+
 fn render_session_rename_overlay(
     frame: &mut Frame,
     area: Rect,
@@ -4288,59 +6561,149 @@ fn render_session_rename_overlay(
 
     let path_prefix = "Project: ";
     let path_budget = max_line_width.saturating_sub(UnicodeWidthStr::width(path_prefix));
-    let cwd_value = truncate_middle(&dialog.session.meta.cwd.display().to_string(), path_budget);
-
-    let info = Paragraph::new(vec![
-        Line::from(vec![
-            Span::styled(id_prefix, label_style),
-            Span::styled(id_value, value_style.add_modifier(Modifier::BOLD)),
-        ]),
-        Line::from(vec![
-            Span::styled(path_prefix, label_style),
-            Span::styled(cwd_value, path_style),
-        ]),
-    ])
+    let cwd_value = truncate_middle(&dialog.session.meta.cwd.display().to_string(), path_budget);
+
+    let info = Paragraph::new(vec![
+        Line::from(vec![
+            Span::styled(id_prefix, label_style),
+            Span::styled(id_value, value_style.add_modifier(Modifier::BOLD)),
+        ]),
+        Line::from(vec![
+            Span::styled(path_prefix, label_style),
+            Span::styled(cwd_value, path_style),
+        ]),
+    ])
+    .style(Style::default().bg(theme::SURFACE).fg(theme::FG));
+    frame.render_widget(info, chunks[0]);
+
+    let placeholder = "Type a title (empty clears rename)...";
+    let input_text = if dialog.editor.text.is_empty() {
+        Text::from(Line::from(Span::styled(
+            placeholder,
+            Style::default().fg(theme::DIM),
+        )))
+    } else {
+        Text::from(dialog.editor.text.as_str())
+    };
+
+    let input = Paragraph::new(input_text).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme::BORDER))
+            .padding(Padding::horizontal(1))
+            .title("Title")
+            .style(Style::default().bg(theme::SURFACE).fg(theme::FG)),
+    );
+    frame.render_widget(input, chunks[1]);
+
+    let hint = Paragraph::new("Keys: Enter=save  Esc=cancel  Backspace/Delete=edit")
+        .style(dim_style)
+        .alignment(Alignment::Center);
+    frame.render_widget(hint, chunks[2]);
+
+    let input_inner = Block::default()
+        .borders(Borders::ALL)
+        .padding(Padding::horizontal(1))
+        .title("Title")
+        .inner(chunks[1]);
+    if input_inner.width > 0 {
+        let mut x_offset = 0u16;
+        for (idx, ch) in dialog.editor.text.chars().enumerate() {
+            if idx >= dialog.editor.cursor_col {
+                break;
+            }
+            x_offset = x_offset.saturating_add(UnicodeWidthChar::width(ch).unwrap_or(0) as u16);
+        }
+        let x = input_inner.x.saturating_add(x_offset).min(
+            input_inner
+                .x
+                .saturating_add(input_inner.width.saturating_sub(1)),
+        );
+        frame.set_cursor_position(Position {
+            x,
+            y: input_inner.y,
+        });
+    }
+}
+
+fn render_project_rename_overlay(
+    frame: &mut Frame,
+    area: Rect,
+    dialog: &crate::app::ProjectRenameDialog,
+) {
+    let popup = centered_rect(76, 26, area);
+    frame.render_widget(Clear, popup);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme::BORDER))
+        .padding(Padding::horizontal(1))
+        .title(Title::from(Span::styled(
+            "Rename Project",
+            Style::default()
+                .fg(theme::ACCENT)
+                .add_modifier(Modifier::BOLD),
+        )))
+        .style(Style::default().bg(theme::SURFACE).fg(theme::FG));
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2),
+            Constraint::Length(3),
+            Constraint::Length(1),
+            Constraint::Min(0),
+        ])
+        .split(inner);
+
+    let max_line_width = (chunks[0].width as usize).saturating_sub(1);
+    let path_prefix = "Project: ";
+    let path_budget = max_line_width.saturating_sub(UnicodeWidthStr::width(path_prefix));
+    let path_value = truncate_middle(&dialog.project_path.display().to_string(), path_budget);
+    let info = Paragraph::new(Line::from(vec![
+        Span::styled(path_prefix, Style::default().fg(theme::MUTED)),
+        Span::styled(path_value, Style::default().fg(theme::ACCENT)),
+    ]))
     .style(Style::default().bg(theme::SURFACE).fg(theme::FG));
     frame.render_widget(info, chunks[0]);
 
-    let placeholder = "Type a title (empty clears rename)...";
     let input_text = if dialog.editor.text.is_empty() {
         Text::from(Line::from(Span::styled(
-            placeholder,
+            "Type a name (empty restores the folder name)...",
             Style::default().fg(theme::DIM),
         )))
     } else {
         Text::from(dialog.editor.text.as_str())
     };
-
     let input = Paragraph::new(input_text).block(
         Block::default()
             .borders(Borders::ALL)
             .border_style(Style::default().fg(theme::BORDER))
             .padding(Padding::horizontal(1))
-            .title("Title")
+            .title("Name")
             .style(Style::default().bg(theme::SURFACE).fg(theme::FG)),
     );
     frame.render_widget(input, chunks[1]);
 
     let hint = Paragraph::new("Keys: Enter=save  Esc=cancel  Backspace/Delete=edit")
-        .style(dim_style)
+        .style(Style::default().fg(theme::DIM))
         .alignment(Alignment::Center);
     frame.render_widget(hint, chunks[2]);
 
     let input_inner = Block::default()
         .borders(Borders::ALL)
         .padding(Padding::horizontal(1))
-        .title("Title")
         .inner(chunks[1]);
     if input_inner.width > 0 {
-        let mut x_offset = 0u16;
-        for (idx, ch) in dialog.editor.text.chars().enumerate() {
-            if idx >= dialog.editor.cursor_col {
-                break;
-            }
-            x_offset = x_offset.saturating_add(UnicodeWidthChar::width(ch).unwrap_or(0) as u16);
-        }
+        let x_offset = dialog
+            .editor
+            .text
+            .chars()
+            .take(dialog.editor.cursor_col)
+            .map(|ch| UnicodeWidthChar::width(ch).unwrap_or(0) as u16)
+            .fold(0u16, u16::saturating_add);
         let x = input_inner.x.saturating_add(x_offset).min(
             input_inner
                 .x
@@ -4358,7 +6721,7 @@ fn render_session_move_overlay(
     area: Rect,
     dialog: &crate::app::SessionMoveDialog,
 ) {
-    let popup = centered_rect(76, 26, area);
+    let popup = centered_rect(76, 48, area);
     frame.render_widget(Clear, popup);
 
     let block = Block::default()
@@ -4417,13 +6780,13 @@ fn render_session_move_overlay(
     frame.render_widget(info, chunks[0]);
 
     let placeholder = "Type a project path (empty clears override)...";
-    let input_text = if dialog.editor.text.is_empty() {
+    let input_text = if dialog.picker.editor.text.is_empty() {
         Text::from(Line::from(Span::styled(
             placeholder,
             Style::default().fg(theme::DIM),
         )))
     } else {
-        Text::from(dialog.editor.text.as_str())
+        Text::from(dialog.picker.editor.text.as_str())
     };
 
     let input = Paragraph::new(input_text).block(
@@ -4436,10 +6799,11 @@ fn render_session_move_overlay(
     );
     frame.render_widget(input, chunks[1]);
 
-    let hint = Paragraph::new("Keys: Enter=move  Esc=cancel  Backspace/Delete=edit")
+    let hint = Paragraph::new("Keys: Enter=move  Tab=complete  Up/Down=suggestions  Esc=cancel")
         .style(dim_style)
         .alignment(Alignment::Center);
     frame.render_widget(hint, chunks[2]);
+    render_path_picker_suggestions(frame, chunks[3], &dialog.picker);
 
     let input_inner = Block::default()
         .borders(Borders::ALL)
@@ -4448,8 +6812,8 @@ fn render_session_move_overlay(
         .inner(chunks[1]);
     if input_inner.width > 0 {
         let mut x_offset = 0u16;
-        for (idx, ch) in dialog.editor.text.chars().enumerate() {
-            if idx >= dialog.editor.cursor_col {
+        for (idx, ch) in dialog.picker.editor.text.chars().enumerate() {
+            if idx >= dialog.picker.editor.cursor_col {
                 break;
             }
             x_offset = x_offset.saturating_add(UnicodeWidthChar::width(ch).unwrap_or(0) as u16);
@@ -4466,6 +6830,42 @@ fn render_session_move_overlay(
     }
 }
 
+/// Validation error, then recent projects and directory completions; the highlighted row is
+/// what Tab/Enter takes.
+fn render_path_picker_suggestions(frame: &mut Frame, area: Rect, picker: &crate::app::PathPicker) {
+    let width = (area.width as usize).saturating_sub(2);
+    let mut lines = Vec::new();
+    if let Some(error) = &picker.error {
+        lines.push(Line::from(Span::styled(
+            truncate_end(error, width),
+            Style::default().fg(theme::ERROR),
+        )));
+    }
+    for (index, suggestion) in picker.suggestions().iter().enumerate() {
+        let style = if picker.selected == Some(index) {
+            Style::default().fg(theme::ACCENT).bg(theme::ACCENT_BG)
+        } else {
+            Style::default().fg(theme::MUTED)
+        };
+        lines.push(Line::from(Span::styled(
+            format!("  {}", truncate_middle(suggestion, width)),
+            style,
+        )));
+    }
+    let list = Paragraph::new(lines).style(Style::default().bg(theme::SURFACE).fg(theme::FG));
+    frame.render_widget(list, area);
+}
+
+/// Shrinks a running total to at most `width` points, keeping the last value of each bucket.
+fn sample_cumulative(values: &[u64], width: usize) -> Vec<u64> {
+    if values.len() <= width {
+        return values.to_vec();
+    }
+    (1..=width)
+        .map(|bucket| values[bucket * values.len() / width - 1])
+        .collect()
+}
+
 fn render_session_stats_overlay(
     frame: &mut Frame,
     area: Rect,
@@ -4591,6 +6991,27 @@ fn render_session_stats_overlay(
         Span::styled(project_value, path_style),
     ]));
 
+    if let Some(git) = overlay.session.meta.git.as_ref() {
+        let git_prefix = "Git: ";
+        let git_budget = max_line_width.saturating_sub(UnicodeWidthStr::width(git_prefix));
+        let git_value = [
+            git.branch
+                .as_deref()
+                .map(|branch| format!("branch {branch}")),
+            git.commit
+                .as_deref()
+                .map(|commit| format!("commit {commit}")),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" · ");
+        lines.push(Line::from(vec![
+            Span::styled(git_prefix, label_style),
+            Span::styled(truncate_end(&git_value, git_budget), value_style),
+        ]));
+    }
+
     let started_prefix = "Started: ";
     let started_budget = max_line_width.saturating_sub(UnicodeWidthStr::width(started_prefix));
     let started_value = truncate_end(&overlay.session.meta.started_at_rfc3339, started_budget);
@@ -4657,6 +7078,104 @@ fn render_session_stats_overlay(
         Span::styled("Last:  ", label_style),
         token_last_span,
     ]));
+    if overlay.stats.cumulative_tokens.len() > 1 {
+        let turns = overlay.stats.cumulative_tokens.len();
+        let prefix = "  Growth: ";
+        let suffix = format!(" over {} turns", format_commas_usize(turns));
+        let width = max_line_width
+            .saturating_sub(UnicodeWidthStr::width(prefix))
+            .saturating_sub(UnicodeWidthStr::width(suffix.as_str()))
+            .max(1);
+        lines.push(Line::from(vec![
+            Span::styled(prefix, label_style),
+            Span::styled(
+                crate::domain::sparkline(&sample_cumulative(
+                    &overlay.stats.cumulative_tokens,
+                    width,
+                )),
+                token_style,
+            ),
+            Span::styled(suffix, dim_style),
+        ]));
+        if let Some((turn, added)) =
+            crate::domain::most_expensive_turn(&overlay.stats.cumulative_tokens)
+        {
+            lines.push(Line::from(vec![
+                Span::styled("  Costliest turn: ", label_style),
+                Span::styled(format!("#{turn}"), value_style.add_modifier(Modifier::BOLD)),
+                Span::styled(
+                    format!(" (+{} tokens)", format_commas_u64(added)),
+                    dim_style,
+                ),
+            ]));
+        }
+    }
+    if !overlay.stats.tokens_by_model.is_empty() {
+        let model_width = overlay
+            .stats
+            .tokens_by_model
+            .iter()
+            .map(|usage| UnicodeWidthStr::width(usage.model.as_str()))
+            .max()
+            .unwrap_or(0)
+            .clamp(5, 28);
+        lines.push(Line::from(vec![Span::styled(
+            format!(
+                "  {:<model_width$} {:>12} {:>12} {:>12} {:>12} {:>12}",
+                "Model", "Input", "Cached", "Output", "Reasoning", "Total"
+            ),
+            label_style,
+        )]));
+        for usage in &overlay.stats.tokens_by_model {
+            let category = |value: u64| {
+                if usage.has_categories() {
+                    format_commas_u64(value)
+                } else {
+                    "-".to_string()
+                }
+            };
+            lines.push(Line::from(vec![
+                Span::raw("  "),
+                Span::styled(
+                    format!("{:<model_width$} ", truncate_end(&usage.model, model_width)),
+                    value_style,
+                ),
+                Span::styled(
+                    format!(
+                        "{:>12} {:>12} {:>12} {:>12} ",
+                        category(usage.input_tokens),
+                        category(usage.cached_input_tokens),
+                        category(usage.output_tokens),
+                        category(usage.reasoning_tokens)
+                    ),
+                    dim_style,
+                ),
+                Span::styled(
+                    format!("{:>12}", format_commas_u64(usage.total_tokens)),
+                    token_style,
+                ),
+            ]));
+        }
+    }
+    if overlay.stats.compactions > 0 {
+        let count = overlay.stats.compactions;
+        let times = if count == 1 { "once" } else { "times" };
+        let count_label = if count == 1 {
+            String::new()
+        } else {
+            format!("{} ", format_commas_usize(count))
+        };
+        lines.push(Line::from(vec![
+            Span::raw("  "),
+            Span::styled(
+                format!("Context was compacted {count_label}{times}"),
+                Style::default()
+                    .fg(theme::ERROR)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(" (earlier turns were replaced by a summary)", dim_style),
+        ]));
+    }
     lines.push(Line::from(""));
 
     lines.push(Line::from(vec![Span::styled("Tools", section_style)]));
@@ -4716,6 +7235,28 @@ fn render_session_stats_overlay(
     }
     lines.push(Line::from(""));
 
+    if !overlay.stats.slowest_tools.is_empty() {
+        lines.push(Line::from(vec![Span::styled(
+            "Slowest tools",
+            section_style,
+        )]));
+        for call in &overlay.stats.slowest_tools {
+            let duration = format_duration(Duration::from_millis(call.duration_ms));
+            let prefix = "  - ";
+            let suffix = format!(": {duration}");
+            let budget = max_line_width
+                .saturating_sub(UnicodeWidthStr::width(prefix))
+                .saturating_sub(UnicodeWidthStr::width(suffix.as_str()));
+            lines.push(Line::from(vec![
+                Span::styled(prefix, dim_style),
+                Span::styled(truncate_end(&call.name, budget), path_style),
+                Span::styled(": ", dim_style),
+                Span::styled(duration, value_style.add_modifier(Modifier::BOLD)),
+            ]));
+        }
+        lines.push(Line::from(""));
+    }
+
     lines.push(Line::from(vec![Span::styled(
         "Changes (apply_patch)",
         section_style,
@@ -4775,6 +7316,26 @@ fn render_session_stats_overlay(
         }
     }
 
+    if let Some(todos) = overlay.stats.final_todos.as_ref() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::styled("Final todo state", section_style),
+            Span::styled(format!("  {}", todos.progress_label()), dim_style),
+        ]));
+        if todos.steps.is_empty() {
+            lines.push(Line::from(vec![
+                Span::raw("  "),
+                Span::styled("(cleared)", dim_style),
+            ]));
+        } else {
+            for line in plan_checklist_lines(todos) {
+                let mut spans = vec![Span::raw("  ")];
+                spans.extend(line.spans);
+                lines.push(Line::from(spans));
+            }
+        }
+    }
+
     let paragraph = Paragraph::new(lines)
         .wrap(Wrap { trim: false })
         .scroll((overlay.scroll, 0));
@@ -4888,35 +7449,108 @@ fn render_help_overlay(frame: &mut Frame, area: Rect) {
         Line::from(""),
         Line::from("Global"),
         Line::from("  - Ctrl+R: rescan sessions"),
+        Line::from("  - Ctrl+G: reload config (also automatic when the file changes)"),
         Line::from("  - Ctrl+4/Cmd+4: open Tasks"),
+        Line::from("  - Ctrl+5/Cmd+5: All Sessions (every project, newest first)"),
+        Line::from(
+            "  - Ctrl+6/Cmd+6: Activity (running processes and recently written sessions; +/- window)",
+        ),
         Line::from("  - Ctrl+T/Cmd+T: New Task"),
         Line::from("  - F2: system menu"),
         Line::from("  - P: processes"),
+        Line::from("  - Ctrl+L/Cmd+L: audit log (deletes, renames, forks, kills, spawns)"),
+        Line::from(
+            "  - Ctrl+B/Cmd+B: paired relay devices (r=revoke/restore, p=re-pair, n=pair new)",
+        ),
+        Line::from("  - Alt+Left/Alt+Right: back/forward through opened sessions"),
+        Line::from("  - F7: recently viewed sessions"),
+        Line::from("  - F9: quick switcher (fuzzy-match projects, sessions, tasks, processes)"),
+        Line::from(
+            "  - F12: attach to the first process waiting for a tool approval (listed in the menu bar)",
+        ),
         Line::from("  - Auto-rescan: watches sessions dir"),
         Line::from("  - Ctrl+Q or Ctrl+C: quit"),
         Line::from(""),
         Line::from("View-specific"),
         Line::from("  - Projects: type to filter, Esc clears filter"),
         Line::from("  - Projects: Del deletes project logs"),
-        Line::from("  - Projects: Space shows Result (newest session Out)"),
-        Line::from("  - Projects: F3 shows Statistics"),
+        Line::from(
+            "  - Projects: Tab-select several projects, then Ctrl+N/Cmd+N sends one prompt to each (a batch in Processes)",
+        ),
+        Line::from(
+            "  - Projects: Space shows Result (newest session Out); Space again cycles recent sessions",
+        ),
+        Line::from("  - Projects: F3 shows Statistics (s adds skill usage across sessions)"),
+        Line::from("  - Projects: F4 cycles the sort (recent, sessions, size, tokens)"),
+        Line::from(
+            "  - Projects: Ctrl+E/Cmd+E renames, Ctrl+X/Cmd+X hides/unhides, F6 shows hidden",
+        ),
+        Line::from("  - Projects/Sessions: Ctrl+W opens the project in ide_command (config)"),
         Line::from("  - Sessions: type to filter, Esc clears filter"),
+        Line::from(
+            "  - Sessions: branch:NAME in the filter keeps sessions on a matching git branch",
+        ),
         Line::from("  - Sessions: Ctrl+X/Cmd+X toggles Tool errors only"),
+        Line::from(
+            "  - Sessions: Ctrl+F/Cmd+F also matches the filter against prompts and outputs",
+        ),
         Line::from("  - Sessions: Ctrl+O/Cmd+O toggles order by Tool errors"),
+        Line::from(
+            "  - Sessions: F4 cycles the sort (newest, started, size, tokens, duration, title)",
+        ),
         Line::from("  - Sessions: Del deletes session log (Backspace edits filter)"),
         Line::from("  - Sessions: Space shows Result (last Out)"),
         Line::from("  - Sessions: Ctrl+N/Cmd+N opens New Session"),
         Line::from("  - Sessions: Ctrl+E/Cmd+E renames session title"),
         Line::from("  - Sessions: Ctrl+P/Cmd+P moves session to another project"),
+        Line::from(
+            "  - Sessions: for OpenCode, renames and moves then offer to update the OpenCode DB (y)",
+        ),
         Line::from("  - Sessions: F3 shows Stats"),
+        Line::from(
+            "  - Sessions: F6 shows item count, duration and token columns plus a token sparkline (orange/red when unusually large)",
+        ),
+        Line::from("  - Sessions: Tab-select two sessions, then Ctrl+K/Cmd+K compares them"),
+        Line::from(
+            "  - Sessions: Claude subagent sessions (↳) are listed under their parent; Ctrl+U/Cmd+U jumps to it",
+        ),
         Line::from(
             "  - New Session: Ctrl+Enter/Cmd+Enter sends, Shift+Tab switches engine, F4 switches I/O mode",
         ),
+        Line::from(
+            "  - New Session: F5 toggles auto-retry (re-spawns quick non-zero exits, up to 3 attempts)",
+        ),
+        Line::from(
+            "  - New Session: Ctrl+O/Cmd+O inserts a text file (e.g. task.md) at the cursor",
+        ),
+        Line::from(
+            "  - New Session: Ctrl+P/Cmd+P picks the directory the agent runs in (default: project root)",
+        ),
+        Line::from(
+            "  - New Session: @ at a word start attaches a project file; mentioned @paths are fenced onto the prompt on send",
+        ),
+        Line::from(
+            "  - New Session: Ctrl+I inserts image, Ctrl+V pastes image (Codex also gets them as --image)",
+        ),
+        Line::from(
+            "  - New Session / Tasks / Task Detail: F10 cycles the model (default, then the engine's \"models\" from config.json)",
+        ),
+        Line::from(
+            "  - New Session / Task Detail: F6 also runs the prompt on the other engine; both results open in the session diff when they finish",
+        ),
         Line::from("  - Tasks: type to filter, Enter opens, Ctrl+Enter spawns"),
         Line::from("  - Tasks: n creates, Del deletes, Shift+Tab switches engine"),
+        Line::from(
+            "  - New Session / Tasks: F8 spawns in a new git worktree (ccbox/<name> branch); `ccbox worktrees` merges or removes it",
+        ),
+        Line::from("  - Tasks: Ctrl+S schedules a spawn (`at 02:00`, `in 2h`); ⏰ marks pending"),
+        Line::from("  - Tasks: TEAM rows are read-only tasks synced from the task registry"),
         Line::from(
             "  - New Task: Ctrl+S saves, Ctrl+I inserts image, Ctrl+V pastes image, Ctrl+P edits project path",
         ),
+        Line::from(
+            "  - Project path pickers: Tab completes directories, Up/Down picks known projects, Enter needs an existing directory",
+        ),
         Line::from("  - Task Detail: Ctrl+Enter spawns, Shift+Tab switches engine, Del deletes"),
         Line::from("  - Projects: CX/CL/GM/OC indicates engine (matches filter or newest)"),
         Line::from("  - Sessions: CX/CL/GM/OC indicates engine"),
@@ -4928,11 +7562,67 @@ fn render_help_overlay(frame: &mut Frame, area: Rect) {
         Line::from("  - Session Detail: F3 shows Stats"),
         Line::from("  - Session Detail: Enter jumps to ToolOut for Tool calls"),
         Line::from(
-            "  - Session Detail: f forks/resumes from selected Turn/User/Out/ToolOut record (Codex)",
+            "  - Session Detail: huge sessions open at their newest items; Up/PgUp at the top loads older ones",
+        ),
+        Line::from(
+            "  - Session Detail: y copies the selected item, Y the last output, Ctrl+Y/Cmd+Y the transcript",
+        ),
+        Line::from(
+            "  - Session Detail: v opens the raw log in $VISUAL/$EDITOR (ccbox resumes when it exits)",
+        ),
+        Line::from("  - Session Detail: [ / ] jump to the previous/next user message"),
+        Line::from("  - Session Detail: { / } jump to the previous/next assistant message"),
+        Line::from(
+            "  - Session Detail: e expands/collapses a long tool output (first 8 lines shown)",
+        ),
+        Line::from(
+            "  - Session Detail: f forks/resumes from selected Turn/User/Out/ToolOut record (Codex), or resumes the whole session (OpenCode)",
         ),
         Line::from("  - Session Detail: c toggles Visible Context"),
         Line::from("  - Session Detail: S toggles Skills summary"),
-        Line::from("  - Processes: a=attach (TTY), s/e/l=open output, k=kill, Enter=open session"),
+        Line::from(
+            "  - Session Detail: Plan sidebar shows the latest TodoWrite/update_plan checklist (✓ done, ▸ active)",
+        ),
+        Line::from(
+            "  - Processes: a=attach (TTY), s/e/l=open output, k/K=stop/kill, Enter=open session",
+        ),
+        Line::from(
+            "  - Processes: r types one line plus Enter into a running TTY process without attaching",
+        ),
+        Line::from(
+            "  - Processes: k stops (SIGTERM, SIGKILL after kill_grace_secs), K force-kills right away",
+        ),
+        Line::from(
+            "  - Process output: ANSI colors are rendered; r shows the raw text with escapes visible",
+        ),
+        Line::from(
+            "  - Process output: / searches, n/N jump between matching lines, f (or End) follows the tail",
+        ),
+        Line::from(
+            "  - Processes / output: w saves the whole output to a file, y copies it to the clipboard",
+        ),
+        Line::from(
+            "  - Processes: t opens the task a process was spawned from; Task Detail lists its spawns",
+        ),
+        Line::from(
+            "  - Process output: Enter opens the agent's session once it reports one (open_spawned_sessions jumps there)",
+        ),
+        Line::from(
+            "  - Processes: g=label (task spawns use the task title); rows group by label, Space/Enter collapses, k on a header kills the group",
+        ),
+        Line::from(
+            "  - Processes: Ctrl+K/Cmd+K kills every running process of the selected project (after a confirmation)",
+        ),
+        Line::from(
+            "  - Processes: `attempt 2/3` marks a retried spawn; k also cancels a pending retry",
+        ),
+        Line::from(
+            "  - Processes: ! marks an agent waiting on you (question, plan approval or a TTY prompt)",
+        ),
+        Line::from("  - Processes: running rows show live CPU % and RSS (sampled every second)"),
+        Line::from(
+            "  - Processes: ◌ ext rows are agents started outside ccbox (Linux); Enter opens their session",
+        ),
         Line::from(""),
         Line::from("Help"),
         Line::from("  - F1 or ?: toggle this help"),
@@ -5513,6 +8203,94 @@ fn render_delete_task_confirm_overlay(
     frame.render_widget(hint, chunks[2]);
 }
 
+fn render_kill_project_confirm_overlay(
+    frame: &mut Frame,
+    area: Rect,
+    confirm: &crate::app::KillProjectConfirmDialog,
+) {
+    let popup = centered_rect(72, 36, area);
+    frame.render_widget(Clear, popup);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme::BORDER))
+        .padding(Padding::horizontal(1))
+        .title(Title::from(Span::styled(
+            "Kill Project Processes",
+            Style::default()
+                .fg(theme::ERROR)
+                .add_modifier(Modifier::BOLD),
+        )))
+        .style(Style::default().bg(theme::SURFACE).fg(theme::FG));
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),
+            Constraint::Length(3),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+    let max_line_width = (chunks[0].width as usize).saturating_sub(1);
+    let project_path = confirm.project_path.display().to_string();
+    let project_path = truncate_middle(&project_path, max_line_width);
+    let process_ids = truncate_end(&confirm.process_ids.join(", "), max_line_width);
+
+    let message = vec![
+        Line::from(vec![
+            Span::raw("Kill "),
+            Span::styled(
+                format!("{} running process(es)", confirm.process_ids.len()),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("?"),
+        ]),
+        Line::from(""),
+        Line::from(format!("Project path: {project_path}")),
+        Line::from(process_ids),
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            "Agents are stopped mid-turn; pending retries are cancelled.",
+            Style::default()
+                .fg(theme::ACCENT)
+                .add_modifier(Modifier::BOLD),
+        )]),
+    ];
+    let paragraph = Paragraph::new(message).wrap(Wrap { trim: false });
+    frame.render_widget(paragraph, chunks[0]);
+
+    let cancel_style = if confirm.selection == DeleteConfirmSelection::Cancel {
+        Style::default()
+            .add_modifier(Modifier::REVERSED)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default()
+    };
+    let kill_base = Style::default().fg(theme::ERROR);
+    let kill_style = if confirm.selection == DeleteConfirmSelection::Delete {
+        kill_base
+            .add_modifier(Modifier::REVERSED)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        kill_base.add_modifier(Modifier::BOLD)
+    };
+    let buttons = Paragraph::new(Line::from(vec![
+        Span::styled("[ Cancel ]", cancel_style),
+        Span::raw("   "),
+        Span::styled("[ Kill ]", kill_style),
+    ]))
+    .alignment(Alignment::Center);
+    frame.render_widget(buttons, chunks[1]);
+
+    let hint = Paragraph::new("Keys: ←/→ choose  Enter confirm  Esc/Backspace cancel  y/n")
+        .style(Style::default().fg(theme::DIM))
+        .alignment(Alignment::Center);
+    frame.render_widget(hint, chunks[2]);
+}
+
 fn render_delete_tasks_confirm_overlay(
     frame: &mut Frame,
     area: Rect,